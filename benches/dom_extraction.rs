@@ -0,0 +1,175 @@
+//! DOM extraction benchmarks
+//!
+//! Measures, for each page in `benches/fixtures`:
+//!
+//! - `extract_dom`: running the extraction script in Chrome and parsing its result
+//! - `to_json`: serializing the extracted tree into the simplified JSON form
+//! - `snapshot`: rendering the extracted tree as the YAML snapshot
+//!
+//! These need a Chrome install and are skipped without one. The `synthetic`
+//! group renders and serializes generated trees of fixed sizes and always runs.
+//!
+//! ```bash
+//! cargo bench --bench dom_extraction
+//! BENCH_JSON=bench.json cargo bench --bench dom_extraction  # summary for CI
+//! ```
+//!
+//! After the run, mean, median and standard deviation of every benchmark (in
+//! nanoseconds) are written as JSON to `$BENCH_JSON`, or to
+//! `target/criterion/summary.json` by default.
+
+use browser_use::dom::{AriaChild, AriaNode, DomTree};
+use browser_use::tools::snapshot::{RenderMode, render_aria_tree};
+use browser_use::{BrowserSession, LaunchOptions};
+use criterion::{BenchmarkId, Criterion};
+use serde_json::{Value, json};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+const FIXTURES: &[&str] = &["article", "form", "product_grid"];
+
+fn fixture_url(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("benches/fixtures")
+        .join(format!("{}.html", name));
+    format!("file://{}", path.display())
+}
+
+/// A page of `rows` list items, each a heading, a link, some text and a button
+fn synthetic_tree(rows: usize) -> DomTree {
+    let mut list = AriaNode::new("list", "");
+    for row in 0..rows {
+        let item = AriaNode::new("listitem", "")
+            .with_child(AriaChild::Node(Box::new(
+                AriaNode::new("heading", format!("Item {}", row)).with_level(3),
+            )))
+            .with_child(AriaChild::Node(Box::new(
+                AriaNode::new("link", format!("Details for item {}", row))
+                    .with_index(row * 2)
+                    .with_prop("url", format!("/items/{}", row))
+                    .with_box(true, Some("pointer".to_string())),
+            )))
+            .with_child(AriaChild::Text(format!("${}.99", 10 + row % 90)))
+            .with_child(AriaChild::Node(Box::new(
+                AriaNode::new("button", "Add to cart")
+                    .with_index(row * 2 + 1)
+                    .with_box(true, Some("pointer".to_string())),
+            )));
+        list = list.with_child(AriaChild::Node(Box::new(item)));
+    }
+    DomTree::new(AriaNode::fragment().with_child(AriaChild::Node(Box::new(list))))
+}
+
+fn bench_synthetic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("synthetic");
+    for rows in [10, 100, 1000] {
+        let tree = synthetic_tree(rows);
+        group.bench_with_input(BenchmarkId::new("snapshot", rows), &tree, |b, tree| {
+            b.iter(|| render_aria_tree(black_box(&tree.root), RenderMode::Ai, None))
+        });
+        group.bench_with_input(BenchmarkId::new("to_json", rows), &tree, |b, tree| {
+            b.iter(|| black_box(tree).to_json().unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_fixtures(c: &mut Criterion) {
+    let session = match BrowserSession::launch(LaunchOptions::new().headless(true)) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!(
+                "Skipping fixture benchmarks, Chrome failed to launch: {}",
+                e
+            );
+            return;
+        }
+    };
+
+    let mut group = c.benchmark_group("fixtures");
+    for name in FIXTURES {
+        session
+            .navigate(&fixture_url(name))
+            .and_then(|_| session.wait_for_navigation())
+            .unwrap_or_else(|e| panic!("Failed to load fixture {}: {}", name, e));
+
+        group.bench_function(BenchmarkId::new("extract_dom", name), |b| {
+            b.iter(|| session.extract_dom().unwrap())
+        });
+
+        let tree = session.extract_dom().unwrap();
+        group.bench_function(BenchmarkId::new("to_json", name), |b| {
+            b.iter(|| black_box(&tree).to_json().unwrap())
+        });
+        group.bench_function(BenchmarkId::new("snapshot", name), |b| {
+            b.iter(|| render_aria_tree(black_box(&tree.root), RenderMode::Ai, None))
+        });
+    }
+    group.finish();
+}
+
+/// Criterion's output directory, as criterion itself resolves it
+fn criterion_dir() -> PathBuf {
+    if let Some(home) = std::env::var_os("CRITERION_HOME") {
+        return PathBuf::from(home);
+    }
+    let target = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("target"));
+    target.join("criterion")
+}
+
+/// Collect `<group>/<function>/<input>/new/estimates.json` files below `dir`
+fn collect_estimates(dir: &Path, id: &str, results: &mut Vec<Value>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+    entries.sort();
+    for path in entries {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if name == "new" {
+            let estimates = std::fs::read_to_string(path.join("estimates.json"))
+                .ok()
+                .and_then(|text| serde_json::from_str::<Value>(&text).ok());
+            if let Some(estimates) = estimates {
+                results.push(json!({
+                    "id": id,
+                    "mean_ns": estimates["mean"]["point_estimate"],
+                    "median_ns": estimates["median"]["point_estimate"],
+                    "std_dev_ns": estimates["std_dev"]["point_estimate"],
+                }));
+            }
+        } else if path.is_dir() && !matches!(name, "base" | "change" | "report") {
+            collect_estimates(&path, &format!("{}/{}", id, name), results);
+        }
+    }
+}
+
+fn write_summary(dir: &Path) {
+    let mut results = Vec::new();
+    for group in ["synthetic", "fixtures"] {
+        collect_estimates(&dir.join(group), group, &mut results);
+    }
+    let path = std::env::var_os("BENCH_JSON")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| dir.join("summary.json"));
+    let summary = json!({ "benchmarks": results });
+    match std::fs::write(&path, serde_json::to_string_pretty(&summary).unwrap()) {
+        Ok(()) => println!("Wrote benchmark summary to {}", path.display()),
+        Err(e) => eprintln!("Failed to write {}: {}", path.display(), e),
+    }
+}
+
+fn main() {
+    let dir = criterion_dir();
+    let mut criterion = Criterion::default()
+        .output_directory(&dir)
+        .configure_from_args();
+    bench_synthetic(&mut criterion);
+    bench_fixtures(&mut criterion);
+    criterion.final_summary();
+    write_summary(&dir);
+}
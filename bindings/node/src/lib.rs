@@ -0,0 +1,245 @@
+//! Node.js bindings for browser-use
+//!
+//! Exposes a [`Browser`] class that launches or connects to Chrome, runs the
+//! registered tools and extracts the DOM, without a separate MCP process:
+//!
+//! ```js
+//! const { Browser } = require('browser-use-rs');
+//!
+//! const browser = await Browser.launch({ headless: true });
+//! await browser.execute('navigate', { url: 'https://example.com' });
+//! const dom = await browser.extractDom();
+//! await browser.execute('click', { index: 0 });
+//! browser.close();
+//! ```
+//!
+//! Every call that talks to the browser runs on the libuv thread pool and
+//! returns a promise; calls on one `Browser` are serialized. Errors are
+//! rejected with messages prefixed by the stable error code, e.g.
+//! `[ELEMENT_NOT_FOUND] Element not found: #submit`.
+
+use browser_use::{BrowserError, BrowserSession, ConnectionOptions, LaunchOptions};
+use napi::bindgen_prelude::*;
+use napi::{Env, JsUnknown, Task};
+use napi_derive::napi;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Options for [`Browser::launch`]
+#[napi(object)]
+#[derive(Default)]
+pub struct LaunchConfig {
+    /// Run without a visible window (default: true)
+    pub headless: Option<bool>,
+    /// Path to the Chrome or Chromium executable
+    pub executable_path: Option<String>,
+    /// Persistent profile directory
+    pub user_data_dir: Option<String>,
+    /// Window width in pixels
+    pub window_width: Option<u32>,
+    /// Window height in pixels
+    pub window_height: Option<u32>,
+    /// Accept invalid TLS certificates
+    pub ignore_https_errors: Option<bool>,
+    /// Directory screenshots, downloads and other files written by tools are confined to
+    pub output_dir: Option<String>,
+}
+
+impl From<LaunchConfig> for LaunchOptions {
+    fn from(config: LaunchConfig) -> Self {
+        let defaults = LaunchOptions::default();
+        LaunchOptions {
+            headless: config.headless.unwrap_or(defaults.headless),
+            chrome_path: config.executable_path.map(PathBuf::from),
+            user_data_dir: config.user_data_dir.map(PathBuf::from),
+            window_width: config.window_width.unwrap_or(defaults.window_width),
+            window_height: config.window_height.unwrap_or(defaults.window_height),
+            ignore_https_errors: config
+                .ignore_https_errors
+                .unwrap_or(defaults.ignore_https_errors),
+            output_dir: config.output_dir.map(PathBuf::from),
+            ..defaults
+        }
+    }
+}
+
+/// Convert a library error into a JS error carrying its code
+fn to_js_error(error: BrowserError) -> Error {
+    Error::new(
+        Status::GenericFailure,
+        format!("[{}] {}", error.code(), error),
+    )
+}
+
+/// Session shared between a `Browser` object and its pending tasks
+type SharedSession = Arc<Mutex<Option<BrowserSession>>>;
+
+fn lock(session: &SharedSession) -> MutexGuard<'_, Option<BrowserSession>> {
+    session.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Run `f` on the open session, or fail if the browser was closed
+fn with_session<T>(
+    session: &SharedSession,
+    f: impl FnOnce(&BrowserSession) -> browser_use::Result<T>,
+) -> Result<T> {
+    match lock(session).as_ref() {
+        Some(session) => f(session).map_err(to_js_error),
+        None => Err(Error::new(
+            Status::GenericFailure,
+            "Browser is closed".to_string(),
+        )),
+    }
+}
+
+/// How a [`StartTask`] gets its browser
+pub enum Start {
+    Launch(Box<LaunchOptions>),
+    Connect(ConnectionOptions),
+}
+
+/// Launches or connects to a browser off the main thread
+pub struct StartTask {
+    start: Option<Start>,
+}
+
+impl Task for StartTask {
+    type Output = BrowserSession;
+    type JsValue = Browser;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let session = match self.start.take() {
+            Some(Start::Launch(options)) => BrowserSession::launch(*options),
+            Some(Start::Connect(options)) => BrowserSession::connect(options),
+            None => return Err(Error::new(Status::GenericFailure, "Already started")),
+        };
+        session.map_err(to_js_error)
+    }
+
+    fn resolve(&mut self, _env: Env, session: Self::Output) -> Result<Self::JsValue> {
+        Ok(Browser {
+            session: Arc::new(Mutex::new(Some(session))),
+        })
+    }
+}
+
+/// Runs one tool call off the main thread
+pub struct ExecuteTask {
+    session: SharedSession,
+    name: String,
+    params: Value,
+}
+
+impl Task for ExecuteTask {
+    type Output = Value;
+    type JsValue = JsUnknown;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let params = std::mem::take(&mut self.params);
+        let result = with_session(&self.session, |session| {
+            session.execute_tool(&self.name, params)
+        })?;
+        serde_json::to_value(result).map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        env.to_js_value(&output)
+    }
+}
+
+/// Extracts the active tab's DOM off the main thread
+pub struct ExtractDomTask {
+    session: SharedSession,
+}
+
+impl Task for ExtractDomTask {
+    type Output = Value;
+    type JsValue = JsUnknown;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let tree = with_session(&self.session, BrowserSession::extract_dom)?;
+        serde_json::to_value(tree).map_err(|e| Error::from_reason(e.to_string()))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        env.to_js_value(&output)
+    }
+}
+
+/// A browser session driven from JavaScript
+#[napi]
+pub struct Browser {
+    session: SharedSession,
+}
+
+#[napi]
+impl Browser {
+    /// Launch a new browser
+    #[napi(ts_return_type = "Promise<Browser>")]
+    pub fn launch(options: Option<LaunchConfig>) -> AsyncTask<StartTask> {
+        AsyncTask::new(StartTask {
+            start: Some(Start::Launch(Box::new(options.unwrap_or_default().into()))),
+        })
+    }
+
+    /// Connect to a running browser by its DevTools WebSocket URL
+    #[napi(ts_return_type = "Promise<Browser>")]
+    pub fn connect(ws_url: String) -> AsyncTask<StartTask> {
+        AsyncTask::new(StartTask {
+            start: Some(Start::Connect(ConnectionOptions::new(ws_url))),
+        })
+    }
+
+    /// Run a tool by name, e.g. `execute("click", { index: 3 })`
+    ///
+    /// Resolves to the tool result: `{ success, data?, error?, metadata }`.
+    #[napi(ts_return_type = "Promise<any>")]
+    pub fn execute(&self, name: String, params: Option<Value>) -> AsyncTask<ExecuteTask> {
+        AsyncTask::new(ExecuteTask {
+            session: Arc::clone(&self.session),
+            name,
+            params: params.unwrap_or_else(|| Value::Object(Default::default())),
+        })
+    }
+
+    /// Extract the active tab's DOM with indexed interactive elements
+    ///
+    /// Resolves to `{ root, selectors, iframe_indices }`.
+    #[napi(ts_return_type = "Promise<any>")]
+    pub fn extract_dom(&self) -> AsyncTask<ExtractDomTask> {
+        AsyncTask::new(ExtractDomTask {
+            session: Arc::clone(&self.session),
+        })
+    }
+
+    /// Names of the tools `execute` accepts
+    #[napi]
+    pub fn tool_names(&self) -> Result<Vec<String>> {
+        with_session(&self.session, |session| {
+            let mut names = session.tool_registry().list_names();
+            names.sort();
+            Ok(names)
+        })
+    }
+
+    /// JSON schema of a tool's parameters
+    #[napi]
+    pub fn tool_schema(&self, name: String) -> Result<Option<Value>> {
+        with_session(&self.session, |session| {
+            Ok(session
+                .tool_registry()
+                .get(&name)
+                .map(|tool| tool.parameters_schema()))
+        })
+    }
+
+    /// Close the browser; later calls fail
+    #[napi]
+    pub fn close(&self) -> Result<()> {
+        match lock(&self.session).take() {
+            Some(session) => session.close().map_err(to_js_error),
+            None => Ok(()),
+        }
+    }
+}
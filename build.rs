@@ -0,0 +1,60 @@
+fn main() {
+    #[cfg(feature = "grpc-server")]
+    generate_grpc_service();
+}
+
+/// Generate the server side of `proto/browser_use.proto`'s service
+///
+/// The messages are hand-written in `src/grpc/proto.rs`, so only the service
+/// is generated and no protoc is needed.
+#[cfg(feature = "grpc-server")]
+fn generate_grpc_service() {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let method = |name: &str, route: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route)
+            .input_type(format!("crate::grpc::proto::{}", input))
+            .output_type(format!("crate::grpc::proto::{}", output))
+            .codec_path("tonic_prost::ProstCodec")
+    };
+
+    let service = Service::builder()
+        .name("BrowserUse")
+        .package("browser_use.v1")
+        .method(
+            method(
+                "execute_tool",
+                "ExecuteTool",
+                "ExecuteToolRequest",
+                "ExecuteToolResponse",
+            )
+            .build(),
+        )
+        .method(method("get_dom", "GetDom", "GetDomRequest", "GetDomResponse").build())
+        .method(
+            method(
+                "stream_events",
+                "StreamEvents",
+                "StreamEventsRequest",
+                "PageEvent",
+            )
+            .server_streaming()
+            .build(),
+        )
+        .method(
+            method(
+                "list_tools",
+                "ListTools",
+                "ListToolsRequest",
+                "ListToolsResponse",
+            )
+            .build(),
+        )
+        .build();
+
+    Builder::new().build_client(false).compile(&[service]);
+}
@@ -0,0 +1,95 @@
+/* browser-use C API. Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef BROWSER_USE_H
+#define BROWSER_USE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of an FFI call
+typedef enum BuStatus {
+  // The call succeeded
+  BU_STATUS_OK = 0,
+  // A required pointer argument was null
+  BU_STATUS_NULL_ARGUMENT = 1,
+  // A string argument was not valid UTF-8
+  BU_STATUS_INVALID_UTF8 = 2,
+  // A JSON argument could not be parsed
+  BU_STATUS_INVALID_JSON = 3,
+  // The browser or tool failed; see the error's `code`
+  BU_STATUS_BROWSER_ERROR = 4,
+  // The library panicked; the session should be freed
+  BU_STATUS_PANIC = 5,
+} BuStatus;
+
+// Opaque browser session handle
+typedef struct BuSession BuSession;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Launch a browser and store its session handle in `*out`
+//
+// `options_json` may be null or an object with `headless`, `executable_path`,
+// `user_data_dir`, `window_width`, `window_height`, `ignore_https_errors` and
+// `output_dir`.
+//
+// # Safety
+// `options_json` must be null or a NUL-terminated string; `out` must be valid for writes.
+enum BuStatus bu_session_launch(const char *options_json, struct BuSession **out);
+
+// Connect to a running browser by its DevTools WebSocket URL
+//
+// # Safety
+// `ws_url` must be a NUL-terminated string; `out` must be valid for writes.
+enum BuStatus bu_session_connect(const char *ws_url, struct BuSession **out);
+
+// Run a tool and store its result JSON (`{"success", "data", "error", "metadata"}`) in `*out_json`
+//
+// A tool that reports failure still returns [`BuStatus::Ok`] with
+// `"success": false`; errors raised by the tool return [`BuStatus::BrowserError`].
+//
+// # Safety
+// `session` must be a live handle, `tool` a NUL-terminated string,
+// `params_json` null or a NUL-terminated string, and `out_json` valid for writes.
+enum BuStatus bu_session_execute(struct BuSession *session,
+                                 const char *tool,
+                                 const char *params_json,
+                                 char **out_json);
+
+// Extract the active tab's DOM and store it as JSON (`{"root", "selectors", "iframe_indices"}`) in `*out_json`
+//
+// # Safety
+// `session` must be a live handle and `out_json` valid for writes.
+enum BuStatus bu_session_extract_dom(struct BuSession *session,
+                                     char **out_json);
+
+// Close the browser and free the session handle; null is ignored
+//
+// # Safety
+// `session` must be null or a live handle, which is invalid afterwards.
+void bu_session_free(struct BuSession *session);
+
+// Free a string returned by this library; null is ignored
+//
+// # Safety
+// `s` must be null or a string returned through an out-parameter of this library.
+void bu_string_free(char *s);
+
+// JSON description of the calling thread's last error, or null after a successful call
+//
+// The string belongs to the library and stays valid until the thread's next call.
+const char *bu_last_error(void);
+
+// Library version, e.g. "0.2.3"
+const char *bu_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BROWSER_USE_H */
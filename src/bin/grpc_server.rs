@@ -0,0 +1,77 @@
+//! Browser-use gRPC Server
+//!
+//! This binary serves the `browser_use.v1.BrowserUse` service of
+//! `proto/browser_use.proto` over one browser session, for infrastructure
+//! that standardizes on gRPC rather than MCP.
+
+use browser_use::browser::{BrowserSession, LaunchOptions};
+use browser_use::grpc::GrpcServer;
+use clap::Parser;
+use log::info;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "browser-use-grpc")]
+#[command(version)]
+#[command(about = "Browser automation gRPC server", long_about = None)]
+struct Cli {
+    /// Launch browser in headed mode (default: headless)
+    #[arg(long, short = 'H')]
+    headed: bool,
+
+    /// Path to custom browser executable
+    #[arg(long, value_name = "PATH")]
+    executable_path: Option<PathBuf>,
+
+    /// Persistent browser profile directory
+    #[arg(long, value_name = "DIR")]
+    user_data_dir: Option<PathBuf>,
+
+    /// Accept invalid TLS certificates, e.g. self-signed ones on staging hosts
+    #[arg(long)]
+    ignore_https_errors: bool,
+
+    /// Confine screenshots, downloads and other files written by tools to this directory
+    #[arg(long, value_name = "DIR")]
+    output_dir: Option<PathBuf>,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on
+    #[arg(long, short = 'p', default_value = "50051")]
+    port: u16,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let options = LaunchOptions {
+        headless: !cli.headed,
+        chrome_path: cli.executable_path,
+        user_data_dir: cli.user_data_dir,
+        ignore_https_errors: cli.ignore_https_errors,
+        output_dir: cli.output_dir,
+        ..Default::default()
+    };
+
+    info!("Browser-use gRPC Server v{}", env!("CARGO_PKG_VERSION"));
+    let session = tokio::task::spawn_blocking(move || BrowserSession::launch(options)).await??;
+    let server = GrpcServer::new(session)?;
+
+    let addr = format!("{}:{}", cli.host, cli.port).parse()?;
+    info!("Ready to accept gRPC connections at {}", addr);
+    tonic::transport::Server::builder()
+        .add_service(server.into_service())
+        .serve_with_shutdown(addr, async {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                info!("Received Ctrl+C, shutting down gracefully...");
+            }
+        })
+        .await?;
+
+    Ok(())
+}
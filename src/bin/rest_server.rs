@@ -0,0 +1,130 @@
+//! Browser-use REST Server
+//!
+//! This binary serves the HTTP API of `browser_use::rest`, launching one
+//! browser per session, for clients that cannot speak MCP or gRPC.
+
+use browser_use::browser::LaunchOptions;
+use browser_use::rest;
+use clap::Parser;
+use log::{info, warn};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Parser)]
+#[command(name = "browser-use-rest")]
+#[command(version)]
+#[command(about = "Browser automation REST server", long_about = None)]
+struct Cli {
+    /// Launch browsers in headed mode unless a session asks otherwise (default: headless)
+    #[arg(long, short = 'H')]
+    headed: bool,
+
+    /// Path to custom browser executable
+    #[arg(long, value_name = "PATH")]
+    executable_path: Option<PathBuf>,
+
+    /// Accept invalid TLS certificates, e.g. self-signed ones on staging hosts
+    #[arg(long)]
+    ignore_https_errors: bool,
+
+    /// Confine screenshots, downloads and other files written by tools to this directory
+    #[arg(long, value_name = "DIR")]
+    output_dir: Option<PathBuf>,
+
+    /// Seconds to wait for in-flight tool calls on shutdown before aborting them
+    #[arg(long, value_name = "SECS", default_value = "10")]
+    shutdown_timeout: u64,
+
+    /// Maximum number of sessions open at once
+    #[arg(long, default_value_t = rest::DEFAULT_MAX_SESSIONS)]
+    max_sessions: usize,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on
+    #[arg(long, short = 'p', default_value = "8080")]
+    port: u16,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let options = LaunchOptions {
+        headless: !cli.headed,
+        chrome_path: cli.executable_path,
+        ignore_https_errors: cli.ignore_https_errors,
+        output_dir: cli.output_dir,
+        ..Default::default()
+    };
+
+    info!("Browser-use REST Server v{}", env!("CARGO_PKG_VERSION"));
+    let listener = tokio::net::TcpListener::bind((cli.host.as_str(), cli.port)).await?;
+    info!(
+        "Ready to accept HTTP connections at {}",
+        listener.local_addr()?
+    );
+    let (app, sessions) = rest::router(options, cli.max_sessions);
+    tokio::select! {
+        result = axum::serve(listener, app) => result?,
+        result = shutdown_signal() => result?,
+    }
+
+    // Let in-flight tool calls finish, then close every session's browser
+    let grace = Duration::from_secs(cli.shutdown_timeout);
+    let closing = tokio::task::spawn_blocking(move || sessions.shutdown());
+    let clean = tokio::time::timeout(grace, closing).await.is_ok();
+    if clean {
+        info!("Browsers closed, exiting");
+    } else {
+        warn!(
+            "Tool call still running after {}s; aborting without closing its browser",
+            cli.shutdown_timeout
+        );
+    }
+    log::logger().flush();
+
+    if !clean {
+        // The blocked tool call would otherwise keep the runtime from shutting down
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Wait for a termination signal (SIGINT/SIGTERM, or Ctrl+C/Ctrl+Break on Windows)
+async fn shutdown_signal() -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        let mut sigterm = signal(SignalKind::terminate())?;
+        let mut sigint = signal(SignalKind::interrupt())?;
+
+        tokio::select! {
+            _ = sigterm.recv() => info!("Received SIGTERM, shutting down gracefully..."),
+            _ = sigint.recv() => info!("Received SIGINT (Ctrl+C), shutting down gracefully..."),
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let mut ctrl_c = tokio::signal::windows::ctrl_c()?;
+        let mut ctrl_break = tokio::signal::windows::ctrl_break()?;
+
+        tokio::select! {
+            _ = ctrl_c.recv() => info!("Received Ctrl+C, shutting down gracefully..."),
+            _ = ctrl_break.recv() => info!("Received Ctrl+Break, shutting down gracefully..."),
+        }
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        tokio::signal::ctrl_c().await?;
+        info!("Received Ctrl+C, shutting down gracefully...");
+    }
+
+    Ok(())
+}
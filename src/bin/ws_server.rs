@@ -0,0 +1,83 @@
+//! Browser-use WebSocket Server
+//!
+//! This binary serves the JSON-RPC channel of `browser_use::ws`, launching
+//! one browser per connection, for interactive frontends that want live page
+//! events alongside command execution.
+
+use browser_use::browser::LaunchOptions;
+use browser_use::ws;
+use clap::Parser;
+use log::info;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(name = "browser-use-ws")]
+#[command(version)]
+#[command(about = "Browser automation WebSocket JSON-RPC server", long_about = None)]
+struct Cli {
+    /// Launch browsers in headed mode (default: headless)
+    #[arg(long, short = 'H')]
+    headed: bool,
+
+    /// Path to custom browser executable
+    #[arg(long, value_name = "PATH")]
+    executable_path: Option<PathBuf>,
+
+    /// Accept invalid TLS certificates, e.g. self-signed ones on staging hosts
+    #[arg(long)]
+    ignore_https_errors: bool,
+
+    /// Confine screenshots, downloads and other files written by tools to this directory
+    #[arg(long, value_name = "DIR")]
+    output_dir: Option<PathBuf>,
+
+    /// Maximum number of connections (and browsers) open at once
+    #[arg(long, default_value_t = ws::DEFAULT_MAX_CONNECTIONS)]
+    max_connections: usize,
+
+    /// Web page origin allowed to connect, e.g. https://app.example.com (repeatable; pages on other origins are refused)
+    #[arg(long = "allow-origin", value_name = "ORIGIN")]
+    allow_origins: Vec<String>,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on
+    #[arg(long, short = 'p', default_value = "8765")]
+    port: u16,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let options = LaunchOptions {
+        headless: !cli.headed,
+        chrome_path: cli.executable_path,
+        ignore_https_errors: cli.ignore_https_errors,
+        output_dir: cli.output_dir,
+        ..Default::default()
+    };
+
+    info!(
+        "Browser-use WebSocket Server v{}",
+        env!("CARGO_PKG_VERSION")
+    );
+    let listener = tokio::net::TcpListener::bind((cli.host.as_str(), cli.port)).await?;
+    info!(
+        "Ready to accept WebSocket connections at ws://{}",
+        listener.local_addr()?
+    );
+    let app = ws::router(options, cli.max_connections, cli.allow_origins);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                info!("Received Ctrl+C, shutting down gracefully...");
+            }
+        })
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,417 @@
+//! Typed events of a session's tabs for library users
+//!
+//! [`SessionEventBus`] turns the DevTools events of the tabs it is attached to
+//! into [`SessionEvent`]s — finished navigations, opened dialogs, completed
+//! downloads and crashed tabs — and runs the callbacks subscribed to their
+//! kind, so embedders can react to the browser instead of polling it.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::browser::tab::EventListener;
+use headless_chrome::protocol::cdp::types::Event;
+use headless_chrome::protocol::cdp::{Browser, Inspector};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::BitOr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+/// Downloads whose URL is remembered until they finish; older ones are forgotten
+const MAX_PENDING_DOWNLOADS: usize = 100;
+
+/// Something that happened in one of the session's tabs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SessionEvent {
+    /// A document finished loading, or the URL changed within the document
+    #[serde(rename_all = "camelCase")]
+    NavigationFinished {
+        /// Target id of the tab
+        tab: String,
+        /// URL of the page
+        url: String,
+        /// Whether the URL changed without loading a new document (history API or fragment)
+        same_document: bool,
+    },
+    /// An alert, confirm, prompt or beforeunload dialog opened
+    #[serde(rename_all = "camelCase")]
+    DialogOpened {
+        /// Target id of the tab
+        tab: String,
+        /// "alert", "confirm", "prompt" or "beforeunload"
+        kind: String,
+        /// Text shown in the dialog
+        message: String,
+    },
+    /// A download finished or was cancelled
+    #[serde(rename_all = "camelCase")]
+    DownloadCompleted {
+        /// Target id of the tab
+        tab: String,
+        /// Download URL, when its start was seen
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        /// Where the file was saved, when the browser reports it
+        #[serde(skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+        /// Size of the file in bytes
+        bytes: u64,
+        /// Whether the download was cancelled instead of completed
+        canceled: bool,
+    },
+    /// The tab's renderer crashed; the tab must be reloaded or closed
+    #[serde(rename_all = "camelCase")]
+    TargetCrashed {
+        /// Target id of the tab
+        tab: String,
+    },
+}
+
+/// Kind of a [`SessionEvent`], used to pick the events a callback receives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionEventKind {
+    /// [`SessionEvent::NavigationFinished`]
+    NavigationFinished,
+    /// [`SessionEvent::DialogOpened`]
+    DialogOpened,
+    /// [`SessionEvent::DownloadCompleted`]
+    DownloadCompleted,
+    /// [`SessionEvent::TargetCrashed`]
+    TargetCrashed,
+}
+
+impl SessionEventKind {
+    fn bit(self) -> u8 {
+        match self {
+            SessionEventKind::NavigationFinished => 1,
+            SessionEventKind::DialogOpened => 1 << 1,
+            SessionEventKind::DownloadCompleted => 1 << 2,
+            SessionEventKind::TargetCrashed => 1 << 3,
+        }
+    }
+}
+
+impl SessionEvent {
+    /// Kind of the event
+    pub fn kind(&self) -> SessionEventKind {
+        match self {
+            SessionEvent::NavigationFinished { .. } => SessionEventKind::NavigationFinished,
+            SessionEvent::DialogOpened { .. } => SessionEventKind::DialogOpened,
+            SessionEvent::DownloadCompleted { .. } => SessionEventKind::DownloadCompleted,
+            SessionEvent::TargetCrashed { .. } => SessionEventKind::TargetCrashed,
+        }
+    }
+}
+
+/// Set of event kinds, built with `|`: `SessionEventKind::DialogOpened | SessionEventKind::TargetCrashed`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SessionEventKinds(u8);
+
+impl SessionEventKinds {
+    /// Every kind of event
+    pub fn all() -> Self {
+        Self(0b1111)
+    }
+
+    /// Whether `kind` is in the set
+    pub fn contains(self, kind: SessionEventKind) -> bool {
+        self.0 & kind.bit() != 0
+    }
+}
+
+impl From<SessionEventKind> for SessionEventKinds {
+    fn from(kind: SessionEventKind) -> Self {
+        Self(kind.bit())
+    }
+}
+
+impl BitOr for SessionEventKind {
+    type Output = SessionEventKinds;
+
+    fn bitor(self, other: Self) -> SessionEventKinds {
+        SessionEventKinds(self.bit() | other.bit())
+    }
+}
+
+impl BitOr<SessionEventKind> for SessionEventKinds {
+    type Output = SessionEventKinds;
+
+    fn bitor(self, other: SessionEventKind) -> SessionEventKinds {
+        SessionEventKinds(self.0 | other.bit())
+    }
+}
+
+/// Handle of a callback added with [`SessionEventBus::on`], to remove it again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// Callback run for subscribed session events
+pub type SessionEventCallback = dyn Fn(&SessionEvent) + Send + Sync;
+
+struct Subscription {
+    id: SubscriptionId,
+    kinds: SessionEventKinds,
+    callback: Arc<SessionEventCallback>,
+}
+
+type SyncListener = dyn EventListener<Event> + Send + Sync;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// What a tab's listener remembers between DevTools events
+#[derive(Debug, Default)]
+struct TabState {
+    /// Target id of the tab, which is also the id of its main frame
+    target_id: String,
+    /// URL of the main frame's current document
+    url: String,
+    /// URLs of downloads in progress, by download id
+    downloads: HashMap<String, String>,
+}
+
+/// Turn a DevTools event into a session event
+fn session_event(event: &Event, state: &mut TabState) -> Option<SessionEvent> {
+    let tab = state.target_id.clone();
+    match event {
+        Event::PageFrameNavigated(event) if event.params.frame.parent_id.is_none() => {
+            state.url = event.params.frame.url.clone();
+            None
+        }
+        Event::PageLoadEventFired(_) => Some(SessionEvent::NavigationFinished {
+            tab,
+            url: state.url.clone(),
+            same_document: false,
+        }),
+        Event::PageNavigatedWithinDocument(event) if event.params.frame_id == state.target_id => {
+            state.url = event.params.url.clone();
+            Some(SessionEvent::NavigationFinished {
+                tab,
+                url: state.url.clone(),
+                same_document: true,
+            })
+        }
+        Event::PageJavascriptDialogOpening(event) => {
+            let kind = serde_json::to_value(&event.params.Type)
+                .ok()
+                .and_then(|kind| kind.as_str().map(String::from))
+                .unwrap_or_default();
+            Some(SessionEvent::DialogOpened {
+                tab,
+                kind,
+                message: event.params.message.clone(),
+            })
+        }
+        Event::BrowserDownloadWillBegin(event) => {
+            if state.downloads.len() >= MAX_PENDING_DOWNLOADS {
+                state.downloads.clear();
+            }
+            state
+                .downloads
+                .insert(event.params.guid.clone(), event.params.url.clone());
+            None
+        }
+        Event::BrowserDownloadProgress(event) => {
+            let canceled = match event.params.state {
+                Browser::DownloadProgressEventStateOption::InProgress => return None,
+                Browser::DownloadProgressEventStateOption::Completed => false,
+                Browser::DownloadProgressEventStateOption::Canceled => true,
+            };
+            Some(SessionEvent::DownloadCompleted {
+                tab,
+                url: state.downloads.remove(&event.params.guid),
+                path: event.params.file_path.clone(),
+                bytes: event.params.received_bytes as u64,
+                canceled,
+            })
+        }
+        Event::InspectorTargetCrashed(_) => Some(SessionEvent::TargetCrashed { tab }),
+        _ => None,
+    }
+}
+
+/// Runs subscribed callbacks for the events of the tabs it is attached to
+#[derive(Default)]
+pub struct SessionEventBus {
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
+    listeners: Mutex<Vec<(String, Weak<SyncListener>)>>,
+    next_id: AtomicU64,
+}
+
+impl SessionEventBus {
+    /// Create a bus that is not attached to any tab yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the bus is attached to any tab
+    pub fn is_active(&self) -> bool {
+        lock(&self.listeners)
+            .iter()
+            .any(|(_, listener)| listener.strong_count() > 0)
+    }
+
+    /// Start reporting the tab's events; does nothing if already attached
+    ///
+    /// Download events are switched on for the browser without changing where
+    /// or whether it saves downloads.
+    pub fn attach(&self, tab: &Arc<Tab>) -> Result<()> {
+        let target_id = tab.get_target_id().clone();
+        let mut listeners = lock(&self.listeners);
+        listeners.retain(|(_, listener)| listener.strong_count() > 0);
+        if listeners.iter().any(|(id, _)| *id == target_id) {
+            return Ok(());
+        }
+
+        let chrome_error = |e: anyhow::Error| {
+            BrowserError::ChromeError(format!("Failed to watch session events: {}", e))
+        };
+        tab.call_method(Inspector::Enable(None))
+            .map_err(chrome_error)?;
+        if let Err(e) = tab.call_method(Browser::SetDownloadBehavior {
+            behavior: Browser::SetDownloadBehaviorBehaviorOption::Default,
+            browser_context_id: None,
+            download_path: None,
+            events_enabled: Some(true),
+        }) {
+            log::debug!("Download events are not reported: {}", e);
+        }
+
+        let subscriptions = Arc::clone(&self.subscriptions);
+        let state = Mutex::new(TabState {
+            target_id: target_id.clone(),
+            url: tab.get_url(),
+            ..TabState::default()
+        });
+        let listener = tab
+            .add_event_listener(Arc::new(move |event: &Event| {
+                let Some(event) = session_event(event, &mut lock(&state)) else {
+                    return;
+                };
+                let callbacks: Vec<_> = lock(&subscriptions)
+                    .iter()
+                    .filter(|subscription| subscription.kinds.contains(event.kind()))
+                    .map(|subscription| Arc::clone(&subscription.callback))
+                    .collect();
+                for callback in callbacks {
+                    callback(&event);
+                }
+            }))
+            .map_err(chrome_error)?;
+        listeners.push((target_id, listener));
+
+        Ok(())
+    }
+
+    /// Run `callback` for every event of the given kinds from now on
+    pub fn on(
+        &self,
+        kinds: impl Into<SessionEventKinds>,
+        callback: impl Fn(&SessionEvent) + Send + Sync + 'static,
+    ) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id.fetch_add(1, Ordering::SeqCst));
+        lock(&self.subscriptions).push(Subscription {
+            id,
+            kinds: kinds.into(),
+            callback: Arc::new(callback),
+        });
+        id
+    }
+
+    /// Stop running a callback; returns whether it was subscribed
+    pub fn off(&self, id: SubscriptionId) -> bool {
+        let mut subscriptions = lock(&self.subscriptions);
+        let before = subscriptions.len();
+        subscriptions.retain(|subscription| subscription.id != id);
+        subscriptions.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(method: &str, params: serde_json::Value) -> Event {
+        serde_json::from_value(serde_json::json!({ "method": method, "params": params })).unwrap()
+    }
+
+    #[test]
+    fn test_event_kinds() {
+        let kinds = SessionEventKind::DialogOpened | SessionEventKind::TargetCrashed;
+        assert!(kinds.contains(SessionEventKind::DialogOpened));
+        assert!(kinds.contains(SessionEventKind::TargetCrashed));
+        assert!(!kinds.contains(SessionEventKind::NavigationFinished));
+        assert!(SessionEventKinds::all().contains(SessionEventKind::DownloadCompleted));
+    }
+
+    #[test]
+    fn test_navigation_and_download_events() {
+        let mut state = TabState {
+            target_id: "T1".to_string(),
+            ..TabState::default()
+        };
+        let navigated = event(
+            "Page.frameNavigated",
+            serde_json::json!({
+                "frame": {
+                    "id": "T1",
+                    "loaderId": "L1",
+                    "url": "https://example.com/",
+                    "domainAndRegistry": "example.com",
+                    "securityOrigin": "https://example.com",
+                    "mimeType": "text/html",
+                    "secureContextType": "Secure",
+                    "crossOriginIsolatedContextType": "NotIsolated",
+                    "gatedAPIFeatures": []
+                },
+                "type": "Navigation"
+            }),
+        );
+        assert_eq!(session_event(&navigated, &mut state), None);
+        let loaded = event(
+            "Page.loadEventFired",
+            serde_json::json!({ "timestamp": 0.0 }),
+        );
+        assert_eq!(
+            session_event(&loaded, &mut state),
+            Some(SessionEvent::NavigationFinished {
+                tab: "T1".to_string(),
+                url: "https://example.com/".to_string(),
+                same_document: false
+            })
+        );
+
+        let begin = event(
+            "Browser.downloadWillBegin",
+            serde_json::json!({
+                "frameId": "T1",
+                "guid": "d1",
+                "url": "https://example.com/report.csv",
+                "suggestedFilename": "report.csv"
+            }),
+        );
+        assert_eq!(session_event(&begin, &mut state), None);
+        let done = event(
+            "Browser.downloadProgress",
+            serde_json::json!({
+                "guid": "d1",
+                "totalBytes": 42.0,
+                "receivedBytes": 42.0,
+                "state": "completed",
+                "filePath": "/tmp/report.csv"
+            }),
+        );
+        assert_eq!(
+            session_event(&done, &mut state),
+            Some(SessionEvent::DownloadCompleted {
+                tab: "T1".to_string(),
+                url: Some("https://example.com/report.csv".to_string()),
+                path: Some("/tmp/report.csv".to_string()),
+                bytes: 42,
+                canceled: false
+            })
+        );
+        assert!(state.downloads.is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+//! Session checkpoints
+//!
+//! A [`Checkpoint`] records where the active tab is and what the site
+//! remembers about the user: the URL, every cookie, the page origin's
+//! localStorage and sessionStorage, and the scroll position. Rolling back to
+//! it lets an agent retry a failed sub-task from a known-good point instead of
+//! replaying the whole journey.
+
+use crate::browser::storage_state::{StorageCookie, StorageEntry};
+use crate::error::{BrowserError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+
+/// Checkpoints kept per session; taking another drops the oldest
+pub const MAX_CHECKPOINTS: usize = 16;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Page state captured by [`BrowserSession::checkpoint`](crate::browser::BrowserSession::checkpoint)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    /// Identifier passed to [`BrowserSession::rollback`](crate::browser::BrowserSession::rollback)
+    pub id: String,
+
+    /// URL of the active tab
+    pub url: String,
+
+    /// All cookies known to the browser
+    pub cookies: Vec<StorageCookie>,
+
+    /// Origin whose storage was captured, or `None` for opaque origins such as `about:blank`
+    pub origin: Option<String>,
+
+    /// localStorage of the page's origin
+    pub local_storage: Vec<StorageEntry>,
+
+    /// sessionStorage of the page's origin
+    pub session_storage: Vec<StorageEntry>,
+
+    /// Horizontal scroll offset in CSS pixels
+    pub scroll_x: f64,
+
+    /// Vertical scroll offset in CSS pixels
+    pub scroll_y: f64,
+}
+
+/// Checkpoints of a session, oldest first
+#[derive(Debug, Default)]
+pub struct CheckpointStore {
+    inner: Mutex<StoreInner>,
+}
+
+#[derive(Debug, Default)]
+struct StoreInner {
+    checkpoints: VecDeque<Checkpoint>,
+    next_id: u64,
+}
+
+impl CheckpointStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Identifier the next checkpoint gets
+    pub(crate) fn next_id(&self) -> String {
+        let mut inner = lock(&self.inner);
+        inner.next_id += 1;
+        format!("cp-{}", inner.next_id)
+    }
+
+    /// Keep a checkpoint, dropping the oldest beyond [`MAX_CHECKPOINTS`]
+    pub(crate) fn push(&self, checkpoint: Checkpoint) {
+        let mut inner = lock(&self.inner);
+        inner.checkpoints.push_back(checkpoint);
+        while inner.checkpoints.len() > MAX_CHECKPOINTS {
+            inner.checkpoints.pop_front();
+        }
+    }
+
+    /// The checkpoint with `id`
+    pub fn get(&self, id: &str) -> Result<Checkpoint> {
+        let inner = lock(&self.inner);
+        inner
+            .checkpoints
+            .iter()
+            .find(|checkpoint| checkpoint.id == id)
+            .cloned()
+            .ok_or_else(|| {
+                let known: Vec<&str> = inner.checkpoints.iter().map(|c| c.id.as_str()).collect();
+                BrowserError::InvalidArgument(format!(
+                    "No checkpoint '{}' (available: {})",
+                    id,
+                    if known.is_empty() {
+                        "none".to_string()
+                    } else {
+                        known.join(", ")
+                    }
+                ))
+            })
+    }
+
+    /// Identifiers of the kept checkpoints, oldest first
+    pub fn ids(&self) -> Vec<String> {
+        lock(&self.inner)
+            .checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.id.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkpoint(store: &CheckpointStore) -> Checkpoint {
+        Checkpoint {
+            id: store.next_id(),
+            url: "https://example.com/cart".to_string(),
+            cookies: Vec::new(),
+            origin: Some("https://example.com".to_string()),
+            local_storage: Vec::new(),
+            session_storage: Vec::new(),
+            scroll_x: 0.0,
+            scroll_y: 240.0,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_lookup() {
+        let store = CheckpointStore::new();
+        let error = store.get("cp-1").unwrap_err();
+        assert!(error.to_string().contains("available: none"), "{}", error);
+
+        let first = checkpoint(&store);
+        store.push(first.clone());
+        assert_eq!(first.id, "cp-1");
+        assert_eq!(store.get("cp-1").unwrap(), first);
+
+        let error = store.get("cp-9").unwrap_err();
+        assert!(error.to_string().contains("available: cp-1"), "{}", error);
+    }
+
+    #[test]
+    fn test_oldest_checkpoints_are_dropped() {
+        let store = CheckpointStore::new();
+        for _ in 0..MAX_CHECKPOINTS + 2 {
+            store.push(checkpoint(&store));
+        }
+        let ids = store.ids();
+        assert_eq!(ids.len(), MAX_CHECKPOINTS);
+        assert_eq!(ids[0], "cp-3");
+        assert!(store.get("cp-1").is_err());
+    }
+}
@@ -1,4 +1,17 @@
-use std::path::PathBuf;
+use crate::browser::confirmation::ConfirmationPolicy;
+use crate::browser::deterministic::DeterministicOptions;
+use crate::browser::policy::EvaluatePolicy;
+use crate::browser::proxy::ProxyOptions;
+use crate::browser::rate_limit::RateLimitOptions;
+use crate::browser::scope::OriginScope;
+use crate::browser::viewport::Viewport;
+use crate::dom::IndexOrder;
+use crate::error::{BrowserError, Result};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Options for launching a new browser instance
 #[derive(Debug, Clone)]
@@ -6,7 +19,7 @@ pub struct LaunchOptions {
     /// Whether to run browser in headless mode (default: true)
     pub headless: bool,
 
-    /// Custom Chrome/Chromium binary path
+    /// Custom Chrome/Chromium binary path, checked to exist and be executable at launch
     pub chrome_path: Option<PathBuf>,
 
     /// Browser window width (default: 1280)
@@ -15,7 +28,12 @@ pub struct LaunchOptions {
     /// Browser window height (default: 720)
     pub window_height: u32,
 
-    /// User data directory for browser profile
+    /// Pin the viewport to the window size at this many device pixels per
+    /// CSS pixel; `None` leaves layout to the window and display (default: None)
+    pub device_scale_factor: Option<f64>,
+
+    /// User data directory for browser profile; cookies, logins and storage
+    /// persist in it across launches and restarts
     pub user_data_dir: Option<PathBuf>,
 
     /// Enable sandbox mode (default: true)
@@ -23,6 +41,59 @@ pub struct LaunchOptions {
 
     /// Timeout for browser launch in milliseconds (default: 30000)
     pub launch_timeout: u64,
+
+    /// How long navigations wait for the page to load, in milliseconds (default: 20000)
+    pub navigation_timeout_ms: u64,
+
+    /// Reload a tab whose renderer crashed when a tool next uses it, instead
+    /// of failing with a page-crashed error (default: false)
+    pub reload_on_crash: bool,
+
+    /// Start a new browser when a tool call finds that Chrome exited or the
+    /// DevTools connection dropped, instead of failing with a
+    /// browser-disconnected error (default: false)
+    pub relaunch_on_disconnect: bool,
+
+    /// Accept invalid TLS certificates, e.g. self-signed ones on staging hosts (default: false)
+    pub ignore_https_errors: bool,
+
+    /// Upstream proxy for all browser traffic; `None` connects directly (default: None)
+    pub proxy: Option<ProxyOptions>,
+
+    /// Extra Chrome command-line flags, e.g. `--lang=de`; passed after the
+    /// crate's own flags, so they win where Chrome keeps the last value (default: none)
+    pub args: Vec<String>,
+
+    /// Environment variables for the Chrome process, on top of the inherited
+    /// ones; `TZ` is overridden by [`LaunchOptions::determinism`] (default: none)
+    pub env: HashMap<String, String>,
+
+    /// User agent token to obey robots.txt for; `None` disables the check (default: None)
+    pub robots_user_agent: Option<String>,
+
+    /// Per-host politeness limits for navigation; `None` disables throttling (default: None)
+    pub rate_limit: Option<RateLimitOptions>,
+
+    /// Directory file-writing tools are confined to; `None` allows any path (default: None)
+    pub output_dir: Option<PathBuf>,
+
+    /// Restrictions on the evaluate tool (default: unrestricted)
+    pub evaluate_policy: EvaluatePolicy,
+
+    /// Actions that need human approval before they run (default: none)
+    pub confirmation: ConfirmationPolicy,
+
+    /// Origins the session may act on; `None` allows any (default: None)
+    pub scope: Option<OriginScope>,
+
+    /// Settings for reproducible rendering; `None` renders normally (default: None)
+    pub determinism: Option<DeterministicOptions>,
+
+    /// Seed for the session's random delays and mouse paths; `None` picks one at random (default: None)
+    pub rng_seed: Option<u64>,
+
+    /// How snapshot elements are numbered (default: document order)
+    pub index_order: IndexOrder,
 }
 
 impl Default for LaunchOptions {
@@ -32,9 +103,26 @@ impl Default for LaunchOptions {
             chrome_path: None,
             window_width: 1280,
             window_height: 720,
+            device_scale_factor: None,
             user_data_dir: None,
             sandbox: true,
             launch_timeout: 30000,
+            navigation_timeout_ms: 20000,
+            reload_on_crash: false,
+            relaunch_on_disconnect: false,
+            ignore_https_errors: false,
+            proxy: None,
+            args: Vec::new(),
+            env: HashMap::new(),
+            robots_user_agent: None,
+            rate_limit: None,
+            output_dir: None,
+            evaluate_policy: EvaluatePolicy::default(),
+            confirmation: ConfirmationPolicy::default(),
+            scope: None,
+            determinism: None,
+            rng_seed: None,
+            index_order: IndexOrder::Dom,
         }
     }
 }
@@ -45,6 +133,11 @@ impl LaunchOptions {
         Self::default()
     }
 
+    /// Default options plus [`DeterministicOptions::default`], for stable screenshots and visual diffs
+    pub fn deterministic() -> Self {
+        Self::default().determinism(DeterministicOptions::default())
+    }
+
     /// Builder method: set headless mode
     pub fn headless(mut self, headless: bool) -> Self {
         self.headless = headless;
@@ -57,6 +150,16 @@ impl LaunchOptions {
         self
     }
 
+    /// Builder method: launch this Chromium-based browser (Chrome, Chromium,
+    /// Brave, Edge or a pinned build) instead of the one found on the system
+    ///
+    /// Same as [`LaunchOptions::chrome_path`]. The path is checked at launch;
+    /// a missing or non-executable file fails with
+    /// [`BrowserError::BrowserNotFound`].
+    pub fn executable_path(self, path: PathBuf) -> Self {
+        self.chrome_path(path)
+    }
+
     /// Builder method: set window dimensions
     pub fn window_size(mut self, width: u32, height: u32) -> Self {
         self.window_width = width;
@@ -64,6 +167,22 @@ impl LaunchOptions {
         self
     }
 
+    /// Builder method: render at a fixed device scale factor, e.g. 2 for retina screenshots
+    ///
+    /// Also pins the viewport to the window size, which headed windows
+    /// otherwise shrink by their toolbars.
+    pub fn device_scale_factor(mut self, factor: f64) -> Self {
+        self.device_scale_factor = Some(factor);
+        self
+    }
+
+    /// Viewport every tab is rendered at, when one is pinned
+    pub fn viewport(&self) -> Option<Viewport> {
+        self.device_scale_factor.map(|factor| {
+            Viewport::new(self.window_width, self.window_height).device_scale_factor(factor)
+        })
+    }
+
     /// Builder method: set user data directory
     pub fn user_data_dir(mut self, dir: PathBuf) -> Self {
         self.user_data_dir = Some(dir);
@@ -81,12 +200,145 @@ impl LaunchOptions {
         self.launch_timeout = timeout_ms;
         self
     }
+
+    /// Builder method: set how long navigations wait for the page to load
+    pub fn navigation_timeout(mut self, timeout_ms: u64) -> Self {
+        self.navigation_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Builder method: reload crashed tabs automatically
+    ///
+    /// The reloaded page starts over: form input and script state are lost.
+    pub fn reload_on_crash(mut self, reload: bool) -> Self {
+        self.reload_on_crash = reload;
+        self
+    }
+
+    /// Builder method: relaunch a browser that exited or disconnected
+    ///
+    /// Used by [`BrowserServer`](crate::mcp::BrowserServer). The new browser
+    /// starts without the old one's tabs, cookies and storage; a server
+    /// connected to a running browser reconnects instead.
+    pub fn relaunch_on_disconnect(mut self, relaunch: bool) -> Self {
+        self.relaunch_on_disconnect = relaunch;
+        self
+    }
+
+    /// Builder method: accept invalid TLS certificates
+    ///
+    /// Only meant for test and staging environments; it disables protection
+    /// against intercepted connections for every site the browser visits.
+    pub fn ignore_https_errors(mut self, ignore: bool) -> Self {
+        self.ignore_https_errors = ignore;
+        self
+    }
+
+    /// Builder method: send all browser traffic through a proxy
+    pub fn proxy(mut self, proxy: ProxyOptions) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Builder method: pass an extra flag to Chrome, e.g. `--disable-gpu`
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Builder method: pass extra flags to Chrome
+    pub fn args<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Builder method: set an environment variable for the Chrome process
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+
+    /// Builder method: refuse navigation to URLs disallowed by robots.txt for this user agent
+    pub fn respect_robots_txt(mut self, user_agent: impl Into<String>) -> Self {
+        self.robots_user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Builder method: throttle navigation per host
+    pub fn rate_limit(mut self, rate_limit: RateLimitOptions) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Builder method: confine tool output files to a directory
+    pub fn output_dir(mut self, dir: PathBuf) -> Self {
+        self.output_dir = Some(dir);
+        self
+    }
+
+    /// Builder method: restrict the evaluate tool
+    pub fn evaluate_policy(mut self, policy: EvaluatePolicy) -> Self {
+        self.evaluate_policy = policy;
+        self
+    }
+
+    /// Builder method: require human approval for matching actions
+    pub fn confirmation(mut self, policy: ConfirmationPolicy) -> Self {
+        self.confirmation = policy;
+        self
+    }
+
+    /// Builder method: pin the session to a set of origins
+    pub fn scope(mut self, scope: OriginScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Builder method: render reproducibly with the given settings
+    pub fn determinism(mut self, options: DeterministicOptions) -> Self {
+        self.determinism = Some(options);
+        self
+    }
+
+    /// Builder method: seed the session's randomness, to replay a run exactly
+    pub fn rng_seed(mut self, seed: u64) -> Self {
+        self.rng_seed = Some(seed);
+        self
+    }
+
+    /// Builder method: number snapshot elements in document or visual reading order
+    pub fn index_order(mut self, order: IndexOrder) -> Self {
+        self.index_order = order;
+        self
+    }
+}
+
+/// Check that `path` is a file this user may execute
+pub fn validate_executable(path: &Path) -> Result<()> {
+    let not_found =
+        |reason: &str| BrowserError::BrowserNotFound(format!("{} {}", path.display(), reason));
+    let metadata = std::fs::metadata(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => not_found("does not exist"),
+        _ => not_found(&format!("cannot be read: {}", e)),
+    })?;
+    if !metadata.is_file() {
+        return Err(not_found("is not a file"));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return Err(not_found("is not executable"));
+        }
+    }
+    Ok(())
 }
 
 /// Options for connecting to an existing browser instance
 #[derive(Debug, Clone)]
 pub struct ConnectionOptions {
-    /// WebSocket URL for Chrome DevTools Protocol
+    /// WebSocket URL for Chrome DevTools Protocol, or the browser's
+    /// `http://host:port` DevTools endpoint to look it up from
     pub ws_url: String,
 
     /// Connection timeout in milliseconds (default: 10000)
@@ -107,6 +359,76 @@ impl ConnectionOptions {
         self.timeout = timeout_ms;
         self
     }
+
+    /// Replace an HTTP endpoint in `ws_url` with the browser's WebSocket URL
+    ///
+    /// `ws://` and `wss://` URLs are kept. For an HTTP endpoint such as
+    /// `http://localhost:9222` (what `--remote-debugging-port` serves), the
+    /// WebSocket URL is read from its `/json/version`.
+    pub fn resolve(mut self) -> Result<Self> {
+        let url = url::Url::parse(&self.ws_url).map_err(|e| {
+            BrowserError::InvalidArgument(format!("Invalid endpoint '{}': {}", self.ws_url, e))
+        })?;
+        match url.scheme() {
+            "ws" | "wss" => Ok(self),
+            "http" => {
+                let body = fetch_version(&url, Duration::from_millis(self.timeout))?;
+                self.ws_url = parse_version_response(&body)?;
+                Ok(self)
+            }
+            scheme => Err(BrowserError::InvalidArgument(format!(
+                "Unsupported endpoint scheme '{}': use an http:// DevTools endpoint or a ws:// URL",
+                scheme
+            ))),
+        }
+    }
+}
+
+/// Raw HTTP response of the endpoint's `/json/version`
+fn fetch_version(endpoint: &url::Url, timeout: Duration) -> Result<String> {
+    let failed = |e: std::io::Error| BrowserError::ConnectionFailed(format!("{}: {}", endpoint, e));
+    let host = endpoint
+        .host_str()
+        .ok_or_else(|| BrowserError::InvalidArgument(format!("No host in '{}'", endpoint)))?;
+    let port = endpoint.port_or_known_default().unwrap_or(80);
+    let address = (host, port)
+        .to_socket_addrs()
+        .map_err(failed)?
+        .next()
+        .ok_or_else(|| BrowserError::ConnectionFailed(format!("Cannot resolve '{}'", host)))?;
+
+    let mut stream = TcpStream::connect_timeout(&address, timeout).map_err(failed)?;
+    stream.set_read_timeout(Some(timeout)).map_err(failed)?;
+    // Chrome answers DevTools HTTP requests only for an IP or localhost Host header
+    write!(
+        stream,
+        "GET /json/version HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+        address
+    )
+    .map_err(failed)?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(failed)?;
+    Ok(response)
+}
+
+/// `webSocketDebuggerUrl` of a `/json/version` HTTP response
+fn parse_version_response(response: &str) -> Result<String> {
+    let failed = |reason: &str| {
+        BrowserError::ConnectionFailed(format!("Unexpected /json/version response: {}", reason))
+    };
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| failed("no body"))?;
+    let status = head.lines().next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        return Err(failed(status));
+    }
+    let version: serde_json::Value =
+        serde_json::from_str(body.trim()).map_err(|_| failed("body is not JSON"))?;
+    version["webSocketDebuggerUrl"]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| failed("no webSocketDebuggerUrl"))
 }
 
 #[cfg(test)]
@@ -119,8 +441,40 @@ mod tests {
         assert!(opts.headless);
         assert_eq!(opts.window_width, 1280);
         assert_eq!(opts.window_height, 720);
+        assert!(opts.viewport().is_none());
         assert!(opts.sandbox);
         assert_eq!(opts.launch_timeout, 30000);
+        assert_eq!(opts.navigation_timeout_ms, 20000);
+        assert!(!opts.reload_on_crash);
+        assert!(!opts.relaunch_on_disconnect);
+        assert!(!opts.ignore_https_errors);
+        assert!(opts.proxy.is_none());
+        assert!(opts.args.is_empty());
+        assert!(opts.env.is_empty());
+        assert!(opts.robots_user_agent.is_none());
+        assert!(opts.rate_limit.is_none());
+        assert!(opts.output_dir.is_none());
+        assert!(opts.confirmation.is_empty());
+        assert!(opts.scope.is_none());
+        assert!(opts.determinism.is_none());
+        assert!(opts.rng_seed.is_none());
+        assert_eq!(opts.index_order, IndexOrder::Dom);
+    }
+
+    #[test]
+    fn test_deterministic_preset() {
+        let opts = LaunchOptions::deterministic();
+        assert!(opts.headless);
+        assert_eq!(opts.determinism, Some(DeterministicOptions::default()));
+
+        let opts = LaunchOptions::new().determinism(
+            DeterministicOptions::new()
+                .timezone("Asia/Tokyo")
+                .freeze_js(true),
+        );
+        let determinism = opts.determinism.unwrap();
+        assert_eq!(determinism.timezone, "Asia/Tokyo");
+        assert!(determinism.freeze_js);
     }
 
     #[test]
@@ -128,14 +482,49 @@ mod tests {
         let opts = LaunchOptions::new()
             .headless(false)
             .window_size(1920, 1080)
+            .device_scale_factor(2.0)
             .sandbox(false)
-            .launch_timeout(60000);
+            .launch_timeout(60000)
+            .navigation_timeout(5000)
+            .reload_on_crash(true)
+            .relaunch_on_disconnect(true)
+            .ignore_https_errors(true)
+            .proxy(ProxyOptions::new("http://proxy:3128"))
+            .arg("--disable-gpu")
+            .args(["--lang=de", "--force-device-scale-factor=2"])
+            .env("LANGUAGE", "de")
+            .respect_robots_txt("browser-use")
+            .output_dir(PathBuf::from("out"))
+            .rng_seed(42)
+            .index_order(IndexOrder::Visual);
 
         assert!(!opts.headless);
         assert_eq!(opts.window_width, 1920);
         assert_eq!(opts.window_height, 1080);
+        assert_eq!(
+            opts.viewport(),
+            Some(Viewport::new(1920, 1080).device_scale_factor(2.0))
+        );
         assert!(!opts.sandbox);
         assert_eq!(opts.launch_timeout, 60000);
+        assert_eq!(opts.navigation_timeout_ms, 5000);
+        assert!(opts.reload_on_crash);
+        assert!(opts.relaunch_on_disconnect);
+        assert!(opts.ignore_https_errors);
+        assert_eq!(opts.proxy.unwrap().server, "http://proxy:3128");
+        assert_eq!(
+            opts.args,
+            [
+                "--disable-gpu",
+                "--lang=de",
+                "--force-device-scale-factor=2"
+            ]
+        );
+        assert_eq!(opts.env.get("LANGUAGE").map(String::as_str), Some("de"));
+        assert_eq!(opts.robots_user_agent.as_deref(), Some("browser-use"));
+        assert_eq!(opts.output_dir, Some(PathBuf::from("out")));
+        assert_eq!(opts.rng_seed, Some(42));
+        assert_eq!(opts.index_order, IndexOrder::Visual);
     }
 
     #[test]
@@ -145,4 +534,52 @@ mod tests {
         assert_eq!(opts.ws_url, "ws://localhost:9222");
         assert_eq!(opts.timeout, 5000);
     }
+
+    #[test]
+    fn test_validate_executable() {
+        let missing = validate_executable(Path::new("/nonexistent/chrome")).unwrap_err();
+        assert!(matches!(missing, BrowserError::BrowserNotFound(_)));
+        assert!(missing.to_string().contains("does not exist"));
+
+        let dir = validate_executable(&std::env::temp_dir()).unwrap_err();
+        assert!(dir.to_string().contains("is not a file"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = std::env::temp_dir().join(format!("browser-use-exe-{}", std::process::id()));
+            std::fs::write(&path, "#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(
+                validate_executable(&path)
+                    .unwrap_err()
+                    .to_string()
+                    .contains("is not executable")
+            );
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            assert!(validate_executable(&path).is_ok());
+            std::fs::remove_file(&path).unwrap();
+        }
+
+        let opts = LaunchOptions::new().executable_path(PathBuf::from("/opt/brave/brave"));
+        assert_eq!(opts.chrome_path, Some(PathBuf::from("/opt/brave/brave")));
+    }
+
+    #[test]
+    fn test_connection_endpoint() {
+        let options = ConnectionOptions::new("ws://127.0.0.1:9222/devtools/browser/abc")
+            .resolve()
+            .unwrap();
+        assert_eq!(options.ws_url, "ws://127.0.0.1:9222/devtools/browser/abc");
+        assert!(ConnectionOptions::new("ftp://127.0.0.1").resolve().is_err());
+        assert!(ConnectionOptions::new("not a url").resolve().is_err());
+
+        let response = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n{\"Browser\": \"Chrome/120\", \"webSocketDebuggerUrl\": \"ws://127.0.0.1:9222/devtools/browser/abc\"}";
+        assert_eq!(
+            parse_version_response(response).unwrap(),
+            "ws://127.0.0.1:9222/devtools/browser/abc"
+        );
+        assert!(parse_version_response("HTTP/1.1 404 Not Found\r\n\r\n").is_err());
+        assert!(parse_version_response("HTTP/1.1 200 OK\r\n\r\n{}").is_err());
+    }
 }
@@ -0,0 +1,301 @@
+//! Human-in-the-loop confirmation for sensitive actions
+//!
+//! A [`ConfirmationPolicy`] lists patterns for actions that need a human's
+//! approval — clicks on elements whose text matches `delete|pay|purchase`,
+//! navigation to payment domains, or every call of a given tool — together with
+//! the callback that is asked to approve them. When no callback is installed,
+//! matching actions are refused.
+
+use crate::error::{BrowserError, Result};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use std::fmt;
+use std::sync::Arc;
+
+/// Action awaiting approval, passed to the confirmation handler
+#[derive(Debug, Clone, Serialize)]
+pub struct SensitiveAction {
+    /// Tool about to run (e.g. "click", "navigate")
+    pub tool: String,
+
+    /// Visible text or label of the target element, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// URL about to be opened, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Pattern or rule that flagged the action
+    pub rule: String,
+}
+
+impl fmt::Display for SensitiveAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tool)?;
+        if let Some(text) = &self.text {
+            write!(f, " on \"{}\"", text)?;
+        }
+        if let Some(url) = &self.url {
+            write!(f, " to {}", url)?;
+        }
+        Ok(())
+    }
+}
+
+/// Callback deciding whether a sensitive action may proceed
+pub type ConfirmationHandler = Arc<dyn Fn(&SensitiveAction) -> bool + Send + Sync>;
+
+/// Which actions require confirmation and who confirms them
+#[derive(Clone, Default)]
+pub struct ConfirmationPolicy {
+    /// Case-insensitive patterns matched against the target element's text
+    pub text_patterns: Vec<Regex>,
+
+    /// Case-insensitive patterns matched against URLs being navigated to
+    pub url_patterns: Vec<Regex>,
+
+    /// Tools that always require confirmation
+    pub tools: Vec<String>,
+
+    /// Approver; `None` refuses every matching action
+    handler: Option<ConfirmationHandler>,
+}
+
+impl fmt::Debug for ConfirmationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfirmationPolicy")
+            .field("text_patterns", &self.text_patterns)
+            .field("url_patterns", &self.url_patterns)
+            .field("tools", &self.tools)
+            .field("handler", &self.handler.is_some())
+            .finish()
+    }
+}
+
+fn compile(pattern: &str) -> Result<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| BrowserError::InvalidArgument(format!("Invalid pattern '{}': {}", pattern, e)))
+}
+
+fn first_match<'a>(patterns: &'a [Regex], value: Option<&str>) -> Option<&'a Regex> {
+    let value = value?;
+    patterns.iter().find(|pattern| pattern.is_match(value))
+}
+
+impl ConfirmationPolicy {
+    /// Create a policy that requires no confirmation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: confirm actions on elements whose text matches `pattern`
+    pub fn text_pattern(mut self, pattern: &str) -> Result<Self> {
+        self.text_patterns.push(compile(pattern)?);
+        Ok(self)
+    }
+
+    /// Builder method: confirm navigation to URLs matching `pattern`
+    pub fn url_pattern(mut self, pattern: &str) -> Result<Self> {
+        self.url_patterns.push(compile(pattern)?);
+        Ok(self)
+    }
+
+    /// Builder method: confirm every call of the named tool
+    pub fn tool(mut self, name: impl Into<String>) -> Self {
+        self.tools.push(name.into());
+        self
+    }
+
+    /// Builder method: set the callback that approves or declines actions
+    pub fn handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&SensitiveAction) -> bool + Send + Sync + 'static,
+    {
+        self.handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Whether any rule is configured
+    pub fn is_empty(&self) -> bool {
+        self.text_patterns.is_empty() && self.url_patterns.is_empty() && self.tools.is_empty()
+    }
+
+    /// Whether any rule looks at element text (so callers can skip reading it)
+    pub fn inspects_text(&self) -> bool {
+        !self.text_patterns.is_empty()
+    }
+
+    /// Return the action if it matches a rule, or `None` if it may run unconfirmed
+    pub fn sensitive_action(
+        &self,
+        tool: &str,
+        text: Option<&str>,
+        url: Option<&str>,
+    ) -> Option<SensitiveAction> {
+        self.sensitive_tool(tool)
+            .or_else(|| self.sensitive_target(tool, text, url))
+    }
+
+    /// Return the call if the tool is listed in `tools`
+    fn sensitive_tool(&self, tool: &str) -> Option<SensitiveAction> {
+        self.tools
+            .iter()
+            .any(|t| t == tool)
+            .then(|| SensitiveAction {
+                tool: tool.to_string(),
+                text: None,
+                url: None,
+                rule: format!("tool:{}", tool),
+            })
+    }
+
+    /// Return the action if its element text or URL matches a pattern
+    fn sensitive_target(
+        &self,
+        tool: &str,
+        text: Option<&str>,
+        url: Option<&str>,
+    ) -> Option<SensitiveAction> {
+        let rule = first_match(&self.text_patterns, text)
+            .or_else(|| first_match(&self.url_patterns, url))?;
+
+        Some(SensitiveAction {
+            tool: tool.to_string(),
+            text: text.map(String::from),
+            url: url.map(String::from),
+            rule: rule.as_str().to_string(),
+        })
+    }
+
+    /// Ask for approval before any call of a tool listed in `tools`
+    ///
+    /// Checked once per call, before the tool runs, so it covers every tool
+    /// whether or not it looks at elements or URLs.
+    pub fn confirm_tool(&self, tool: &str) -> Result<()> {
+        match self.sensitive_tool(tool) {
+            Some(action) => self.ask(action),
+            None => Ok(()),
+        }
+    }
+
+    /// Ask for approval if the element text or URL an action targets matches a pattern
+    ///
+    /// Tools call this with what they are about to act on; the `tools` rule is
+    /// left to [`ConfirmationPolicy::confirm_tool`]. Returns
+    /// [`BrowserError::PolicyDenied`] if the handler declines the action or no
+    /// handler is installed.
+    pub fn confirm(&self, tool: &str, text: Option<&str>, url: Option<&str>) -> Result<()> {
+        match self.sensitive_target(tool, text, url) {
+            Some(action) => self.ask(action),
+            None => Ok(()),
+        }
+    }
+
+    fn ask(&self, action: SensitiveAction) -> Result<()> {
+        match &self.handler {
+            Some(handler) if handler(&action) => {
+                log::info!("Sensitive action approved: {}", action);
+                Ok(())
+            }
+            Some(_) => Err(BrowserError::PolicyDenied(format!(
+                "{} was declined by the user",
+                action
+            ))),
+            None => Err(BrowserError::PolicyDenied(format!(
+                "{} requires human confirmation (matched '{}')",
+                action, action.rule
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_matching_rules() {
+        let policy = ConfirmationPolicy::new()
+            .text_pattern("delete|pay|purchase")
+            .unwrap()
+            .url_pattern(r"checkout\.|paypal\.com")
+            .unwrap()
+            .tool("evaluate");
+
+        assert!(
+            policy
+                .sensitive_action("click", Some("Delete account"), None)
+                .is_some()
+        );
+        assert!(
+            policy
+                .sensitive_action("click", Some("Read more"), None)
+                .is_none()
+        );
+        assert!(
+            policy
+                .sensitive_action("navigate", None, Some("https://www.paypal.com/pay"))
+                .is_some()
+        );
+        assert_eq!(
+            policy
+                .sensitive_action("evaluate", None, None)
+                .unwrap()
+                .rule,
+            "tool:evaluate"
+        );
+        assert!(ConfirmationPolicy::new().text_pattern("(").is_err());
+    }
+
+    #[test]
+    fn test_confirm_without_handler_refuses() {
+        let policy = ConfirmationPolicy::new().text_pattern("pay").unwrap();
+        assert!(policy.confirm("click", Some("Continue"), None).is_ok());
+        assert!(matches!(
+            policy.confirm("click", Some("Pay now"), None),
+            Err(BrowserError::PolicyDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_confirm_calls_handler() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let policy = ConfirmationPolicy::new()
+            .text_pattern("delete")
+            .unwrap()
+            .handler(move |action| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                action.text.as_deref() == Some("Delete draft")
+            });
+
+        assert!(policy.confirm("click", Some("Delete draft"), None).is_ok());
+        assert!(
+            policy
+                .confirm("click", Some("Delete account"), None)
+                .is_err()
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_tool_rule_is_checked_per_call() {
+        let policy = ConfirmationPolicy::new()
+            .text_pattern("pay")
+            .unwrap()
+            .tool("evaluate");
+
+        assert!(matches!(
+            policy.confirm_tool("evaluate"),
+            Err(BrowserError::PolicyDenied(_))
+        ));
+        assert!(policy.confirm_tool("click").is_ok());
+        // Tools asking about their target only hit the text and URL rules
+        assert!(policy.confirm("evaluate", None, None).is_ok());
+        assert!(policy.confirm("press_key", Some("Pay now"), None).is_err());
+    }
+}
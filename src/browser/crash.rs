@@ -0,0 +1,103 @@
+//! Crashed tabs
+//!
+//! When a tab's renderer crashes or is killed for running out of memory, the
+//! tab stays in the tab list but every script and DOM call on it fails with
+//! an opaque DevTools error. [`CrashWatcher`] listens for
+//! `Inspector.targetCrashed` on the session's tabs, so the session can report
+//! [`BrowserError::PageCrashed`](crate::error::BrowserError::PageCrashed) or
+//! reload the tab instead.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::browser::tab::EventListener;
+use headless_chrome::protocol::cdp::Inspector;
+use headless_chrome::protocol::cdp::types::Event;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+type SyncListener = dyn EventListener<Event> + Send + Sync;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Tabs whose renderer crashed and has not been replaced since
+#[derive(Default)]
+pub struct CrashWatcher {
+    crashed: Arc<Mutex<BTreeSet<String>>>,
+    listeners: Mutex<Vec<(String, Weak<SyncListener>)>>,
+}
+
+impl CrashWatcher {
+    /// Create a watcher that is not attached to any tab yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching the tab for crashes; does nothing if already attached
+    pub fn attach(&self, tab: &Arc<Tab>) -> Result<()> {
+        let target_id = tab.get_target_id().clone();
+        let mut listeners = lock(&self.listeners);
+        listeners.retain(|(_, listener)| listener.strong_count() > 0);
+        if listeners.iter().any(|(id, _)| *id == target_id) {
+            return Ok(());
+        }
+
+        let chrome_error = |e: anyhow::Error| {
+            BrowserError::ChromeError(format!("Failed to watch for crashes: {}", e))
+        };
+        tab.call_method(Inspector::Enable(None))
+            .map_err(chrome_error)?;
+        let crashed = Arc::clone(&self.crashed);
+        let id = target_id.clone();
+        let listener = tab
+            .add_event_listener(Arc::new(move |event: &Event| match event {
+                Event::InspectorTargetCrashed(_) => {
+                    log::warn!("Tab {} crashed", id);
+                    lock(&crashed).insert(id.clone());
+                }
+                Event::InspectorTargetReloadedAfterCrash(_) => {
+                    lock(&crashed).remove(&id);
+                }
+                _ => {}
+            }))
+            .map_err(chrome_error)?;
+        listeners.push((target_id, listener));
+        Ok(())
+    }
+
+    /// Whether the tab with target id `id` crashed
+    pub fn is_crashed(&self, id: &str) -> bool {
+        lock(&self.crashed).contains(id)
+    }
+
+    /// Target ids of the crashed tabs
+    pub fn crashed(&self) -> Vec<String> {
+        lock(&self.crashed).iter().cloned().collect()
+    }
+
+    /// Forget a crash, once the tab was reloaded, navigated or closed
+    pub fn clear(&self, id: &str) {
+        lock(&self.crashed).remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crashed_tabs() {
+        let watcher = CrashWatcher::new();
+        assert!(watcher.crashed().is_empty());
+
+        lock(&watcher.crashed).insert("B".to_string());
+        lock(&watcher.crashed).insert("A".to_string());
+        assert!(watcher.is_crashed("A"));
+        assert!(!watcher.is_crashed("C"));
+        assert_eq!(watcher.crashed(), ["A", "B"]);
+
+        watcher.clear("A");
+        assert_eq!(watcher.crashed(), ["B"]);
+    }
+}
@@ -0,0 +1,238 @@
+//! Reproducible rendering for screenshots and visual diffs
+//!
+//! [`DeterministicOptions`] removes the usual sources of run-to-run noise:
+//!
+//! - CSS animations and transitions jump to their end state, and the caret is hidden
+//! - Chrome renders with a fixed color profile, scale factor and font hinting, without scrollbars
+//! - Pages see a fixed timezone and locale
+//! - Optionally, `Math.random` is seeded and `Date` is stopped at a fixed instant
+//!
+//! Enable it with [`LaunchOptions::deterministic`](crate::browser::LaunchOptions::deterministic)
+//! or [`LaunchOptions::determinism`](crate::browser::LaunchOptions::determinism).
+//! Settings are applied to tabs the session opens; popups a page opens itself
+//! only get the launch flags and the process timezone.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::protocol::cdp::{Emulation, Page};
+
+/// Chrome flags that make rasterization independent of the host
+const RENDER_ARGS: &[&str] = &[
+    "--force-color-profile=srgb",
+    "--force-device-scale-factor=1",
+    "--font-render-hinting=none",
+    "--disable-lcd-text",
+    "--disable-partial-raster",
+    "--disable-skia-runtime-opts",
+    "--hide-scrollbars",
+];
+
+/// Stylesheet that finishes animations and transitions immediately
+const DISABLE_ANIMATIONS_CSS: &str = "*, *::before, *::after { \
+    animation-duration: 0s !important; \
+    animation-delay: 0s !important; \
+    animation-iteration-count: 1 !important; \
+    transition-duration: 0s !important; \
+    transition-delay: 0s !important; \
+    caret-color: transparent !important; \
+    scroll-behavior: auto !important; }";
+
+/// Settings for reproducible rendering
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeterministicOptions {
+    /// IANA timezone pages run in (default: "UTC")
+    pub timezone: String,
+
+    /// Locale for `navigator.language` and `Intl` formatting (default: "en-US")
+    pub locale: String,
+
+    /// Seed `Math.random` and stop `Date` at `frozen_time_ms` (default: false)
+    pub freeze_js: bool,
+
+    /// Seed for `Math.random` when `freeze_js` is set (default: 1)
+    pub random_seed: u32,
+
+    /// Milliseconds since the epoch `Date` reports when `freeze_js` is set (default: 2024-01-01T00:00:00Z)
+    pub frozen_time_ms: i64,
+}
+
+impl Default for DeterministicOptions {
+    fn default() -> Self {
+        Self {
+            timezone: "UTC".to_string(),
+            locale: "en-US".to_string(),
+            freeze_js: false,
+            random_seed: 1,
+            frozen_time_ms: 1_704_067_200_000,
+        }
+    }
+}
+
+impl DeterministicOptions {
+    /// Create new DeterministicOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: set the timezone, e.g. "Europe/Berlin"
+    pub fn timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = timezone.into();
+        self
+    }
+
+    /// Builder method: set the locale, e.g. "de-DE"
+    pub fn locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = locale.into();
+        self
+    }
+
+    /// Builder method: seed `Math.random` and stop `Date`
+    ///
+    /// `Date` never advances while frozen, so pages that wait for time to
+    /// pass by polling `Date.now()` will wait forever. Timers still fire.
+    pub fn freeze_js(mut self, freeze: bool) -> Self {
+        self.freeze_js = freeze;
+        self
+    }
+
+    /// Builder method: set the seed for `Math.random`
+    pub fn random_seed(mut self, seed: u32) -> Self {
+        self.random_seed = seed;
+        self
+    }
+
+    /// Builder method: set the instant `Date` reports, in milliseconds since the epoch
+    pub fn frozen_time(mut self, time_ms: i64) -> Self {
+        self.frozen_time_ms = time_ms;
+        self
+    }
+
+    /// Chrome command-line flags
+    pub fn launch_args(&self) -> Vec<String> {
+        RENDER_ARGS
+            .iter()
+            .map(|arg| arg.to_string())
+            .chain(std::iter::once(format!("--lang={}", self.locale)))
+            .collect()
+    }
+
+    /// Script run in every new document before the page's own scripts
+    pub fn init_script(&self) -> String {
+        let mut script = format!(
+            r#"(() => {{
+    const style = document.createElement('style');
+    style.textContent = {css};
+    const attach = () => (document.head || document.documentElement).appendChild(style);
+    if (document.documentElement) {{
+        attach();
+    }} else {{
+        new MutationObserver((_, observer) => {{
+            if (document.documentElement) {{
+                observer.disconnect();
+                attach();
+            }}
+        }}).observe(document, {{ childList: true }});
+    }}
+"#,
+            css = serde_json::Value::from(DISABLE_ANIMATIONS_CSS),
+        );
+
+        if self.freeze_js {
+            // mulberry32, small and good enough for layout jitter
+            script.push_str(&format!(
+                r#"
+    let seed = {seed} >>> 0;
+    Math.random = () => {{
+        seed = (seed + 0x6D2B79F5) >>> 0;
+        let t = seed;
+        t = Math.imul(t ^ (t >>> 15), t | 1);
+        t ^= t + Math.imul(t ^ (t >>> 7), t | 61);
+        return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    }};
+
+    const NOW = {now};
+    const RealDate = Date;
+    function FrozenDate(...args) {{
+        if (!new.target) {{
+            return new RealDate(NOW).toString();
+        }}
+        return new RealDate(...(args.length ? args : [NOW]));
+    }}
+    FrozenDate.prototype = RealDate.prototype;
+    FrozenDate.now = () => NOW;
+    FrozenDate.parse = RealDate.parse;
+    FrozenDate.UTC = RealDate.UTC;
+    window.Date = FrozenDate;
+"#,
+                seed = self.random_seed,
+                now = self.frozen_time_ms,
+            ));
+        }
+
+        script.push_str("})();");
+        script
+    }
+
+    /// Apply the per-tab settings: timezone, locale, reduced motion and the init script
+    pub fn apply(&self, tab: &Tab) -> Result<()> {
+        tab.call_method(Emulation::SetTimezoneOverride {
+            timezone_id: self.timezone.clone(),
+        })
+        .map_err(|e| BrowserError::ChromeError(format!("Failed to set timezone: {}", e)))?;
+
+        tab.call_method(Emulation::SetLocaleOverride {
+            locale: Some(self.locale.clone()),
+        })
+        .map_err(|e| BrowserError::ChromeError(format!("Failed to set locale: {}", e)))?;
+
+        tab.call_method(Emulation::SetEmulatedMedia {
+            media: None,
+            features: Some(vec![Emulation::MediaFeature {
+                name: "prefers-reduced-motion".to_string(),
+                value: "reduce".to_string(),
+            }]),
+        })
+        .map_err(|e| BrowserError::ChromeError(format!("Failed to emulate media: {}", e)))?;
+
+        tab.call_method(Page::AddScriptToEvaluateOnNewDocument {
+            source: self.init_script(),
+            world_name: None,
+            include_command_line_api: None,
+            run_immediately: None,
+        })
+        .map_err(|e| {
+            BrowserError::ChromeError(format!("Failed to register deterministic script: {}", e))
+        })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_args() {
+        let args = DeterministicOptions::new().locale("de-DE").launch_args();
+        assert!(args.contains(&"--force-color-profile=srgb".to_string()));
+        assert!(args.contains(&"--hide-scrollbars".to_string()));
+        assert_eq!(args.last().map(String::as_str), Some("--lang=de-DE"));
+    }
+
+    #[test]
+    fn test_init_script() {
+        let script = DeterministicOptions::new().init_script();
+        assert!(script.contains("animation-duration: 0s !important"));
+        assert!(!script.contains("Math.random"));
+
+        let script = DeterministicOptions::new()
+            .freeze_js(true)
+            .random_seed(42)
+            .frozen_time(1000)
+            .init_script();
+        assert!(script.contains("let seed = 42 >>> 0"));
+        assert!(script.contains("const NOW = 1000;"));
+        assert!(script.ends_with("})();"));
+    }
+}
@@ -0,0 +1,283 @@
+//! What an action did to the page
+//!
+//! An [`EffectObserver`] is started just before an action such as a click and
+//! finished after it. Within a short window it notices a document navigation
+//! starting, a same-document URL change, DOM mutations, a JavaScript dialog
+//! opening and new tabs, so a tool can report whether the action did anything
+//! without the caller taking another snapshot to find out.
+
+use crate::browser::BrowserSession;
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::browser::tab::EventListener;
+use headless_chrome::protocol::cdp::Page::ClientNavigationDisposition;
+use headless_chrome::protocol::cdp::types::Event;
+use serde::Serialize;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+/// How often the observer checks for effects
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// One kind of change an action caused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionEffect {
+    /// The tab started loading a new document
+    Navigation,
+    /// The URL changed without a new document (history API or fragment)
+    UrlChange,
+    /// Elements, attributes or text of the document changed
+    DomMutation,
+    /// An alert, confirm, prompt or beforeunload dialog opened
+    Dialog,
+    /// A new tab or popup window opened
+    NewTab,
+}
+
+/// A JavaScript dialog the action opened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DialogInfo {
+    /// "alert", "confirm", "prompt" or "beforeunload"
+    pub kind: String,
+    /// Text shown in the dialog
+    pub message: String,
+}
+
+/// Everything an action was seen to do, in the order listed by [`ActionEffect`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ActionEffects {
+    /// Kinds of change seen; empty when nothing happened
+    pub effects: Vec<ActionEffect>,
+    /// URL being navigated to, or the new URL after a same-document change
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Dialog that opened; it stays open and blocks the page until handled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dialog: Option<DialogInfo>,
+    /// URLs of tabs that opened
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub new_tabs: Vec<String>,
+    /// Number of DOM mutation records observed
+    #[serde(skip_serializing_if = "is_zero")]
+    pub mutations: u64,
+    /// How long the observer watched, in milliseconds
+    pub observed_ms: u64,
+}
+
+fn is_zero(count: &u64) -> bool {
+    *count == 0
+}
+
+impl ActionEffects {
+    /// Whether the action visibly did nothing
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Short description such as "navigation, dom_mutation" or "none"
+    pub fn summary(&self) -> String {
+        if self.effects.is_empty() {
+            return "none".to_string();
+        }
+        self.effects
+            .iter()
+            .map(|effect| match effect {
+                ActionEffect::Navigation => "navigation",
+                ActionEffect::UrlChange => "url_change",
+                ActionEffect::DomMutation => "dom_mutation",
+                ActionEffect::Dialog => "dialog",
+                ActionEffect::NewTab => "new_tab",
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Page events seen while observing
+#[derive(Debug, Default)]
+struct Observed {
+    navigation: Option<String>,
+    navigation_started: bool,
+    same_document_url: Option<String>,
+    dialog: Option<DialogInfo>,
+}
+
+type SyncListener = dyn EventListener<Event> + Send + Sync;
+
+/// Watches a tab for the effects of an action
+pub struct EffectObserver<'a> {
+    session: &'a BrowserSession,
+    tab: Arc<Tab>,
+    observed: Arc<Mutex<Observed>>,
+    listener: Weak<SyncListener>,
+    tabs_before: Vec<String>,
+    mutations_before: Option<u64>,
+}
+
+impl<'a> EffectObserver<'a> {
+    /// Start watching the session's active tab
+    pub fn start(session: &'a BrowserSession) -> Result<Self> {
+        let tab = session.tab()?;
+        let tabs_before = session
+            .get_tabs()?
+            .iter()
+            .map(|tab| tab.get_target_id().clone())
+            .collect();
+        // Without a baseline mutations are not reported, but the rest still is
+        let mutations_before = session
+            .call_helper::<u64>("watchMutations", serde_json::json!({}))
+            .map_err(|e| log::debug!("Not watching DOM mutations: {}", e))
+            .ok();
+
+        let observed = Arc::new(Mutex::new(Observed::default()));
+        let frame_id = tab.get_target_id().clone();
+        let events = Arc::clone(&observed);
+        let listener = tab
+            .add_event_listener(Arc::new(move |event: &Event| {
+                let mut observed = events.lock().unwrap_or_else(|e| e.into_inner());
+                match event {
+                    Event::PageFrameRequestedNavigation(event)
+                        if event.params.frame_id == frame_id
+                            && event.params.disposition
+                                == ClientNavigationDisposition::CurrentTab =>
+                    {
+                        observed.navigation = Some(event.params.url.clone());
+                    }
+                    Event::PageFrameStartedLoading(event) if event.params.frame_id == frame_id => {
+                        observed.navigation_started = true;
+                    }
+                    Event::PageNavigatedWithinDocument(event)
+                        if event.params.frame_id == frame_id =>
+                    {
+                        observed.same_document_url = Some(event.params.url.clone());
+                    }
+                    Event::PageJavascriptDialogOpening(event) => {
+                        let kind = serde_json::to_value(&event.params.Type)
+                            .ok()
+                            .and_then(|kind| kind.as_str().map(String::from))
+                            .unwrap_or_default();
+                        observed.dialog = Some(DialogInfo {
+                            kind,
+                            message: event.params.message.clone(),
+                        });
+                    }
+                    _ => {}
+                }
+            }))
+            .map_err(|e| {
+                BrowserError::ChromeError(format!("Failed to watch for action effects: {}", e))
+            })?;
+
+        Ok(Self {
+            session,
+            tab,
+            observed,
+            listener,
+            tabs_before,
+            mutations_before,
+        })
+    }
+
+    /// Watch for up to `window` and report what happened
+    ///
+    /// Returns early once a navigation, dialog or new tab is seen, since the
+    /// page the action ran on is then gone or blocked.
+    pub fn finish(self, window: Duration) -> ActionEffects {
+        let start = Instant::now();
+        let new_tabs = loop {
+            let new_tabs = self.new_tabs();
+            let decisive = {
+                let observed = self.observed();
+                observed.navigation.is_some()
+                    || observed.navigation_started
+                    || observed.dialog.is_some()
+            };
+            if decisive || !new_tabs.is_empty() || start.elapsed() >= window {
+                break new_tabs;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+        self.tab.remove_event_listener(&self.listener).ok();
+
+        let observed = std::mem::take(&mut *self.observed());
+        let navigated = observed.navigation.is_some() || observed.navigation_started;
+        // A blocked or replaced document cannot be asked about its mutations
+        let mutations = match self.mutations_before {
+            Some(before) if !navigated && observed.dialog.is_none() => self
+                .session
+                .call_helper::<u64>("watchMutations", serde_json::json!({}))
+                .map(|after| after.saturating_sub(before))
+                .unwrap_or(0),
+            _ => 0,
+        };
+
+        let mut effects = ActionEffects {
+            observed_ms: start.elapsed().as_millis() as u64,
+            ..ActionEffects::default()
+        };
+        if navigated {
+            effects.effects.push(ActionEffect::Navigation);
+            effects.url = observed.navigation;
+        } else if let Some(url) = observed.same_document_url {
+            effects.effects.push(ActionEffect::UrlChange);
+            effects.url = Some(url);
+        }
+        if mutations > 0 {
+            effects.effects.push(ActionEffect::DomMutation);
+            effects.mutations = mutations;
+        }
+        if observed.dialog.is_some() {
+            effects.effects.push(ActionEffect::Dialog);
+            effects.dialog = observed.dialog;
+        }
+        if !new_tabs.is_empty() {
+            effects.effects.push(ActionEffect::NewTab);
+            effects.new_tabs = new_tabs;
+        }
+        effects
+    }
+
+    fn observed(&self) -> std::sync::MutexGuard<'_, Observed> {
+        self.observed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// URLs of tabs that were not open when observing started
+    fn new_tabs(&self) -> Vec<String> {
+        self.session
+            .get_tabs()
+            .unwrap_or_default()
+            .iter()
+            .filter(|tab| !self.tabs_before.contains(tab.get_target_id()))
+            .map(|tab| tab.get_url())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(ActionEffects::default().summary(), "none");
+        assert!(ActionEffects::default().is_empty());
+
+        let effects = ActionEffects {
+            effects: vec![ActionEffect::Navigation, ActionEffect::NewTab],
+            url: Some("https://example.com/next".to_string()),
+            new_tabs: vec!["about:blank".to_string()],
+            observed_ms: 120,
+            ..ActionEffects::default()
+        };
+        assert_eq!(effects.summary(), "navigation, new_tab");
+
+        let json = serde_json::to_value(&effects).unwrap();
+        assert_eq!(
+            json["effects"],
+            serde_json::json!(["navigation", "new_tab"])
+        );
+        assert!(json.get("dialog").is_none());
+        assert!(json.get("mutations").is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//! Encryption at rest for persisted session state
+//!
+//! Files are encrypted with AES-256-GCM using a key derived from a user-supplied
+//! passphrase (PBKDF2-HMAC-SHA256 with a random per-file salt). Layout:
+//!
+//! ```text
+//! magic (8 bytes) | salt (16 bytes) | nonce (12 bytes) | ciphertext + tag
+//! ```
+
+use crate::error::{BrowserError, Result};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use sha2::Sha256;
+
+/// Environment variable the state tools read the encryption passphrase from
+pub const STATE_KEY_ENV: &str = "BROWSER_USE_STATE_KEY";
+
+const MAGIC: &[u8; 8] = b"BUSTATE1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+#[cfg(not(test))]
+const PBKDF2_ROUNDS: u32 = 600_000;
+#[cfg(test)]
+const PBKDF2_ROUNDS: u32 = 1_000;
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Key<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    key.into()
+}
+
+/// Check whether a byte buffer was produced by [`encrypt`]
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Encrypt plaintext with a passphrase
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    if passphrase.is_empty() {
+        return Err(BrowserError::InvalidArgument(
+            "Encryption passphrase must not be empty".to_string(),
+        ));
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, &salt));
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| BrowserError::EncryptionFailed(e.to_string()))?;
+
+    let mut output = Vec::with_capacity(MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    output.extend_from_slice(MAGIC);
+    output.extend_from_slice(&salt);
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Decrypt data produced by [`encrypt`]
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let header_len = MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if !is_encrypted(data) || data.len() < header_len {
+        return Err(BrowserError::EncryptionFailed(
+            "Data is not an encrypted browser-use state file".to_string(),
+        ));
+    }
+
+    let salt = &data[MAGIC.len()..MAGIC.len() + SALT_LEN];
+    let nonce = Nonce::from_slice(&data[MAGIC.len() + SALT_LEN..header_len]);
+
+    let cipher = Aes256Gcm::new(&derive_key(passphrase, salt));
+    cipher.decrypt(nonce, &data[header_len..]).map_err(|_| {
+        BrowserError::EncryptionFailed("Wrong passphrase or corrupted state file".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let encrypted = encrypt(b"{\"cookies\":[]}", "secret").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert_eq!(decrypt(&encrypted, "secret").unwrap(), b"{\"cookies\":[]}");
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let encrypted = encrypt(b"data", "secret").unwrap();
+        assert!(matches!(
+            decrypt(&encrypted, "other"),
+            Err(BrowserError::EncryptionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_plaintext_is_rejected() {
+        assert!(!is_encrypted(b"{}"));
+        assert!(decrypt(b"{}", "secret").is_err());
+    }
+}
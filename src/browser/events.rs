@@ -0,0 +1,290 @@
+//! Problems reported by pages as they happen
+//!
+//! [`PageEventWatcher`] listens to a tab's console, uncaught exceptions and
+//! network activity and hands console errors, page errors and failed
+//! requests to callbacks, so a supervisor can react to a broken page without
+//! polling it.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::browser::tab::EventListener;
+use headless_chrome::protocol::cdp::Network;
+use headless_chrome::protocol::cdp::Runtime::{ConsoleAPICalledEventTypeOption, RemoteObject};
+use headless_chrome::protocol::cdp::types::Event;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+/// Requests whose URL is remembered in case they fail; older ones are forgotten
+const MAX_PENDING_REQUESTS: usize = 1000;
+
+/// A problem reported by a page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PageEvent {
+    /// `console.error` or a failed `console.assert`
+    #[serde(rename_all = "camelCase")]
+    ConsoleError {
+        /// Logged message, arguments joined with spaces
+        message: String,
+    },
+    /// Uncaught exception or unhandled promise rejection
+    #[serde(rename_all = "camelCase")]
+    PageError {
+        /// Exception message
+        message: String,
+        /// Script the exception was thrown in, if known
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+    },
+    /// Request that failed at the network level or answered with an HTTP error
+    #[serde(rename_all = "camelCase")]
+    RequestFailed {
+        /// Request URL
+        url: String,
+        /// HTTP status, for error responses
+        #[serde(skip_serializing_if = "Option::is_none")]
+        status: Option<u32>,
+        /// Network error, e.g. "net::ERR_NAME_NOT_RESOLVED"
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+}
+
+/// Callback run for every page event
+pub type PageEventCallback = dyn Fn(&PageEvent) + Send + Sync;
+
+type SyncListener = dyn EventListener<Event> + Send + Sync;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Text of a console argument, as the DevTools console would show it
+fn console_text(arg: &RemoteObject) -> String {
+    match &arg.value {
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+        None => arg
+            .description
+            .clone()
+            .or_else(|| arg.unserializable_value.clone())
+            .unwrap_or_default(),
+    }
+}
+
+/// Turn a CDP event into a page event; `pending` maps request ids to URLs
+fn page_event(event: &Event, pending: &mut HashMap<String, String>) -> Option<PageEvent> {
+    match event {
+        Event::RuntimeConsoleAPICalled(event) => match event.params.Type {
+            ConsoleAPICalledEventTypeOption::Error | ConsoleAPICalledEventTypeOption::Assert => {
+                let message = event
+                    .params
+                    .args
+                    .iter()
+                    .map(console_text)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                Some(PageEvent::ConsoleError { message })
+            }
+            _ => None,
+        },
+        Event::RuntimeExceptionThrown(event) => {
+            let details = &event.params.exception_details;
+            let message = details
+                .exception
+                .as_ref()
+                .and_then(|exception| exception.description.clone())
+                .unwrap_or_else(|| details.text.clone());
+            Some(PageEvent::PageError {
+                message,
+                url: details.url.clone(),
+            })
+        }
+        Event::NetworkRequestWillBeSent(event) => {
+            if pending.len() >= MAX_PENDING_REQUESTS {
+                pending.clear();
+            }
+            pending.insert(
+                event.params.request_id.clone(),
+                event.params.request.url.clone(),
+            );
+            None
+        }
+        Event::NetworkResponseReceived(event) => {
+            let response = &event.params.response;
+            (response.status >= 400).then(|| PageEvent::RequestFailed {
+                url: response.url.clone(),
+                status: Some(response.status),
+                error: None,
+            })
+        }
+        Event::NetworkLoadingFinished(event) => {
+            pending.remove(&event.params.request_id);
+            None
+        }
+        Event::NetworkLoadingFailed(event) => {
+            let url = pending.remove(&event.params.request_id)?;
+            // Requests cancelled by the page (navigation, aborted fetch) are not failures
+            if event.params.canceled == Some(true) {
+                return None;
+            }
+            Some(PageEvent::RequestFailed {
+                url,
+                status: None,
+                error: Some(event.params.error_text.clone()),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Reports console errors, page errors and failed requests of the tabs it is attached to
+#[derive(Default)]
+pub struct PageEventWatcher {
+    callbacks: Arc<Mutex<Vec<Arc<PageEventCallback>>>>,
+    listeners: Mutex<Vec<(String, Weak<SyncListener>)>>,
+}
+
+impl PageEventWatcher {
+    /// Create a watcher that is not attached to any tab yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the watcher is attached to any tab
+    pub fn is_active(&self) -> bool {
+        lock(&self.listeners)
+            .iter()
+            .any(|(_, listener)| listener.strong_count() > 0)
+    }
+
+    /// Start reporting the tab's problems; does nothing if already attached
+    pub fn attach(&self, tab: &Arc<Tab>) -> Result<()> {
+        let target_id = tab.get_target_id().clone();
+        let mut listeners = lock(&self.listeners);
+        listeners.retain(|(_, listener)| listener.strong_count() > 0);
+        if listeners.iter().any(|(id, _)| *id == target_id) {
+            return Ok(());
+        }
+
+        let chrome_error = |e: anyhow::Error| {
+            BrowserError::ChromeError(format!("Failed to watch page events: {}", e))
+        };
+        tab.enable_runtime().map_err(chrome_error)?;
+        tab.call_method(Network::Enable {
+            max_total_buffer_size: None,
+            max_resource_buffer_size: None,
+            max_post_data_size: None,
+            report_direct_socket_traffic: None,
+            enable_durable_messages: None,
+        })
+        .map_err(chrome_error)?;
+
+        let callbacks = Arc::clone(&self.callbacks);
+        let pending = Mutex::new(HashMap::new());
+        let listener = tab
+            .add_event_listener(Arc::new(move |event: &Event| {
+                let Some(event) = page_event(event, &mut lock(&pending)) else {
+                    return;
+                };
+                let callbacks = lock(&callbacks).clone();
+                for callback in callbacks {
+                    callback(&event);
+                }
+            }))
+            .map_err(chrome_error)?;
+        listeners.push((target_id, listener));
+
+        Ok(())
+    }
+
+    /// Run `callback` for every page event from now on
+    pub fn on_event(&self, callback: impl Fn(&PageEvent) + Send + Sync + 'static) {
+        lock(&self.callbacks).push(Arc::new(callback));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(method: &str, params: serde_json::Value) -> Event {
+        serde_json::from_value(serde_json::json!({ "method": method, "params": params })).unwrap()
+    }
+
+    #[test]
+    fn test_console_error() {
+        let mut pending = HashMap::new();
+        let error = event(
+            "Runtime.consoleAPICalled",
+            serde_json::json!({
+                "type": "error",
+                "args": [{ "type": "string", "value": "Failed to load" }, { "type": "number", "value": 3 }],
+                "executionContextId": 1,
+                "timestamp": 0.0
+            }),
+        );
+        assert_eq!(
+            page_event(&error, &mut pending),
+            Some(PageEvent::ConsoleError {
+                message: "Failed to load 3".to_string()
+            })
+        );
+
+        let log = event(
+            "Runtime.consoleAPICalled",
+            serde_json::json!({
+                "type": "log",
+                "args": [{ "type": "string", "value": "hello" }],
+                "executionContextId": 1,
+                "timestamp": 0.0
+            }),
+        );
+        assert_eq!(page_event(&log, &mut pending), None);
+    }
+
+    #[test]
+    fn test_failed_request() {
+        let mut pending = HashMap::new();
+        let sent = event(
+            "Network.requestWillBeSent",
+            serde_json::json!({
+                "requestId": "7",
+                "loaderId": "1",
+                "documentURL": "https://example.com/",
+                "request": {
+                    "url": "https://api.example.com/items",
+                    "method": "GET",
+                    "headers": {},
+                    "initialPriority": "High",
+                    "referrerPolicy": "no-referrer"
+                },
+                "timestamp": 0.0,
+                "wallTime": 0.0,
+                "initiator": { "type": "script" },
+                "redirectHasExtraInfo": false
+            }),
+        );
+        assert_eq!(page_event(&sent, &mut pending), None);
+
+        let failed = event(
+            "Network.loadingFailed",
+            serde_json::json!({
+                "requestId": "7",
+                "timestamp": 0.0,
+                "type": "Fetch",
+                "errorText": "net::ERR_CONNECTION_REFUSED"
+            }),
+        );
+        assert_eq!(
+            page_event(&failed, &mut pending),
+            Some(PageEvent::RequestFailed {
+                url: "https://api.example.com/items".to_string(),
+                status: None,
+                error: Some("net::ERR_CONNECTION_REFUSED".to_string())
+            })
+        );
+        assert!(pending.is_empty());
+    }
+}
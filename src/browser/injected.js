@@ -0,0 +1,154 @@
+// Helpers for JS-backed tools, installed once per document in an isolated world.
+// Every function takes one JSON object of arguments; selectors are arrays of CSS
+// steps, where each step but the last matches an iframe or a shadow host.
+(function () {
+  "use strict";
+  if (globalThis.__browserUse) {
+    return;
+  }
+
+  class ElementNotFound extends Error {
+    constructor(message) {
+      super(message);
+      this.name = "ElementNotFound";
+    }
+  }
+
+  function query(steps) {
+    let scope = document;
+    for (let i = 0; i < steps.length; i++) {
+      const element = scope.querySelector(steps[i]);
+      if (!element) {
+        throw new ElementNotFound(
+          i === 0 ? `Element '${steps[i]}' not found` : `Element '${steps[i]}' not found inside '${steps[i - 1]}'`
+        );
+      }
+      if (i === steps.length - 1) {
+        return element;
+      }
+      if (element.tagName === "IFRAME" || element.tagName === "FRAME") {
+        let inner = null;
+        try {
+          inner = element.contentDocument;
+        } catch (e) {}
+        if (!inner) {
+          throw new Error(`Cannot enter frame '${steps[i]}': it is cross-origin or not loaded`);
+        }
+        scope = inner;
+      } else if (element.shadowRoot) {
+        scope = element.shadowRoot;
+      } else {
+        throw new Error(`'${steps[i]}' is neither a frame nor a host with an open shadow root`);
+      }
+    }
+    throw new Error("Empty selector");
+  }
+
+  function box(element) {
+    const rect = element.getBoundingClientRect();
+    const style = element.ownerDocument.defaultView.getComputedStyle(element);
+    return {
+      x: rect.left,
+      y: rect.top,
+      width: rect.width,
+      height: rect.height,
+      visible: rect.width > 0 && rect.height > 0 && style.visibility !== "hidden" && style.display !== "none",
+    };
+  }
+
+  function scrollIntoView(element) {
+    element.scrollIntoView({ behavior: "auto", block: "center", inline: "center" });
+  }
+
+  let mutations = 0;
+  let mutationObserver = null;
+
+  const helpers = {
+    hover({ selector }) {
+      const element = query(selector);
+      scrollIntoView(element);
+
+      const rect = element.getBoundingClientRect();
+      element.dispatchEvent(
+        new MouseEvent("mouseover", {
+          view: element.ownerDocument.defaultView,
+          bubbles: true,
+          cancelable: true,
+          clientX: rect.left + rect.width / 2,
+          clientY: rect.top + rect.height / 2,
+        })
+      );
+
+      return {
+        tagName: element.tagName,
+        id: element.id,
+        className: typeof element.className === "string" ? element.className : "",
+      };
+    },
+
+    select({ selector, value }) {
+      const element = query(selector);
+      if (element.tagName !== "SELECT") {
+        throw new Error("Element is not a SELECT element");
+      }
+
+      element.value = value;
+      element.dispatchEvent(new Event("change", { bubbles: true }));
+
+      return {
+        selectedValue: element.value,
+        selectedText: element.options[element.selectedIndex]?.text ?? null,
+      };
+    },
+
+    scrollIntoView({ selector }) {
+      const element = query(selector);
+      scrollIntoView(element);
+      return box(element);
+    },
+
+    getBox({ selector }) {
+      return box(query(selector));
+    },
+
+    stableBox({ selector, frames, maxMs }) {
+      const element = query(selector);
+      const view = element.ownerDocument.defaultView;
+      const started = performance.now();
+      let last = box(element);
+      let still = 0;
+      return new Promise((resolve) => {
+        const step = () => {
+          if (!element.isConnected) {
+            resolve({ stable: false, box: null });
+            return;
+          }
+          const current = box(element);
+          const same = ["x", "y", "width", "height"].every((key) => current[key] === last[key]);
+          still = same ? still + 1 : 0;
+          last = current;
+          if (still >= frames) {
+            resolve({ stable: true, box: current });
+          } else if (performance.now() - started >= maxMs) {
+            resolve({ stable: false, box: current });
+          } else {
+            view.requestAnimationFrame(step);
+          }
+        };
+        view.requestAnimationFrame(step);
+      });
+    },
+
+    watchMutations() {
+      if (!mutationObserver) {
+        mutationObserver = new MutationObserver((records) => {
+          mutations += records.length;
+        });
+        mutationObserver.observe(document, { subtree: true, childList: true, attributes: true, characterData: true });
+      }
+      return mutations;
+    },
+  };
+
+  Object.defineProperty(globalThis, "__browserUse", { value: Object.freeze(helpers) });
+})();
@@ -0,0 +1,189 @@
+//! Helper script shared by JS-backed tools
+//!
+//! Instead of splicing arguments into a script per call, tools call named
+//! functions of one helper bundle (`hover`, `select`, `scrollIntoView`,
+//! `getBox`, `stableBox`, `watchMutations`) with JSON arguments; a helper may
+//! return a promise, which is awaited. The bundle is evaluated once per
+//! document in an isolated world, so page scripts can neither see nor tamper
+//! with it, and the world is reused until the tab loads a new document.
+//!
+//! Selector arguments are arrays of CSS steps as returned by
+//! [`DeepSelector::steps`](crate::dom::DeepSelector::steps), so the helpers
+//! reach into iframes and shadow roots like the other selector-based tools.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::protocol::cdp::{Page, Runtime};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// Name of the isolated world the helpers live in
+const WORLD_NAME: &str = "browser_use";
+
+const INJECTED_JS: &str = include_str!("injected.js");
+
+/// Calls a helper and reports its result or error without throwing
+const DISPATCH_JS: &str = r#"async function (name, args) {
+    try {
+        return { ok: (await globalThis.__browserUse[name](args)) ?? null };
+    } catch (e) {
+        return { error: String(e && e.message || e), notFound: !!e && e.name === "ElementNotFound" };
+    }
+}"#;
+
+/// Helper world of the document a tab currently shows
+#[derive(Debug, Clone)]
+struct HelperWorld {
+    loader_id: String,
+    context_id: Runtime::ExecutionContextId,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn chrome_error(e: anyhow::Error) -> BrowserError {
+    BrowserError::ChromeError(format!("Failed to run page helper: {}", e))
+}
+
+/// Installs the helper bundle into documents and calls its functions
+#[derive(Default)]
+pub struct InjectedHelpers {
+    /// Helper world per tab, keyed by target id
+    worlds: Mutex<HashMap<String, HelperWorld>>,
+}
+
+impl InjectedHelpers {
+    /// Create an empty set of helper worlds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call helper `function` with `args` in the tab's main frame
+    ///
+    /// Fails with [`BrowserError::ElementNotFound`] when the helper's selector
+    /// matches nothing, and with [`BrowserError::EvaluationFailed`] for any
+    /// other error the helper raises.
+    pub fn call<T: DeserializeOwned>(
+        &self,
+        tab: &Tab,
+        function: &str,
+        args: serde_json::Value,
+    ) -> Result<T> {
+        let world = self.world(tab)?;
+        let response = match self.dispatch(tab, &world, function, &args) {
+            Ok(response) => response,
+            // The document may have been replaced since the world was looked up
+            Err(_) => {
+                lock(&self.worlds).remove(tab.get_target_id());
+                let world = self.world(tab)?;
+                self.dispatch(tab, &world, function, &args)?
+            }
+        };
+
+        if let Some(error) = response.get("error").and_then(|error| error.as_str()) {
+            return Err(if response["notFound"].as_bool() == Some(true) {
+                BrowserError::ElementNotFound(error.to_string())
+            } else {
+                BrowserError::EvaluationFailed(format!("{}: {}", function, error))
+            });
+        }
+        serde_json::from_value(response["ok"].clone()).map_err(|e| {
+            BrowserError::EvaluationFailed(format!("Unexpected result from {}: {}", function, e))
+        })
+    }
+
+    /// Helper world of the tab's current document, installing the bundle if needed
+    fn world(&self, tab: &Tab) -> Result<HelperWorld> {
+        let frame = tab
+            .call_method(Page::GetFrameTree(None))
+            .map_err(chrome_error)?
+            .frame_tree
+            .frame;
+
+        let target_id = tab.get_target_id();
+        if let Some(world) = lock(&self.worlds).get(target_id)
+            && world.loader_id == frame.loader_id
+        {
+            return Ok(world.clone());
+        }
+
+        let context_id = tab
+            .call_method(Page::CreateIsolatedWorld {
+                frame_id: frame.id,
+                world_name: Some(WORLD_NAME.to_string()),
+                grant_univeral_access: None,
+            })
+            .map_err(chrome_error)?
+            .execution_context_id;
+        let installed = tab
+            .call_method(Runtime::Evaluate {
+                expression: INJECTED_JS.to_string(),
+                object_group: None,
+                include_command_line_api: None,
+                silent: Some(true),
+                context_id: Some(context_id),
+                return_by_value: Some(true),
+                generate_preview: None,
+                user_gesture: None,
+                await_promise: None,
+                throw_on_side_effect: None,
+                timeout: None,
+                disable_breaks: None,
+                repl_mode: None,
+                allow_unsafe_eval_blocked_by_csp: None,
+                unique_context_id: None,
+                serialization_options: None,
+            })
+            .map_err(chrome_error)?;
+        if let Some(details) = installed.exception_details {
+            return Err(BrowserError::EvaluationFailed(format!(
+                "Failed to install page helpers: {}",
+                details.text
+            )));
+        }
+
+        let world = HelperWorld {
+            loader_id: frame.loader_id,
+            context_id,
+        };
+        lock(&self.worlds).insert(target_id.clone(), world.clone());
+        Ok(world)
+    }
+
+    fn dispatch(
+        &self,
+        tab: &Tab,
+        world: &HelperWorld,
+        function: &str,
+        args: &serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let argument = |value: serde_json::Value| Runtime::CallArgument {
+            value: Some(value),
+            unserializable_value: None,
+            object_id: None,
+        };
+        let response = tab
+            .call_method(Runtime::CallFunctionOn {
+                function_declaration: DISPATCH_JS.to_string(),
+                object_id: None,
+                arguments: Some(vec![argument(function.into()), argument(args.clone())]),
+                silent: Some(true),
+                return_by_value: Some(true),
+                generate_preview: None,
+                user_gesture: Some(true),
+                await_promise: Some(true),
+                execution_context_id: Some(world.context_id),
+                object_group: None,
+                throw_on_side_effect: None,
+                unique_context_id: None,
+                serialization_options: None,
+            })
+            .map_err(chrome_error)?;
+        if let Some(details) = response.exception_details {
+            return Err(BrowserError::EvaluationFailed(details.text));
+        }
+        Ok(response.result.value.unwrap_or_default())
+    }
+}
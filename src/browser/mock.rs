@@ -0,0 +1,275 @@
+//! Scripted stand-in for Chrome, for unit-testing tools
+//!
+//! A [`MockSession`] serves a canned [`DomTree`] and records what tools do
+//! with it, so a tool's parameter handling, index resolution, policies and
+//! results can be tested without launching a browser:
+//!
+//! ```
+//! use browser_use::browser::{MockAction, MockSession};
+//! use browser_use::dom::{AriaChild, AriaNode, DomTree};
+//!
+//! let mut tree = DomTree::new(AriaNode::fragment().with_child(AriaChild::Node(Box::new(
+//!     AriaNode::new("button", "Sign in").with_index(0),
+//! ))));
+//! tree.selectors = vec!["#sign-in".to_string()];
+//!
+//! let mock = MockSession::new(tree).on_click("#sign-in", |page| {
+//!     page.url = "https://example.com/account".to_string();
+//! });
+//! let session = mock.session();
+//! session
+//!     .execute_tool("click", serde_json::json!({ "index": 0 }))
+//!     .unwrap();
+//!
+//! assert_eq!(mock.actions(), vec![MockAction::Click("#sign-in".to_string())]);
+//! assert_eq!(mock.url(), "https://example.com/account");
+//! ```
+//!
+//! DOM extraction, navigation and the element actions of the `click` and
+//! `input` tools are mocked. Tools that drive a tab directly (screenshots,
+//! JavaScript evaluation, tab management) fail with
+//! [`BrowserError::TabOperationFailed`].
+
+use crate::browser::{BrowserSession, LaunchOptions};
+use crate::dom::DomTree;
+use crate::dom::suggest;
+use crate::error::{BrowserError, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Something a tool did to a mocked page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockAction {
+    Navigate(String),
+    Click(String),
+    /// Clearing a field before typing into it
+    Clear(String),
+    Type {
+        selector: String,
+        text: String,
+    },
+}
+
+/// Page state scripted behaviors may change
+#[derive(Debug, Clone)]
+pub struct MockPage {
+    /// Tree returned by the next DOM extraction
+    pub dom: DomTree,
+    /// URL of the page
+    pub url: String,
+}
+
+type Behavior = Box<dyn FnMut(&mut MockPage) + Send>;
+
+struct MockState {
+    page: MockPage,
+    actions: Vec<MockAction>,
+    on_click: HashMap<String, Behavior>,
+}
+
+/// A scripted page that [`BrowserSession`]s can run against
+///
+/// Clones share state, so a test keeps one to inspect what the tools did.
+/// Elements exist if their selector is in the tree's `selectors`.
+#[derive(Clone)]
+pub struct MockSession {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockSession {
+    /// Serve `dom` at `about:blank`
+    pub fn new(dom: DomTree) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(MockState {
+                page: MockPage {
+                    dom,
+                    url: "about:blank".to_string(),
+                },
+                actions: Vec::new(),
+                on_click: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Start at `url`
+    pub fn with_url(self, url: impl Into<String>) -> Self {
+        self.lock().page.url = url.into();
+        self
+    }
+
+    /// Run `behavior` whenever the element with `selector` is clicked
+    pub fn on_click(
+        self,
+        selector: impl Into<String>,
+        behavior: impl FnMut(&mut MockPage) + Send + 'static,
+    ) -> Self {
+        self.lock()
+            .on_click
+            .insert(selector.into(), Box::new(behavior));
+        self
+    }
+
+    /// A session with default options running against this page
+    pub fn session(&self) -> BrowserSession {
+        self.session_with(LaunchOptions::default())
+    }
+
+    /// A session with the policies of `options` (scope, confirmation, output
+    /// directory, ...) running against this page
+    pub fn session_with(&self, options: LaunchOptions) -> BrowserSession {
+        BrowserSession::from_mock(self.clone(), options)
+    }
+
+    /// Actions performed so far, oldest first
+    pub fn actions(&self) -> Vec<MockAction> {
+        self.lock().actions.clone()
+    }
+
+    /// Current URL
+    pub fn url(&self) -> String {
+        self.lock().page.url.clone()
+    }
+
+    /// Replace the tree returned by the next DOM extraction
+    pub fn set_dom(&self, dom: DomTree) {
+        self.lock().page.dom = dom;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn dom(&self) -> DomTree {
+        self.lock().page.dom.clone()
+    }
+
+    /// Accessible name of the element with `selector`, failing like Chrome if there is none
+    pub(crate) fn element_label(&self, selector: &str) -> Result<String> {
+        let state = self.lock();
+        let dom = &state.page.dom;
+        let Some(index) = dom.selectors.iter().position(|s| s == selector) else {
+            return Err(
+                BrowserError::ElementNotFound(format!("Element '{}' not found", selector))
+                    .with_selector(selector)
+                    .with_suggestions(suggest::suggest_for_selector(dom, selector)),
+            );
+        };
+        Ok(dom
+            .find_node_by_index(index)
+            .map(|node| node.name.clone())
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn click(&self, selector: &str) -> Result<()> {
+        self.element_label(selector)?;
+        let mut state = self.lock();
+        state.actions.push(MockAction::Click(selector.to_string()));
+        let MockState { page, on_click, .. } = &mut *state;
+        if let Some(behavior) = on_click.get_mut(selector) {
+            behavior(page);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn type_text(&self, selector: &str, text: &str, clear: bool) -> Result<()> {
+        self.element_label(selector)?;
+        let mut state = self.lock();
+        if clear {
+            state.actions.push(MockAction::Clear(selector.to_string()));
+        }
+        state.actions.push(MockAction::Type {
+            selector: selector.to_string(),
+            text: text.to_string(),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn navigate(&self, url: &str) {
+        let mut state = self.lock();
+        state.actions.push(MockAction::Navigate(url.to_string()));
+        state.page.url = url.to_string();
+    }
+}
+
+impl std::fmt::Debug for MockSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("MockSession")
+            .field("page", &state.page)
+            .field("actions", &state.actions)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::{ConnectionState, SessionRng};
+    use crate::dom::{AriaChild, AriaNode};
+    use crate::error::ErrorCode;
+
+    fn login_page() -> DomTree {
+        let mut tree = DomTree::new(
+            AriaNode::fragment()
+                .with_child(AriaChild::Node(Box::new(
+                    AriaNode::new("textbox", "Email").with_index(0),
+                )))
+                .with_child(AriaChild::Node(Box::new(
+                    AriaNode::new("button", "Sign in").with_index(1),
+                ))),
+        );
+        tree.selectors = vec!["#email".to_string(), "#sign-in".to_string()];
+        tree
+    }
+
+    #[test]
+    fn test_click_runs_behavior() {
+        let mock = MockSession::new(login_page()).on_click("#sign-in", |page| {
+            page.url = "https://example.com/home".to_string();
+            page.dom = DomTree::new(AriaNode::fragment());
+        });
+
+        mock.click("#sign-in").unwrap();
+        assert_eq!(mock.url(), "https://example.com/home");
+        assert_eq!(mock.dom().count_interactive(), 0);
+        assert_eq!(
+            mock.actions(),
+            vec![MockAction::Click("#sign-in".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_missing_element() {
+        let mock = MockSession::new(login_page());
+        let error = mock.click("#sign-up").unwrap_err();
+        assert_eq!(error.code(), ErrorCode::ElementNotFound);
+        assert_eq!(
+            error.context().and_then(|c| c.selector.as_deref()),
+            Some("#sign-up")
+        );
+        assert!(mock.actions().is_empty());
+        assert_eq!(mock.element_label("#sign-in").unwrap(), "Sign in");
+    }
+
+    #[test]
+    fn test_session_without_tabs() {
+        let mock = MockSession::new(login_page()).with_url("https://example.com/login");
+        let session = mock.session();
+        assert_eq!(session.current_url().unwrap(), "https://example.com/login");
+        session.check_connection().unwrap();
+        assert!(session.is_alive());
+        assert_eq!(session.session_info().websocket, ConnectionState::Mock);
+
+        let seeded = mock.session_with(LaunchOptions::new().rng_seed(5));
+        assert_eq!(seeded.rng().seed(), 5);
+        assert_eq!(seeded.rng().next_u64(), SessionRng::new(5).next_u64());
+        assert_eq!(session.extract_dom().unwrap().count_interactive(), 2);
+
+        session.navigate("https://example.com/other").unwrap();
+        session.wait_for_navigation().unwrap();
+        assert_eq!(mock.url(), "https://example.com/other");
+
+        let error = session.screenshot_bytes(&Default::default()).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::TabFailed);
+    }
+}
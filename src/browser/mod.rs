@@ -3,11 +3,64 @@
 //! This module provides functionality for launching and managing Chrome/Chromium browser instances.
 //! It includes configuration options, session management, and browser lifecycle control.
 
+pub mod bus;
+pub mod checkpoint;
 pub mod config;
+pub mod confirmation;
+pub mod crash;
+pub mod deterministic;
+pub mod effects;
+#[cfg(feature = "encryption")]
+pub mod encryption;
+pub mod events;
+pub mod injected;
+pub mod mock;
+pub mod navigation;
+pub mod network;
+pub mod output;
+pub mod policy;
+pub mod profile;
+pub mod proxy;
+pub mod rate_limit;
+pub mod rng;
+pub mod robots;
+pub mod routes;
+pub mod scope;
+pub mod screenshot;
+pub mod screenshot_job;
 pub mod session;
+pub mod status;
+pub mod storage_state;
+pub mod tabs;
+pub mod viewport;
 
+pub use bus::{SessionEvent, SessionEventBus, SessionEventKind, SessionEventKinds, SubscriptionId};
+pub use checkpoint::{Checkpoint, CheckpointStore};
 pub use config::{ConnectionOptions, LaunchOptions};
-pub use session::BrowserSession;
+pub use confirmation::{ConfirmationPolicy, SensitiveAction};
+pub use crash::CrashWatcher;
+pub use deterministic::DeterministicOptions;
+pub use effects::{ActionEffect, ActionEffects, EffectObserver};
+pub use events::{PageEvent, PageEventWatcher};
+pub use injected::InjectedHelpers;
+pub use mock::{MockAction, MockPage, MockSession};
+pub use navigation::{
+    LoadStatus, NavigateOptions, NavigationRecorder, NavigationResponse, PageLoad, Redirect,
+};
+pub use network::{CapturedRequest, NetworkCapture};
+pub use policy::{EvaluateAccess, EvaluatePolicy, LimitedResult, OversizeAction, Truncation};
+pub use proxy::{ProxyCredentials, ProxyOptions};
+pub use rate_limit::RateLimitOptions;
+pub use rng::SessionRng;
+pub use routes::{RouteChange, RouteWatcher};
+pub use scope::OriginScope;
+pub use screenshot::{ImageFormat, ScreenshotOptions};
+pub use screenshot_job::{ScreenshotJobOptions, ScreenshotJobResult};
+pub use session::{BrowserSession, PageGeneration};
+pub use status::{ConnectionState, SessionInfo, TargetSummary};
+pub use storage_state::StorageState;
+pub use tabs::{TabSummary, TabTracker};
+pub use viewport::Viewport;
 
 use crate::error::Result;
 
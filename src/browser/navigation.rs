@@ -0,0 +1,457 @@
+//! Main-document responses for navigations
+//!
+//! A [`NavigationRecorder`] listens to the tab's network events while a
+//! navigation runs and reports the main document's HTTP status, the final URL
+//! and every redirect on the way, so callers can tell a 404, a 403 or a
+//! redirect to a login page apart from a normal load. [`NavigateOptions`]
+//! carries per-navigation request extras such as headers, the referrer and a
+//! load timeout, and [`PageLoad`] follows a navigation started with them until
+//! it loads.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::browser::tab::EventListener;
+use headless_chrome::protocol::cdp::types::Event;
+use headless_chrome::protocol::cdp::{Network, Page};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+
+/// Default for how long to wait for a navigation to load, matching headless_chrome's tab timeout
+pub const LOAD_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// How far a navigation got before the wait for it ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoadStatus {
+    /// The document and its subresources finished loading
+    Complete,
+    /// The document was parsed and can be used, but the load did not finish
+    /// in time, e.g. a stalled image or a request that never ends
+    Interactive,
+}
+/// Request options for a single navigation
+#[derive(Debug, Clone, Default)]
+pub struct NavigateOptions {
+    /// Headers sent with the document request, on top of the session's extra headers
+    pub headers: HashMap<String, String>,
+
+    /// Referrer for the document request, also exposed as `document.referrer`
+    pub referrer: Option<String>,
+
+    /// How long to wait for the page to load; `None` uses the session's default
+    pub timeout: Option<Duration>,
+}
+
+impl NavigateOptions {
+    /// Create NavigateOptions with no extras
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: send a header with the document request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Builder method: navigate as if coming from this URL
+    pub fn referrer(mut self, referrer: impl Into<String>) -> Self {
+        self.referrer = Some(referrer.into());
+        self
+    }
+
+    /// Builder method: wait this long for the page to load
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// One redirect hop of a navigation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Redirect {
+    /// URL that answered with the redirect
+    pub url: String,
+    /// Redirect status (301, 302, 307, ...)
+    pub status: u32,
+}
+
+/// Response to a navigation's main document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NavigationResponse {
+    /// HTTP status of the final response
+    pub status: u32,
+    /// HTTP status text of the final response
+    pub status_text: String,
+    /// URL of the final response, after redirects
+    pub url: String,
+    /// MIME type of the final response
+    pub mime_type: String,
+    /// Redirects followed before the final response, in order
+    pub redirects: Vec<Redirect>,
+}
+
+impl NavigationResponse {
+    /// Whether the final response is an HTTP error (4xx or 5xx)
+    pub fn is_error(&self) -> bool {
+        self.status >= 400
+    }
+}
+
+/// Main-document requests and responses seen during a navigation
+#[derive(Debug, Default)]
+struct NavigationLog {
+    redirects: Vec<Redirect>,
+    response: Option<NavigationResponse>,
+}
+
+impl NavigationLog {
+    /// A main-document request started; `redirect` is set when it follows a redirect
+    fn on_request(&mut self, redirect: Option<Redirect>) {
+        match redirect {
+            Some(redirect) => self.redirects.push(redirect),
+            // A fresh navigation replaces anything recorded before it
+            None => self.redirects.clear(),
+        }
+        self.response = None;
+    }
+
+    /// The main document responded
+    fn on_response(&mut self, response: &Network::Response) {
+        self.response = Some(NavigationResponse {
+            status: response.status,
+            status_text: response.status_text.clone(),
+            url: response.url.clone(),
+            mime_type: response.mime_type.clone(),
+            redirects: self.redirects.clone(),
+        });
+    }
+}
+
+type SyncListener = dyn EventListener<Event> + Send + Sync;
+
+/// Records the main document's response while a navigation runs
+pub struct NavigationRecorder {
+    tab: Arc<Tab>,
+    log: Arc<Mutex<NavigationLog>>,
+    listener: Weak<SyncListener>,
+}
+
+impl NavigationRecorder {
+    /// Start listening on `tab`; the Network domain is enabled if it was not already
+    pub fn attach(tab: &Arc<Tab>) -> Result<Self> {
+        tab.call_method(Network::Enable {
+            max_total_buffer_size: None,
+            max_resource_buffer_size: None,
+            max_post_data_size: None,
+            report_direct_socket_traffic: None,
+            enable_durable_messages: None,
+        })
+        .map_err(|e| {
+            BrowserError::ChromeError(format!("Failed to enable network events: {}", e))
+        })?;
+
+        let log = Arc::new(Mutex::new(NavigationLog::default()));
+        let frame_id = tab.get_target_id().clone();
+        let events = Arc::clone(&log);
+        let listener = tab
+            .add_event_listener(Arc::new(move |event: &Event| {
+                let mut log = events.lock().unwrap_or_else(|e| e.into_inner());
+                match event {
+                    Event::NetworkRequestWillBeSent(event)
+                        if event.params.frame_id.as_ref() == Some(&frame_id)
+                            && event.params.Type == Some(Network::ResourceType::Document) =>
+                    {
+                        log.on_request(event.params.redirect_response.as_ref().map(|response| {
+                            Redirect {
+                                url: response.url.clone(),
+                                status: response.status,
+                            }
+                        }));
+                    }
+                    Event::NetworkResponseReceived(event)
+                        if event.params.frame_id.as_ref() == Some(&frame_id)
+                            && event.params.Type == Network::ResourceType::Document =>
+                    {
+                        log.on_response(&event.params.response);
+                    }
+                    _ => {}
+                }
+            }))
+            .map_err(|e| BrowserError::ChromeError(e.to_string()))?;
+
+        Ok(Self {
+            tab: Arc::clone(tab),
+            log,
+            listener,
+        })
+    }
+
+    /// Stop listening and return the main document's response, if one arrived
+    pub fn finish(self) -> Option<NavigationResponse> {
+        self.tab.remove_event_listener(&self.listener).ok();
+        let log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        log.response.clone()
+    }
+}
+
+/// Main-frame lifecycle events seen while a navigation loads
+#[derive(Debug, Default)]
+struct LoadState {
+    /// Loader of the most recent document to start loading
+    latest_loader: Option<String>,
+    /// Loaders whose documents were parsed
+    interactive: HashSet<String>,
+    /// Loaders whose documents finished loading
+    idle: HashSet<String>,
+}
+
+impl LoadState {
+    fn on_lifecycle(&mut self, loader_id: &str, name: &str) {
+        match name {
+            "init" => self.latest_loader = Some(loader_id.to_string()),
+            "DOMContentLoaded" => {
+                self.interactive.insert(loader_id.to_string());
+            }
+            // The same signal headless_chrome's wait_until_navigated uses
+            "networkAlmostIdle" => {
+                self.idle.insert(loader_id.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `loader_id`, or a document that replaced it, finished loading
+    fn is_loaded(&self, loader_id: &str) -> bool {
+        Self::reached(&self.idle, &self.latest_loader, loader_id)
+    }
+
+    /// Whether `loader_id`, or a document that replaced it, was parsed
+    fn is_interactive(&self, loader_id: &str) -> bool {
+        Self::reached(&self.interactive, &self.latest_loader, loader_id)
+    }
+
+    fn reached(loaders: &HashSet<String>, latest: &Option<String>, loader_id: &str) -> bool {
+        loaders.contains(loader_id)
+            || latest
+                .as_ref()
+                .is_some_and(|latest| latest != loader_id && loaders.contains(latest))
+    }
+}
+
+/// A navigation started with `Page.navigate`, followed until its document loads
+///
+/// headless_chrome only tracks navigations it starts itself through
+/// `Tab::navigate_to`, which cannot pass a referrer, so the session starts
+/// navigations here and waits on the tab's lifecycle events instead.
+pub struct PageLoad {
+    tab: Arc<Tab>,
+    loader_id: Option<String>,
+    state: Arc<Mutex<LoadState>>,
+    listener: Weak<SyncListener>,
+}
+
+impl PageLoad {
+    /// Start navigating `tab` to `url`
+    pub fn start(tab: &Arc<Tab>, url: &str, options: &NavigateOptions) -> Result<Self> {
+        let state = Arc::new(Mutex::new(LoadState::default()));
+        let frame_id = tab.get_target_id().clone();
+        let events = Arc::clone(&state);
+        let listener = tab
+            .add_event_listener(Arc::new(move |event: &Event| {
+                if let Event::PageLifecycleEvent(event) = event
+                    && event.params.frame_id == frame_id
+                {
+                    events
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .on_lifecycle(&event.params.loader_id, &event.params.name);
+                }
+            }))
+            .map_err(|e| BrowserError::ChromeError(e.to_string()))?;
+
+        let mut load = Self {
+            tab: Arc::clone(tab),
+            loader_id: None,
+            state,
+            listener,
+        };
+        let result = tab
+            .call_method(Page::Navigate {
+                url: url.to_string(),
+                referrer: options.referrer.clone(),
+                transition_Type: None,
+                frame_id: None,
+                referrer_policy: None,
+            })
+            .map_err(|e| {
+                BrowserError::NavigationFailed(format!("Failed to navigate to {}: {}", url, e))
+            })?;
+        if let Some(error) = result.error_text {
+            return Err(BrowserError::NavigationFailed(format!(
+                "Failed to navigate to {}: {}",
+                url, error
+            )));
+        }
+
+        load.loader_id = result.loader_id;
+        if load.is_same_document() {
+            scroll_to_fragment(tab, url);
+        }
+        Ok(load)
+    }
+
+    /// Whether the navigation stayed in the current document (e.g. `#section`),
+    /// in which case nothing is loaded and there is nothing to wait for
+    pub fn is_same_document(&self) -> bool {
+        self.loader_id.is_none()
+    }
+
+    /// Wait until the navigated document has loaded
+    ///
+    /// A document that was parsed but did not finish loading within `timeout`
+    /// counts as [`LoadStatus::Interactive`]; one that was not even parsed is
+    /// a [`BrowserError::NavigationTimeout`].
+    pub fn wait(&self, timeout: Duration) -> Result<LoadStatus> {
+        let Some(loader_id) = &self.loader_id else {
+            return Ok(LoadStatus::Complete);
+        };
+        let deadline = Instant::now() + timeout;
+        loop {
+            let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            if state.is_loaded(loader_id) {
+                return Ok(LoadStatus::Complete);
+            }
+            if Instant::now() >= deadline {
+                if state.is_interactive(loader_id) {
+                    return Ok(LoadStatus::Interactive);
+                }
+                return Err(BrowserError::NavigationTimeout(format!(
+                    "page did not load within {:?}",
+                    timeout
+                )));
+            }
+            drop(state);
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+/// Bring a same-document navigation's anchor into view
+///
+/// Chrome scrolls on fragment navigation only if the anchor already exists;
+/// pages that render it late are scrolled here. Best effort.
+fn scroll_to_fragment(tab: &Tab, url: &str) {
+    let Some(fragment) = url.split_once('#').map(|(_, fragment)| fragment) else {
+        return;
+    };
+    let js = format!(
+        r#"(function (fragment) {{
+            let id = fragment;
+            try {{ id = decodeURIComponent(fragment); }} catch (e) {{}}
+            const target = document.getElementById(id) || document.getElementsByName(id)[0];
+            if (target) {{
+                target.scrollIntoView();
+            }} else if (id === "" || id.toLowerCase() === "top") {{
+                window.scrollTo(0, 0);
+            }}
+        }})({})"#,
+        serde_json::Value::from(fragment)
+    );
+    if let Err(e) = tab.evaluate(&js, false) {
+        log::debug!("Failed to scroll to #{}: {}", fragment, e);
+    }
+}
+
+impl Drop for PageLoad {
+    fn drop(&mut self) {
+        self.tab.remove_event_listener(&self.listener).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(url: &str, status: u32) -> Network::Response {
+        serde_json::from_value(serde_json::json!({
+            "url": url,
+            "status": status,
+            "statusText": if status == 200 { "OK" } else { "Found" },
+            "headers": {},
+            "mimeType": "text/html",
+            "charset": "utf-8",
+            "connectionReused": false,
+            "connectionId": 1,
+            "encodedDataLength": 0,
+            "securityState": "secure"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_redirect_chain() {
+        let mut log = NavigationLog::default();
+        log.on_request(None);
+        log.on_request(Some(Redirect {
+            url: "http://example.com/account".to_string(),
+            status: 301,
+        }));
+        log.on_request(Some(Redirect {
+            url: "https://example.com/account".to_string(),
+            status: 302,
+        }));
+        log.on_response(&response("https://example.com/login", 200));
+
+        let result = log.response.unwrap();
+        assert_eq!(result.status, 200);
+        assert_eq!(result.url, "https://example.com/login");
+        assert_eq!(result.redirects.len(), 2);
+        assert_eq!(result.redirects[0].status, 301);
+        assert!(!result.is_error());
+    }
+
+    #[test]
+    fn test_new_navigation_resets_chain() {
+        let mut log = NavigationLog::default();
+        log.on_request(None);
+        log.on_request(Some(Redirect {
+            url: "https://a.example/".to_string(),
+            status: 302,
+        }));
+        log.on_request(None);
+        log.on_response(&response("https://b.example/missing", 404));
+
+        let result = log.response.unwrap();
+        assert!(result.redirects.is_empty());
+        assert!(result.is_error());
+    }
+
+    #[test]
+    fn test_load_state() {
+        let mut state = LoadState::default();
+        state.on_lifecycle("a", "init");
+        state.on_lifecycle("a", "DOMContentLoaded");
+        assert!(!state.is_loaded("a"));
+        state.on_lifecycle("a", "networkAlmostIdle");
+        assert!(state.is_loaded("a"));
+
+        // A client-side redirect replaces the document before it goes idle
+        let mut state = LoadState::default();
+        state.on_lifecycle("a", "init");
+        state.on_lifecycle("b", "init");
+        assert!(!state.is_loaded("a"));
+        state.on_lifecycle("b", "networkAlmostIdle");
+        assert!(state.is_loaded("a"));
+
+        // A parsed document whose subresources never finish
+        let mut state = LoadState::default();
+        state.on_lifecycle("a", "init");
+        assert!(!state.is_interactive("a"));
+        state.on_lifecycle("a", "DOMContentLoaded");
+        assert!(state.is_interactive("a"));
+        assert!(!state.is_loaded("a"));
+    }
+}
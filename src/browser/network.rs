@@ -0,0 +1,210 @@
+//! Capture of a session's XHR and fetch traffic
+//!
+//! [`NetworkCapture`] listens to the Network domain of the tabs it is attached
+//! to and keeps the most recent XHR/fetch requests in a bounded buffer, with
+//! their method, URL, request body and response status. Response bodies are
+//! left in Chrome and fetched on demand, so capture stays cheap until a tool
+//! actually looks at the traffic.
+
+use crate::error::{BrowserError, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use headless_chrome::Tab;
+use headless_chrome::browser::tab::EventListener;
+use headless_chrome::protocol::cdp::Network;
+use headless_chrome::protocol::cdp::types::Event;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+/// Number of requests kept when no capacity is given
+pub const DEFAULT_CAPTURE_CAPACITY: usize = 500;
+
+/// One XHR or fetch request seen by the capture
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedRequest {
+    /// CDP request id, used to fetch bodies
+    #[serde(skip)]
+    pub request_id: String,
+
+    /// Target id of the tab that made the request
+    #[serde(skip)]
+    pub target_id: String,
+
+    /// HTTP method
+    pub method: String,
+
+    /// Request URL
+    pub url: String,
+
+    /// "XHR" or "Fetch"
+    pub resource_type: String,
+
+    /// Request body, when Chrome included it in the event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<String>,
+
+    /// Whether the request had a body, even if it was too large to include
+    #[serde(skip)]
+    pub has_post_data: bool,
+
+    /// Response status, once the response arrived
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u32>,
+
+    /// Response MIME type, once the response arrived
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+type SyncListener = dyn EventListener<Event> + Send + Sync;
+
+/// Bounded log of XHR/fetch requests across the tabs it is attached to
+pub struct NetworkCapture {
+    entries: Arc<Mutex<VecDeque<CapturedRequest>>>,
+    capacity: usize,
+    listeners: Mutex<Vec<(String, Weak<SyncListener>)>>,
+}
+
+impl Default for NetworkCapture {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPTURE_CAPACITY)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl NetworkCapture {
+    /// Create a capture that keeps at most `capacity` requests
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::new())),
+            capacity: capacity.max(1),
+            listeners: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Whether the capture is attached to any tab
+    pub fn is_active(&self) -> bool {
+        !lock(&self.listeners).is_empty()
+    }
+
+    /// Start recording the tab's traffic; does nothing if already attached
+    pub fn attach(&self, tab: &Arc<Tab>) -> Result<()> {
+        let target_id = tab.get_target_id().clone();
+        let mut listeners = lock(&self.listeners);
+        listeners.retain(|(_, listener)| listener.strong_count() > 0);
+        if listeners.iter().any(|(id, _)| *id == target_id) {
+            return Ok(());
+        }
+
+        tab.call_method(Network::Enable {
+            max_total_buffer_size: None,
+            max_resource_buffer_size: None,
+            max_post_data_size: None,
+            report_direct_socket_traffic: None,
+            enable_durable_messages: None,
+        })
+        .map_err(|e| {
+            BrowserError::ChromeError(format!("Failed to enable network events: {}", e))
+        })?;
+
+        let entries = Arc::clone(&self.entries);
+        let capacity = self.capacity;
+        let tab_id = target_id.clone();
+        let listener = tab
+            .add_event_listener(Arc::new(move |event: &Event| match event {
+                Event::NetworkRequestWillBeSent(event) => {
+                    let params = &event.params;
+                    let resource_type = match params.Type {
+                        Some(Network::ResourceType::Xhr) => "XHR",
+                        Some(Network::ResourceType::Fetch) => "Fetch",
+                        _ => return,
+                    };
+                    let mut entries = lock(&entries);
+                    if entries.len() >= capacity {
+                        entries.pop_front();
+                    }
+                    entries.push_back(CapturedRequest {
+                        request_id: params.request_id.clone(),
+                        target_id: tab_id.clone(),
+                        method: params.request.method.clone(),
+                        url: params.request.url.clone(),
+                        resource_type: resource_type.to_string(),
+                        post_data: params.request.post_data.clone(),
+                        has_post_data: params.request.has_post_data.unwrap_or(false)
+                            || params.request.post_data.is_some(),
+                        status: None,
+                        mime_type: None,
+                    });
+                }
+                Event::NetworkResponseReceived(event) => {
+                    let params = &event.params;
+                    let mut entries = lock(&entries);
+                    if let Some(entry) = entries
+                        .iter_mut()
+                        .rev()
+                        .find(|entry| entry.request_id == params.request_id)
+                    {
+                        entry.status = Some(params.response.status);
+                        entry.mime_type = Some(params.response.mime_type.clone());
+                    }
+                }
+                _ => {}
+            }))
+            .map_err(|e| BrowserError::ChromeError(e.to_string()))?;
+
+        listeners.push((target_id, listener));
+        Ok(())
+    }
+
+    /// Requests recorded so far, oldest first
+    pub fn requests(&self) -> Vec<CapturedRequest> {
+        lock(&self.entries).iter().cloned().collect()
+    }
+
+    /// Forget all recorded requests
+    pub fn clear(&self) {
+        lock(&self.entries).clear();
+    }
+
+    /// Fetch a request's body from Chrome when the event did not carry it
+    pub fn request_body(tab: &Tab, request: &CapturedRequest) -> Option<String> {
+        if request.post_data.is_some() || !request.has_post_data {
+            return request.post_data.clone();
+        }
+        tab.call_method(Network::GetRequestPostData {
+            request_id: request.request_id.clone(),
+        })
+        .ok()
+        .map(|result| result.post_data)
+    }
+
+    /// Fetch a request's response body from Chrome
+    ///
+    /// Fails once Chrome has evicted the body, e.g. after the page navigated.
+    pub fn response_body(tab: &Tab, request: &CapturedRequest) -> Result<String> {
+        let result = tab
+            .call_method(Network::GetResponseBody {
+                request_id: request.request_id.clone(),
+            })
+            .map_err(|e| {
+                BrowserError::ChromeError(format!(
+                    "Response body of {} is not available: {}",
+                    request.url, e
+                ))
+            })?;
+
+        if result.base_64_encoded {
+            let bytes = STANDARD.decode(&result.body).map_err(|e| {
+                BrowserError::ChromeError(format!("Invalid response body encoding: {}", e))
+            })?;
+            Ok(String::from_utf8_lossy(&bytes).into_owned())
+        } else {
+            Ok(result.body)
+        }
+    }
+}
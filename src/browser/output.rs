@@ -0,0 +1,260 @@
+//! Sandboxed output paths for file-writing tools
+//!
+//! When an output root is configured, every path a tool writes to must be a
+//! relative path that stays inside that root. Absolute paths, drive prefixes
+//! and `..` components are rejected, so an MCP client cannot direct the server
+//! to write elsewhere on the host filesystem. Symlinks inside the root that
+//! point outside of it are caught by canonicalizing directories before they
+//! are created or written to, and the file written must not be a symlink, so
+//! tools go through [`prepare_output_file`] or [`create_dir_within`].
+
+use crate::error::{BrowserError, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Resolve a tool-supplied `path` against the output `root`
+///
+/// Without a root the path is returned unchanged.
+pub fn resolve_output_path(root: Option<&Path>, path: &str) -> Result<PathBuf> {
+    let requested = Path::new(path.trim());
+    let Some(root) = root else {
+        return Ok(requested.to_path_buf());
+    };
+
+    if requested.as_os_str().is_empty() {
+        return Err(BrowserError::PathNotAllowed(
+            "Output path must not be empty".to_string(),
+        ));
+    }
+
+    let mut relative = PathBuf::new();
+    for component in requested.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                return Err(BrowserError::PathNotAllowed(format!(
+                    "'{}' must not contain '..'",
+                    path
+                )));
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(BrowserError::PathNotAllowed(format!(
+                    "'{}' must be relative to the output directory",
+                    path
+                )));
+            }
+        }
+    }
+
+    Ok(root.join(relative))
+}
+
+/// Resolve a tool-supplied path of a file to read, which must lie inside `root`
+///
+/// Relative paths are taken from the root; absolute ones, such as paths
+/// returned by download_url, are accepted when they canonicalize inside it.
+/// Without a root nothing may be read, since the caller could name any file
+/// on the host.
+pub fn resolve_input_path(root: Option<&Path>, path: &str) -> Result<PathBuf> {
+    let Some(root) = root else {
+        return Err(BrowserError::PathNotAllowed(
+            "Reading files needs an output directory (--output-dir)".to_string(),
+        ));
+    };
+    let requested = Path::new(path.trim());
+    if requested.as_os_str().is_empty() {
+        return Err(BrowserError::PathNotAllowed(
+            "Input path must not be empty".to_string(),
+        ));
+    }
+
+    let canonical = root.join(requested).canonicalize()?;
+    if !canonical.starts_with(root.canonicalize()?) {
+        return Err(BrowserError::PathNotAllowed(format!(
+            "'{}' resolves outside the output directory",
+            path
+        )));
+    }
+    Ok(canonical)
+}
+
+/// Prepare a resolved output path for writing and return its canonical form
+///
+/// Refuses to replace an existing file unless `overwrite` is set. Missing parent
+/// directories are only created inside `root`; without a root they must exist.
+/// Inside a root, the file itself must not be a symlink.
+pub fn prepare_output_file(root: Option<&Path>, path: &Path, overwrite: bool) -> Result<PathBuf> {
+    let file_name = path.file_name().ok_or_else(|| {
+        BrowserError::PathNotAllowed(format!("'{}' is not a file path", path.display()))
+    })?;
+
+    if path.exists() && !overwrite {
+        return Err(BrowserError::PathNotAllowed(format!(
+            "'{}' already exists; set overwrite to replace it",
+            path.display()
+        )));
+    }
+
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    if let Some(root) = root {
+        let file = create_dir_within(root, &parent)?.join(file_name);
+        if std::fs::symlink_metadata(&file).is_ok_and(|meta| meta.file_type().is_symlink()) {
+            return Err(BrowserError::PathNotAllowed(format!(
+                "'{}' is a symlink",
+                path.display()
+            )));
+        }
+        return Ok(file);
+    }
+
+    Ok(parent.canonicalize()?.join(file_name))
+}
+
+/// Create `dir` and its missing ancestors, provided they all resolve inside `root`
+///
+/// The deepest existing ancestor is canonicalized and checked before anything
+/// is created, so a symlink pointing out of the root cannot be used to create
+/// directories elsewhere. Returns the canonical directory.
+pub fn create_dir_within(root: &Path, dir: &Path) -> Result<PathBuf> {
+    let canonical_root = root.canonicalize()?;
+    let outside = || {
+        BrowserError::PathNotAllowed(format!(
+            "'{}' resolves outside the output directory",
+            dir.display()
+        ))
+    };
+
+    let existing = dir
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(Path::new("."));
+    if !existing.canonicalize()?.starts_with(&canonical_root) {
+        return Err(outside());
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let canonical_dir = dir.canonicalize()?;
+    if !canonical_dir.starts_with(&canonical_root) {
+        return Err(outside());
+    }
+    Ok(canonical_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_root_is_unrestricted() {
+        assert_eq!(
+            resolve_output_path(None, "/tmp/shot.png").unwrap(),
+            PathBuf::from("/tmp/shot.png")
+        );
+    }
+
+    #[test]
+    fn test_relative_paths_are_joined() {
+        let root = Path::new("/srv/out");
+        assert_eq!(
+            resolve_output_path(Some(root), "shots/./home.png").unwrap(),
+            PathBuf::from("/srv/out/shots/home.png")
+        );
+    }
+
+    #[test]
+    fn test_escapes_are_rejected() {
+        let root = Path::new("/srv/out");
+        for path in ["/etc/passwd", "../secret.png", "shots/../../x.png", ""] {
+            assert!(
+                matches!(
+                    resolve_output_path(Some(root), path),
+                    Err(BrowserError::PathNotAllowed(_))
+                ),
+                "{} should be rejected",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_prepare_output_file() {
+        let root = std::env::temp_dir().join(format!("browser-use-output-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+
+        let path = resolve_output_path(Some(&root), "nested/dir/shot.png").unwrap();
+        let prepared = prepare_output_file(Some(&root), &path, false).unwrap();
+        assert!(prepared.is_absolute());
+        assert!(prepared.ends_with("nested/dir/shot.png"));
+        assert!(root.join("nested/dir").is_dir());
+
+        std::fs::write(&prepared, b"png").unwrap();
+        assert!(matches!(
+            prepare_output_file(Some(&root), &path, false),
+            Err(BrowserError::PathNotAllowed(_))
+        ));
+        assert_eq!(
+            prepare_output_file(Some(&root), &path, true).unwrap(),
+            prepared
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_input_path() {
+        let root = std::env::temp_dir().join(format!("browser-use-input-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("downloads")).unwrap();
+        std::fs::write(root.join("downloads/report.csv"), "a,b").unwrap();
+
+        let relative = resolve_input_path(Some(&root), "downloads/report.csv").unwrap();
+        assert!(relative.is_absolute());
+        assert_eq!(
+            resolve_input_path(Some(&root), &relative.to_string_lossy()).unwrap(),
+            relative
+        );
+
+        for path in ["/etc/passwd", "../../etc/passwd", "downloads/../../x", ""] {
+            assert!(
+                resolve_input_path(Some(&root), path).is_err(),
+                "{} should be rejected",
+                path
+            );
+        }
+        assert!(matches!(
+            resolve_input_path(None, "report.csv"),
+            Err(BrowserError::PathNotAllowed(_))
+        ));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_out_of_root_are_refused() {
+        let base = std::env::temp_dir().join(format!("browser-use-symlink-{}", std::process::id()));
+        let root = base.join("root");
+        let outside = base.join("outside");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+        std::os::unix::fs::symlink(outside.join("target.png"), root.join("shot.png")).unwrap();
+
+        let nested = resolve_output_path(Some(&root), "escape/new/shot.png").unwrap();
+        assert!(matches!(
+            prepare_output_file(Some(&root), &nested, false),
+            Err(BrowserError::PathNotAllowed(_))
+        ));
+        assert!(!outside.join("new").exists());
+
+        let linked = resolve_output_path(Some(&root), "shot.png").unwrap();
+        assert!(matches!(
+            prepare_output_file(Some(&root), &linked, true),
+            Err(BrowserError::PathNotAllowed(_))
+        ));
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
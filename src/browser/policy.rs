@@ -0,0 +1,314 @@
+//! Execution policy for the evaluate tool
+//!
+//! Running arbitrary JavaScript is the most powerful thing a model can do with
+//! the browser. An [`EvaluatePolicy`] lets operators disable it, restrict it to
+//! a set of named snippets, and cap its execution time and result size. The
+//! size cap also applies to page content returned by the extract tool, so one
+//! `document.body.innerHTML` cannot produce a multi-megabyte message.
+
+use crate::error::{BrowserError, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Numbers files of spilled results written by this process
+static SPILLED_RESULTS: AtomicU64 = AtomicU64::new(0);
+
+/// What JavaScript the evaluate tool may run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvaluateAccess {
+    /// Any code as well as named snippets (default)
+    #[default]
+    Unrestricted,
+    /// Only snippets registered in the policy
+    SnippetsOnly,
+    /// Nothing; the tool always fails
+    Disabled,
+}
+
+/// What happens to a result larger than the size limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OversizeAction {
+    /// Fail the call (default)
+    #[default]
+    Reject,
+    /// Cut the result at the limit and mark where it was cut
+    Truncate,
+    /// Truncate, and save the full result to a file named in the result
+    Spill,
+}
+
+/// How a result was cut to the size limit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Truncation {
+    /// Size of the full serialized result in bytes
+    pub total_bytes: usize,
+    /// File holding the full result, when it was spilled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spilled_to: Option<PathBuf>,
+}
+
+/// A result within the size limit, with how it was cut if it had to be
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LimitedResult {
+    /// The result, or a string with its start when it was truncated
+    pub result: Value,
+    /// Present when the result was truncated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncated: Option<Truncation>,
+}
+
+/// Policy applied to every evaluate tool call
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EvaluatePolicy {
+    /// Which code may run (default: unrestricted)
+    pub access: EvaluateAccess,
+
+    /// Named snippets callers can run by name
+    pub snippets: BTreeMap<String, String>,
+
+    /// Maximum script execution time in milliseconds (default: no limit)
+    pub timeout_ms: Option<u64>,
+
+    /// Maximum size of the JSON-serialized result in bytes (default: no limit)
+    pub max_result_bytes: Option<usize>,
+
+    /// What happens to results over `max_result_bytes` (default: reject)
+    pub oversize: OversizeAction,
+}
+
+impl EvaluatePolicy {
+    /// Create a policy that allows any code
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a policy that blocks all evaluation
+    pub fn disabled() -> Self {
+        Self {
+            access: EvaluateAccess::Disabled,
+            ..Self::default()
+        }
+    }
+
+    /// Builder method: set access mode
+    pub fn access(mut self, access: EvaluateAccess) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// Builder method: register a named snippet
+    pub fn snippet(mut self, name: impl Into<String>, code: impl Into<String>) -> Self {
+        self.snippets.insert(name.into(), code.into());
+        self
+    }
+
+    /// Builder method: cap script execution time
+    pub fn timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Builder method: cap result size
+    pub fn max_result_bytes(mut self, max: usize) -> Self {
+        self.max_result_bytes = Some(max);
+        self
+    }
+
+    /// Builder method: truncate or spill oversized results instead of rejecting them
+    pub fn oversize(mut self, action: OversizeAction) -> Self {
+        self.oversize = action;
+        self
+    }
+
+    /// Resolve the code to run for a call giving either raw `code` or a `snippet` name
+    pub fn resolve(&self, code: Option<&str>, snippet: Option<&str>) -> Result<String> {
+        if self.access == EvaluateAccess::Disabled {
+            return Err(BrowserError::PolicyDenied(
+                "JavaScript evaluation is disabled on this server".to_string(),
+            ));
+        }
+
+        match (code, snippet) {
+            (Some(_), Some(_)) => Err(BrowserError::InvalidArgument(
+                "Cannot specify both 'code' and 'snippet'".to_string(),
+            )),
+            (None, None) => Err(BrowserError::InvalidArgument(
+                "Must specify either 'code' or 'snippet'".to_string(),
+            )),
+            (Some(_), None) if self.access == EvaluateAccess::SnippetsOnly => {
+                Err(BrowserError::PolicyDenied(format!(
+                    "Only named snippets may be evaluated (available: {})",
+                    self.snippet_names()
+                )))
+            }
+            (Some(code), None) => Ok(code.to_string()),
+            (None, Some(name)) => self.snippets.get(name).cloned().ok_or_else(|| {
+                BrowserError::PolicyDenied(format!(
+                    "Unknown snippet '{}' (available: {})",
+                    name,
+                    self.snippet_names()
+                ))
+            }),
+        }
+    }
+
+    /// Return an error if a serialized result exceeds the size limit
+    pub fn check_result_size(&self, size: usize) -> Result<()> {
+        match self.max_result_bytes {
+            Some(max) if size > max => Err(BrowserError::PolicyDenied(format!(
+                "Result is {} bytes, exceeding the {} byte limit",
+                size, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply the size limit to a result
+    ///
+    /// Oversized results are rejected, or cut to a string of at most the limit
+    /// ending in a truncation marker. Spilled results are written to
+    /// `spill_dir`, or the system temp directory without one: strings as
+    /// text, other values as JSON.
+    pub fn limit_result(&self, value: Value, spill_dir: Option<&Path>) -> Result<LimitedResult> {
+        let serialized = value.to_string();
+        let total_bytes = serialized.len();
+        let Some(max) = self.max_result_bytes.filter(|max| total_bytes > *max) else {
+            return Ok(LimitedResult {
+                result: value,
+                truncated: None,
+            });
+        };
+        if self.oversize == OversizeAction::Reject {
+            self.check_result_size(total_bytes)?;
+        }
+
+        let full = match value {
+            Value::String(text) => text,
+            _ => serialized,
+        };
+        let spilled_to = match self.oversize {
+            OversizeAction::Spill => Some(spill_result(&full, spill_dir)?),
+            _ => None,
+        };
+        let marker = format!("\n…[truncated: {} bytes in total]", total_bytes);
+        let preview = truncate_at(&full, max.saturating_sub(marker.len()));
+        Ok(LimitedResult {
+            result: Value::String(format!("{}{}", preview, marker)),
+            truncated: Some(Truncation {
+                total_bytes,
+                spilled_to,
+            }),
+        })
+    }
+
+    fn snippet_names(&self) -> String {
+        if self.snippets.is_empty() {
+            "none".to_string()
+        } else {
+            self.snippets.keys().cloned().collect::<Vec<_>>().join(", ")
+        }
+    }
+}
+
+/// Longest prefix of `text` of at most `max` bytes that ends on a character boundary
+pub(crate) fn truncate_at(text: &str, max: usize) -> &str {
+    let mut end = max.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Write a full result to a new file in `dir` (or the temp directory) and return its path
+fn spill_result(full: &str, dir: Option<&Path>) -> Result<PathBuf> {
+    let dir = dir.map_or_else(std::env::temp_dir, Path::to_path_buf);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "browser-use-result-{}-{}.txt",
+        std::process::id(),
+        SPILLED_RESULTS.fetch_add(1, Ordering::SeqCst)
+    ));
+    std::fs::write(&path, full)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unrestricted() {
+        let policy = EvaluatePolicy::new().snippet("title", "document.title");
+        assert_eq!(policy.resolve(Some("1 + 1"), None).unwrap(), "1 + 1");
+        assert_eq!(
+            policy.resolve(None, Some("title")).unwrap(),
+            "document.title"
+        );
+        assert!(policy.resolve(None, None).is_err());
+    }
+
+    #[test]
+    fn test_snippets_only() {
+        let policy = EvaluatePolicy::new()
+            .access(EvaluateAccess::SnippetsOnly)
+            .snippet("title", "document.title");
+        assert!(matches!(
+            policy.resolve(Some("alert(1)"), None),
+            Err(BrowserError::PolicyDenied(_))
+        ));
+        assert!(policy.resolve(None, Some("title")).is_ok());
+        assert!(matches!(
+            policy.resolve(None, Some("other")),
+            Err(BrowserError::PolicyDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_disabled() {
+        let policy = EvaluatePolicy::disabled().snippet("title", "document.title");
+        assert!(matches!(
+            policy.resolve(None, Some("title")),
+            Err(BrowserError::PolicyDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_result_size() {
+        let policy = EvaluatePolicy::new().max_result_bytes(10);
+        assert!(policy.check_result_size(10).is_ok());
+        assert!(policy.check_result_size(11).is_err());
+    }
+
+    #[test]
+    fn test_limit_result() {
+        let long = Value::String("é".repeat(100));
+        let small = Value::String("short".to_string());
+
+        let policy = EvaluatePolicy::new().max_result_bytes(64);
+        assert!(policy.limit_result(long.clone(), None).is_err());
+        let limited = policy.limit_result(small.clone(), None).unwrap();
+        assert_eq!(limited.result, small);
+        assert!(limited.truncated.is_none());
+
+        let policy = policy.oversize(OversizeAction::Truncate);
+        let limited = policy.limit_result(long.clone(), None).unwrap();
+        let text = limited.result.as_str().unwrap();
+        assert!(text.len() <= 64);
+        assert!(text.starts_with('é'));
+        assert!(text.ends_with("[truncated: 202 bytes in total]"));
+        assert_eq!(limited.truncated.unwrap().spilled_to, None);
+
+        let dir = std::env::temp_dir().join(format!("browser-use-spill-{}", std::process::id()));
+        let policy = policy.oversize(OversizeAction::Spill);
+        let limited = policy
+            .limit_result(serde_json::json!({ "items": [long] }), Some(&dir))
+            .unwrap();
+        let path = limited.truncated.unwrap().spilled_to.unwrap();
+        let full: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(full["items"][0].as_str().unwrap().len(), 200);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
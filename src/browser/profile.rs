@@ -0,0 +1,123 @@
+//! Persistent browser profiles
+//!
+//! Chrome guards a user data directory with a `SingletonLock` symlink naming
+//! the host and process that own it. A second Chrome started on a locked
+//! profile hands its arguments to the first one and exits, which looks like
+//! a failed launch; a lock left behind by a crashed Chrome blocks the profile
+//! for good. [`prepare_user_data_dir`] tells the two apart before launching:
+//! it waits briefly for an exiting owner (as on a restart), refuses profiles
+//! that stay in use, and clears stale locks.
+
+use crate::error::{BrowserError, Result};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Files Chrome keeps next to the lock while it owns the profile
+const SINGLETON_FILES: &[&str] = &["SingletonLock", "SingletonSocket", "SingletonCookie"];
+
+/// How long a profile owner is given to exit, e.g. the browser being restarted
+const OWNER_EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Process id recorded in a `SingletonLock` target, which reads `hostname-pid`
+pub fn lock_owner(target: &str) -> Option<u32> {
+    target.rsplit_once('-')?.1.parse().ok()
+}
+
+/// Create the profile directory if needed and make sure no live Chrome holds it
+///
+/// Fails with [`BrowserError::LaunchFailed`] when another process keeps the
+/// profile locked; removes the lock files of an owner that no longer runs.
+pub fn prepare_user_data_dir(dir: &Path) -> Result<()> {
+    prepare(dir, OWNER_EXIT_TIMEOUT)
+}
+
+fn prepare(dir: &Path, owner_exit_timeout: Duration) -> Result<()> {
+    std::fs::create_dir_all(dir).map_err(|e| {
+        BrowserError::LaunchFailed(format!(
+            "Cannot create user data directory {}: {}",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    // Locks in another format are left for Chrome to judge
+    let Some(pid) = std::fs::read_link(dir.join("SingletonLock"))
+        .ok()
+        .and_then(|target| lock_owner(&target.to_string_lossy()))
+    else {
+        return Ok(());
+    };
+    let deadline = Instant::now() + owner_exit_timeout;
+    while is_running(pid) {
+        if Instant::now() >= deadline {
+            return Err(BrowserError::LaunchFailed(format!(
+                "User data directory {} is in use by another browser (process {}); \
+                 close it or use a different profile directory",
+                dir.display(),
+                pid
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    log::info!("Removing stale profile lock in {}", dir.display());
+    for name in SINGLETON_FILES {
+        let _ = std::fs::remove_file(dir.join(name));
+    }
+    Ok(())
+}
+
+/// Whether a process with this id runs on this machine
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Whether a process with this id runs on this machine
+///
+/// Chrome on Windows locks the profile with a file it holds open instead.
+#[cfg(not(unix))]
+fn is_running(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_owner() {
+        assert_eq!(lock_owner("build-host-4242"), Some(4242));
+        assert_eq!(lock_owner("laptop.local-17"), Some(17));
+        assert_eq!(lock_owner("no-pid-here"), None);
+        assert_eq!(lock_owner("4242"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_prepare_user_data_dir() {
+        let dir = std::env::temp_dir().join(format!("browser-use-profile-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // A missing directory is created
+        prepare(&dir, Duration::ZERO).unwrap();
+        assert!(dir.is_dir());
+
+        // A lock held by a live process is refused
+        let lock = dir.join("SingletonLock");
+        std::os::unix::fs::symlink(format!("host-{}", std::process::id()), &lock).unwrap();
+        assert!(prepare(&dir, Duration::ZERO).is_err());
+
+        // A lock left by a process that is gone is cleared
+        std::fs::remove_file(&lock).unwrap();
+        std::os::unix::fs::symlink(format!("host-{}", u32::MAX - 1), &lock).unwrap();
+        prepare(&dir, Duration::ZERO).unwrap();
+        assert!(std::fs::symlink_metadata(&lock).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,179 @@
+//! Per-host politeness scheduling
+//!
+//! A [`RateLimiter`] enforces a minimum delay between requests to the same host
+//! and caps how many requests may be in flight per host at once. Callers block
+//! in [`RateLimiter::acquire`] until both limits allow the request and hold the
+//! returned [`RatePermit`] for the duration of the request.
+
+use std::collections::HashMap;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Politeness limits applied per host
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitOptions {
+    /// Minimum delay between the start of two requests to the same host in milliseconds (default: 1000)
+    pub min_delay_ms: u64,
+
+    /// Maximum concurrent requests per host (default: 1)
+    pub max_concurrent_per_host: usize,
+}
+
+impl Default for RateLimitOptions {
+    fn default() -> Self {
+        Self {
+            min_delay_ms: 1000,
+            max_concurrent_per_host: 1,
+        }
+    }
+}
+
+impl RateLimitOptions {
+    /// Create new RateLimitOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: set minimum delay between requests to a host
+    pub fn min_delay(mut self, delay_ms: u64) -> Self {
+        self.min_delay_ms = delay_ms;
+        self
+    }
+
+    /// Builder method: set maximum concurrent requests per host
+    pub fn max_concurrent_per_host(mut self, max: usize) -> Self {
+        self.max_concurrent_per_host = max.max(1);
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    last_start: Option<Instant>,
+    in_flight: usize,
+}
+
+/// Blocking per-host rate limiter shared across threads
+#[derive(Debug)]
+pub struct RateLimiter {
+    options: RateLimitOptions,
+    hosts: Mutex<HashMap<String, HostState>>,
+    released: Condvar,
+}
+
+/// Guard for an in-flight request; releases the host slot on drop
+#[derive(Debug)]
+pub struct RatePermit<'a> {
+    limiter: &'a RateLimiter,
+    host: String,
+}
+
+impl RateLimiter {
+    /// Create a limiter with the given options
+    pub fn new(options: RateLimitOptions) -> Self {
+        Self {
+            options,
+            hosts: Mutex::new(HashMap::new()),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Limits enforced by this limiter
+    pub fn options(&self) -> &RateLimitOptions {
+        &self.options
+    }
+
+    /// Block until a request to `url` is allowed, then reserve a slot for it
+    ///
+    /// URLs without a host (data:, about:, ...) are never throttled.
+    pub fn acquire(&self, url: &str) -> Option<RatePermit<'_>> {
+        let host = Url::parse(url).ok()?.host_str()?.to_lowercase();
+        let min_delay = Duration::from_millis(self.options.min_delay_ms);
+
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            let state = hosts.entry(host.clone()).or_default();
+
+            if state.in_flight >= self.options.max_concurrent_per_host {
+                hosts = self.released.wait(hosts).unwrap_or_else(|e| e.into_inner());
+                continue;
+            }
+
+            let wait = state
+                .last_start
+                .map(|last| min_delay.saturating_sub(last.elapsed()))
+                .unwrap_or_default();
+
+            if wait.is_zero() {
+                state.in_flight += 1;
+                state.last_start = Some(Instant::now());
+                break;
+            }
+
+            hosts = self
+                .released
+                .wait_timeout(hosts, wait)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+
+        Some(RatePermit {
+            limiter: self,
+            host,
+        })
+    }
+
+    fn release(&self, host: &str) {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = hosts.get_mut(host) {
+            state.in_flight = state.in_flight.saturating_sub(1);
+        }
+        self.released.notify_all();
+    }
+}
+
+impl Drop for RatePermit<'_> {
+    fn drop(&mut self) {
+        self.limiter.release(&self.host);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_builder() {
+        let opts = RateLimitOptions::new()
+            .min_delay(250)
+            .max_concurrent_per_host(0);
+        assert_eq!(opts.min_delay_ms, 250);
+        assert_eq!(opts.max_concurrent_per_host, 1);
+    }
+
+    #[test]
+    fn test_hostless_urls_are_not_throttled() {
+        let limiter = RateLimiter::new(RateLimitOptions::default());
+        assert!(limiter.acquire("about:blank").is_none());
+        assert!(limiter.acquire("data:text/html,hi").is_none());
+    }
+
+    #[test]
+    fn test_min_delay_per_host() {
+        let limiter = RateLimiter::new(RateLimitOptions::new().min_delay(100));
+        let start = Instant::now();
+        drop(limiter.acquire("https://example.com/a"));
+        drop(limiter.acquire("https://example.com/b"));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_hosts_are_independent() {
+        let limiter = RateLimiter::new(RateLimitOptions::new().min_delay(10_000));
+        let start = Instant::now();
+        let _a = limiter.acquire("https://a.example.com/");
+        let _b = limiter.acquire("https://b.example.com/");
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,112 @@
+//! Seeded randomness for human-like behavior
+//!
+//! Every session owns a [`SessionRng`]. Anything that randomizes what the
+//! browser does (jittered delays, mouse paths) draws from it instead of a
+//! global generator, so a run can be replayed exactly by launching with the
+//! same [`LaunchOptions::rng_seed`](crate::browser::LaunchOptions::rng_seed).
+//! Without an explicit seed one is picked at random and logged at debug level.
+//!
+//! The generator is SplitMix64, implemented here so the sequence for a seed
+//! never changes with a dependency upgrade.
+
+use std::hash::{BuildHasher, RandomState};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Deterministic random number generator shared by a session's tools
+#[derive(Debug)]
+pub struct SessionRng {
+    seed: u64,
+    state: Mutex<u64>,
+}
+
+impl SessionRng {
+    /// Generator producing the sequence for `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            state: Mutex::new(seed),
+        }
+    }
+
+    /// Generator with a random seed
+    pub fn from_entropy() -> Self {
+        Self::new(RandomState::new().hash_one(std::process::id()))
+    }
+
+    /// Seed this generator started from, to replay the run
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Next 64 random bits
+    pub fn next_u64(&self) -> u64 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in `[0, 1)`
+    pub fn next_f64(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform float in `[low, high)`; `low` if the range is empty
+    pub fn range(&self, low: f64, high: f64) -> f64 {
+        if high <= low {
+            return low;
+        }
+        low + self.next_f64() * (high - low)
+    }
+
+    /// `base` scaled by a random factor in `[1 - spread, 1 + spread]`
+    ///
+    /// `spread` is clamped to `[0, 1]`, so the result is never negative.
+    pub fn jitter(&self, base: Duration, spread: f64) -> Duration {
+        let spread = spread.clamp(0.0, 1.0);
+        base.mul_f64(self.range(1.0 - spread, 1.0 + spread))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let a = SessionRng::new(7);
+        let b = SessionRng::new(7);
+        let sequence: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        assert_eq!(sequence, (0..5).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(sequence[0], SessionRng::new(8).next_u64());
+        assert_eq!(a.seed(), 7);
+    }
+
+    #[test]
+    fn test_known_sequence() {
+        // Reference values of SplitMix64 for seed 0
+        let rng = SessionRng::new(0);
+        assert_eq!(rng.next_u64(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(rng.next_u64(), 0x6E78_9E6A_A1B9_65F4);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let rng = SessionRng::new(42);
+        for _ in 0..1000 {
+            let value = rng.range(2.0, 3.0);
+            assert!((2.0..3.0).contains(&value));
+
+            let delay = rng.jitter(Duration::from_millis(100), 0.2);
+            assert!(delay >= Duration::from_millis(80) && delay <= Duration::from_millis(120));
+        }
+        assert_eq!(rng.range(5.0, 5.0), 5.0);
+        assert_eq!(
+            rng.jitter(Duration::from_millis(100), 0.0),
+            Duration::from_millis(100)
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! robots.txt compliance
+//!
+//! When enabled, navigation consults the target origin's robots.txt for the
+//! configured user agent and refuses disallowed paths. Rules are fetched once
+//! per origin through a hidden background tab and cached for the session.
+//!
+//! Matching follows RFC 9309: the most specific user-agent group applies, the
+//! longest matching rule wins, `Allow` wins ties, and `*` / `$` wildcards are
+//! supported. A missing robots.txt (4xx) allows everything, while a server
+//! error (5xx) disallows everything.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Browser;
+use headless_chrome::protocol::cdp::Target::CreateTarget;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use url::Url;
+
+/// A single Allow/Disallow rule
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+/// Parsed robots.txt rules applicable to one user agent
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsRules {
+    rules: Vec<Rule>,
+}
+
+impl RobotsRules {
+    /// Rules that allow every path
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Rules that disallow every path
+    pub fn disallow_all() -> Self {
+        Self {
+            rules: vec![Rule {
+                allow: false,
+                pattern: "/".to_string(),
+            }],
+        }
+    }
+
+    /// Parse robots.txt content, keeping only the group that applies to `user_agent`
+    pub fn parse(content: &str, user_agent: &str) -> Self {
+        let agent = user_agent.to_lowercase();
+
+        // (agents, rules) per group, in file order
+        let mut groups: Vec<(Vec<String>, Vec<Rule>)> = Vec::new();
+        let mut in_agent_lines = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let Some((field, value)) = line.split_once(':') else {
+                continue;
+            };
+            let field = field.trim().to_lowercase();
+            let value = value.trim();
+
+            match field.as_str() {
+                "user-agent" => {
+                    if !in_agent_lines || groups.is_empty() {
+                        groups.push((Vec::new(), Vec::new()));
+                    }
+                    in_agent_lines = true;
+                    if let Some(group) = groups.last_mut() {
+                        group.0.push(value.to_lowercase());
+                    }
+                }
+                "allow" | "disallow" => {
+                    in_agent_lines = false;
+                    // An empty Disallow means "allow everything" and adds no rule
+                    if value.is_empty() {
+                        continue;
+                    }
+                    if let Some(group) = groups.last_mut() {
+                        group.1.push(Rule {
+                            allow: field == "allow",
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Prefer groups naming our agent, then the wildcard group
+        let names_agent = |agents: &Vec<String>| {
+            agents
+                .iter()
+                .any(|a| a != "*" && !a.is_empty() && agent.contains(a.as_str()))
+        };
+        let has_specific = groups.iter().any(|(agents, _)| names_agent(agents));
+
+        let rules = groups
+            .iter()
+            .filter(|(agents, _)| {
+                if has_specific {
+                    names_agent(agents)
+                } else {
+                    agents.iter().any(|a| a == "*")
+                }
+            })
+            .flat_map(|(_, rules)| rules.clone())
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Check whether a path (including query string) may be fetched
+    pub fn is_allowed(&self, path: &str) -> bool {
+        let mut best: Option<(usize, bool)> = None;
+
+        for rule in &self.rules {
+            if pattern_matches(&rule.pattern, path) {
+                let len = rule.pattern.len();
+                best = match best {
+                    Some((best_len, best_allow))
+                        if best_len > len || (best_len == len && best_allow) =>
+                    {
+                        Some((best_len, best_allow))
+                    }
+                    _ => Some((len, rule.allow)),
+                };
+            }
+        }
+
+        best.is_none_or(|(_, allow)| allow)
+    }
+}
+
+/// Match a robots.txt path pattern supporting `*` and a trailing `$`
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let first = parts[0];
+    if !path.starts_with(first) {
+        return false;
+    }
+    let mut pos = first.len();
+
+    // With an anchor, the last segment is matched against the end of the path instead
+    let middle_end = if anchored && parts.len() > 1 {
+        parts.len() - 1
+    } else {
+        parts.len()
+    };
+    for part in &parts[1..middle_end] {
+        match path[pos..].find(part) {
+            Some(found) => pos += found + part.len(),
+            None => return false,
+        }
+    }
+
+    if anchored {
+        if parts.len() == 1 {
+            return path.len() == first.len();
+        }
+        let last = parts[parts.len() - 1];
+        return path.len() >= pos + last.len() && path.ends_with(last);
+    }
+
+    true
+}
+
+/// Session-wide robots.txt checker with a per-origin cache
+pub struct RobotsChecker {
+    user_agent: String,
+    cache: Mutex<HashMap<String, RobotsRules>>,
+}
+
+impl RobotsChecker {
+    /// Create a checker for the given user agent token
+    pub fn new(user_agent: impl Into<String>) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// User agent the rules are evaluated for
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Return an error if `url` is disallowed by its origin's robots.txt
+    ///
+    /// Non-HTTP(S) URLs (data:, about:, file:, ...) are always allowed.
+    pub fn check(&self, browser: &Browser, url: &str) -> Result<()> {
+        let Ok(parsed) = Url::parse(url) else {
+            return Ok(());
+        };
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Ok(());
+        }
+
+        let origin = parsed.origin().ascii_serialization();
+        let rules = {
+            let cached = self
+                .cache
+                .lock()
+                .map_err(|e| BrowserError::ChromeError(e.to_string()))?
+                .get(&origin)
+                .cloned();
+            match cached {
+                Some(rules) => rules,
+                None => {
+                    let rules = self.fetch_rules(browser, &origin);
+                    self.cache
+                        .lock()
+                        .map_err(|e| BrowserError::ChromeError(e.to_string()))?
+                        .insert(origin.clone(), rules.clone());
+                    rules
+                }
+            }
+        };
+
+        let mut path = parsed.path().to_string();
+        if let Some(query) = parsed.query() {
+            path.push('?');
+            path.push_str(query);
+        }
+
+        if rules.is_allowed(&path) {
+            Ok(())
+        } else {
+            Err(BrowserError::RobotsDisallowed {
+                url: url.to_string(),
+                user_agent: self.user_agent.clone(),
+            })
+        }
+    }
+
+    /// Fetch and parse robots.txt for an origin using a hidden background tab
+    fn fetch_rules(&self, browser: &Browser, origin: &str) -> RobotsRules {
+        let robots_url = format!("{}/robots.txt", origin);
+
+        let fetched = (|| -> Result<(u64, String)> {
+            let tab = browser.new_tab_with_options(CreateTarget {
+                url: "about:blank".to_string(),
+                left: None,
+                top: None,
+                width: None,
+                height: None,
+                window_state: None,
+                browser_context_id: None,
+                enable_begin_frame_control: None,
+                new_window: None,
+                background: Some(true),
+                for_tab: None,
+                hidden: Some(true),
+            })?;
+
+            let result = (|| -> Result<(u64, String)> {
+                tab.navigate_to(&robots_url)?.wait_until_navigated()?;
+                let value = tab
+                    .evaluate(
+                        r#"
+                        JSON.stringify({
+                            status: (performance.getEntriesByType('navigation')[0] || {}).responseStatus || 0,
+                            body: document.body ? document.body.innerText : ''
+                        })
+                        "#,
+                        false,
+                    )?
+                    .value
+                    .and_then(|v| v.as_str().map(String::from))
+                    .unwrap_or_default();
+                let json: serde_json::Value = serde_json::from_str(&value)?;
+                Ok((
+                    json["status"].as_u64().unwrap_or(0),
+                    json["body"].as_str().unwrap_or_default().to_string(),
+                ))
+            })();
+
+            let _ = tab.close(false);
+            result
+        })();
+
+        match fetched {
+            Ok((status, body)) if (200..300).contains(&status) => {
+                RobotsRules::parse(&body, &self.user_agent)
+            }
+            Ok((status, _)) if status >= 500 => {
+                log::warn!("robots.txt at {} returned {}", robots_url, status);
+                RobotsRules::disallow_all()
+            }
+            Ok(_) => RobotsRules::allow_all(),
+            Err(e) => {
+                log::debug!("Failed to fetch {}: {}", robots_url, e);
+                RobotsRules::allow_all()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = r#"
+# Example robots.txt
+User-agent: *
+Disallow: /private/
+Allow: /private/public
+Disallow: /*.pdf$
+
+User-agent: browser-use
+User-agent: otherbot
+Disallow: /admin
+"#;
+
+    #[test]
+    fn test_wildcard_group() {
+        let rules = RobotsRules::parse(ROBOTS, "SomeBot/1.0");
+        assert!(rules.is_allowed("/"));
+        assert!(!rules.is_allowed("/private/secret"));
+        assert!(rules.is_allowed("/private/public/page"));
+        assert!(!rules.is_allowed("/docs/file.pdf"));
+        assert!(rules.is_allowed("/docs/file.pdf?download=1"));
+    }
+
+    #[test]
+    fn test_specific_group_overrides_wildcard() {
+        let rules = RobotsRules::parse(ROBOTS, "browser-use/0.2");
+        assert!(!rules.is_allowed("/admin/users"));
+        assert!(rules.is_allowed("/private/secret"));
+    }
+
+    #[test]
+    fn test_empty_disallow_allows_everything() {
+        let rules = RobotsRules::parse("User-agent: *\nDisallow:\n", "bot");
+        assert!(rules.is_allowed("/anything"));
+    }
+
+    #[test]
+    fn test_disallow_all() {
+        assert!(!RobotsRules::disallow_all().is_allowed("/"));
+        assert!(RobotsRules::allow_all().is_allowed("/"));
+    }
+
+    #[test]
+    fn test_pattern_matches() {
+        assert!(pattern_matches("/a*c", "/abc"));
+        assert!(pattern_matches("/a", "/abc"));
+        assert!(!pattern_matches("/a$", "/abc"));
+        assert!(pattern_matches("/*.php$", "/index.php"));
+        assert!(!pattern_matches("/*.php$", "/index.php5"));
+    }
+}
@@ -0,0 +1,34 @@
+(function () {
+  // Only the top-level document's route matters, and only instrument once
+  if (window !== window.top || window.__browserUseRoutes) {
+    return;
+  }
+  window.__browserUseRoutes = true;
+
+  const binding = __ROUTE_BINDING__;
+  let last = location.href;
+
+  const notify = (kind) => {
+    // popstate and hashchange both fire for fragment changes; report one
+    if (location.href === last) {
+      return;
+    }
+    last = location.href;
+    try {
+      window[binding](JSON.stringify({ url: location.href, kind: kind }));
+    } catch (e) {
+      // The binding is missing until the session attaches to this tab
+    }
+  };
+
+  for (const method of ["pushState", "replaceState"]) {
+    const original = history[method];
+    history[method] = function () {
+      const result = original.apply(this, arguments);
+      notify(method);
+      return result;
+    };
+  }
+  window.addEventListener("popstate", () => notify("popstate"));
+  window.addEventListener("hashchange", () => notify("hashchange"));
+})();
@@ -0,0 +1,250 @@
+//! Client-side route changes
+//!
+//! Single-page apps change the URL through the history API without loading a
+//! new document, so no navigation events fire. [`RouteWatcher`] hooks
+//! `pushState`, `replaceState`, `popstate` and `hashchange` with an init
+//! script and reports each URL change back through a CDP binding.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::browser::tab::EventListener;
+use headless_chrome::protocol::cdp::types::Event;
+use headless_chrome::protocol::cdp::{Page, Runtime};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Weak};
+use std::time::{Duration, Instant};
+
+/// Name of the binding the init script reports route changes through
+const ROUTE_BINDING: &str = "__browserUseRouteChanged";
+
+/// Number of route changes kept in the log
+const ROUTE_LOG_CAPACITY: usize = 100;
+
+const ROUTES_JS: &str = include_str!("routes.js");
+
+/// One client-side route change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RouteChange {
+    /// URL after the change
+    pub url: String,
+    /// What changed it: "pushState", "replaceState", "popstate" or "hashchange"
+    pub kind: String,
+    /// Position in the session's sequence of route changes, starting at 1
+    pub sequence: u64,
+}
+
+/// Payload the init script sends through the binding
+#[derive(Debug, Deserialize)]
+struct RoutePayload {
+    url: String,
+    kind: String,
+}
+
+/// Callback run for every route change
+pub type RouteCallback = dyn Fn(&RouteChange) + Send + Sync;
+
+#[derive(Default)]
+struct RouteLog {
+    changes: VecDeque<RouteChange>,
+    last_sequence: u64,
+}
+
+impl RouteLog {
+    fn push(&mut self, payload: RoutePayload) -> RouteChange {
+        self.last_sequence += 1;
+        let change = RouteChange {
+            url: payload.url,
+            kind: payload.kind,
+            sequence: self.last_sequence,
+        };
+        if self.changes.len() >= ROUTE_LOG_CAPACITY {
+            self.changes.pop_front();
+        }
+        self.changes.push_back(change.clone());
+        change
+    }
+
+    /// First change after `sequence`, if it is still in the log
+    fn after(&self, sequence: u64) -> Option<&RouteChange> {
+        self.changes
+            .iter()
+            .find(|change| change.sequence > sequence)
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    log: Mutex<RouteLog>,
+    changed: Condvar,
+    callbacks: Mutex<Vec<Arc<RouteCallback>>>,
+}
+
+type SyncListener = dyn EventListener<Event> + Send + Sync;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Reports client-side route changes across the tabs it is attached to
+#[derive(Default)]
+pub struct RouteWatcher {
+    shared: Arc<Shared>,
+    listeners: Mutex<Vec<(String, Weak<SyncListener>)>>,
+}
+
+impl RouteWatcher {
+    /// Create a watcher that is not attached to any tab yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the watcher is attached to any tab
+    pub fn is_active(&self) -> bool {
+        lock(&self.listeners)
+            .iter()
+            .any(|(_, listener)| listener.strong_count() > 0)
+    }
+
+    /// Instrument the tab's history API; does nothing if already attached
+    ///
+    /// The current document is instrumented right away and every document the
+    /// tab loads afterwards on creation.
+    pub fn attach(&self, tab: &Arc<Tab>) -> Result<()> {
+        let target_id = tab.get_target_id().clone();
+        let mut listeners = lock(&self.listeners);
+        listeners.retain(|(_, listener)| listener.strong_count() > 0);
+        if listeners.iter().any(|(id, _)| *id == target_id) {
+            return Ok(());
+        }
+
+        let chrome_error = |e: anyhow::Error| {
+            BrowserError::ChromeError(format!("Failed to watch route changes: {}", e))
+        };
+        // Binding calls are only reported with the Runtime domain enabled
+        tab.enable_runtime().map_err(chrome_error)?;
+        tab.call_method(Runtime::AddBinding {
+            name: ROUTE_BINDING.to_string(),
+            execution_context_id: None,
+            execution_context_name: None,
+        })
+        .map_err(chrome_error)?;
+
+        let shared = Arc::clone(&self.shared);
+        let listener = tab
+            .add_event_listener(Arc::new(move |event: &Event| {
+                let Event::RuntimeBindingCalled(event) = event else {
+                    return;
+                };
+                if event.params.name != ROUTE_BINDING {
+                    return;
+                }
+                let Ok(payload) = serde_json::from_str::<RoutePayload>(&event.params.payload)
+                else {
+                    return;
+                };
+
+                let change = lock(&shared.log).push(payload);
+                shared.changed.notify_all();
+                let callbacks = lock(&shared.callbacks).clone();
+                for callback in callbacks {
+                    callback(&change);
+                }
+            }))
+            .map_err(chrome_error)?;
+        listeners.push((target_id, listener));
+
+        let script = ROUTES_JS.replace(
+            "__ROUTE_BINDING__",
+            &serde_json::Value::from(ROUTE_BINDING).to_string(),
+        );
+        tab.call_method(Page::AddScriptToEvaluateOnNewDocument {
+            source: script.clone(),
+            world_name: None,
+            include_command_line_api: None,
+            run_immediately: None,
+        })
+        .map_err(chrome_error)?;
+        tab.evaluate(&script, false).map_err(chrome_error)?;
+
+        Ok(())
+    }
+
+    /// Run `callback` for every route change from now on
+    pub fn on_change(&self, callback: impl Fn(&RouteChange) + Send + Sync + 'static) {
+        lock(&self.shared.callbacks).push(Arc::new(callback));
+    }
+
+    /// Route changes seen so far, oldest first (the most recent 100)
+    pub fn changes(&self) -> Vec<RouteChange> {
+        lock(&self.shared.log).changes.iter().cloned().collect()
+    }
+
+    /// Sequence number of the latest route change, 0 if there was none
+    pub fn last_sequence(&self) -> u64 {
+        lock(&self.shared.log).last_sequence
+    }
+
+    /// Wait for a route change after `sequence`, returning it if one happens in time
+    pub fn wait_for_change(&self, sequence: u64, timeout: Duration) -> Option<RouteChange> {
+        let deadline = Instant::now() + timeout;
+        let mut log = lock(&self.shared.log);
+        loop {
+            if let Some(change) = log.after(sequence) {
+                return Some(change.clone());
+            }
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            log = self
+                .shared
+                .changed
+                .wait_timeout(log, remaining)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(url: &str, kind: &str) -> RoutePayload {
+        RoutePayload {
+            url: url.to_string(),
+            kind: kind.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_route_log_sequence() {
+        let mut log = RouteLog::default();
+        assert!(log.after(0).is_none());
+
+        log.push(payload("https://app.example/inbox", "pushState"));
+        let second = log.push(payload("https://app.example/inbox/7", "pushState"));
+        assert_eq!(second.sequence, 2);
+        assert_eq!(log.after(0).unwrap().url, "https://app.example/inbox");
+        assert_eq!(log.after(1), Some(&second));
+        assert!(log.after(2).is_none());
+    }
+
+    #[test]
+    fn test_route_log_capacity() {
+        let mut log = RouteLog::default();
+        for i in 0..ROUTE_LOG_CAPACITY + 5 {
+            log.push(payload(
+                &format!("https://app.example/{}", i),
+                "replaceState",
+            ));
+        }
+        assert_eq!(log.changes.len(), ROUTE_LOG_CAPACITY);
+        assert_eq!(log.after(0).unwrap().sequence, 6);
+    }
+
+    #[test]
+    fn test_wait_for_change_times_out() {
+        let watcher = RouteWatcher::new();
+        assert!(!watcher.is_active());
+        assert_eq!(watcher.wait_for_change(0, Duration::from_millis(10)), None);
+    }
+}
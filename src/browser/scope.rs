@@ -0,0 +1,237 @@
+//! Origin scoping for sessions
+//!
+//! An [`OriginScope`] pins a session to a set of allowed origins. Once a scope
+//! is set, navigation outside it is refused and tools refuse to act on a page
+//! that has left it, which keeps tasks sharing a server isolated from each
+//! other. A scope can be narrowed later but never widened.
+//!
+//! Entries are either full origins (`https://example.com`, `http://localhost:8080`),
+//! which must match scheme, host and port exactly, or host patterns
+//! (`example.com`, `*.example.com`) which match over http and https on any port.
+
+use crate::error::{BrowserError, Result};
+use std::fmt;
+use url::Url;
+
+/// URLs that never leave the browser and are always in scope
+const ALWAYS_ALLOWED: &[&str] = &["about:blank"];
+
+/// Tools that may run while the active page is out of scope
+///
+/// These either check their own target URLs or only manage tabs and the
+/// session, and are needed to get back into scope.
+pub const UNSCOPED_TOOLS: &[&str] = &[
+    "navigate",
+    "new_tab",
+    "go_back",
+    "go_forward",
+    "tab_list",
+    "switch_tab",
+    "close_tab",
+    "close",
+    "crawl",
+    "screenshot_urls",
+    "set_scope",
+];
+
+/// One allowed origin or host pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ScopeEntry {
+    /// Exact scheme, host and port
+    Origin(url::Origin),
+    /// Host over http(s); `subdomains` also admits any subdomain of `host`
+    Host { host: String, subdomains: bool },
+}
+
+impl ScopeEntry {
+    fn parse(entry: &str) -> Result<Self> {
+        let entry = entry.trim().trim_end_matches('/');
+        let invalid = || BrowserError::InvalidArgument(format!("Invalid origin '{}'", entry));
+
+        if entry.contains("://") {
+            let url = Url::parse(entry).map_err(|_| invalid())?;
+            let origin = url.origin();
+            if !origin.is_tuple() || url.path() != "/" {
+                return Err(invalid());
+            }
+            return Ok(Self::Origin(origin));
+        }
+
+        let (host, subdomains) = match entry.strip_prefix("*.") {
+            Some(host) => (host, true),
+            None => (entry, false),
+        };
+        if host.is_empty() || host.contains(['/', ':', '*', ' ']) {
+            return Err(invalid());
+        }
+        Ok(Self::Host {
+            host: host.to_ascii_lowercase(),
+            subdomains,
+        })
+    }
+
+    fn allows(&self, url: &Url) -> bool {
+        match self {
+            Self::Origin(origin) => url.origin() == *origin,
+            Self::Host { host, subdomains } => {
+                let Some(url_host) = url.host_str() else {
+                    return false;
+                };
+                let url_host = url_host.to_ascii_lowercase();
+                matches!(url.scheme(), "http" | "https")
+                    && (url_host == *host
+                        || (*subdomains && url_host.ends_with(&format!(".{}", host))))
+            }
+        }
+    }
+
+    /// Whether every URL this entry admits is also admitted by `other`
+    fn within(&self, other: &ScopeEntry) -> bool {
+        match (self, other) {
+            (Self::Origin(origin), _) => {
+                Url::parse(&origin.ascii_serialization()).is_ok_and(|url| other.allows(&url))
+            }
+            (Self::Host { .. }, Self::Origin(_)) => false,
+            (
+                Self::Host { host, subdomains },
+                Self::Host {
+                    host: other_host,
+                    subdomains: other_subdomains,
+                },
+            ) => {
+                if *other_subdomains {
+                    host == other_host || host.ends_with(&format!(".{}", other_host))
+                } else {
+                    !subdomains && host == other_host
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for ScopeEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Origin(origin) => write!(f, "{}", origin.ascii_serialization()),
+            Self::Host {
+                host,
+                subdomains: true,
+            } => write!(f, "*.{}", host),
+            Self::Host { host, .. } => write!(f, "{}", host),
+        }
+    }
+}
+
+/// Set of origins a session is allowed to act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginScope {
+    entries: Vec<ScopeEntry>,
+}
+
+impl OriginScope {
+    /// Build a scope from origins (`https://example.com`) and host patterns (`*.example.com`)
+    pub fn new<I, S>(origins: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let entries = origins
+            .into_iter()
+            .map(|origin| ScopeEntry::parse(origin.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+
+        if entries.is_empty() {
+            return Err(BrowserError::InvalidArgument(
+                "A scope needs at least one origin".to_string(),
+            ));
+        }
+        Ok(Self { entries })
+    }
+
+    /// Allowed origins and patterns, in their normalized form
+    pub fn origins(&self) -> Vec<String> {
+        self.entries.iter().map(ToString::to_string).collect()
+    }
+
+    /// Check whether a URL is inside the scope
+    pub fn allows(&self, url: &str) -> bool {
+        let url = url.trim();
+        if ALWAYS_ALLOWED.contains(&url) {
+            return true;
+        }
+        match Url::parse(url) {
+            Ok(url) => self.entries.iter().any(|entry| entry.allows(&url)),
+            Err(_) => false,
+        }
+    }
+
+    /// Return [`BrowserError::PolicyDenied`] if the URL is outside the scope
+    pub fn check(&self, url: &str) -> Result<()> {
+        if self.allows(url) {
+            Ok(())
+        } else {
+            Err(BrowserError::PolicyDenied(format!(
+                "{} is outside the session scope ({})",
+                url,
+                self.origins().join(", ")
+            )))
+        }
+    }
+
+    /// Whether this scope admits nothing that `other` does not
+    pub fn is_within(&self, other: &OriginScope) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| other.entries.iter().any(|outer| entry.within(outer)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_origin() {
+        let scope = OriginScope::new(["https://example.com"]).unwrap();
+        assert!(scope.allows("https://example.com/a?b=1"));
+        assert!(scope.allows("about:blank"));
+        assert!(!scope.allows("http://example.com/"));
+        assert!(!scope.allows("https://example.com:8443/"));
+        assert!(!scope.allows("https://shop.example.com/"));
+        assert!(!scope.allows("data:text/html,hi"));
+    }
+
+    #[test]
+    fn test_host_patterns() {
+        let scope = OriginScope::new(["docs.rs", "*.example.com"]).unwrap();
+        assert!(scope.allows("https://docs.rs/serde"));
+        assert!(scope.allows("http://docs.rs:8080/"));
+        assert!(!scope.allows("https://www.docs.rs/"));
+        assert!(scope.allows("https://example.com/"));
+        assert!(scope.allows("https://a.b.example.com/"));
+        assert!(!scope.allows("https://notexample.com/"));
+        assert!(!scope.allows("file:///etc/passwd"));
+    }
+
+    #[test]
+    fn test_invalid_entries() {
+        assert!(OriginScope::new(Vec::<String>::new()).is_err());
+        assert!(OriginScope::new(["https://example.com/path"]).is_err());
+        assert!(OriginScope::new(["example.com/path"]).is_err());
+        assert!(OriginScope::new(["data:text/html,x"]).is_err());
+    }
+
+    #[test]
+    fn test_narrowing() {
+        let outer = OriginScope::new(["*.example.com", "https://docs.rs"]).unwrap();
+        let inner = OriginScope::new(["https://shop.example.com", "api.example.com"]).unwrap();
+        assert!(inner.is_within(&outer));
+        assert!(!outer.is_within(&inner));
+        assert!(!OriginScope::new(["docs.rs"]).unwrap().is_within(&outer));
+        assert!(
+            OriginScope::new(["https://docs.rs/"])
+                .unwrap()
+                .is_within(&outer)
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! In-memory screenshot capture
+//!
+//! [`BrowserSession::screenshot_bytes`] returns encoded image bytes without
+//! touching the filesystem, for services that stream images elsewhere.
+
+use crate::browser::BrowserSession;
+use crate::error::{BrowserError, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use headless_chrome::protocol::cdp::Page;
+use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+use serde::{Deserialize, Serialize};
+
+/// Image encoding for screenshots
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    /// Lossless PNG
+    #[default]
+    Png,
+    /// Lossy JPEG
+    Jpeg,
+    /// WebP
+    Webp,
+}
+
+impl ImageFormat {
+    /// MIME type of the encoded image
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg => "image/jpeg",
+            ImageFormat::Webp => "image/webp",
+        }
+    }
+
+    fn cdp_format(&self) -> CaptureScreenshotFormatOption {
+        match self {
+            ImageFormat::Png => CaptureScreenshotFormatOption::Png,
+            ImageFormat::Jpeg => CaptureScreenshotFormatOption::Jpeg,
+            ImageFormat::Webp => CaptureScreenshotFormatOption::Webp,
+        }
+    }
+}
+
+/// Options for capturing a screenshot
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScreenshotOptions {
+    /// Image encoding (default: PNG)
+    pub format: ImageFormat,
+
+    /// Compression quality 0-100 for JPEG and WebP (default: browser default)
+    pub quality: Option<u32>,
+
+    /// Capture the full scrollable page instead of the viewport (default: false)
+    pub full_page: bool,
+}
+
+impl ScreenshotOptions {
+    /// Create new ScreenshotOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: set image format
+    pub fn format(mut self, format: ImageFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Builder method: set JPEG/WebP quality
+    pub fn quality(mut self, quality: u32) -> Self {
+        self.quality = Some(quality.min(100));
+        self
+    }
+
+    /// Builder method: capture the full page
+    pub fn full_page(mut self, full_page: bool) -> Self {
+        self.full_page = full_page;
+        self
+    }
+}
+
+impl BrowserSession {
+    /// Capture the active tab and return the encoded image bytes
+    pub fn screenshot_bytes(&self, options: &ScreenshotOptions) -> Result<Vec<u8>> {
+        let tab = self.tab()?;
+
+        let quality = match options.format {
+            ImageFormat::Png => None,
+            _ => options.quality,
+        };
+
+        let clip = if options.full_page {
+            let metrics = tab
+                .call_method(Page::GetLayoutMetrics(None))
+                .map_err(|e| BrowserError::ScreenshotFailed(e.to_string()))?;
+            Some(Page::Viewport {
+                x: 0.0,
+                y: 0.0,
+                width: metrics.css_content_size.width,
+                height: metrics.css_content_size.height,
+                scale: 1.0,
+            })
+        } else {
+            None
+        };
+
+        let data = tab
+            .call_method(Page::CaptureScreenshot {
+                format: Some(options.format.cdp_format()),
+                quality,
+                clip,
+                from_surface: Some(true),
+                capture_beyond_viewport: Some(options.full_page),
+                optimize_for_speed: None,
+            })
+            .map_err(|e| BrowserError::ScreenshotFailed(e.to_string()))?
+            .data;
+
+        STANDARD
+            .decode(data)
+            .map_err(|e| BrowserError::ScreenshotFailed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screenshot_options_builder() {
+        let opts = ScreenshotOptions::new()
+            .format(ImageFormat::Jpeg)
+            .quality(150)
+            .full_page(true);
+
+        assert_eq!(opts.format, ImageFormat::Jpeg);
+        assert_eq!(opts.quality, Some(100));
+        assert!(opts.full_page);
+    }
+
+    #[test]
+    fn test_image_format_serde() {
+        let format: ImageFormat = serde_json::from_str("\"webp\"").unwrap();
+        assert_eq!(format, ImageFormat::Webp);
+        assert_eq!(format.mime_type(), "image/webp");
+    }
+}
@@ -0,0 +1,359 @@
+//! Bulk screenshot jobs
+//!
+//! [`BrowserSession::screenshot_urls`] visits a list of URLs on a small pool of
+//! tabs and saves one screenshot per URL, recording per-URL status and timing.
+//! Failures are reported per URL instead of aborting the job, which suits
+//! site-wide visual inventories and scheduled monitoring runs.
+
+use crate::browser::BrowserSession;
+use crate::browser::output::{create_dir_within, prepare_output_file};
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Options for a bulk screenshot job
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenshotJobOptions {
+    /// Directory screenshots are written to (default: "screenshots")
+    pub output_dir: PathBuf,
+
+    /// Capture the full scrollable page instead of the viewport (default: false)
+    pub full_page: bool,
+
+    /// Number of tabs capturing in parallel (default: 4)
+    pub concurrency: usize,
+
+    /// Extra delay after load before capturing, for late-rendering content (default: 0)
+    pub settle_ms: u64,
+
+    /// Navigation timeout per URL in milliseconds (default: 30000)
+    pub page_timeout_ms: u64,
+
+    /// Replace screenshots that already exist (default: false)
+    pub overwrite: bool,
+}
+
+impl Default for ScreenshotJobOptions {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("screenshots"),
+            full_page: false,
+            concurrency: 4,
+            settle_ms: 0,
+            page_timeout_ms: 30_000,
+            overwrite: false,
+        }
+    }
+}
+
+impl ScreenshotJobOptions {
+    /// Create new ScreenshotJobOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: set output directory
+    pub fn output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = dir.into();
+        self
+    }
+
+    /// Builder method: capture full pages
+    pub fn full_page(mut self, full_page: bool) -> Self {
+        self.full_page = full_page;
+        self
+    }
+
+    /// Builder method: set number of parallel tabs
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Builder method: set delay between load and capture
+    pub fn settle(mut self, settle_ms: u64) -> Self {
+        self.settle_ms = settle_ms;
+        self
+    }
+
+    /// Builder method: set per-URL navigation timeout
+    pub fn page_timeout(mut self, timeout_ms: u64) -> Self {
+        self.page_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Builder method: replace screenshots that already exist
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+}
+
+/// Outcome of capturing a single URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotJobResult {
+    /// URL that was requested
+    pub url: String,
+
+    /// Path of the saved screenshot, if capture succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// HTTP status of the document (0 when unknown or the page failed to load)
+    pub status: u16,
+
+    /// Size of the saved image in bytes
+    pub size_bytes: usize,
+
+    /// Time spent loading the page in milliseconds
+    pub load_ms: u64,
+
+    /// Time spent capturing and saving the screenshot in milliseconds
+    pub capture_ms: u64,
+
+    /// Error message if the URL could not be captured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ScreenshotJobResult {
+    fn failed(url: &str, load_ms: u64, error: BrowserError) -> Self {
+        Self {
+            url: url.to_string(),
+            path: None,
+            status: 0,
+            size_bytes: 0,
+            load_ms,
+            capture_ms: 0,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// Build a file name like `003-example.com-docs-intro.png` for the URL at `index`
+fn screenshot_file_name(index: usize, url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let mut slug: String = without_scheme
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    slug.truncate(80);
+    if slug.is_empty() {
+        slug.push_str("page");
+    }
+    format!("{:03}-{}.png", index, slug)
+}
+
+fn capture_url(
+    session: &BrowserSession,
+    tab: &Tab,
+    url: &str,
+    path: &Path,
+    options: &ScreenshotJobOptions,
+) -> ScreenshotJobResult {
+    let started = Instant::now();
+
+    let loaded = (|| -> Result<u16> {
+        session.check_navigation(url)?;
+        let _permit = session.throttle(url);
+
+        tab.navigate_to(url)
+            .and_then(|tab| tab.wait_until_navigated())
+            .map_err(|e| {
+                BrowserError::NavigationFailed(format!("Failed to navigate to {}: {}", url, e))
+            })?;
+
+        let status = tab
+            .evaluate(
+                "(performance.getEntriesByType('navigation')[0] || {}).responseStatus || 0",
+                false,
+            )
+            .ok()
+            .and_then(|r| r.value)
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        Ok(status as u16)
+    })();
+    let load_ms = started.elapsed().as_millis() as u64;
+
+    let status = match loaded {
+        Ok(status) => status,
+        Err(e) => return ScreenshotJobResult::failed(url, load_ms, e),
+    };
+
+    if options.settle_ms > 0 {
+        std::thread::sleep(Duration::from_millis(options.settle_ms));
+    }
+
+    let capture_started = Instant::now();
+    let captured = tab
+        .capture_screenshot(
+            CaptureScreenshotFormatOption::Png,
+            None,
+            None,
+            options.full_page,
+        )
+        .map_err(|e| BrowserError::ScreenshotFailed(e.to_string()))
+        .and_then(|data| {
+            std::fs::write(path, &data).map_err(|e| {
+                BrowserError::ScreenshotFailed(format!("Failed to save screenshot: {}", e))
+            })?;
+            Ok(data.len())
+        });
+
+    match captured {
+        Ok(size_bytes) => ScreenshotJobResult {
+            url: url.to_string(),
+            path: Some(path.to_string_lossy().into_owned()),
+            status,
+            size_bytes,
+            load_ms,
+            capture_ms: capture_started.elapsed().as_millis() as u64,
+            error: None,
+        },
+        Err(e) => ScreenshotJobResult {
+            status,
+            ..ScreenshotJobResult::failed(url, load_ms, e)
+        },
+    }
+}
+
+impl BrowserSession {
+    /// Capture a screenshot of every URL, returning results in input order
+    ///
+    /// URLs are distributed over `options.concurrency` temporary tabs that share
+    /// the session's cookies, extra headers, rendering settings, scope,
+    /// confirmation rules, robots.txt policy and rate limits. Per-URL
+    /// failures, including screenshots that exist and may not be replaced,
+    /// are recorded in the results rather than returned as errors.
+    pub fn screenshot_urls<I, S>(
+        &self,
+        urls: I,
+        options: &ScreenshotJobOptions,
+    ) -> Result<Vec<ScreenshotJobResult>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let urls: Vec<String> = urls
+            .into_iter()
+            .map(|url| url.as_ref().trim().to_string())
+            .collect();
+        if urls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Inside the session's output directory, only through paths that stay in it
+        match self.output_dir() {
+            Some(root) => {
+                create_dir_within(root, &options.output_dir)?;
+            }
+            None => std::fs::create_dir_all(&options.output_dir)?,
+        }
+
+        let settings = self.tab_settings();
+        let mut tabs = Vec::new();
+        for _ in 0..options.concurrency.clamp(1, urls.len()) {
+            let tab = self.browser()?.new_tab().map_err(|e| {
+                BrowserError::TabOperationFailed(format!("Failed to create tab: {}", e))
+            })?;
+            tab.set_default_timeout(Duration::from_millis(options.page_timeout_ms));
+            settings.apply(&tab)?;
+            tabs.push(tab);
+        }
+
+        let next_index = Mutex::new(0usize);
+        let results: Mutex<Vec<Option<ScreenshotJobResult>>> = Mutex::new(vec![None; urls.len()]);
+
+        std::thread::scope(|scope| {
+            for tab in &tabs {
+                scope.spawn(|| {
+                    loop {
+                        let index = {
+                            let mut next = next_index.lock().unwrap_or_else(|e| e.into_inner());
+                            let index = *next;
+                            *next += 1;
+                            index
+                        };
+                        let Some(url) = urls.get(index) else {
+                            break;
+                        };
+
+                        let path = options
+                            .output_dir
+                            .join(screenshot_file_name(index + 1, url));
+                        let result = match prepare_output_file(
+                            self.output_dir(),
+                            &path,
+                            options.overwrite,
+                        ) {
+                            Ok(path) => capture_url(self, tab, url, &path, options),
+                            Err(e) => ScreenshotJobResult::failed(url, 0, e),
+                        };
+                        results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(result);
+                    }
+                });
+            }
+        });
+
+        for tab in tabs {
+            let _ = tab.close(false);
+        }
+
+        Ok(results
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner())
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options_builder() {
+        let opts = ScreenshotJobOptions::new()
+            .output_dir("/tmp/shots")
+            .full_page(true)
+            .concurrency(0)
+            .settle(500);
+
+        assert_eq!(opts.output_dir, PathBuf::from("/tmp/shots"));
+        assert!(opts.full_page);
+        assert_eq!(opts.concurrency, 1);
+        assert_eq!(opts.settle_ms, 500);
+        assert!(!opts.overwrite);
+        assert!(opts.overwrite(true).overwrite);
+    }
+
+    #[test]
+    fn test_screenshot_file_name() {
+        assert_eq!(
+            screenshot_file_name(3, "https://example.com/docs/intro?x=1"),
+            "003-example.com-docs-intro-x-1.png"
+        );
+        assert_eq!(
+            screenshot_file_name(1, "about:blank"),
+            "001-about-blank.png"
+        );
+        assert_eq!(screenshot_file_name(12, "///"), "012-page.png");
+    }
+}
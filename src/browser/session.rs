@@ -1,11 +1,46 @@
-use crate::browser::config::{ConnectionOptions, LaunchOptions};
-use crate::dom::DomTree;
+use crate::browser::bus::{SessionEvent, SessionEventBus, SessionEventKinds, SubscriptionId};
+use crate::browser::checkpoint::{Checkpoint, CheckpointStore};
+use crate::browser::config::{ConnectionOptions, LaunchOptions, validate_executable};
+use crate::browser::confirmation::ConfirmationPolicy;
+use crate::browser::crash::CrashWatcher;
+use crate::browser::deterministic::DeterministicOptions;
+use crate::browser::events::{PageEvent, PageEventWatcher};
+use crate::browser::injected::InjectedHelpers;
+use crate::browser::mock::MockSession;
+use crate::browser::navigation::{
+    LoadStatus, NavigateOptions, NavigationRecorder, NavigationResponse, PageLoad,
+};
+use crate::browser::network::NetworkCapture;
+use crate::browser::output::{
+    create_dir_within, prepare_output_file, resolve_input_path, resolve_output_path,
+};
+use crate::browser::policy::EvaluatePolicy;
+use crate::browser::profile;
+use crate::browser::proxy::ProxyOptions;
+use crate::browser::rate_limit::{RateLimitOptions, RateLimiter, RatePermit};
+use crate::browser::rng::SessionRng;
+use crate::browser::robots::RobotsChecker;
+use crate::browser::routes::{RouteChange, RouteWatcher};
+use crate::browser::scope::{OriginScope, UNSCOPED_TOOLS};
+use crate::browser::status::{ConnectionState, SessionInfo, TargetSummary};
+use crate::browser::storage_state::{OriginState, StorageCookie, StorageEntry, StorageState};
+use crate::browser::tabs::{TabSummary, TabTracker, next_active_index};
+use crate::browser::viewport::Viewport;
+use crate::dom::selector::DeepSelector;
+use crate::dom::suggest::{self, ElementSuggestion};
+use crate::dom::{DomTree, IndexOrder};
 use crate::error::{BrowserError, Result};
 use crate::tools::{ToolContext, ToolRegistry};
+use headless_chrome::protocol::cdp::{Network, Page, Target};
 use headless_chrome::{Browser, Tab};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
 use std::ffi::OsStr;
-use std::sync::Arc;
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 /// Wrapper for Tab and Element to maintain proper lifetime relationships
 pub struct TabElement<'a> {
@@ -15,16 +50,233 @@ pub struct TabElement<'a> {
 
 /// Browser session that manages a Chrome/Chromium instance
 pub struct BrowserSession {
-    /// The underlying headless_chrome Browser instance
-    browser: Browser,
+    /// Chrome, or the scripted page of a mock session
+    driver: Driver,
 
     /// Tool registry for executing browser automation tools
     tool_registry: ToolRegistry,
+
+    /// robots.txt checker, present when compliance mode is enabled
+    robots: Option<RobotsChecker>,
+
+    /// Per-host rate limiter, present when throttling is enabled; shared with crawls
+    rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// Directory file-writing tools are confined to
+    output_dir: Option<PathBuf>,
+
+    /// Restrictions on the evaluate tool
+    evaluate_policy: EvaluatePolicy,
+
+    /// Actions that need human approval
+    confirmation: ConfirmationPolicy,
+
+    /// Origins the session may act on; can be narrowed at runtime but never widened
+    scope: RwLock<Option<OriginScope>>,
+
+    /// Reproducible rendering settings applied to every tab the session opens
+    determinism: Option<DeterministicOptions>,
+
+    /// Proxy whose authentication challenges every tab answers
+    proxy: Option<ProxyOptions>,
+
+    /// Viewport every tab is rendered at, when one is pinned
+    viewport: RwLock<Option<Viewport>>,
+
+    /// Headers sent with every request the session's tabs make
+    extra_headers: RwLock<HashMap<String, String>>,
+
+    /// Navigation started by [`BrowserSession::navigate`] that has not been waited for
+    pending_load: Mutex<Option<PageLoad>>,
+
+    /// How long to wait for a page to load unless a call says otherwise
+    navigation_timeout: Duration,
+
+    /// Tabs whose renderer crashed
+    crashes: CrashWatcher,
+
+    /// Reload a crashed tab when it is next used instead of failing
+    reload_on_crash: bool,
+
+    /// XHR/fetch traffic, recorded once a tool first asks for it
+    network: NetworkCapture,
+
+    /// Client-side route changes, watched once a caller first asks for them
+    routes: RouteWatcher,
+
+    /// Latest route change already accounted for by a navigation or wait
+    routes_seen: AtomicU64,
+
+    /// Console errors, page errors and failed requests, watched once a callback is added
+    page_events: PageEventWatcher,
+
+    /// Navigations, dialogs, downloads and crashes, watched once a callback is added
+    session_events: SessionEventBus,
+
+    /// How extracted DOM trees number their elements
+    index_order: IndexOrder,
+
+    /// Tab tools act on, and the tabs already set up
+    tabs: TabTracker,
+
+    /// Helper bundle installed into each document JS-backed tools act on
+    helpers: InjectedHelpers,
+
+    /// Randomness for human-like behavior, seeded so runs can be replayed
+    rng: SessionRng,
+
+    /// Recent DOM snapshots, to suggest elements for stale indices
+    dom_snapshots: Mutex<DomSnapshots>,
+
+    /// Actions that may have changed page state the DOM does not show, such
+    /// as typed values or hover styles
+    page_actions: AtomicU64,
+
+    /// Page states the session can roll back to
+    checkpoints: CheckpointStore,
+
+    /// localStorage seeds still waiting for their origin to load
+    storage_seeds: Mutex<Vec<StorageSeed>>,
+
+    /// When the browser was launched, connected to or restarted
+    started: Instant,
+
+    /// How the browser was obtained, for restarts
+    origin: SessionOrigin,
+}
+
+/// The two most recently extracted DOM trees
+#[derive(Default)]
+struct DomSnapshots {
+    latest: Option<DomTree>,
+    previous: Option<DomTree>,
+}
+
+/// Identifies the state of the page a DOM tree was extracted from
+///
+/// Two generations are equal when the same document is shown, no DOM
+/// mutation happened and no action was recorded in between. See
+/// [`BrowserSession::page_generation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageGeneration {
+    /// Target id and loader id of the active tab's document, if known
+    document: Option<String>,
+    /// DOM mutations observed in the document so far, if observable
+    mutations: Option<u64>,
+    /// Actions recorded with [`BrowserSession::record_page_action`]
+    actions: u64,
+}
+
+impl PageGeneration {
+    /// Whether a tree extracted at `self` still describes the page at `current`
+    ///
+    /// An unknown document never matches, so a failed lookup causes a fresh
+    /// extraction rather than a stale tree.
+    pub fn matches(&self, current: &PageGeneration) -> bool {
+        self.document.is_some() && self == current
+    }
+}
+
+/// A localStorage seed registered for an origin the tab has not loaded yet
+struct StorageSeed {
+    tab: Arc<Tab>,
+    origin: String,
+    identifier: Page::ScriptIdentifier,
+}
+
+impl StorageSeed {
+    /// Whether the tab shows the origin and the seed has run in it
+    fn is_seeded(&self) -> bool {
+        let js = format!(
+            "window.location.origin === {} && window.sessionStorage.getItem('__browser_use_storage_state__') !== null",
+            serde_json::Value::from(self.origin.as_str())
+        );
+        self.tab
+            .evaluate(&js, false)
+            .ok()
+            .and_then(|result| result.value)
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+    }
+
+    fn remove(&self) {
+        if let Err(e) = self
+            .tab
+            .call_method(Page::RemoveScriptToEvaluateOnNewDocument {
+                identifier: self.identifier.clone(),
+            })
+        {
+            log::warn!("Failed to remove storage seed for {}: {}", self.origin, e);
+        }
+    }
+}
+
+/// What a session drives
+enum Driver {
+    Chrome(Browser),
+    Mock(MockSession),
+}
+
+/// How a session's browser was obtained, so it can be restarted the same way
+enum SessionOrigin {
+    Launched(Box<LaunchOptions>),
+    Connected(ConnectionOptions),
 }
 
 impl BrowserSession {
     /// Launch a new browser instance with the given options
     pub fn launch(options: LaunchOptions) -> Result<Self> {
+        let browser = Self::launch_browser(&options)?;
+        Ok(Self::with_driver(Driver::Chrome(browser), options))
+    }
+
+    /// A session running against a scripted page instead of Chrome; see [`MockSession`]
+    pub fn from_mock(mock: MockSession, options: LaunchOptions) -> Self {
+        Self::with_driver(Driver::Mock(mock), options)
+    }
+
+    fn with_driver(driver: Driver, options: LaunchOptions) -> Self {
+        let launch_options = options.clone();
+        let viewport = options.viewport();
+        Self {
+            driver,
+            tool_registry: ToolRegistry::with_defaults(),
+            robots: options.robots_user_agent.map(RobotsChecker::new),
+            rate_limiter: options
+                .rate_limit
+                .map(|limit| Arc::new(RateLimiter::new(limit))),
+            output_dir: options.output_dir,
+            evaluate_policy: options.evaluate_policy,
+            confirmation: options.confirmation,
+            scope: RwLock::new(options.scope),
+            determinism: options.determinism,
+            proxy: options.proxy,
+            viewport: RwLock::new(viewport),
+            extra_headers: RwLock::new(HashMap::new()),
+            pending_load: Mutex::new(None),
+            navigation_timeout: Duration::from_millis(options.navigation_timeout_ms),
+            crashes: CrashWatcher::new(),
+            reload_on_crash: options.reload_on_crash,
+            network: NetworkCapture::default(),
+            routes: RouteWatcher::new(),
+            routes_seen: AtomicU64::new(0),
+            page_events: PageEventWatcher::new(),
+            session_events: SessionEventBus::new(),
+            tabs: TabTracker::new(),
+            helpers: InjectedHelpers::new(),
+            index_order: options.index_order,
+            rng: session_rng(options.rng_seed),
+            dom_snapshots: Mutex::default(),
+            page_actions: AtomicU64::new(0),
+            checkpoints: CheckpointStore::new(),
+            storage_seeds: Mutex::default(),
+            started: Instant::now(),
+            origin: SessionOrigin::Launched(Box::new(launch_options)),
+        }
+    }
+
+    /// Start a Chrome process with one open tab
+    fn launch_browser(options: &LaunchOptions) -> Result<Browser> {
         let mut launch_opts = headless_chrome::LaunchOptions::default();
 
         // Ignore default arguments to prevent detection by anti-bot services
@@ -45,41 +297,175 @@ impl BrowserSession {
         launch_opts.window_size = Some((options.window_width, options.window_height));
 
         // Set Chrome binary path if provided
-        if let Some(path) = options.chrome_path {
-            launch_opts.path = Some(path);
+        if let Some(path) = &options.chrome_path {
+            validate_executable(path)?;
+            launch_opts.path = Some(path.clone());
         }
 
-        // Set user data directory if provided
-        if let Some(dir) = options.user_data_dir {
-            launch_opts.user_data_dir = Some(dir);
+        // Set user data directory if provided, once no other browser holds it
+        if let Some(dir) = &options.user_data_dir {
+            profile::prepare_user_data_dir(dir)?;
+            launch_opts.user_data_dir = Some(dir.clone());
         }
 
         // Set sandbox mode
         launch_opts.sandbox = options.sandbox;
 
+        // headless_chrome ignores certificate errors unless told otherwise
+        launch_opts.ignore_certificate_errors = options.ignore_https_errors;
+
+        // Fixed rendering flags, and a process timezone for workers and popups
+        let deterministic_args = options
+            .determinism
+            .as_ref()
+            .map(DeterministicOptions::launch_args)
+            .unwrap_or_default();
+        launch_opts
+            .args
+            .extend(deterministic_args.iter().map(OsStr::new));
+        // Route traffic through the proxy; its credentials are answered per tab
+        let proxy_args = options
+            .proxy
+            .as_ref()
+            .map(ProxyOptions::launch_args)
+            .unwrap_or_default();
+        launch_opts.args.extend(proxy_args.iter().map(OsStr::new));
+
+        // The caller's flags last, so they can override ours
+        launch_opts.args.extend(options.args.iter().map(OsStr::new));
+
+        // The caller's environment, with the deterministic timezone on top
+        let mut envs = options.env.clone();
+        if let Some(determinism) = &options.determinism {
+            envs.insert("TZ".to_string(), determinism.timezone.clone());
+        }
+        if !envs.is_empty() {
+            launch_opts.process_envs = Some(envs);
+        }
+
         // Launch browser
         let browser =
             Browser::new(launch_opts).map_err(|e| BrowserError::LaunchFailed(e.to_string()))?;
 
-        browser
+        let tab = browser
             .new_tab()
             .map_err(|e| BrowserError::LaunchFailed(format!("Failed to create tab: {}", e)))?;
+        if let Some(determinism) = &options.determinism {
+            determinism.apply(&tab)?;
+        }
+        if let Some(proxy) = &options.proxy {
+            proxy.apply(&tab)?;
+        }
+        if let Some(viewport) = options.viewport() {
+            viewport.apply(&tab)?;
+        }
 
-        Ok(Self {
-            browser,
-            tool_registry: ToolRegistry::with_defaults(),
-        })
+        Ok(browser)
+    }
+
+    /// Tear down the browser and start a fresh one with the same options
+    ///
+    /// An escape hatch for when the renderer gets into a bad state. Session
+    /// settings (policies, scope, output directory) are kept. With
+    /// `preserve_state`, cookies and localStorage are carried over on a
+    /// best-effort basis; open tabs and page state are always lost. Sessions
+    /// created with [`BrowserSession::connect`] reconnect instead, since the
+    /// external browser is not ours to relaunch.
+    ///
+    /// The old browser is closed before the new one starts (a persistent
+    /// profile cannot be opened twice), so on error the session is gone.
+    pub fn restart(self, preserve_state: bool) -> Result<Self> {
+        if self.mock().is_some() {
+            return Ok(self);
+        }
+
+        let state = if preserve_state {
+            self.storage_state()
+                .map_err(|e| log::warn!("Could not save state before restart: {}", e))
+                .ok()
+        } else {
+            None
+        };
+
+        let watching_routes = self.routes.is_active();
+        let watching_events = self.page_events.is_active();
+        let watching_session_events = self.session_events.is_active();
+        let _ = self.close();
+        drop(self.driver);
+
+        let browser = match &self.origin {
+            SessionOrigin::Launched(options) => Self::launch_browser(options)?,
+            SessionOrigin::Connected(options) => {
+                let browser = Self::connect_browser(options)?;
+                browser.new_tab().map_err(|e| {
+                    BrowserError::TabOperationFailed(format!("Failed to create tab: {}", e))
+                })?;
+                browser
+            }
+        };
+
+        let restarted = Self {
+            driver: Driver::Chrome(browser),
+            pending_load: Mutex::new(None),
+            network: NetworkCapture::default(),
+            tabs: TabTracker::new(),
+            helpers: InjectedHelpers::new(),
+            crashes: CrashWatcher::new(),
+            dom_snapshots: Mutex::default(),
+            storage_seeds: Mutex::default(),
+            started: Instant::now(),
+            ..self
+        };
+        if let Some(state) = state {
+            restarted.set_storage_state(&state)?;
+        }
+        // A viewport set at runtime outlives the browser
+        if let Some(viewport) = restarted.viewport() {
+            viewport.apply(&*restarted.tab()?)?;
+        }
+        if watching_routes {
+            restarted.route_watcher()?;
+        }
+        if watching_events {
+            restarted.page_events.attach(&restarted.tab()?)?;
+        }
+        if watching_session_events {
+            restarted.session_events.attach(&restarted.tab()?)?;
+        }
+        Ok(restarted)
     }
 
     /// Connect to an existing browser instance via WebSocket
     pub fn connect(options: ConnectionOptions) -> Result<Self> {
-        let browser = Browser::connect(options.ws_url)
-            .map_err(|e| BrowserError::ConnectionFailed(e.to_string()))?;
+        Self::connect_with_options(options, LaunchOptions::default())
+    }
 
-        Ok(Self {
-            browser,
-            tool_registry: ToolRegistry::with_defaults(),
-        })
+    /// Connect to an existing browser, with the session settings of `options`
+    ///
+    /// Policies, scope, output directory, timeouts, seeding and per-tab
+    /// emulation (viewport, determinism, proxy credentials) apply as for a
+    /// launched browser, and the active tab is set up with them. Options that
+    /// only take effect when starting Chrome, such as flags, the executable and
+    /// the profile directory, are ignored.
+    pub fn connect_with_options(
+        connection: ConnectionOptions,
+        options: LaunchOptions,
+    ) -> Result<Self> {
+        let browser = Self::connect_browser(&connection)?;
+        let session = Self {
+            origin: SessionOrigin::Connected(connection),
+            ..Self::with_driver(Driver::Chrome(browser), options)
+        };
+        if session.determinism.is_some() || session.proxy.is_some() || session.viewport().is_some()
+        {
+            session.setup_tab(&session.tab()?)?;
+        }
+        Ok(session)
+    }
+
+    fn connect_browser(options: &ConnectionOptions) -> Result<Browser> {
+        let options = options.clone().resolve()?;
+        Browser::connect(options.ws_url).map_err(|e| BrowserError::ConnectionFailed(e.to_string()))
     }
 
     /// Launch a browser with default options
@@ -94,16 +480,187 @@ impl BrowserSession {
 
     /// Create a new tab and set it as active
     pub fn new_tab(&mut self) -> Result<Arc<Tab>> {
-        let tab = self.browser.new_tab().map_err(|e| {
+        self.open_blank_tab()
+    }
+
+    fn open_blank_tab(&self) -> Result<Arc<Tab>> {
+        let tab = self.browser()?.new_tab().map_err(|e| {
             BrowserError::TabOperationFailed(format!("Failed to create tab: {}", e))
         })?;
+        self.tabs.add(tab.get_target_id());
+        self.setup_tab(&tab)?;
+        self.tabs.set_active(tab.get_target_id());
         Ok(tab)
     }
 
+    /// Open a blank tab in a new incognito browser context and make it the active tab
+    ///
+    /// The context shares no cookies, storage or cache with the default
+    /// context or other incognito contexts, so tasks run in separate contexts
+    /// of one Chrome do not see each other's state. Returns the context id,
+    /// for [`BrowserSession::close_incognito_context`].
+    pub fn new_incognito_context(&self) -> Result<String> {
+        let context = self.browser()?.new_context().map_err(|e| {
+            BrowserError::TabOperationFailed(format!("Failed to create browser context: {}", e))
+        })?;
+        let tab = context.new_tab().map_err(|e| {
+            BrowserError::TabOperationFailed(format!("Failed to create tab in context: {}", e))
+        })?;
+        self.tabs.add(tab.get_target_id());
+        self.setup_tab(&tab)?;
+        self.tabs.set_active(tab.get_target_id());
+        Ok(context.get_id().to_string())
+    }
+
+    /// Close the tabs of incognito context `id`
+    ///
+    /// The session never opens another tab in the context, so its cookies and
+    /// storage are out of reach afterwards. When the active tab belonged to
+    /// the context, the visible tab of the remaining ones becomes active.
+    pub fn close_incognito_context(&self, id: &str) -> Result<()> {
+        let tabs: Vec<Arc<Tab>> = self
+            .get_tabs()?
+            .into_iter()
+            .filter(|tab| tab.get_browser_context_id().ok().flatten().as_deref() == Some(id))
+            .collect();
+        if tabs.is_empty() {
+            return Err(BrowserError::TabOperationFailed(format!(
+                "No tabs in browser context {}",
+                id
+            )));
+        }
+        for tab in tabs {
+            let tab_id = tab.get_target_id();
+            tab.close(true).map_err(|e| {
+                BrowserError::TabOperationFailed(format!("Failed to close tab {}: {}", tab_id, e))
+            })?;
+            self.crashes.clear(tab_id);
+            self.tabs.remove(tab_id);
+        }
+        Ok(())
+    }
+
+    /// Apply the session's headers, rendering settings and watchers to a tab
+    fn setup_tab(&self, tab: &Arc<Tab>) -> Result<()> {
+        self.tab_settings().apply(tab)?;
+        if self.network.is_active() {
+            self.network.attach(tab)?;
+        }
+        if self.routes.is_active() {
+            self.routes.attach(tab)?;
+        }
+        if self.page_events.is_active() {
+            self.page_events.attach(tab)?;
+        }
+        if self.session_events.is_active() {
+            self.session_events.attach(tab)?;
+        }
+        Ok(())
+    }
+
+    /// Set up tabs that pages opened since the last look
+    ///
+    /// A popup of the active tab becomes the active tab, as it comes to the
+    /// front in a headed browser.
+    fn adopt_popups(&self, tabs: &[Arc<Tab>]) {
+        for tab in self.tabs.new_tabs(tabs) {
+            let Some(opener) = tab.get_target_info().ok().and_then(|info| info.opener_id) else {
+                continue;
+            };
+            if let Err(e) = self.setup_tab(&tab) {
+                log::warn!("Failed to set up tab {}: {}", tab.get_target_id(), e);
+            }
+            if self.tabs.active().as_ref() == Some(&opener) {
+                self.tabs.set_active(tab.get_target_id());
+            }
+        }
+    }
+
+    /// Open a tab, load `url` in it and make it the active tab
+    ///
+    /// The navigation goes through the same scope, confirmation, robots and
+    /// rate-limit checks as [`BrowserSession::navigate`]; if it fails the tab
+    /// is closed and the previous tab stays active.
+    pub fn open_tab(&self, url: &str) -> Result<Arc<Tab>> {
+        let previous = self.tabs.active();
+        let tab = self.open_blank_tab()?;
+        let loaded = self.navigate(url).and_then(|_| self.wait_for_navigation());
+        if let Err(e) = loaded {
+            self.tabs.remove(tab.get_target_id());
+            tab.close(false).ok();
+            if let Some(previous) = previous {
+                self.tabs.set_active(&previous);
+            }
+            return Err(e);
+        }
+        Ok(tab)
+    }
+
+    /// The session's tabs in browser order, marking the active one
+    pub fn list_tabs(&self) -> Result<Vec<TabSummary>> {
+        let active = self.tab()?;
+        Ok(self
+            .get_tabs()?
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| TabSummary {
+                id: tab.get_target_id().clone(),
+                index,
+                title: tab.get_title().unwrap_or_default(),
+                url: tab.get_url(),
+                active: Arc::ptr_eq(tab, &active),
+            })
+            .collect())
+    }
+
+    /// Bring the tab with target id `id` to the front and make tools act on it
+    pub fn switch_to_tab(&self, id: &str) -> Result<Arc<Tab>> {
+        let tab = self.find_tab(id)?;
+        tab.activate().map_err(|e| {
+            BrowserError::TabOperationFailed(format!("Failed to activate tab {}: {}", id, e))
+        })?;
+        self.tabs.set_active(id);
+        Ok(tab)
+    }
+
+    /// Close the tab with target id `id`
+    ///
+    /// When it was the active tab, the tab that takes its place in the tab
+    /// list becomes active.
+    pub fn close_tab(&self, id: &str) -> Result<()> {
+        let tabs = self.get_tabs()?;
+        let index = tabs
+            .iter()
+            .position(|tab| tab.get_target_id() == id)
+            .ok_or_else(|| BrowserError::TabOperationFailed(format!("No tab with id {}", id)))?;
+        tabs[index]
+            .close(true)
+            .map_err(|e| BrowserError::TabOperationFailed(format!("Failed to close tab: {}", e)))?;
+
+        self.crashes.clear(id);
+        if self.tabs.remove(id)
+            && let Some(next) = next_active_index(index, tabs.len())
+        {
+            let remaining: Vec<_> = tabs
+                .iter()
+                .filter(|tab| tab.get_target_id() != id)
+                .collect();
+            self.switch_to_tab(remaining[next].get_target_id())?;
+        }
+        Ok(())
+    }
+
+    fn find_tab(&self, id: &str) -> Result<Arc<Tab>> {
+        self.get_tabs()?
+            .into_iter()
+            .find(|tab| tab.get_target_id() == id)
+            .ok_or_else(|| BrowserError::TabOperationFailed(format!("No tab with id {}", id)))
+    }
+
     /// Get all tabs
     pub fn get_tabs(&self) -> Result<Vec<Arc<Tab>>> {
         let tabs = self
-            .browser
+            .browser()?
             .get_tabs()
             .lock()
             .map_err(|e| BrowserError::TabOperationFailed(format!("Failed to get tabs: {}", e)))?
@@ -112,22 +669,171 @@ impl BrowserSession {
         Ok(tabs)
     }
 
-    /// Get the currently active tab by checking the document visibility and focus state
+    /// Get the tab tools act on
+    ///
+    /// That is the tab last opened or switched to through the session, or a
+    /// popup it opened. Until there is one, the tab whose document is visible
+    /// and focused is picked and kept.
+    ///
+    /// A tab whose renderer crashed fails with [`BrowserError::PageCrashed`],
+    /// or is reloaded first when the session reloads crashed tabs.
     pub fn get_active_tab(&self) -> Result<Arc<Tab>> {
+        let tab = self.active_tab()?;
+        self.recover_crashed(&tab)?;
+        Ok(tab)
+    }
+
+    /// The active tab, whether or not it crashed
+    fn active_tab(&self) -> Result<Arc<Tab>> {
         let tabs = self.get_tabs()?;
+        self.adopt_popups(&tabs);
+        if let Some(active) = self.tabs.active()
+            && let Some(tab) = tabs.iter().find(|tab| *tab.get_target_id() == active)
+        {
+            return Ok(tab.clone());
+        }
+        let tab = Self::focused_tab(&tabs)?;
+        self.tabs.set_active(tab.get_target_id());
+        Ok(tab)
+    }
+
+    /// Watch the tab for crashes, and deal with one that already happened
+    fn recover_crashed(&self, tab: &Arc<Tab>) -> Result<()> {
+        let id = tab.get_target_id();
+        if !self.crashes.is_crashed(id) {
+            if let Err(e) = self.crashes.attach(tab) {
+                log::debug!("Not watching tab {} for crashes: {}", id, e);
+            }
+            return Ok(());
+        }
+        if !self.reload_on_crash {
+            return Err(BrowserError::PageCrashed(format!(
+                "Tab {} crashed (renderer crash or out of memory); navigate it elsewhere, \
+                 close it or restart the browser",
+                id
+            )));
+        }
+
+        log::warn!("Reloading crashed tab {}", id);
+        tab.reload(false, None).map_err(|e| {
+            BrowserError::PageCrashed(format!("Tab {} crashed and did not reload: {}", id, e))
+        })?;
+        self.crashes.clear(id);
+        self.record_page_action();
+        Ok(())
+    }
+
+    /// Whether a tab of the session crashed and was not recovered yet
+    pub fn is_degraded(&self) -> bool {
+        !self.crashes.crashed().is_empty()
+    }
+
+    /// Target ids of tabs whose renderer crashed and that were not recovered yet
+    pub fn crashed_tabs(&self) -> Vec<String> {
+        self.crashes.crashed()
+    }
 
+    /// Return an error if Chrome exited or its DevTools connection dropped
+    ///
+    /// Sends a cheap `Browser.getVersion` round trip; mock sessions are always
+    /// connected. Once this fails the session cannot recover and has to be
+    /// replaced by a new one.
+    pub fn check_connection(&self) -> Result<()> {
+        match &self.driver {
+            Driver::Chrome(browser) => browser.get_version().map(|_| ()).map_err(|e| {
+                BrowserError::BrowserDisconnected(format!("Chrome is not responding: {}", e))
+            }),
+            Driver::Mock(_) => Ok(()),
+        }
+    }
+
+    /// Whether Chrome is still running and answering over the websocket
+    pub fn is_alive(&self) -> bool {
+        self.check_connection().is_ok()
+    }
+
+    /// Chrome version, open targets, uptime and websocket state
+    ///
+    /// Never fails: a browser that does not answer is reported as
+    /// disconnected with the error, so the call can diagnose a dead browser.
+    pub fn session_info(&self) -> SessionInfo {
+        let mut info = SessionInfo {
+            alive: true,
+            websocket: ConnectionState::Connected,
+            error: None,
+            chrome_version: None,
+            protocol_version: None,
+            targets: Vec::new(),
+            active_tab: self.tabs.active(),
+            crashed_tabs: self.crashed_tabs(),
+            uptime: self.started.elapsed(),
+        };
+        let browser = match &self.driver {
+            Driver::Chrome(browser) => browser,
+            Driver::Mock(_) => {
+                info.websocket = ConnectionState::Mock;
+                return info;
+            }
+        };
+
+        match browser.get_version() {
+            Ok(version) => {
+                info.chrome_version = Some(version.product);
+                info.protocol_version = Some(version.protocol_version);
+            }
+            Err(e) => {
+                info.alive = false;
+                info.websocket = ConnectionState::Disconnected;
+                info.error = Some(format!("Chrome is not responding: {}", e));
+                return info;
+            }
+        }
+
+        // Target.getTargets is only reachable through a tab's session
+        let tab = self
+            .get_tabs()
+            .ok()
+            .and_then(|tabs| tabs.into_iter().next());
+        if let Some(tab) = tab {
+            match tab.call_method(Target::GetTargets { filter: None }) {
+                Ok(targets) => {
+                    info.targets = targets
+                        .target_infos
+                        .into_iter()
+                        .map(|target| TargetSummary {
+                            id: target.target_id,
+                            kind: target.Type,
+                            title: target.title,
+                            url: target.url,
+                            attached: target.attached,
+                        })
+                        .collect();
+                }
+                Err(e) => log::debug!("Failed to list targets: {}", e),
+            }
+        }
+        info
+    }
+
+    /// Reload crashed tabs when they are next used instead of failing
+    pub fn set_reload_on_crash(&mut self, reload: bool) {
+        self.reload_on_crash = reload;
+    }
+
+    /// Tab whose document is visible and focused, or at least visible
+    fn focused_tab(tabs: &[Arc<Tab>]) -> Result<Arc<Tab>> {
         // First pass: check for both visibility and focus (strongest signal)
-        for tab in &tabs {
+        for tab in tabs {
             let result = tab.evaluate(
                 "document.visibilityState === 'visible' && document.hasFocus()",
                 false,
             );
             match result {
                 Ok(remote_object) => {
-                    if let Some(value) = remote_object.value {
-                        if value.as_bool().unwrap_or(false) {
-                            return Ok(tab.clone());
-                        }
+                    if let Some(value) = remote_object.value
+                        && value.as_bool().unwrap_or(false)
+                    {
+                        return Ok(tab.clone());
                     }
                 }
                 Err(e) => {
@@ -138,14 +844,14 @@ impl BrowserSession {
         }
 
         // Second pass: check just for visibility (weaker signal, but better than nothing)
-        for tab in &tabs {
+        for tab in tabs {
             let result = tab.evaluate("document.visibilityState === 'visible'", false);
             match result {
                 Ok(remote_object) => {
-                    if let Some(value) = remote_object.value {
-                        if value.as_bool().unwrap_or(false) {
-                            return Ok(tab.clone());
-                        }
+                    if let Some(value) = remote_object.value
+                        && value.as_bool().unwrap_or(false)
+                    {
+                        return Ok(tab.clone());
                     }
                 }
                 Err(_) => continue,
@@ -159,39 +865,575 @@ impl BrowserSession {
 
     /// Close the active tab
     pub fn close_active_tab(&mut self) -> Result<()> {
-        self.tab()?
-            .close(true)
-            .map_err(|e| BrowserError::TabOperationFailed(format!("Failed to close tab: {}", e)))?;
+        let id = self.tab()?.get_target_id().clone();
+        self.close_tab(&id)
+    }
+
+    /// Get the underlying Browser instance
+    ///
+    /// Sessions created with [`BrowserSession::from_mock`] have none and return an error.
+    pub fn browser(&self) -> Result<&Browser> {
+        match &self.driver {
+            Driver::Chrome(browser) => Ok(browser),
+            Driver::Mock(_) => Err(BrowserError::TabOperationFailed(
+                "Not supported by mock sessions".to_string(),
+            )),
+        }
+    }
+
+    /// The scripted page this session runs against, if it is a mock session
+    pub fn mock(&self) -> Option<&MockSession> {
+        match &self.driver {
+            Driver::Mock(mock) => Some(mock),
+            Driver::Chrome(_) => None,
+        }
+    }
+
+    /// Enable robots.txt compliance for the given user agent, or disable it with `None`
+    pub fn set_robots_user_agent(&mut self, user_agent: Option<String>) {
+        self.robots = user_agent.map(RobotsChecker::new);
+    }
 
+    /// User agent robots.txt rules are evaluated for, when compliance is enabled
+    pub fn robots_user_agent(&self) -> Option<&str> {
+        self.robots.as_ref().map(RobotsChecker::user_agent)
+    }
+
+    /// Return an error if robots.txt compliance is enabled and disallows `url`
+    pub fn check_robots(&self, url: &str) -> Result<()> {
+        match &self.robots {
+            Some(robots) => robots.check(self.browser()?, url),
+            None => Ok(()),
+        }
+    }
+
+    /// Enable per-host throttling with the given limits, or disable it with `None`
+    pub fn set_rate_limit(&mut self, rate_limit: Option<RateLimitOptions>) {
+        self.rate_limiter = rate_limit.map(|limit| Arc::new(RateLimiter::new(limit)));
+    }
+
+    /// Per-host limits in effect, when throttling is enabled
+    pub fn rate_limit(&self) -> Option<&RateLimitOptions> {
+        self.rate_limiter.as_deref().map(RateLimiter::options)
+    }
+
+    /// The rate limiter, for work on other threads that must count towards the same hosts
+    pub(crate) fn rate_limiter(&self) -> Option<Arc<RateLimiter>> {
+        self.rate_limiter.clone()
+    }
+
+    /// Wait until a request to `url` is allowed by the rate limiter
+    ///
+    /// The returned permit counts towards the host's concurrency limit until dropped.
+    /// Returns `None` when throttling is disabled or the URL has no host.
+    pub fn throttle(&self, url: &str) -> Option<RatePermit<'_>> {
+        self.rate_limiter.as_ref()?.acquire(url)
+    }
+
+    /// Confine file-writing tools to a directory, or lift the restriction with `None`
+    pub fn set_output_dir(&mut self, dir: Option<PathBuf>) {
+        self.output_dir = dir;
+    }
+
+    /// Render the active tab, and tabs opened from now on, at `viewport`
+    ///
+    /// Takes effect immediately: the page is laid out again at the new size,
+    /// so DOM trees extracted before are not reused.
+    pub fn set_viewport(&self, viewport: Viewport) -> Result<()> {
+        viewport.validate()?;
+        if self.mock().is_none() {
+            viewport.apply(&*self.tab()?)?;
+        }
+        *self.viewport.write().unwrap_or_else(|e| e.into_inner()) = Some(viewport);
+        self.record_page_action();
         Ok(())
     }
 
-    /// Get the underlying Browser instance
-    pub fn browser(&self) -> &Browser {
-        &self.browser
+    /// Viewport tabs are rendered at, if one was pinned
+    pub fn viewport(&self) -> Option<Viewport> {
+        *self.viewport.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait this long for pages to load unless a call passes its own timeout
+    pub fn set_navigation_timeout(&mut self, timeout: Duration) {
+        self.navigation_timeout = timeout;
+    }
+
+    /// How long navigations wait for the page to load by default
+    pub fn navigation_timeout(&self) -> Duration {
+        self.navigation_timeout
+    }
+
+    /// Number elements of DOM trees extracted from now on in document or visual reading order
+    pub fn set_index_order(&mut self, order: IndexOrder) {
+        self.index_order = order;
+    }
+
+    /// How extracted DOM trees number their elements
+    pub fn index_order(&self) -> IndexOrder {
+        self.index_order
+    }
+
+    /// Directory file-writing tools are confined to, if any
+    pub fn output_dir(&self) -> Option<&Path> {
+        self.output_dir.as_deref()
+    }
+
+    /// Resolve a tool-supplied output path, rejecting paths that escape the output directory
+    pub fn output_path(&self, path: &str) -> Result<PathBuf> {
+        resolve_output_path(self.output_dir(), path)
+    }
+
+    /// Resolve an output path and prepare it for writing a new file
+    ///
+    /// Fails if the file exists and `overwrite` is false. Returns the canonical path.
+    pub fn output_file(&self, path: &str, overwrite: bool) -> Result<PathBuf> {
+        let resolved = self.output_path(path)?;
+        prepare_output_file(self.output_dir(), &resolved, overwrite)
+    }
+
+    /// Resolve a tool-supplied path of a file to read; it must lie inside the output directory
+    ///
+    /// Fails when no output directory is configured. Returns the canonical path.
+    pub fn input_file(&self, path: &str) -> Result<PathBuf> {
+        resolve_input_path(self.output_dir(), path)
+    }
+
+    /// Resolve an output directory path, creating it inside the output directory if needed
+    ///
+    /// Without an output directory the path is returned as given and not created.
+    pub fn output_subdir(&self, path: &str) -> Result<PathBuf> {
+        let resolved = self.output_path(path)?;
+        match self.output_dir() {
+            Some(root) => create_dir_within(root, &resolved),
+            None => Ok(resolved),
+        }
+    }
+
+    /// Call a function of the injected helper bundle in the active tab
+    ///
+    /// See [`InjectedHelpers`] for the functions and how their arguments look.
+    pub fn call_helper<T: DeserializeOwned>(
+        &self,
+        function: &str,
+        args: serde_json::Value,
+    ) -> Result<T> {
+        let tab = self.tab()?;
+        self.helpers.call(&tab, function, args)
+    }
+
+    /// Randomness for jittered delays and mouse paths
+    ///
+    /// Draw from this rather than a global generator, so that launching with
+    /// the same [`LaunchOptions::rng_seed`] replays a run exactly.
+    pub fn rng(&self) -> &SessionRng {
+        &self.rng
+    }
+
+    /// Restrictions applied to the evaluate tool
+    pub fn evaluate_policy(&self) -> &EvaluatePolicy {
+        &self.evaluate_policy
+    }
+
+    /// Replace the evaluate tool policy
+    pub fn set_evaluate_policy(&mut self, policy: EvaluatePolicy) {
+        self.evaluate_policy = policy;
+    }
+
+    /// Actions that need human approval
+    pub fn confirmation(&self) -> &ConfirmationPolicy {
+        &self.confirmation
+    }
+
+    /// Replace the confirmation policy
+    pub fn set_confirmation(&mut self, policy: ConfirmationPolicy) {
+        self.confirmation = policy;
+    }
+
+    /// Ask for approval if the element or URL a tool acts on matches the confirmation policy
+    pub fn confirm_action(&self, tool: &str, text: Option<&str>, url: Option<&str>) -> Result<()> {
+        self.confirmation.confirm(tool, text, url)
+    }
+
+    /// Checks every tool call goes through before the tool runs
+    ///
+    /// Refuses calls outside the session scope (see
+    /// [`BrowserSession::check_tool_scope`]) and asks for approval of tools the
+    /// confirmation policy lists.
+    pub fn check_tool_call(&self, tool: &str) -> Result<()> {
+        self.check_tool_scope(tool)?;
+        self.confirmation.confirm_tool(tool)
+    }
+
+    /// Origins the session is pinned to, if any
+    pub fn scope(&self) -> Option<OriginScope> {
+        self.scope.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Pin the session to a set of origins
+    ///
+    /// If a scope is already set, the new one must lie within it; scopes can be
+    /// narrowed but never widened.
+    pub fn set_scope(&self, scope: OriginScope) -> Result<()> {
+        let mut current = self.scope.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(existing) = current.as_ref()
+            && !scope.is_within(existing)
+        {
+            return Err(BrowserError::PolicyDenied(format!(
+                "Scope can only be narrowed; {} is not within ({})",
+                scope.origins().join(", "),
+                existing.origins().join(", ")
+            )));
+        }
+        *current = Some(scope);
+        Ok(())
+    }
+
+    /// Refuse URLs outside the session scope
+    pub fn check_scope(&self, url: &str) -> Result<()> {
+        match self
+            .scope
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+        {
+            Some(scope) => scope.check(url),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuse to run a tool while the active page is outside the session scope
+    ///
+    /// Navigation and tab management tools are exempt so that an agent can get
+    /// back into scope.
+    pub fn check_tool_scope(&self, tool: &str) -> Result<()> {
+        if UNSCOPED_TOOLS.contains(&tool) || self.scope().is_none() {
+            return Ok(());
+        }
+        self.check_scope(&self.current_url()?)
+    }
+
+    /// URL of the active tab
+    pub fn current_url(&self) -> Result<String> {
+        match self.mock() {
+            Some(mock) => Ok(mock.url()),
+            None => Ok(self.tab()?.get_url()),
+        }
+    }
+
+    /// Headers sent with every request the session's tabs make
+    pub fn extra_headers(&self) -> HashMap<String, String> {
+        self.extra_headers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Send these headers with every request, replacing any set before
+    ///
+    /// Applied to all open tabs right away and to other tabs before they
+    /// navigate. An empty map stops sending extra headers.
+    pub fn set_extra_headers(&self, headers: HashMap<String, String>) -> Result<()> {
+        *self
+            .extra_headers
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = headers;
+        for tab in self.get_tabs()? {
+            self.apply_extra_headers(&tab)?;
+        }
+        Ok(())
+    }
+
+    /// Headers and rendering settings the session gives its tabs, for tabs opened on other threads
+    pub(crate) fn tab_settings(&self) -> TabSettings {
+        TabSettings {
+            extra_headers: self.extra_headers(),
+            determinism: self.determinism.clone(),
+            proxy: self.proxy.clone(),
+            viewport: self.viewport(),
+        }
+    }
+
+    /// Set the session's extra headers on a tab
+    pub fn apply_extra_headers(&self, tab: &Tab) -> Result<()> {
+        set_tab_headers(tab, &self.extra_headers())
     }
 
     /// Navigate to a URL using the active tab
     pub fn navigate(&self, url: &str) -> Result<()> {
-        self.tab()?.navigate_to(url).map_err(|e| {
-            BrowserError::NavigationFailed(format!("Failed to navigate to {}: {}", url, e))
-        })?;
+        self.navigate_with_options(url, &NavigateOptions::default())
+    }
+
+    /// Navigate to a URL using the active tab, with per-navigation request options
+    pub fn navigate_with_options(&self, url: &str, options: &NavigateOptions) -> Result<()> {
+        self.start_navigation(url, options)
+            .map_err(|e| e.with_url(url))
+    }
 
+    /// Checks every navigation goes through, whichever tab or tool makes it
+    ///
+    /// Refuses URLs outside the session scope, asks for approval of URLs the
+    /// confirmation policy lists and enforces robots.txt when enabled.
+    pub fn check_navigation(&self, url: &str) -> Result<()> {
+        self.check_scope(url)?;
+        self.confirm_action("navigate", None, Some(url))?;
+        self.check_robots(url)
+    }
+
+    /// Run the navigation checks and start loading `url`
+    fn start_navigation(&self, url: &str, options: &NavigateOptions) -> Result<()> {
+        self.check_navigation(url)?;
+        let _permit = self.throttle(url);
+        if let Some(mock) = self.mock() {
+            mock.navigate(url);
+            return Ok(());
+        }
+
+        // Navigating is how a crashed tab gets a new renderer
+        let tab = self.active_tab()?;
+        let session_headers = self.extra_headers();
+        let mut headers = session_headers.clone();
+        headers.extend(options.headers.clone());
+        if !headers.is_empty() {
+            set_tab_headers(&tab, &headers)?;
+        }
+
+        let load = PageLoad::start(&tab, url, options);
+        self.routes_seen
+            .store(self.routes.last_sequence(), Ordering::SeqCst);
+
+        // Per-navigation headers only go with the document request
+        if !options.headers.is_empty() {
+            set_tab_headers(&tab, &session_headers)?;
+        }
+        *self.pending_load.lock().unwrap_or_else(|e| e.into_inner()) = Some(load?);
+        self.crashes.clear(tab.get_target_id());
         Ok(())
     }
 
-    /// Wait for navigation to complete
-    pub fn wait_for_navigation(&self) -> Result<()> {
-        self.tab()?
-            .wait_until_navigated()
-            .map_err(|e| BrowserError::NavigationFailed(format!("Navigation timeout: {}", e)))?;
+    /// Navigate the active tab and report the main document's response
+    ///
+    /// With `wait` set, also waits for the navigation to complete, for
+    /// `options.timeout` or the session's default, and returns how far the
+    /// page got. The response is `None` if none was seen (e.g. `about:blank`,
+    /// or when not waiting and the response has not arrived yet).
+    pub fn navigate_with_response(
+        &self,
+        url: &str,
+        wait: bool,
+        options: &NavigateOptions,
+    ) -> Result<(Option<NavigationResponse>, Option<LoadStatus>)> {
+        if self.mock().is_some() {
+            self.navigate_with_options(url, options)?;
+            return Ok((None, wait.then_some(LoadStatus::Complete)));
+        }
+
+        let recorder = NavigationRecorder::attach(&self.active_tab()?)?;
+        self.navigate_with_options(url, options)?;
+        let status = if wait {
+            let timeout = options.timeout.unwrap_or(self.navigation_timeout);
+            Some(self.wait_for_navigation_within(timeout)?)
+        } else {
+            None
+        };
+        Ok((recorder.finish(), status))
+    }
+
+    /// XHR/fetch traffic recorded for the session
+    ///
+    /// Recording starts on the active tab the first time this is called, so
+    /// only requests made after that point are seen.
+    pub fn network_capture(&self) -> Result<&NetworkCapture> {
+        self.network.attach(&self.tab()?)?;
+        Ok(&self.network)
+    }
+
+    /// Client-side route changes of the session's tabs
+    ///
+    /// Watching starts on the active tab the first time this is called. From
+    /// then on, [`BrowserSession::wait_for_navigation`] also returns on route
+    /// changes of single-page apps, which never load a new document.
+    pub fn route_watcher(&self) -> Result<&RouteWatcher> {
+        self.routes.attach(&self.tab()?)?;
+        Ok(&self.routes)
+    }
+
+    /// Run `callback` for every client-side route change, starting the watcher if needed
+    pub fn on_route_change(
+        &self,
+        callback: impl Fn(&RouteChange) + Send + Sync + 'static,
+    ) -> Result<()> {
+        self.route_watcher()?.on_change(callback);
+        Ok(())
+    }
 
+    /// Run `callback` for every console error, uncaught page error and failed request
+    ///
+    /// Watching starts on the active tab and on tabs opened with
+    /// [`BrowserSession::new_tab`] afterwards.
+    pub fn on_page_event(
+        &self,
+        callback: impl Fn(&PageEvent) + Send + Sync + 'static,
+    ) -> Result<()> {
+        self.page_events.attach(&self.tab()?)?;
+        self.page_events.on_event(callback);
         Ok(())
     }
 
+    /// Run `callback` for every session event of the given kinds
+    ///
+    /// ```no_run
+    /// # use browser_use::browser::{BrowserSession, SessionEventKind};
+    /// # let session = BrowserSession::new()?;
+    /// session.on(
+    ///     SessionEventKind::DialogOpened | SessionEventKind::TargetCrashed,
+    ///     |event| println!("{:?}", event),
+    /// )?;
+    /// # Ok::<(), browser_use::BrowserError>(())
+    /// ```
+    ///
+    /// Watching starts on the active tab and on tabs opened with
+    /// [`BrowserSession::new_tab`] afterwards. Returns a handle for
+    /// [`BrowserSession::off`].
+    pub fn on(
+        &self,
+        kinds: impl Into<SessionEventKinds>,
+        callback: impl Fn(&SessionEvent) + Send + Sync + 'static,
+    ) -> Result<SubscriptionId> {
+        self.session_events.attach(&self.tab()?)?;
+        Ok(self.session_events.on(kinds, callback))
+    }
+
+    /// Remove a callback added with [`BrowserSession::on`]; returns whether it was subscribed
+    pub fn off(&self, id: SubscriptionId) -> bool {
+        self.session_events.off(id)
+    }
+
+    /// Wait for navigation to complete, for the session's navigation timeout
+    ///
+    /// When route changes are watched and no document load is pending, waits
+    /// briefly for a client-side route change instead. A page that is usable
+    /// but still loading when the timeout ends is not an error; see
+    /// [`BrowserSession::wait_for_navigation_within`].
+    pub fn wait_for_navigation(&self) -> Result<()> {
+        self.wait_for_navigation_within(self.navigation_timeout)
+            .map(|_| ())
+    }
+
+    /// Wait up to `timeout` for navigation to complete and report how far the page got
+    ///
+    /// Returns [`LoadStatus::Interactive`] when the document was parsed but
+    /// its load never finished (a stalled image, a long-polling request), and
+    /// fails with [`BrowserError::NavigationTimeout`] only when the page is
+    /// not usable yet.
+    pub fn wait_for_navigation_within(&self, timeout: Duration) -> Result<LoadStatus> {
+        if self.mock().is_some() {
+            return Ok(LoadStatus::Complete);
+        }
+        let pending = self
+            .pending_load
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(load) = pending {
+            if load.wait(timeout)? == LoadStatus::Interactive {
+                log::warn!(
+                    "Page is interactive but did not finish loading within {:?}",
+                    timeout
+                );
+                self.settle_storage_seeds();
+                return Ok(LoadStatus::Interactive);
+            }
+        } else if self.routes.is_active() {
+            let seen = self.routes_seen.load(Ordering::SeqCst);
+            if self
+                .routes
+                .wait_for_change(seen, ROUTE_CHANGE_GRACE)
+                .is_some()
+            {
+                self.routes_seen
+                    .store(self.routes.last_sequence(), Ordering::SeqCst);
+            }
+        }
+
+        let tab = self.tab()?;
+        if let Err(e) = tab.wait_until_navigated() {
+            let ready_state = tab
+                .evaluate("document.readyState", false)
+                .ok()
+                .and_then(|result| result.value)
+                .and_then(|value| value.as_str().map(String::from));
+            return match ready_state.as_deref() {
+                Some("interactive" | "complete") => Ok(LoadStatus::Interactive),
+                _ => Err(BrowserError::NavigationTimeout(e.to_string())),
+            };
+        }
+
+        self.settle_storage_seeds();
+        Ok(LoadStatus::Complete)
+    }
+
     /// Extract the DOM tree from the active tab
     pub fn extract_dom(&self) -> Result<DomTree> {
-        DomTree::from_tab(&self.tab()?)
+        let tree = match self.mock() {
+            Some(mock) => mock.dom(),
+            None => DomTree::from_tab_with_order(&self.tab()?, self.index_order)?,
+        };
+        let mut snapshots = self.dom_snapshots.lock().unwrap_or_else(|e| e.into_inner());
+        snapshots.previous = snapshots.latest.replace(tree.clone());
+        Ok(tree)
+    }
+
+    /// Current generation of the active tab's page
+    ///
+    /// Changes when the tab navigates or another tab becomes active, when the
+    /// document's DOM mutates, and when an action is recorded.
+    pub fn page_generation(&self) -> PageGeneration {
+        let actions = self.page_actions.load(Ordering::SeqCst);
+        if self.mock().is_some() {
+            return PageGeneration {
+                document: Some("mock".to_string()),
+                mutations: None,
+                actions,
+            };
+        }
+
+        let document = self.tab().ok().and_then(|tab| {
+            let tree = tab.call_method(Page::GetFrameTree(None)).ok()?;
+            Some(format!(
+                "{}/{}",
+                tab.get_target_id(),
+                tree.frame_tree.frame.loader_id
+            ))
+        });
+        let mutations = document.as_ref().and_then(|_| {
+            self.call_helper::<u64>("watchMutations", serde_json::json!({}))
+                .ok()
+        });
+        PageGeneration {
+            document,
+            mutations,
+            actions,
+        }
+    }
+
+    /// Note an action that may have changed the page in ways the DOM does not
+    /// show, so DOM trees extracted before it are not reused
+    pub fn record_page_action(&self) {
+        self.page_actions.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Elements of `tree` the caller may have meant by an `index` it does not have
+    ///
+    /// Looks the index up in the most recent earlier snapshot that had it, so an
+    /// element that moved since the caller last looked is found again.
+    pub fn suggest_for_index(&self, tree: &DomTree, index: usize) -> Vec<ElementSuggestion> {
+        let snapshots = self.dom_snapshots.lock().unwrap_or_else(|e| e.into_inner());
+        let earlier = [&snapshots.latest, &snapshots.previous]
+            .into_iter()
+            .flatten()
+            .find(|snapshot| snapshot.get_selector(index).is_some());
+        suggest::suggest_for_index(tree, earlier, index)
     }
 
     /// Extract the DOM tree with a custom ref prefix (for iframe handling)
@@ -200,13 +1442,33 @@ impl BrowserSession {
     }
 
     /// Find an element by CSS selector using the provided tab
+    ///
+    /// Deep selectors (`iframe#pay >>> button`) are resolved across iframes
+    /// and shadow roots; see [`DeepSelector`].
     pub fn find_element<'a>(
         &self,
         tab: &'a Arc<Tab>,
         css_selector: &str,
     ) -> Result<headless_chrome::Element<'a>> {
-        tab.find_element(css_selector).map_err(|e| {
-            BrowserError::ElementNotFound(format!("Element '{}' not found: {}", css_selector, e))
+        let found = if DeepSelector::is_deep(css_selector) {
+            DeepSelector::parse(css_selector)?.find(tab)
+        } else {
+            tab.find_element(css_selector)
+                .map_err(|e| BrowserError::ElementNotFound(e.to_string()))
+        };
+        found.map_err(|e| {
+            let BrowserError::ElementNotFound(reason) = e else {
+                return e;
+            };
+            let suggestions = DomTree::from_tab_with_order(tab, self.index_order)
+                .map(|tree| suggest::suggest_for_selector(&tree, css_selector))
+                .unwrap_or_default();
+            BrowserError::ElementNotFound(format!(
+                "Element '{}' not found: {}",
+                css_selector, reason
+            ))
+            .with_selector(css_selector)
+            .with_suggestions(suggestions)
         })
     }
 
@@ -226,12 +1488,36 @@ impl BrowserSession {
         name: &str,
         params: serde_json::Value,
     ) -> Result<crate::tools::ToolResult> {
+        self.check_tool_call(name)?;
         let mut context = ToolContext::new(self);
         self.tool_registry.execute(name, params, &mut context)
     }
 
+    /// Refuse a history navigation by `offset` entries that would leave the session scope
+    fn check_history_scope(&self, offset: i64) -> Result<()> {
+        if self.scope().is_none() {
+            return Ok(());
+        }
+
+        let history = self
+            .tab()?
+            .call_method(Page::GetNavigationHistory(None))
+            .map_err(|e| {
+                BrowserError::NavigationFailed(format!("Failed to read history: {}", e))
+            })?;
+        let target = history.current_index as i64 + offset;
+        match usize::try_from(target)
+            .ok()
+            .and_then(|index| history.entries.get(index))
+        {
+            Some(entry) => self.check_scope(&entry.url),
+            None => Ok(()),
+        }
+    }
+
     /// Navigate back in browser history
     pub fn go_back(&self) -> Result<()> {
+        self.check_history_scope(-1)?;
         let go_back_js = r#"
             (function() {
                 window.history.back();
@@ -251,6 +1537,7 @@ impl BrowserSession {
 
     /// Navigate forward in browser history
     pub fn go_forward(&self) -> Result<()> {
+        self.check_history_scope(1)?;
         let go_forward_js = r#"
             (function() {
                 window.history.forward();
@@ -268,6 +1555,320 @@ impl BrowserSession {
         Ok(())
     }
 
+    /// Capture cookies and localStorage of all open tabs as a Playwright-compatible storage state
+    pub fn storage_state(&self) -> Result<StorageState> {
+        let tab = self.tab()?;
+        let cookies = tab
+            .call_method(Network::GetAllCookies(None))
+            .map_err(|e| BrowserError::ChromeError(format!("Failed to read cookies: {}", e)))?
+            .cookies
+            .into_iter()
+            .map(StorageCookie::from)
+            .collect();
+
+        let local_storage_js = r#"
+            JSON.stringify({
+                origin: window.location.origin,
+                localStorage: Object.keys(window.localStorage).map(name => ({
+                    name: name,
+                    value: window.localStorage.getItem(name)
+                }))
+            })
+        "#;
+
+        let mut origins: Vec<OriginState> = Vec::new();
+        for tab in self.get_tabs()? {
+            let origin_state = tab
+                .evaluate(local_storage_js, false)
+                .ok()
+                .and_then(|r| r.value)
+                .and_then(|v| v.as_str().map(String::from))
+                .and_then(|s| serde_json::from_str::<OriginState>(&s).ok());
+
+            if let Some(origin_state) = origin_state
+                && origin_state.origin != "null"
+                && !origin_state.local_storage.is_empty()
+                && !origins.iter().any(|o| o.origin == origin_state.origin)
+            {
+                origins.push(origin_state);
+            }
+        }
+
+        Ok(StorageState { cookies, origins })
+    }
+
+    /// Restore cookies and localStorage from a storage state
+    ///
+    /// Cookies are applied immediately. localStorage is seeded into the active tab
+    /// when it is already on a matching origin, and otherwise the first time it
+    /// loads a page on that origin.
+    pub fn set_storage_state(&self, state: &StorageState) -> Result<()> {
+        let tab = self.tab()?;
+
+        if !state.cookies.is_empty() {
+            let cookies = state
+                .cookies
+                .iter()
+                .map(Network::CookieParam::from)
+                .collect();
+            tab.call_method(Network::SetCookies { cookies })
+                .map_err(|e| BrowserError::ChromeError(format!("Failed to set cookies: {}", e)))?;
+        }
+
+        for origin in &state.origins {
+            let seed_js = format!(
+                r#"
+                (function() {{
+                    const origin = {};
+                    const entries = {};
+                    const marker = '__browser_use_storage_state__';
+                    if (window.location.origin !== origin || window.sessionStorage.getItem(marker)) {{
+                        return false;
+                    }}
+                    for (const entry of entries) {{
+                        window.localStorage.setItem(entry.name, entry.value);
+                    }}
+                    window.sessionStorage.setItem(marker, '1');
+                    return true;
+                }})()
+                "#,
+                serde_json::to_string(&origin.origin)?,
+                serde_json::to_string(&origin.local_storage)?
+            );
+
+            let identifier = tab
+                .call_method(Page::AddScriptToEvaluateOnNewDocument {
+                    source: seed_js.clone(),
+                    world_name: None,
+                    include_command_line_api: None,
+                    run_immediately: None,
+                })
+                .map_err(|e| {
+                    BrowserError::ChromeError(format!("Failed to register storage seed: {}", e))
+                })?
+                .identifier;
+
+            let seeded = tab
+                .evaluate(&seed_js, false)
+                .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?
+                .value
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false);
+            let seed = StorageSeed {
+                tab: tab.clone(),
+                origin: origin.origin.clone(),
+                identifier,
+            };
+            if seeded {
+                seed.remove();
+            } else {
+                self.storage_seeds
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(seed);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unregister the localStorage seeds of origins that have been loaded since
+    ///
+    /// A seed left registered would run again whenever its marker in
+    /// sessionStorage is gone, e.g. after a rollback, and bring back stale values.
+    fn settle_storage_seeds(&self) {
+        let mut seeds = self.storage_seeds.lock().unwrap_or_else(|e| e.into_inner());
+        seeds.retain(|seed| {
+            if seed.is_seeded() {
+                seed.remove();
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Save the current storage state to a Playwright `storageState.json` file
+    pub fn save_storage_state(&self, path: impl AsRef<Path>) -> Result<StorageState> {
+        let state = self.storage_state()?;
+        state.save(path)?;
+        Ok(state)
+    }
+
+    /// Load a Playwright `storageState.json` file and apply it to the browser
+    pub fn load_storage_state(&self, path: impl AsRef<Path>) -> Result<StorageState> {
+        let state = StorageState::load(path)?;
+        self.set_storage_state(&state)?;
+        Ok(state)
+    }
+
+    /// Save the current storage state to a passphrase-encrypted file
+    #[cfg(feature = "encryption")]
+    pub fn save_storage_state_encrypted(
+        &self,
+        path: impl AsRef<Path>,
+        passphrase: &str,
+    ) -> Result<StorageState> {
+        let state = self.storage_state()?;
+        state.save_encrypted(path, passphrase)?;
+        Ok(state)
+    }
+
+    /// Load a passphrase-encrypted storage state file and apply it to the browser
+    #[cfg(feature = "encryption")]
+    pub fn load_storage_state_encrypted(
+        &self,
+        path: impl AsRef<Path>,
+        passphrase: &str,
+    ) -> Result<StorageState> {
+        let state = StorageState::load_encrypted(path, passphrase)?;
+        self.set_storage_state(&state)?;
+        Ok(state)
+    }
+
+    /// Record the active tab's URL, cookies, storage and scroll position
+    ///
+    /// Returns the checkpoint; pass its id to [`BrowserSession::rollback`].
+    /// Only the page origin's localStorage and sessionStorage are captured.
+    /// The session keeps the latest [`MAX_CHECKPOINTS`](crate::browser::checkpoint::MAX_CHECKPOINTS).
+    pub fn checkpoint(&self) -> Result<Checkpoint> {
+        let tab = self.tab()?;
+        let cookies = tab
+            .call_method(Network::GetAllCookies(None))
+            .map_err(|e| BrowserError::ChromeError(format!("Failed to read cookies: {}", e)))?
+            .cookies
+            .into_iter()
+            .map(StorageCookie::from)
+            .collect();
+
+        let page_state_js = r#"
+            (function() {
+                const entries = (storage) => {
+                    try {
+                        const area = storage();
+                        return Object.keys(area).map(name => ({ name: name, value: area.getItem(name) }));
+                    } catch (e) {
+                        return [];
+                    }
+                };
+                return JSON.stringify({
+                    origin: window.location.origin,
+                    localStorage: entries(() => window.localStorage),
+                    sessionStorage: entries(() => window.sessionStorage),
+                    scrollX: window.scrollX,
+                    scrollY: window.scrollY
+                });
+            })()
+        "#;
+        let page: PageState = tab
+            .evaluate(page_state_js, false)
+            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .ok_or_else(|| {
+                BrowserError::EvaluationFailed("Failed to read the page state".to_string())
+            })
+            .and_then(|json| Ok(serde_json::from_str(&json)?))?;
+
+        let checkpoint = Checkpoint {
+            id: self.checkpoints.next_id(),
+            url: tab.get_url(),
+            cookies,
+            origin: Some(page.origin).filter(|origin| origin != "null"),
+            local_storage: page.local_storage,
+            session_storage: page.session_storage,
+            scroll_x: page.scroll_x,
+            scroll_y: page.scroll_y,
+        };
+        self.checkpoints.push(checkpoint.clone());
+        Ok(checkpoint)
+    }
+
+    /// Return the active tab to the state recorded by [`BrowserSession::checkpoint`]
+    ///
+    /// Replaces all cookies with the checkpoint's, loads its URL with the
+    /// origin's storage restored, and scrolls back to where the page was. The
+    /// navigation goes through the usual scope, confirmation and robots
+    /// checks. The checkpoint is kept, so it can be rolled back to again.
+    pub fn rollback(&self, checkpoint_id: &str) -> Result<Checkpoint> {
+        let checkpoint = self.checkpoints.get(checkpoint_id)?;
+        self.settle_storage_seeds();
+        let tab = self.tab()?;
+        tab.call_method(Network::ClearBrowserCookies(None))
+            .map_err(|e| BrowserError::ChromeError(format!("Failed to clear cookies: {}", e)))?;
+        if !checkpoint.cookies.is_empty() {
+            let cookies = checkpoint
+                .cookies
+                .iter()
+                .map(Network::CookieParam::from)
+                .collect();
+            tab.call_method(Network::SetCookies { cookies })
+                .map_err(|e| BrowserError::ChromeError(format!("Failed to set cookies: {}", e)))?;
+        }
+
+        // Storage can only be written from a page of its origin; when the tab
+        // is elsewhere, load the page once to get there and again to let it
+        // start from the restored storage
+        let restored = self.restore_page_storage(&checkpoint)?;
+        self.navigate(&checkpoint.url)?;
+        self.wait_for_navigation()?;
+        if !restored && self.restore_page_storage(&checkpoint)? {
+            self.navigate(&checkpoint.url)?;
+            self.wait_for_navigation()?;
+        }
+
+        let scroll_js = format!(
+            "window.scrollTo({}, {})",
+            checkpoint.scroll_x, checkpoint.scroll_y
+        );
+        self.tab()?
+            .evaluate(&scroll_js, false)
+            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
+        self.record_page_action();
+        Ok(checkpoint)
+    }
+
+    /// Replace the active page's storage with the checkpoint's if the page is on its origin
+    fn restore_page_storage(&self, checkpoint: &Checkpoint) -> Result<bool> {
+        let Some(origin) = &checkpoint.origin else {
+            return Ok(false);
+        };
+        let restore_js = format!(
+            r#"
+            (function() {{
+                if (window.location.origin !== {}) {{
+                    return false;
+                }}
+                const restore = (area, entries) => {{
+                    area.clear();
+                    for (const entry of entries) {{
+                        area.setItem(entry.name, entry.value);
+                    }}
+                }};
+                restore(window.localStorage, {});
+                restore(window.sessionStorage, {});
+                return true;
+            }})()
+            "#,
+            serde_json::to_string(origin)?,
+            serde_json::to_string(&checkpoint.local_storage)?,
+            serde_json::to_string(&checkpoint.session_storage)?
+        );
+        let restored = self
+            .tab()?
+            .evaluate(&restore_js, false)
+            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?
+            .value
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        Ok(restored)
+    }
+
+    /// Identifiers of the checkpoints the session can roll back to, oldest first
+    pub fn checkpoint_ids(&self) -> Vec<String> {
+        self.checkpoints.ids()
+    }
+
     /// Close the browser
     pub fn close(&self) -> Result<()> {
         // Note: The Browser struct doesn't have a public close method in headless_chrome
@@ -281,6 +1882,67 @@ impl BrowserSession {
     }
 }
 
+/// Storage and scroll position of a page, as read by [`BrowserSession::checkpoint`]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageState {
+    origin: String,
+    local_storage: Vec<StorageEntry>,
+    session_storage: Vec<StorageEntry>,
+    scroll_x: f64,
+    scroll_y: f64,
+}
+
+/// How long `wait_for_navigation` waits for a client-side route change
+const ROUTE_CHANGE_GRACE: Duration = Duration::from_millis(500);
+
+/// Generator for a new session, logging the seed so the run can be replayed
+fn session_rng(seed: Option<u64>) -> SessionRng {
+    let rng = seed.map_or_else(SessionRng::from_entropy, SessionRng::new);
+    log::debug!("Session random seed: {}", rng.seed());
+    rng
+}
+
+/// Replace a tab's extra HTTP headers; an empty map clears them
+/// Headers and rendering settings of a session, applied to every tab opened for it
+///
+/// Detached from the session so that work on other threads, such as a crawl,
+/// can set up its own tabs the way the session does.
+#[derive(Clone)]
+pub(crate) struct TabSettings {
+    extra_headers: HashMap<String, String>,
+    determinism: Option<DeterministicOptions>,
+    proxy: Option<ProxyOptions>,
+    viewport: Option<Viewport>,
+}
+
+impl TabSettings {
+    /// Send the headers from `tab` and render it with the settings
+    pub(crate) fn apply(&self, tab: &Arc<Tab>) -> Result<()> {
+        set_tab_headers(tab, &self.extra_headers)?;
+        if let Some(determinism) = &self.determinism {
+            determinism.apply(tab)?;
+        }
+        if let Some(proxy) = &self.proxy {
+            proxy.apply(tab)?;
+        }
+        if let Some(viewport) = self.viewport {
+            viewport.apply(tab)?;
+        }
+        Ok(())
+    }
+}
+
+fn set_tab_headers(tab: &Tab, headers: &HashMap<String, String>) -> Result<()> {
+    tab.set_extra_http_headers(
+        headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect(),
+    )
+    .map_err(|e| BrowserError::ChromeError(format!("Failed to set extra headers: {}", e)))
+}
+
 impl Default for BrowserSession {
     fn default() -> Self {
         Self::new().expect("Failed to create default browser session")
@@ -0,0 +1,113 @@
+//! Health of a session's browser
+//!
+//! [`BrowserSession::session_info`](crate::browser::BrowserSession::session_info)
+//! gathers what an operator needs to tell a dead or hung browser from a slow
+//! page: whether Chrome still answers, which version it is, the targets it has
+//! open and how long the session has been running.
+
+use serde::Serialize;
+use std::time::Duration;
+
+/// State of the DevTools websocket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    /// Chrome answered a round trip over the websocket
+    Connected,
+
+    /// Chrome exited, the websocket dropped or Chrome did not answer
+    Disconnected,
+
+    /// The session runs against a [`MockSession`](crate::browser::MockSession); there is no websocket
+    Mock,
+}
+
+/// A DevTools target: a tab, iframe, worker or the browser itself
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TargetSummary {
+    /// Target id
+    pub id: String,
+    /// Target type such as `page`, `iframe` or `service_worker`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Title of the target's document
+    pub title: String,
+    /// URL of the target's document
+    pub url: String,
+    /// Whether a DevTools client is attached to the target
+    pub attached: bool,
+}
+
+/// Snapshot of a session's browser health
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionInfo {
+    /// Whether Chrome answered
+    pub alive: bool,
+
+    /// State of the DevTools websocket
+    pub websocket: ConnectionState,
+
+    /// Why Chrome is considered disconnected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Browser product and version, e.g. `HeadlessChrome/126.0.6478.126`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chrome_version: Option<String>,
+
+    /// DevTools protocol version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+
+    /// Targets Chrome has open; empty when it is disconnected
+    pub targets: Vec<TargetSummary>,
+
+    /// Target id of the tab tools act on, once one was chosen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_tab: Option<String>,
+
+    /// Target ids of tabs whose renderer crashed and that were not recovered yet
+    pub crashed_tabs: Vec<String>,
+
+    /// Time since the browser was launched, connected to or restarted
+    #[serde(rename = "uptime_secs", serialize_with = "serialize_secs")]
+    pub uptime: Duration,
+}
+
+fn serialize_secs<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_info_json() {
+        let info = SessionInfo {
+            alive: false,
+            websocket: ConnectionState::Disconnected,
+            error: Some("Chrome is not responding: connection closed".to_string()),
+            chrome_version: None,
+            protocol_version: None,
+            targets: Vec::new(),
+            active_tab: None,
+            crashed_tabs: Vec::new(),
+            uptime: Duration::from_millis(90_500),
+        };
+        assert_eq!(
+            serde_json::to_value(&info).unwrap(),
+            serde_json::json!({
+                "alive": false,
+                "websocket": "disconnected",
+                "error": "Chrome is not responding: connection closed",
+                "targets": [],
+                "crashed_tabs": [],
+                "uptime_secs": 90,
+            })
+        );
+    }
+}
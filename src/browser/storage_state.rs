@@ -0,0 +1,216 @@
+//! Browser storage state in Playwright's `storageState.json` format
+//!
+//! The structures here serialize to exactly the same JSON layout Playwright
+//! produces with `context.storageState({ path })`, so authenticated states can
+//! be shared between Playwright and browser-use without conversion.
+
+use crate::error::Result;
+use headless_chrome::protocol::cdp::Network;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Snapshot of cookies and per-origin localStorage
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StorageState {
+    /// All cookies known to the browser
+    #[serde(default)]
+    pub cookies: Vec<StorageCookie>,
+
+    /// localStorage contents grouped by origin
+    #[serde(default)]
+    pub origins: Vec<OriginState>,
+}
+
+/// A single cookie as written by Playwright
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+
+    /// Unix time in seconds, or -1 for session cookies
+    #[serde(serialize_with = "serialize_js_number")]
+    pub expires: f64,
+
+    pub http_only: bool,
+    pub secure: bool,
+    pub same_site: SameSite,
+}
+
+/// Cookie SameSite attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SameSite {
+    Strict,
+    #[default]
+    Lax,
+    None,
+}
+
+/// localStorage entries for one origin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginState {
+    /// Origin such as `https://example.com`
+    pub origin: String,
+
+    /// localStorage key/value pairs
+    #[serde(default)]
+    pub local_storage: Vec<StorageEntry>,
+}
+
+/// A name/value pair in localStorage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageEntry {
+    pub name: String,
+    pub value: String,
+}
+
+impl StorageState {
+    /// Parse a storage state from a Playwright-compatible JSON string
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize to Playwright-compatible pretty JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read a storage state file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json(&json)
+    }
+
+    /// Write the storage state to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Read a storage state file encrypted with [`StorageState::save_encrypted`]
+    #[cfg(feature = "encryption")]
+    pub fn load_encrypted(path: impl AsRef<Path>, passphrase: &str) -> Result<Self> {
+        let data = std::fs::read(path)?;
+        let json = super::encryption::decrypt(&data, passphrase)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// Write the storage state to a file encrypted with a passphrase
+    #[cfg(feature = "encryption")]
+    pub fn save_encrypted(&self, path: impl AsRef<Path>, passphrase: &str) -> Result<()> {
+        let data = super::encryption::encrypt(self.to_json()?.as_bytes(), passphrase)?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+}
+
+/// Write whole numbers without a fractional part, like `JSON.stringify` does
+fn serialize_js_number<S: serde::Serializer>(
+    value: &f64,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        serializer.serialize_i64(*value as i64)
+    } else {
+        serializer.serialize_f64(*value)
+    }
+}
+
+impl From<Network::Cookie> for StorageCookie {
+    fn from(cookie: Network::Cookie) -> Self {
+        Self {
+            name: cookie.name,
+            value: cookie.value,
+            domain: cookie.domain,
+            path: cookie.path,
+            expires: if cookie.session { -1.0 } else { cookie.expires },
+            http_only: cookie.http_only,
+            secure: cookie.secure,
+            same_site: match cookie.same_site {
+                Some(Network::CookieSameSite::Strict) => SameSite::Strict,
+                Some(Network::CookieSameSite::None) => SameSite::None,
+                Some(Network::CookieSameSite::Lax) | None => SameSite::Lax,
+            },
+        }
+    }
+}
+
+impl From<&StorageCookie> for Network::CookieParam {
+    fn from(cookie: &StorageCookie) -> Self {
+        Self {
+            name: cookie.name.clone(),
+            value: cookie.value.clone(),
+            url: None,
+            domain: Some(cookie.domain.clone()),
+            path: Some(cookie.path.clone()),
+            secure: Some(cookie.secure),
+            http_only: Some(cookie.http_only),
+            same_site: Some(match cookie.same_site {
+                SameSite::Strict => Network::CookieSameSite::Strict,
+                SameSite::Lax => Network::CookieSameSite::Lax,
+                SameSite::None => Network::CookieSameSite::None,
+            }),
+            expires: (cookie.expires >= 0.0).then_some(cookie.expires),
+            priority: None,
+            same_party: None,
+            source_scheme: None,
+            source_port: None,
+            partition_key: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYWRIGHT_STATE: &str = r#"{
+      "cookies": [
+        {
+          "name": "sid",
+          "value": "abc123",
+          "domain": ".example.com",
+          "path": "/",
+          "expires": -1,
+          "httpOnly": true,
+          "secure": true,
+          "sameSite": "None"
+        }
+      ],
+      "origins": [
+        {
+          "origin": "https://example.com",
+          "localStorage": [{ "name": "token", "value": "xyz" }]
+        }
+      ]
+    }"#;
+
+    #[test]
+    fn test_parse_playwright_state() {
+        let state = StorageState::from_json(PLAYWRIGHT_STATE).unwrap();
+        assert_eq!(state.cookies.len(), 1);
+        assert_eq!(state.cookies[0].same_site, SameSite::None);
+        assert!(state.cookies[0].http_only);
+        assert_eq!(state.cookies[0].expires, -1.0);
+        assert_eq!(state.origins[0].local_storage[0].name, "token");
+    }
+
+    #[test]
+    fn test_roundtrip_uses_playwright_keys() {
+        let state = StorageState::from_json(PLAYWRIGHT_STATE).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&state.to_json().unwrap()).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(PLAYWRIGHT_STATE).unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_session_cookie_has_no_expiry_param() {
+        let state = StorageState::from_json(PLAYWRIGHT_STATE).unwrap();
+        let param = Network::CookieParam::from(&state.cookies[0]);
+        assert_eq!(param.expires, None);
+        assert_eq!(param.domain.as_deref(), Some(".example.com"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Which of the browser's tabs the session acts on
+//!
+//! Chrome has no notion of a "current" tab that survives popups and closed
+//! tabs, so the session keeps one: [`TabTracker`] remembers the active tab by
+//! target id and notices tabs the page opened itself (`window.open`,
+//! `target="_blank"` links), which the session then sets up like its own.
+
+use headless_chrome::Tab;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A tab of the session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TabSummary {
+    /// Target id, stable while the tab is open
+    pub id: String,
+    /// Position in the browser's tab list
+    pub index: usize,
+    /// Page title
+    pub title: String,
+    /// Page URL
+    pub url: String,
+    /// Whether tools act on this tab
+    pub active: bool,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Active tab and the tabs already seen
+#[derive(Debug, Default)]
+pub struct TabTracker {
+    /// Target id of the tab tools act on, once one was chosen
+    active: Mutex<Option<String>>,
+    /// Target ids of the tabs seen so far; `None` before the first look
+    known: Mutex<Option<HashSet<String>>>,
+}
+
+impl TabTracker {
+    /// Create a tracker that has not seen any tab yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Target id of the active tab, if one was chosen
+    pub fn active(&self) -> Option<String> {
+        lock(&self.active).clone()
+    }
+
+    /// Make the tab with target id `id` the active one
+    pub fn set_active(&self, id: &str) {
+        *lock(&self.active) = Some(id.to_string());
+    }
+
+    /// Record a tab the session opened itself, so it is not taken for a popup
+    pub fn add(&self, id: &str) {
+        lock(&self.known)
+            .get_or_insert_with(HashSet::new)
+            .insert(id.to_string());
+    }
+
+    /// Forget a closed tab; returns whether it was the active one
+    pub fn remove(&self, id: &str) -> bool {
+        if let Some(known) = lock(&self.known).as_mut() {
+            known.remove(id);
+        }
+        let mut active = lock(&self.active);
+        if active.as_deref() == Some(id) {
+            *active = None;
+            return true;
+        }
+        false
+    }
+
+    /// Tabs of `tabs` not seen before, recording them as seen
+    ///
+    /// The first call only records the tabs that are already open, which
+    /// were not opened by a page the session acted on.
+    pub fn new_tabs(&self, tabs: &[Arc<Tab>]) -> Vec<Arc<Tab>> {
+        let mut known = lock(&self.known);
+        let first_look = known.is_none();
+        let known = known.get_or_insert_with(HashSet::new);
+        known.retain(|id| tabs.iter().any(|tab| tab.get_target_id() == id));
+        let new: Vec<Arc<Tab>> = tabs
+            .iter()
+            .filter(|tab| known.insert(tab.get_target_id().clone()))
+            .cloned()
+            .collect();
+        if first_look { Vec::new() } else { new }
+    }
+}
+
+/// Position of the tab to activate after closing the one at `closed` of `count` tabs
+///
+/// The tab that took the closed one's place, or the new last tab; `None`
+/// when no tab is left.
+pub fn next_active_index(closed: usize, count: usize) -> Option<usize> {
+    let remaining = count.checked_sub(1)?;
+    (remaining > 0).then(|| closed.min(remaining - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_tab() {
+        let tracker = TabTracker::new();
+        assert_eq!(tracker.active(), None);
+
+        tracker.set_active("A");
+        tracker.add("A");
+        assert_eq!(tracker.active().as_deref(), Some("A"));
+        assert!(!tracker.remove("B"));
+        assert!(tracker.remove("A"));
+        assert_eq!(tracker.active(), None);
+    }
+
+    #[test]
+    fn test_next_active_index() {
+        assert_eq!(next_active_index(0, 1), None);
+        assert_eq!(next_active_index(0, 3), Some(0));
+        assert_eq!(next_active_index(2, 3), Some(1));
+        assert_eq!(next_active_index(1, 3), Some(1));
+    }
+}
@@ -0,0 +1,129 @@
+//! Page viewport emulation
+//!
+//! The window size Chrome is launched with only sets the viewport roughly:
+//! headed windows lose space to toolbars, and the pixel density follows the
+//! host's display. A [`Viewport`] pins the layout size and device scale
+//! factor with `Emulation.setDeviceMetricsOverride`, so screenshots and
+//! visibility checks see the same page on every machine.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::Tab;
+use headless_chrome::protocol::cdp::Emulation;
+use serde::{Deserialize, Serialize};
+
+/// Largest width or height accepted, in CSS pixels
+const MAX_VIEWPORT_SIZE: u32 = 10_000;
+
+/// Layout size and pixel density pages are rendered at
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Viewport {
+    /// Width in CSS pixels
+    pub width: u32,
+
+    /// Height in CSS pixels
+    pub height: u32,
+
+    /// Device pixels per CSS pixel, e.g. 2 for a retina display (default: 1)
+    pub device_scale_factor: f64,
+
+    /// Emulate a mobile device: meta viewport tags apply and scrollbars overlay (default: false)
+    pub mobile: bool,
+}
+
+impl Viewport {
+    /// A desktop viewport of the given size at scale factor 1
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            device_scale_factor: 1.0,
+            mobile: false,
+        }
+    }
+
+    /// Builder method: set the device scale factor
+    pub fn device_scale_factor(mut self, factor: f64) -> Self {
+        self.device_scale_factor = factor;
+        self
+    }
+
+    /// Builder method: emulate a mobile device
+    pub fn mobile(mut self, mobile: bool) -> Self {
+        self.mobile = mobile;
+        self
+    }
+
+    /// Check that the size and scale factor are usable
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=MAX_VIEWPORT_SIZE).contains(&self.width)
+            || !(1..=MAX_VIEWPORT_SIZE).contains(&self.height)
+        {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Viewport size {}x{} must be between 1 and {} pixels per side",
+                self.width, self.height, MAX_VIEWPORT_SIZE
+            )));
+        }
+        if !(self.device_scale_factor > 0.0 && self.device_scale_factor <= 10.0) {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Device scale factor {} must be greater than 0 and at most 10",
+                self.device_scale_factor
+            )));
+        }
+        Ok(())
+    }
+
+    /// Render `tab` at this viewport
+    pub fn apply(&self, tab: &Tab) -> Result<()> {
+        self.validate()?;
+        tab.call_method(Emulation::SetDeviceMetricsOverride {
+            width: self.width,
+            height: self.height,
+            device_scale_factor: self.device_scale_factor,
+            mobile: self.mobile,
+            scale: None,
+            screen_width: None,
+            screen_height: None,
+            position_x: None,
+            position_y: None,
+            dont_set_visible_size: None,
+            screen_orientation: None,
+            viewport: None,
+            display_feature: None,
+            device_posture: None,
+        })
+        .map_err(|e| BrowserError::ChromeError(format!("Failed to set viewport: {}", e)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_viewport() {
+        assert!(Viewport::new(1280, 720).validate().is_ok());
+        assert!(
+            Viewport::new(390, 844)
+                .device_scale_factor(3.0)
+                .mobile(true)
+                .validate()
+                .is_ok()
+        );
+        assert!(Viewport::new(0, 720).validate().is_err());
+        assert!(Viewport::new(1280, 20_000).validate().is_err());
+        assert!(
+            Viewport::new(1280, 720)
+                .device_scale_factor(0.0)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            Viewport::new(1280, 720)
+                .device_scale_factor(f64::NAN)
+                .validate()
+                .is_err()
+        );
+    }
+}
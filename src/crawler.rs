@@ -0,0 +1,606 @@
+//! Parallel site crawler
+//!
+//! A [`Crawler`] fans out over a pool of workers, each driving its own tab
+//! (optionally in an isolated browser context). Workers pull URLs from a shared
+//! frontier, deduplicated by canonical URL, and honour a shared per-host rate
+//! limiter and robots.txt checker. Results are streamed back as each page
+//! finishes, so thousand-page jobs can be consumed incrementally.
+//!
+//! ```rust,no_run
+//! use browser_use::crawler::{CrawlOptions, Crawler};
+//! use browser_use::{BrowserSession, LaunchOptions};
+//!
+//! # fn main() -> browser_use::Result<()> {
+//! let session = BrowserSession::launch(LaunchOptions::default())?;
+//! let crawler = Crawler::from_session(&session, CrawlOptions::new().concurrency(8))?;
+//!
+//! for page in crawler.crawl(["https://example.com"])? {
+//!     println!("{} {} {}", page.status, page.url, page.title);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::browser::BrowserSession;
+use crate::browser::confirmation::ConfirmationPolicy;
+use crate::browser::rate_limit::{RateLimitOptions, RateLimiter};
+use crate::browser::robots::RobotsChecker;
+use crate::browser::scope::OriginScope;
+use crate::browser::session::TabSettings;
+use crate::error::{BrowserError, Result};
+use crate::url::{UrlSet, is_same_site, resolve};
+use headless_chrome::protocol::cdp::Target;
+use headless_chrome::{Browser, Tab};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Collects the response status, title and outgoing links of the loaded page
+const PAGE_INFO_JS: &str = r#"
+    JSON.stringify({
+        status: (performance.getEntriesByType('navigation')[0] || {}).responseStatus || 0,
+        title: document.title || '',
+        links: Array.from(document.querySelectorAll('a[href]'))
+            .map(el => el.getAttribute('href') || '')
+            .filter(href => href !== '')
+    })
+"#;
+
+/// Crawl limits and behaviour
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrawlOptions {
+    /// Maximum number of pages to visit (default: 100)
+    pub max_pages: usize,
+
+    /// Maximum link depth from the seed URLs (default: 2)
+    pub max_depth: usize,
+
+    /// Number of parallel workers (default: 4)
+    pub concurrency: usize,
+
+    /// Only follow links on the same site as a seed URL (default: true)
+    pub same_site: bool,
+
+    /// Give each worker its own incognito-like browser context (default: true)
+    pub isolated_contexts: bool,
+
+    /// Navigation timeout per page in milliseconds (default: 30000)
+    pub page_timeout_ms: u64,
+
+    /// Per-host politeness limits shared by all workers (default: None)
+    pub rate_limit: Option<RateLimitOptions>,
+
+    /// Respect robots.txt for this user agent (default: None)
+    pub robots_user_agent: Option<String>,
+
+    /// Never visit URLs outside these origins (default: None)
+    pub scope: Option<OriginScope>,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self {
+            max_pages: 100,
+            max_depth: 2,
+            concurrency: 4,
+            same_site: true,
+            isolated_contexts: true,
+            page_timeout_ms: 30_000,
+            rate_limit: None,
+            robots_user_agent: None,
+            scope: None,
+        }
+    }
+}
+
+impl CrawlOptions {
+    /// Create new CrawlOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: set maximum number of pages
+    pub fn max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Builder method: set maximum link depth
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Builder method: set number of parallel workers
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Builder method: restrict the crawl to the seeds' sites
+    pub fn same_site(mut self, same_site: bool) -> Self {
+        self.same_site = same_site;
+        self
+    }
+
+    /// Builder method: isolate workers in separate browser contexts
+    pub fn isolated_contexts(mut self, isolated: bool) -> Self {
+        self.isolated_contexts = isolated;
+        self
+    }
+
+    /// Builder method: set per-page navigation timeout
+    pub fn page_timeout(mut self, timeout_ms: u64) -> Self {
+        self.page_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Builder method: throttle requests per host
+    pub fn rate_limit(mut self, rate_limit: RateLimitOptions) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Builder method: respect robots.txt for the given user agent
+    pub fn respect_robots_txt(mut self, user_agent: impl Into<String>) -> Self {
+        self.robots_user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Builder method: never leave the given origins
+    pub fn scope(mut self, scope: OriginScope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+}
+
+/// Outcome of visiting a single page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrawlResult {
+    /// URL that was requested
+    pub url: String,
+
+    /// URL after redirects
+    pub final_url: String,
+
+    /// Link depth from the nearest seed
+    pub depth: usize,
+
+    /// HTTP status of the document (0 when unknown or the page failed to load)
+    pub status: u16,
+
+    /// Document title
+    pub title: String,
+
+    /// Absolute, deduplicated links found on the page
+    pub links: Vec<String>,
+
+    /// Time spent on the page in milliseconds, including throttling
+    pub duration_ms: u64,
+
+    /// Index of the worker that visited the page
+    pub worker: usize,
+
+    /// Error message if the page could not be visited
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Page information returned by [`PAGE_INFO_JS`]
+#[derive(Debug, Default, Deserialize)]
+struct PageInfo {
+    status: u16,
+    title: String,
+    links: Vec<String>,
+}
+
+/// Shared work queue of URLs waiting to be visited
+#[derive(Debug, Default)]
+struct Frontier {
+    queue: VecDeque<(String, usize)>,
+    seen: UrlSet,
+    scheduled: usize,
+    in_flight: usize,
+    stopped: bool,
+}
+
+/// State shared by all workers of one crawl
+struct CrawlShared {
+    browser: Browser,
+    options: CrawlOptions,
+    seeds: Vec<String>,
+    frontier: Mutex<Frontier>,
+    changed: Condvar,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    robots: Option<RobotsChecker>,
+    confirmation: ConfirmationPolicy,
+}
+
+impl CrawlShared {
+    /// Block until a URL is available, or return `None` once the crawl is exhausted
+    fn next_job(&self) -> Option<(String, usize)> {
+        let mut frontier = self.frontier.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if frontier.stopped || frontier.scheduled >= self.options.max_pages {
+                return None;
+            }
+            if let Some(job) = frontier.queue.pop_front() {
+                frontier.scheduled += 1;
+                frontier.in_flight += 1;
+                return Some(job);
+            }
+            if frontier.in_flight == 0 {
+                return None;
+            }
+            frontier = self
+                .changed
+                .wait(frontier)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Mark a page as done and enqueue its in-scope links
+    fn finish_job(&self, result: &CrawlResult) {
+        let mut frontier = self.frontier.lock().unwrap_or_else(|e| e.into_inner());
+        frontier.in_flight = frontier.in_flight.saturating_sub(1);
+
+        if result.depth < self.options.max_depth {
+            for link in &result.links {
+                if self.in_scope(link) && frontier.seen.insert(link) {
+                    frontier.queue.push_back((link.clone(), result.depth + 1));
+                }
+            }
+        }
+
+        self.changed.notify_all();
+    }
+
+    fn stop(&self) {
+        self.frontier
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .stopped = true;
+        self.changed.notify_all();
+    }
+
+    fn in_scope(&self, url: &str) -> bool {
+        if let Some(scope) = &self.options.scope
+            && !scope.allows(url)
+        {
+            return false;
+        }
+        !self.options.same_site || self.seeds.iter().any(|seed| is_same_site(seed, url))
+    }
+
+    /// Visit one page with the worker's tab
+    fn visit(&self, tab: &Tab, url: &str, depth: usize, worker: usize) -> CrawlResult {
+        let started = Instant::now();
+
+        let outcome = (|| -> Result<PageInfo> {
+            self.confirmation.confirm("navigate", None, Some(url))?;
+            if let Some(robots) = &self.robots {
+                robots.check(&self.browser, url)?;
+            }
+            let _permit = self.rate_limiter.as_ref().and_then(|l| l.acquire(url));
+
+            tab.navigate_to(url)
+                .and_then(|tab| tab.wait_until_navigated())
+                .map_err(|e| {
+                    BrowserError::NavigationFailed(format!("Failed to navigate to {}: {}", url, e))
+                })?;
+
+            let value = tab
+                .evaluate(PAGE_INFO_JS, false)
+                .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?
+                .value
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or_default();
+            Ok(serde_json::from_str(&value)?)
+        })();
+
+        let final_url = tab.get_url();
+        let (info, error) = match outcome {
+            Ok(info) => (info, None),
+            Err(e) => (PageInfo::default(), Some(e.to_string())),
+        };
+
+        let mut seen = UrlSet::new();
+        let links = info
+            .links
+            .iter()
+            .filter_map(|href| resolve(&final_url, href))
+            .filter(|link| seen.insert(link))
+            .collect();
+
+        CrawlResult {
+            url: url.to_string(),
+            final_url,
+            depth,
+            status: info.status,
+            title: info.title,
+            links,
+            duration_ms: started.elapsed().as_millis() as u64,
+            worker,
+            error,
+        }
+    }
+}
+
+/// Parallel crawler over a pool of browser tabs
+pub struct Crawler {
+    browser: Browser,
+    options: CrawlOptions,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    confirmation: ConfirmationPolicy,
+    tab_settings: Option<TabSettings>,
+}
+
+impl Crawler {
+    /// Create a crawler driving the given browser
+    pub fn new(browser: Browser, options: CrawlOptions) -> Self {
+        Self {
+            browser,
+            options,
+            rate_limiter: None,
+            confirmation: ConfirmationPolicy::default(),
+            tab_settings: None,
+        }
+    }
+
+    /// Create a crawler that shares the browser of an existing session
+    ///
+    /// A session that throttles requests shares its rate limiter with the
+    /// crawl, so crawl and navigation traffic to a host count against the same
+    /// limits; `options.rate_limit` only applies to sessions without one.
+    /// Every page also needs the approval the session's confirmation policy
+    /// asks for navigations to its URL, and worker tabs get the session's
+    /// extra headers, proxy credentials and rendering settings. Fails for mock
+    /// sessions, which have no browser to crawl with.
+    pub fn from_session(session: &BrowserSession, options: CrawlOptions) -> Result<Self> {
+        Ok(Self {
+            rate_limiter: session.rate_limiter(),
+            confirmation: session.confirmation().clone(),
+            tab_settings: Some(session.tab_settings()),
+            ..Self::new(session.browser()?.clone(), options)
+        })
+    }
+
+    /// Crawl options in use
+    pub fn options(&self) -> &CrawlOptions {
+        &self.options
+    }
+
+    /// Start crawling from the seed URLs and stream results as pages finish
+    ///
+    /// Worker tabs are opened up front; an error is returned only if none of
+    /// them could be created.
+    pub fn crawl<I, S>(&self, seeds: I) -> Result<CrawlStream>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut frontier = Frontier::default();
+        let mut seed_urls = Vec::new();
+        for seed in seeds {
+            let seed = seed.as_ref().trim().to_string();
+            if frontier.seen.insert(&seed) {
+                frontier.queue.push_back((seed.clone(), 0));
+                seed_urls.push(seed);
+            }
+        }
+        if seed_urls.is_empty() {
+            return Err(BrowserError::InvalidArgument(
+                "At least one seed URL is required".to_string(),
+            ));
+        }
+        if let Some(scope) = &self.options.scope {
+            for seed in &seed_urls {
+                scope.check(seed)?;
+            }
+        }
+
+        let shared = Arc::new(CrawlShared {
+            browser: self.browser.clone(),
+            options: self.options.clone(),
+            seeds: seed_urls,
+            frontier: Mutex::new(frontier),
+            changed: Condvar::new(),
+            rate_limiter: self.rate_limiter.clone().or_else(|| {
+                self.options
+                    .rate_limit
+                    .clone()
+                    .map(|limit| Arc::new(RateLimiter::new(limit)))
+            }),
+            robots: self
+                .options
+                .robots_user_agent
+                .clone()
+                .map(RobotsChecker::new),
+            confirmation: self.confirmation.clone(),
+        });
+
+        let mut tabs = Vec::new();
+        let mut contexts = Vec::new();
+        let mut last_error = None;
+        for _ in 0..self.options.concurrency.max(1) {
+            match self.open_worker_tab(&mut contexts) {
+                Ok(tab) => tabs.push(tab),
+                Err(e) => {
+                    log::warn!("Failed to open crawler tab: {}", e);
+                    last_error = Some(e);
+                }
+            }
+        }
+        if tabs.is_empty() {
+            dispose_contexts(&self.browser, &contexts);
+            return Err(last_error.unwrap_or_else(|| {
+                BrowserError::TabOperationFailed("No crawler tabs available".to_string())
+            }));
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let workers = tabs
+            .into_iter()
+            .enumerate()
+            .map(|(worker, tab)| {
+                let shared = Arc::clone(&shared);
+                let sender = sender.clone();
+                std::thread::spawn(move || run_worker(worker, tab, shared, sender))
+            })
+            .collect();
+
+        Ok(CrawlStream {
+            receiver,
+            shared,
+            workers,
+            contexts,
+        })
+    }
+
+    /// Open a worker tab, recording the id of the browser context made for it
+    fn open_worker_tab(&self, contexts: &mut Vec<String>) -> Result<Arc<Tab>> {
+        let failed = |e: anyhow::Error| {
+            BrowserError::TabOperationFailed(format!("Failed to create tab: {}", e))
+        };
+        let tab = if self.options.isolated_contexts {
+            let context = self.browser.new_context().map_err(failed)?;
+            contexts.push(context.get_id().to_string());
+            context.new_tab()
+        } else {
+            self.browser.new_tab()
+        }
+        .map_err(failed)?;
+
+        tab.set_default_timeout(Duration::from_millis(self.options.page_timeout_ms));
+        if let Some(settings) = &self.tab_settings
+            && let Err(e) = settings.apply(&tab)
+        {
+            let _ = tab.close(false);
+            return Err(e);
+        }
+        Ok(tab)
+    }
+}
+
+/// Dispose of the browser contexts made for worker tabs, once those tabs are closed
+///
+/// The command goes through a tab outside the contexts; without one the
+/// contexts are left to close with the browser.
+fn dispose_contexts(browser: &Browser, contexts: &[String]) {
+    if contexts.is_empty() {
+        return;
+    }
+    let tabs = browser
+        .get_tabs()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let outside = tabs.iter().find(|tab| {
+        tab.get_browser_context_id()
+            .ok()
+            .flatten()
+            .is_none_or(|id| !contexts.contains(&id))
+    });
+    let Some(tab) = outside else {
+        log::warn!("No tab left to dispose of crawler browser contexts through");
+        return;
+    };
+    for id in contexts {
+        if let Err(e) = tab.call_method(Target::DisposeBrowserContext {
+            browser_context_id: id.clone(),
+        }) {
+            log::warn!("Failed to dispose of browser context {}: {}", id, e);
+        }
+    }
+}
+
+fn run_worker(worker: usize, tab: Arc<Tab>, shared: Arc<CrawlShared>, sender: Sender<CrawlResult>) {
+    while let Some((url, depth)) = shared.next_job() {
+        let result = shared.visit(&tab, &url, depth, worker);
+        shared.finish_job(&result);
+        if sender.send(result).is_err() {
+            shared.stop();
+            break;
+        }
+    }
+
+    let _ = tab.close(false);
+}
+
+/// Stream of crawl results, yielded in completion order
+///
+/// Dropping the stream stops the crawl after in-flight pages finish and
+/// disposes of the workers' browser contexts.
+pub struct CrawlStream {
+    receiver: Receiver<CrawlResult>,
+    shared: Arc<CrawlShared>,
+    workers: Vec<JoinHandle<()>>,
+    contexts: Vec<String>,
+}
+
+impl CrawlStream {
+    /// Stop scheduling new pages; pages already in flight are still yielded
+    pub fn stop(&self) {
+        self.shared.stop();
+    }
+}
+
+impl Iterator for CrawlStream {
+    type Item = CrawlResult;
+
+    fn next(&mut self) -> Option<CrawlResult> {
+        self.receiver.recv().ok()
+    }
+}
+
+impl Drop for CrawlStream {
+    fn drop(&mut self) {
+        self.shared.stop();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+        dispose_contexts(&self.shared.browser, &self.contexts);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crawl_options_builder() {
+        let opts = CrawlOptions::new()
+            .max_pages(10)
+            .max_depth(1)
+            .concurrency(0)
+            .same_site(false)
+            .respect_robots_txt("browser-use");
+
+        assert_eq!(opts.max_pages, 10);
+        assert_eq!(opts.max_depth, 1);
+        assert_eq!(opts.concurrency, 1);
+        assert!(!opts.same_site);
+        assert_eq!(opts.robots_user_agent.as_deref(), Some("browser-use"));
+    }
+
+    #[test]
+    fn test_crawl_result_serialization() {
+        let result = CrawlResult {
+            url: "https://example.com".to_string(),
+            final_url: "https://example.com/".to_string(),
+            depth: 0,
+            status: 200,
+            title: "Example".to_string(),
+            links: vec![],
+            duration_ms: 5,
+            worker: 0,
+            error: None,
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["status"], 200);
+        assert!(json.get("error").is_none());
+    }
+}
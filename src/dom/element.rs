@@ -82,7 +82,7 @@ pub enum AriaPressed {
 }
 
 /// Box/visibility information for an element
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct BoxInfo {
     /// Whether the element is visible (non-zero bounding box)
     #[serde(default)]
@@ -93,15 +93,6 @@ pub struct BoxInfo {
     pub cursor: Option<String>,
 }
 
-impl Default for BoxInfo {
-    fn default() -> Self {
-        Self {
-            visible: false,
-            cursor: None,
-        }
-    }
-}
-
 impl AriaNode {
     /// Create a new AriaNode with minimal fields
     pub fn new(role: impl Into<String>, name: impl Into<String>) -> Self {
@@ -191,7 +182,14 @@ impl AriaNode {
         self.box_info
             .cursor
             .as_ref()
-            .map_or(false, |c| c == "pointer")
+            .is_some_and(|c| c == "pointer")
+    }
+
+    /// Check if this is a password or other secret input whose value was masked
+    ///
+    /// Snapshots report only the length of such values, in the `masked` prop.
+    pub fn is_masked(&self) -> bool {
+        self.props.contains_key("masked")
     }
 
     /// Check if this is a fragment or iframe
@@ -239,10 +237,10 @@ impl AriaNode {
         }
 
         for child in &self.children {
-            if let AriaChild::Node(node) = child {
-                if let Some(found) = node.find_by_index(index) {
-                    return Some(found);
-                }
+            if let AriaChild::Node(node) = child
+                && let Some(found) = node.find_by_index(index)
+            {
+                return Some(found);
             }
         }
 
@@ -256,10 +254,10 @@ impl AriaNode {
         }
 
         for child in &mut self.children {
-            if let AriaChild::Node(node) = child {
-                if let Some(found) = node.find_by_index_mut(index) {
-                    return Some(found);
-                }
+            if let AriaChild::Node(node) = child
+                && let Some(found) = node.find_by_index_mut(index)
+            {
+                return Some(found);
             }
         }
 
@@ -4,6 +4,11 @@ JSON.stringify((function() {
     'use strict';
     
     let currentIndex = 0;
+    const config = __EXTRACT_DOM_CONFIG__;
+
+    // Inputs whose values are masked in snapshots
+    const SECRET_AUTOCOMPLETE = ['current-password', 'new-password', 'one-time-code', 'cc-number', 'cc-csc'];
+    const SECRET_NAME = /passw|passcode|secret|token|api[-_]?key|\botp\b|cvc|cvv/i;
 
     // Helper: normalize whitespace
     function normalizeWhiteSpace(text) {
@@ -52,6 +57,15 @@ JSON.stringify((function() {
         return false;
     }
 
+    // Helper: check if an input holds a password or other secret
+    function isSecretInput(element) {
+        if (element.tagName !== 'INPUT' && element.tagName !== 'TEXTAREA') return false;
+        if ((element.type || '').toLowerCase() === 'password') return true;
+        const autocomplete = (element.getAttribute('autocomplete') || '').toLowerCase().split(/\s+/);
+        if (autocomplete.some(token => SECRET_AUTOCOMPLETE.includes(token))) return true;
+        return SECRET_NAME.test(element.name || '') || SECRET_NAME.test(element.id || '');
+    }
+
     // Helper: check if element is visible (bounding box check)
     function isElementVisible(element) {
         const rect = element.getBoundingClientRect();
@@ -185,11 +199,41 @@ JSON.stringify((function() {
         if (element.tagName === 'A' || element.tagName === 'BUTTON') {
             const text = element.textContent || '';
             if (text.trim()) return text.trim();
+            return getIconName(element);
         }
         
         return '';
     }
 
+    // Helper: name of an icon-only control, taken from the icon inside it
+    function getIconName(element) {
+        for (const icon of element.querySelectorAll('[aria-label], img[alt], svg title')) {
+            const name = icon.tagName === 'IMG' ? icon.getAttribute('alt')
+                : icon.tagName.toLowerCase() === 'title' ? icon.textContent
+                : icon.getAttribute('aria-label');
+            if (name && name.trim()) return name.trim();
+        }
+        return '';
+    }
+
+    // Helper: attribute that tells apart controls without an accessible name
+    function getUnnamedHint(element) {
+        for (const attribute of ['data-testid', 'name', 'id']) {
+            const value = element.getAttribute(attribute);
+            if (value) return `${attribute}=${value}`;
+        }
+        // Icon sprites and icon fonts name the icon in a reference or class
+        const use = element.querySelector('svg use');
+        const href = use && (use.getAttribute('href') || use.getAttribute('xlink:href'));
+        if (href && href.includes('#')) return `icon=${href.split('#').pop()}`;
+        for (const node of [element, ...element.querySelectorAll('i, span, svg')]) {
+            const classes = typeof node.className === 'string' ? node.className : node.getAttribute('class') || '';
+            const icon = classes.split(/\s+/).find(name => /(^|-)icon-|^fa-(?!solid$|regular$|brands$)|^bi-|^mdi-/.test(name));
+            if (icon) return `icon=${icon}`;
+        }
+        return null;
+    }
+
     // Helper: get ARIA checked state
     function getAriaChecked(element) {
         const checked = element.getAttribute('aria-checked');
@@ -364,7 +408,10 @@ JSON.stringify((function() {
             if (checked !== undefined) result.checked = checked;
         }
         
-        const disabledRoles = ['button', 'input', 'select', 'textarea'];
+        const disabledRoles = [
+            'button', 'checkbox', 'combobox', 'listbox', 'option', 'radio', 'searchbox',
+            'slider', 'spinbutton', 'switch', 'tab', 'textbox'
+        ];
         if (disabledRoles.includes(role) || role.includes('menuitem')) {
             const disabled = getAriaDisabled(element);
             if (disabled !== undefined) result.disabled = disabled;
@@ -396,7 +443,11 @@ JSON.stringify((function() {
         
         // Special handling for input/textarea values
         if (element.tagName === 'INPUT' || element.tagName === 'TEXTAREA') {
-            if (element.type !== 'checkbox' && element.type !== 'radio' && element.type !== 'file') {
+            if (isSecretInput(element)) {
+                // Report only the length so secrets stay out of transcripts
+                const length = (element.value || '').length;
+                if (length > 0) result.props.masked = `${length} characters`;
+            } else if (element.type !== 'checkbox' && element.type !== 'radio' && element.type !== 'file') {
                 result.children = [element.value || ''];
             }
         }
@@ -515,12 +566,29 @@ JSON.stringify((function() {
             ariaNode.props.url = element.getAttribute('href');
         }
         
-        if (ariaNode.role === 'textbox' && element.hasAttribute('placeholder')) {
+        const placeholderRoles = ['textbox', 'searchbox', 'combobox', 'spinbutton'];
+        if (placeholderRoles.includes(ariaNode.role) && element.hasAttribute('placeholder')) {
             const placeholder = element.getAttribute('placeholder');
             if (placeholder !== ariaNode.name) {
                 ariaNode.props.placeholder = placeholder;
             }
         }
+        
+        // A closed select shows its chosen option, not its option list
+        if (element.tagName === 'SELECT' && ariaNode.role === 'combobox') {
+            const option = element.options[element.selectedIndex];
+            const value = option ? option.text.trim() : '';
+            if (value && value !== ariaNode.name) {
+                ariaNode.props.value = value;
+            }
+        }
+        
+        // Unnamed controls (icon buttons) would otherwise all look identical
+        const unnamed = !ariaNode.name && ariaNode.children.every(child => typeof child === 'string' && !child.trim());
+        if (unnamed && ariaNode.index !== undefined && ariaNode.role !== 'generic') {
+            const hint = getUnnamedHint(element);
+            if (hint) ariaNode.props.hint = hint;
+        }
     }
 
     // Normalize string children
@@ -647,6 +715,109 @@ JSON.stringify((function() {
         }
     }
 
+    // Scroll position, page size, and indexed elements outside the viewport
+    function collectPageMetrics(snapshot) {
+        const scroller = document.scrollingElement || document.documentElement;
+        const metrics = {
+            scrollX: window.scrollX,
+            scrollY: window.scrollY,
+            viewportWidth: window.innerWidth,
+            viewportHeight: window.innerHeight,
+            pageWidth: Math.max(scroller.scrollWidth, window.innerWidth),
+            pageHeight: Math.max(scroller.scrollHeight, window.innerHeight),
+            offscreenAbove: 0,
+            offscreenBelow: 0,
+            offscreenBeside: 0
+        };
+        
+        function visitNode(ariaNode) {
+            if (ariaNode.index !== undefined && ariaNode.box && ariaNode.box.rect) {
+                const rect = ariaNode.box.rect;
+                if (rect.bottom <= 0) metrics.offscreenAbove++;
+                else if (rect.top >= window.innerHeight) metrics.offscreenBelow++;
+                else if (rect.right <= 0 || rect.left >= window.innerWidth) metrics.offscreenBeside++;
+            }
+            for (const child of ariaNode.children) {
+                if (typeof child !== 'string') visitNode(child);
+            }
+        }
+        visitNode(snapshot);
+        
+        return metrics;
+    }
+
+    // Renumber indices in reading order: top to bottom, left to right, one column at a time
+    function orderIndicesVisually(snapshot) {
+        // Indexed nodes with their indexed ancestors, in DOM order
+        const entries = [];
+        function collect(ariaNode, ancestors) {
+            let inner = ancestors;
+            if (ariaNode.index !== undefined) {
+                const rect = ariaNode.box.rect;
+                const entry = {
+                    node: ariaNode,
+                    ancestors,
+                    leaf: true,
+                    top: rect.top + window.scrollY,
+                    left: rect.left + window.scrollX,
+                    bottom: rect.bottom + window.scrollY,
+                    right: rect.right + window.scrollX
+                };
+                ancestors.forEach(ancestor => { ancestor.leaf = false; });
+                entries.push(entry);
+                inner = ancestors.concat([entry]);
+            }
+            for (const child of ariaNode.children) {
+                if (typeof child !== 'string') collect(child, inner);
+            }
+        }
+        collect(snapshot, []);
+
+        // Boxes closer than this are treated as touching
+        const TOLERANCE = 2;
+
+        // Widest empty band across the boxes along one axis: [gap size, cut position]
+        function widestGap(boxes, start, end) {
+            const sorted = boxes.slice().sort((a, b) => a[start] - b[start]);
+            let reach = sorted[0][end];
+            let best = [0, null];
+            for (const box of sorted.slice(1)) {
+                const gap = box[start] - reach;
+                if (gap > TOLERANCE && gap > best[0]) best = [gap, box[start]];
+                reach = Math.max(reach, box[end]);
+            }
+            return best;
+        }
+
+        // Recursive XY-cut; the wider of a row gap and a column gap is cut first
+        function readingOrder(boxes) {
+            if (boxes.length <= 1) return boxes;
+            const [rowGap, rowCut] = widestGap(boxes, 'top', 'bottom');
+            const [columnGap, columnCut] = widestGap(boxes, 'left', 'right');
+            if (rowCut === null && columnCut === null) {
+                return boxes.slice().sort((a, b) => a.top - b.top || a.left - b.left);
+            }
+            const [start, cut] = rowGap >= columnGap ? ['top', rowCut] : ['left', columnCut];
+            return readingOrder(boxes.filter(box => box[start] < cut))
+                .concat(readingOrder(boxes.filter(box => box[start] >= cut)));
+        }
+
+        // Containers (indexed nodes holding other indexed nodes) would span their
+        // contents and block every cut, so only leaves are laid out; a container
+        // goes right before the first of its contents
+        const leaves = readingOrder(entries.filter(entry => entry.leaf));
+        leaves.forEach((entry, rank) => { entry.rank = rank; });
+        for (const entry of leaves) {
+            for (const ancestor of entry.ancestors) {
+                if (ancestor.rank === undefined || ancestor.rank > entry.rank) ancestor.rank = entry.rank;
+            }
+        }
+        const ordered = entries
+            .filter(entry => entry.rank !== undefined)
+            .sort((a, b) => a.rank - b.rank || a.leaf - b.leaf || a.ancestors.length - b.ancestors.length);
+        ordered.forEach((entry, index) => { entry.node.index = index; });
+    }
+
     // Build CSS selector for element
     function buildSelector(element) {
         if (element.id) {
@@ -708,6 +879,9 @@ JSON.stringify((function() {
         // Normalize
         normalizeStringChildren(snapshot);
         normalizeGenericRoles(snapshot);
+        if (config.indexOrder === 'visual') {
+            orderIndicesVisually(snapshot);
+        }
         
         // Collect selectors and iframe indices
         const selectors = [];
@@ -720,7 +894,8 @@ JSON.stringify((function() {
         return {
             root: serialized,
             selectors: selectors,
-            iframeIndices: iframeIndices
+            iframeIndices: iframeIndices,
+            page: collectPageMetrics(snapshot)
         };
         
     } catch (error) {
@@ -4,11 +4,20 @@
 //! of web pages. It includes:
 //! - ElementNode: Representation of DOM elements
 //! - DomTree: Complete DOM tree with indexing for interactive elements
+//! - selector: Deep selectors that pierce iframes and shadow roots (`iframe >>> button`)
+//! - suggest: Closest elements for indices and selectors that match nothing
+//! - wire: Binary encoding of snapshots for other processes (requires `binary` feature)
 
 pub mod element;
+pub mod selector;
+pub mod suggest;
 pub mod tree;
+#[cfg(feature = "binary")]
+pub mod wire;
 pub mod yaml;
 
 pub use element::{AriaChild, AriaNode, BoundingBox, ElementNode};
-pub use tree::DomTree;
+pub use selector::DeepSelector;
+pub use suggest::{ElementSuggestion, SuggestionReason};
+pub use tree::{DomTree, IndexOrder, PageMetrics};
 pub use yaml::{yaml_escape_key_if_needed, yaml_escape_value_if_needed};
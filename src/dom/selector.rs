@@ -0,0 +1,190 @@
+//! Selectors that pierce iframes and shadow roots
+//!
+//! A plain CSS selector only matches within one document or shadow tree. A
+//! deep selector chains CSS selectors with `>>>`; each step but the last must
+//! match an `<iframe>` (or `<frame>`), whose document the next step searches,
+//! or a shadow host, whose open shadow root the next step searches:
+//!
+//! ```text
+//! iframe#checkout >>> payment-form >>> button.pay
+//! ```
+//!
+//! [`BrowserSession::find_element`](crate::browser::BrowserSession::find_element)
+//! accepts both forms, so every tool that takes a `selector` understands deep
+//! selectors. Frames from another origin cannot be entered.
+
+use crate::error::{BrowserError, Result};
+use headless_chrome::protocol::cdp::DOM;
+use headless_chrome::{Element, Tab};
+
+/// Combinator separating the steps of a deep selector
+pub const PIERCE_COMBINATOR: &str = ">>>";
+
+/// Walks the steps from the top document; returns the element, or why it was not found
+const RESOLVE_DEEP_SELECTOR_JS: &str = r#"
+(function (steps) {
+  let scope = document;
+  for (let i = 0; i < steps.length; i++) {
+    let element;
+    try {
+      element = scope.querySelector(steps[i]);
+    } catch (e) {
+      return `invalid selector '${steps[i]}'`;
+    }
+    if (!element) {
+      return i === 0 ? `no match for '${steps[i]}'` : `no match for '${steps[i]}' inside '${steps[i - 1]}'`;
+    }
+    if (i === steps.length - 1) {
+      return element;
+    }
+    if (element.tagName === 'IFRAME' || element.tagName === 'FRAME') {
+      let inner = null;
+      try {
+        inner = element.contentDocument;
+      } catch (e) {}
+      if (!inner) {
+        return `cannot enter frame '${steps[i]}': it is cross-origin or not loaded`;
+      }
+      scope = inner;
+    } else if (element.shadowRoot) {
+      scope = element.shadowRoot;
+    } else {
+      return `'${steps[i]}' is neither a frame nor a host with an open shadow root`;
+    }
+  }
+})"#;
+
+/// A selector of CSS steps separated by `>>>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeepSelector {
+    steps: Vec<String>,
+}
+
+impl DeepSelector {
+    /// Whether `selector` uses the `>>>` combinator outside quotes
+    pub fn is_deep(selector: &str) -> bool {
+        split_steps(selector).len() > 1
+    }
+
+    /// Parse a deep selector, rejecting empty steps
+    pub fn parse(selector: &str) -> Result<Self> {
+        let steps: Vec<String> = split_steps(selector)
+            .into_iter()
+            .map(|step| step.trim().to_string())
+            .collect();
+        if steps.iter().any(|step| step.is_empty()) {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Invalid selector '{}': every '{}' needs a selector on both sides",
+                selector, PIERCE_COMBINATOR
+            )));
+        }
+        Ok(Self { steps })
+    }
+
+    /// CSS selectors of each step, outermost first
+    pub fn steps(&self) -> &[String] {
+        &self.steps
+    }
+
+    /// Find the element the selector points to in `tab`
+    pub fn find<'a>(&self, tab: &'a Tab) -> Result<Element<'a>> {
+        let expression = format!(
+            "{}({})",
+            RESOLVE_DEEP_SELECTOR_JS,
+            serde_json::Value::from(self.steps.clone())
+        );
+        let object = tab
+            .evaluate(&expression, false)
+            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
+
+        let Some(object_id) = object.object_id else {
+            let reason = object
+                .value
+                .as_ref()
+                .and_then(|value| value.as_str())
+                .unwrap_or("no match");
+            return Err(BrowserError::ElementNotFound(reason.to_string()));
+        };
+
+        // The DOM agent only hands out node ids once it has sent the document
+        tab.get_document()
+            .and_then(|_| tab.call_method(DOM::RequestNode { object_id }))
+            .and_then(|node| Element::new(tab, node.node_id))
+            .map_err(|e| BrowserError::ElementNotFound(e.to_string()))
+    }
+}
+
+impl std::fmt::Display for DeepSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.steps.join(" >>> "))
+    }
+}
+
+/// Split on `>>>` that is not inside quotes, brackets or parentheses
+fn split_steps(selector: &str) -> Vec<&str> {
+    let mut steps = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut chars = selector.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\\') => {
+                chars.next();
+            }
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '(') => depth += 1,
+            (None, ']' | ')') => depth = depth.saturating_sub(1),
+            (None, '>') if depth == 0 && selector[i..].starts_with(PIERCE_COMBINATOR) => {
+                steps.push(&selector[start..i]);
+                start = i + PIERCE_COMBINATOR.len();
+                chars.nth(PIERCE_COMBINATOR.len() - 2);
+            }
+            _ => {}
+        }
+    }
+    steps.push(&selector[start..]);
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let selector = DeepSelector::parse("iframe#checkout >>> my-widget>>>button.pay").unwrap();
+        assert_eq!(
+            selector.steps(),
+            &["iframe#checkout", "my-widget", "button.pay"]
+        );
+        assert_eq!(
+            selector.to_string(),
+            "iframe#checkout >>> my-widget >>> button.pay"
+        );
+    }
+
+    #[test]
+    fn test_is_deep() {
+        assert!(DeepSelector::is_deep("a >>> b"));
+        assert!(!DeepSelector::is_deep("div > p"));
+        assert!(!DeepSelector::is_deep("[title='a >>> b']"));
+        assert!(!DeepSelector::is_deep(r#"[title="say \" >>> "]"#));
+        assert!(!DeepSelector::is_deep(":is(a >>> b)"));
+        assert!(DeepSelector::is_deep("[title='x'] >>> :is(a, b)"));
+    }
+
+    #[test]
+    fn test_rejects_empty_steps() {
+        for selector in [">>> button", "iframe >>>", "a >>> >>> b"] {
+            let error = DeepSelector::parse(selector).unwrap_err();
+            assert!(matches!(error, BrowserError::InvalidArgument(_)));
+        }
+    }
+}
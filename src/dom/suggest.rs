@@ -0,0 +1,283 @@
+//! Closest matches for element references that resolve to nothing
+//!
+//! When an index or selector no longer matches, the caller usually meant an
+//! element that moved, was renamed slightly, or sits next to the one it asked
+//! for. These helpers rank the current snapshot's elements against what was
+//! asked for so the error can say which ones to try instead.
+
+use crate::dom::{AriaChild, AriaNode, DomTree};
+use serde::Serialize;
+
+/// Maximum number of suggestions attached to an error
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Similarity below which elements are not suggested
+const MIN_SIMILARITY: f64 = 0.4;
+
+/// An element the caller may have meant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ElementSuggestion {
+    /// Index of the element in the current snapshot
+    pub index: usize,
+    /// CSS selector of the element
+    pub selector: String,
+    /// ARIA role
+    pub role: String,
+    /// Accessible name, or text content (then placeholder or hint) when it has none
+    pub name: String,
+    /// Why the element is suggested
+    pub reason: SuggestionReason,
+}
+
+/// Why an element is suggested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SuggestionReason {
+    /// Same role and a similar name as the element that had the index in an earlier snapshot
+    SameElement,
+    /// Index close to the one asked for
+    NearbyIndex,
+    /// Selector similar to the one asked for
+    SimilarSelector,
+    /// Name similar to the words in the selector asked for
+    SimilarText,
+}
+
+/// Lowercase character bigrams, ignoring whitespace
+fn bigrams(text: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+/// Dice similarity of two strings' character bigrams, from 0 (nothing shared) to 1 (equal)
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a_pairs = bigrams(a);
+    let mut b_pairs = bigrams(b);
+    if a_pairs.is_empty() || b_pairs.is_empty() {
+        return if a.trim().to_lowercase() == b.trim().to_lowercase() {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    let total = a_pairs.len() + b_pairs.len();
+    let mut shared = 0;
+    for pair in a_pairs {
+        if let Some(position) = b_pairs.iter().position(|other| *other == pair) {
+            b_pairs.swap_remove(position);
+            shared += 1;
+        }
+    }
+    (2 * shared) as f64 / total as f64
+}
+
+/// Accessible name of a node, or its text when it has none
+pub(crate) fn label(node: &AriaNode) -> String {
+    if !node.name.is_empty() {
+        return node.name.clone();
+    }
+    let text = node.get_text_content();
+    if !text.trim().is_empty() {
+        return text;
+    }
+    // Unnamed icon buttons are told apart by a hint attribute instead
+    ["placeholder", "hint"]
+        .iter()
+        .find_map(|key| node.props.get(*key).cloned())
+        .unwrap_or(text)
+}
+
+fn collect_indexed<'a>(node: &'a AriaNode, nodes: &mut Vec<(usize, &'a AriaNode)>) {
+    if let Some(index) = node.index {
+        nodes.push((index, node));
+    }
+    for child in &node.children {
+        if let AriaChild::Node(child) = child {
+            collect_indexed(child, nodes);
+        }
+    }
+}
+
+/// Indexed elements of a tree that have a selector
+fn indexed_elements(tree: &DomTree) -> Vec<(usize, &AriaNode)> {
+    let mut nodes = Vec::new();
+    collect_indexed(&tree.root, &mut nodes);
+    nodes.retain(|(index, _)| tree.get_selector(*index).is_some());
+    nodes
+}
+
+fn suggestion(
+    tree: &DomTree,
+    index: usize,
+    node: &AriaNode,
+    reason: SuggestionReason,
+) -> ElementSuggestion {
+    ElementSuggestion {
+        index,
+        selector: tree.get_selector(index).cloned().unwrap_or_default(),
+        role: node.role.clone(),
+        name: label(node),
+        reason,
+    }
+}
+
+/// Best-scoring candidates, highest first, dropping those below [`MIN_SIMILARITY`]
+fn best(
+    mut scored: Vec<(f64, usize, &AriaNode, SuggestionReason)>,
+    tree: &DomTree,
+) -> Vec<ElementSuggestion> {
+    scored.retain(|(score, ..)| *score >= MIN_SIMILARITY);
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, index, node, reason)| suggestion(tree, index, node, reason))
+        .collect()
+}
+
+/// Elements of `tree` the caller may have meant by an `index` it does not have
+///
+/// If `previous` (an earlier snapshot) had an element at `index`, elements of
+/// the same role with a similar name are suggested; otherwise the elements
+/// with the closest indices.
+pub fn suggest_for_index(
+    tree: &DomTree,
+    previous: Option<&DomTree>,
+    index: usize,
+) -> Vec<ElementSuggestion> {
+    let elements = indexed_elements(tree);
+
+    if let Some(old) = previous.and_then(|previous| previous.find_node_by_index(index)) {
+        let old_label = label(old);
+        let scored = elements
+            .iter()
+            .filter(|(_, node)| node.role == old.role)
+            .map(|&(i, node)| {
+                let score = similarity(&old_label, &label(node));
+                (score, i, node, SuggestionReason::SameElement)
+            })
+            .collect();
+        let suggestions = best(scored, tree);
+        if !suggestions.is_empty() {
+            return suggestions;
+        }
+    }
+
+    let mut nearby = elements;
+    nearby.sort_by_key(|(i, _)| (i.abs_diff(index), *i));
+    nearby
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(i, node)| suggestion(tree, i, node, SuggestionReason::NearbyIndex))
+        .collect()
+}
+
+/// Elements of `tree` whose selector or name resembles a `selector` that matched nothing
+pub fn suggest_for_selector(tree: &DomTree, selector: &str) -> Vec<ElementSuggestion> {
+    // "#submit-button" or "button[aria-label='Save draft']" -> "submit button", "button aria label save draft"
+    let words = selector
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 1)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let scored = indexed_elements(tree)
+        .into_iter()
+        .map(|(index, node)| {
+            let element_selector = tree.get_selector(index).map(String::as_str);
+            let by_selector = similarity(selector, element_selector.unwrap_or_default());
+            let by_text = if words.is_empty() {
+                0.0
+            } else {
+                similarity(&words, &label(node))
+            };
+            if by_selector >= by_text {
+                (by_selector, index, node, SuggestionReason::SimilarSelector)
+            } else {
+                (by_text, index, node, SuggestionReason::SimilarText)
+            }
+        })
+        .collect();
+    best(scored, tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(elements: &[(&str, &str, &str)]) -> DomTree {
+        let mut root = AriaNode::fragment();
+        let mut selectors = Vec::new();
+        for (index, (role, name, selector)) in elements.iter().enumerate() {
+            root = root.with_child(AriaChild::Node(Box::new(
+                AriaNode::new(*role, *name).with_index(index),
+            )));
+            selectors.push(selector.to_string());
+        }
+        let mut tree = DomTree::new(root);
+        tree.selectors = selectors;
+        tree
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("Submit", "submit"), 1.0);
+        assert_eq!(similarity("abc", "xyz"), 0.0);
+        assert!(similarity("#submit-btn", "#submit-button") > 0.6);
+        assert_eq!(similarity("a", "A"), 1.0);
+    }
+
+    #[test]
+    fn test_suggest_for_index_same_element() {
+        let previous = tree(&[("link", "Home", "#home"), ("button", "Add to cart", "#add")]);
+        let current = tree(&[("button", "Add to cart", "#add")]);
+
+        let suggestions = suggest_for_index(&current, Some(&previous), 1);
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].index, 0);
+        assert_eq!(suggestions[0].selector, "#add");
+        assert_eq!(suggestions[0].reason, SuggestionReason::SameElement);
+    }
+
+    #[test]
+    fn test_suggest_for_index_nearby() {
+        let current = tree(&[
+            ("link", "Home", "#home"),
+            ("link", "About", "#about"),
+            ("button", "Search", "#search"),
+            ("textbox", "Query", "#q"),
+        ]);
+
+        let suggestions = suggest_for_index(&current, None, 9);
+        let indices: Vec<usize> = suggestions.iter().map(|s| s.index).collect();
+        assert_eq!(indices, vec![3, 2, 1]);
+        assert!(
+            suggestions
+                .iter()
+                .all(|s| s.reason == SuggestionReason::NearbyIndex)
+        );
+    }
+
+    #[test]
+    fn test_suggest_for_selector() {
+        let current = tree(&[
+            ("link", "Home", "#home"),
+            ("button", "Submit order", "#submit-order"),
+            ("button", "Cancel", "form > button:nth-child(2)"),
+        ]);
+
+        let suggestions = suggest_for_selector(&current, "#submit-btn");
+        assert_eq!(suggestions[0].index, 1);
+        assert_eq!(suggestions[0].reason, SuggestionReason::SimilarSelector);
+
+        let suggestions = suggest_for_selector(&current, "button[aria-label='Cancel']");
+        assert_eq!(suggestions[0].index, 2);
+
+        assert!(suggest_for_selector(&current, "#zzz").is_empty());
+    }
+}
@@ -1,20 +1,122 @@
 use crate::dom::element::{AriaChild, AriaNode};
 use crate::error::{BrowserError, Result};
 use headless_chrome::Tab;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 /// Represents the ARIA snapshot of a web page
 /// Based on Playwright's AriaSnapshot structure
-#[derive(Debug, Clone)]
+///
+/// Serializes with serde to any self-describing format (e.g. JSON); see
+/// `dom::wire` (`binary` feature) for a compact binary encoding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DomTree {
     /// Root AriaNode (usually a fragment)
     pub root: AriaNode,
 
     /// Array of CSS selectors indexed by element index
+    #[serde(default)]
     pub selectors: Vec<String>,
 
     /// List of iframe indices (for multi-frame snapshots)
+    #[serde(default)]
     pub iframe_indices: Vec<usize>,
+
+    /// Scroll position and size of the page when the tree was extracted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<PageMetrics>,
+}
+
+/// How interactive elements are numbered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexOrder {
+    /// Document order (default)
+    #[default]
+    Dom,
+    /// Reading order of the rendered page: top to bottom and left to right,
+    /// finishing one column before the next
+    Visual,
+}
+
+/// Scroll position and dimensions of a page, in CSS pixels
+///
+/// Tells whether there is more page to explore than the snapshot's viewport
+/// shows; snapshots list every element, but the off-screen counts say how
+/// many of them need scrolling to reach.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageMetrics {
+    /// Horizontal scroll offset
+    pub scroll_x: f64,
+    /// Vertical scroll offset
+    pub scroll_y: f64,
+    /// Width of the viewport
+    pub viewport_width: f64,
+    /// Height of the viewport
+    pub viewport_height: f64,
+    /// Total scrollable width of the page
+    pub page_width: f64,
+    /// Total scrollable height of the page
+    pub page_height: f64,
+    /// Indexed elements entirely above the viewport
+    #[serde(default)]
+    pub offscreen_above: usize,
+    /// Indexed elements entirely below the viewport
+    #[serde(default)]
+    pub offscreen_below: usize,
+    /// Indexed elements left or right of the viewport
+    #[serde(default)]
+    pub offscreen_beside: usize,
+}
+
+impl PageMetrics {
+    /// Indexed elements outside the viewport
+    pub fn offscreen(&self) -> usize {
+        self.offscreen_above + self.offscreen_below + self.offscreen_beside
+    }
+
+    /// Pixels of page below the bottom of the viewport
+    pub fn remaining_below(&self) -> f64 {
+        (self.page_height - self.scroll_y - self.viewport_height).max(0.0)
+    }
+
+    /// One-line description, e.g. "viewport shows 0-800 of 3200px; 2400px
+    /// below; 12 of 40 elements off-screen (12 below)"
+    pub fn summary(&self, interactive: usize) -> String {
+        let mut summary = format!(
+            "viewport shows {:.0}-{:.0} of {:.0}px",
+            self.scroll_y,
+            self.scroll_y + self.viewport_height,
+            self.page_height
+        );
+        let below = self.remaining_below();
+        summary.push_str(&if below > 0.0 {
+            format!("; {:.0}px below", below)
+        } else {
+            "; at the bottom".to_string()
+        });
+
+        let mut sides = Vec::new();
+        for (count, side) in [
+            (self.offscreen_above, "above"),
+            (self.offscreen_below, "below"),
+            (self.offscreen_beside, "beside"),
+        ] {
+            if count > 0 {
+                sides.push(format!("{} {}", count, side));
+            }
+        }
+        summary.push_str(&format!(
+            "; {} of {} elements off-screen",
+            self.offscreen(),
+            interactive
+        ));
+        if !sides.is_empty() {
+            summary.push_str(&format!(" ({})", sides.join(", ")));
+        }
+        summary
+    }
 }
 
 /// Snapshot extraction response from JavaScript
@@ -24,6 +126,8 @@ struct SnapshotResponse {
     selectors: Vec<String>,
     #[serde(rename = "iframeIndices")]
     iframe_indices: Vec<usize>,
+    #[serde(default)]
+    page: Option<PageMetrics>,
 }
 
 impl DomTree {
@@ -33,6 +137,7 @@ impl DomTree {
             root,
             selectors: Vec::new(),
             iframe_indices: Vec::new(),
+            page: None,
         };
         tree.rebuild_maps();
         tree
@@ -40,17 +145,28 @@ impl DomTree {
 
     /// Build DOM tree from a browser tab
     pub fn from_tab(tab: &Arc<Tab>) -> Result<Self> {
-        Self::from_tab_with_prefix(tab, "")
+        Self::from_tab_with_order(tab, IndexOrder::Dom)
+    }
+
+    /// Build DOM tree from a browser tab, numbering elements in the given order
+    pub fn from_tab_with_order(tab: &Arc<Tab>, order: IndexOrder) -> Result<Self> {
+        let config = serde_json::json!({ "indexOrder": order });
+        Self::extract(tab, &config)
     }
 
     /// Build DOM tree from a browser tab with a ref prefix (for iframe handling)
     pub fn from_tab_with_prefix(tab: &Arc<Tab>, _ref_prefix: &str) -> Result<Self> {
         // Note: ref_prefix is deprecated but kept for API compatibility
+        Self::from_tab(tab)
+    }
+
+    fn extract(tab: &Arc<Tab>, config: &serde_json::Value) -> Result<Self> {
         // JavaScript code to extract ARIA snapshot
-        let js_code = include_str!("extract_dom.js");
+        let js_code =
+            include_str!("extract_dom.js").replace("__EXTRACT_DOM_CONFIG__", &config.to_string());
 
         // Execute JavaScript to extract DOM
-        let result = tab.evaluate(js_code, false).map_err(|e| {
+        let result = tab.evaluate(&js_code, false).map_err(|e| {
             BrowserError::DomParseFailed(format!("Failed to execute DOM extraction script: {}", e))
         })?;
 
@@ -73,6 +189,7 @@ impl DomTree {
             root: response.root,
             selectors: response.selectors,
             iframe_indices: response.iframe_indices,
+            page: response.page,
         })
     }
 
@@ -86,10 +203,10 @@ impl DomTree {
         let max_index = self.find_max_index(&self.root.clone());
 
         // Resize selectors array if needed
-        if let Some(max_idx) = max_index {
-            if self.selectors.len() <= max_idx {
-                self.selectors.resize(max_idx + 1, String::new());
-            }
+        if let Some(max_idx) = max_index
+            && self.selectors.len() <= max_idx
+        {
+            self.selectors.resize(max_idx + 1, String::new());
         }
 
         // Collect iframe indices
@@ -101,13 +218,13 @@ impl DomTree {
         let mut max = node.index;
 
         for child in &node.children {
-            if let AriaChild::Node(child_node) = child {
-                if let Some(child_max) = self.find_max_index(child_node) {
-                    max = match max {
-                        Some(current) => Some(current.max(child_max)),
-                        None => Some(child_max),
-                    };
-                }
+            if let AriaChild::Node(child_node) = child
+                && let Some(child_max) = self.find_max_index(child_node)
+            {
+                max = match max {
+                    Some(current) => Some(current.max(child_max)),
+                    None => Some(child_max),
+                };
             }
         }
 
@@ -115,10 +232,10 @@ impl DomTree {
     }
 
     fn collect_iframe_indices(&mut self, node: &AriaNode) {
-        if let Some(index) = node.index {
-            if node.role == "iframe" {
-                self.iframe_indices.push(index);
-            }
+        if let Some(index) = node.index
+            && node.role == "iframe"
+        {
+            self.iframe_indices.push(index);
         }
 
         for child in &node.children {
@@ -284,6 +401,32 @@ mod tests {
         assert!(indices.contains(&1));
     }
 
+    #[test]
+    fn test_page_metrics_summary() {
+        let metrics = PageMetrics {
+            scroll_y: 800.0,
+            viewport_width: 1280.0,
+            viewport_height: 800.0,
+            page_width: 1280.0,
+            page_height: 4000.0,
+            offscreen_above: 3,
+            offscreen_below: 20,
+            ..PageMetrics::default()
+        };
+        assert_eq!(metrics.offscreen(), 23);
+        assert_eq!(metrics.remaining_below(), 2400.0);
+        assert_eq!(
+            metrics.summary(40),
+            "viewport shows 800-1600 of 4000px; 2400px below; 23 of 40 elements off-screen (3 above, 20 below)"
+        );
+
+        let bottom = PageMetrics {
+            scroll_y: 3200.0,
+            ..metrics
+        };
+        assert!(bottom.summary(40).contains("at the bottom"));
+    }
+
     #[test]
     fn test_inject_iframe_content() {
         let mut main_tree = AriaNode::fragment();
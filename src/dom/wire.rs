@@ -0,0 +1,270 @@
+//! Binary encoding of DOM snapshots for cross-process pipelines
+//!
+//! Lets one process extract a [`DomTree`] and another target its elements,
+//! e.g. a pool of extraction workers feeding sharded agents. The encoding is
+//! bincode behind a 4-byte magic and a format version:
+//!
+//! ```text
+//! "BUDT" | version: u16 (little endian) | bincode(tree)
+//! ```
+//!
+//! ## Schema evolution
+//!
+//! bincode is not self-describing: fields are read by position, so adding,
+//! removing or reordering a field of the wire structs below changes the
+//! format. Any such change must bump [`FORMAT_VERSION`] and keep decoding the
+//! previous version (convert it into the new structs) for at least one
+//! release, so workers on different versions can be upgraded one at a time.
+//! Decoding an unknown, newer version fails with a clear error rather than
+//! misreading the payload. The wire structs are separate from [`AriaNode`] so
+//! the in-memory types (and their JSON form) can change freely.
+
+use crate::dom::DomTree;
+use crate::dom::element::{AriaChecked, AriaChild, AriaNode, AriaPressed, BoxInfo};
+use crate::error::{BrowserError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Leading bytes of every encoded tree
+const MAGIC: &[u8; 4] = b"BUDT";
+
+/// Version of the wire structs written by [`DomTree::to_bytes`]
+pub const FORMAT_VERSION: u16 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct WireTree {
+    root: WireNode,
+    selectors: Vec<String>,
+    iframe_indices: Vec<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireNode {
+    role: String,
+    name: String,
+    index: Option<usize>,
+    children: Vec<WireChild>,
+    /// Sorted by key so equal trees encode to equal bytes
+    props: Vec<(String, String)>,
+    visible: bool,
+    cursor: Option<String>,
+    checked: Option<WireState>,
+    disabled: Option<bool>,
+    expanded: Option<bool>,
+    level: Option<u32>,
+    pressed: Option<WireState>,
+    selected: Option<bool>,
+    active: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum WireChild {
+    Text(String),
+    Node(WireNode),
+}
+
+/// Checked or pressed state
+#[derive(Serialize, Deserialize)]
+enum WireState {
+    False,
+    True,
+    Mixed,
+}
+
+impl From<&AriaChecked> for WireState {
+    fn from(state: &AriaChecked) -> Self {
+        match state {
+            AriaChecked::Bool(false) => Self::False,
+            AriaChecked::Bool(true) => Self::True,
+            AriaChecked::Mixed(_) => Self::Mixed,
+        }
+    }
+}
+
+impl From<&AriaPressed> for WireState {
+    fn from(state: &AriaPressed) -> Self {
+        match state {
+            AriaPressed::Bool(false) => Self::False,
+            AriaPressed::Bool(true) => Self::True,
+            AriaPressed::Mixed(_) => Self::Mixed,
+        }
+    }
+}
+
+impl From<WireState> for AriaChecked {
+    fn from(state: WireState) -> Self {
+        match state {
+            WireState::False => Self::Bool(false),
+            WireState::True => Self::Bool(true),
+            WireState::Mixed => Self::Mixed("mixed".to_string()),
+        }
+    }
+}
+
+impl From<WireState> for AriaPressed {
+    fn from(state: WireState) -> Self {
+        match state {
+            WireState::False => Self::Bool(false),
+            WireState::True => Self::Bool(true),
+            WireState::Mixed => Self::Mixed("mixed".to_string()),
+        }
+    }
+}
+
+impl From<&AriaNode> for WireNode {
+    fn from(node: &AriaNode) -> Self {
+        let mut props: Vec<(String, String)> = node
+            .props
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        props.sort();
+
+        Self {
+            role: node.role.clone(),
+            name: node.name.clone(),
+            index: node.index,
+            children: node
+                .children
+                .iter()
+                .map(|child| match child {
+                    AriaChild::Text(text) => WireChild::Text(text.clone()),
+                    AriaChild::Node(node) => WireChild::Node(node.as_ref().into()),
+                })
+                .collect(),
+            props,
+            visible: node.box_info.visible,
+            cursor: node.box_info.cursor.clone(),
+            checked: node.checked.as_ref().map(WireState::from),
+            disabled: node.disabled,
+            expanded: node.expanded,
+            level: node.level,
+            pressed: node.pressed.as_ref().map(WireState::from),
+            selected: node.selected,
+            active: node.active,
+        }
+    }
+}
+
+impl From<WireNode> for AriaNode {
+    fn from(node: WireNode) -> Self {
+        Self {
+            role: node.role,
+            name: node.name,
+            index: node.index,
+            children: node
+                .children
+                .into_iter()
+                .map(|child| match child {
+                    WireChild::Text(text) => AriaChild::Text(text),
+                    WireChild::Node(node) => AriaChild::Node(Box::new(node.into())),
+                })
+                .collect(),
+            props: node.props.into_iter().collect(),
+            box_info: BoxInfo {
+                visible: node.visible,
+                cursor: node.cursor,
+            },
+            checked: node.checked.map(AriaChecked::from),
+            disabled: node.disabled,
+            expanded: node.expanded,
+            level: node.level,
+            pressed: node.pressed.map(AriaPressed::from),
+            selected: node.selected,
+            active: node.active,
+        }
+    }
+}
+
+impl DomTree {
+    /// Encode the tree, selectors included, for another process
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let tree = WireTree {
+            root: (&self.root).into(),
+            selectors: self.selectors.clone(),
+            iframe_indices: self.iframe_indices.clone(),
+        };
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut bytes, &tree).map_err(|e| {
+            BrowserError::DomParseFailed(format!("Failed to encode DOM tree: {}", e))
+        })?;
+        Ok(bytes)
+    }
+
+    /// Decode a tree encoded with [`DomTree::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let payload = bytes
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or_else(|| BrowserError::DomParseFailed("Not an encoded DOM tree".to_string()))?;
+        let (version, payload) = payload.split_at_checked(2).ok_or_else(|| {
+            BrowserError::DomParseFailed("Encoded DOM tree is truncated".to_string())
+        })?;
+        let version = u16::from_le_bytes([version[0], version[1]]);
+        if version != FORMAT_VERSION {
+            return Err(BrowserError::DomParseFailed(format!(
+                "Unsupported DOM tree format version {} (this build reads version {})",
+                version, FORMAT_VERSION
+            )));
+        }
+
+        let tree: WireTree = bincode::deserialize(payload).map_err(|e| {
+            BrowserError::DomParseFailed(format!("Failed to decode DOM tree: {}", e))
+        })?;
+        Ok(Self {
+            root: tree.root.into(),
+            selectors: tree.selectors,
+            iframe_indices: tree.iframe_indices,
+            page: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_tree() -> DomTree {
+        let root = AriaNode::fragment()
+            .with_child(AriaChild::Node(Box::new(
+                AriaNode::new("checkbox", "Remember me")
+                    .with_index(0)
+                    .with_checked(true)
+                    .with_prop("data-id", "remember")
+                    .with_box(true, Some("pointer".to_string())),
+            )))
+            .with_child(AriaChild::Text("Terms apply".to_string()))
+            .with_child(AriaChild::Node(Box::new(
+                AriaNode::new("heading", "Sign in").with_level(2),
+            )));
+        let mut tree = DomTree::new(root);
+        tree.selectors = vec!["#remember".to_string()];
+        tree.iframe_indices = vec![];
+        tree
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let tree = sample_tree();
+        let bytes = tree.to_bytes().unwrap();
+        assert!(bytes.starts_with(b"BUDT"));
+
+        let decoded = DomTree::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.root, tree.root);
+        assert_eq!(decoded.selectors, tree.selectors);
+        assert_eq!(
+            decoded.get_selector(0).map(String::as_str),
+            Some("#remember")
+        );
+    }
+
+    #[test]
+    fn test_rejects_foreign_data() {
+        let mut bytes = sample_tree().to_bytes().unwrap();
+        assert!(DomTree::from_bytes(b"{}").is_err());
+        assert!(DomTree::from_bytes(&bytes[..5]).is_err());
+
+        bytes[4] = 99;
+        let error = DomTree::from_bytes(&bytes).unwrap_err();
+        assert!(error.to_string().contains("version 99"));
+    }
+}
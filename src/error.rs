@@ -1,20 +1,63 @@
+use crate::dom::ElementSuggestion;
+use serde::Serialize;
+use std::fmt;
 use thiserror::Error;
 
 /// Core error type for browser-use operations
+///
+/// Every error has a stable machine-readable [`ErrorCode`] and says whether
+/// retrying may help, so callers can branch on the kind of failure instead of
+/// parsing messages. Where an error happened (tool, selector, URL) is attached
+/// with [`BrowserError::with_tool`] and friends.
 #[derive(Error, Debug)]
 pub enum BrowserError {
+    // ---- Browser process ----
     /// Browser launch failed
     #[error("Failed to launch browser: {0}")]
     LaunchFailed(String),
 
+    /// The browser executable is missing or cannot be run
+    #[error("Browser not found: {0}")]
+    BrowserNotFound(String),
+
     /// Browser connection failed
     #[error("Failed to connect to browser: {0}")]
     ConnectionFailed(String),
 
+    /// The connection to a running browser was lost
+    #[error("Browser disconnected: {0}")]
+    BrowserDisconnected(String),
+
+    /// Chrome/CDP error from headless_chrome crate
+    #[error("Chrome error: {0}")]
+    ChromeError(String),
+
     /// Operation timed out
     #[error("Operation timed out: {0}")]
     Timeout(String),
 
+    // ---- Navigation ----
+    /// Navigation failed
+    #[error("Navigation failed: {0}")]
+    NavigationFailed(String),
+
+    /// Page did not finish loading in time
+    #[error("Navigation timed out: {0}")]
+    NavigationTimeout(String),
+
+    /// Navigation refused because robots.txt disallows the URL
+    #[error("Disallowed by robots.txt for user agent '{user_agent}': {url}")]
+    RobotsDisallowed { url: String, user_agent: String },
+
+    /// Tab operation failed
+    #[error("Tab operation failed: {0}")]
+    TabOperationFailed(String),
+
+    /// The tab's renderer crashed or ran out of memory
+    #[error("Page crashed: {0}")]
+    PageCrashed(String),
+
+    // ---- Page content ----
     /// Invalid CSS selector
     #[error("Invalid selector: {0}")]
     SelectorInvalid(String),
@@ -27,6 +70,11 @@ pub enum BrowserError {
     #[error("Failed to parse DOM: {0}")]
     DomParseFailed(String),
 
+    /// JavaScript evaluation failed
+    #[error("JavaScript evaluation failed: {0}")]
+    EvaluationFailed(String),
+
+    // ---- Tools and arguments ----
     /// Tool execution failed
     #[error("Tool '{tool}' execution failed: {reason}")]
     ToolExecutionFailed { tool: String, reason: String },
@@ -35,29 +83,39 @@ pub enum BrowserError {
     #[error("Invalid argument: {0}")]
     InvalidArgument(String),
 
-    /// Navigation failed
-    #[error("Navigation failed: {0}")]
-    NavigationFailed(String),
+    // ---- Policy ----
+    /// A file path supplied to a tool is outside the allowed output directory
+    #[error("Path not allowed: {0}")]
+    PathNotAllowed(String),
 
-    /// JavaScript evaluation failed
-    #[error("JavaScript evaluation failed: {0}")]
-    EvaluationFailed(String),
+    /// Action refused by a server-configured policy
+    #[error("Blocked by policy: {0}")]
+    PolicyDenied(String),
 
+    // ---- Output ----
     /// Screenshot capture failed
     #[error("Screenshot failed: {0}")]
     ScreenshotFailed(String),
 
+    /// Decoding, encoding or comparing an image failed
+    #[error("Image processing failed: {0}")]
+    ImageFailed(String),
+
+    /// Text recognition failed
+    #[error("OCR failed: {0}")]
+    OcrFailed(String),
+
     /// Download operation failed
     #[error("Download failed: {0}")]
     DownloadFailed(String),
 
-    /// Tab operation failed
-    #[error("Tab operation failed: {0}")]
-    TabOperationFailed(String),
+    /// A downloaded document (PDF, CSV, ...) could not be parsed
+    #[error("Document parsing failed: {0}")]
+    DocumentFailed(String),
 
-    /// Chrome/CDP error from headless_chrome crate
-    #[error("Chrome error: {0}")]
-    ChromeError(String),
+    /// Encrypting or decrypting persisted state failed
+    #[error("Encryption failed: {0}")]
+    EncryptionFailed(String),
 
     /// JSON serialization/deserialization error
     #[error("JSON error: {0}")]
@@ -66,15 +124,236 @@ pub enum BrowserError {
     /// IO error
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
+
+    // ---- Context ----
+    /// Another error with details on where it happened; code and message are the inner error's
+    #[error("{error}")]
+    WithContext {
+        error: Box<BrowserError>,
+        context: ErrorContext,
+    },
+}
+
+/// Stable machine-readable error codes, serialized as `SCREAMING_SNAKE_CASE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    LaunchFailed,
+    BrowserNotFound,
+    ConnectionFailed,
+    BrowserDisconnected,
+    CdpError,
+    Timeout,
+    NavFailed,
+    NavTimeout,
+    RobotsDisallowed,
+    TabFailed,
+    PageCrashed,
+    InvalidSelector,
+    ElementNotFound,
+    DomParseFailed,
+    EvalFailed,
+    ToolFailed,
+    InvalidArgument,
+    PathNotAllowed,
+    PolicyDenied,
+    ScreenshotFailed,
+    ImageFailed,
+    OcrFailed,
+    DownloadFailed,
+    DocumentFailed,
+    EncryptionFailed,
+    JsonError,
+    IoError,
+}
+
+impl ErrorCode {
+    /// The code as clients see it, e.g. `"ELEMENT_NOT_FOUND"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::LaunchFailed => "LAUNCH_FAILED",
+            ErrorCode::BrowserNotFound => "BROWSER_NOT_FOUND",
+            ErrorCode::ConnectionFailed => "CONNECTION_FAILED",
+            ErrorCode::BrowserDisconnected => "BROWSER_DISCONNECTED",
+            ErrorCode::CdpError => "CDP_ERROR",
+            ErrorCode::Timeout => "TIMEOUT",
+            ErrorCode::NavFailed => "NAV_FAILED",
+            ErrorCode::NavTimeout => "NAV_TIMEOUT",
+            ErrorCode::RobotsDisallowed => "ROBOTS_DISALLOWED",
+            ErrorCode::TabFailed => "TAB_FAILED",
+            ErrorCode::PageCrashed => "PAGE_CRASHED",
+            ErrorCode::InvalidSelector => "INVALID_SELECTOR",
+            ErrorCode::ElementNotFound => "ELEMENT_NOT_FOUND",
+            ErrorCode::DomParseFailed => "DOM_PARSE_FAILED",
+            ErrorCode::EvalFailed => "EVAL_FAILED",
+            ErrorCode::ToolFailed => "TOOL_FAILED",
+            ErrorCode::InvalidArgument => "INVALID_ARGUMENT",
+            ErrorCode::PathNotAllowed => "PATH_NOT_ALLOWED",
+            ErrorCode::PolicyDenied => "POLICY_DENIED",
+            ErrorCode::ScreenshotFailed => "SCREENSHOT_FAILED",
+            ErrorCode::ImageFailed => "IMAGE_FAILED",
+            ErrorCode::OcrFailed => "OCR_FAILED",
+            ErrorCode::DownloadFailed => "DOWNLOAD_FAILED",
+            ErrorCode::DocumentFailed => "DOCUMENT_FAILED",
+            ErrorCode::EncryptionFailed => "ENCRYPTION_FAILED",
+            ErrorCode::JsonError => "JSON_ERROR",
+            ErrorCode::IoError => "IO_ERROR",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where an error happened
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorContext {
+    /// Tool that was running
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// Selector that was being resolved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    /// URL that was being loaded or acted on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Elements the caller may have meant, for references that matched nothing
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<ElementSuggestion>,
+}
+
+impl BrowserError {
+    /// Stable code for the kind of error
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            BrowserError::LaunchFailed(_) => ErrorCode::LaunchFailed,
+            BrowserError::BrowserNotFound(_) => ErrorCode::BrowserNotFound,
+            BrowserError::ConnectionFailed(_) => ErrorCode::ConnectionFailed,
+            BrowserError::BrowserDisconnected(_) => ErrorCode::BrowserDisconnected,
+            BrowserError::ChromeError(_) => ErrorCode::CdpError,
+            BrowserError::Timeout(_) => ErrorCode::Timeout,
+            BrowserError::NavigationFailed(_) => ErrorCode::NavFailed,
+            BrowserError::NavigationTimeout(_) => ErrorCode::NavTimeout,
+            BrowserError::RobotsDisallowed { .. } => ErrorCode::RobotsDisallowed,
+            BrowserError::TabOperationFailed(_) => ErrorCode::TabFailed,
+            BrowserError::PageCrashed(_) => ErrorCode::PageCrashed,
+            BrowserError::SelectorInvalid(_) => ErrorCode::InvalidSelector,
+            BrowserError::ElementNotFound(_) => ErrorCode::ElementNotFound,
+            BrowserError::DomParseFailed(_) => ErrorCode::DomParseFailed,
+            BrowserError::EvaluationFailed(_) => ErrorCode::EvalFailed,
+            BrowserError::ToolExecutionFailed { .. } => ErrorCode::ToolFailed,
+            BrowserError::InvalidArgument(_) => ErrorCode::InvalidArgument,
+            BrowserError::PathNotAllowed(_) => ErrorCode::PathNotAllowed,
+            BrowserError::PolicyDenied(_) => ErrorCode::PolicyDenied,
+            BrowserError::ScreenshotFailed(_) => ErrorCode::ScreenshotFailed,
+            BrowserError::ImageFailed(_) => ErrorCode::ImageFailed,
+            BrowserError::OcrFailed(_) => ErrorCode::OcrFailed,
+            BrowserError::DownloadFailed(_) => ErrorCode::DownloadFailed,
+            BrowserError::DocumentFailed(_) => ErrorCode::DocumentFailed,
+            BrowserError::EncryptionFailed(_) => ErrorCode::EncryptionFailed,
+            BrowserError::JsonError(_) => ErrorCode::JsonError,
+            BrowserError::IoError(_) => ErrorCode::IoError,
+            BrowserError::WithContext { error, .. } => error.code(),
+        }
+    }
+
+    /// Whether the same call may succeed if retried, possibly after a wait or
+    /// a browser restart
+    ///
+    /// Timeouts, lost connections, crashed pages and elements that have not
+    /// appeared yet are retryable; invalid input, policy refusals and failures
+    /// that depend on the page's content are not.
+    pub fn retryable(&self) -> bool {
+        matches!(
+            self.code(),
+            ErrorCode::ConnectionFailed
+                | ErrorCode::BrowserDisconnected
+                | ErrorCode::Timeout
+                | ErrorCode::NavTimeout
+                | ErrorCode::PageCrashed
+                | ErrorCode::ElementNotFound
+        )
+    }
+
+    /// Details on where the error happened, if any were attached
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            BrowserError::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The error without its context
+    pub fn inner(&self) -> &BrowserError {
+        match self {
+            BrowserError::WithContext { error, .. } => error.inner(),
+            error => error,
+        }
+    }
+
+    fn update_context(self, update: impl FnOnce(&mut ErrorContext)) -> Self {
+        let (error, mut context) = match self {
+            BrowserError::WithContext { error, context } => (error, context),
+            error => (Box::new(error), ErrorContext::default()),
+        };
+        update(&mut context);
+        BrowserError::WithContext { error, context }
+    }
+
+    /// Record the tool that failed, unless one is already recorded
+    pub fn with_tool(self, tool: impl Into<String>) -> Self {
+        self.update_context(|context| {
+            context.tool.get_or_insert_with(|| tool.into());
+        })
+    }
+
+    /// Record the selector being resolved, unless one is already recorded
+    pub fn with_selector(self, selector: impl Into<String>) -> Self {
+        self.update_context(|context| {
+            context.selector.get_or_insert_with(|| selector.into());
+        })
+    }
+
+    /// Record elements the caller may have meant, unless some are already recorded
+    pub fn with_suggestions(self, suggestions: Vec<ElementSuggestion>) -> Self {
+        if suggestions.is_empty() {
+            return self;
+        }
+        self.update_context(|context| {
+            if context.suggestions.is_empty() {
+                context.suggestions = suggestions;
+            }
+        })
+    }
+
+    /// Record the URL being loaded, unless one is already recorded
+    pub fn with_url(self, url: impl Into<String>) -> Self {
+        self.update_context(|context| {
+            context.url.get_or_insert_with(|| url.into());
+        })
+    }
 }
 
 /// Result type alias for browser-use operations
 pub type Result<T> = std::result::Result<T, BrowserError>;
 
 /// Convert anyhow::Error from headless_chrome to BrowserError
+///
+/// Lost connections and timed-out waits get their own variants so they are
+/// reported as retryable.
 impl From<anyhow::Error> for BrowserError {
     fn from(err: anyhow::Error) -> Self {
-        BrowserError::ChromeError(err.to_string())
+        if err.is::<headless_chrome::browser::ConnectionClosed>() {
+            BrowserError::BrowserDisconnected(err.to_string())
+        } else if err.is::<headless_chrome::util::Timeout>() {
+            BrowserError::Timeout(err.to_string())
+        } else {
+            BrowserError::ChromeError(err.to_string())
+        }
     }
 }
 
@@ -103,6 +382,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_codes() {
+        let err = BrowserError::ElementNotFound("#submit".to_string());
+        assert_eq!(err.code(), ErrorCode::ElementNotFound);
+        assert_eq!(err.code().as_str(), "ELEMENT_NOT_FOUND");
+        assert!(err.retryable());
+
+        let err = BrowserError::NavigationTimeout("20s".to_string());
+        assert_eq!(err.code().to_string(), "NAV_TIMEOUT");
+        assert!(err.retryable());
+
+        let err = BrowserError::PageCrashed("tab 1".to_string());
+        assert_eq!(err.code().as_str(), "PAGE_CRASHED");
+        assert!(err.retryable());
+
+        let err = BrowserError::PolicyDenied("file://".to_string());
+        assert_eq!(
+            serde_json::to_value(err.code()).unwrap(),
+            serde_json::json!("POLICY_DENIED")
+        );
+        assert!(!err.retryable());
+    }
+
+    #[test]
+    fn test_error_context() {
+        let err = BrowserError::ElementNotFound("Element '#buy' not found".to_string())
+            .with_selector("#buy")
+            .with_tool("click")
+            .with_tool("outer");
+
+        assert_eq!(
+            err.to_string(),
+            "Element not found: Element '#buy' not found"
+        );
+        assert_eq!(err.code(), ErrorCode::ElementNotFound);
+        assert!(matches!(err.inner(), BrowserError::ElementNotFound(_)));
+        assert_eq!(
+            err.context(),
+            Some(&ErrorContext {
+                tool: Some("click".to_string()),
+                selector: Some("#buy".to_string()),
+                ..Default::default()
+            })
+        );
+        assert!(BrowserError::Timeout("x".to_string()).context().is_none());
+    }
+
+    #[test]
+    fn test_anyhow_conversion() {
+        let err: BrowserError =
+            anyhow::Error::from(headless_chrome::browser::ConnectionClosed {}).into();
+        assert_eq!(err.code(), ErrorCode::BrowserDisconnected);
+
+        let err: BrowserError = anyhow::anyhow!("Protocol error").into();
+        assert_eq!(err.code(), ErrorCode::CdpError);
+        assert!(!err.retryable());
+    }
+
     #[test]
     fn test_json_error_conversion() {
         let json_err = serde_json::from_str::<serde_json::Value>("invalid json");
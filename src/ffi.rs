@@ -0,0 +1,420 @@
+//! C ABI for embedding from Go, C#, and other runtimes
+//!
+//! Sessions are opaque handles; tool calls take and return JSON strings.
+//! Every function returns a [`BuStatus`]; on failure, [`bu_last_error`]
+//! describes the error of the calling thread as JSON:
+//!
+//! ```json
+//! {"status": "BROWSER_ERROR", "code": "ELEMENT_NOT_FOUND", "message": "...", "retryable": true}
+//! ```
+//!
+//! Strings returned through out-parameters are owned by the caller and must
+//! be released with [`bu_string_free`]; sessions with [`bu_session_free`].
+//! The header is `include/browser_use.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/browser_use.h`.
+//! Build the library with `cargo rustc --release --lib --features ffi --crate-type cdylib`
+//! (or `staticlib`).
+
+use crate::browser::{BrowserSession, ConnectionOptions, LaunchOptions};
+use crate::error::BrowserError;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::PathBuf;
+use std::ptr;
+
+/// Result of an FFI call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullArgument = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// A JSON argument could not be parsed
+    InvalidJson = 3,
+    /// The browser or tool failed; see the error's `code`
+    BrowserError = 4,
+    /// The library panicked; the session should be freed
+    Panic = 5,
+}
+
+impl BuStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::NullArgument => "NULL_ARGUMENT",
+            Self::InvalidUtf8 => "INVALID_UTF8",
+            Self::InvalidJson => "INVALID_JSON",
+            Self::BrowserError => "BROWSER_ERROR",
+            Self::Panic => "PANIC",
+        }
+    }
+}
+
+/// Opaque browser session handle
+pub struct BuSession {
+    session: BrowserSession,
+}
+
+/// Launch options accepted by [`bu_session_launch`]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FfiLaunchOptions {
+    headless: Option<bool>,
+    executable_path: Option<String>,
+    user_data_dir: Option<String>,
+    window_width: Option<u32>,
+    window_height: Option<u32>,
+    ignore_https_errors: Option<bool>,
+    output_dir: Option<String>,
+}
+
+impl From<FfiLaunchOptions> for LaunchOptions {
+    fn from(options: FfiLaunchOptions) -> Self {
+        let defaults = LaunchOptions::default();
+        LaunchOptions {
+            headless: options.headless.unwrap_or(defaults.headless),
+            chrome_path: options.executable_path.map(PathBuf::from),
+            user_data_dir: options.user_data_dir.map(PathBuf::from),
+            window_width: options.window_width.unwrap_or(defaults.window_width),
+            window_height: options.window_height.unwrap_or(defaults.window_height),
+            ignore_https_errors: options
+                .ignore_https_errors
+                .unwrap_or(defaults.ignore_https_errors),
+            output_dir: options.output_dir.map(PathBuf::from),
+            ..defaults
+        }
+    }
+}
+
+/// A failed call: its status and the error JSON for [`bu_last_error`]
+struct FfiError {
+    status: BuStatus,
+    detail: Value,
+}
+
+impl FfiError {
+    fn new(status: BuStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            detail: json!({ "status": status.as_str(), "message": message.into() }),
+        }
+    }
+}
+
+impl From<BrowserError> for FfiError {
+    fn from(error: BrowserError) -> Self {
+        let status = BuStatus::BrowserError;
+        Self {
+            status,
+            detail: json!({
+                "status": status.as_str(),
+                "code": error.code(),
+                "message": error.to_string(),
+                "retryable": error.retryable(),
+            }),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(detail: Option<&Value>) {
+    let message = detail.and_then(|detail| CString::new(detail.to_string()).ok());
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+/// Run an entry point, recording its error and catching panics
+fn guard(f: impl FnOnce() -> Result<(), FfiError>) -> BuStatus {
+    let result = catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(FfiError::new(BuStatus::Panic, message))
+    });
+    match result {
+        Ok(()) => {
+            set_last_error(None);
+            BuStatus::Ok
+        }
+        Err(error) => {
+            set_last_error(Some(&error.detail));
+            error.status
+        }
+    }
+}
+
+/// Borrow a C string argument
+///
+/// # Safety
+/// `ptr` must be null or point to a NUL-terminated string valid for `'a`.
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::new(
+            BuStatus::NullArgument,
+            format!("{} is null", name),
+        ));
+    }
+    // SAFETY: non-null and NUL-terminated per the caller's contract
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| FfiError::new(BuStatus::InvalidUtf8, format!("{} is not UTF-8", name)))
+}
+
+/// Parse an optional JSON argument; null means `default`
+///
+/// # Safety
+/// Same as [`str_arg`].
+unsafe fn json_arg<T: for<'de> Deserialize<'de>>(
+    ptr: *const c_char,
+    name: &str,
+    default: T,
+) -> Result<T, FfiError> {
+    if ptr.is_null() {
+        return Ok(default);
+    }
+    // SAFETY: forwarded caller contract
+    let text = unsafe { str_arg(ptr, name) }?;
+    serde_json::from_str(text)
+        .map_err(|e| FfiError::new(BuStatus::InvalidJson, format!("{}: {}", name, e)))
+}
+
+/// Hand a JSON value to the caller through an out-parameter
+///
+/// # Safety
+/// `out` must be null or valid for writes.
+unsafe fn write_json(out: *mut *mut c_char, value: &Value) -> Result<(), FfiError> {
+    if out.is_null() {
+        return Err(FfiError::new(BuStatus::NullArgument, "out is null"));
+    }
+    let text = CString::new(value.to_string())
+        .map_err(|e| FfiError::new(BuStatus::InvalidJson, e.to_string()))?;
+    // SAFETY: non-null and writable per the caller's contract
+    unsafe { *out = text.into_raw() };
+    Ok(())
+}
+
+/// Borrow the session behind a handle
+///
+/// # Safety
+/// `handle` must be null or a live handle from [`bu_session_launch`] or [`bu_session_connect`].
+unsafe fn session_arg<'a>(handle: *mut BuSession) -> Result<&'a BrowserSession, FfiError> {
+    // SAFETY: null or live per the caller's contract
+    unsafe { handle.as_ref() }
+        .map(|handle| &handle.session)
+        .ok_or_else(|| FfiError::new(BuStatus::NullArgument, "session is null"))
+}
+
+/// Store a new session in `out`
+///
+/// # Safety
+/// `out` must be null or valid for writes.
+unsafe fn write_session(
+    out: *mut *mut BuSession,
+    session: crate::Result<BrowserSession>,
+) -> Result<(), FfiError> {
+    if out.is_null() {
+        return Err(FfiError::new(BuStatus::NullArgument, "out is null"));
+    }
+    let handle = Box::new(BuSession { session: session? });
+    // SAFETY: non-null and writable per the caller's contract
+    unsafe { *out = Box::into_raw(handle) };
+    Ok(())
+}
+
+/// Launch a browser and store its session handle in `*out`
+///
+/// `options_json` may be null or an object with `headless`, `executable_path`,
+/// `user_data_dir`, `window_width`, `window_height`, `ignore_https_errors` and
+/// `output_dir`.
+///
+/// # Safety
+/// `options_json` must be null or a NUL-terminated string; `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_session_launch(
+    options_json: *const c_char,
+    out: *mut *mut BuSession,
+) -> BuStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let options: FfiLaunchOptions =
+            unsafe { json_arg(options_json, "options_json", FfiLaunchOptions::default()) }?;
+        let session = BrowserSession::launch(options.into());
+        // SAFETY: forwarded caller contract
+        unsafe { write_session(out, session) }
+    })
+}
+
+/// Connect to a running browser by its DevTools WebSocket URL
+///
+/// # Safety
+/// `ws_url` must be a NUL-terminated string; `out` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_session_connect(
+    ws_url: *const c_char,
+    out: *mut *mut BuSession,
+) -> BuStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let ws_url = unsafe { str_arg(ws_url, "ws_url") }?;
+        let session = BrowserSession::connect(ConnectionOptions::new(ws_url));
+        // SAFETY: forwarded caller contract
+        unsafe { write_session(out, session) }
+    })
+}
+
+/// Run a tool and store its result JSON (`{"success", "data", "error", "metadata"}`) in `*out_json`
+///
+/// A tool that reports failure still returns [`BuStatus::Ok`] with
+/// `"success": false`; errors raised by the tool return [`BuStatus::BrowserError`].
+///
+/// # Safety
+/// `session` must be a live handle, `tool` a NUL-terminated string,
+/// `params_json` null or a NUL-terminated string, and `out_json` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_session_execute(
+    session: *mut BuSession,
+    tool: *const c_char,
+    params_json: *const c_char,
+    out_json: *mut *mut c_char,
+) -> BuStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let session = unsafe { session_arg(session) }?;
+        // SAFETY: forwarded caller contract
+        let tool = unsafe { str_arg(tool, "tool") }?;
+        // SAFETY: forwarded caller contract
+        let params: Value = unsafe { json_arg(params_json, "params_json", json!({})) }?;
+        let result = session.execute_tool(tool, params)?;
+        let result = serde_json::to_value(result).map_err(BrowserError::from)?;
+        // SAFETY: forwarded caller contract
+        unsafe { write_json(out_json, &result) }
+    })
+}
+
+/// Extract the active tab's DOM and store it as JSON (`{"root", "selectors", "iframe_indices"}`) in `*out_json`
+///
+/// # Safety
+/// `session` must be a live handle and `out_json` valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_session_extract_dom(
+    session: *mut BuSession,
+    out_json: *mut *mut c_char,
+) -> BuStatus {
+    guard(|| {
+        // SAFETY: forwarded caller contract
+        let session = unsafe { session_arg(session) }?;
+        let tree = session.extract_dom()?;
+        let tree = serde_json::to_value(tree).map_err(BrowserError::from)?;
+        // SAFETY: forwarded caller contract
+        unsafe { write_json(out_json, &tree) }
+    })
+}
+
+/// Close the browser and free the session handle; null is ignored
+///
+/// # Safety
+/// `session` must be null or a live handle, which is invalid afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_session_free(session: *mut BuSession) {
+    if session.is_null() {
+        return;
+    }
+    // SAFETY: live handle created by Box::into_raw, per the caller's contract
+    let handle = unsafe { Box::from_raw(session) };
+    let _ = catch_unwind(AssertUnwindSafe(move || {
+        if let Err(e) = handle.session.close() {
+            log::debug!("Failed to close browser: {}", e);
+        }
+    }));
+}
+
+/// Free a string returned by this library; null is ignored
+///
+/// # Safety
+/// `s` must be null or a string returned through an out-parameter of this library.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bu_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: created by CString::into_raw, per the caller's contract
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// JSON description of the calling thread's last error, or null after a successful call
+///
+/// The string belongs to the library and stays valid until the thread's next call.
+#[unsafe(no_mangle)]
+pub extern "C" fn bu_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Library version, e.g. "0.2.3"
+#[unsafe(no_mangle)]
+pub extern "C" fn bu_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> Value {
+        let message = unsafe { CStr::from_ptr(bu_last_error()) };
+        serde_json::from_str(message.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_null_arguments() {
+        let mut out = ptr::null_mut();
+        let status = unsafe {
+            bu_session_execute(ptr::null_mut(), c"click".as_ptr(), ptr::null(), &mut out)
+        };
+        assert_eq!(status, BuStatus::NullArgument);
+        assert_eq!(last_error()["status"], "NULL_ARGUMENT");
+        assert!(out.is_null());
+
+        let status = unsafe { bu_session_connect(ptr::null(), ptr::null_mut()) };
+        assert_eq!(status, BuStatus::NullArgument);
+        assert!(last_error()["message"].as_str().unwrap().contains("ws_url"));
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let mut out = ptr::null_mut();
+        let status = unsafe { bu_session_launch(c"{\"headles\": true}".as_ptr(), &mut out) };
+        assert_eq!(status, BuStatus::InvalidJson);
+        assert!(out.is_null());
+    }
+
+    #[test]
+    fn test_browser_error_details() {
+        let error = FfiError::from(BrowserError::Timeout("page load".to_string()));
+        assert_eq!(error.status, BuStatus::BrowserError);
+        assert_eq!(error.detail["code"], "TIMEOUT");
+        assert_eq!(error.detail["retryable"], true);
+    }
+
+    #[test]
+    fn test_version_and_free() {
+        let version = unsafe { CStr::from_ptr(bu_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+        unsafe {
+            bu_string_free(ptr::null_mut());
+            bu_session_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,591 @@
+//! Parsing of downloaded structured files
+//!
+//! After `download_url` saves a report, the `parse_file` tool turns it into
+//! JSON rows so an agent can work with the numbers without leaving the
+//! toolset. CSV, TSV, JSON and JSON Lines are always supported; Excel and
+//! OpenDocument spreadsheets need the `xlsx` feature.
+//!
+//! ```
+//! use browser_use::files::{FileFormat, ParseOptions, parse_bytes};
+//!
+//! let csv = b"region,revenue\nNorth,1200\nSouth,800.5\n";
+//! let parsed = parse_bytes(csv, FileFormat::Csv, &ParseOptions::default()).unwrap();
+//! assert_eq!(parsed.columns, vec!["region", "revenue"]);
+//! assert_eq!(parsed.rows[1]["revenue"], 800.5);
+//! assert_eq!(parsed.totals["revenue"], 2000.5);
+//! ```
+
+use crate::error::{BrowserError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Supported file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FileFormat {
+    /// Comma-separated values
+    Csv,
+    /// Tab-separated values
+    Tsv,
+    /// A JSON document; an array becomes one row per element
+    Json,
+    /// One JSON value per line
+    JsonLines,
+    /// Excel (xlsx, xlsm, xlsb, xls) or OpenDocument (ods) workbook
+    Spreadsheet,
+}
+
+impl FileFormat {
+    /// Format for a file extension, if it is a known one
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "tsv" | "tab" => Some(Self::Tsv),
+            "json" => Some(Self::Json),
+            "jsonl" | "ndjson" => Some(Self::JsonLines),
+            "xlsx" | "xlsm" | "xlsb" | "xls" | "ods" => Some(Self::Spreadsheet),
+            _ => None,
+        }
+    }
+}
+
+/// How to turn a file into rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    /// Use the first row of CSV and spreadsheet files as column names (default: true)
+    pub header: bool,
+
+    /// Worksheet to read (default: the first)
+    pub sheet: Option<String>,
+
+    /// Keep at most this many rows; totals still cover every row (default: no limit)
+    pub max_rows: Option<usize>,
+
+    /// Turn numeric-looking CSV cells into numbers (default: true)
+    pub infer_types: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            header: true,
+            sheet: None,
+            max_rows: None,
+            infer_types: true,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Create new ParseOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: treat the first row as data or as column names
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Builder method: read this worksheet
+    pub fn sheet(mut self, sheet: impl Into<String>) -> Self {
+        self.sheet = Some(sheet.into());
+        self
+    }
+
+    /// Builder method: limit the rows returned
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Builder method: keep CSV cells as strings
+    pub fn infer_types(mut self, infer: bool) -> Self {
+        self.infer_types = infer;
+        self
+    }
+}
+
+/// Rows of a parsed file
+#[derive(Debug, Clone, Serialize)]
+pub struct ParsedFile {
+    /// Format the file was parsed as
+    pub format: FileFormat,
+
+    /// Column names in file order; empty for headerless files and non-object JSON
+    pub columns: Vec<String>,
+
+    /// Rows: objects keyed by column, or arrays of cells without a header
+    pub rows: Vec<Value>,
+
+    /// Number of rows in the file, before `max_rows` was applied
+    pub total_rows: usize,
+
+    /// Sum of every column whose values are all numbers (empty cells are skipped)
+    pub totals: BTreeMap<String, f64>,
+
+    /// Worksheet names of a spreadsheet, in workbook order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sheets: Vec<String>,
+}
+
+/// Parse a file, detecting the format from its extension unless `format` is given
+pub fn parse_path(
+    path: impl AsRef<Path>,
+    format: Option<FileFormat>,
+    options: &ParseOptions,
+) -> Result<ParsedFile> {
+    let path = path.as_ref();
+    let format = format
+        .or_else(|| FileFormat::from_path(path))
+        .ok_or_else(|| {
+            BrowserError::InvalidArgument(format!(
+                "Cannot tell the format of '{}' from its extension; specify it",
+                path.display()
+            ))
+        })?;
+    let data = std::fs::read(path)?;
+    parse_bytes(&data, format, options)
+}
+
+/// Parse file contents
+pub fn parse_bytes(data: &[u8], format: FileFormat, options: &ParseOptions) -> Result<ParsedFile> {
+    let (columns, rows, sheets) = match format {
+        FileFormat::Csv | FileFormat::Tsv => {
+            let delimiter = if format == FileFormat::Tsv { '\t' } else { ',' };
+            let records = parse_delimited(decode_text(data)?, delimiter)?;
+            let records = records
+                .into_iter()
+                .map(|record| {
+                    record
+                        .into_iter()
+                        .map(|cell| csv_value(cell, options.infer_types))
+                        .collect()
+                })
+                .collect();
+            let (columns, rows) = table_rows(records, options.header);
+            (columns, rows, Vec::new())
+        }
+        FileFormat::Json => {
+            let value: Value = serde_json::from_slice(data)
+                .map_err(|e| BrowserError::DocumentFailed(format!("Invalid JSON: {}", e)))?;
+            let rows = match value {
+                Value::Array(items) => items,
+                value => vec![value],
+            };
+            (object_columns(&rows), rows, Vec::new())
+        }
+        FileFormat::JsonLines => {
+            let rows = decode_text(data)?
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(number, line)| {
+                    serde_json::from_str(line).map_err(|e| {
+                        BrowserError::DocumentFailed(format!(
+                            "Invalid JSON on line {}: {}",
+                            number + 1,
+                            e
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<Value>>>()?;
+            (object_columns(&rows), rows, Vec::new())
+        }
+        FileFormat::Spreadsheet => spreadsheet::parse(data, options)?,
+    };
+
+    let totals = column_totals(&columns, &rows);
+    let total_rows = rows.len();
+    let mut rows = rows;
+    if let Some(max_rows) = options.max_rows {
+        rows.truncate(max_rows);
+    }
+
+    Ok(ParsedFile {
+        format,
+        columns,
+        rows,
+        total_rows,
+        totals,
+        sheets,
+    })
+}
+
+/// UTF-8 text without a byte order mark
+fn decode_text(data: &[u8]) -> Result<&str> {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    std::str::from_utf8(data)
+        .map_err(|e| BrowserError::DocumentFailed(format!("File is not UTF-8 text: {}", e)))
+}
+
+/// Split delimited text into records, following RFC 4180 quoting
+fn parse_delimited(text: &str, delimiter: char) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(BrowserError::DocumentFailed(
+            "Unterminated quoted field".to_string(),
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    // Blank lines carry no data
+    records.retain(|record| !(record.len() == 1 && record[0].is_empty()));
+    Ok(records)
+}
+
+/// A CSV cell as JSON: null when empty, a number when it looks like one
+fn csv_value(cell: String, infer_types: bool) -> Value {
+    if cell.trim().is_empty() {
+        return Value::Null;
+    }
+    if !infer_types {
+        return Value::String(cell);
+    }
+    let trimmed = cell.trim();
+    let digits = trimmed.trim_start_matches(['-', '+']);
+    let looks_numeric = digits
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '-' | '+'))
+        && digits.starts_with(|c: char| c.is_ascii_digit() || c == '.');
+    // Leading zeros are identifiers (zip codes, account numbers), not numbers
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if looks_numeric && !leading_zero {
+        if let Ok(int) = trimmed.parse::<i64>() {
+            return Value::from(int);
+        }
+        if let Ok(float) = trimmed.parse::<f64>()
+            && float.is_finite()
+        {
+            return Value::from(float);
+        }
+    }
+    Value::String(cell)
+}
+
+/// Rows as objects keyed by the header, or as arrays without one
+fn table_rows(records: Vec<Vec<Value>>, header: bool) -> (Vec<String>, Vec<Value>) {
+    let mut records = records.into_iter();
+    if !header {
+        return (Vec::new(), records.map(Value::Array).collect());
+    }
+    let Some(header) = records.next() else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let mut columns: Vec<String> = Vec::with_capacity(header.len());
+    for (i, cell) in header.into_iter().enumerate() {
+        let name = match cell {
+            Value::Null => format!("column_{}", i + 1),
+            Value::String(name) => name.trim().to_string(),
+            value => value.to_string(),
+        };
+        // Keep duplicate names apart so no cell is lost
+        let mut unique = name.clone();
+        let mut suffix = 2;
+        while columns.contains(&unique) {
+            unique = format!("{}_{}", name, suffix);
+            suffix += 1;
+        }
+        columns.push(unique);
+    }
+
+    let rows = records
+        .map(|record| {
+            let mut row = Map::new();
+            for (i, cell) in record.into_iter().enumerate() {
+                let column = match columns.get(i) {
+                    Some(column) => column.clone(),
+                    None => format!("column_{}", i + 1),
+                };
+                row.insert(column, cell);
+            }
+            for column in &columns {
+                row.entry(column.clone()).or_insert(Value::Null);
+            }
+            Value::Object(row)
+        })
+        .collect();
+    (columns, rows)
+}
+
+/// Keys of object rows in first-seen order
+fn object_columns(rows: &[Value]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    for row in rows {
+        if let Value::Object(object) = row {
+            for key in object.keys() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+    }
+    columns
+}
+
+/// Sums of the columns whose non-empty values are all numbers
+fn column_totals(columns: &[String], rows: &[Value]) -> BTreeMap<String, f64> {
+    let mut totals = BTreeMap::new();
+    'columns: for column in columns {
+        let mut sum = 0.0;
+        let mut seen = false;
+        for row in rows {
+            match row.get(column) {
+                None | Some(Value::Null) => {}
+                Some(value) => match value.as_f64() {
+                    Some(number) => {
+                        sum += number;
+                        seen = true;
+                    }
+                    None => continue 'columns,
+                },
+            }
+        }
+        if seen {
+            totals.insert(column.clone(), sum);
+        }
+    }
+    totals
+}
+
+#[cfg(feature = "xlsx")]
+mod spreadsheet {
+    use super::{ParseOptions, table_rows};
+    use crate::error::{BrowserError, Result};
+    use calamine::{Data, Reader, open_workbook_auto_from_rs};
+    use serde_json::Value;
+    use std::io::Cursor;
+
+    fn cell_value(cell: &Data) -> Value {
+        match cell {
+            Data::Empty => Value::Null,
+            Data::Int(int) => Value::from(*int),
+            Data::Float(float) => Value::from(*float),
+            Data::Bool(bool) => Value::Bool(*bool),
+            Data::String(text) | Data::DateTimeIso(text) | Data::DurationIso(text) => {
+                Value::String(text.clone())
+            }
+            cell => Value::String(cell.to_string()),
+        }
+    }
+
+    /// Columns, rows and sheet names of the selected worksheet
+    pub(super) fn parse(
+        data: &[u8],
+        options: &ParseOptions,
+    ) -> Result<(Vec<String>, Vec<Value>, Vec<String>)> {
+        let mut workbook = open_workbook_auto_from_rs(Cursor::new(data))
+            .map_err(|e| BrowserError::DocumentFailed(format!("Invalid spreadsheet: {}", e)))?;
+        let sheets = workbook.sheet_names();
+        let sheet = match &options.sheet {
+            Some(sheet) if sheets.contains(sheet) => sheet.clone(),
+            Some(sheet) => {
+                return Err(BrowserError::InvalidArgument(format!(
+                    "No sheet named '{}'; sheets are: {}",
+                    sheet,
+                    sheets.join(", ")
+                )));
+            }
+            None => sheets.first().cloned().ok_or_else(|| {
+                BrowserError::DocumentFailed("Workbook has no sheets".to_string())
+            })?,
+        };
+
+        let range = workbook.worksheet_range(&sheet).map_err(|e| {
+            BrowserError::DocumentFailed(format!("Failed to read '{}': {}", sheet, e))
+        })?;
+        let records = range
+            .rows()
+            .map(|row| row.iter().map(cell_value).collect())
+            .collect();
+        let (columns, rows) = table_rows(records, options.header);
+        Ok((columns, rows, sheets))
+    }
+}
+
+#[cfg(not(feature = "xlsx"))]
+mod spreadsheet {
+    use super::ParseOptions;
+    use crate::error::{BrowserError, Result};
+    use serde_json::Value;
+
+    pub(super) fn parse(
+        _data: &[u8],
+        _options: &ParseOptions,
+    ) -> Result<(Vec<String>, Vec<Value>, Vec<String>)> {
+        Err(BrowserError::DocumentFailed(
+            "Reading spreadsheets requires the `xlsx` feature".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(FileFormat::from_path("report.CSV"), Some(FileFormat::Csv));
+        assert_eq!(
+            FileFormat::from_path("dir/export.ndjson"),
+            Some(FileFormat::JsonLines)
+        );
+        assert_eq!(
+            FileFormat::from_path("totals.xlsx"),
+            Some(FileFormat::Spreadsheet)
+        );
+        assert_eq!(FileFormat::from_path("notes.txt"), None);
+        assert_eq!(FileFormat::from_path("README"), None);
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let text = "name,notes\r\n\"Smith, J.\",\"said \"\"hi\"\"\nthen left\"\n\nLee,\n";
+        let records = parse_delimited(text, ',').unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["name", "notes"],
+                vec!["Smith, J.", "said \"hi\"\nthen left"],
+                vec!["Lee", ""],
+            ]
+        );
+        assert!(parse_delimited("a,\"b\n", ',').is_err());
+    }
+
+    #[test]
+    fn test_csv_values() {
+        assert_eq!(csv_value("42".into(), true), json!(42));
+        assert_eq!(csv_value("-3.5".into(), true), json!(-3.5));
+        assert_eq!(csv_value("1e3".into(), true), json!(1000.0));
+        assert_eq!(csv_value("02134".into(), true), json!("02134"));
+        assert_eq!(csv_value("0.5".into(), true), json!(0.5));
+        assert_eq!(csv_value("NaN".into(), true), json!("NaN"));
+        assert_eq!(csv_value("12 apples".into(), true), json!("12 apples"));
+        assert_eq!(csv_value("42".into(), false), json!("42"));
+        assert_eq!(csv_value(" ".into(), true), Value::Null);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = b"\xEF\xBB\xBFitem,qty,,qty\nPens,3,x,1\nInk,,y,2\nPaper,10,z\n";
+        let parsed = parse_bytes(csv, FileFormat::Csv, &ParseOptions::new().max_rows(2)).unwrap();
+        assert_eq!(parsed.columns, vec!["item", "qty", "column_3", "qty_2"]);
+        assert_eq!(parsed.total_rows, 3);
+        assert_eq!(parsed.rows.len(), 2);
+        assert_eq!(parsed.rows[1]["qty"], Value::Null);
+        assert_eq!(parsed.totals.get("qty"), Some(&13.0));
+        assert_eq!(parsed.totals.get("qty_2"), Some(&3.0));
+        assert!(!parsed.totals.contains_key("item"));
+
+        let parsed = parse_bytes(
+            b"a\tb\n1\t2\n",
+            FileFormat::Tsv,
+            &ParseOptions::new().header(false),
+        )
+        .unwrap();
+        assert!(parsed.columns.is_empty());
+        assert_eq!(parsed.rows, vec![json!(["a", "b"]), json!([1, 2])]);
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let data = br#"[{"id": 1, "total": 9.5}, {"id": 2, "total": 0.5, "note": "late"}]"#;
+        let parsed = parse_bytes(data, FileFormat::Json, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.columns, vec!["id", "total", "note"]);
+        assert_eq!(parsed.totals.get("total"), Some(&10.0));
+
+        let parsed = parse_bytes(
+            br#"{"ok": true}"#,
+            FileFormat::Json,
+            &ParseOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(parsed.rows, vec![json!({"ok": true})]);
+
+        let data = b"{\"n\": 1}\n\n{\"n\": 2}\n";
+        let parsed = parse_bytes(data, FileFormat::JsonLines, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.total_rows, 2);
+
+        let error = parse_bytes(
+            b"{\"n\": 1}\nnope\n",
+            FileFormat::JsonLines,
+            &ParseOptions::default(),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+    }
+
+    #[cfg(feature = "xlsx")]
+    #[test]
+    fn test_parse_spreadsheet() {
+        let data = include_bytes!("../tests/fixtures/totals.xlsx");
+        let parsed = parse_bytes(data, FileFormat::Spreadsheet, &ParseOptions::default()).unwrap();
+        assert_eq!(parsed.sheets, vec!["Sales", "Notes"]);
+        assert_eq!(parsed.columns, vec!["region", "amount"]);
+        assert_eq!(parsed.rows[0], json!({"region": "North", "amount": 120.5}));
+        assert_eq!(parsed.totals.get("amount"), Some(&200.0));
+
+        let notes = parse_bytes(
+            data,
+            FileFormat::Spreadsheet,
+            &ParseOptions::new().sheet("Notes").header(false),
+        )
+        .unwrap();
+        assert_eq!(notes.rows, vec![json!(["Preliminary figures"])]);
+
+        let error = parse_bytes(
+            data,
+            FileFormat::Spreadsheet,
+            &ParseOptions::new().sheet("Missing"),
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("Sales, Notes"));
+    }
+
+    #[cfg(not(feature = "xlsx"))]
+    #[test]
+    fn test_spreadsheet_needs_feature() {
+        let error =
+            parse_bytes(b"PK", FileFormat::Spreadsheet, &ParseOptions::default()).unwrap_err();
+        assert!(matches!(error, BrowserError::DocumentFailed(_)));
+    }
+}
@@ -0,0 +1,214 @@
+//! HTTP server with bundled test pages
+//!
+//! Integration tests that load `data:` URLs see pages without an origin,
+//! which changes link resolution, iframes and some visibility checks.
+//! [`FixtureServer`] serves real pages from `http://127.0.0.1` instead:
+//!
+//! | Path | Content |
+//! |------|---------|
+//! | `/index.html` | Links to the other pages |
+//! | `/form.html` | Sign-up form (text, email, select, checkbox, submit) |
+//! | `/submitted.html` | Form target that echoes its query string |
+//! | `/iframe.html` | Page embedding `/iframe_child.html` |
+//! | `/shadow_dom.html` | Custom elements with open and closed shadow roots |
+//! | `/infinite_scroll.html` | Feed loading 5 pages of 20 items as the end comes into view |
+//!
+//! ```no_run
+//! use browser_use::BrowserSession;
+//! use browser_use::fixtures::FixtureServer;
+//!
+//! let server = FixtureServer::start().unwrap();
+//! let session = BrowserSession::new().unwrap();
+//! session.navigate(&server.url("/form.html")).unwrap();
+//! ```
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Bundled pages by path
+const PAGES: &[(&str, &str)] = &[
+    ("/index.html", include_str!("pages/index.html")),
+    ("/form.html", include_str!("pages/form.html")),
+    ("/submitted.html", include_str!("pages/submitted.html")),
+    ("/iframe.html", include_str!("pages/iframe.html")),
+    (
+        "/iframe_child.html",
+        include_str!("pages/iframe_child.html"),
+    ),
+    ("/shadow_dom.html", include_str!("pages/shadow_dom.html")),
+    (
+        "/infinite_scroll.html",
+        include_str!("pages/infinite_scroll.html"),
+    ),
+];
+
+/// How long a connection may stay silent before it is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Page served for `path`, ignoring any query string; `/` is the index
+pub fn page(path: &str) -> Option<&'static str> {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let path = if path == "/" { "/index.html" } else { path };
+    PAGES
+        .iter()
+        .find(|(page_path, _)| *page_path == path)
+        .map(|(_, html)| *html)
+}
+
+/// Paths of the bundled pages
+pub fn paths() -> impl Iterator<Item = &'static str> {
+    PAGES.iter().map(|(path, _)| *path)
+}
+
+/// Serves the bundled pages on a local port until dropped
+pub struct FixtureServer {
+    addr: SocketAddr,
+    stopped: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FixtureServer {
+    /// Start serving on a free port of 127.0.0.1
+    pub fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let stop = Arc::clone(&stopped);
+        let thread = std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                // Browsers keep idle connections open, so each gets its own thread
+                if let Ok(stream) = stream {
+                    std::thread::spawn(move || {
+                        if let Err(e) = serve(stream) {
+                            log::debug!("Fixture server connection failed: {}", e);
+                        }
+                    });
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            stopped,
+            thread: Some(thread),
+        })
+    }
+
+    /// Address the server listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Absolute URL of `path`, e.g. `url("/form.html")`
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.addr, path)
+    }
+}
+
+impl Drop for FixtureServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Answer the requests of one connection until the client closes it
+fn serve(stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line)? == 0 {
+            return Ok(());
+        }
+        // Skip the headers; requests for pages have no body
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+                break;
+            }
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default();
+        let path = parts.next().unwrap_or_default();
+        let (status, body) = match (method, page(path)) {
+            ("GET" | "HEAD", Some(html)) => ("200 OK", html),
+            ("GET" | "HEAD", None) => ("404 Not Found", "Not found"),
+            _ => ("405 Method Not Allowed", "Method not allowed"),
+        };
+        let content_type = if status.starts_with("200") {
+            "text/html; charset=utf-8"
+        } else {
+            "text/plain; charset=utf-8"
+        };
+
+        write!(
+            writer,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\n\r\n",
+            status,
+            content_type,
+            body.len()
+        )?;
+        if method != "HEAD" {
+            writer.write_all(body.as_bytes())?;
+        }
+        writer.flush()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn get(server: &FixtureServer, path: &str) -> String {
+        let mut stream = TcpStream::connect(server.addr()).unwrap();
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+            path
+        )
+        .unwrap();
+        stream.shutdown(std::net::Shutdown::Write).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_page_lookup() {
+        assert!(page("/").unwrap().contains("<h1>Fixtures</h1>"));
+        assert!(page("/form.html?step=2").unwrap().contains("id=\"signup\""));
+        assert!(page("/missing.html").is_none());
+        assert!(paths().all(|path| page(path).is_some()));
+    }
+
+    #[test]
+    fn test_serves_pages() {
+        let server = FixtureServer::start().unwrap();
+        assert!(server.url("/form.html").starts_with("http://127.0.0.1:"));
+
+        let response = get(&server, "/iframe.html");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("text/html"));
+        assert!(response.contains("/iframe_child.html"));
+
+        let response = get(&server, "/nope");
+        assert!(response.starts_with("HTTP/1.1 404"));
+    }
+}
@@ -0,0 +1,255 @@
+//! gRPC server exposing the tool registry
+//!
+//! Implements the `browser_use.v1.BrowserUse` service of
+//! `proto/browser_use.proto` over one browser session: `ExecuteTool` runs a
+//! registered tool, `GetDom` extracts the DOM, `StreamEvents` streams page
+//! problems and `ListTools` describes the tools. Calls are serialized on the
+//! session and run on tokio's blocking pool.
+//!
+//! Tool errors are returned as gRPC statuses with the library's error code in
+//! the `error-code` metadata entry, e.g. `ELEMENT_NOT_FOUND`.
+
+pub mod proto;
+
+use crate::browser::BrowserSession;
+use crate::browser::events::PageEvent;
+use crate::error::BrowserError;
+use crate::tools::ToolResult;
+use proto::browser_use_server::{BrowserUse, BrowserUseServer};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+/// Page events buffered per subscriber before the oldest are dropped
+const EVENT_CAPACITY: usize = 256;
+
+/// Metadata key carrying the library's error code on failed calls
+pub const ERROR_CODE_METADATA: &str = "error-code";
+
+/// The `BrowserUse` service over one browser session
+#[derive(Clone)]
+pub struct GrpcServer {
+    session: Arc<Mutex<BrowserSession>>,
+    events: broadcast::Sender<PageEvent>,
+}
+
+impl GrpcServer {
+    /// Serve `session`, forwarding its page events to `StreamEvents` subscribers
+    pub fn new(session: BrowserSession) -> crate::Result<Self> {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        let sender = events.clone();
+        session.on_page_event(move |event| {
+            // No subscribers is fine; the event is dropped
+            let _ = sender.send(event.clone());
+        })?;
+
+        Ok(Self {
+            session: Arc::new(Mutex::new(session)),
+            events,
+        })
+    }
+
+    /// Wrap the server for a tonic router, e.g. `Server::builder().add_service(server.into_service())`
+    pub fn into_service(self) -> BrowserUseServer<Self> {
+        BrowserUseServer::new(self)
+    }
+
+    /// Run `f` on the session on the blocking pool, after earlier calls finish
+    async fn with_session<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&BrowserSession) -> crate::Result<T> + Send + 'static,
+    ) -> Result<T, Status> {
+        let session = Arc::clone(&self.session);
+        tokio::task::spawn_blocking(move || f(&lock(&session)))
+            .await
+            .map_err(|e| Status::internal(format!("Tool call aborted: {}", e)))?
+            .map_err(to_status)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// gRPC status for a library error, with its error code in the metadata
+fn to_status(error: BrowserError) -> Status {
+    use crate::error::ErrorCode::*;
+    use tonic::Code;
+
+    let code = error.code();
+    let grpc_code = match code {
+        InvalidArgument | InvalidSelector => Code::InvalidArgument,
+        ElementNotFound => Code::NotFound,
+        Timeout | NavTimeout => Code::DeadlineExceeded,
+        PolicyDenied | PathNotAllowed | RobotsDisallowed => Code::PermissionDenied,
+        ConnectionFailed | BrowserDisconnected | PageCrashed => Code::Unavailable,
+        _ => Code::Internal,
+    };
+    let mut status = Status::new(grpc_code, error.to_string());
+    if let Ok(value) = code.as_str().parse() {
+        status.metadata_mut().insert(ERROR_CODE_METADATA, value);
+    }
+    status
+}
+
+fn to_json(value: &impl serde::Serialize) -> Result<String, Status> {
+    serde_json::to_string(value).map_err(|e| Status::internal(e.to_string()))
+}
+
+impl TryFrom<ToolResult> for proto::ExecuteToolResponse {
+    type Error = Status;
+
+    fn try_from(result: ToolResult) -> Result<Self, Status> {
+        Ok(Self {
+            success: result.success,
+            data_json: match result.data {
+                Some(ref data) => to_json(data)?,
+                None => String::new(),
+            },
+            error: result.error.unwrap_or_default(),
+            metadata_json: to_json(&result.metadata)?,
+        })
+    }
+}
+
+impl From<PageEvent> for proto::PageEvent {
+    fn from(event: PageEvent) -> Self {
+        match event {
+            PageEvent::ConsoleError { message } => Self {
+                r#type: "consoleError".to_string(),
+                message,
+                ..Default::default()
+            },
+            PageEvent::PageError { message, url } => Self {
+                r#type: "pageError".to_string(),
+                message,
+                url: url.unwrap_or_default(),
+                ..Default::default()
+            },
+            PageEvent::RequestFailed { url, status, error } => Self {
+                r#type: "requestFailed".to_string(),
+                url,
+                status: status.unwrap_or_default(),
+                error: error.unwrap_or_default(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<proto::PageEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl BrowserUse for GrpcServer {
+    async fn execute_tool(
+        &self,
+        request: Request<proto::ExecuteToolRequest>,
+    ) -> Result<Response<proto::ExecuteToolResponse>, Status> {
+        let request = request.into_inner();
+        let params = if request.params_json.trim().is_empty() {
+            serde_json::json!({})
+        } else {
+            serde_json::from_str(&request.params_json).map_err(|e| {
+                Status::invalid_argument(format!("params_json is not valid JSON: {}", e))
+            })?
+        };
+
+        let name = request.name;
+        let result = self
+            .with_session(move |session| session.execute_tool(&name, params))
+            .await?;
+        Ok(Response::new(result.try_into()?))
+    }
+
+    async fn get_dom(
+        &self,
+        _request: Request<proto::GetDomRequest>,
+    ) -> Result<Response<proto::GetDomResponse>, Status> {
+        let tree = self.with_session(|session| session.extract_dom()).await?;
+        Ok(Response::new(proto::GetDomResponse {
+            tree_json: to_json(&tree)?,
+            interactive_count: tree.count_interactive() as u32,
+        }))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        _request: Request<proto::StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        // Subscribers that fall behind skip the events they missed
+        let stream = BroadcastStream::new(self.events.subscribe())
+            .filter_map(|event| event.ok())
+            .map(|event| Ok(event.into()));
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Request<proto::ListToolsRequest>,
+    ) -> Result<Response<proto::ListToolsResponse>, Status> {
+        let tools = self
+            .with_session(|session| Ok(session.tool_registry().all_tools()))
+            .await?;
+        let mut tools = tools
+            .iter()
+            .map(|tool| {
+                Ok(proto::ToolInfo {
+                    name: tool.name().to_string(),
+                    description: tool.description().to_string(),
+                    parameters_schema_json: to_json(&tool.parameters_schema())?,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Response::new(proto::ListToolsResponse { tools }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_status() {
+        let status = to_status(BrowserError::ElementNotFound("#submit".to_string()));
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert_eq!(
+            status.metadata().get(ERROR_CODE_METADATA).unwrap(),
+            "ELEMENT_NOT_FOUND"
+        );
+
+        let status = to_status(BrowserError::NavigationTimeout("slow".to_string()));
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    }
+
+    #[test]
+    fn test_tool_result_response() {
+        let result = ToolResult::success_with(serde_json::json!({ "url": "https://example.com" }));
+        let response = proto::ExecuteToolResponse::try_from(result).unwrap();
+        assert!(response.success);
+        assert_eq!(response.data_json, r#"{"url":"https://example.com"}"#);
+        assert_eq!(response.metadata_json, "{}");
+
+        let response = proto::ExecuteToolResponse::try_from(ToolResult::failure("nope")).unwrap();
+        assert!(!response.success);
+        assert_eq!(response.error, "nope");
+        assert!(response.data_json.is_empty());
+    }
+
+    #[test]
+    fn test_page_event_message() {
+        let event = proto::PageEvent::from(PageEvent::RequestFailed {
+            url: "https://example.com/api".to_string(),
+            status: Some(502),
+            error: None,
+        });
+        assert_eq!(event.r#type, "requestFailed");
+        assert_eq!(event.status, 502);
+        assert!(event.message.is_empty());
+    }
+}
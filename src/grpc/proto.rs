@@ -0,0 +1,74 @@
+//! Messages of `proto/browser_use.proto` and the generated service
+//!
+//! The messages are written by hand with prost derives so building needs no
+//! protoc; the service code is generated by `build.rs`. Keep both in sync
+//! with the .proto file.
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ExecuteToolRequest {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub params_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ExecuteToolResponse {
+    #[prost(bool, tag = "1")]
+    pub success: bool,
+    #[prost(string, tag = "2")]
+    pub data_json: String,
+    #[prost(string, tag = "3")]
+    pub error: String,
+    #[prost(string, tag = "4")]
+    pub metadata_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetDomRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GetDomResponse {
+    #[prost(string, tag = "1")]
+    pub tree_json: String,
+    #[prost(uint32, tag = "2")]
+    pub interactive_count: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StreamEventsRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PageEvent {
+    #[prost(string, tag = "1")]
+    pub r#type: String,
+    #[prost(string, tag = "2")]
+    pub message: String,
+    #[prost(string, tag = "3")]
+    pub url: String,
+    #[prost(uint32, tag = "4")]
+    pub status: u32,
+    #[prost(string, tag = "5")]
+    pub error: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListToolsRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ToolInfo {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(string, tag = "3")]
+    pub parameters_schema_json: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListToolsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub tools: Vec<ToolInfo>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/browser_use.v1.BrowserUse.rs"));
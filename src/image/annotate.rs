@@ -0,0 +1,413 @@
+//! Drawing annotations over screenshots
+//!
+//! Rectangles, arrows and text labels drawn straight into an [`RgbaImage`],
+//! for set-of-mark style element labels and failure visualizations:
+//!
+//! ```
+//! use browser_use::BoundingBox;
+//! use browser_use::image::{LabelStyle, RgbaImage};
+//!
+//! let mut image = RgbaImage::filled(200, 100, [255, 255, 255, 255]);
+//! let button = BoundingBox::new(40.0, 30.0, 80.0, 24.0);
+//! image.stroke_rect(&button, [220, 38, 38, 255], 2);
+//! image.draw_label(40.0, 10.0, "Submit?", &LabelStyle::default());
+//! image.draw_arrow((180.0, 90.0), (120.0, 54.0), [220, 38, 38, 255], 2);
+//! ```
+//!
+//! Coordinates are image pixels. Element boxes from the page are in CSS
+//! pixels, so scale them by the device pixel ratio first if it is not 1.
+//! Text uses a built-in 5x7 pixel font covering printable ASCII; lowercase
+//! letters are drawn as capitals and other characters as `?`.
+
+use super::RgbaImage;
+use crate::dom::BoundingBox;
+
+/// Colors cycled through by [`RgbaImage::mark_elements`]
+pub const MARK_COLORS: &[[u8; 4]] = &[
+    [220, 38, 38, 255],
+    [37, 99, 235, 255],
+    [22, 163, 74, 255],
+    [217, 119, 6, 255],
+    [147, 51, 234, 255],
+    [8, 145, 178, 255],
+];
+
+/// Glyph width in font pixels, excluding spacing
+const GLYPH_WIDTH: u32 = 5;
+
+/// Glyph height in font pixels
+const GLYPH_HEIGHT: u32 = 7;
+
+/// 5x7 glyphs for ' ' through '_', one byte per column, least significant bit at the top
+const FONT: [[u8; 5]; 64] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x56, 0x20, 0x50], // '&'
+    [0x00, 0x08, 0x07, 0x03, 0x00], // '\''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x2A, 0x1C, 0x7F, 0x1C, 0x2A], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+];
+
+fn glyph(c: char) -> &'static [u8; 5] {
+    let c = c.to_ascii_uppercase();
+    match c {
+        ' '..='_' => &FONT[c as usize - ' ' as usize],
+        _ => &FONT['?' as usize - ' ' as usize],
+    }
+}
+
+/// Appearance of a text label
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelStyle {
+    /// Text color (default: white)
+    pub color: [u8; 4],
+
+    /// Box color behind the text; `None` draws no box (default: red)
+    pub background: Option<[u8; 4]>,
+
+    /// Font pixels per image pixel (default: 2)
+    pub scale: u32,
+
+    /// Space between text and box edge in image pixels (default: 2)
+    pub padding: u32,
+}
+
+impl Default for LabelStyle {
+    fn default() -> Self {
+        Self {
+            color: [255, 255, 255, 255],
+            background: Some(MARK_COLORS[0]),
+            scale: 2,
+            padding: 2,
+        }
+    }
+}
+
+impl LabelStyle {
+    /// Create new LabelStyle with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: set text color
+    pub fn color(mut self, color: [u8; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Builder method: set or remove the background box
+    pub fn background(mut self, background: Option<[u8; 4]>) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Builder method: set font scale
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Builder method: set padding
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Size of `text` drawn in this style, including padding
+    pub fn measure(&self, text: &str) -> (u32, u32) {
+        let chars = text.chars().count() as u32;
+        let width = if chars == 0 {
+            0
+        } else {
+            (chars * (GLYPH_WIDTH + 1) - 1) * self.scale
+        };
+        (
+            width + 2 * self.padding,
+            GLYPH_HEIGHT * self.scale + 2 * self.padding,
+        )
+    }
+}
+
+impl RgbaImage {
+    /// Blend `color` over the pixel at (x, y) by the color's alpha; no-op outside the image
+    pub fn blend_pixel(&mut self, x: i64, y: i64, color: [u8; 4]) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let offset = (y as usize * self.width as usize + x as usize) * 4;
+        let alpha = color[3] as u32;
+        let px = &mut self.pixels[offset..offset + 4];
+        for channel in 0..3 {
+            px[channel] =
+                ((color[channel] as u32 * alpha + px[channel] as u32 * (255 - alpha)) / 255) as u8;
+        }
+        px[3] = px[3].max(color[3]);
+    }
+
+    /// Fill `bounds`, blending by the color's alpha so content stays visible under translucent fills
+    pub fn fill_rect(&mut self, bounds: &BoundingBox, color: [u8; 4]) {
+        let (left, top, right, bottom) = pixel_bounds(bounds);
+        for y in top..bottom {
+            for x in left..right {
+                self.blend_pixel(x, y, color);
+            }
+        }
+    }
+
+    /// Outline `bounds` with a border `thickness` pixels wide, drawn inside the box
+    pub fn stroke_rect(&mut self, bounds: &BoundingBox, color: [u8; 4], thickness: u32) {
+        let (left, top, right, bottom) = pixel_bounds(bounds);
+        let t = (thickness.max(1) as i64)
+            .min((right - left + 1) / 2)
+            .min((bottom - top + 1) / 2);
+        for y in top..bottom {
+            for x in left..right {
+                let on_edge = x < left + t || x >= right - t || y < top + t || y >= bottom - t;
+                if on_edge {
+                    self.blend_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    /// Draw a straight line `thickness` pixels wide
+    pub fn draw_line(&mut self, from: (f64, f64), to: (f64, f64), color: [u8; 4], thickness: u32) {
+        let (mut x, mut y) = (from.0.round() as i64, from.1.round() as i64);
+        let (x1, y1) = (to.0.round() as i64, to.1.round() as i64);
+        let thickness = thickness.max(1) as i64;
+        let offset = (thickness - 1) / 2;
+
+        // Bresenham, stamping a square brush at every step
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        loop {
+            for by in 0..thickness {
+                for bx in 0..thickness {
+                    self.blend_pixel(x + bx - offset, y + by - offset, color);
+                }
+            }
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Draw an arrow from `from` with its head at `to`
+    pub fn draw_arrow(&mut self, from: (f64, f64), to: (f64, f64), color: [u8; 4], thickness: u32) {
+        self.draw_line(from, to, color, thickness);
+
+        let angle = (from.1 - to.1).atan2(from.0 - to.0);
+        let length = (4.0 * thickness.max(1) as f64).max(10.0);
+        for side in [-0.5f64, 0.5] {
+            let head = (
+                to.0 + length * (angle + side).cos(),
+                to.1 + length * (angle + side).sin(),
+            );
+            self.draw_line(to, head, color, thickness);
+        }
+    }
+
+    /// Draw `text` with its top-left corner (including padding) at (x, y)
+    ///
+    /// Returns the area the label covers, for placing further marks.
+    pub fn draw_label(&mut self, x: f64, y: f64, text: &str, style: &LabelStyle) -> BoundingBox {
+        let (width, height) = style.measure(text);
+        let area = BoundingBox::new(x.round(), y.round(), width as f64, height as f64);
+        if let Some(background) = style.background {
+            self.fill_rect(&area, background);
+        }
+
+        let scale = style.scale.max(1) as i64;
+        let mut pen_x = area.x as i64 + style.padding as i64;
+        let top = area.y as i64 + style.padding as i64;
+        for c in text.chars() {
+            for (column, bits) in glyph(c).iter().enumerate() {
+                for row in 0..GLYPH_HEIGHT as i64 {
+                    if bits & (1 << row) == 0 {
+                        continue;
+                    }
+                    let px = pen_x + column as i64 * scale;
+                    let py = top + row * scale;
+                    for sy in 0..scale {
+                        for sx in 0..scale {
+                            self.blend_pixel(px + sx, py + sy, style.color);
+                        }
+                    }
+                }
+            }
+            pen_x += (GLYPH_WIDTH as i64 + 1) * scale;
+        }
+        area
+    }
+
+    /// Set-of-mark overlay: outline each box and label it with its number
+    ///
+    /// Colors cycle through [`MARK_COLORS`]. Labels sit just above the box's
+    /// top-left corner, or inside it when the box touches the top edge.
+    pub fn mark_elements(&mut self, marks: &[(usize, BoundingBox)]) {
+        for (i, (index, bounds)) in marks.iter().enumerate() {
+            let color = MARK_COLORS[i % MARK_COLORS.len()];
+            self.stroke_rect(bounds, color, 2);
+
+            let style = LabelStyle::default().background(Some(color));
+            let text = index.to_string();
+            let (_, height) = style.measure(&text);
+            let y = if bounds.y >= height as f64 {
+                bounds.y - height as f64
+            } else {
+                bounds.y
+            };
+            self.draw_label(bounds.x, y, &text, &style);
+        }
+    }
+}
+
+/// Pixel rows and columns covered by `bounds` as (left, top, right, bottom), right and bottom exclusive
+fn pixel_bounds(bounds: &BoundingBox) -> (i64, i64, i64, i64) {
+    let left = bounds.x.round() as i64;
+    let top = bounds.y.round() as i64;
+    let right = (bounds.x + bounds.width).round() as i64;
+    let bottom = (bounds.y + bounds.height).round() as i64;
+    (left, top, right.max(left), bottom.max(top))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const RED: [u8; 4] = [255, 0, 0, 255];
+
+    #[test]
+    fn test_stroke_and_fill_rect() {
+        let mut image = RgbaImage::filled(20, 20, WHITE);
+        image.stroke_rect(&BoundingBox::new(2.0, 2.0, 10.0, 10.0), RED, 2);
+        assert_eq!(image.pixel(2, 2), Some(RED));
+        assert_eq!(image.pixel(3, 7), Some(RED));
+        assert_eq!(image.pixel(11, 11), Some(RED));
+        assert_eq!(image.pixel(6, 6), Some(WHITE));
+        assert_eq!(image.pixel(12, 12), Some(WHITE));
+
+        // Half-transparent red over white
+        image.fill_rect(&BoundingBox::new(5.0, 5.0, 2.0, 2.0), [255, 0, 0, 128]);
+        assert_eq!(image.pixel(6, 6), Some([255, 127, 127, 255]));
+
+        // Boxes partly outside the image are clipped
+        image.fill_rect(&BoundingBox::new(-5.0, 15.0, 100.0, 100.0), RED);
+        assert_eq!(image.pixel(0, 19), Some(RED));
+    }
+
+    #[test]
+    fn test_lines_and_arrows() {
+        let mut image = RgbaImage::filled(30, 30, WHITE);
+        image.draw_line((0.0, 0.0), (29.0, 29.0), RED, 1);
+        assert!((0..30).all(|i| image.pixel(i, i) == Some(RED)));
+        assert_eq!(image.pixel(0, 29), Some(WHITE));
+
+        let mut image = RgbaImage::filled(30, 30, WHITE);
+        image.draw_arrow((2.0, 15.0), (27.0, 15.0), RED, 1);
+        assert_eq!(image.pixel(15, 15), Some(RED));
+        // Head strokes fan out behind the tip
+        assert_eq!(image.pixel(19, 11), Some(RED));
+        assert_eq!(image.pixel(19, 19), Some(RED));
+    }
+
+    #[test]
+    fn test_label() {
+        let style = LabelStyle::new().scale(1).padding(1).background(None);
+        assert_eq!(style.measure("12"), (13, 9));
+        assert_eq!(style.measure(""), (2, 9));
+
+        let mut image = RgbaImage::filled(20, 12, [0, 0, 0, 255]);
+        let area = image.draw_label(0.0, 0.0, "1", &style.color(WHITE));
+        assert_eq!(area, BoundingBox::new(0.0, 0.0, 7.0, 9.0));
+        // Stem of the '1' runs down the middle column
+        assert!((1..8).all(|y| image.pixel(3, y) == Some(WHITE)));
+        assert_eq!(image.pixel(1, 1), Some([0, 0, 0, 255]));
+        assert_eq!(glyph('a'), glyph('A'));
+        assert_eq!(glyph('é'), glyph('?'));
+    }
+
+    #[test]
+    fn test_mark_elements() {
+        let mut image = RgbaImage::filled(100, 60, WHITE);
+        image.mark_elements(&[
+            (0, BoundingBox::new(10.0, 30.0, 40.0, 20.0)),
+            (1, BoundingBox::new(60.0, 0.0, 30.0, 20.0)),
+        ]);
+        assert_eq!(image.pixel(30, 49), Some(MARK_COLORS[0]));
+        // First label above the box, second inside since there is no room above
+        assert_eq!(image.pixel(10, 12), Some(MARK_COLORS[0]));
+        assert_eq!(image.pixel(61, 2), Some(MARK_COLORS[1]));
+    }
+}
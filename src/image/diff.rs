@@ -0,0 +1,155 @@
+//! Pixel-level image comparison
+
+use super::RgbaImage;
+
+/// Tolerances for comparing two images
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffOptions {
+    /// Per-pixel color distance (0.0 - 1.0) below which pixels count as equal (default: 0.1)
+    pub threshold: f64,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self { threshold: 0.1 }
+    }
+}
+
+impl DiffOptions {
+    /// Create new DiffOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: set per-pixel color threshold
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Result of comparing two images
+#[derive(Debug, Clone)]
+pub struct DiffResult {
+    /// Width of the compared area (the larger of both widths)
+    pub width: u32,
+
+    /// Height of the compared area (the larger of both heights)
+    pub height: u32,
+
+    /// Number of pixels that differ
+    pub diff_pixels: u64,
+
+    /// Fraction of pixels that differ (0.0 - 1.0)
+    pub diff_ratio: f64,
+
+    /// Whether the two images have different dimensions
+    pub size_mismatch: bool,
+
+    /// Visualization: differing pixels in red over a faded copy of the actual image
+    pub diff_image: RgbaImage,
+}
+
+/// Color distance between two pixels, normalized to 0.0 - 1.0
+fn color_distance(a: [u8; 4], b: [u8; 4]) -> f64 {
+    let sum: f64 = a
+        .iter()
+        .zip(b.iter())
+        .map(|(x, y)| {
+            let d = *x as f64 - *y as f64;
+            d * d
+        })
+        .sum();
+    (sum / (4.0 * 255.0 * 255.0)).sqrt()
+}
+
+/// Faded grayscale rendition of an unchanged pixel for the diff image
+fn faded(px: [u8; 4]) -> [u8; 4] {
+    let luma = 0.299 * px[0] as f64 + 0.587 * px[1] as f64 + 0.114 * px[2] as f64;
+    let alpha = px[3] as f64 / 255.0;
+    let value = 255.0 - 0.1 * alpha * (255.0 - luma);
+    let value = value.round() as u8;
+    [value, value, value, 255]
+}
+
+/// Compare `actual` against `baseline`
+///
+/// Pixels outside the overlap of differently sized images always count as different.
+pub fn diff(baseline: &RgbaImage, actual: &RgbaImage, options: &DiffOptions) -> DiffResult {
+    let width = baseline.width.max(actual.width);
+    let height = baseline.height.max(actual.height);
+    let mut diff_image = RgbaImage::filled(width, height, [255, 0, 0, 255]);
+    let mut diff_pixels = 0u64;
+
+    for y in 0..height {
+        for x in 0..width {
+            let offset = (y as usize * width as usize + x as usize) * 4;
+            let same = match (baseline.pixel(x, y), actual.pixel(x, y)) {
+                (Some(expected), Some(got))
+                    if color_distance(expected, got) <= options.threshold =>
+                {
+                    Some(faded(got))
+                }
+                _ => None,
+            };
+
+            match same {
+                Some(px) => diff_image.pixels[offset..offset + 4].copy_from_slice(&px),
+                None => diff_pixels += 1,
+            }
+        }
+    }
+
+    let total = width as u64 * height as u64;
+    DiffResult {
+        width,
+        height,
+        diff_pixels,
+        diff_ratio: if total == 0 {
+            0.0
+        } else {
+            diff_pixels as f64 / total as f64
+        },
+        size_mismatch: baseline.width != actual.width || baseline.height != actual.height,
+        diff_image,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_images() {
+        let image = RgbaImage::filled(4, 4, [10, 20, 30, 255]);
+        let result = diff(&image, &image, &DiffOptions::default());
+        assert_eq!(result.diff_pixels, 0);
+        assert_eq!(result.diff_ratio, 0.0);
+        assert!(!result.size_mismatch);
+    }
+
+    #[test]
+    fn test_threshold() {
+        let baseline = RgbaImage::filled(2, 1, [100, 100, 100, 255]);
+        let mut actual = baseline.clone();
+        actual.pixels[0] = 110;
+        actual.pixels[4..8].copy_from_slice(&[255, 0, 0, 255]);
+
+        let result = diff(&baseline, &actual, &DiffOptions::new().threshold(0.1));
+        assert_eq!(result.diff_pixels, 1);
+        assert_eq!(result.diff_image.pixel(1, 0), Some([255, 0, 0, 255]));
+
+        let strict = diff(&baseline, &actual, &DiffOptions::new().threshold(0.0));
+        assert_eq!(strict.diff_pixels, 2);
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let baseline = RgbaImage::filled(2, 2, [0, 0, 0, 255]);
+        let actual = RgbaImage::filled(2, 3, [0, 0, 0, 255]);
+        let result = diff(&baseline, &actual, &DiffOptions::default());
+        assert!(result.size_mismatch);
+        assert_eq!(result.diff_pixels, 2);
+        assert_eq!(result.height, 3);
+    }
+}
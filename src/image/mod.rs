@@ -0,0 +1,71 @@
+//! Image decoding, encoding, comparison and annotation
+//!
+//! Screenshots come back from Chrome as PNG bytes. This module decodes them
+//! into RGBA pixel buffers so they can be compared against baselines for
+//! visual regression checks, or annotated with boxes, arrows and labels.
+
+pub mod annotate;
+pub mod diff;
+pub mod png;
+
+pub use annotate::{LabelStyle, MARK_COLORS};
+pub use diff::{DiffOptions, DiffResult};
+
+use crate::error::Result;
+use std::path::Path;
+
+/// 8-bit RGBA image stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    /// Width in pixels
+    pub width: u32,
+
+    /// Height in pixels
+    pub height: u32,
+
+    /// Pixel data, four bytes (R, G, B, A) per pixel
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Create an image filled with a single color
+    pub fn filled(width: u32, height: u32, rgba: [u8; 4]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: rgba.repeat(width as usize * height as usize),
+        }
+    }
+
+    /// Decode PNG bytes
+    pub fn from_png(data: &[u8]) -> Result<Self> {
+        png::decode(data)
+    }
+
+    /// Encode as PNG bytes
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        png::encode(self)
+    }
+
+    /// Read a PNG file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_png(&std::fs::read(path)?)
+    }
+
+    /// Write the image as a PNG file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_png()?)?;
+        Ok(())
+    }
+
+    /// Pixel at (x, y), or `None` outside the image
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let offset = (y as usize * self.width as usize + x as usize) * 4;
+        self.pixels
+            .get(offset..offset + 4)
+            .map(|px| [px[0], px[1], px[2], px[3]])
+    }
+}
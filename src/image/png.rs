@@ -0,0 +1,227 @@
+//! Minimal PNG codec
+//!
+//! Decodes the non-interlaced, 8-bit PNGs produced by Chrome and most image
+//! tools (grayscale, RGB, palette, with or without alpha) and encodes RGBA
+//! images. Anything else is rejected with [`BrowserError::ImageFailed`].
+
+use super::RgbaImage;
+use crate::error::{BrowserError, Result};
+use flate2::Compression;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use std::io::{Read, Write};
+
+const SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1a\n";
+
+fn invalid(reason: impl Into<String>) -> BrowserError {
+    BrowserError::ImageFailed(reason.into())
+}
+
+/// Decode a PNG into an RGBA image
+pub fn decode(data: &[u8]) -> Result<RgbaImage> {
+    if !data.starts_with(SIGNATURE) {
+        return Err(invalid("Not a PNG file"));
+    }
+
+    let mut pos = SIGNATURE.len();
+    let mut header: Option<(u32, u32, u8, u8, u8)> = None;
+    let mut palette: Vec<[u8; 4]> = Vec::new();
+    let mut compressed = Vec::new();
+
+    while pos + 8 <= data.len() {
+        let len = u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        let kind = &data[pos + 4..pos + 8];
+        let body = data
+            .get(pos + 8..pos + 8 + len)
+            .ok_or_else(|| invalid("Truncated PNG chunk"))?;
+        pos += 12 + len;
+
+        match kind {
+            b"IHDR" if body.len() >= 13 => {
+                header = Some((
+                    u32::from_be_bytes(body[0..4].try_into().unwrap()),
+                    u32::from_be_bytes(body[4..8].try_into().unwrap()),
+                    body[8],
+                    body[9],
+                    body[12],
+                ));
+            }
+            b"PLTE" => {
+                palette = body
+                    .chunks_exact(3)
+                    .map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                    .collect();
+            }
+            b"tRNS" => {
+                for (entry, alpha) in palette.iter_mut().zip(body) {
+                    entry[3] = *alpha;
+                }
+            }
+            b"IDAT" => compressed.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+    }
+
+    let (width, height, bit_depth, color_type, interlace) =
+        header.ok_or_else(|| invalid("Missing PNG header"))?;
+    if bit_depth != 8 || interlace != 0 {
+        return Err(invalid(format!(
+            "Unsupported PNG format (bit depth {}, interlace {})",
+            bit_depth, interlace
+        )));
+    }
+    let channels = match color_type {
+        0 => 1,
+        2 => 3,
+        3 => 1,
+        4 => 2,
+        6 => 4,
+        other => return Err(invalid(format!("Unsupported PNG color type {}", other))),
+    };
+
+    let mut raw = Vec::new();
+    ZlibDecoder::new(compressed.as_slice())
+        .read_to_end(&mut raw)
+        .map_err(|e| invalid(format!("Corrupt PNG data: {}", e)))?;
+
+    let stride = width as usize * channels;
+    if raw.len() < (stride + 1) * height as usize {
+        return Err(invalid("Truncated PNG image data"));
+    }
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    let mut previous = vec![0u8; stride];
+    let mut current = vec![0u8; stride];
+
+    for row in raw.chunks_exact(stride + 1).take(height as usize) {
+        unfilter(row[0], &row[1..], &previous, &mut current, channels)?;
+
+        for px in current.chunks_exact(channels) {
+            let rgba = match color_type {
+                0 => [px[0], px[0], px[0], 255],
+                2 => [px[0], px[1], px[2], 255],
+                3 => *palette
+                    .get(px[0] as usize)
+                    .ok_or_else(|| invalid("PNG palette index out of range"))?,
+                4 => [px[0], px[0], px[0], px[1]],
+                _ => [px[0], px[1], px[2], px[3]],
+            };
+            pixels.extend_from_slice(&rgba);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    Ok(RgbaImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Reverse the per-row PNG filter into `out`
+fn unfilter(filter: u8, row: &[u8], prev: &[u8], out: &mut [u8], bpp: usize) -> Result<()> {
+    for i in 0..row.len() {
+        let left = if i >= bpp { out[i - bpp] } else { 0 };
+        let up = prev[i];
+        let up_left = if i >= bpp { prev[i - bpp] } else { 0 };
+
+        let predictor = match filter {
+            0 => 0,
+            1 => left,
+            2 => up,
+            3 => ((left as u16 + up as u16) / 2) as u8,
+            4 => paeth(left, up, up_left),
+            other => return Err(invalid(format!("Unknown PNG filter {}", other))),
+        };
+        out[i] = row[i].wrapping_add(predictor);
+    }
+    Ok(())
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let pa = (p - a as i16).abs();
+    let pb = (p - b as i16).abs();
+    let pc = (p - c as i16).abs();
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+/// Encode an RGBA image as PNG
+pub fn encode(image: &RgbaImage) -> Result<Vec<u8>> {
+    let stride = image.width as usize * 4;
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in image.pixels.chunks_exact(stride) {
+        encoder.write_all(&[0])?;
+        encoder.write_all(row)?;
+    }
+    let compressed = encoder.finish()?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&image.width.to_be_bytes());
+    header.extend_from_slice(&image.height.to_be_bytes());
+    // 8-bit RGBA, deflate, adaptive filtering, no interlace
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut output = SIGNATURE.to_vec();
+    write_chunk(&mut output, b"IHDR", &header);
+    write_chunk(&mut output, b"IDAT", &compressed);
+    write_chunk(&mut output, b"IEND", &[]);
+    Ok(output)
+}
+
+fn write_chunk(output: &mut Vec<u8>, kind: &[u8; 4], body: &[u8]) {
+    output.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    output.extend_from_slice(kind);
+    output.extend_from_slice(body);
+
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(kind);
+    crc.update(body);
+    output.extend_from_slice(&crc.finalize().to_be_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let image = RgbaImage {
+            width: 2,
+            height: 2,
+            pixels: vec![
+                255, 0, 0, 255, 0, 255, 0, 255, //
+                0, 0, 255, 255, 10, 20, 30, 40,
+            ],
+        };
+        let decoded = decode(&encode(&image).unwrap()).unwrap();
+        assert_eq!(decoded, image);
+    }
+
+    #[test]
+    fn test_unfilter_paeth_and_average() {
+        let prev = [10, 20, 30];
+        let mut out = [0u8; 3];
+        unfilter(3, &[1, 1, 1], &prev, &mut out, 1).unwrap();
+        assert_eq!(out, [6, 14, 23]);
+
+        unfilter(4, &[0, 0, 0], &prev, &mut out, 1).unwrap();
+        assert_eq!(out, [10, 20, 30]);
+    }
+
+    #[test]
+    fn test_rejects_non_png() {
+        assert!(matches!(
+            decode(b"GIF89a"),
+            Err(BrowserError::ImageFailed(_))
+        ));
+    }
+}
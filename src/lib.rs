@@ -86,18 +86,69 @@
 //! # }
 //! ```
 //!
+//! ## Cargo Features
+//!
+//! - `schema` (default): JSON schemas of tool parameters via schemars
+//! - `mcp-handler` (default): the [`mcp`] server library on rmcp and tokio; implies `schema`
+//! - `mcp-server`: the `mcp-server` binary; implies `mcp-handler` and `cli`
+//! - `cli`: command-line parsing and logging for the binaries
+//! - `encryption`: password-encrypted storage state files
+//! - `binary`: binary encoding of DOM snapshots for cross-process pipelines
+//! - `ffi`: C ABI for embedding from other languages (header in `include/browser_use.h`)
+//! - `grpc-server`: gRPC service and the `grpc-server` binary
+//! - `rest-server`: REST API and the `rest-server` binary
+//! - `ws-server`: WebSocket JSON-RPC channel and the `ws-server` binary
+//! - `test-fixtures`: local HTTP server with bundled pages for integration tests
+//! - `ocr`: text recognition over screenshots
+//! - `pdf`: text and outline extraction from PDF documents
+//! - `xlsx`: spreadsheet parsing in the `parse_file` tool
+//!
+//! With `default-features = false` the browser, DOM and tool layers build
+//! without schemars, rmcp, tokio or clap.
+//!
 //! ## Module Overview
 //!
 //! - [`browser`]: Browser session management and configuration
+//! - [`crawler`]: Parallel site crawling over a pool of browser contexts
 //! - [`dom`]: DOM extraction, element indexing, and tree representation
+//! - [`image`]: PNG decoding, pixel diffing and screenshot annotation
+//! - [`plugin`]: Stable API for crates that publish tool packs
 //! - [`tools`]: Browser automation tools (navigate, click, input, extract, etc.)
 //! - [`error`]: Error types and result aliases
+//! - [`files`]: CSV, JSON and spreadsheet parsing of downloaded files
+//! - `ffi`: C ABI with opaque sessions and JSON tool calls (requires `ffi` feature)
+//! - `fixtures`: HTTP server with bundled test pages (requires `test-fixtures` feature)
+//! - `grpc`: gRPC service over the tool registry (requires `grpc-server` feature)
+//! - `rest`: REST API over browser sessions (requires `rest-server` feature)
+//! - `ws`: WebSocket JSON-RPC channel with live page events (requires `ws-server` feature)
+//! - [`url`]: URL normalization and deduplication helpers
+//! - `ocr`: Text recognition over screenshots (requires `ocr` feature and a tesseract install)
+//! - `pdf`: Text and outline extraction from PDFs (requires `pdf` feature)
 //! - [`mcp`]: **Model Context Protocol server** (requires `mcp-handler` feature) - **Start here for AI integration**
 
 pub mod browser;
+pub mod crawler;
 pub mod dom;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod files;
+#[cfg(feature = "test-fixtures")]
+pub mod fixtures;
+#[cfg(feature = "grpc-server")]
+pub mod grpc;
+pub mod image;
+#[cfg(feature = "ocr")]
+pub mod ocr;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod plugin;
+#[cfg(feature = "rest-server")]
+pub mod rest;
 pub mod tools;
+pub mod url;
+#[cfg(feature = "ws-server")]
+pub mod ws;
 
 #[cfg(feature = "mcp-handler")]
 pub mod mcp;
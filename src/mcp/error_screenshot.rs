@@ -0,0 +1,141 @@
+//! Screenshots attached to failed tool calls
+
+use crate::browser::BrowserSession;
+use crate::browser::screenshot::ScreenshotOptions;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rmcp::ErrorData as McpError;
+use rmcp::model::{CallToolResult, Content};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What [`BrowserServer::screenshot_on_error`](crate::mcp::BrowserServer::screenshot_on_error)
+/// does with the screenshot of a failed tool call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorScreenshot {
+    /// Save a PNG in this directory and name its path in the error data as `screenshot`
+    Save(PathBuf),
+
+    /// Return the failure as a tool result flagged `isError`, with the screenshot as image content
+    Inline,
+}
+
+impl ErrorScreenshot {
+    /// Capture the active tab of `session` and attach it to the error of a failed call to `tool`
+    ///
+    /// The error is returned unchanged when no screenshot can be taken, e.g.
+    /// because the tab crashed.
+    pub(crate) fn attach(
+        &self,
+        session: &BrowserSession,
+        tool: &str,
+        error: McpError,
+    ) -> Result<CallToolResult, McpError> {
+        let png = match session.screenshot_bytes(&ScreenshotOptions::new()) {
+            Ok(png) => png,
+            Err(e) => {
+                log::debug!("No screenshot of failed call to {}: {}", tool, e);
+                return Err(error);
+            }
+        };
+
+        match self {
+            Self::Inline => Ok(inline_error(error, &png)),
+            Self::Save(dir) => {
+                let path = dir.join(screenshot_file_name(tool));
+                let saved = std::fs::create_dir_all(dir).and_then(|()| std::fs::write(&path, &png));
+                match saved {
+                    Ok(()) => Err(with_screenshot_path(error, &path)),
+                    Err(e) => {
+                        log::warn!("Failed to save screenshot to {}: {}", path.display(), e);
+                        Err(error)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `<tool>-<unix millis>.png`
+fn screenshot_file_name(tool: &str) -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    format!("{}-{}.png", tool, millis)
+}
+
+/// Add the screenshot's path to the error data
+fn with_screenshot_path(mut error: McpError, path: &Path) -> McpError {
+    let path = serde_json::Value::from(path.to_string_lossy());
+    match error.data {
+        Some(serde_json::Value::Object(ref mut data)) => {
+            data.insert("screenshot".to_string(), path);
+        }
+        _ => error.data = Some(serde_json::json!({ "screenshot": path })),
+    }
+    error
+}
+
+/// Tool result carrying the error message, the screenshot and the error data
+fn inline_error(error: McpError, png: &[u8]) -> CallToolResult {
+    let mut result = CallToolResult::error(vec![
+        Content::text(error.message),
+        Content::image(STANDARD.encode(png), "image/png"),
+    ]);
+    result.structured_content = error.data;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screenshot_path_in_error_data() {
+        let error = McpError::internal_error(
+            "Element not found",
+            Some(serde_json::json!({ "code": "ELEMENT_NOT_FOUND" })),
+        );
+        let error = with_screenshot_path(error, Path::new("/tmp/errors/browser_click-1.png"));
+        assert_eq!(
+            error.data,
+            Some(serde_json::json!({
+                "code": "ELEMENT_NOT_FOUND",
+                "screenshot": "/tmp/errors/browser_click-1.png",
+            }))
+        );
+
+        let error = with_screenshot_path(
+            McpError::internal_error("Busy", None),
+            Path::new("shot.png"),
+        );
+        assert_eq!(
+            error.data,
+            Some(serde_json::json!({ "screenshot": "shot.png" }))
+        );
+    }
+
+    #[test]
+    fn test_inline_error_result() {
+        let error =
+            McpError::internal_error("Timeout", Some(serde_json::json!({ "code": "TIMEOUT" })));
+        let result = inline_error(error, b"png");
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(result.content.len(), 2);
+        assert_eq!(result.content[0].as_text().unwrap().text, "Timeout");
+        let image = result.content[1].as_image().unwrap();
+        assert_eq!(image.mime_type, "image/png");
+        assert_eq!(image.data, STANDARD.encode(b"png"));
+        assert_eq!(
+            result.structured_content,
+            Some(serde_json::json!({ "code": "TIMEOUT" }))
+        );
+    }
+
+    #[test]
+    fn test_screenshot_file_name() {
+        let name = screenshot_file_name("browser_click");
+        assert!(name.starts_with("browser_click-"), "{}", name);
+        assert!(name.ends_with(".png"), "{}", name);
+    }
+}
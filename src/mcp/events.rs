@@ -0,0 +1,110 @@
+//! Page events streamed to MCP clients as logging notifications
+
+use crate::browser::events::PageEvent;
+use rmcp::model::{LoggingLevel, LoggingMessageNotificationParam};
+use rmcp::service::{Peer, RoleServer};
+use std::sync::{Mutex, MutexGuard};
+use tokio::runtime::Handle;
+
+/// Logger name clients see on streamed page events
+const LOGGER: &str = "browser";
+
+/// Client connection that receives page events
+struct Subscriber {
+    peer: Peer<RoleServer>,
+    runtime: Handle,
+}
+
+/// Forwards page events to the connected client as `notifications/message`
+pub(crate) struct EventStream {
+    subscriber: Mutex<Option<Subscriber>>,
+    min_level: Mutex<LoggingLevel>,
+}
+
+impl Default for EventStream {
+    fn default() -> Self {
+        Self {
+            subscriber: Mutex::new(None),
+            min_level: Mutex::new(LoggingLevel::Debug),
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Severity a page event is reported with
+fn level_of(event: &PageEvent) -> LoggingLevel {
+    match event {
+        PageEvent::ConsoleError { .. } | PageEvent::PageError { .. } => LoggingLevel::Error,
+        PageEvent::RequestFailed { .. } => LoggingLevel::Warning,
+    }
+}
+
+impl EventStream {
+    /// Send events to this client from now on; must be called inside the server's runtime
+    pub(crate) fn subscribe(&self, peer: Peer<RoleServer>) {
+        match Handle::try_current() {
+            Ok(runtime) => *lock(&self.subscriber) = Some(Subscriber { peer, runtime }),
+            Err(e) => log::warn!("Cannot stream page events without a runtime: {}", e),
+        }
+    }
+
+    /// Only send events at or above `level`, as requested with `logging/setLevel`
+    pub(crate) fn set_level(&self, level: LoggingLevel) {
+        *lock(&self.min_level) = level;
+    }
+
+    /// Send an event to the client, if one is subscribed and wants its level
+    ///
+    /// Called from browser event threads; the notification is sent on the
+    /// server's runtime without waiting for it.
+    pub(crate) fn publish(&self, event: &PageEvent) {
+        let level = level_of(event);
+        if (level as u8) < (*lock(&self.min_level) as u8) {
+            return;
+        }
+
+        let mut subscriber = lock(&self.subscriber);
+        let Some(Subscriber { peer, runtime }) = subscriber.as_ref() else {
+            return;
+        };
+        if peer.is_transport_closed() {
+            *subscriber = None;
+            return;
+        }
+
+        let peer = peer.clone();
+        let notification = LoggingMessageNotificationParam {
+            level,
+            logger: Some(LOGGER.to_string()),
+            data: serde_json::to_value(event).unwrap_or_default(),
+        };
+        runtime.spawn(async move {
+            if let Err(e) = peer.notify_logging_message(notification).await {
+                log::debug!("Failed to send page event: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_levels() {
+        let console = PageEvent::ConsoleError {
+            message: "boom".to_string(),
+        };
+        let request = PageEvent::RequestFailed {
+            url: "https://example.com/api".to_string(),
+            status: Some(500),
+            error: None,
+        };
+        assert_eq!(level_of(&console), LoggingLevel::Error);
+        assert_eq!(level_of(&request), LoggingLevel::Warning);
+        assert!((LoggingLevel::Warning as u8) < (LoggingLevel::Error as u8));
+    }
+}
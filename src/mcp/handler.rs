@@ -1,51 +1,855 @@
 //! ServerHandler implementation for BrowserSession
 
-use crate::browser::BrowserSession;
-use log::debug;
+use crate::browser::{
+    BrowserSession, ConnectionOptions, EvaluateAccess, LaunchOptions, SessionInfo,
+};
+use crate::error::ErrorCode;
+use crate::mcp::error_screenshot::ErrorScreenshot;
+use crate::mcp::events::EventStream;
+use crate::mcp::prompts::{PROMPTS, WorkflowPrompt, find_prompt};
+use crate::mcp::queue::CallQueue;
+use crate::mcp::resources::{
+    ELEMENTS_PAGE_SIZE, ELEMENTS_URI, ELEMENTS_URI_TEMPLATE, ElementPage, element_page,
+    parse_elements_uri,
+};
+use crate::mcp::{READ_ONLY_REFUSED_PARAMS, READ_ONLY_TOOLS, custom_tool_info, describe_routes};
+use crate::plugin::{ToolPack, check_pack};
+use crate::tools::{DynTool, ToolContext};
+use log::{debug, info, warn};
 use rmcp::{
-    ServerHandler,
-    handler::server::tool::ToolRouter,
-    model::{ServerCapabilities, ServerInfo},
-    tool_handler,
+    ErrorData as McpError, RoleServer, ServerHandler,
+    handler::server::tool::{ToolCallContext, ToolRouter},
+    model::{
+        AnnotateAble, CallToolRequestParam, CallToolResult, GetPromptRequestParam, GetPromptResult,
+        JsonObject, ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult,
+        ListToolsResult, PaginatedRequestParam, RawResource, RawResourceTemplate,
+        ReadResourceRequestParam, ReadResourceResult, ResourceContents, ServerCapabilities,
+        ServerInfo, SetLevelRequestParam,
+    },
+    service::{NotificationContext, RequestContext},
 };
-use std::sync::{Arc, Mutex};
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{BuildHasher, RandomState};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, TryLockError, Weak};
+use std::time::{Duration, Instant};
+
+/// Sessions created with `browser_session_create` open at once unless configured otherwise
+pub const DEFAULT_MAX_SESSIONS: usize = 4;
+
+/// Id tools accept for the session they use when no `session_id` is given
+pub const DEFAULT_SESSION_ID: &str = "default";
+
+/// How long a browser that has not failed a call goes without a connection check
+const CONNECTION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Error codes after which the next call checks whether the browser is still there
+const CONNECTION_ERRORS: [ErrorCode; 3] = [
+    ErrorCode::BrowserDisconnected,
+    ErrorCode::ConnectionFailed,
+    ErrorCode::Timeout,
+];
+
+/// Browser session that can be closed while idle and relaunched on demand
+struct SessionSlot {
+    /// Options used to (re)launch the browser; only the session settings apply to a connected one
+    options: LaunchOptions,
+
+    /// Running browser to connect to instead of launching one
+    connection: Option<ConnectionOptions>,
+
+    /// Running session, `None` after an idle shutdown
+    session: Option<BrowserSession>,
+
+    /// When the last tool call finished
+    last_used: Instant,
+
+    /// When the connection was last known to work; `None` has the next call check it
+    checked: Option<Instant>,
+
+    /// Set once the server shuts down; no further launches happen
+    closed: bool,
+
+    /// Where page events go, when streaming them to the client is enabled
+    events: Option<Arc<EventStream>>,
+
+    /// Template kept up to date with this slot's scope; only the default session has one
+    template: Option<Arc<Mutex<SessionTemplate>>>,
+}
+
+/// What `browser_session_create` copies from the default session
+///
+/// Kept apart from the default slot so creating a session does not wait for
+/// a tool call running on the default session.
+struct SessionTemplate {
+    /// Launch options of the default session, with its scope as narrowed at runtime
+    options: LaunchOptions,
+
+    /// Whether the default session drives a browser it connected to
+    connected: bool,
+
+    /// Set once the server shuts down; no further sessions are created
+    closed: bool,
+}
+
+impl SessionSlot {
+    /// Launch the browser if it is not running
+    ///
+    /// A running browser's connection is checked after a call failed with a
+    /// connection error or timeout, and otherwise at most every
+    /// [`CONNECTION_CHECK_INTERVAL`], so most calls skip the round trip.
+    fn ensure_launched(&mut self) -> Result<&BrowserSession, String> {
+        if self.closed {
+            return Err("Server is shutting down".to_string());
+        }
+        if let Some(session) = &self.session
+            && self
+                .checked
+                .is_none_or(|checked| checked.elapsed() >= CONNECTION_CHECK_INTERVAL)
+        {
+            if let Err(e) = session.check_connection() {
+                if !self.options.relaunch_on_disconnect {
+                    return Err(format!(
+                        "{}; call browser_restart to start a new browser",
+                        e
+                    ));
+                }
+                warn!("{}; starting a new browser", e);
+                self.drop_disconnected();
+            } else {
+                self.checked = Some(Instant::now());
+            }
+        }
+        if self.session.is_none() {
+            let session = match &self.connection {
+                Some(connection) => self.connect(connection.clone())?,
+                None => {
+                    info!("Launching browser");
+                    BrowserSession::launch(self.options.clone())
+                        .map_err(|e| format!("Failed to launch browser: {}", e))?
+                }
+            };
+            if let Some(events) = &self.events {
+                let events = Arc::clone(events);
+                if let Err(e) = session.on_page_event(move |event| events.publish(event)) {
+                    warn!("Page events will not be streamed: {}", e);
+                }
+            }
+            self.session = Some(session);
+            self.checked = Some(Instant::now());
+        }
+        Ok(self.session.as_ref().expect("session was just launched"))
+    }
+
+    /// Connect to the running browser, applying the launch options' session settings
+    fn connect(&self, connection: ConnectionOptions) -> Result<BrowserSession, String> {
+        info!("Connecting to browser at {}", connection.ws_url);
+        BrowserSession::connect_with_options(connection, self.options.clone())
+            .map_err(|e| format!("Failed to connect to browser: {}", e))
+    }
+
+    /// Close the browser, keeping runtime restrictions for the next launch
+    ///
+    /// A browser the server connected to is left running with its tabs; only
+    /// the connection is dropped.
+    fn shut_down(&mut self) {
+        if let Some(session) = self.session.take() {
+            self.keep_scope(&session);
+            if self.connection.is_none() {
+                let _ = session.close();
+            }
+        }
+    }
+
+    /// Forget a session whose browser is gone, keeping runtime restrictions
+    fn drop_disconnected(&mut self) {
+        if let Some(session) = self.session.take() {
+            self.keep_scope(&session);
+        }
+    }
+
+    /// Replace the browser with a fresh one, or launch it if it is not running
+    ///
+    /// State cannot be preserved from a browser that exited or disconnected;
+    /// a new one is launched without it.
+    fn restart(&mut self, preserve_state: bool) -> Result<(), String> {
+        if let Some(session) = &self.session
+            && let Err(e) = session.check_connection()
+        {
+            warn!("{}; launching a new browser", e);
+            self.drop_disconnected();
+        }
+        match self.session.take() {
+            Some(session) => {
+                self.keep_scope(&session);
+                let session = session
+                    .restart(preserve_state)
+                    .map_err(|e| format!("Failed to restart browser: {}", e))?;
+                self.session = Some(session);
+                self.checked = Some(Instant::now());
+                Ok(())
+            }
+            None => self.ensure_launched().map(|_| ()),
+        }
+    }
+
+    /// Carry a scope narrowed at runtime over to future launches
+    fn keep_scope(&mut self, session: &BrowserSession) {
+        if let Some(scope) = session.scope() {
+            self.options.scope = Some(scope.clone());
+            if let Some(template) = &self.template {
+                lock(template).options.scope = Some(scope);
+            }
+        }
+    }
+
+    /// Copy a scope narrowed at runtime to the template for new sessions
+    fn publish_scope(&self) {
+        if let Some(template) = &self.template
+            && let Some(scope) = self.session.as_ref().and_then(BrowserSession::scope)
+        {
+            lock(template).options.scope = Some(scope);
+        }
+    }
+}
+
+/// Locked, launched browser session; marks the session as used when dropped
+pub(crate) struct SessionGuard<'a>(MutexGuard<'a, SessionSlot>);
+
+impl Deref for SessionGuard<'_> {
+    type Target = BrowserSession;
+
+    fn deref(&self) -> &BrowserSession {
+        self.0.session.as_ref().expect("session is launched")
+    }
+}
+
+impl SessionGuard<'_> {
+    /// Have the next call check the connection if `error` suggests the browser is gone or hung
+    fn note_error(&mut self, error: &McpError) {
+        let code = error
+            .data
+            .as_ref()
+            .and_then(|data| data.get("code"))
+            .and_then(serde_json::Value::as_str);
+        if CONNECTION_ERRORS.iter().any(|c| Some(c.as_str()) == code) {
+            self.0.checked = None;
+        }
+    }
+}
+
+impl Drop for SessionGuard<'_> {
+    fn drop(&mut self) {
+        self.0.last_used = Instant::now();
+        self.0.publish_scope();
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A session slot and the queue its tool calls wait in
+#[derive(Clone)]
+pub(crate) struct SessionEntry {
+    slot: Arc<Mutex<SessionSlot>>,
+    queue: Arc<CallQueue>,
+}
+
+impl SessionEntry {
+    /// Queue that serializes tool calls on this session
+    pub(crate) fn queue(&self) -> &CallQueue {
+        &self.queue
+    }
+
+    /// The browser session, relaunching it after an idle shutdown (blocking lock)
+    pub(crate) fn session(&self) -> Result<SessionGuard<'_>, String> {
+        let mut slot = lock(&self.slot);
+        slot.ensure_launched()?;
+        Ok(SessionGuard(slot))
+    }
+
+    /// Tear down the browser and start a fresh one, optionally keeping cookies and localStorage
+    pub(crate) fn restart(&self, preserve_state: bool) -> Result<(), String> {
+        let mut slot = lock(&self.slot);
+        if slot.closed {
+            return Err("Server is shutting down".to_string());
+        }
+        slot.restart(preserve_state)?;
+        slot.last_used = Instant::now();
+        Ok(())
+    }
+
+    /// Health of the browser, without launching it or waiting for a running tool call
+    pub(crate) fn status(&self) -> SessionStatus {
+        let slot = match self.slot.try_lock() {
+            Ok(slot) => slot,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => return SessionStatus::Busy,
+        };
+        match &slot.session {
+            Some(session) => SessionStatus::Running(Box::new(session.session_info())),
+            None => SessionStatus::NotRunning,
+        }
+    }
+
+    /// Wait for the running tool call to finish, then close the browser for good
+    fn close(&self) {
+        let mut slot = lock(&self.slot);
+        slot.closed = true;
+        slot.shut_down();
+    }
+}
+
+/// What `browser_status` found out about a session
+pub(crate) enum SessionStatus {
+    /// No browser: not launched yet, closed while idle, or the server shut down
+    NotRunning,
+
+    /// A tool call holds the session; the browser may be hung in it
+    Busy,
+
+    /// The browser's health
+    Running(Box<SessionInfo>),
+}
+
+/// Close the browser once it has been idle for `timeout`; exits when the server is dropped
+fn reap_idle_session(slot: Weak<Mutex<SessionSlot>>, timeout: Duration) {
+    let interval = (timeout / 4).clamp(Duration::from_millis(10), Duration::from_secs(30));
+    loop {
+        std::thread::sleep(interval);
+        let Some(slot) = slot.upgrade() else {
+            return;
+        };
+        // A locked slot means a tool call is running
+        let Ok(mut slot) = slot.try_lock() else {
+            continue;
+        };
+        if slot.session.is_some() && slot.last_used.elapsed() >= timeout {
+            info!(
+                "Browser idle for {}s; closing it until the next tool call",
+                slot.last_used.elapsed().as_secs()
+            );
+            slot.shut_down();
+        }
+    }
+}
+
+/// Sessions created with `browser_session_create`, by id
+type SessionRegistry = Mutex<BTreeMap<String, SessionEntry>>;
+
+/// Handle for shutting down a server's browser without keeping the server alive
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    slot: Weak<Mutex<SessionSlot>>,
+    template: Weak<Mutex<SessionTemplate>>,
+    sessions: Weak<SessionRegistry>,
+}
+
+impl ShutdownHandle {
+    /// Whether the server still exists
+    pub fn is_alive(&self) -> bool {
+        self.slot.strong_count() > 0
+    }
+
+    /// Wait for the running tool calls to finish, then close the browsers and refuse further calls
+    pub fn shutdown(&self) {
+        if let Some(template) = self.template.upgrade() {
+            lock(&template).closed = true;
+        }
+        if let Some(slot) = self.slot.upgrade() {
+            let mut slot = lock(&slot);
+            slot.closed = true;
+            slot.shut_down();
+        }
+        if let Some(sessions) = self.sessions.upgrade() {
+            let entries = std::mem::take(&mut *lock(&sessions));
+            for entry in entries.values() {
+                entry.close();
+            }
+        }
+    }
+}
 
 /// MCP Server wrapper for BrowserSession
 ///
 /// This struct holds a browser session and provides thread-safe access
-/// for MCP tool execution.
+/// for MCP tool execution. Concurrent tool calls are queued and run one at a
+/// time in arrival order.
+///
+/// Clients can open further sessions with `browser_session_create` and pass
+/// their id as `session_id` to any tool. Each has its own browser and queue,
+/// so calls on different sessions run concurrently.
 #[derive(Clone)]
 pub struct BrowserServer {
-    session: Arc<Mutex<BrowserSession>>,
+    session: Arc<Mutex<SessionSlot>>,
+    template: Arc<Mutex<SessionTemplate>>,
+    queue: Arc<CallQueue>,
+    sessions: Arc<SessionRegistry>,
+    max_sessions: usize,
+    idle_timeout: Option<Duration>,
+    events: Option<Arc<EventStream>>,
+    custom_tools: Arc<RwLock<BTreeMap<String, Arc<dyn DynTool>>>>,
+    error_screenshot: Option<ErrorScreenshot>,
+    read_only: bool,
+    builtin_tools: Arc<BTreeSet<String>>,
     tool_router: ToolRouter<Self>,
 }
 
 impl BrowserServer {
     /// Create a new browser server with default launch options
     pub fn new() -> Result<Self, String> {
-        let session =
-            BrowserSession::new().map_err(|e| format!("Failed to launch browser: {}", e))?;
-
-        Ok(Self {
-            session: Arc::new(Mutex::new(session)),
-            tool_router: Self::tool_router(),
-        })
+        Self::with_options(LaunchOptions::default())
     }
 
     /// Create a new browser server with custom launch options
-    pub fn with_options(options: crate::browser::LaunchOptions) -> Result<Self, String> {
-        let session = BrowserSession::launch(options)
-            .map_err(|e| format!("Failed to launch browser: {}", e))?;
+    ///
+    /// The browser is not started until the first tool call that needs it (or
+    /// [`BrowserServer::launch`]), so idle servers hold no Chrome process.
+    pub fn with_options(options: LaunchOptions) -> Result<Self, String> {
+        Ok(Self::with_slot(options, None))
+    }
 
-        Ok(Self {
-            session: Arc::new(Mutex::new(session)),
-            tool_router: Self::tool_router(),
-        })
+    /// Create a browser server that drives an already running browser
+    ///
+    /// The connection is made on the first tool call that needs it, and made
+    /// again after an idle timeout or restart. Shutting the server down
+    /// disconnects but leaves the browser and its tabs open.
+    pub fn connect(connection: ConnectionOptions) -> Result<Self, String> {
+        Self::connect_with_options(connection, LaunchOptions::default())
+    }
+
+    /// Like [`BrowserServer::connect`], applying the policies of `options`
+    ///
+    /// Scope, robots.txt, rate limit, output directory, evaluate policy,
+    /// confirmation and index order carry over; options that only concern
+    /// launching Chrome, such as headless mode or the window size, are ignored.
+    pub fn connect_with_options(
+        connection: ConnectionOptions,
+        options: LaunchOptions,
+    ) -> Result<Self, String> {
+        Ok(Self::with_slot(options, Some(connection)))
+    }
+
+    fn with_slot(options: LaunchOptions, connection: Option<ConnectionOptions>) -> Self {
+        let template = Arc::new(Mutex::new(SessionTemplate {
+            options: options.clone(),
+            connected: connection.is_some(),
+            closed: false,
+        }));
+        let slot = SessionSlot {
+            options,
+            connection,
+            session: None,
+            last_used: Instant::now(),
+            checked: None,
+            closed: false,
+            events: None,
+            template: Some(Arc::clone(&template)),
+        };
+        let tool_router = Self::all_tool_routes();
+        let builtin_tools = tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+
+        Self {
+            session: Arc::new(Mutex::new(slot)),
+            template,
+            queue: Arc::new(CallQueue::default()),
+            sessions: Arc::default(),
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            idle_timeout: None,
+            events: None,
+            custom_tools: Arc::default(),
+            error_screenshot: None,
+            read_only: false,
+            builtin_tools: Arc::new(builtin_tools),
+            tool_router,
+        }
+    }
+
+    /// Close the browser after `timeout` without tool calls; it is relaunched on the next call
+    ///
+    /// Pages, cookies and tabs of the closed browser are lost, but a scope set
+    /// with `browser_set_scope` is carried over to the relaunched session.
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        let slot = Arc::downgrade(&self.session);
+        std::thread::spawn(move || reap_idle_session(slot, timeout));
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Handle that shuts this server down, usable after the server is moved into a transport
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle {
+            slot: Arc::downgrade(&self.session),
+            template: Arc::downgrade(&self.template),
+            sessions: Arc::downgrade(&self.sessions),
+        }
+    }
+
+    /// Wait for the running tool call to finish, then close the browser and refuse further calls
+    pub fn shutdown(&self) {
+        self.shutdown_handle().shutdown();
+    }
+
+    /// Start the browser now instead of on the first tool call
+    pub fn launch(&self) -> Result<(), String> {
+        self.session().map(|_| ())
+    }
+
+    /// Tear down the browser and start a fresh one, optionally keeping cookies and localStorage
+    pub fn restart(&self, preserve_state: bool) -> Result<(), String> {
+        self.default_entry().restart(preserve_state)
+    }
+
+    /// Whether the browser is currently running
+    pub fn is_browser_running(&self) -> bool {
+        self.slot().session.is_some()
+    }
+
+    /// Stream console errors, uncaught page errors and failed requests to the client
+    ///
+    /// Events are sent as MCP logging notifications (`notifications/message`)
+    /// from logger "browser", so a supervising agent can react to a broken
+    /// page without polling. Clients can raise the threshold with
+    /// `logging/setLevel`; console and page errors are sent at level "error",
+    /// failed requests at "warning".
+    pub fn stream_events(mut self) -> Self {
+        let events = Arc::new(EventStream::default());
+        self.slot().events = Some(Arc::clone(&events));
+        self.events = Some(events);
+        self
+    }
+
+    /// Set how many sessions `browser_session_create` may have open at once
+    pub fn max_sessions(mut self, max: usize) -> Self {
+        self.max_sessions = max;
+        self
+    }
+
+    /// Launch a browser for a new session and return its id
+    ///
+    /// The session uses the server's launch options, including a scope
+    /// narrowed at runtime, but not its user data directory, so it starts
+    /// without the default session's cookies and storage. It is closed with
+    /// [`BrowserServer::close_session`] or when the server shuts down.
+    pub fn create_session(&self) -> Result<String, String> {
+        let full = || format!("At most {} sessions may be open", self.max_sessions);
+        if lock(&self.sessions).len() >= self.max_sessions {
+            return Err(full());
+        }
+
+        let mut slot = {
+            let template = lock(&self.template);
+            if template.closed {
+                return Err("Server is shutting down".to_string());
+            }
+            if template.connected {
+                return Err(
+                    "Separate sessions need launched browsers; this server drives a browser it \
+                     connected to"
+                        .to_string(),
+                );
+            }
+            let mut options = template.options.clone();
+            options.user_data_dir = None;
+            SessionSlot {
+                options,
+                connection: None,
+                session: None,
+                last_used: Instant::now(),
+                checked: None,
+                closed: false,
+                events: self.events.clone(),
+                template: None,
+            }
+        };
+        slot.ensure_launched()?;
+
+        let entry = SessionEntry {
+            slot: Arc::new(Mutex::new(slot)),
+            queue: Arc::new(CallQueue::new(self.queue.capacity())),
+        };
+        let id = format!("{:016x}", RandomState::new().hash_one(Instant::now()));
+        let mut sessions = lock(&self.sessions);
+        if lock(&self.template).closed {
+            drop(sessions);
+            entry.close();
+            return Err("Server is shutting down".to_string());
+        }
+        if sessions.len() >= self.max_sessions {
+            drop(sessions);
+            entry.close();
+            return Err(full());
+        }
+        if let Some(timeout) = self.idle_timeout {
+            let slot = Arc::downgrade(&entry.slot);
+            std::thread::spawn(move || reap_idle_session(slot, timeout));
+        }
+        sessions.insert(id.clone(), entry);
+        info!("Opened session {}", id);
+        Ok(id)
+    }
+
+    /// Close the browser of a session made with [`BrowserServer::create_session`]
+    ///
+    /// Waits for a tool call running on the session to finish first.
+    pub fn close_session(&self, id: &str) -> Result<(), String> {
+        if id == DEFAULT_SESSION_ID {
+            return Err("The default session cannot be closed; use browser_close".to_string());
+        }
+        let entry = lock(&self.sessions)
+            .remove(id)
+            .ok_or_else(|| format!("No session '{}'", id))?;
+        entry.close();
+        info!("Closed session {}", id);
+        Ok(())
+    }
+
+    /// Ids of the sessions made with [`BrowserServer::create_session`]
+    pub fn session_ids(&self) -> Vec<String> {
+        lock(&self.sessions).keys().cloned().collect()
+    }
+
+    /// Set how many tool calls may wait behind the running one before new calls are rejected as busy
+    pub fn max_queued_calls(mut self, capacity: usize) -> Self {
+        self.queue = Arc::new(CallQueue::new(capacity));
+        self
+    }
+
+    /// Capture the active tab whenever a tool call fails, so error reports show what the page looked like
+    ///
+    /// With [`ErrorScreenshot::Save`] the PNG's path is added to the error data
+    /// as `screenshot`; with [`ErrorScreenshot::Inline`] the failure is returned
+    /// as a tool result flagged `isError` that carries the image, with the error
+    /// data as structured content. Calls that fail before the browser runs get
+    /// no screenshot.
+    pub fn screenshot_on_error(mut self, mode: ErrorScreenshot) -> Self {
+        self.error_screenshot = Some(mode);
+        self
+    }
+
+    /// Restrict the server to tools that do not change the page or session state
+    ///
+    /// Interaction, script evaluation and state-restoring tools are removed from
+    /// the router, so they are neither listed to clients nor callable. Plugin
+    /// tools not annotated as read-only are dropped, whether added before or
+    /// after, and calls that would make a kept tool write a file are refused.
+    pub fn read_only(mut self) -> Self {
+        let names: Vec<String> = self
+            .tool_router
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+        for name in names {
+            if !READ_ONLY_TOOLS.contains(&name.as_str()) {
+                self.tool_router.remove_route(&name);
+            }
+        }
+        self.custom_tools
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|name, tool| {
+                let keep = tool.annotations().read_only;
+                if !keep {
+                    warn!("Read-only mode: removing tool '{}'", name);
+                }
+                keep
+            });
+        self.read_only = true;
+        self
+    }
+
+    /// Add tools loaded from plugins, e.g. with [`crate::tools::plugins::load_plugins`]
+    ///
+    /// Tools named like a built-in tool, even one removed by read-only mode,
+    /// are skipped with a warning, as are tools not annotated as read-only on
+    /// a read-only server. Plugin tools are listed next to the built-in ones
+    /// and survive browser restarts.
+    pub fn plugin_tools(self, tools: &[Arc<dyn DynTool>]) -> Self {
+        for tool in tools {
+            if self.is_builtin_name(tool.name()) {
+                warn!(
+                    "Skipping plugin tool '{}': a built-in tool has that name",
+                    tool.name()
+                );
+                continue;
+            }
+            if self.read_only && !tool.annotations().read_only {
+                warn!(
+                    "Read-only mode: skipping plugin tool '{}', which is not read-only",
+                    tool.name()
+                );
+                continue;
+            }
+            self.add_custom_tool(Arc::clone(tool));
+        }
+        self
     }
 
-    /// Get a reference to the browser session (blocking lock)
-    pub(crate) fn session(&self) -> std::sync::MutexGuard<'_, BrowserSession> {
-        self.session.lock().expect("Failed to lock browser session")
+    /// Add the tools of a [`ToolPack`] published by another crate
+    ///
+    /// Same as [`BrowserServer::plugin_tools`] with the pack's tools, after
+    /// checking the pack's plugin API version.
+    pub fn tool_pack(self, pack: &dyn ToolPack) -> Result<Self, String> {
+        check_pack(pack).map_err(|e| e.to_string())?;
+        Ok(self.plugin_tools(&pack.tools()))
+    }
+
+    /// Router with the default tools plus those of enabled optional features
+    pub(crate) fn all_tool_routes() -> ToolRouter<Self> {
+        let mut router = Self::tool_router();
+        describe_routes(&mut router, Self::tool_attributes());
+        router.merge(Self::lifecycle_tool_router());
+        router.merge(Self::custom_tool_router());
+        #[cfg(feature = "ocr")]
+        {
+            router.merge(Self::ocr_tool_router());
+            describe_routes(&mut router, Self::ocr_tool_attributes());
+        }
+        #[cfg(feature = "pdf")]
+        {
+            router.merge(Self::pdf_tool_router());
+            describe_routes(&mut router, Self::pdf_tool_attributes());
+        }
+        router
+    }
+
+    /// Whether `name` is a built-in tool this server offers, i.e. not removed by read-only mode
+    pub(crate) fn has_builtin_tool(&self, name: &str) -> bool {
+        self.tool_router.has_route(name)
+    }
+
+    /// Whether `name` belongs to a built-in tool, offered or not, so no other tool may take it
+    pub(crate) fn is_builtin_name(&self, name: &str) -> bool {
+        self.builtin_tools.contains(name)
+    }
+
+    /// Refuse a call that read-only mode does not allow, e.g. a screenshot saved to a file
+    pub(crate) fn check_read_only_call(
+        &self,
+        tool: &str,
+        arguments: Option<&JsonObject>,
+    ) -> Result<(), McpError> {
+        if !self.read_only {
+            return Ok(());
+        }
+        for (name, param) in READ_ONLY_REFUSED_PARAMS {
+            if *name == tool
+                && arguments
+                    .and_then(|arguments| arguments.get(*param))
+                    .is_some_and(|value| !value.is_null())
+            {
+                return Err(McpError::invalid_params(
+                    format!("Read-only mode: {} cannot be called with '{}'", tool, param),
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the launch options allow running arbitrary page scripts, as custom tools do
+    pub(crate) fn scripts_allowed(&self) -> bool {
+        self.slot().options.evaluate_policy.access == EvaluateAccess::Unrestricted
+    }
+
+    /// Tool added with `browser_define_tool` or from a plugin, if any
+    pub(crate) fn custom_tool(&self, name: &str) -> Option<Arc<dyn DynTool>> {
+        self.custom_tools
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    /// Add or replace a tool defined at runtime; returns whether one was replaced
+    ///
+    /// Definitions belong to the server, so they survive browser restarts and idle relaunches.
+    pub(crate) fn add_custom_tool(&self, tool: Arc<dyn DynTool>) -> bool {
+        self.custom_tools
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(tool.name().to_string(), tool)
+            .is_some()
+    }
+
+    /// Result of a tool call on `session`, with a screenshot attached if it failed and that is enabled
+    pub(crate) fn finish_call(
+        &self,
+        session: &mut SessionGuard<'_>,
+        tool: &str,
+        result: Result<CallToolResult, McpError>,
+    ) -> Result<CallToolResult, McpError> {
+        if let Err(error) = &result {
+            session.note_error(error);
+        }
+        match (result, &self.error_screenshot) {
+            (Err(error), Some(mode)) => mode.attach(session, tool, error),
+            (result, _) => result,
+        }
+    }
+
+    /// Whether every tool the prompt's instructions call is offered, e.g. not removed by read-only mode
+    fn prompt_available(&self, prompt: &WorkflowPrompt) -> bool {
+        prompt.tools.iter().all(|tool| self.has_builtin_tool(tool))
+    }
+
+    /// Get the queue that serializes tool calls on this session
+    pub(crate) fn queue(&self) -> &CallQueue {
+        &self.queue
+    }
+
+    fn default_entry(&self) -> SessionEntry {
+        SessionEntry {
+            slot: Arc::clone(&self.session),
+            queue: Arc::clone(&self.queue),
+        }
+    }
+
+    /// Session a tool call names with `session_id`, or the default session
+    pub(crate) fn session_entry(&self, id: Option<&str>) -> Result<SessionEntry, String> {
+        match id {
+            None | Some(DEFAULT_SESSION_ID) => Ok(self.default_entry()),
+            Some(id) => lock(&self.sessions)
+                .get(id)
+                .cloned()
+                .ok_or_else(|| format!("No session '{}'", id)),
+        }
+    }
+
+    /// Page `page` of the active tab's indexed elements, queued like a tool call
+    fn read_elements(&self, page: usize) -> Result<ElementPage, McpError> {
+        let _turn = self
+            .queue()
+            .enter()
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let session = self
+            .session()
+            .map_err(|e| McpError::internal_error(e, None))?;
+        session
+            .check_tool_scope("snapshot")
+            .map_err(|e| McpError::invalid_request(e.to_string(), None))?;
+        let mut context = ToolContext::new(&session);
+        let dom = context
+            .get_dom()
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        element_page(dom, page, ELEMENTS_PAGE_SIZE).map_err(|e| McpError::invalid_params(e, None))
+    }
+
+    fn slot(&self) -> MutexGuard<'_, SessionSlot> {
+        lock(&self.session)
+    }
+
+    /// Get the browser session, relaunching it after an idle shutdown (blocking lock)
+    pub(crate) fn session(&self) -> Result<SessionGuard<'_>, String> {
+        let mut slot = self.slot();
+        slot.ensure_launched()?;
+        Ok(SessionGuard(slot))
     }
 }
 
@@ -61,13 +865,164 @@ impl Drop for BrowserServer {
     }
 }
 
-#[tool_handler]
 impl ServerHandler for BrowserServer {
     fn get_info(&self) -> ServerInfo {
+        let capabilities = if self.events.is_some() {
+            ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .enable_resources()
+                .enable_prompts()
+                .enable_logging()
+                .build()
+        } else {
+            ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .enable_resources()
+                .enable_prompts()
+                .build()
+        };
         ServerInfo {
             instructions: Some("Browser-use MCP Server".into()),
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities,
             ..Default::default()
         }
     }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        self.check_read_only_call(&request.name, request.arguments.as_ref())?;
+        if !self.is_builtin_name(&request.name)
+            && let Some(tool) = self.custom_tool(&request.name)
+        {
+            return self.call_custom_tool(tool.as_ref(), request.arguments);
+        }
+        let context = ToolCallContext::new(self, request, context);
+        self.tool_router.call(context).await
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let mut tools = self.tool_router.list_all();
+        let custom_tools = self.custom_tools.read().unwrap_or_else(|e| e.into_inner());
+        tools.extend(
+            custom_tools
+                .values()
+                .filter(|tool| !self.is_builtin_name(tool.name()))
+                .map(|tool| custom_tool_info(tool.as_ref())),
+        );
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        let prompts = PROMPTS
+            .iter()
+            .filter(|prompt| self.prompt_available(prompt))
+            .map(WorkflowPrompt::info)
+            .collect();
+        Ok(ListPromptsResult::with_all_items(prompts))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let prompt = find_prompt(&request.name)
+            .filter(|prompt| self.prompt_available(prompt))
+            .ok_or_else(|| {
+                McpError::invalid_params(format!("Unknown prompt '{}'", request.name), None)
+            })?;
+        prompt
+            .render(request.arguments.as_ref())
+            .map_err(|e| McpError::invalid_params(e, None))
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let mut resource = RawResource::new(ELEMENTS_URI, "elements");
+        resource.description = Some(format!(
+            "Indexed elements of the active tab, {} per page; follow `next` for the rest",
+            ELEMENTS_PAGE_SIZE
+        ));
+        resource.mime_type = Some("application/json".to_string());
+        Ok(ListResourcesResult::with_all_items(vec![
+            resource.no_annotation(),
+        ]))
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        let template = RawResourceTemplate {
+            uri_template: ELEMENTS_URI_TEMPLATE.to_string(),
+            name: "elements".to_string(),
+            title: None,
+            description: Some("One page of the active tab's indexed elements".to_string()),
+            mime_type: Some("application/json".to_string()),
+        };
+        Ok(ListResourceTemplatesResult::with_all_items(vec![
+            template.no_annotation(),
+        ]))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let page = match parse_elements_uri(&request.uri) {
+            Some(page) => page.map_err(|e| McpError::invalid_params(e, None))?,
+            None => {
+                return Err(McpError::resource_not_found(
+                    format!("Unknown resource '{}'", request.uri),
+                    None,
+                ));
+            }
+        };
+        let elements = self.read_elements(page)?;
+        let text = serde_json::to_string(&elements)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: request.uri,
+                mime_type: Some("application/json".to_string()),
+                text,
+                meta: None,
+            }],
+        })
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        if let Some(events) = &self.events {
+            events.subscribe(context.peer);
+        }
+    }
+
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        if let Some(events) = &self.events {
+            events.set_level(request.level);
+        }
+        Ok(())
+    }
 }
@@ -2,16 +2,88 @@
 //!
 //! This module provides rmcp-compatible tools by wrapping the existing tool implementations.
 
+mod error_screenshot;
+pub(crate) mod events;
 pub mod handler;
-pub use handler::BrowserServer;
+mod prompts;
+pub mod queue;
+mod resources;
+pub use error_screenshot::ErrorScreenshot;
+use handler::SessionStatus;
+pub use handler::{BrowserServer, DEFAULT_MAX_SESSIONS, DEFAULT_SESSION_ID, ShutdownHandle};
 
-use crate::tools::{self, Tool, ToolContext, ToolResult as InternalToolResult};
+use crate::error::BrowserError;
+use crate::tools::{
+    self, CustomTool, CustomToolDefinition, DynTool, Tool, ToolContext,
+    ToolResult as InternalToolResult,
+};
 use rmcp::{
-    ErrorData as McpError,
+    ErrorData as McpError, Peer, RoleServer,
+    handler::server::tool::ToolRouter,
     handler::server::wrapper::Parameters,
-    model::{CallToolResult, Content},
+    model::{CallToolResult, Content, JsonObject, ToolAnnotations},
     tool, tool_router,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// MCP tools that only observe the page and are kept by [`BrowserServer::read_only`]
+///
+/// Navigation and tab switching are allowed so that research and monitoring
+/// agents can move around; anything that clicks, types, runs scripts, restores
+/// session state, writes files, opens sessions or closes tabs and sessions is
+/// not. Every entry must be annotated as non-destructive; tools that can also
+/// write a file are only called without the parameters in
+/// [`READ_ONLY_REFUSED_PARAMS`].
+pub const READ_ONLY_TOOLS: &[&str] = &[
+    "browser_navigate",
+    "browser_go_back",
+    "browser_go_forward",
+    "browser_wait",
+    "browser_scroll",
+    "browser_scroll_to_load",
+    "browser_get_markdown",
+    "browser_get_html",
+    "browser_get_text",
+    "browser_snapshot",
+    "browser_screenshot",
+    "browser_extract_images",
+    "browser_get_tab_order",
+    "browser_audit_accessibility",
+    "browser_read_pdf",
+    "browser_parse_file",
+    "browser_get_graphql_requests",
+    "browser_discover_api_endpoints",
+    "browser_ocr_screenshot",
+    "browser_crawl",
+    "browser_check_fingerprint",
+    "browser_checkpoint",
+    "browser_new_tab",
+    "browser_new_context",
+    "browser_tab_list",
+    "browser_switch_tab",
+    "browser_get_frame_tree",
+    "browser_set_scope",
+    "browser_launch",
+    "browser_status",
+];
+
+/// Parameters that make a [`READ_ONLY_TOOLS`] entry write a file, refused in read-only mode
+pub const READ_ONLY_REFUSED_PARAMS: &[(&str, &str)] = &[("browser_screenshot", "path")];
+
+/// Parameters of a tool plus the session to run it in
+///
+/// Every tool accepts `session_id` next to its own parameters.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SessionParams<P> {
+    /// Session to run the tool in, from browser_session_create (default: the default session)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+
+    #[serde(flatten)]
+    pub params: P,
+}
 
 /// Convert internal ToolResult to MCP CallToolResult
 fn convert_result(result: InternalToolResult) -> Result<CallToolResult, McpError> {
@@ -28,56 +100,766 @@ fn convert_result(result: InternalToolResult) -> Result<CallToolResult, McpError
     }
 }
 
+/// Convert a failed tool call to an MCP error
+///
+/// The error's code, retryability and context go in the error data so that
+/// clients can branch on them without parsing the message.
+fn tool_error(error: BrowserError) -> McpError {
+    let mut data = serde_json::json!({
+        "code": error.code(),
+        "retryable": error.retryable(),
+    });
+    if let Some(context) = error.context()
+        && let (Some(data), Ok(serde_json::Value::Object(context))) =
+            (data.as_object_mut(), serde_json::to_value(context))
+    {
+        data.extend(context);
+    }
+    McpError::internal_error(error.to_string(), Some(data))
+}
+
 /// Macro to register MCP tools by automatically generating wrapper functions
+///
+/// Tools behind optional features are registered in a separately named router
+/// (`router = name, attributes = name;`) that the handler merges when the
+/// feature is enabled. The attributes function lists each tool's
+/// [`Tool::description`] and [`Tool::annotations`] for the router's listing.
 macro_rules! register_mcp_tools {
-    ($($mcp_name:ident => $tool_type:ty, $description:expr);* $(;)?) => {
-        #[tool_router]
+    (
+        router = $router:ident, attributes = $attributes:ident;
+        $($mcp_name:ident => $tool_type:ty);* $(;)?
+    ) => {
+        #[tool_router(router = $router)]
         impl BrowserServer {
             $(
-                #[tool(description = $description)]
+                #[tool]
                 fn $mcp_name(
                     &self,
-                    params: Parameters<<$tool_type as Tool>::Params>,
+                    params: Parameters<SessionParams<<$tool_type as Tool>::Params>>,
                 ) -> Result<CallToolResult, McpError> {
-                    let session = self.session();
-                    let mut context = ToolContext::new(&*session);
-                    let tool = <$tool_type>::default();
-                    let result = tool.execute_typed(params.0, &mut context)
+                    let SessionParams { session_id, params } = params.0;
+                    let entry = self.session_entry(session_id.as_deref())
+                        .map_err(|e| McpError::invalid_params(e, None))?;
+                    let _turn = entry.queue().enter()
                         .map_err(|e| McpError::internal_error(e.to_string(), None))?;
-                    convert_result(result)
+                    let mut session = entry.session()
+                        .map_err(|e| McpError::internal_error(e, None))?;
+                    let tool = <$tool_type>::default();
+                    let mut context = ToolContext::new(&*session);
+                    let result = session.check_tool_call(Tool::name(&tool))
+                        .and_then(|()| tool.execute_typed(params, &mut context))
+                        .map_err(|e| tool_error(e.with_tool(stringify!($mcp_name))))
+                        .and_then(convert_result);
+                    self.finish_call(&mut session, stringify!($mcp_name), result)
                 }
             )*
         }
+
+        impl BrowserServer {
+            fn $attributes() -> Vec<(&'static str, String, tools::ToolAnnotations)> {
+                vec![$({
+                    let tool = <$tool_type>::default();
+                    (stringify!($mcp_name), Tool::description(&tool).to_string(), Tool::annotations(&tool))
+                },)*]
+            }
+        }
+    };
+    ($($mcp_name:ident => $tool_type:ty);* $(;)?) => {
+        register_mcp_tools! {
+            router = tool_router, attributes = tool_attributes;
+            $($mcp_name => $tool_type);*
+        }
     };
 }
 
+/// MCP form of a tool's annotations
+fn mcp_annotations(annotations: tools::ToolAnnotations) -> ToolAnnotations {
+    ToolAnnotations::new()
+        .read_only(annotations.read_only)
+        .destructive(annotations.destructive)
+        .idempotent(annotations.idempotent)
+}
+
+/// Show the descriptions and annotations of registered tools in the router's listing
+fn describe_routes(
+    router: &mut ToolRouter<BrowserServer>,
+    attributes: Vec<(&'static str, String, tools::ToolAnnotations)>,
+) {
+    for (name, description, annotations) in attributes {
+        if let Some(route) = router.map.get_mut(name) {
+            route.attr.description = Some(description.into());
+            route.attr.annotations = Some(mcp_annotations(annotations));
+        }
+    }
+}
+
 // Register all MCP tools using the macro
 register_mcp_tools! {
     // ---- Navigation and Browser Flow ----
-    browser_navigate => tools::navigate::NavigateTool, "Navigate to a specified URL in the browser";
-    browser_go_back => tools::go_back::GoBackTool, "Navigate back in browser history";
-    browser_go_forward => tools::go_forward::GoForwardTool, "Navigate forward in browser history";
-    browser_close => tools::close::CloseTool, "Close the browser when the task is complete";
+    browser_navigate => tools::navigate::NavigateTool;
+    browser_go_back => tools::go_back::GoBackTool;
+    browser_go_forward => tools::go_forward::GoForwardTool;
+    browser_close => tools::close::CloseTool;
 
     // ---- Page Content and Extraction ----
-    browser_get_markdown => tools::markdown::GetMarkdownTool, "Get the markdown content of the current page (use this tool only for information extraction; for interaction use the snapshot tool instead)";
-    browser_snapshot => tools::snapshot::SnapshotTool, "Get a snapshot of the current page with indexed interactive elements for interaction";
-    browser_screenshot => tools::screenshot::ScreenshotTool, "Capture a screenshot of the current page";
+    browser_get_markdown => tools::markdown::GetMarkdownTool;
+    browser_get_html => tools::get_html::GetHtmlTool;
+    browser_get_text => tools::get_text::GetTextTool;
+    browser_snapshot => tools::snapshot::SnapshotTool;
+    browser_extract_images => tools::extract_images::ExtractImagesTool;
+    browser_get_tab_order => tools::get_tab_order::GetTabOrderTool;
+    browser_audit_accessibility => tools::audit_accessibility::AuditAccessibilityTool;
+    browser_download_url => tools::download_url::DownloadUrlTool;
+    browser_parse_file => tools::parse_file::ParseFileTool;
+    browser_get_graphql_requests => tools::get_graphql_requests::GetGraphqlRequestsTool;
+    browser_discover_api_endpoints => tools::discover_api_endpoints::DiscoverApiEndpointsTool;
+    browser_screenshot => tools::screenshot::ScreenshotTool;
+    browser_capture_canvas => tools::capture_canvas::CaptureCanvasTool;
+    browser_compare_screenshot => tools::compare_screenshot::CompareScreenshotTool;
+    browser_screenshot_urls => tools::screenshot_urls::ScreenshotUrlsTool;
     // browser_get_text => tools::extract::ExtractContentTool, "Extract text or HTML content from the page or an element";
-    browser_evaluate => tools::evaluate::EvaluateTool, "Execute JavaScript code in the browser context";
+    browser_crawl => tools::crawl::CrawlTool;
+    browser_extract_table_all_pages => tools::extract_table_all_pages::ExtractTableAllPagesTool;
+    browser_evaluate => tools::evaluate::EvaluateTool;
+    browser_check_fingerprint => tools::check_fingerprint::CheckFingerprintTool;
 
     // ---- Interaction ----
-    browser_click => tools::click::ClickTool, "Click on an element specified by CSS selector or index (index obtained from browser_snapshot tool)";
-    browser_hover => tools::hover::HoverTool, "Hover over an element specified by CSS selector or index (index obtained from browser_snapshot tool)";
-    browser_select => tools::select::SelectTool, "Select an option in a dropdown element by CSS selector or index (index obtained from browser_snapshot tool)";
-    browser_input_fill => tools::input::InputTool, "Type text into an input element specified by CSS selector or index (index obtained from browser_snapshot tool)";
-    browser_press_key => tools::press_key::PressKeyTool, "Press a key on the keyboard";
-    browser_scroll => tools::scroll::ScrollTool, "Scroll the page by a specified amount or to the bottom";
-    browser_wait => tools::wait::WaitTool, "Wait for an element to appear on the page";
+    browser_click => tools::click::ClickTool;
+    browser_hover => tools::hover::HoverTool;
+    browser_select => tools::select::SelectTool;
+    browser_set_date => tools::set_date::SetDateTool;
+    browser_input_fill => tools::input::InputTool;
+    browser_press_key => tools::press_key::PressKeyTool;
+    browser_key_sequence => tools::key_sequence::KeySequenceTool;
+    browser_go_next_page => tools::go_next_page::GoNextPageTool;
+    browser_dismiss_overlays => tools::dismiss_overlays::DismissOverlaysTool;
+    browser_scroll => tools::scroll::ScrollTool;
+    browser_scroll_to_load => tools::scroll_to_load::ScrollToLoadTool;
+    browser_media_control => tools::media_control::MediaControlTool;
+    browser_wait => tools::wait::WaitTool;
 
     // ---- Tab Management ----
-    browser_new_tab => tools::new_tab::NewTabTool, "Open a new tab and navigate to the specified URL";
-    browser_tab_list => tools::tab_list::TabListTool, "Get the list of all browser tabs with their titles and URLs";
-    browser_switch_tab => tools::switch_tab::SwitchTabTool, "Switch to a specific tab by index";
-    browser_close_tab => tools::close_tab::CloseTabTool, "Close the current active tab";
+    browser_new_tab => tools::new_tab::NewTabTool;
+    browser_new_context => tools::new_context::NewContextTool;
+    browser_tab_list => tools::tab_list::TabListTool;
+    browser_switch_tab => tools::switch_tab::SwitchTabTool;
+    browser_close_tab => tools::close_tab::CloseTabTool;
+    browser_get_frame_tree => tools::get_frame_tree::GetFrameTreeTool;
+
+    // ---- Session State ----
+    browser_save_storage_state => tools::save_storage_state::SaveStorageStateTool;
+    browser_load_storage_state => tools::load_storage_state::LoadStorageStateTool;
+    browser_checkpoint => tools::checkpoint::CheckpointTool;
+    browser_rollback => tools::rollback::RollbackTool;
+    browser_set_scope => tools::set_scope::SetScopeTool;
+}
+
+/// Parameters for the browser_restart tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RestartBrowserParams {
+    /// Carry cookies and localStorage over to the new browser (default: false)
+    #[serde(default)]
+    pub preserve_state: bool,
+
+    /// Session whose browser to restart, from browser_session_create (default: the default session)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Parameters for the browser_status tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BrowserStatusParams {
+    /// Session to report on, from browser_session_create (default: the default session)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+/// Parameters for the browser_session_close tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CloseSessionParams {
+    /// Id returned by browser_session_create
+    pub session_id: String,
+}
+
+// Tools that manage the server's browser rather than a page
+#[tool_router(router = lifecycle_tool_router)]
+impl BrowserServer {
+    #[tool(
+        description = "Start the browser now; it otherwise starts on the first tool call that needs it",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = true
+        )
+    )]
+    fn browser_launch(&self) -> Result<CallToolResult, McpError> {
+        let _turn = self
+            .queue()
+            .enter()
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let was_running = self.is_browser_running();
+        self.launch()
+            .map_err(|e| McpError::internal_error(e, None))?;
+        convert_result(InternalToolResult::success_with(serde_json::json!({
+            "launched": !was_running
+        })))
+    }
+
+    #[tool(
+        description = "Close the browser and start a fresh one with the same options, optionally keeping cookies and localStorage. Use when pages stop responding",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = false
+        )
+    )]
+    fn browser_restart(
+        &self,
+        params: Parameters<RestartBrowserParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let entry = self
+            .session_entry(params.0.session_id.as_deref())
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let _turn = entry
+            .queue()
+            .enter()
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        entry
+            .restart(params.0.preserve_state)
+            .map_err(|e| McpError::internal_error(e, None))?;
+        convert_result(InternalToolResult::success_with(serde_json::json!({
+            "restarted": true,
+            "preserved_state": params.0.preserve_state
+        })))
+    }
+
+    #[tool(
+        description = "Report whether the browser is alive: Chrome version, open targets, crashed tabs, uptime and websocket state. Does not start the browser or wait for a running tool call; use it to tell a dead or hung browser from a slow page before retrying",
+        annotations(
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true
+        )
+    )]
+    fn browser_status(
+        &self,
+        params: Parameters<BrowserStatusParams>,
+    ) -> Result<CallToolResult, McpError> {
+        let session_id = params.0.session_id.as_deref();
+        let entry = self
+            .session_entry(session_id)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let session_id = session_id.unwrap_or(DEFAULT_SESSION_ID);
+        let status = match entry.status() {
+            SessionStatus::NotRunning => serde_json::json!({
+                "session_id": session_id,
+                "state": "not_running",
+                "alive": false
+            }),
+            SessionStatus::Busy => serde_json::json!({
+                "session_id": session_id,
+                "state": "busy",
+                "message": "A tool call is running in this session; if it does not finish, call browser_restart"
+            }),
+            SessionStatus::Running(info) => {
+                let mut status = serde_json::json!({
+                    "session_id": session_id,
+                    "state": "running"
+                });
+                if let (Some(status), Ok(serde_json::Value::Object(info))) =
+                    (status.as_object_mut(), serde_json::to_value(&info))
+                {
+                    status.extend(info);
+                }
+                status
+            }
+        };
+        convert_result(InternalToolResult::success_with(status))
+    }
+
+    #[tool(
+        description = "Open a separate browser session with its own cookies, storage and tabs, and return its session_id. Pass session_id to other tools to act in it; calls on different sessions run concurrently",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false
+        )
+    )]
+    fn browser_session_create(&self) -> Result<CallToolResult, McpError> {
+        let session_id = self
+            .create_session()
+            .map_err(|e| McpError::internal_error(e, None))?;
+        convert_result(InternalToolResult::success_with(serde_json::json!({
+            "session_id": session_id
+        })))
+    }
+
+    #[tool(
+        description = "Close a session opened with browser_session_create and its browser",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = false
+        )
+    )]
+    fn browser_session_close(
+        &self,
+        params: Parameters<CloseSessionParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.close_session(&params.0.session_id)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        convert_result(InternalToolResult::success_with(serde_json::json!({
+            "closed": params.0.session_id
+        })))
+    }
+}
+
+/// MCP description of a tool added at runtime or from a plugin
+pub(crate) fn custom_tool_info(tool: &dyn DynTool) -> rmcp::model::Tool {
+    let schema = match tool.parameters_schema() {
+        serde_json::Value::Object(schema) => schema,
+        _ => JsonObject::new(),
+    };
+    rmcp::model::Tool::new(
+        tool.name().to_string(),
+        tool.description().to_string(),
+        Arc::new(with_session_id(schema)),
+    )
+    .annotate(mcp_annotations(tool.annotations()))
+}
+
+/// Add the `session_id` every tool accepts to a parameters schema
+fn with_session_id(mut schema: JsonObject) -> JsonObject {
+    let properties = schema
+        .entry("properties")
+        .or_insert_with(|| serde_json::json!({}));
+    if let Some(properties) = properties.as_object_mut() {
+        properties.entry("session_id").or_insert_with(|| {
+            serde_json::json!({
+                "type": "string",
+                "description": "Session to run the tool in, from browser_session_create (default: the default session)"
+            })
+        });
+    }
+    schema
+}
+
+// Tools that extend the server with page scripts
+#[tool_router(router = custom_tool_router)]
+impl BrowserServer {
+    #[tool(
+        description = "Define a new tool that runs a JavaScript snippet in the page. The script is the body of an async function: use `return` for the result; arguments are in `params`, and `{{name}}` is replaced with argument `name` as JSON. The tool is then listed and called like any other; redefining a custom tool replaces it",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = true
+        )
+    )]
+    fn browser_define_tool(
+        &self,
+        params: Parameters<CustomToolDefinition>,
+        peer: Peer<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let _turn = self
+            .queue()
+            .enter()
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let define = || {
+            if !self.scripts_allowed() {
+                return Err(BrowserError::PolicyDenied(
+                    "Custom tools need unrestricted JavaScript evaluation".to_string(),
+                ));
+            }
+            let tool = CustomTool::new(params.0)?;
+            let name = tool.name();
+            if self.is_builtin_name(name) {
+                return Err(BrowserError::InvalidArgument(format!(
+                    "Tool '{}' already exists and cannot be redefined",
+                    name
+                )));
+            }
+            Ok(tool)
+        };
+        let tool = define().map_err(|e| tool_error(e.with_tool("browser_define_tool")))?;
+
+        let name = tool.name().to_string();
+        let replaced = self.add_custom_tool(Arc::new(tool));
+        tokio::spawn(async move {
+            if let Err(e) = peer.notify_tool_list_changed().await {
+                log::debug!("Failed to announce tool list change: {}", e);
+            }
+        });
+        convert_result(InternalToolResult::success_with(serde_json::json!({
+            "defined": name,
+            "replaced": replaced
+        })))
+    }
+}
+
+impl BrowserServer {
+    /// Run a tool added at runtime or from a plugin
+    pub(crate) fn call_custom_tool(
+        &self,
+        tool: &dyn DynTool,
+        mut arguments: Option<JsonObject>,
+    ) -> Result<CallToolResult, McpError> {
+        let session_id = match arguments.as_mut().and_then(|a| a.remove("session_id")) {
+            Some(serde_json::Value::String(id)) => Some(id),
+            Some(serde_json::Value::Null) | None => None,
+            Some(other) => {
+                return Err(McpError::invalid_params(
+                    format!("session_id must be a string, got {}", other),
+                    None,
+                ));
+            }
+        };
+        let entry = self
+            .session_entry(session_id.as_deref())
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        let _turn = entry
+            .queue()
+            .enter()
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let mut session = entry
+            .session()
+            .map_err(|e| McpError::internal_error(e, None))?;
+        let name = tool.name();
+        let mut context = ToolContext::new(&session);
+        let params = arguments.map_or(serde_json::Value::Null, serde_json::Value::Object);
+        let result = session
+            .check_tool_call(name)
+            .map_err(|e| e.with_tool(name))
+            .and_then(|()| tool.execute(params, &mut context))
+            .map_err(tool_error)
+            .and_then(convert_result);
+        self.finish_call(&mut session, name, result)
+    }
+}
+
+// Tools that depend on optional features
+#[cfg(feature = "ocr")]
+register_mcp_tools! {
+    router = ocr_tool_router, attributes = ocr_tool_attributes;
+    browser_ocr_screenshot => tools::ocr_screenshot::OcrScreenshotTool;
+}
+
+#[cfg(feature = "pdf")]
+register_mcp_tools! {
+    router = pdf_tool_router, attributes = pdf_tool_attributes;
+    browser_read_pdf => tools::read_pdf::ReadPdfTool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::ConnectionOptions;
+
+    #[test]
+    fn test_read_only_tools_are_registered() {
+        let router = BrowserServer::all_tool_routes();
+        for name in READ_ONLY_TOOLS {
+            if *name == "browser_ocr_screenshot" && !cfg!(feature = "ocr") {
+                continue;
+            }
+            if *name == "browser_read_pdf" && !cfg!(feature = "pdf") {
+                continue;
+            }
+            assert!(router.has_route(name), "{} is not a registered tool", name);
+        }
+    }
+
+    #[test]
+    fn test_read_only_excludes_mutating_tools() {
+        for name in [
+            "browser_click",
+            "browser_input_fill",
+            "browser_select",
+            "browser_evaluate",
+            "browser_load_storage_state",
+            "browser_rollback",
+            "browser_download_url",
+            "browser_close",
+            "browser_session_create",
+        ] {
+            assert!(!READ_ONLY_TOOLS.contains(&name));
+        }
+    }
+
+    #[test]
+    fn test_read_only_tools_are_not_destructive() {
+        let tools = BrowserServer::all_tool_routes().list_all();
+        for tool in tools
+            .iter()
+            .filter(|tool| READ_ONLY_TOOLS.contains(&tool.name.as_ref()))
+        {
+            let annotations = tool.annotations.clone().unwrap_or_default();
+            assert!(
+                !annotations.is_destructive(),
+                "{} is destructive",
+                tool.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_tools_are_annotated() {
+        let tools = BrowserServer::all_tool_routes().list_all();
+        for tool in &tools {
+            assert!(
+                tool.annotations.is_some(),
+                "{} has no annotations",
+                tool.name
+            );
+        }
+        let annotations = |name: &str| {
+            tools
+                .iter()
+                .find(|tool| tool.name == name)
+                .and_then(|tool| tool.annotations.clone())
+                .unwrap()
+        };
+
+        let snapshot = annotations("browser_snapshot");
+        assert_eq!(snapshot.read_only_hint, Some(true));
+        let navigate = annotations("browser_navigate");
+        assert_eq!(navigate.read_only_hint, Some(false));
+        assert_eq!(navigate.destructive_hint, Some(false));
+        assert_eq!(navigate.idempotent_hint, Some(true));
+        for name in ["browser_click", "browser_evaluate", "browser_close_tab"] {
+            assert!(annotations(name).is_destructive(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_tools_are_described() {
+        for tool in BrowserServer::all_tool_routes().list_all() {
+            assert!(
+                tool.description.as_deref().is_some_and(|d| !d.is_empty()),
+                "{} has no description",
+                tool.name
+            );
+        }
+        let tools = BrowserServer::all_tool_routes();
+        let navigate = tools.map.get("browser_navigate").unwrap();
+        assert_eq!(
+            navigate.attr.description.as_deref(),
+            Some(Tool::description(&tools::navigate::NavigateTool))
+        );
+    }
+
+    #[test]
+    fn test_tool_error_data() {
+        let error = tool_error(
+            BrowserError::ElementNotFound("Element '#buy' not found".to_string())
+                .with_selector("#buy")
+                .with_tool("browser_click"),
+        );
+        assert_eq!(error.message, "Element not found: Element '#buy' not found");
+        assert_eq!(
+            error.data,
+            Some(serde_json::json!({
+                "code": "ELEMENT_NOT_FOUND",
+                "retryable": true,
+                "tool": "browser_click",
+                "selector": "#buy",
+            }))
+        );
+    }
+
+    #[test]
+    fn test_custom_tools_are_listed_by_name() {
+        let server = BrowserServer::new().unwrap();
+        let tool = CustomTool::new(CustomToolDefinition {
+            name: "cart_total".to_string(),
+            description: "Read the cart total".to_string(),
+            parameters: None,
+            script: "return document.querySelector('.total').textContent;".to_string(),
+        })
+        .unwrap();
+
+        let tool: Arc<dyn DynTool> = Arc::new(tool);
+        assert!(!server.add_custom_tool(Arc::clone(&tool)));
+        assert!(server.add_custom_tool(tool));
+        let info = custom_tool_info(server.custom_tool("cart_total").unwrap().as_ref());
+        assert_eq!(info.name, "cart_total");
+        assert_eq!(info.input_schema.get("type").unwrap(), "object");
+        assert!(info.input_schema["properties"].get("session_id").is_some());
+        assert!(server.has_builtin_tool("browser_define_tool"));
+        assert!(!server.read_only().has_builtin_tool("browser_define_tool"));
+    }
+
+    /// Plugin-style tool with a fixed name and annotations
+    struct StubTool(&'static str, tools::ToolAnnotations);
+
+    impl DynTool for StubTool {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn annotations(&self) -> tools::ToolAnnotations {
+            self.1
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        fn execute(
+            &self,
+            _params: serde_json::Value,
+            _context: &mut ToolContext,
+        ) -> crate::error::Result<InternalToolResult> {
+            Ok(InternalToolResult::success_with(serde_json::json!({})))
+        }
+    }
+
+    #[test]
+    fn test_read_only_applies_to_plugin_tools() {
+        let plugins: Vec<Arc<dyn DynTool>> = vec![
+            Arc::new(StubTool("page_stats", tools::ToolAnnotations::read_only())),
+            Arc::new(StubTool("submit_order", tools::ToolAnnotations::default())),
+            Arc::new(StubTool(
+                "browser_click",
+                tools::ToolAnnotations::read_only(),
+            )),
+        ];
+
+        // Added after read-only mode: mutating tools and built-in names are skipped
+        let server = BrowserServer::new()
+            .unwrap()
+            .read_only()
+            .plugin_tools(&plugins);
+        assert!(server.custom_tool("page_stats").is_some());
+        assert!(server.custom_tool("submit_order").is_none());
+        assert!(server.custom_tool("browser_click").is_none());
+        assert!(!server.has_builtin_tool("browser_click"));
+        assert!(server.is_builtin_name("browser_click"));
+
+        // Added before read-only mode: mutating tools are removed
+        let server = BrowserServer::new()
+            .unwrap()
+            .plugin_tools(&plugins)
+            .read_only();
+        assert!(server.custom_tool("page_stats").is_some());
+        assert!(server.custom_tool("submit_order").is_none());
+    }
+
+    #[test]
+    fn test_read_only_screenshots_stay_inline() {
+        let path = serde_json::json!({ "path": "page.png" });
+        let path = path.as_object();
+        let inline = serde_json::json!({ "path": null, "full_page": true });
+        let inline = inline.as_object();
+
+        let server = BrowserServer::new().unwrap();
+        assert!(
+            server
+                .check_read_only_call("browser_screenshot", path)
+                .is_ok()
+        );
+
+        let server = server.read_only();
+        assert!(server.has_builtin_tool("browser_screenshot"));
+        assert!(
+            server
+                .check_read_only_call("browser_screenshot", None)
+                .is_ok()
+        );
+        assert!(
+            server
+                .check_read_only_call("browser_screenshot", inline)
+                .is_ok()
+        );
+        assert!(
+            server
+                .check_read_only_call("browser_screenshot", path)
+                .is_err()
+        );
+        assert!(
+            server
+                .check_read_only_call("browser_snapshot", path)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_session_params() {
+        let params: SessionParams<tools::NavigateParams> = serde_json::from_value(
+            serde_json::json!({ "url": "https://example.com", "session_id": "abc" }),
+        )
+        .unwrap();
+        assert_eq!(params.session_id.as_deref(), Some("abc"));
+        assert_eq!(params.params.url, "https://example.com");
+
+        let router = BrowserServer::all_tool_routes();
+        let navigate = router
+            .list_all()
+            .into_iter()
+            .find(|tool| tool.name == "browser_navigate")
+            .unwrap();
+        let properties = navigate.input_schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("url"));
+        assert!(properties.contains_key("session_id"));
+    }
+
+    #[test]
+    fn test_session_registry_errors() {
+        let server = BrowserServer::new().unwrap();
+        assert!(server.session_entry(None).is_ok());
+        assert!(server.session_entry(Some(DEFAULT_SESSION_ID)).is_ok());
+        assert_eq!(
+            server.session_entry(Some("missing")).err().unwrap(),
+            "No session 'missing'"
+        );
+        assert!(server.close_session(DEFAULT_SESSION_ID).is_err());
+        assert!(server.close_session("missing").is_err());
+        assert!(server.session_ids().is_empty());
+
+        let error = server.max_sessions(0).create_session().unwrap_err();
+        assert_eq!(error, "At most 0 sessions may be open");
+
+        let server = BrowserServer::connect(ConnectionOptions::new("http://127.0.0.1:1")).unwrap();
+        let error = server.create_session().unwrap_err();
+        assert!(error.contains("launched browsers"), "{}", error);
+    }
+
+    #[test]
+    fn test_status_does_not_launch() {
+        let server = BrowserServer::new().unwrap();
+        let result = server
+            .browser_status(Parameters(BrowserStatusParams { session_id: None }))
+            .unwrap();
+        let status: serde_json::Value =
+            serde_json::from_str(&result.content[0].as_text().unwrap().text).unwrap();
+        assert_eq!(status["session_id"], "default");
+        assert_eq!(status["state"], "not_running");
+        assert!(!server.is_browser_running());
+
+        let error = server
+            .browser_status(Parameters(BrowserStatusParams {
+                session_id: Some("missing".to_string()),
+            }))
+            .unwrap_err();
+        assert_eq!(error.message, "No session 'missing'");
+    }
+
+    #[test]
+    fn test_connect_is_lazy() {
+        let server = BrowserServer::connect(ConnectionOptions::new("http://127.0.0.1:1")).unwrap();
+        assert!(!server.is_browser_running());
+        let error = server.launch().unwrap_err();
+        assert!(error.contains("Failed to connect"), "{}", error);
+    }
+
+    #[test]
+    fn test_stream_events_enables_logging() {
+        use rmcp::ServerHandler;
+
+        let server = BrowserServer::new().unwrap();
+        assert!(server.get_info().capabilities.logging.is_none());
+        assert!(server.get_info().capabilities.resources.is_some());
+
+        let server = server.stream_events();
+        assert!(server.get_info().capabilities.logging.is_some());
+    }
 }
@@ -0,0 +1,271 @@
+//! Common browsing workflows as MCP prompts
+//!
+//! A prompt expands to instructions that name the tools to call and in which
+//! order, so client models follow a recipe known to work instead of
+//! improvising a tool sequence. Arguments are filled into `{{name}}`
+//! placeholders of the template.
+
+use rmcp::model::{
+    GetPromptResult, JsonObject, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+};
+
+/// An argument of a workflow prompt
+pub(crate) struct WorkflowArgument {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Text used when the argument is not given; `None` makes it required
+    pub default: Option<&'static str>,
+}
+
+/// A workflow offered as an MCP prompt
+pub(crate) struct WorkflowPrompt {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub arguments: &'static [WorkflowArgument],
+    /// Tools the instructions call; the prompt is only offered when all of them are
+    pub tools: &'static [&'static str],
+    pub template: &'static str,
+}
+
+/// Prompts offered by [`BrowserServer`](crate::mcp::BrowserServer)
+pub(crate) const PROMPTS: &[WorkflowPrompt] = &[
+    WorkflowPrompt {
+        name: "extract_article",
+        description: "Read the main article of a page as clean markdown with its title, author and date",
+        arguments: &[WorkflowArgument {
+            name: "url",
+            description: "URL of the article",
+            default: None,
+        }],
+        tools: &[
+            "browser_navigate",
+            "browser_dismiss_overlays",
+            "browser_get_markdown",
+        ],
+        template: "Extract the article at {{url}}.
+
+1. Call browser_navigate with url \"{{url}}\".
+2. Call browser_dismiss_overlays to close cookie banners and sign-up dialogs covering the text.
+3. Call browser_get_markdown. If the result is cut off, call it again with the next page number until you have the whole article.
+4. Reply with the article's title, author and publication date (write \"unknown\" for any the page does not show), followed by the article body as markdown. Leave out navigation, advertisements, related-article lists and comments. Do not summarize or reword the body.",
+    },
+    WorkflowPrompt {
+        name: "fill_login",
+        description: "Sign in to a site through its login form and confirm the login worked",
+        arguments: &[
+            WorkflowArgument {
+                name: "url",
+                description: "URL of the login page",
+                default: None,
+            },
+            WorkflowArgument {
+                name: "username",
+                description: "User name or email address to sign in with",
+                default: None,
+            },
+            WorkflowArgument {
+                name: "password",
+                description: "Password to sign in with",
+                default: None,
+            },
+        ],
+        tools: &[
+            "browser_navigate",
+            "browser_dismiss_overlays",
+            "browser_snapshot",
+            "browser_input_fill",
+            "browser_click",
+            "browser_wait",
+        ],
+        template: "Sign in at {{url}} as {{username}}.
+
+1. Call browser_navigate with url \"{{url}}\", then browser_dismiss_overlays.
+2. Call browser_snapshot and find the user name or email field, the password field and the submit button. If only a user name field is shown, the password field appears after submitting it: fill the user name, click the button, call browser_snapshot again and continue.
+3. Call browser_input_fill with the user name field's index and text \"{{username}}\".
+4. Call browser_input_fill with the password field's index and text \"{{password}}\".
+5. Call browser_click with the submit button's index.
+6. Call browser_wait for an element that only signed-in users see, or call browser_snapshot, and check that the login form is gone and no error message is shown.
+7. Reply whether the sign-in succeeded and quote any error message the page showed. Never repeat the password in your reply.",
+    },
+    WorkflowPrompt {
+        name: "compare_products",
+        description: "Visit several product pages and compare the products side by side in a table",
+        arguments: &[
+            WorkflowArgument {
+                name: "urls",
+                description: "Product page URLs, separated by commas or whitespace",
+                default: None,
+            },
+            WorkflowArgument {
+                name: "criteria",
+                description: "What to compare the products on",
+                default: Some("price, rating, availability and key specifications"),
+            },
+        ],
+        tools: &[
+            "browser_navigate",
+            "browser_dismiss_overlays",
+            "browser_get_markdown",
+        ],
+        template: "Compare the products at these pages: {{urls}}
+
+For each URL, one at a time:
+1. Call browser_navigate with the URL, then browser_dismiss_overlays.
+2. Call browser_get_markdown and note the product's name and its {{criteria}}. Write \"not shown\" for anything the page does not state; do not guess.
+
+Then reply with a markdown table with one row per product and one column per criterion ({{criteria}}), plus a column with the product's URL. Below the table, say in two or three sentences which product fits best on these criteria and why.",
+    },
+];
+
+/// Prompt named `name`, if there is one
+pub(crate) fn find_prompt(name: &str) -> Option<&'static WorkflowPrompt> {
+    PROMPTS.iter().find(|prompt| prompt.name == name)
+}
+
+impl WorkflowPrompt {
+    /// MCP listing of the prompt
+    pub(crate) fn info(&self) -> Prompt {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|argument| PromptArgument {
+                name: argument.name.to_string(),
+                title: None,
+                description: Some(match argument.default {
+                    Some(default) => format!("{} (default: {})", argument.description, default),
+                    None => argument.description.to_string(),
+                }),
+                required: Some(argument.default.is_none()),
+            })
+            .collect();
+        Prompt::new(self.name, Some(self.description), Some(arguments))
+    }
+
+    /// Instructions with the arguments filled in; fails when a required argument is missing
+    pub(crate) fn render(&self, arguments: Option<&JsonObject>) -> Result<GetPromptResult, String> {
+        let mut text = self.template.to_string();
+        for argument in self.arguments {
+            let value = match arguments.and_then(|arguments| arguments.get(argument.name)) {
+                Some(serde_json::Value::String(value)) => value.trim().to_string(),
+                Some(serde_json::Value::Null) | None => String::new(),
+                Some(value) => value.to_string(),
+            };
+            let value = match (value.is_empty(), argument.default) {
+                (false, _) => value,
+                (true, Some(default)) => default.to_string(),
+                (true, None) => {
+                    return Err(format!(
+                        "Prompt '{}' needs argument '{}'",
+                        self.name, argument.name
+                    ));
+                }
+            };
+            text = text.replace(&format!("{{{{{}}}}}", argument.name), &value);
+        }
+        Ok(GetPromptResult {
+            description: Some(self.description.to_string()),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::BrowserServer;
+    use rmcp::model::PromptMessageContent;
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        template
+            .split("{{")
+            .skip(1)
+            .filter_map(|rest| rest.split_once("}}").map(|(name, _)| name))
+            .collect()
+    }
+
+    fn prompt_text(result: &GetPromptResult) -> &str {
+        match &result.messages[0].content {
+            PromptMessageContent::Text { text } => text,
+            other => panic!("Unexpected prompt content {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_prompts_use_registered_tools() {
+        let router = BrowserServer::all_tool_routes();
+        for prompt in PROMPTS {
+            for tool in prompt.tools {
+                assert!(
+                    router.has_route(tool),
+                    "{}: {} is not a tool",
+                    prompt.name,
+                    tool
+                );
+                assert!(
+                    prompt.template.contains(tool),
+                    "{}: {} is not used",
+                    prompt.name,
+                    tool
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_prompt_placeholders_are_arguments() {
+        for prompt in PROMPTS {
+            let used = placeholders(prompt.template);
+            for name in &used {
+                assert!(
+                    prompt.arguments.iter().any(|a| a.name == *name),
+                    "{}: {{{{{}}}}} is not an argument",
+                    prompt.name,
+                    name
+                );
+            }
+            for argument in prompt.arguments {
+                assert!(
+                    used.contains(&argument.name),
+                    "{}: argument {} is not used",
+                    prompt.name,
+                    argument.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_prompt() {
+        let prompt = find_prompt("compare_products").unwrap();
+        let arguments = serde_json::json!({ "urls": "https://a.test/1, https://b.test/2" });
+        let result = prompt.render(arguments.as_object()).unwrap();
+        let text = prompt_text(&result);
+        assert!(text.contains("https://a.test/1, https://b.test/2"));
+        assert!(text.contains("price, rating, availability and key specifications"));
+        assert!(!text.contains("{{"));
+
+        let arguments = serde_json::json!({ "urls": "https://a.test/1", "criteria": "weight" });
+        let result = prompt.render(arguments.as_object()).unwrap();
+        assert!(prompt_text(&result).contains("its weight"));
+
+        let error = prompt.render(None).unwrap_err();
+        assert!(error.contains("'urls'"), "{}", error);
+        assert!(find_prompt("unknown").is_none());
+    }
+
+    #[test]
+    fn test_prompt_info() {
+        let info = find_prompt("compare_products").unwrap().info();
+        let arguments = info.arguments.unwrap();
+        assert_eq!(arguments[0].name, "urls");
+        assert_eq!(arguments[0].required, Some(true));
+        assert_eq!(arguments[1].required, Some(false));
+        assert!(
+            arguments[1]
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("(default: ")
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Serialized tool-call queue
+//!
+//! Tool calls on one browser session must not interleave: two calls issuing CDP
+//! commands against the same tab at once corrupt each other's interaction
+//! state. A [`CallQueue`] admits calls strictly in arrival order, one at a
+//! time, and rejects new calls with a busy error once too many are waiting.
+
+use std::fmt;
+use std::sync::{Condvar, Mutex};
+
+/// Default number of calls allowed to wait behind the running one
+pub const DEFAULT_QUEUE_CAPACITY: usize = 16;
+
+#[derive(Debug, Default)]
+struct QueueState {
+    /// Ticket handed to the next arriving call
+    next_ticket: u64,
+    /// Ticket currently allowed to run
+    now_serving: u64,
+}
+
+impl QueueState {
+    /// Calls that hold a ticket, including the running one
+    fn pending(&self) -> u64 {
+        self.next_ticket - self.now_serving
+    }
+}
+
+/// Error returned when the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueFull {
+    /// Number of calls waiting when the new call was rejected
+    pub waiting: usize,
+}
+
+impl fmt::Display for QueueFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Browser is busy: {} tool calls are already queued for this session; retry once they finish",
+            self.waiting
+        )
+    }
+}
+
+impl std::error::Error for QueueFull {}
+
+/// FIFO admission queue with a bounded number of waiting calls
+#[derive(Debug)]
+pub struct CallQueue {
+    capacity: usize,
+    state: Mutex<QueueState>,
+    turn: Condvar,
+}
+
+/// Turn held by the running call; lets the next call in when dropped
+#[derive(Debug)]
+pub struct QueueTurn<'a> {
+    queue: &'a CallQueue,
+}
+
+impl CallQueue {
+    /// Create a queue allowing `capacity` calls to wait behind the running one
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(QueueState::default()),
+            turn: Condvar::new(),
+        }
+    }
+
+    /// Maximum number of waiting calls
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of calls waiting behind the running one
+    pub fn waiting(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.pending().saturating_sub(1) as usize
+    }
+
+    /// Block until it is this call's turn, or fail immediately if the queue is full
+    pub fn enter(&self) -> Result<QueueTurn<'_>, QueueFull> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let waiting = state.pending().saturating_sub(1) as usize;
+        if state.pending() > 0 && waiting >= self.capacity {
+            return Err(QueueFull { waiting });
+        }
+
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        while state.now_serving != ticket {
+            state = self.turn.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+
+        Ok(QueueTurn { queue: self })
+    }
+}
+
+impl Default for CallQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUEUE_CAPACITY)
+    }
+}
+
+impl Drop for QueueTurn<'_> {
+    fn drop(&mut self) {
+        let mut state = self.queue.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.now_serving += 1;
+        self.queue.turn.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_calls_run_in_arrival_order() {
+        let queue = Arc::new(CallQueue::new(8));
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let first = queue.enter().unwrap();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let queue = Arc::clone(&queue);
+                let order = Arc::clone(&order);
+                // Stagger arrivals so ticket order is deterministic
+                std::thread::sleep(Duration::from_millis(20));
+                std::thread::spawn(move || {
+                    let _turn = queue.enter().unwrap();
+                    order.lock().unwrap().push(i);
+                })
+            })
+            .collect();
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(queue.waiting(), 4);
+        drop(first);
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_full_queue_is_rejected() {
+        let queue = Arc::new(CallQueue::new(1));
+        let running = queue.enter().unwrap();
+
+        let waiter = {
+            let queue = Arc::clone(&queue);
+            std::thread::spawn(move || queue.enter().map(|_| ()))
+        };
+        std::thread::sleep(Duration::from_millis(50));
+
+        let rejected = queue.enter().unwrap_err();
+        assert_eq!(rejected.waiting, 1);
+        assert!(rejected.to_string().contains("busy"));
+
+        drop(running);
+        assert!(waiter.join().unwrap().is_ok());
+        assert!(queue.enter().is_ok());
+    }
+}
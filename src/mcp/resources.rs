@@ -0,0 +1,173 @@
+//! Indexed elements of the current page as a paginated MCP resource
+//!
+//! Large pages list hundreds of interactive elements. Clients with resource
+//! support can read them page by page from `elements://current?page=N`
+//! instead of spending tool calls, and model context, on full snapshots.
+
+use crate::dom::suggest::label;
+use crate::dom::{AriaChild, AriaNode, DomTree};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// URI of the first page of the element list
+pub(crate) const ELEMENTS_URI: &str = "elements://current";
+
+/// URI template clients fill in to read a given page
+pub(crate) const ELEMENTS_URI_TEMPLATE: &str = "elements://current{?page}";
+
+/// Elements listed per page
+pub(crate) const ELEMENTS_PAGE_SIZE: usize = 100;
+
+/// One indexed element of the page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ElementEntry {
+    /// Index to pass to click, input and the other index-based tools
+    pub index: usize,
+    /// ARIA role
+    pub role: String,
+    /// Accessible name, or text content (then placeholder or hint) when it has none
+    pub name: String,
+    /// CSS selector of the element
+    pub selector: String,
+    /// Element properties such as url, placeholder or value
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub props: BTreeMap<String, String>,
+}
+
+/// One page of the element list
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ElementPage {
+    /// Page number, starting at 1
+    pub page: usize,
+    /// Number of pages
+    pub pages: usize,
+    /// Number of indexed elements on the page
+    pub total: usize,
+    /// Elements of this page, ordered by index
+    pub elements: Vec<ElementEntry>,
+    /// URI of the next page, absent on the last one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+}
+
+/// Page number requested by an element list URI, or `None` for other URIs
+///
+/// `elements://current` is page 1. Fails when the page is not a positive number.
+pub(crate) fn parse_elements_uri(uri: &str) -> Option<Result<usize, String>> {
+    let rest = uri.strip_prefix(ELEMENTS_URI)?;
+    if rest.is_empty() {
+        return Some(Ok(1));
+    }
+    let query = rest.strip_prefix('?')?;
+    let page = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("page="))
+        .unwrap_or("1");
+    Some(match page.parse::<usize>() {
+        Ok(page) if page > 0 => Ok(page),
+        _ => Err(format!("Invalid page '{}': pages start at 1", page)),
+    })
+}
+
+fn collect_elements(node: &AriaNode, dom: &DomTree, elements: &mut Vec<ElementEntry>) {
+    if let Some(index) = node.index {
+        elements.push(ElementEntry {
+            index,
+            role: node.role.clone(),
+            name: label(node),
+            selector: dom.get_selector(index).cloned().unwrap_or_default(),
+            props: node
+                .props
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        });
+    }
+    for child in &node.children {
+        if let AriaChild::Node(child) = child {
+            collect_elements(child, dom, elements);
+        }
+    }
+}
+
+/// Page `page` (starting at 1) of the tree's indexed elements
+///
+/// Fails when the page is past the last one; an empty list has one empty page.
+pub(crate) fn element_page(
+    dom: &DomTree,
+    page: usize,
+    page_size: usize,
+) -> Result<ElementPage, String> {
+    let mut elements = Vec::new();
+    collect_elements(&dom.root, dom, &mut elements);
+    elements.sort_by_key(|element| element.index);
+
+    let total = elements.len();
+    let pages = total.div_ceil(page_size).max(1);
+    if page == 0 || page > pages {
+        return Err(format!(
+            "Page {} is out of range: the element list has {} page(s)",
+            page, pages
+        ));
+    }
+    let elements = elements
+        .into_iter()
+        .skip((page - 1) * page_size)
+        .take(page_size)
+        .collect();
+    Ok(ElementPage {
+        page,
+        pages,
+        total,
+        elements,
+        next: (page < pages).then(|| format!("{}?page={}", ELEMENTS_URI, page + 1)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_elements_uri() {
+        assert_eq!(parse_elements_uri("elements://current"), Some(Ok(1)));
+        assert_eq!(parse_elements_uri("elements://current?page=3"), Some(Ok(3)));
+        assert!(matches!(
+            parse_elements_uri("elements://current?page=0"),
+            Some(Err(_))
+        ));
+        assert!(matches!(
+            parse_elements_uri("elements://current?page=x"),
+            Some(Err(_))
+        ));
+        assert_eq!(parse_elements_uri("elements://currently"), None);
+        assert_eq!(parse_elements_uri("file:///tmp/a"), None);
+    }
+
+    #[test]
+    fn test_element_page() {
+        let buttons = (0..5)
+            .map(|i| {
+                AriaChild::Node(Box::new(
+                    AriaNode::new("button", format!("Button {}", i)).with_index(i),
+                ))
+            })
+            .collect();
+        let mut dom = DomTree::new(AriaNode::fragment().with_children(buttons));
+        dom.selectors = (0..5).map(|i| format!("#b{}", i)).collect();
+
+        let first = element_page(&dom, 1, 2).unwrap();
+        assert_eq!((first.page, first.pages, first.total), (1, 3, 5));
+        assert_eq!(first.elements[1].selector, "#b1");
+        assert_eq!(first.next.as_deref(), Some("elements://current?page=2"));
+
+        let last = element_page(&dom, 3, 2).unwrap();
+        assert_eq!(last.elements.len(), 1);
+        assert_eq!(last.elements[0].name, "Button 4");
+        assert!(last.next.is_none());
+
+        assert!(element_page(&dom, 4, 2).is_err());
+        let empty = DomTree::new(AriaNode::fragment());
+        assert_eq!(element_page(&empty, 1, 2).unwrap().pages, 1);
+    }
+}
@@ -0,0 +1,223 @@
+//! Optical character recognition over screenshots
+//!
+//! Text drawn into canvases or baked into images is invisible to DOM
+//! extraction. This module runs screenshots through the
+//! [Tesseract](https://github.com/tesseract-ocr/tesseract) command line tool,
+//! which must be installed separately, and returns the recognized text with
+//! per-word boxes and confidences.
+
+use crate::dom::BoundingBox;
+use crate::error::{BrowserError, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Environment variable overriding the tesseract binary path
+pub const TESSERACT_ENV: &str = "BROWSER_USE_TESSERACT";
+
+/// Options for running OCR
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrOptions {
+    /// Tesseract language code(s), e.g. "eng" or "eng+deu" (default: "eng")
+    pub language: String,
+
+    /// Path to the tesseract binary (default: `$BROWSER_USE_TESSERACT` or `tesseract` on PATH)
+    pub tesseract_path: Option<PathBuf>,
+
+    /// Words below this confidence (0 - 100) are dropped (default: 0)
+    pub min_confidence: f32,
+}
+
+impl Default for OcrOptions {
+    fn default() -> Self {
+        Self {
+            language: "eng".to_string(),
+            tesseract_path: None,
+            min_confidence: 0.0,
+        }
+    }
+}
+
+impl OcrOptions {
+    /// Create new OcrOptions with default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method: set recognition language(s)
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = language.into();
+        self
+    }
+
+    /// Builder method: set tesseract binary path
+    pub fn tesseract_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tesseract_path = Some(path.into());
+        self
+    }
+
+    /// Builder method: set minimum word confidence
+    pub fn min_confidence(mut self, confidence: f32) -> Self {
+        self.min_confidence = confidence;
+        self
+    }
+}
+
+/// A recognized word
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrWord {
+    /// Word text
+    pub text: String,
+
+    /// Recognition confidence (0 - 100)
+    pub confidence: f32,
+
+    /// Position in image pixels
+    pub bounding_box: BoundingBox,
+}
+
+/// Recognized text of an image
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OcrResult {
+    /// Full text with line breaks between lines and blank lines between blocks
+    pub text: String,
+
+    /// Individual words in reading order
+    pub words: Vec<OcrWord>,
+}
+
+impl OcrResult {
+    /// Average confidence of all words, or 0 when nothing was recognized
+    pub fn mean_confidence(&self) -> f32 {
+        if self.words.is_empty() {
+            return 0.0;
+        }
+        self.words.iter().map(|w| w.confidence).sum::<f32>() / self.words.len() as f32
+    }
+}
+
+/// Recognize text in a PNG (or any format tesseract reads)
+pub fn recognize(image: &[u8], options: &OcrOptions) -> Result<OcrResult> {
+    let binary = options
+        .tesseract_path
+        .clone()
+        .or_else(|| std::env::var_os(TESSERACT_ENV).map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("tesseract"));
+
+    let mut child = Command::new(&binary)
+        .args(["stdin", "stdout", "-l", &options.language, "tsv"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            BrowserError::OcrFailed(format!(
+                "Failed to run {} (is tesseract installed?): {}",
+                binary.display(),
+                e
+            ))
+        })?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(image)?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(BrowserError::OcrFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(parse_tsv(
+        &String::from_utf8_lossy(&output.stdout),
+        options.min_confidence,
+    ))
+}
+
+/// Parse tesseract TSV output into words and reflowed text
+fn parse_tsv(tsv: &str, min_confidence: f32) -> OcrResult {
+    let mut result = OcrResult::default();
+    let mut last_line: Option<(u32, u32, u32)> = None;
+    let mut last_block: Option<u32> = None;
+
+    // level page block par line word left top width height conf text
+    for row in tsv.lines().skip(1) {
+        let cols: Vec<&str> = row.split('\t').collect();
+        if cols.len() < 12 || cols[0] != "5" {
+            continue;
+        }
+        let text = cols[11].trim();
+        let confidence: f32 = cols[10].parse().unwrap_or(-1.0);
+        if text.is_empty() || confidence < min_confidence.max(0.0) {
+            continue;
+        }
+
+        let num = |i: usize| cols[i].parse::<u32>().unwrap_or(0);
+        let line = (num(2), num(3), num(4));
+
+        if last_line != Some(line) {
+            if last_block.is_some_and(|block| block != line.0) {
+                result.text.push_str("\n\n");
+            } else if last_line.is_some() {
+                result.text.push('\n');
+            }
+        } else {
+            result.text.push(' ');
+        }
+        result.text.push_str(text);
+        last_line = Some(line);
+        last_block = Some(line.0);
+
+        result.words.push(OcrWord {
+            text: text.to_string(),
+            confidence,
+            bounding_box: BoundingBox::new(
+                num(6) as f64,
+                num(7) as f64,
+                num(8) as f64,
+                num(9) as f64,
+            ),
+        });
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TSV: &str = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext
+1\t1\t0\t0\t0\t0\t0\t0\t800\t600\t-1\t
+5\t1\t1\t1\t1\t1\t10\t10\t50\t12\t96.5\tHello
+5\t1\t1\t1\t1\t2\t65\t10\t60\t12\t91.0\tworld
+5\t1\t1\t1\t2\t1\t10\t30\t40\t12\t88.0\tSecond
+5\t1\t2\t1\t1\t1\t10\t80\t40\t12\t20.0\tnoise
+5\t1\t3\t1\t1\t1\t10\t120\t40\t12\t90.0\tTotal:
+";
+
+    #[test]
+    fn test_parse_tsv() {
+        let result = parse_tsv(TSV, 0.0);
+        assert_eq!(result.text, "Hello world\nSecond\n\nnoise\n\nTotal:");
+        assert_eq!(result.words.len(), 5);
+        assert_eq!(result.words[1].bounding_box.x, 65.0);
+    }
+
+    #[test]
+    fn test_min_confidence() {
+        let result = parse_tsv(TSV, 50.0);
+        assert_eq!(result.text, "Hello world\nSecond\n\nTotal:");
+        assert!(result.mean_confidence() > 90.0);
+    }
+
+    #[test]
+    fn test_missing_binary() {
+        let options = OcrOptions::new().tesseract_path("/nonexistent/tesseract");
+        assert!(matches!(
+            recognize(b"", &options),
+            Err(BrowserError::OcrFailed(_))
+        ));
+    }
+}
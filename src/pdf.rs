@@ -0,0 +1,234 @@
+//! Text and outline extraction from PDF documents
+//!
+//! When a tab shows a PDF, the DOM is Chrome's viewer rather than the
+//! document, so DOM extraction and markdown conversion find nothing to read.
+//! The `read_pdf` tool and `get_markdown` instead fetch the file and parse it
+//! here. Only text that is stored as text is found; scanned pages need OCR.
+
+use crate::error::{BrowserError, Result};
+use lopdf::Document;
+use serde::{Deserialize, Serialize};
+
+/// Text of one page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PdfPage {
+    /// Page number, starting at 1
+    pub number: u32,
+
+    /// Extracted text, with line breaks where the PDF starts new lines
+    pub text: String,
+}
+
+/// A bookmark of the document outline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PdfOutlineEntry {
+    /// Nesting depth, starting at 1 for top-level entries
+    pub level: usize,
+
+    /// Bookmark title
+    pub title: String,
+
+    /// Page the bookmark points to
+    pub page: usize,
+}
+
+/// Extracted content of a PDF
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PdfDocument {
+    /// Title from the document information, if set
+    pub title: Option<String>,
+
+    /// Number of pages in the document
+    pub page_count: usize,
+
+    /// Text of the extracted pages, in order
+    pub pages: Vec<PdfPage>,
+
+    /// Bookmarks in document order; empty if the PDF has none
+    pub outline: Vec<PdfOutlineEntry>,
+}
+
+/// Whether `data` starts like a PDF file
+pub fn is_pdf(data: &[u8]) -> bool {
+    data.starts_with(b"%PDF-")
+}
+
+impl PdfDocument {
+    /// Parse every page of a PDF
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        Self::parse_pages(data, None)
+    }
+
+    /// Parse a PDF, extracting text only from `pages` (1-based) if given
+    ///
+    /// Pages that fail to decode are kept with empty text rather than failing
+    /// the whole document.
+    pub fn parse_pages(data: &[u8], pages: Option<&[u32]>) -> Result<Self> {
+        if !is_pdf(data) {
+            return Err(BrowserError::DocumentFailed("Not a PDF file".to_string()));
+        }
+        let document = Document::load_mem(data)
+            .map_err(|e| BrowserError::DocumentFailed(format!("Invalid PDF: {}", e)))?;
+
+        let page_numbers: Vec<u32> = document.get_pages().keys().copied().collect();
+        let pages = page_numbers
+            .iter()
+            .filter(|number| pages.is_none_or(|wanted| wanted.contains(number)))
+            .map(|&number| PdfPage {
+                number,
+                text: document
+                    .extract_text(&[number])
+                    .map(|text| text.trim_end().to_string())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        let outline = document
+            .get_toc()
+            .map(|toc| {
+                toc.toc
+                    .into_iter()
+                    .map(|entry| PdfOutlineEntry {
+                        level: entry.level,
+                        title: entry.title,
+                        page: entry.page,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            title: document_title(&document),
+            page_count: page_numbers.len(),
+            pages,
+            outline,
+        })
+    }
+
+    /// Text of all extracted pages, separated by blank lines
+    pub fn text(&self) -> String {
+        self.pages
+            .iter()
+            .map(|page| page.text.as_str())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Markdown with a heading per page, for the `get_markdown` tool
+    pub fn to_markdown(&self) -> String {
+        self.pages
+            .iter()
+            .map(|page| format!("## Page {}\n\n{}", page.number, page.text))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// `Title` entry of the document information dictionary
+fn document_title(document: &Document) -> Option<String> {
+    let info = document.trailer.get(b"Info").ok()?;
+    let (_, info) = document.dereference(info).ok()?;
+    let title = info.as_dict().ok()?.get(b"Title").ok()?;
+    lopdf::decode_text_string(title)
+        .ok()
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::content::{Content, Operation};
+    use lopdf::{Object, Stream, dictionary};
+
+    /// A PDF with one line of text per page
+    fn sample_pdf(lines: &[&str]) -> Vec<u8> {
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font_id = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let resources_id = document.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+
+        let mut kids = Vec::new();
+        for line in lines {
+            let content = Content {
+                operations: vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["F1".into(), 24.into()]),
+                    Operation::new("Td", vec![72.into(), 700.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(*line)]),
+                    Operation::new("ET", vec![]),
+                ],
+            };
+            let content_id =
+                document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            let page_id = document.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            });
+            kids.push(page_id.into());
+        }
+
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Count" => lines.len() as i64,
+                "Kids" => kids,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }),
+        );
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        let info_id = document.add_object(dictionary! {
+            "Title" => Object::string_literal("Quarterly report"),
+        });
+        document.trailer.set("Root", catalog_id);
+        document.trailer.set("Info", info_id);
+
+        let mut data = Vec::new();
+        document.save_to(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_parse() {
+        let data = sample_pdf(&["Revenue grew", "Costs fell"]);
+        assert!(is_pdf(&data));
+
+        let pdf = PdfDocument::parse(&data).unwrap();
+        assert_eq!(pdf.title.as_deref(), Some("Quarterly report"));
+        assert_eq!(pdf.page_count, 2);
+        assert_eq!(pdf.pages[1].number, 2);
+        assert!(pdf.pages[0].text.contains("Revenue grew"));
+        assert!(pdf.text().contains("Costs fell"));
+        assert!(pdf.to_markdown().starts_with("## Page 1\n\n"));
+        assert!(pdf.outline.is_empty());
+    }
+
+    #[test]
+    fn test_parse_selected_pages() {
+        let data = sample_pdf(&["One", "Two", "Three"]);
+        let pdf = PdfDocument::parse_pages(&data, Some(&[2])).unwrap();
+        assert_eq!(pdf.page_count, 3);
+        assert_eq!(pdf.pages.len(), 1);
+        assert!(pdf.pages[0].text.contains("Two"));
+    }
+
+    #[test]
+    fn test_rejects_non_pdf() {
+        let error = PdfDocument::parse(b"<html></html>").unwrap_err();
+        assert!(matches!(error, BrowserError::DocumentFailed(_)));
+        assert!(PdfDocument::parse(b"%PDF-1.5 garbage").is_err());
+    }
+}
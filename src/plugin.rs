@@ -0,0 +1,182 @@
+//! API for crates that publish tool packs
+//!
+//! A tool pack is a crate (e.g. `browser-use-tools-salesforce`) exporting a
+//! [`ToolPack`]: a named set of tools that users add to their registry or MCP
+//! server next to the built-in ones. Pack crates should depend on this module
+//! only; everything a tool needs is re-exported here, including the `serde_json`
+//! and `schemars` versions this crate uses (`schemars` with the `schema` feature).
+//!
+//! ```rust,no_run
+//! use browser_use::plugin::{
+//!     DynTool, Result, Tool, ToolContext, ToolPack, ToolRegistry, ToolResult, schemars,
+//!     serde_json,
+//! };
+//! use serde::{Deserialize, Serialize};
+//! use std::sync::Arc;
+//!
+//! #[derive(Serialize, Deserialize, schemars::JsonSchema)]
+//! #[schemars(crate = "browser_use::plugin::schemars")]
+//! struct OpportunityParams {
+//!     /// Opportunity id
+//!     id: String,
+//! }
+//!
+//! struct OpenOpportunityTool;
+//!
+//! impl Tool for OpenOpportunityTool {
+//!     type Params = OpportunityParams;
+//!
+//!     fn name(&self) -> &str {
+//!         "salesforce_open_opportunity"
+//!     }
+//!
+//!     fn description(&self) -> &str {
+//!         "Open a Salesforce opportunity by id"
+//!     }
+//!
+//!     fn execute_typed(&self, params: OpportunityParams, context: &mut ToolContext) -> Result<ToolResult> {
+//!         let url = format!("https://example.lightning.force.com/{}", params.id);
+//!         context.session.navigate(&url)?;
+//!         Ok(ToolResult::success_with(serde_json::json!({ "url": url })))
+//!     }
+//! }
+//!
+//! struct SalesforcePack;
+//!
+//! impl ToolPack for SalesforcePack {
+//!     fn name(&self) -> &str {
+//!         "salesforce"
+//!     }
+//!
+//!     fn tools(&self) -> Vec<Arc<dyn DynTool>> {
+//!         vec![Arc::new(OpenOpportunityTool)]
+//!     }
+//! }
+//!
+//! # fn main() -> Result<()> {
+//! let registry = ToolRegistry::with_defaults().with_pack(&SalesforcePack)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+pub use crate::browser::BrowserSession;
+pub use crate::dom::DomTree;
+pub use crate::error::{BrowserError, ErrorCode, Result};
+pub use crate::tools::{DynTool, Tool, ToolContext, ToolRegistry, ToolResult};
+#[cfg(feature = "schema")]
+pub use schemars;
+pub use serde_json;
+
+/// Version of the plugin API, raised on every breaking change to this module
+///
+/// Packs built against another version are refused by
+/// [`ToolRegistry::register_pack`] with a clear error instead of misbehaving.
+pub const API_VERSION: u32 = 1;
+
+/// A named set of tools published by a crate
+pub trait ToolPack: Send + Sync {
+    /// Name of the pack, used in error messages and logs
+    fn name(&self) -> &str;
+
+    /// Plugin API version the pack was written for (default: the current one)
+    ///
+    /// Override it with a literal to have the pack refused by a library with
+    /// a different [`API_VERSION`].
+    fn api_version(&self) -> u32 {
+        API_VERSION
+    }
+
+    /// The pack's tools
+    fn tools(&self) -> Vec<Arc<dyn DynTool>>;
+}
+
+/// Check that a pack was written for this library's plugin API
+pub fn check_pack(pack: &dyn ToolPack) -> Result<()> {
+    if pack.api_version() != API_VERSION {
+        return Err(BrowserError::InvalidArgument(format!(
+            "Tool pack '{}' needs plugin API version {}, this library provides {}",
+            pack.name(),
+            pack.api_version(),
+            API_VERSION
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+    struct EchoParams {
+        text: String,
+    }
+
+    struct EchoTool(&'static str);
+
+    impl Tool for EchoTool {
+        type Params = EchoParams;
+
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn execute_typed(&self, params: EchoParams, _: &mut ToolContext) -> Result<ToolResult> {
+            Ok(ToolResult::success_with(params.text))
+        }
+    }
+
+    struct Pack {
+        version: u32,
+        tools: &'static [&'static str],
+    }
+
+    impl ToolPack for Pack {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn api_version(&self) -> u32 {
+            self.version
+        }
+
+        fn tools(&self) -> Vec<Arc<dyn DynTool>> {
+            self.tools
+                .iter()
+                .map(|name| Arc::new(EchoTool(name)) as Arc<dyn DynTool>)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_register_pack() {
+        let pack = Pack {
+            version: API_VERSION,
+            tools: &["echo", "shout"],
+        };
+        let registry = ToolRegistry::new().with_pack(&pack).unwrap();
+        assert!(registry.has("echo"));
+        assert!(registry.has("shout"));
+    }
+
+    #[test]
+    fn test_register_pack_errors() {
+        let other_version = Pack {
+            version: API_VERSION + 1,
+            tools: &["echo"],
+        };
+        assert!(ToolRegistry::new().with_pack(&other_version).is_err());
+
+        let clashing = Pack {
+            version: API_VERSION,
+            tools: &["echo", "navigate"],
+        };
+        let mut registry = ToolRegistry::with_defaults();
+        assert!(registry.register_pack(&clashing).is_err());
+        assert!(!registry.has("echo"));
+    }
+}
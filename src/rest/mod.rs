@@ -0,0 +1,436 @@
+//! REST API over browser sessions
+//!
+//! A small HTTP façade for clients that cannot speak MCP or gRPC, such as
+//! no-code platforms:
+//!
+//! | Method | Path | Description |
+//! |--------|------|-------------|
+//! | `GET` | `/tools` | Names, descriptions and parameter schemas of the tools |
+//! | `POST` | `/sessions` | Launch a browser; body `{"headless", "window_width", "window_height"}` (all optional); returns `{"id"}` |
+//! | `DELETE` | `/sessions/{id}` | Close a session's browser |
+//! | `POST` | `/sessions/{id}/tools/{name}` | Run a tool with the JSON body as arguments; returns the tool result |
+//! | `GET` | `/sessions/{id}/screenshot` | Image of the active tab; query `format`, `quality`, `full_page` |
+//!
+//! Errors are JSON `{"error": {"code", "message", "retryable"}}` with an HTTP
+//! status derived from the error code. `POST` requests must be sent as
+//! `Content-Type: application/json`, which web pages cannot do cross-site
+//! without a CORS preflight. Session ids are random but not secret; put the
+//! server behind authentication before exposing it.
+
+use crate::browser::{BrowserSession, ImageFormat, LaunchOptions, ScreenshotOptions};
+use crate::error::{BrowserError, ErrorCode};
+use crate::tools::ToolRegistry;
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Sessions open at once unless configured otherwise
+pub const DEFAULT_MAX_SESSIONS: usize = 4;
+
+type SharedSession = Arc<Mutex<BrowserSession>>;
+
+struct RestState {
+    /// Options every session is launched with, before the request's overrides
+    base: LaunchOptions,
+    max_sessions: usize,
+    sessions: Mutex<HashMap<String, SharedSession>>,
+    /// Set on shutdown, after which no sessions are launched
+    closed: AtomicBool,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl RestState {
+    fn session(&self, id: &str) -> Result<SharedSession, ApiError> {
+        lock(&self.sessions)
+            .get(id)
+            .cloned()
+            .ok_or_else(|| ApiError::not_found("SESSION_NOT_FOUND", format!("No session '{}'", id)))
+    }
+}
+
+/// Handle for closing the browsers of a router's sessions when the server stops
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    state: Arc<RestState>,
+}
+
+impl ShutdownHandle {
+    /// Wait for the running tool calls to finish, then close every session's browser and refuse new sessions
+    pub fn shutdown(&self) {
+        let sessions = {
+            let mut sessions = lock(&self.state.sessions);
+            self.state.closed.store(true, Ordering::SeqCst);
+            std::mem::take(&mut *sessions)
+        };
+        for (id, session) in sessions {
+            if let Err(e) = lock(&session).close() {
+                log::warn!("Failed to close session {}: {}", id, e);
+            }
+        }
+    }
+}
+
+/// Error response: an HTTP status and a JSON body
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    body: Value,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": { "code": code, "message": message.into(), "retryable": false } }),
+        }
+    }
+
+    fn not_found(code: &str, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, code, message)
+    }
+}
+
+impl From<BrowserError> for ApiError {
+    fn from(error: BrowserError) -> Self {
+        let code = error.code();
+        let status = match code {
+            ErrorCode::InvalidArgument | ErrorCode::InvalidSelector => StatusCode::BAD_REQUEST,
+            ErrorCode::ElementNotFound => StatusCode::NOT_FOUND,
+            ErrorCode::PolicyDenied | ErrorCode::PathNotAllowed | ErrorCode::RobotsDisallowed => {
+                StatusCode::FORBIDDEN
+            }
+            ErrorCode::Timeout | ErrorCode::NavTimeout => StatusCode::GATEWAY_TIMEOUT,
+            ErrorCode::ConnectionFailed
+            | ErrorCode::BrowserDisconnected
+            | ErrorCode::PageCrashed => StatusCode::BAD_GATEWAY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        let mut detail = json!({
+            "code": code,
+            "message": error.to_string(),
+            "retryable": error.retryable(),
+        });
+        if let Some(context) = error.context()
+            && let Ok(Value::Object(context)) = serde_json::to_value(context)
+            && let Value::Object(ref mut fields) = detail
+        {
+            fields.extend(context);
+        }
+        Self {
+            status,
+            body: json!({ "error": detail }),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
+/// Run blocking browser work off the async runtime
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, ApiError> + Send + 'static,
+) -> Result<T, ApiError> {
+    tokio::task::spawn_blocking(f).await.map_err(|e| {
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "TASK_FAILED",
+            e.to_string(),
+        )
+    })?
+}
+
+/// Launch settings a client may choose for its session
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CreateSession {
+    headless: Option<bool>,
+    window_width: Option<u32>,
+    window_height: Option<u32>,
+}
+
+/// Query of `GET /sessions/{id}/screenshot`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ScreenshotQuery {
+    format: ImageFormat,
+    quality: Option<u32>,
+    full_page: bool,
+}
+
+/// Refuse request bodies not declared as JSON
+///
+/// Browsers send `text/plain` and form bodies cross-site without a preflight,
+/// so accepting them would let any web page drive the API.
+fn require_json(headers: &HeaderMap) -> Result<(), ApiError> {
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+    if is_json {
+        return Ok(());
+    }
+    Err(ApiError::new(
+        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        "UNSUPPORTED_MEDIA_TYPE",
+        "Request body must be sent as Content-Type: application/json",
+    ))
+}
+
+/// Parse an optional JSON body; empty means `default`
+fn json_body<T: for<'de> Deserialize<'de>>(body: &Bytes, default: T) -> Result<T, ApiError> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(default);
+    }
+    serde_json::from_slice(body).map_err(|e| {
+        ApiError::new(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidArgument.as_str(),
+            format!("Invalid JSON body: {}", e),
+        )
+    })
+}
+
+async fn list_tools() -> Json<Value> {
+    let registry = ToolRegistry::with_defaults();
+    let mut tools: Vec<Value> = registry
+        .all_tools()
+        .iter()
+        .map(|tool| {
+            json!({
+                "name": tool.name(),
+                "description": tool.description(),
+                "parameters": tool.parameters_schema(),
+            })
+        })
+        .collect();
+    tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+    Json(json!({ "tools": tools }))
+}
+
+async fn create_session(
+    State(state): State<Arc<RestState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, ApiError> {
+    require_json(&headers)?;
+    let request: CreateSession = json_body(&body, CreateSession::default())?;
+    let too_many = || {
+        ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "TOO_MANY_SESSIONS",
+            format!("At most {} sessions may be open", state.max_sessions),
+        )
+    };
+    if lock(&state.sessions).len() >= state.max_sessions {
+        return Err(too_many());
+    }
+
+    let mut options = state.base.clone();
+    options.headless = request.headless.unwrap_or(options.headless);
+    options.window_width = request.window_width.unwrap_or(options.window_width);
+    options.window_height = request.window_height.unwrap_or(options.window_height);
+    let session = blocking(move || Ok(BrowserSession::launch(options)?)).await?;
+
+    let id = format!(
+        "{:016x}",
+        RandomState::new().hash_one(std::time::Instant::now())
+    );
+    let closed = {
+        // Other requests may have launched sessions in the meantime
+        let mut sessions = lock(&state.sessions);
+        let closed = state.closed.load(Ordering::SeqCst);
+        if sessions.len() < state.max_sessions && !closed {
+            sessions.insert(id.clone(), Arc::new(Mutex::new(session)));
+            return Ok((StatusCode::CREATED, Json(json!({ "id": id }))));
+        }
+        closed
+    };
+    blocking(move || Ok(session.close()?)).await?;
+    if closed {
+        return Err(ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "SHUTTING_DOWN",
+            "The server is shutting down",
+        ));
+    }
+    Err(too_many())
+}
+
+async fn close_session(
+    State(state): State<Arc<RestState>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let session = lock(&state.sessions)
+        .remove(&id)
+        .ok_or_else(|| ApiError::not_found("SESSION_NOT_FOUND", format!("No session '{}'", id)))?;
+    blocking(move || Ok(lock(&session).close()?)).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn execute_tool(
+    State(state): State<Arc<RestState>>,
+    Path((id, name)): Path<(String, String)>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<Value>, ApiError> {
+    require_json(&headers)?;
+    let session = state.session(&id)?;
+    let params: Value = json_body(&body, json!({}))?;
+    let result = blocking(move || {
+        let session = lock(&session);
+        if !session.tool_registry().has(&name) {
+            return Err(ApiError::not_found(
+                "TOOL_NOT_FOUND",
+                format!("No tool '{}'", name),
+            ));
+        }
+        Ok(session.execute_tool(&name, params)?)
+    })
+    .await?;
+    Ok(Json(serde_json::to_value(result).unwrap_or_default()))
+}
+
+async fn screenshot(
+    State(state): State<Arc<RestState>>,
+    Path(id): Path<String>,
+    Query(query): Query<ScreenshotQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let session = state.session(&id)?;
+    let mut options = ScreenshotOptions::new()
+        .format(query.format)
+        .full_page(query.full_page);
+    if let Some(quality) = query.quality {
+        options = options.quality(quality);
+    }
+    let bytes = blocking(move || Ok(lock(&session).screenshot_bytes(&options)?)).await?;
+    Ok(([(header::CONTENT_TYPE, query.format.mime_type())], bytes))
+}
+
+/// Router serving the REST API; sessions launch with `base` plus the client's overrides
+///
+/// The returned handle closes the sessions' browsers when the server stops.
+pub fn router(base: LaunchOptions, max_sessions: usize) -> (Router, ShutdownHandle) {
+    let state = Arc::new(RestState {
+        base,
+        max_sessions,
+        sessions: Mutex::default(),
+        closed: AtomicBool::new(false),
+    });
+    let handle = ShutdownHandle {
+        state: Arc::clone(&state),
+    };
+    let router = Router::new()
+        .route("/tools", get(list_tools))
+        .route("/sessions", post(create_session))
+        .route("/sessions/{id}", delete(close_session))
+        .route("/sessions/{id}/tools/{name}", post(execute_tool))
+        .route("/sessions/{id}/screenshot", get(screenshot))
+        .with_state(state);
+    (router, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_status() {
+        let error =
+            ApiError::from(BrowserError::ElementNotFound("#go".to_string()).with_selector("#go"));
+        assert_eq!(error.status, StatusCode::NOT_FOUND);
+        assert_eq!(error.body["error"]["code"], "ELEMENT_NOT_FOUND");
+        assert_eq!(error.body["error"]["retryable"], true);
+        assert_eq!(error.body["error"]["selector"], "#go");
+
+        let error = ApiError::from(BrowserError::PolicyDenied("no".to_string()));
+        assert_eq!(error.status, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_json_body() {
+        let empty = Bytes::from_static(b"  ");
+        assert_eq!(json_body(&empty, json!({})).unwrap(), json!({}));
+
+        let body = Bytes::from_static(br#"{"headless": false}"#);
+        let request: CreateSession = json_body(&body, CreateSession::default()).unwrap();
+        assert_eq!(request.headless, Some(false));
+
+        let body = Bytes::from_static(br#"{"executable_path": "/bin/sh"}"#);
+        let error = json_body::<CreateSession>(&body, CreateSession::default()).unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_require_json() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            require_json(&headers).unwrap_err().status,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+
+        headers.insert(header::CONTENT_TYPE, "text/plain".parse().unwrap());
+        assert!(require_json(&headers).is_err());
+
+        headers.insert(
+            header::CONTENT_TYPE,
+            "application/json; charset=utf-8".parse().unwrap(),
+        );
+        assert!(require_json(&headers).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_session() {
+        let state = Arc::new(RestState {
+            base: LaunchOptions::default(),
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            sessions: Mutex::default(),
+            closed: AtomicBool::new(false),
+        });
+        let Err(error) = state.session("missing") else {
+            panic!("expected no session");
+        };
+        assert_eq!(error.status, StatusCode::NOT_FOUND);
+        assert_eq!(error.body["error"]["code"], "SESSION_NOT_FOUND");
+    }
+
+    #[tokio::test]
+    async fn test_list_tools_describes_tools() {
+        let Json(body) = list_tools().await;
+        let tools = body["tools"].as_array().unwrap();
+        assert!(!tools.is_empty());
+        for tool in tools {
+            assert!(
+                tool["description"].as_str().is_some_and(|d| !d.is_empty()),
+                "{} has no description",
+                tool["name"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_shutdown_refuses_new_sessions() {
+        let (_router, handle) = router(LaunchOptions::default(), DEFAULT_MAX_SESSIONS);
+        assert!(!handle.state.closed.load(Ordering::SeqCst));
+
+        handle.shutdown();
+        assert!(handle.state.closed.load(Ordering::SeqCst));
+        assert!(lock(&handle.state.sessions).is_empty());
+    }
+}
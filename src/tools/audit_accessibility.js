@@ -0,0 +1,208 @@
+JSON.stringify(
+  (function () {
+    const config = __AUDIT_CONFIG__;
+    const issues = [];
+
+    function enabled(rule) {
+      return !config.rules || config.rules.includes(rule);
+    }
+
+    // Same selector as the DOM extraction, so issues can be matched to element indices
+    function buildSelector(element) {
+      if (element.id) {
+        return "#" + element.id;
+      }
+      const path = [];
+      let current = element;
+      while (current && current !== document.body) {
+        let selector = current.tagName.toLowerCase();
+        if (current.className && typeof current.className === "string") {
+          const classes = current.className.trim().split(/\s+/);
+          if (classes.length > 0 && classes[0]) {
+            selector += "." + classes[0];
+          }
+        }
+        const parent = current.parentElement;
+        if (parent) {
+          const siblings = Array.from(parent.children);
+          const index = siblings.indexOf(current);
+          if (siblings.filter(function (s) { return s.tagName === current.tagName; }).length > 1) {
+            selector += ":nth-child(" + (index + 1) + ")";
+          }
+        }
+        path.unshift(selector);
+        current = current.parentElement;
+      }
+      return path.join(" > ");
+    }
+
+    function report(rule, element, message) {
+      issues.push({ rule: rule, message: message, selector: buildSelector(element) });
+    }
+
+    function isRendered(element) {
+      if (typeof element.checkVisibility === "function") {
+        return element.checkVisibility({ visibilityProperty: true });
+      }
+      const style = window.getComputedStyle(element);
+      return style.display !== "none" && style.visibility !== "hidden" &&
+        element.getClientRects().length > 0;
+    }
+
+    function isAriaHidden(element) {
+      return !!element.closest('[aria-hidden="true"]');
+    }
+
+    function describe(element) {
+      const tag = element.tagName.toLowerCase();
+      return element.type && tag === "input" ? "<input type=\"" + element.type + "\">" : "<" + tag + ">";
+    }
+
+    // Images without a text alternative
+    if (enabled("missing-alt")) {
+      document.querySelectorAll("img, input[type=image], area[href]").forEach(function (element) {
+        const role = element.getAttribute("role");
+        if (role === "presentation" || role === "none" || isAriaHidden(element)) return;
+        if (element.hasAttribute("alt") || element.getAttribute("aria-label") ||
+            element.hasAttribute("aria-labelledby")) return;
+        if (element.tagName === "IMG" && !isRendered(element)) return;
+        const src = element.getAttribute("src") || element.getAttribute("href") || "";
+        report("missing-alt", element,
+          describe(element) + " has no alt attribute" + (src ? " (" + src.slice(0, 80) + ")" : ""));
+      });
+    }
+
+    // Form controls without a label
+    if (enabled("unlabeled-control")) {
+      const skipTypes = ["hidden", "submit", "reset", "button", "image"];
+      document.querySelectorAll("input, select, textarea").forEach(function (element) {
+        if (element.tagName === "INPUT" && skipTypes.includes(element.type)) return;
+        if (!isRendered(element) || isAriaHidden(element)) return;
+        const labelled =
+          (element.labels && Array.from(element.labels).some(function (label) {
+            return label.textContent.trim();
+          })) ||
+          (element.getAttribute("aria-label") || "").trim() ||
+          (element.getAttribute("aria-labelledby") || "").trim() ||
+          (element.getAttribute("title") || "").trim();
+        if (labelled) return;
+        const placeholder = (element.getAttribute("placeholder") || "").trim();
+        report("unlabeled-control", element, placeholder
+          ? describe(element) + " is labelled only by its placeholder \"" + placeholder.slice(0, 60) + "\""
+          : describe(element) + " has no label");
+      });
+    }
+
+    // Text whose contrast against its background is below WCAG AA
+    if (enabled("low-contrast")) {
+      const parse = function (color) {
+        const match = /rgba?\(([^)]+)\)/.exec(color);
+        if (!match) return null;
+        const parts = match[1].split(/[\s,\/]+/).filter(Boolean).map(parseFloat);
+        return { r: parts[0], g: parts[1], b: parts[2], a: parts.length > 3 ? parts[3] : 1 };
+      };
+      const blend = function (top, bottom) {
+        const a = top.a + bottom.a * (1 - top.a);
+        if (a === 0) return { r: 0, g: 0, b: 0, a: 0 };
+        const mix = function (channel) {
+          return (top[channel] * top.a + bottom[channel] * bottom.a * (1 - top.a)) / a;
+        };
+        return { r: mix("r"), g: mix("g"), b: mix("b"), a: a };
+      };
+      const luminance = function (color) {
+        const channel = function (value) {
+          const c = value / 255;
+          return c <= 0.03928 ? c / 12.92 : Math.pow((c + 0.055) / 1.055, 2.4);
+        };
+        return 0.2126 * channel(color.r) + 0.7152 * channel(color.g) + 0.0722 * channel(color.b);
+      };
+      const hex = function (color) {
+        return "#" + [color.r, color.g, color.b].map(function (value) {
+          return Math.round(value).toString(16).padStart(2, "0");
+        }).join("");
+      };
+      // Background behind an element, or null when an image or gradient makes it unknown
+      const background = function (element) {
+        const layers = [];
+        for (let current = element; current; current = current.parentElement) {
+          const style = window.getComputedStyle(current);
+          if (style.backgroundImage && style.backgroundImage !== "none") return null;
+          const color = parse(style.backgroundColor);
+          if (color && color.a > 0) {
+            layers.push(color);
+            if (color.a >= 1) break;
+          }
+        }
+        let result = { r: 255, g: 255, b: 255, a: 1 };
+        for (let i = layers.length - 1; i >= 0; i--) {
+          result = blend(layers[i], result);
+        }
+        return result;
+      };
+
+      const walker = document.createTreeWalker(document.body || document.documentElement, NodeFilter.SHOW_ELEMENT);
+      let checked = 0;
+      for (let element = walker.nextNode(); element && checked < config.maxContrastChecks; element = walker.nextNode()) {
+        const hasText = Array.from(element.childNodes).some(function (node) {
+          return node.nodeType === Node.TEXT_NODE && node.textContent.trim();
+        });
+        if (!hasText || !isRendered(element) || isAriaHidden(element)) continue;
+        if (element.matches(":disabled")) continue;
+        checked++;
+        const style = window.getComputedStyle(element);
+        const bg = background(element);
+        const fg = parse(style.color);
+        if (!bg || !fg) continue;
+        const text = blend(fg, bg);
+        const lighter = Math.max(luminance(text), luminance(bg));
+        const darker = Math.min(luminance(text), luminance(bg));
+        const ratio = (lighter + 0.05) / (darker + 0.05);
+        const size = parseFloat(style.fontSize);
+        const bold = parseInt(style.fontWeight, 10) >= 700;
+        const required = size >= 24 || (bold && size >= 18.66) ? 3 : 4.5;
+        if (ratio < required) {
+          const sample = element.textContent.replace(/\s+/g, " ").trim().slice(0, 40);
+          report("low-contrast", element,
+            "Contrast " + ratio.toFixed(2) + ":1 is below " + required + ":1 (" + hex(text) + " on " +
+            hex(bg) + ") for \"" + sample + "\"");
+        }
+      }
+    }
+
+    // aria-hidden content that keyboard users can still reach
+    if (enabled("aria-hidden-focusable")) {
+      const focusable = 'a[href], button, input:not([type=hidden]), select, textarea, iframe, [tabindex], [contenteditable=""], [contenteditable="true"]';
+      document.querySelectorAll('[aria-hidden="true"]').forEach(function (container) {
+        const candidates = [container].concat(Array.from(container.querySelectorAll(focusable)));
+        candidates.forEach(function (element) {
+          if (!element.matches(focusable) || element.tabIndex < 0 || element.matches(":disabled")) return;
+          if (!isRendered(element)) return;
+          report("aria-hidden-focusable", element,
+            describe(element) + " is focusable inside aria-hidden content");
+        });
+      });
+    }
+
+    // ARIA attributes that point at ids missing from the document
+    if (enabled("broken-aria-reference")) {
+      const attributes = ["aria-labelledby", "aria-describedby", "aria-controls", "aria-owns", "aria-activedescendant"];
+      const selector = attributes.map(function (name) { return "[" + name + "]"; }).join(", ");
+      document.querySelectorAll(selector).forEach(function (element) {
+        const root = element.getRootNode();
+        attributes.forEach(function (name) {
+          const value = element.getAttribute(name);
+          if (value === null) return;
+          const missing = value.split(/\s+/).filter(Boolean).filter(function (id) {
+            return !(root.getElementById ? root.getElementById(id) : document.getElementById(id));
+          });
+          if (missing.length > 0) {
+            report("broken-aria-reference", element,
+              name + " refers to missing id(s): " + missing.join(", "));
+          }
+        });
+      });
+    }
+
+    return issues;
+  })()
+)
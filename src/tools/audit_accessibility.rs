@@ -0,0 +1,466 @@
+use crate::dom::{AriaChild, AriaNode, DomTree};
+use crate::error::{BrowserError, Result};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const AUDIT_ACCESSIBILITY_JS: &str = include_str!("audit_accessibility.js");
+
+/// Most text elements whose contrast is computed
+const MAX_CONTRAST_CHECKS: usize = 5000;
+
+/// Rules checked against the page's markup and computed styles
+const PAGE_RULES: &[&str] = &[
+    "missing-alt",
+    "unlabeled-control",
+    "low-contrast",
+    "aria-hidden-focusable",
+    "broken-aria-reference",
+];
+
+/// Rules checked against the extracted accessibility tree
+const TREE_RULES: &[&str] = &[
+    "invalid-role",
+    "unnamed-control",
+    "required-context",
+    "nested-interactive",
+    "heading-order",
+];
+
+/// WAI-ARIA 1.2 roles, plus the tree's own "fragment" and "iframe"
+const KNOWN_ROLES: &[&str] = &[
+    "alert",
+    "alertdialog",
+    "application",
+    "article",
+    "banner",
+    "blockquote",
+    "button",
+    "caption",
+    "cell",
+    "checkbox",
+    "code",
+    "columnheader",
+    "combobox",
+    "complementary",
+    "contentinfo",
+    "definition",
+    "deletion",
+    "dialog",
+    "directory",
+    "document",
+    "emphasis",
+    "feed",
+    "figure",
+    "form",
+    "fragment",
+    "generic",
+    "grid",
+    "gridcell",
+    "group",
+    "heading",
+    "iframe",
+    "img",
+    "insertion",
+    "link",
+    "list",
+    "listbox",
+    "listitem",
+    "log",
+    "main",
+    "marquee",
+    "math",
+    "menu",
+    "menubar",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "meter",
+    "navigation",
+    "none",
+    "note",
+    "option",
+    "paragraph",
+    "presentation",
+    "progressbar",
+    "radio",
+    "radiogroup",
+    "region",
+    "row",
+    "rowgroup",
+    "rowheader",
+    "scrollbar",
+    "search",
+    "searchbox",
+    "separator",
+    "slider",
+    "spinbutton",
+    "status",
+    "strong",
+    "subscript",
+    "superscript",
+    "switch",
+    "tab",
+    "table",
+    "tablist",
+    "tabpanel",
+    "term",
+    "textbox",
+    "time",
+    "timer",
+    "toolbar",
+    "tooltip",
+    "tree",
+    "treegrid",
+    "treeitem",
+];
+
+/// Roles that need an accessible name from their content or a label
+const NAMED_CONTROL_ROLES: &[&str] = &[
+    "button",
+    "link",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "tab",
+    "switch",
+    "checkbox",
+    "radio",
+    "option",
+    "treeitem",
+];
+
+/// Roles that must not contain other interactive elements
+const INTERACTIVE_CONTAINER_ROLES: &[&str] = &["button", "link"];
+
+/// Roles only valid inside one of the given ancestor roles
+const REQUIRED_CONTEXT: &[(&str, &[&str])] = &[
+    ("listitem", &["list", "directory"]),
+    ("option", &["listbox", "combobox", "group"]),
+    ("tab", &["tablist"]),
+    ("menuitem", &["menu", "menubar", "group"]),
+    ("menuitemcheckbox", &["menu", "menubar", "group"]),
+    ("menuitemradio", &["menu", "menubar", "group"]),
+    ("treeitem", &["tree", "group"]),
+    ("row", &["table", "grid", "treegrid", "rowgroup"]),
+    ("cell", &["row"]),
+    ("gridcell", &["row"]),
+    ("columnheader", &["row"]),
+    ("rowheader", &["row"]),
+];
+
+/// Parameters for the audit_accessibility tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AuditAccessibilityParams {
+    /// Rules to check (default: all): "missing-alt", "unlabeled-control", "low-contrast",
+    /// "aria-hidden-focusable", "broken-aria-reference", "invalid-role", "unnamed-control",
+    /// "required-context", "nested-interactive", "heading-order"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<String>>,
+
+    /// Most issues listed; counts per rule still cover all of them (default: 200)
+    #[serde(default = "default_max_issues")]
+    pub max_issues: usize,
+}
+
+impl Default for AuditAccessibilityParams {
+    fn default() -> Self {
+        Self {
+            rules: None,
+            max_issues: default_max_issues(),
+        }
+    }
+}
+
+fn default_max_issues() -> usize {
+    200
+}
+
+/// An accessibility problem found on the page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessibilityIssue {
+    /// Rule that found the problem, e.g. "missing-alt" or "low-contrast"
+    pub rule: String,
+    /// What is wrong, naming the element
+    pub message: String,
+    /// CSS selector of the element, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    /// Element index in the DOM snapshot, when the element is indexed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
+fn describe(node: &AriaNode) -> String {
+    if node.name.is_empty() {
+        node.role.clone()
+    } else {
+        format!("{} \"{}\"", node.role, node.name)
+    }
+}
+
+fn check_node(
+    node: &AriaNode,
+    ancestors: &mut Vec<String>,
+    last_heading: &mut Option<u32>,
+    dom: &DomTree,
+    issues: &mut Vec<AccessibilityIssue>,
+) {
+    let mut report = |rule: &str, message: String| {
+        issues.push(AccessibilityIssue {
+            rule: rule.to_string(),
+            message,
+            selector: node.index.and_then(|i| dom.get_selector(i)).cloned(),
+            index: node.index,
+        });
+    };
+    let role = node.role.as_str();
+
+    if !KNOWN_ROLES.contains(&role) {
+        report(
+            "invalid-role",
+            format!("role=\"{}\" is not a WAI-ARIA role", role),
+        );
+    }
+
+    if NAMED_CONTROL_ROLES.contains(&role)
+        && node.name.trim().is_empty()
+        && node.get_text_content().is_empty()
+    {
+        let hint = node
+            .props
+            .get("hint")
+            .map(|hint| format!(" ({})", hint))
+            .unwrap_or_default();
+        report(
+            "unnamed-control",
+            format!("{} has no accessible name{}", role, hint),
+        );
+    }
+
+    if let Some((_, contexts)) = REQUIRED_CONTEXT.iter().find(|(r, _)| *r == role) {
+        // Wrappers without semantics are transparent to the required context
+        let parent = ancestors
+            .iter()
+            .rev()
+            .find(|r| !matches!(r.as_str(), "generic" | "none" | "presentation"));
+        if !parent.is_some_and(|parent| contexts.contains(&parent.as_str())) {
+            report(
+                "required-context",
+                format!("{} is not inside {}", describe(node), contexts.join(" or ")),
+            );
+        }
+    }
+
+    if node.index.is_some()
+        && NAMED_CONTROL_ROLES.contains(&role)
+        && let Some(container) = ancestors
+            .iter()
+            .find(|r| INTERACTIVE_CONTAINER_ROLES.contains(&r.as_str()))
+    {
+        report(
+            "nested-interactive",
+            format!("{} is nested inside a {}", describe(node), container),
+        );
+    }
+
+    if role == "heading"
+        && let Some(level) = node.level
+    {
+        if let Some(previous) = *last_heading
+            && level > previous + 1
+        {
+            report(
+                "heading-order",
+                format!(
+                    "{} is level {} after a level {} heading",
+                    describe(node),
+                    level,
+                    previous
+                ),
+            );
+        }
+        *last_heading = Some(level);
+    }
+
+    ancestors.push(node.role.clone());
+    for child in &node.children {
+        if let AriaChild::Node(child) = child {
+            check_node(child, ancestors, last_heading, dom, issues);
+        }
+    }
+    ancestors.pop();
+}
+
+/// Issues found in the extracted accessibility tree, in document order
+pub fn tree_issues(dom: &DomTree) -> Vec<AccessibilityIssue> {
+    let mut issues = Vec::new();
+    check_node(&dom.root, &mut Vec::new(), &mut None, dom, &mut issues);
+    issues
+}
+
+/// Tool for a quick automated accessibility pass over the current page
+///
+/// Checks images without alt text, form controls without a label, text
+/// below the WCAG AA contrast ratio (from computed colors; text over
+/// background images is skipped), focusable content hidden with
+/// aria-hidden, and ARIA references to missing ids. The extracted tree is
+/// checked for invalid roles, unnamed controls, roles outside their required
+/// context, interactive elements nested in buttons or links, and skipped
+/// heading levels. Findings are heuristics, not a full WCAG audit.
+#[derive(Default)]
+pub struct AuditAccessibilityTool;
+
+impl Tool for AuditAccessibilityTool {
+    type Params = AuditAccessibilityParams;
+
+    fn name(&self) -> &str {
+        "audit_accessibility"
+    }
+
+    fn description(&self) -> &str {
+        "Audit the current page for missing alt text, unlabeled form controls, low-contrast text, aria-hidden focusable content, broken ARIA references, invalid roles, unnamed controls, misplaced roles, nested interactive elements and skipped heading levels"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: AuditAccessibilityParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let tool_error = |reason: String| BrowserError::ToolExecutionFailed {
+            tool: "audit_accessibility".to_string(),
+            reason,
+        };
+
+        if let Some(rules) = &params.rules
+            && let Some(unknown) = rules.iter().find(|rule| {
+                !PAGE_RULES.contains(&rule.as_str()) && !TREE_RULES.contains(&rule.as_str())
+            })
+        {
+            return Ok(ToolResult::failure(format!(
+                "Unknown rule '{}'. Rules: {}, {}",
+                unknown,
+                PAGE_RULES.join(", "),
+                TREE_RULES.join(", ")
+            )));
+        }
+        let enabled = |rule: &str| {
+            params
+                .rules
+                .as_ref()
+                .is_none_or(|rules| rules.iter().any(|r| r == rule))
+        };
+
+        let config = serde_json::json!({
+            "rules": params.rules,
+            "maxContrastChecks": MAX_CONTRAST_CHECKS,
+        });
+        let js = AUDIT_ACCESSIBILITY_JS.replace("__AUDIT_CONFIG__", &config.to_string());
+        let mut issues: Vec<AccessibilityIssue> = context
+            .session
+            .tab()?
+            .evaluate(&js, false)
+            .map_err(|e| tool_error(e.to_string()))?
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .ok_or_else(|| tool_error("Failed to parse audit results".to_string()))?;
+
+        let dom = context.get_dom()?;
+        for issue in &mut issues {
+            issue.index = issue
+                .selector
+                .as_ref()
+                .and_then(|selector| dom.selectors.iter().position(|s| s == selector));
+        }
+        issues.extend(
+            tree_issues(dom)
+                .into_iter()
+                .filter(|issue| enabled(&issue.rule)),
+        );
+
+        let mut by_rule: BTreeMap<&str, usize> = BTreeMap::new();
+        for issue in &issues {
+            *by_rule.entry(issue.rule.as_str()).or_default() += 1;
+        }
+        let by_rule: BTreeMap<String, usize> = by_rule
+            .into_iter()
+            .map(|(rule, count)| (rule.to_string(), count))
+            .collect();
+        let total = issues.len();
+        issues.truncate(params.max_issues);
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "count": total,
+            "byRule": by_rule,
+            "issues": issues,
+            "truncated": total > params.max_issues,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(child: AriaNode) -> AriaChild {
+        AriaChild::Node(Box::new(child))
+    }
+
+    #[test]
+    fn test_tree_issues() {
+        let root = AriaNode::fragment().with_children(vec![
+            node(AriaNode::new("heading", "Title").with_level(1)),
+            node(AriaNode::new("heading", "Details").with_level(3)),
+            node(AriaNode::new("button", "").with_index(0)),
+            node(AriaNode::new("button", "Save").with_index(1)),
+            node(AriaNode::new("listitem", "Stray")),
+            node(AriaNode::new("list", "").with_children(vec![node(
+                AriaNode::new("generic", "")
+                    .with_children(vec![node(AriaNode::new("listitem", "Ok"))]),
+            )])),
+            node(
+                AriaNode::new("link", "Card")
+                    .with_index(2)
+                    .with_children(vec![node(AriaNode::new("button", "Buy").with_index(3))]),
+            ),
+            node(AriaNode::new("buton", "Typo")),
+        ]);
+        let mut dom = DomTree::new(root);
+        dom.selectors = (0..4).map(|i| format!("#e{}", i)).collect();
+
+        let issues = tree_issues(&dom);
+        let rules: Vec<&str> = issues.iter().map(|issue| issue.rule.as_str()).collect();
+        assert_eq!(
+            rules,
+            [
+                "heading-order",
+                "unnamed-control",
+                "required-context",
+                "nested-interactive",
+                "invalid-role"
+            ]
+        );
+        assert_eq!(issues[1].index, Some(0));
+        assert_eq!(issues[1].selector.as_deref(), Some("#e0"));
+        assert_eq!(
+            issues[2].message,
+            "listitem \"Stray\" is not inside list or directory"
+        );
+        assert_eq!(issues[3].index, Some(3));
+        assert_eq!(issues[4].selector, None);
+    }
+
+    #[test]
+    fn test_audit_params() {
+        let params: AuditAccessibilityParams =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(params.rules.is_none());
+        assert_eq!(params.max_issues, 200);
+    }
+}
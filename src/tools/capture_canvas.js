@@ -0,0 +1,31 @@
+JSON.stringify(
+  (function () {
+    const config = __CANVAS_CONFIG__;
+    const element = document.querySelector(config.selector);
+
+    if (!element) {
+      return { success: false, error: "Element not found" };
+    }
+
+    if (element.tagName !== "CANVAS") {
+      return { success: false, error: "Element is not a CANVAS element" };
+    }
+
+    const info = {
+      success: true,
+      width: element.width,
+      height: element.height,
+      dataUrl: null,
+      error: null,
+    };
+
+    try {
+      info.dataUrl = element.toDataURL("image/png");
+    } catch (e) {
+      // Canvases that drew cross-origin images are tainted and cannot be read
+      info.error = e.name + ": " + e.message;
+    }
+
+    return info;
+  })()
+);
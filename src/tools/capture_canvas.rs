@@ -0,0 +1,203 @@
+use crate::error::{BrowserError, Result};
+use crate::image::RgbaImage;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+use serde::{Deserialize, Serialize};
+
+/// How to read the canvas contents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CanvasCaptureMethod {
+    /// Use toDataURL, falling back to a screenshot for tainted or blank WebGL canvases
+    #[default]
+    Auto,
+    /// Read the backing buffer at its native resolution with toDataURL
+    DataUrl,
+    /// Screenshot the canvas box as rendered on screen
+    Screenshot,
+}
+
+/// Parameters for the capture_canvas tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CaptureCanvasParams {
+    /// Path to save the PNG image
+    pub path: String,
+
+    /// CSS selector of the canvas (defaults to the first canvas on the page)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree (use either this or selector)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Capture method (default: auto)
+    #[serde(default)]
+    pub method: CanvasCaptureMethod,
+}
+
+/// Canvas state returned by capture_canvas.js
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CanvasInfo {
+    success: bool,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+    data_url: Option<String>,
+    error: Option<String>,
+}
+
+/// Decode a `data:image/png;base64,...` URL
+fn decode_data_url(data_url: &str) -> Option<Vec<u8>> {
+    let (_, encoded) = data_url.split_once(";base64,")?;
+    STANDARD.decode(encoded).ok()
+}
+
+/// WebGL canvases without preserveDrawingBuffer read back as fully transparent
+fn is_blank(png: &[u8]) -> bool {
+    RgbaImage::from_png(png)
+        .map(|image| image.pixels.chunks_exact(4).all(|px| px[3] == 0))
+        .unwrap_or(false)
+}
+
+/// Tool for saving the contents of a canvas element as an image
+#[derive(Default)]
+pub struct CaptureCanvasTool;
+
+const CAPTURE_CANVAS_JS: &str = include_str!("capture_canvas.js");
+
+impl Tool for CaptureCanvasTool {
+    type Params = CaptureCanvasParams;
+
+    fn name(&self) -> &str {
+        "capture_canvas"
+    }
+
+    fn description(&self) -> &str {
+        "Save the contents of a canvas element (charts, maps, WebGL) as a PNG image"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default().idempotent()
+    }
+
+    fn execute_typed(
+        &self,
+        params: CaptureCanvasParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let css_selector = match (params.selector, params.index) {
+            (Some(_), Some(_)) => {
+                return Err(BrowserError::ToolExecutionFailed {
+                    tool: "capture_canvas".to_string(),
+                    reason: "Cannot specify both 'selector' and 'index'. Use one or the other."
+                        .to_string(),
+                });
+            }
+            (Some(selector), None) => selector,
+            (None, Some(index)) => context.selector_for_index(index)?,
+            (None, None) => "canvas".to_string(),
+        };
+
+        let path = context.session.output_file(&params.path, true)?;
+
+        let canvas_config = serde_json::json!({ "selector": css_selector });
+        let canvas_js = CAPTURE_CANVAS_JS.replace("__CANVAS_CONFIG__", &canvas_config.to_string());
+
+        let tab = context.session.tab()?;
+        let result = tab
+            .evaluate(&canvas_js, false)
+            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
+        let info: CanvasInfo = result
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .ok_or_else(|| BrowserError::ToolExecutionFailed {
+                tool: "capture_canvas".to_string(),
+                reason: "Failed to read canvas".to_string(),
+            })?;
+
+        if !info.success {
+            return Err(BrowserError::ToolExecutionFailed {
+                tool: "capture_canvas".to_string(),
+                reason: info.error.unwrap_or_else(|| "Unknown error".to_string()),
+            });
+        }
+
+        let from_data_url = match params.method {
+            CanvasCaptureMethod::Screenshot => None,
+            _ => info.data_url.as_deref().and_then(decode_data_url),
+        };
+
+        let (png, method, note) = match (params.method, from_data_url) {
+            (CanvasCaptureMethod::DataUrl, None) => {
+                return Err(BrowserError::ToolExecutionFailed {
+                    tool: "capture_canvas".to_string(),
+                    reason: info
+                        .error
+                        .unwrap_or_else(|| "Canvas contents could not be read".to_string()),
+                });
+            }
+            (CanvasCaptureMethod::DataUrl, Some(png)) => (png, "data_url", None),
+            (CanvasCaptureMethod::Auto, Some(png)) if !is_blank(&png) => (png, "data_url", None),
+            (method, data) => {
+                let note = match (method, data, info.error) {
+                    (CanvasCaptureMethod::Auto, Some(_), _) => {
+                        Some("Canvas read back blank, used a screenshot instead".to_string())
+                    }
+                    (CanvasCaptureMethod::Auto, None, Some(error)) => Some(format!(
+                        "Canvas could not be read ({}), used a screenshot instead",
+                        error
+                    )),
+                    _ => None,
+                };
+                let png = context
+                    .session
+                    .find_element(&tab, &css_selector)?
+                    .capture_screenshot(CaptureScreenshotFormatOption::Png)
+                    .map_err(|e| BrowserError::ScreenshotFailed(e.to_string()))?;
+                (png, "screenshot", note)
+            }
+        };
+
+        std::fs::write(&path, &png).map_err(|e| {
+            BrowserError::ScreenshotFailed(format!("Failed to save canvas image: {}", e))
+        })?;
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "path": path.to_string_lossy(),
+            "selector": css_selector,
+            "method": method,
+            "canvas_width": info.width,
+            "canvas_height": info.height,
+            "size_bytes": png.len(),
+            "note": note
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_data_url() {
+        let png = RgbaImage::filled(1, 1, [0, 0, 0, 255]).to_png().unwrap();
+        let data_url = format!("data:image/png;base64,{}", STANDARD.encode(&png));
+        assert_eq!(decode_data_url(&data_url), Some(png.clone()));
+        assert_eq!(decode_data_url("data:,"), None);
+        assert!(!is_blank(&png));
+    }
+
+    #[test]
+    fn test_is_blank() {
+        let png = RgbaImage::filled(2, 2, [0, 0, 0, 0]).to_png().unwrap();
+        assert!(is_blank(&png));
+    }
+}
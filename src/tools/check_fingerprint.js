@@ -0,0 +1,71 @@
+JSON.stringify(
+  (function () {
+    const signals = [];
+
+    function check(name, exposed, detail) {
+      signals.push({ name: name, exposed: !!exposed, detail: String(detail) });
+    }
+
+    // navigator.webdriver is set to true by automation unless patched
+    check("webdriver", navigator.webdriver === true, navigator.webdriver);
+
+    // Headless Chrome advertises itself in the user agent
+    const userAgent = navigator.userAgent || "";
+    check("headless_user_agent", /HeadlessChrome/i.test(userAgent), userAgent);
+
+    // Real Chrome ships with built-in PDF viewer plugins
+    const pluginCount = navigator.plugins ? navigator.plugins.length : 0;
+    check("missing_plugins", pluginCount === 0, pluginCount + " plugins");
+
+    // Languages are empty in some headless configurations
+    const languages = navigator.languages || [];
+    check("missing_languages", languages.length === 0, languages.join(","));
+
+    // window.chrome is absent in headless Chrome
+    check("missing_window_chrome", typeof window.chrome === "undefined", typeof window.chrome);
+
+    // The UA string and userAgentData brands should agree on the browser
+    let uaMismatch = false;
+    let brandDetail = "userAgentData unavailable";
+    if (navigator.userAgentData && navigator.userAgentData.brands) {
+      const brands = navigator.userAgentData.brands.map((b) => b.brand);
+      brandDetail = brands.join(", ");
+      const headlessBrand = brands.some((b) => /Headless/i.test(b));
+      const chromeInUa = /Chrome\//.test(userAgent);
+      const chromeBrand = brands.some((b) => /Chrom/i.test(b));
+      uaMismatch = headlessBrand || chromeInUa !== chromeBrand;
+    }
+    check("user_agent_mismatch", uaMismatch, brandDetail);
+
+    // Notification permission is "denied" while the permission query reports "prompt"
+    // in headless Chrome; this check is synchronous so only the static part is used
+    const notificationPermission =
+      typeof Notification !== "undefined" ? Notification.permission : "unavailable";
+    check("notification_permission", notificationPermission === "denied", notificationPermission);
+
+    // A zero-sized outer window indicates a headless browser
+    check(
+      "zero_outer_dimensions",
+      window.outerWidth === 0 || window.outerHeight === 0,
+      window.outerWidth + "x" + window.outerHeight
+    );
+
+    // Software WebGL renderers are typical of headless environments
+    let renderer = "unavailable";
+    try {
+      const canvas = document.createElement("canvas");
+      const gl = canvas.getContext("webgl") || canvas.getContext("experimental-webgl");
+      if (gl) {
+        const info = gl.getExtension("WEBGL_debug_renderer_info");
+        renderer = info
+          ? gl.getParameter(info.UNMASKED_RENDERER_WEBGL)
+          : gl.getParameter(gl.RENDERER);
+      }
+    } catch (e) {
+      renderer = "error: " + e;
+    }
+    check("software_webgl", /SwiftShader|llvmpipe|Software/i.test(renderer), renderer);
+
+    return { userAgent: userAgent, signals: signals };
+  })()
+);
@@ -0,0 +1,90 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the check_fingerprint tool (no parameters needed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CheckFingerprintParams {}
+
+/// A single automation signal checked in the page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FingerprintSignal {
+    /// Signal name (e.g., "webdriver", "missing_plugins")
+    pub name: String,
+    /// Whether the signal would reveal automation to a detection script
+    pub exposed: bool,
+    /// Raw value observed in the page
+    pub detail: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintReport {
+    user_agent: String,
+    signals: Vec<FingerprintSignal>,
+}
+
+/// Tool for reporting which automation signals the current page can observe
+#[derive(Default)]
+pub struct CheckFingerprintTool;
+
+const CHECK_FINGERPRINT_JS: &str = include_str!("check_fingerprint.js");
+
+impl Tool for CheckFingerprintTool {
+    type Params = CheckFingerprintParams;
+
+    fn name(&self) -> &str {
+        "check_fingerprint"
+    }
+
+    fn description(&self) -> &str {
+        "Report which automation signals (webdriver flag, missing plugins, user agent mismatch, etc.) the current page can detect"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        _params: CheckFingerprintParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let result = context
+            .session
+            .tab()?
+            .evaluate(CHECK_FINGERPRINT_JS, false)
+            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
+
+        let report: FingerprintReport = result
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .ok_or_else(|| BrowserError::ToolExecutionFailed {
+                tool: "check_fingerprint".to_string(),
+                reason: "Failed to parse fingerprint report".to_string(),
+            })?;
+
+        let exposed: Vec<&str> = report
+            .signals
+            .iter()
+            .filter(|s| s.exposed)
+            .map(|s| s.name.as_str())
+            .collect();
+
+        let message = if exposed.is_empty() {
+            "No automation signals exposed".to_string()
+        } else {
+            format!("Exposed automation signals: {}", exposed.join(", "))
+        };
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "userAgent": report.user_agent,
+            "exposedCount": exposed.len(),
+            "signals": report.signals,
+            "message": message
+        })))
+    }
+}
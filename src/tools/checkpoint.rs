@@ -0,0 +1,46 @@
+use crate::error::Result;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the checkpoint tool (no parameters needed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CheckpointParams {}
+
+/// Tool for recording the page state to roll back to later
+#[derive(Default)]
+pub struct CheckpointTool;
+
+impl Tool for CheckpointTool {
+    type Params = CheckpointParams;
+
+    fn name(&self) -> &str {
+        "checkpoint"
+    }
+
+    fn description(&self) -> &str {
+        "Record the page URL, cookies, storage and scroll position so a failed sub-task can be retried from here"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        _params: CheckpointParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let checkpoint = context.session.checkpoint()?;
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "checkpoint_id": checkpoint.id,
+            "url": checkpoint.url,
+            "cookies": checkpoint.cookies.len(),
+            "local_storage": checkpoint.local_storage.len(),
+            "session_storage": checkpoint.session_storage.len(),
+            "scroll": [checkpoint.scroll_x, checkpoint.scroll_y],
+            "message": format!("Saved checkpoint {} at {}", checkpoint.id, checkpoint.url)
+        })))
+    }
+}
@@ -1,18 +1,30 @@
+use crate::browser::EffectObserver;
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::utils::click_element;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 /// Parameters for the click tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ClickParams {
-    /// CSS selector (use either this or index, not both)
+    /// CSS selector, with `>>>` to reach into iframes and shadow roots (use either this or index, not both)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub selector: Option<String>,
 
     /// Element index from DOM tree (use either this or selector, not both)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub index: Option<usize>,
+
+    /// How long to watch for navigation, DOM changes, dialogs and new tabs
+    /// after clicking, in milliseconds; 0 skips the check (default: 500)
+    #[serde(default = "default_observe_ms")]
+    pub observe_ms: u64,
+}
+
+fn default_observe_ms() -> u64 {
+    500
 }
 
 /// Tool for clicking elements
@@ -26,6 +38,14 @@ impl Tool for ClickTool {
         "click"
     }
 
+    fn description(&self) -> &str {
+        "Click on an element specified by CSS selector or index (index obtained from browser_snapshot tool) and report whether it navigated, changed the DOM, opened a dialog or opened a tab"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
     fn execute_typed(&self, params: ClickParams, context: &mut ToolContext) -> Result<ToolResult> {
         // Validate that exactly one selector method is provided
         match (&params.selector, &params.index) {
@@ -47,45 +67,130 @@ impl Tool for ClickTool {
 
         if let Some(selector) = params.selector {
             // CSS selector path
-            let tab = context.session.tab()?;
-            let element = context.session.find_element(&tab, &selector)?;
-            element
-                .click()
-                .map_err(|e| BrowserError::ToolExecutionFailed {
-                    tool: "click".to_string(),
-                    reason: e.to_string(),
-                })?;
+            let effects = click_and_observe(context, &selector, params.observe_ms)?;
 
             Ok(ToolResult::success_with(serde_json::json!({
                 "selector": selector,
-                "method": "css"
+                "method": "css",
+                "effects": effects
             })))
         } else if let Some(index) = params.index {
             // Index path - convert index to CSS selector
-            let css_selector = {
-                let dom = context.get_dom()?;
-                let selector = dom.get_selector(index).ok_or_else(|| {
-                    BrowserError::ElementNotFound(format!("No element with index {}", index))
-                })?;
-                selector.clone()
-            };
-
-            let tab = context.session.tab()?;
-            let element = context.session.find_element(&tab, &css_selector)?;
-            element
-                .click()
-                .map_err(|e| BrowserError::ToolExecutionFailed {
-                    tool: "click".to_string(),
-                    reason: e.to_string(),
-                })?;
+            let css_selector = { context.selector_for_index(index)? };
+
+            let effects = click_and_observe(context, &css_selector, params.observe_ms)?;
 
             Ok(ToolResult::success_with(serde_json::json!({
                 "index": index,
                 "selector": css_selector,
-                "method": "index"
+                "method": "index",
+                "effects": effects
             })))
         } else {
             unreachable!("Validation above ensures one field is Some")
         }
     }
 }
+
+/// Click `selector` and report what the click did, if observing is enabled
+fn click_and_observe(
+    context: &mut ToolContext,
+    selector: &str,
+    observe_ms: u64,
+) -> Result<Option<serde_json::Value>> {
+    let observer = if observe_ms == 0 || context.session.mock().is_some() {
+        None
+    } else {
+        // Not knowing the effects is no reason to skip the click
+        EffectObserver::start(context.session)
+            .map_err(|e| log::debug!("Not observing click effects: {}", e))
+            .ok()
+    };
+
+    click_element("click", context, selector)?;
+
+    Ok(observer.map(|observer| {
+        let effects = observer.finish(Duration::from_millis(observe_ms));
+        serde_json::to_value(effects).unwrap_or_default()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::{ConfirmationPolicy, LaunchOptions, MockAction, MockSession};
+    use crate::dom::{AriaChild, AriaNode, DomTree};
+    use crate::error::ErrorCode;
+
+    fn page() -> MockSession {
+        let mut tree = DomTree::new(
+            AriaNode::fragment()
+                .with_child(AriaChild::Node(Box::new(
+                    AriaNode::new("button", "Save").with_index(0),
+                )))
+                .with_child(AriaChild::Node(Box::new(
+                    AriaNode::new("button", "Delete account").with_index(1),
+                ))),
+        );
+        tree.selectors = vec!["#save".to_string(), "#delete".to_string()];
+        MockSession::new(tree)
+    }
+
+    #[test]
+    fn test_click_by_index() {
+        let mock = page();
+        let session = mock.session();
+        let mut context = ToolContext::new(&session);
+        let params = ClickParams {
+            selector: None,
+            index: Some(0),
+            observe_ms: 500,
+        };
+
+        let result = ClickTool.execute_typed(params, &mut context).unwrap();
+        let data = result.data.unwrap();
+        assert_eq!(data["selector"], "#save");
+        assert_eq!(data["method"], "index");
+        assert_eq!(mock.actions(), vec![MockAction::Click("#save".to_string())]);
+    }
+
+    #[test]
+    fn test_click_unknown_index() {
+        let mock = page();
+        let session = mock.session();
+        let mut context = ToolContext::new(&session);
+        let params = ClickParams {
+            selector: None,
+            index: Some(7),
+            observe_ms: 500,
+        };
+
+        let error = ClickTool.execute_typed(params, &mut context).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::ElementNotFound);
+        assert!(mock.actions().is_empty());
+    }
+
+    #[test]
+    fn test_click_needs_confirmation() {
+        let mock = page();
+        let policy = ConfirmationPolicy::new().text_pattern("delete").unwrap();
+        let session = mock.session_with(LaunchOptions::new().confirmation(policy));
+        let mut context = ToolContext::new(&session);
+
+        let params = ClickParams {
+            selector: Some("#delete".to_string()),
+            index: None,
+            observe_ms: 500,
+        };
+        let error = ClickTool.execute_typed(params, &mut context).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::PolicyDenied);
+
+        let params = ClickParams {
+            selector: Some("#save".to_string()),
+            index: None,
+            observe_ms: 500,
+        };
+        ClickTool.execute_typed(params, &mut context).unwrap();
+        assert_eq!(mock.actions(), vec![MockAction::Click("#save".to_string())]);
+    }
+}
@@ -1,10 +1,10 @@
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the close tool (no parameters needed)
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CloseParams {}
 
 /// Tool for closing the browser
@@ -18,6 +18,14 @@ impl Tool for CloseTool {
         "close"
     }
 
+    fn description(&self) -> &str {
+        "Close the browser when the task is complete"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
     fn execute_typed(&self, _params: CloseParams, context: &mut ToolContext) -> Result<ToolResult> {
         // Note: Closing the browser via BrowserSession is tricky because we hold a reference
         // In a real implementation, this would need to signal the session owner to close
@@ -1,13 +1,23 @@
-use crate::error::Result;
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::error::{BrowserError, Result};
+use crate::tools::utils::resolve_tab;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
-/// Parameters for the close_tab tool (no parameters needed)
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct CloseTabParams {}
+/// Parameters for the close_tab tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CloseTabParams {
+    /// Index of the tab to close, from the tab list (default: the active tab)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
 
-/// Tool for closing the current active tab
+    /// Id of the tab to close, from the tab list (use either this or index)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+/// Tool for closing the active tab or a tab by index
 #[derive(Default)]
 pub struct CloseTabTool;
 
@@ -18,37 +28,51 @@ impl Tool for CloseTabTool {
         "close_tab"
     }
 
+    fn description(&self) -> &str {
+        "Close the active tab, or the tab with the given id or index (from browser_tab_list)"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
     fn execute_typed(
         &self,
-        _params: CloseTabParams,
+        params: CloseTabParams,
         context: &mut ToolContext,
     ) -> Result<ToolResult> {
-        // Get the current tab info before closing
-        let active_tab = context.session.tab()?;
-        let tab_title = active_tab.get_title().unwrap_or_default();
-        let tab_url = active_tab.get_url();
-
-        // Get the current tab index
         let tabs = context.session.get_tabs()?;
-        let current_index = tabs
-            .iter()
-            .position(|tab| std::sync::Arc::ptr_eq(tab, &active_tab))
-            .unwrap_or(0);
+        let active_tab = context.session.tab().ok();
+
+        // Resolve the tab to close: the given index or id, or the active tab
+        let target_index = match resolve_tab(&tabs, params.index, params.id.as_deref()) {
+            Ok(Some(index)) => index,
+            Ok(None) => {
+                let active_tab = active_tab.as_ref().ok_or_else(|| {
+                    BrowserError::TabOperationFailed("No active tab found".to_string())
+                })?;
+                tabs.iter()
+                    .position(|tab| Arc::ptr_eq(tab, active_tab))
+                    .unwrap_or(0)
+            }
+            Err(message) => return Ok(ToolResult::failure(message)),
+        };
 
-        // Close the active tab
-        active_tab.close(true).map_err(|e| {
-            crate::error::BrowserError::TabOperationFailed(format!("Failed to close tab: {}", e))
-        })?;
+        let target_tab = tabs[target_index].clone();
+        let tab_title = target_tab.get_title().unwrap_or_default();
+        let tab_url = target_tab.get_url();
+        context.session.close_tab(target_tab.get_target_id())?;
+        context.invalidate_dom();
+        let remaining = tabs.len() - 1;
 
-        let message = format!(
-            "Closed tab [{}]: {} ({})",
-            current_index, tab_title, tab_url
-        );
+        let message = format!("Closed tab [{}]: {} ({})", target_index, tab_title, tab_url);
 
         Ok(ToolResult::success_with(serde_json::json!({
-            "index": current_index,
+            "index": target_index,
+            "id": target_tab.get_target_id(),
             "title": tab_title,
             "url": tab_url,
+            "remaining": remaining,
             "message": message
         })))
     }
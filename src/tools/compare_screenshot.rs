@@ -0,0 +1,153 @@
+use crate::browser::output::prepare_output_file;
+use crate::error::Result;
+use crate::image::diff::diff;
+use crate::image::{DiffOptions, RgbaImage};
+use crate::tools::utils::capture_png;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CompareScreenshotParams {
+    /// Path of the baseline PNG (created from the current page if it does not exist)
+    pub baseline: String,
+
+    /// CSS selector of the element to compare (use either this or index, omit both for the viewport)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree (use either this or selector)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Capture the full page instead of the viewport (default: false)
+    #[serde(default)]
+    pub full_page: bool,
+
+    /// Per-pixel color distance (0.0 - 1.0) tolerated as equal (default: 0.1)
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+
+    /// Fraction of differing pixels tolerated before the comparison fails (default: 0.0)
+    #[serde(default)]
+    pub max_diff_ratio: f64,
+
+    /// Where to write the diff image (default: next to the baseline with a `.diff.png` suffix)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff_path: Option<String>,
+
+    /// Replace the baseline with the current screenshot after comparing (default: false)
+    #[serde(default)]
+    pub update_baseline: bool,
+}
+
+fn default_threshold() -> f64 {
+    0.1
+}
+
+/// Default diff image path: `home.png` -> `home.diff.png`
+fn default_diff_path(baseline: &Path) -> PathBuf {
+    let stem = baseline
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "screenshot".to_string());
+    baseline.with_file_name(format!("{}.diff.png", stem))
+}
+
+#[derive(Default)]
+pub struct CompareScreenshotTool;
+
+impl Tool for CompareScreenshotTool {
+    type Params = CompareScreenshotParams;
+
+    fn name(&self) -> &str {
+        "compare_screenshot"
+    }
+
+    fn description(&self) -> &str {
+        "Compare a screenshot of the page or an element against a baseline PNG and write a diff image highlighting changed pixels"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
+    fn execute_typed(
+        &self,
+        params: CompareScreenshotParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let baseline_path = context.session.output_file(&params.baseline, true)?;
+        let png = capture_png(
+            "compare_screenshot",
+            context,
+            params.selector.as_deref(),
+            params.index,
+            params.full_page,
+        )?;
+
+        if !baseline_path.exists() {
+            std::fs::write(&baseline_path, &png)?;
+            return Ok(ToolResult::success_with(serde_json::json!({
+                "passed": true,
+                "baseline_created": true,
+                "baseline": baseline_path.to_string_lossy(),
+            })));
+        }
+
+        let baseline = RgbaImage::load(&baseline_path)?;
+        let actual = RgbaImage::from_png(&png)?;
+        let result = diff(
+            &baseline,
+            &actual,
+            &DiffOptions::new().threshold(params.threshold),
+        );
+        let passed = !result.size_mismatch && result.diff_ratio <= params.max_diff_ratio;
+
+        let diff_path = if result.diff_pixels > 0 {
+            let path = match params.diff_path.as_deref() {
+                Some(path) => context.session.output_file(path, true)?,
+                None => prepare_output_file(
+                    context.session.output_dir(),
+                    &default_diff_path(&baseline_path),
+                    true,
+                )?,
+            };
+            result.diff_image.save(&path)?;
+            Some(path.to_string_lossy().into_owned())
+        } else {
+            None
+        };
+
+        if params.update_baseline {
+            std::fs::write(&baseline_path, &png)?;
+        }
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "passed": passed,
+            "baseline_created": false,
+            "baseline": baseline_path.to_string_lossy(),
+            "baseline_updated": params.update_baseline,
+            "diff_pixels": result.diff_pixels,
+            "diff_ratio": result.diff_ratio,
+            "size_mismatch": result.size_mismatch,
+            "baseline_size": [baseline.width, baseline.height],
+            "actual_size": [actual.width, actual.height],
+            "diff_path": diff_path,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_diff_path() {
+        assert_eq!(
+            default_diff_path(Path::new("baselines/home.png")),
+            PathBuf::from("baselines/home.diff.png")
+        );
+    }
+}
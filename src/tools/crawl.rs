@@ -0,0 +1,111 @@
+use crate::crawler::{CrawlOptions, CrawlResult, Crawler};
+use crate::error::Result;
+use crate::tools::utils::normalize_url;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Most pages a single crawl call visits
+const MAX_PAGES: usize = 1000;
+
+/// Most tabs a single crawl call opens
+const MAX_CONCURRENCY: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CrawlParams {
+    /// URL to start crawling from
+    pub url: String,
+
+    /// Maximum number of pages to visit (default: 20, at most 1000)
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
+
+    /// Maximum link depth from the start URL (default: 1)
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+
+    /// Number of pages to load in parallel (default: 4, at most 16)
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+
+    /// Follow links to other sites (default: false)
+    #[serde(default)]
+    pub follow_external: bool,
+}
+
+fn default_max_pages() -> usize {
+    20
+}
+
+fn default_max_depth() -> usize {
+    1
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+#[derive(Default)]
+pub struct CrawlTool;
+
+impl Tool for CrawlTool {
+    type Params = CrawlParams;
+
+    fn name(&self) -> &str {
+        "crawl"
+    }
+
+    fn description(&self) -> &str {
+        "Crawl a site from a start URL in parallel and report each page's status, title and links"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(&self, params: CrawlParams, context: &mut ToolContext) -> Result<ToolResult> {
+        let mut options = CrawlOptions::new()
+            .max_pages(params.max_pages.min(MAX_PAGES))
+            .max_depth(params.max_depth)
+            .concurrency(params.concurrency.min(MAX_CONCURRENCY))
+            .same_site(!params.follow_external);
+
+        // Inherit the session's robots.txt policy and origin scope; the crawler
+        // shares its rate limiter
+        if let Some(user_agent) = context.session.robots_user_agent() {
+            options = options.respect_robots_txt(user_agent);
+        }
+        if let Some(scope) = context.session.scope() {
+            options = options.scope(scope);
+        }
+
+        let crawler = Crawler::from_session(context.session, options)?;
+        let pages: Vec<CrawlResult> = crawler.crawl([normalize_url(&params.url)])?.collect();
+        let failed = pages.iter().filter(|page| page.error.is_some()).count();
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "pages": pages,
+            "count": pages.len(),
+            "failed": failed
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::MockSession;
+    use crate::dom::{AriaNode, DomTree};
+    use crate::error::ErrorCode;
+
+    #[test]
+    fn test_crawl_needs_a_browser() {
+        let session = MockSession::new(DomTree::new(AriaNode::fragment())).session();
+        let mut context = ToolContext::new(&session);
+        let params: CrawlParams =
+            serde_json::from_value(serde_json::json!({ "url": "https://example.com" })).unwrap();
+
+        let error = CrawlTool.execute_typed(params, &mut context).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::TabFailed);
+    }
+}
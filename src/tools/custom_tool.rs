@@ -0,0 +1,268 @@
+//! Tools defined at runtime from a JavaScript snippet
+//!
+//! A [`CustomToolDefinition`] gives a tool name, description, JSON schema for
+//! its arguments and a script; [`CustomTool`] runs the script in the active
+//! tab with the call's arguments. Custom tools run arbitrary JavaScript, so
+//! they are only allowed while the session's evaluate policy is unrestricted.
+
+use crate::browser::EvaluateAccess;
+use crate::error::{BrowserError, Result};
+use crate::tools::evaluate::run_script;
+use crate::tools::{DynTool, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Longest accepted tool name
+const MAX_NAME_LEN: usize = 64;
+
+/// Definition of a tool backed by a JavaScript snippet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CustomToolDefinition {
+    /// Tool name: letters, digits, '_' and '-', starting with a letter
+    pub name: String,
+
+    /// What the tool does, shown to clients
+    pub description: String,
+
+    /// JSON schema of the tool's arguments (default: no arguments)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Value>,
+
+    /// Body of an async function run in the page; use `return` for the result.
+    /// Arguments are available as `params`, and `{{name}}` is replaced with the
+    /// JSON value of argument `name` (`undefined` if not given).
+    pub script: String,
+}
+
+/// Tool that runs a [`CustomToolDefinition`]'s script
+#[derive(Debug, Clone)]
+pub struct CustomTool {
+    definition: CustomToolDefinition,
+    schema: Map<String, Value>,
+}
+
+/// Check that a tool name uses only letters, digits, '_' and '-', starting with a letter
+pub(crate) fn check_tool_name(name: &str) -> Result<()> {
+    let valid = name.len() <= MAX_NAME_LEN
+        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(BrowserError::InvalidArgument(format!(
+            "Invalid tool name '{}': use up to {} letters, digits, '_' or '-', starting with a letter",
+            name, MAX_NAME_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Argument schema of tool `name`, checked to be an object schema and completed with defaults
+pub(crate) fn object_schema(name: &str, parameters: Option<&Value>) -> Result<Map<String, Value>> {
+    let mut schema = match parameters {
+        None => Map::new(),
+        Some(Value::Object(schema)) => schema.clone(),
+        Some(_) => {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Parameters of tool '{}' must be a JSON schema object",
+                name
+            )));
+        }
+    };
+    match schema.get("type") {
+        None => {
+            schema.insert("type".to_string(), Value::from("object"));
+        }
+        Some(Value::String(kind)) if kind == "object" => {}
+        Some(_) => {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Parameters of tool '{}' must be an object schema",
+                name
+            )));
+        }
+    }
+    schema
+        .entry("properties")
+        .or_insert_with(|| Value::Object(Map::new()));
+    Ok(schema)
+}
+
+/// Check call arguments against an object schema's required properties
+pub(crate) fn check_arguments(
+    schema: &Map<String, Value>,
+    params: &Value,
+) -> Result<Map<String, Value>> {
+    let arguments = match params {
+        Value::Null => Map::new(),
+        Value::Object(arguments) => arguments.clone(),
+        _ => {
+            return Err(BrowserError::InvalidArgument(
+                "Invalid parameters: expected an object".to_string(),
+            ));
+        }
+    };
+    let required = schema.get("required").and_then(Value::as_array);
+    for name in required.into_iter().flatten().filter_map(Value::as_str) {
+        if !arguments.contains_key(name) {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Invalid parameters: missing field `{}`",
+                name
+            )));
+        }
+    }
+    Ok(arguments)
+}
+
+impl CustomTool {
+    /// Validate a definition and build the tool
+    pub fn new(definition: CustomToolDefinition) -> Result<Self> {
+        let name = &definition.name;
+        check_tool_name(name)?;
+        if definition.script.trim().is_empty() {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Tool '{}' has an empty script",
+                name
+            )));
+        }
+        let schema = object_schema(name, definition.parameters.as_ref())?;
+
+        Ok(Self { definition, schema })
+    }
+
+    /// The definition the tool was built from
+    pub fn definition(&self) -> &CustomToolDefinition {
+        &self.definition
+    }
+
+    /// Give the tool another name, e.g. to namespace it
+    pub fn renamed(mut self, name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        check_tool_name(&name)?;
+        self.definition.name = name;
+        Ok(self)
+    }
+
+    /// JSON schema of the tool's arguments, always an object schema
+    pub fn schema(&self) -> &Map<String, Value> {
+        &self.schema
+    }
+
+    /// Run the script for a call, unless the evaluate policy forbids scripts
+    fn run(&self, params: Value, context: &mut ToolContext) -> Result<ToolResult> {
+        if context.session.evaluate_policy().access != EvaluateAccess::Unrestricted {
+            return Err(BrowserError::PolicyDenied(
+                "Custom tools need unrestricted JavaScript evaluation".to_string(),
+            ));
+        }
+        let arguments = check_arguments(&self.schema, &params)?;
+        let result = run_script(context, self.script_for(&arguments), true)?;
+        Ok(ToolResult::success_with(serde_json::to_value(result)?))
+    }
+
+    /// Script to evaluate for a call with these arguments
+    fn script_for(&self, arguments: &Map<String, Value>) -> String {
+        let declared = self
+            .schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flat_map(|properties| properties.keys());
+        let mut body = self.definition.script.clone();
+        for name in declared.chain(arguments.keys()) {
+            let value = arguments
+                .get(name)
+                .map_or_else(|| "undefined".to_string(), Value::to_string);
+            body = body.replace(&format!("{{{{{}}}}}", name), &value);
+        }
+        format!(
+            "(async (params) => {{\n{}\n}})({})",
+            body,
+            Value::Object(arguments.clone())
+        )
+    }
+}
+
+impl DynTool for CustomTool {
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    fn description(&self) -> &str {
+        &self.definition.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        Value::Object(self.schema.clone())
+    }
+
+    fn execute(&self, params: Value, context: &mut ToolContext) -> Result<ToolResult> {
+        self.run(params, context)
+            .map_err(|e| e.with_tool(self.name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(parameters: Option<Value>, script: &str) -> CustomToolDefinition {
+        CustomToolDefinition {
+            name: "cart_total".to_string(),
+            description: "Read the cart total".to_string(),
+            parameters,
+            script: script.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_custom_tool_validation() {
+        assert!(CustomTool::new(definition(None, "return 1")).is_ok());
+
+        let mut bad_name = definition(None, "return 1");
+        bad_name.name = "1st tool".to_string();
+        assert!(CustomTool::new(bad_name).is_err());
+
+        assert!(CustomTool::new(definition(None, "  ")).is_err());
+        assert!(
+            CustomTool::new(definition(
+                Some(serde_json::json!({ "type": "string" })),
+                "return 1"
+            ))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_custom_tool_schema() {
+        let tool = CustomTool::new(definition(None, "return 1")).unwrap();
+        assert_eq!(
+            tool.parameters_schema(),
+            serde_json::json!({ "type": "object", "properties": {} })
+        );
+    }
+
+    #[test]
+    fn test_custom_tool_arguments() {
+        let tool = CustomTool::new(definition(
+            Some(serde_json::json!({
+                "properties": {
+                    "currency": { "type": "string" },
+                    "round": { "type": "boolean" }
+                },
+                "required": ["currency"]
+            })),
+            "return [{{currency}}, {{round}}, params.currency];",
+        ))
+        .unwrap();
+
+        assert!(check_arguments(tool.schema(), &serde_json::json!({})).is_err());
+        assert!(check_arguments(tool.schema(), &serde_json::json!([1])).is_err());
+
+        let arguments =
+            check_arguments(tool.schema(), &serde_json::json!({ "currency": "EUR" })).unwrap();
+        let script = tool.script_for(&arguments);
+        assert!(script.contains(r#"return ["EUR", undefined, params.currency];"#));
+        assert!(script.ends_with(r#"})({"currency":"EUR"})"#));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::browser::network::{CapturedRequest, NetworkCapture};
+use crate::error::Result;
+use crate::tools::get_graphql_requests::parse_operations;
+use crate::tools::utils::json_shape;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use url::Url;
+
+/// Default number of endpoints returned
+const DEFAULT_LIMIT: usize = 50;
+
+/// Nesting depth past which response shapes are cut off
+const MAX_SHAPE_DEPTH: usize = 4;
+
+/// Non-JSON example bodies longer than this are truncated
+const MAX_BODY_CHARS: usize = 500;
+
+/// Parameters for the discover_api_endpoints tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DiscoverApiEndpointsParams {
+    /// Only include requests whose URL contains this text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_contains: Option<String>,
+
+    /// Include the shape of an example response per endpoint (default: true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_responses: Option<bool>,
+
+    /// Maximum number of endpoints to return (default: 50)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// Placeholder for a path segment that looks like an identifier, if it does
+fn segment_placeholder(segment: &str) -> Option<&'static str> {
+    let is_hex = |s: &str| s.chars().all(|c| c.is_ascii_hexdigit());
+    if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+        return Some("{id}");
+    }
+    let parts: Vec<&str> = segment.split('-').collect();
+    if parts.iter().map(|part| part.len()).eq([8, 4, 4, 4, 12]) && parts.iter().all(|p| is_hex(p)) {
+        return Some("{uuid}");
+    }
+    if segment.len() >= 16 && is_hex(segment) {
+        return Some("{hash}");
+    }
+    // Long opaque tokens mix letters and digits; slugs and words do not
+    if segment.len() >= 20
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && segment.chars().any(|c| c.is_ascii_digit())
+    {
+        return Some("{token}");
+    }
+    None
+}
+
+/// URL template for a request, with ID-like path segments and the query replaced
+///
+/// Returns the template and the sorted query parameter names.
+fn url_template(url: &str) -> (String, Vec<String>) {
+    let Ok(parsed) = Url::parse(url) else {
+        return (url.to_string(), Vec::new());
+    };
+    let path = parsed
+        .path()
+        .split('/')
+        .map(|segment| segment_placeholder(segment).unwrap_or(segment))
+        .collect::<Vec<_>>()
+        .join("/");
+    let params: BTreeSet<String> = parsed
+        .query_pairs()
+        .map(|(key, _)| key.into_owned())
+        .collect();
+
+    (
+        format!("{}{}", parsed.origin().ascii_serialization(), path),
+        params.into_iter().collect(),
+    )
+}
+
+/// Request body as JSON when it parses, otherwise as (truncated) text
+fn example_body(body: &str) -> Value {
+    serde_json::from_str(body).unwrap_or_else(|_| match body.char_indices().nth(MAX_BODY_CHARS) {
+        Some((end, _)) => Value::from(format!("{}...", &body[..end])),
+        None => Value::from(body),
+    })
+}
+
+/// Requests grouped under one method and URL template
+struct Endpoint<'a> {
+    method: &'a str,
+    template: String,
+    query_params: BTreeSet<String>,
+    statuses: BTreeSet<u32>,
+    examples: Vec<&'a CapturedRequest>,
+}
+
+/// Group requests by method and URL template, in order of first appearance
+fn group_requests(requests: &[CapturedRequest]) -> Vec<Endpoint<'_>> {
+    let mut endpoints: IndexMap<(String, String), Endpoint> = IndexMap::new();
+    for request in requests {
+        let (template, params) = url_template(&request.url);
+        let endpoint = endpoints
+            .entry((request.method.clone(), template.clone()))
+            .or_insert_with(|| Endpoint {
+                method: &request.method,
+                template,
+                query_params: BTreeSet::new(),
+                statuses: BTreeSet::new(),
+                examples: Vec::new(),
+            });
+        endpoint.query_params.extend(params);
+        endpoint.statuses.extend(request.status);
+        endpoint.examples.push(request);
+    }
+    endpoints.into_values().collect()
+}
+
+/// Tool for discovering the JSON APIs a page talks to
+///
+/// Summarizes the session's captured XHR/fetch requests as unique method and
+/// URL templates, with an example request body and response shape for each,
+/// so a site's data can be fetched directly instead of scraped from the DOM.
+/// Capture starts on the first call, so an empty result means the page
+/// should be reloaded or interacted with before calling again.
+#[derive(Default)]
+pub struct DiscoverApiEndpointsTool;
+
+impl Tool for DiscoverApiEndpointsTool {
+    type Params = DiscoverApiEndpointsParams;
+
+    fn name(&self) -> &str {
+        "discover_api_endpoints"
+    }
+
+    fn description(&self) -> &str {
+        "Summarize the XHR/fetch API calls the page has made since capture started as method and URL templates with example payloads and response shapes"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: DiscoverApiEndpointsParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let capture = context.session.network_capture()?;
+        let requests: Vec<CapturedRequest> = capture
+            .requests()
+            .into_iter()
+            .filter(|request| match &params.url_contains {
+                Some(text) => request.url.contains(text.as_str()),
+                None => true,
+            })
+            .collect();
+        let tabs = context.session.get_tabs()?;
+        let tab_for = |request: &CapturedRequest| {
+            tabs.iter()
+                .find(|tab| *tab.get_target_id() == request.target_id)
+        };
+        let include_responses = params.include_responses.unwrap_or(true);
+        let endpoints = group_requests(&requests);
+        let total = endpoints.len();
+
+        let summaries: Vec<Value> = endpoints
+            .into_iter()
+            .take(params.limit.unwrap_or(DEFAULT_LIMIT))
+            .map(|endpoint| {
+                let example = endpoint.examples[0];
+                let body =
+                    tab_for(example).and_then(|tab| NetworkCapture::request_body(tab, example));
+
+                let mut summary = serde_json::json!({
+                    "method": endpoint.method,
+                    "urlTemplate": endpoint.template,
+                    "queryParams": endpoint.query_params,
+                    "count": endpoint.examples.len(),
+                    "statuses": endpoint.statuses,
+                    "exampleUrl": example.url,
+                    "mimeType": example.mime_type,
+                });
+                if !parse_operations(&example.url, body.as_deref()).is_empty() {
+                    summary["graphql"] = Value::Bool(true);
+                }
+                if let Some(body) = &body {
+                    summary["exampleBody"] = example_body(body);
+                }
+
+                // Take the shape from the most recent example that still has a JSON body
+                let response = include_responses
+                    .then(|| {
+                        endpoint.examples.iter().rev().find_map(|request| {
+                            let tab = tab_for(request)?;
+                            let body = NetworkCapture::response_body(tab, request).ok()?;
+                            serde_json::from_str::<Value>(&body).ok()
+                        })
+                    })
+                    .flatten();
+                if let Some(response) = response {
+                    summary["responseShape"] = json_shape(&response, MAX_SHAPE_DEPTH);
+                }
+                summary
+            })
+            .collect();
+
+        let mut result = serde_json::json!({
+            "count": summaries.len(),
+            "totalEndpoints": total,
+            "requestsSeen": requests.len(),
+            "endpoints": summaries,
+        });
+        if requests.is_empty() {
+            result["note"] = Value::from(
+                "No XHR/fetch requests captured yet. Traffic is recorded from the first call on; \
+                 reload the page or interact with it, then call again.",
+            );
+        }
+
+        Ok(ToolResult::success_with(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, url: &str, status: u32) -> CapturedRequest {
+        CapturedRequest {
+            request_id: String::new(),
+            target_id: String::new(),
+            method: method.to_string(),
+            url: url.to_string(),
+            resource_type: "Fetch".to_string(),
+            post_data: None,
+            has_post_data: false,
+            status: Some(status),
+            mime_type: Some("application/json".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_discover_api_endpoints_params() {
+        let json = serde_json::json!({
+            "url_contains": "/api/",
+            "include_responses": false
+        });
+
+        let params: DiscoverApiEndpointsParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.url_contains.as_deref(), Some("/api/"));
+        assert_eq!(params.include_responses, Some(false));
+        assert_eq!(params.limit, None);
+    }
+
+    #[test]
+    fn test_url_template() {
+        assert_eq!(
+            url_template("https://example.com/api/users/42/posts?page=2&sort=new"),
+            (
+                "https://example.com/api/users/{id}/posts".to_string(),
+                vec!["page".to_string(), "sort".to_string()]
+            )
+        );
+        assert_eq!(
+            url_template("https://example.com/orders/3f2b8c1e-9a4d-4e6f-8b7a-1c2d3e4f5a6b").0,
+            "https://example.com/orders/{uuid}"
+        );
+        assert_eq!(
+            url_template("https://example.com/blob/9f86d081884c7d659a2feaa0c55ad015").0,
+            "https://example.com/blob/{hash}"
+        );
+        assert_eq!(
+            url_template("https://example.com/blog/how-to-bake-bread").0,
+            "https://example.com/blog/how-to-bake-bread"
+        );
+    }
+
+    #[test]
+    fn test_group_requests() {
+        let requests = vec![
+            request("GET", "https://example.com/api/items/1", 200),
+            request("GET", "https://example.com/api/items/2?fields=name", 404),
+            request("POST", "https://example.com/api/items", 201),
+            request("GET", "https://example.com/api/items/3", 200),
+        ];
+
+        let endpoints = group_requests(&requests);
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].template, "https://example.com/api/items/{id}");
+        assert_eq!(endpoints[0].examples.len(), 3);
+        assert_eq!(
+            endpoints[0].statuses.iter().copied().collect::<Vec<_>>(),
+            vec![200, 404]
+        );
+        assert!(endpoints[0].query_params.contains("fields"));
+        assert_eq!(endpoints[1].method, "POST");
+    }
+
+    #[test]
+    fn test_example_body() {
+        assert_eq!(
+            example_body(r#"{"q":"shoes"}"#),
+            serde_json::json!({ "q": "shoes" })
+        );
+        assert_eq!(example_body("a=1&b=2"), Value::from("a=1&b=2"));
+    }
+}
@@ -0,0 +1,167 @@
+(function () {
+  const config = __DISMISS_OVERLAYS_CONFIG__;
+
+  // Buttons of common consent managers: [name, accept selector, reject selector]
+  const CONSENT_MANAGERS = [
+    ["onetrust", "#onetrust-accept-btn-handler", "#onetrust-reject-all-handler"],
+    [
+      "cookiebot",
+      "#CybotCookiebotDialogBodyLevelButtonLevelOptinAllowAll, #CybotCookiebotDialogBodyButtonAccept",
+      "#CybotCookiebotDialogBodyButtonDecline",
+    ],
+    ["didomi", "#didomi-notice-agree-button", "#didomi-notice-disagree-button"],
+    [
+      "quantcast",
+      ".qc-cmp2-summary-buttons button[mode='primary']",
+      ".qc-cmp2-summary-buttons button[mode='secondary']",
+    ],
+    ["trustarc", "#truste-consent-button", "#truste-consent-required"],
+    ["cookieyes", ".cky-btn-accept", ".cky-btn-reject"],
+    ["complianz", ".cmplz-accept", ".cmplz-deny"],
+    ["osano", ".osano-cm-accept-all", ".osano-cm-denyAll"],
+    ["klaro", ".cm-btn-accept-all", ".cm-btn-decline"],
+    ["iubenda", ".iubenda-cs-accept-btn", ".iubenda-cs-reject-btn"],
+    ["borlabs", "a[data-cookie-accept-all]", "a[data-cookie-refuse]"],
+    ["cookie-notice", "#cn-accept-cookie", "#cn-refuse-cookie"],
+    ["civic", "#ccc-recommended-settings", "#ccc-reject-settings"],
+  ];
+
+  // Consent managers that render into an open shadow root: [name, host, accept, reject]
+  const SHADOW_CONSENT_MANAGERS = [
+    [
+      "usercentrics",
+      "#usercentrics-root",
+      "[data-testid='uc-accept-all-button']",
+      "[data-testid='uc-deny-all-button']",
+    ],
+    ["cmpbox", "#cmpwrapper", ".cmpboxbtnyes", ".cmpboxbtnno"],
+  ];
+
+  const ACCEPT_TEXT =
+    /^(accept|accept all|accept all cookies|accept cookies|allow all|allow all cookies|allow cookies|agree|i agree|agree and close|agree & close|got it|ok|okay|i understand|understood|continue|alle akzeptieren|akzeptieren|alle zulassen|zustimmen|einverstanden|tout accepter|accepter|j'accepte|aceptar|aceptar todo|aceptar todas|accetta|accetta tutto|accetto|alles accepteren|accepteren|akkoord|aceitar|aceitar tudo|zaakceptuj|acceptera|godkänn|hyväksy|accepter alle)$/i;
+  const REJECT_TEXT =
+    /^(reject|reject all|reject all cookies|decline|decline all|deny|refuse|only necessary|necessary only|use necessary cookies only|essential only|alle ablehnen|ablehnen|nur notwendige|tout refuser|refuser|rechazar|rechazar todo|rifiuta|rifiuta tutto|alles weigeren|weigeren|rejeitar|odrzuć|avvisa|hylkää)$/i;
+  const CLOSE_LABEL = /^(close|dismiss|schließen|fermer|cerrar|chiudi|sluiten|fechar|×|✕|x)$/i;
+  const CONSENT_HINT = /cookie|consent|gdpr|privacy|cmp|tracking|datenschutz/i;
+  const CLICKABLE =
+    "button, a, [role='button'], input[type='button'], input[type='submit']";
+
+  const isVisible = (el) => {
+    const rect = el.getBoundingClientRect();
+    const style = window.getComputedStyle(el);
+    return (
+      rect.width > 0 &&
+      rect.height > 0 &&
+      style.visibility !== "hidden" &&
+      style.display !== "none" &&
+      style.opacity !== "0"
+    );
+  };
+
+  const textOf = (el) =>
+    (el.innerText || el.value || el.getAttribute("aria-label") || el.textContent || "")
+      .trim()
+      .replace(/\s+/g, " ");
+
+  const describe = (el) => {
+    if (el.id) return `#${el.id}`;
+    const classes = typeof el.className === "string" ? el.className.trim().split(/\s+/) : [];
+    return el.tagName.toLowerCase() + (classes[0] ? `.${classes[0]}` : "");
+  };
+
+  const dismissed = [];
+  const handled = new Set();
+  // Whether el is inside, or holds, something already dismissed
+  const isHandled = (el) =>
+    Array.from(handled).some((other) => other.contains(el) || el.contains(other));
+
+  const click = (rule, button, container) => {
+    if (isHandled(container)) return false;
+    handled.add(container);
+    dismissed.push({
+      rule,
+      action: "clicked",
+      text: textOf(button).slice(0, 80),
+      target: describe(container),
+    });
+    button.click();
+    return true;
+  };
+
+  // Accept button first, or reject first when asked to decline
+  const pick = (accept, reject) => (config.reject ? reject || accept : accept || reject);
+
+  // 1. Known consent managers
+  for (const [name, accept, reject] of CONSENT_MANAGERS) {
+    const find = (selector) =>
+      Array.from(document.querySelectorAll(selector)).find(isVisible) || null;
+    const button = pick(find(accept), find(reject));
+    if (button) click(`consent-manager:${name}`, button, button);
+  }
+  for (const [name, host, accept, reject] of SHADOW_CONSENT_MANAGERS) {
+    const root = document.querySelector(host)?.shadowRoot;
+    if (!root) continue;
+    const find = (selector) =>
+      Array.from(root.querySelectorAll(selector)).find(isVisible) || null;
+    const button = pick(find(accept), find(reject));
+    if (button) click(`consent-manager:${name}`, button, button);
+  }
+
+  // 2. Dialogs and banners offering an accept, reject or close button
+  const buttonsOf = (container) =>
+    Array.from(container.querySelectorAll(CLICKABLE)).filter(isVisible);
+  const dialogs = Array.from(
+    document.querySelectorAll(
+      "[role='dialog'], [role='alertdialog'], [aria-modal='true'], dialog[open], " +
+        "[id*='cookie' i], [class*='cookie' i], [id*='consent' i], [class*='consent' i], " +
+        "[id*='gdpr' i], [class*='gdpr' i]"
+    )
+  ).filter(isVisible);
+  for (const dialog of dialogs) {
+    // Nested matches belong to a dialog already handled
+    if (isHandled(dialog)) continue;
+    const buttons = buttonsOf(dialog);
+    const accept = buttons.find((el) => ACCEPT_TEXT.test(textOf(el)));
+    const reject = buttons.find((el) => REJECT_TEXT.test(textOf(el)));
+    const close = buttons.find(
+      (el) =>
+        CLOSE_LABEL.test(el.getAttribute("aria-label") || "") ||
+        CLOSE_LABEL.test(el.getAttribute("title") || "") ||
+        CLOSE_LABEL.test(textOf(el))
+    );
+    const consent = CONSENT_HINT.test(`${dialog.id} ${dialog.className} ${textOf(dialog).slice(0, 500)}`);
+    // Accepting is only safe on consent banners; other dialogs are closed
+    const button = consent ? pick(accept, reject) || close : close;
+    if (button) click(consent ? "consent-banner" : "dialog", button, dialog);
+  }
+
+  // 3. Full-screen fixed overlays without a button to close them
+  if (config.removeBlocking) {
+    const viewport = window.innerWidth * window.innerHeight;
+    for (const el of document.body ? document.body.querySelectorAll("*") : []) {
+      const style = window.getComputedStyle(el);
+      if (style.position !== "fixed") continue;
+      if (!isVisible(el) || isHandled(el)) continue;
+      const rect = el.getBoundingClientRect();
+      const covered =
+        Math.max(0, Math.min(rect.right, window.innerWidth) - Math.max(rect.left, 0)) *
+        Math.max(0, Math.min(rect.bottom, window.innerHeight) - Math.max(rect.top, 0));
+      if (covered < viewport * 0.5) continue;
+      // Keep app shells: only remove overlays that sit above the content
+      if ((parseInt(style.zIndex, 10) || 0) < 1) continue;
+      handled.add(el);
+      dismissed.push({ rule: "fullscreen-overlay", action: "removed", text: textOf(el).slice(0, 80), target: describe(el) });
+      el.style.setProperty("display", "none", "important");
+    }
+    if (dismissed.length) {
+      // Overlays usually lock scrolling while they are shown
+      for (const el of [document.documentElement, document.body]) {
+        if (el && window.getComputedStyle(el).overflow === "hidden") {
+          el.style.setProperty("overflow", "auto", "important");
+        }
+      }
+    }
+  }
+
+  return JSON.stringify({ dismissed });
+})()
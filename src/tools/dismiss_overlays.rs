@@ -0,0 +1,160 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Most passes made over the page; banners sometimes open a second step
+const MAX_PASSES: usize = 3;
+
+/// Time given to a banner's closing animation before looking again
+const SETTLE_DELAY: Duration = Duration::from_millis(400);
+
+const DISMISS_OVERLAYS_JS: &str = include_str!("dismiss_overlays.js");
+
+/// Parameters for the dismiss_overlays tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DismissOverlaysParams {
+    /// Prefer "reject all" / "only necessary" over "accept" on cookie banners (default: false)
+    #[serde(default)]
+    pub reject: bool,
+
+    /// Hide full-screen fixed overlays that offer no button to close them (default: true)
+    #[serde(default = "default_remove_blocking")]
+    pub remove_blocking: bool,
+}
+
+impl Default for DismissOverlaysParams {
+    fn default() -> Self {
+        Self {
+            reject: false,
+            remove_blocking: default_remove_blocking(),
+        }
+    }
+}
+
+fn default_remove_blocking() -> bool {
+    true
+}
+
+/// An overlay the script closed or hid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DismissedOverlay {
+    /// Heuristic that matched, e.g. "consent-manager:onetrust", "consent-banner",
+    /// "dialog" or "fullscreen-overlay"
+    pub rule: String,
+    /// "clicked" when a button was clicked, "removed" when the overlay was hidden
+    pub action: String,
+    /// Text of the clicked button, or the start of the hidden overlay's text
+    pub text: String,
+    /// Short selector of the banner or overlay
+    pub target: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PassResult {
+    dismissed: Vec<DismissedOverlay>,
+}
+
+/// Tool for closing cookie-consent banners, modal dialogs and blocking overlays
+///
+/// Clicks the accept (or reject) button of common consent managers, then of
+/// any visible dialog or cookie banner, and finally hides full-screen fixed
+/// overlays. Banners inside cross-origin iframes are out of reach.
+#[derive(Default)]
+pub struct DismissOverlaysTool;
+
+impl Tool for DismissOverlaysTool {
+    type Params = DismissOverlaysParams;
+
+    fn name(&self) -> &str {
+        "dismiss_overlays"
+    }
+
+    fn description(&self) -> &str {
+        "Close cookie-consent banners, modal dialogs and full-screen overlays that block the page (accepts by default, or rejects with reject=true)"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        params: DismissOverlaysParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let tool_error = |reason: String| BrowserError::ToolExecutionFailed {
+            tool: "dismiss_overlays".to_string(),
+            reason,
+        };
+
+        let config = serde_json::json!({
+            "reject": params.reject,
+            "removeBlocking": params.remove_blocking,
+        });
+        let js = DISMISS_OVERLAYS_JS.replace("__DISMISS_OVERLAYS_CONFIG__", &config.to_string());
+
+        let tab = context.session.tab()?;
+        let mut dismissed = Vec::new();
+        for pass in 0..MAX_PASSES {
+            if pass > 0 {
+                std::thread::sleep(SETTLE_DELAY);
+            }
+            let result: PassResult = tab
+                .evaluate(&js, false)
+                .map_err(|e| tool_error(e.to_string()))?
+                .value
+                .and_then(|v| v.as_str().map(String::from))
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .ok_or_else(|| tool_error("No result returned".to_string()))?;
+            // A banner still fading out is matched again; count it once
+            let new: Vec<DismissedOverlay> = result
+                .dismissed
+                .into_iter()
+                .filter(|overlay| {
+                    !dismissed.iter().any(|seen: &DismissedOverlay| {
+                        seen.rule == overlay.rule && seen.target == overlay.target
+                    })
+                })
+                .collect();
+            if new.is_empty() {
+                break;
+            }
+            context.session.record_page_action();
+            dismissed.extend(new);
+        }
+
+        let message = if dismissed.is_empty() {
+            "No overlays found".to_string()
+        } else {
+            format!("Dismissed {} overlay(s)", dismissed.len())
+        };
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "count": dismissed.len(),
+            "dismissed": dismissed,
+            "message": message
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dismiss_overlays_params() {
+        let params: DismissOverlaysParams = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(!params.reject);
+        assert!(params.remove_blocking);
+
+        let params: DismissOverlaysParams = serde_json::from_value(serde_json::json!({
+            "reject": true,
+            "remove_blocking": false
+        }))
+        .unwrap();
+        assert!(params.reject);
+        assert!(!params.remove_blocking);
+    }
+}
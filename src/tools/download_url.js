@@ -0,0 +1,43 @@
+(async function () {
+  const config = __DOWNLOAD_CONFIG__;
+  try {
+    // credentials: "include" sends the page's cookies, like a click would
+    const response = await fetch(config.url, { credentials: "include" });
+    if (!response.ok) {
+      return JSON.stringify({
+        error: "HTTP " + response.status + " " + response.statusText,
+      });
+    }
+
+    const declared = Number(response.headers.get("content-length") || 0);
+    if (declared > config.maxBytes) {
+      return JSON.stringify({
+        error: "File is " + declared + " bytes, over the " + config.maxBytes + " byte limit",
+      });
+    }
+
+    const blob = await response.blob();
+    if (blob.size > config.maxBytes) {
+      return JSON.stringify({
+        error: "File is " + blob.size + " bytes, over the " + config.maxBytes + " byte limit",
+      });
+    }
+
+    const dataUrl = await new Promise((resolve, reject) => {
+      const reader = new FileReader();
+      reader.onload = () => resolve(reader.result);
+      reader.onerror = () => reject(reader.error);
+      reader.readAsDataURL(blob);
+    });
+
+    return JSON.stringify({
+      data: dataUrl.slice(dataUrl.indexOf(",") + 1),
+      contentType: response.headers.get("content-type"),
+      disposition: response.headers.get("content-disposition"),
+      finalUrl: response.url,
+    });
+  } catch (e) {
+    // Cross-origin files without CORS headers end up here
+    return JSON.stringify({ error: String(e) });
+  }
+})()
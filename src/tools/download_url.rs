@@ -0,0 +1,274 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use headless_chrome::Tab;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Default size limit for a download, in bytes
+pub(crate) const DEFAULT_MAX_BYTES: u64 = 25 * 1024 * 1024;
+
+/// File name used when neither the response nor the URL suggests one
+const FALLBACK_FILE_NAME: &str = "download";
+
+/// Parameters for the download_url tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DownloadUrlParams {
+    /// URL of the file, absolute or relative to the current page
+    pub url: String,
+
+    /// Path to save the file to (default: the file name from the response or URL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Replace the file if it already exists (default: false)
+    #[serde(default)]
+    pub overwrite: bool,
+
+    /// Refuse files larger than this many bytes (default: 25 MB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+}
+
+/// A file fetched from inside the page
+pub(crate) struct FetchedBytes {
+    pub bytes: Vec<u8>,
+    pub content_type: Option<String>,
+    pub disposition: Option<String>,
+    /// URL after redirects
+    pub final_url: Url,
+}
+
+/// Response of the in-page fetch
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FetchedFile {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    data: String,
+    #[serde(default)]
+    content_type: Option<String>,
+    #[serde(default)]
+    disposition: Option<String>,
+    #[serde(default)]
+    final_url: Option<String>,
+}
+
+/// Resolve a tool-supplied URL against the page's URL
+pub(crate) fn resolve_url(page_url: &str, url: &str) -> Result<Url> {
+    Url::parse(page_url)
+        .and_then(|base| base.join(url))
+        .or_else(|_| Url::parse(url))
+        .map_err(|e| BrowserError::InvalidArgument(format!("Invalid URL '{}': {}", url, e)))
+}
+
+/// Fetch `url` from inside the page, so the session's cookies and origin apply
+///
+/// Callers are responsible for scope, robots.txt and rate limit checks.
+pub(crate) fn fetch_in_page(tab: &Tab, url: &Url, max_bytes: u64) -> Result<FetchedBytes> {
+    let config = serde_json::json!({
+        "url": url.as_str(),
+        "maxBytes": max_bytes,
+    });
+    let download_js = DOWNLOAD_URL_JS.replace("__DOWNLOAD_CONFIG__", &config.to_string());
+
+    let result = tab
+        .evaluate(&download_js, true)
+        .map_err(|e| BrowserError::DownloadFailed(e.to_string()))?;
+    let fetched: FetchedFile = result
+        .value
+        .and_then(|v| v.as_str().map(String::from))
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .ok_or_else(|| BrowserError::DownloadFailed("No result returned".to_string()))?;
+    if let Some(error) = fetched.error {
+        return Err(BrowserError::DownloadFailed(format!("{}: {}", url, error)));
+    }
+
+    let bytes = STANDARD
+        .decode(&fetched.data)
+        .map_err(|e| BrowserError::DownloadFailed(format!("Invalid file data: {}", e)))?;
+    let final_url = fetched
+        .final_url
+        .as_deref()
+        .and_then(|final_url| Url::parse(final_url).ok())
+        .unwrap_or_else(|| url.clone());
+
+    Ok(FetchedBytes {
+        bytes,
+        content_type: fetched.content_type,
+        disposition: fetched.disposition,
+        final_url,
+    })
+}
+
+/// Pick a file name from a Content-Disposition header, falling back to the URL's last segment
+fn file_name_for(url: &Url, disposition: Option<&str>) -> String {
+    let from_header = disposition.and_then(|header| {
+        let mut plain = None;
+        for part in header.split(';').map(str::trim) {
+            if let Some(value) = part.strip_prefix("filename*=") {
+                // RFC 5987: charset'language'percent-encoded
+                let encoded = value.rsplit('\'').next().unwrap_or(value);
+                return Some(percent_decode(encoded));
+            }
+            if let Some(value) = part.strip_prefix("filename=") {
+                plain = Some(value.trim_matches('"').to_string());
+            }
+        }
+        plain
+    });
+    let from_url = || {
+        url.path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .map(percent_decode)
+    };
+
+    from_header
+        .or_else(from_url)
+        .map(|name| sanitize_file_name(&name))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| FALLBACK_FILE_NAME.to_string())
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+            continue;
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Keep only characters that are safe in a single path component
+fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    name.trim().trim_start_matches('.').to_string()
+}
+
+/// Tool for downloading a file with the page's cookies
+///
+/// The file is fetched from inside the page so the session's cookies and
+/// origin apply, then written under the session's output directory.
+#[derive(Default)]
+pub struct DownloadUrlTool;
+
+const DOWNLOAD_URL_JS: &str = include_str!("download_url.js");
+
+impl Tool for DownloadUrlTool {
+    type Params = DownloadUrlParams;
+
+    fn name(&self) -> &str {
+        "download_url"
+    }
+
+    fn description(&self) -> &str {
+        "Download a file URL using the page's cookies and save it to the output directory, returning path, size and content type"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        params: DownloadUrlParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let tab = context.session.tab()?;
+        let url = resolve_url(&tab.get_url(), &params.url)?;
+
+        context.session.check_scope(url.as_str())?;
+        context
+            .session
+            .confirm_action("download_url", None, Some(url.as_str()))?;
+        context.session.check_robots(url.as_str())?;
+        let _permit = context.session.throttle(url.as_str());
+
+        let fetched = fetch_in_page(&tab, &url, params.max_bytes.unwrap_or(DEFAULT_MAX_BYTES))?;
+
+        let path = match params.path {
+            Some(path) => path,
+            None => file_name_for(&fetched.final_url, fetched.disposition.as_deref()),
+        };
+        let path = context.session.output_file(&path, params.overwrite)?;
+
+        std::fs::write(&path, &fetched.bytes).map_err(|e| {
+            BrowserError::DownloadFailed(format!("Failed to save {}: {}", path.display(), e))
+        })?;
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "url": fetched.final_url.as_str(),
+            "path": path.to_string_lossy(),
+            "size_bytes": fetched.bytes.len(),
+            "content_type": fetched.content_type
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_url_params() {
+        let json = serde_json::json!({
+            "url": "/files/report.pdf"
+        });
+
+        let params: DownloadUrlParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.url, "/files/report.pdf");
+        assert_eq!(params.path, None);
+        assert!(!params.overwrite);
+        assert_eq!(params.max_bytes, None);
+    }
+
+    #[test]
+    fn test_file_name_from_url() {
+        let url = Url::parse("https://example.com/docs/Annual%20Report.pdf?x=1").unwrap();
+        assert_eq!(file_name_for(&url, None), "Annual Report.pdf");
+
+        let url = Url::parse("https://example.com/").unwrap();
+        assert_eq!(file_name_for(&url, None), "download");
+    }
+
+    #[test]
+    fn test_file_name_from_disposition() {
+        let url = Url::parse("https://example.com/get?id=7").unwrap();
+        assert_eq!(
+            file_name_for(&url, Some("attachment; filename=\"invoice 7.pdf\"")),
+            "invoice 7.pdf"
+        );
+        assert_eq!(
+            file_name_for(
+                &url,
+                Some("attachment; filename=\"x.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf")
+            ),
+            "résumé.pdf"
+        );
+        assert_eq!(
+            file_name_for(&url, Some("attachment; filename=\"../../etc/passwd\"")),
+            "_.._etc_passwd"
+        );
+    }
+}
@@ -1,13 +1,21 @@
+use crate::browser::policy::LimitedResult;
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use headless_chrome::protocol::cdp::Runtime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct EvaluateParams {
-    /// JavaScript code to execute
-    pub code: String,
+    /// JavaScript code to execute (use either this or snippet)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// Name of a server-provided snippet to execute instead of code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
 
     /// Wait for promise resolution (default: false)
     #[serde(default)]
@@ -24,21 +32,81 @@ impl Tool for EvaluateTool {
         "evaluate"
     }
 
+    fn description(&self) -> &str {
+        "Execute JavaScript code in the browser context"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
     fn execute_typed(
         &self,
         params: EvaluateParams,
         context: &mut ToolContext,
     ) -> Result<ToolResult> {
+        let policy = context.session.evaluate_policy();
+        let code = policy.resolve(params.code.as_deref(), params.snippet.as_deref())?;
+        // Scripts can change anything, including state the DOM does not show
+        let result = run_script(context, code, params.await_promise);
+        context.session.record_page_action();
+        Ok(ToolResult::success_with(serde_json::to_value(result?)?))
+    }
+}
+
+/// Run already-approved code in the active tab under the session's time and result size limits
+pub(crate) fn run_script(
+    context: &ToolContext,
+    code: String,
+    await_promise: bool,
+) -> Result<LimitedResult> {
+    let policy = context.session.evaluate_policy();
+    let Some(timeout_ms) = policy.timeout_ms else {
         let result = context
             .session
             .tab()?
-            .evaluate(&params.code, params.await_promise)
+            .evaluate(&code, await_promise)
             .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
 
         let result_value = result.value.unwrap_or(Value::Null);
+        return policy.limit_result(result_value, context.session.output_dir());
+    };
 
-        Ok(ToolResult::success_with(serde_json::json!({
-            "result": result_value
-        })))
+    // Same call as Tab::evaluate, plus a CDP-enforced execution timeout
+    let started = Instant::now();
+    let response = context
+        .session
+        .tab()?
+        .call_method(Runtime::Evaluate {
+            expression: code,
+            return_by_value: Some(false),
+            generate_preview: Some(true),
+            silent: Some(false),
+            await_promise: Some(await_promise),
+            include_command_line_api: Some(false),
+            user_gesture: Some(false),
+            object_group: None,
+            context_id: None,
+            throw_on_side_effect: None,
+            timeout: Some(timeout_ms as f64),
+            disable_breaks: None,
+            repl_mode: None,
+            allow_unsafe_eval_blocked_by_csp: None,
+            unique_context_id: None,
+            serialization_options: None,
+        })
+        .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
+
+    // A terminated script surfaces as an exception once the limit has elapsed
+    if response.exception_details.is_some()
+        && started.elapsed() >= Duration::from_millis(timeout_ms)
+    {
+        return Err(BrowserError::Timeout(format!(
+            "Script exceeded the {} ms evaluation limit",
+            timeout_ms
+        )));
     }
+
+    let result_value = response.result.value.unwrap_or(Value::Null);
+    policy.limit_result(result_value, context.session.output_dir())
 }
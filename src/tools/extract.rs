@@ -1,9 +1,9 @@
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ExtractParams {
     /// CSS selector (optional, defaults to body)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -28,6 +28,14 @@ impl Tool for ExtractContentTool {
         "extract"
     }
 
+    fn description(&self) -> &str {
+        "Extract the text or HTML of the page or of an element by CSS selector"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
     fn execute_typed(
         &self,
         params: ExtractParams,
@@ -72,10 +80,20 @@ impl Tool for ExtractContentTool {
                 .unwrap_or_default()
         };
 
-        Ok(ToolResult::success_with(serde_json::json!({
-            "content": content,
+        let length = content.len();
+        let limited = context
+            .session
+            .evaluate_policy()
+            .limit_result(content.into(), context.session.output_dir())?;
+
+        let mut data = serde_json::json!({
+            "content": limited.result,
             "format": params.format,
-            "length": content.len()
-        })))
+            "length": length
+        });
+        if let Some(truncated) = limited.truncated {
+            data["truncated"] = serde_json::to_value(truncated)?;
+        }
+        Ok(ToolResult::success_with(data))
     }
 }
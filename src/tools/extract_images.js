@@ -0,0 +1,58 @@
+JSON.stringify(
+  (function () {
+    const config = __CONFIG__;
+    const viewportWidth = window.innerWidth || document.documentElement.clientWidth;
+    const viewportHeight = window.innerHeight || document.documentElement.clientHeight;
+    const lazyAttributes = ["data-src", "data-srcset", "data-lazy", "data-lazy-src", "data-original"];
+
+    const images = [];
+    document.querySelectorAll("img").forEach(function (img, position) {
+      const rect = img.getBoundingClientRect();
+      const style = window.getComputedStyle(img);
+      const visible =
+        rect.width > 0 &&
+        rect.height > 0 &&
+        style.display !== "none" &&
+        style.visibility !== "hidden" &&
+        parseFloat(style.opacity) > 0;
+
+      if (config.visibleOnly && !visible) {
+        return;
+      }
+
+      const lazyAttribute = lazyAttributes.find(function (name) {
+        return img.hasAttribute(name);
+      });
+      const loading = (img.getAttribute("loading") || "auto").toLowerCase();
+
+      let loadState = "pending";
+      if (img.complete) {
+        loadState = img.naturalWidth > 0 ? "loaded" : "broken";
+      }
+
+      images.push({
+        position: position,
+        src: img.currentSrc || img.src || "",
+        srcset: img.getAttribute("srcset") || "",
+        lazySrc: lazyAttribute ? img.getAttribute(lazyAttribute) : "",
+        alt: img.hasAttribute("alt") ? img.getAttribute("alt") : null,
+        title: img.getAttribute("title") || "",
+        naturalWidth: img.naturalWidth,
+        naturalHeight: img.naturalHeight,
+        renderedWidth: Math.round(rect.width),
+        renderedHeight: Math.round(rect.height),
+        visible: visible,
+        inViewport:
+          visible &&
+          rect.bottom > 0 &&
+          rect.right > 0 &&
+          rect.top < viewportHeight &&
+          rect.left < viewportWidth,
+        lazy: loading === "lazy" || !!lazyAttribute,
+        loadState: loadState,
+      });
+    });
+
+    return images;
+  })()
+)
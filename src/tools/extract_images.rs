@@ -0,0 +1,108 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the extract_images tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExtractImagesParams {
+    /// Only list images that are currently rendered (default: false)
+    #[serde(default)]
+    pub visible_only: bool,
+}
+
+/// An image element found on the page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PageImage {
+    /// Position among all `<img>` elements in document order
+    pub position: usize,
+    /// Absolute URL of the image currently shown
+    pub src: String,
+    /// Raw srcset attribute
+    pub srcset: String,
+    /// Deferred source from a lazy-loading attribute such as data-src
+    pub lazy_src: String,
+    /// Alt text, or null when the alt attribute is missing
+    pub alt: Option<String>,
+    /// Title attribute
+    pub title: String,
+    /// Intrinsic width of the loaded image
+    pub natural_width: u32,
+    /// Intrinsic height of the loaded image
+    pub natural_height: u32,
+    /// Width as laid out on the page
+    pub rendered_width: u32,
+    /// Height as laid out on the page
+    pub rendered_height: u32,
+    /// Whether the image is rendered (non-zero size, not hidden)
+    pub visible: bool,
+    /// Whether the image is visible within the current viewport
+    pub in_viewport: bool,
+    /// Whether the image uses native or script-based lazy loading
+    pub lazy: bool,
+    /// "loaded", "pending" or "broken"
+    pub load_state: String,
+}
+
+/// Tool for listing the images on the current page
+#[derive(Default)]
+pub struct ExtractImagesTool;
+
+const EXTRACT_IMAGES_JS: &str = include_str!("extract_images.js");
+
+impl Tool for ExtractImagesTool {
+    type Params = ExtractImagesParams;
+
+    fn name(&self) -> &str {
+        "extract_images"
+    }
+
+    fn description(&self) -> &str {
+        "List images on the current page with src, alt text, natural and rendered size, visibility and lazy-load status"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: ExtractImagesParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let config = serde_json::json!({
+            "visibleOnly": params.visible_only
+        });
+        let js = EXTRACT_IMAGES_JS.replace("__CONFIG__", &config.to_string());
+
+        let result = context
+            .session
+            .tab()?
+            .evaluate(&js, false)
+            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
+
+        let images: Vec<PageImage> = result
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .ok_or_else(|| BrowserError::ToolExecutionFailed {
+                tool: "extract_images".to_string(),
+                reason: "Failed to parse image list".to_string(),
+            })?;
+
+        let missing_alt = images.iter().filter(|img| img.alt.is_none()).count();
+        let broken = images
+            .iter()
+            .filter(|img| img.load_state == "broken")
+            .count();
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "images": images,
+            "count": images.len(),
+            "missingAlt": missing_alt,
+            "broken": broken
+        })))
+    }
+}
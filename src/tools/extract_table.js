@@ -0,0 +1,48 @@
+(function () {
+  const config = __TABLE_CONFIG__;
+
+  const cellText = (cell) =>
+    (cell.innerText || cell.textContent || "").trim().replace(/\s+/g, " ");
+
+  let table;
+  if (config.selector) {
+    table = document.querySelector(config.selector);
+    if (!table) {
+      return JSON.stringify({ error: "Table not found: " + config.selector });
+    }
+  } else {
+    // The table with the most rows is usually the data table
+    table = Array.from(document.querySelectorAll("table")).reduce(
+      (best, t) => (!best || t.rows.length > best.rows.length ? t : best),
+      null
+    );
+    if (!table) {
+      return JSON.stringify({ error: "No table found on the page" });
+    }
+  }
+
+  const rows = Array.from(table.querySelectorAll("tr")).filter(
+    (tr) => tr.closest("table") === table
+  );
+
+  let headers = [];
+  let bodyRows = rows;
+  const headRow = table.tHead && table.tHead.rows[0];
+  if (headRow) {
+    headers = Array.from(headRow.cells).map(cellText);
+    bodyRows = rows.filter((tr) => tr.parentElement !== table.tHead);
+  } else if (
+    rows.length > 0 &&
+    Array.from(rows[0].cells).every((cell) => cell.tagName === "TH")
+  ) {
+    headers = Array.from(rows[0].cells).map(cellText);
+    bodyRows = rows.slice(1);
+  }
+
+  return JSON.stringify({
+    headers,
+    rows: bodyRows
+      .map((tr) => Array.from(tr.cells).map(cellText))
+      .filter((cells) => cells.some((cell) => cell.length > 0)),
+  });
+})()
@@ -0,0 +1,240 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::go_next_page::{DEFAULT_TIMEOUT_MS, follow_next_page};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Default number of pages to read
+const DEFAULT_MAX_PAGES: usize = 10;
+
+/// Hard cap on pages read in one call, whatever `max_pages` says
+const PAGE_LIMIT: usize = 100;
+
+/// Attempts to find the table on a freshly loaded page before giving up
+const TABLE_RETRIES: usize = 5;
+
+/// Parameters for the extract_table_all_pages tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExtractTableAllPagesParams {
+    /// CSS selector of the table (default: the table with the most rows)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Maximum number of pages to read (default: 10, at most 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<usize>,
+
+    /// Drop rows identical to a row already collected
+    #[serde(default)]
+    pub dedup: bool,
+
+    /// Exact text of the next-page control, for sites whose label the heuristics miss
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_text: Option<String>,
+
+    /// Time to wait for each next page to load, in milliseconds (default: 10000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// One page of a table, as read by the page script
+#[derive(Debug, Deserialize)]
+struct TablePage {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    headers: Vec<String>,
+    #[serde(default)]
+    rows: Vec<Vec<String>>,
+}
+
+/// Why extract_table_all_pages stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StopReason {
+    /// No next-page control was found
+    LastPage,
+    /// The next-page control did not change the page
+    NoChange,
+    /// The next page showed the same rows as the previous one
+    Repeated,
+    /// The page cap was reached
+    MaxPages,
+}
+
+/// Tool for reading a paginated table across all its pages
+///
+/// Reads the table, follows the next-page control (see `go_next_page`) and
+/// repeats until the last page or the page cap, returning the merged rows.
+#[derive(Default)]
+pub struct ExtractTableAllPagesTool;
+
+const EXTRACT_TABLE_JS: &str = include_str!("extract_table.js");
+
+fn read_table(context: &ToolContext, table_js: &str) -> Result<TablePage> {
+    let mut last_error = None;
+    for attempt in 0..TABLE_RETRIES {
+        if attempt > 0 {
+            // The next page may still be rendering its table
+            std::thread::sleep(Duration::from_millis(500));
+        }
+
+        let result = context
+            .session
+            .tab()?
+            .evaluate(table_js, false)
+            .map_err(|e| BrowserError::ToolExecutionFailed {
+                tool: "extract_table_all_pages".to_string(),
+                reason: e.to_string(),
+            })?;
+        let page: TablePage = result
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .ok_or_else(|| BrowserError::ToolExecutionFailed {
+                tool: "extract_table_all_pages".to_string(),
+                reason: "No result returned".to_string(),
+            })?;
+
+        match page.error {
+            Some(error) => last_error = Some(error),
+            None => return Ok(page),
+        }
+    }
+    Err(BrowserError::ElementNotFound(last_error.unwrap_or_else(
+        || "No table found on the page".to_string(),
+    )))
+}
+
+impl Tool for ExtractTableAllPagesTool {
+    type Params = ExtractTableAllPagesParams;
+
+    fn name(&self) -> &str {
+        "extract_table_all_pages"
+    }
+
+    fn description(&self) -> &str {
+        "Extract a paginated table across all its pages by following the next-page control, returning the merged rows"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        params: ExtractTableAllPagesParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let max_pages = params
+            .max_pages
+            .unwrap_or(DEFAULT_MAX_PAGES)
+            .clamp(1, PAGE_LIMIT);
+        let timeout = Duration::from_millis(params.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+
+        let config = serde_json::json!({ "selector": params.selector });
+        let table_js = EXTRACT_TABLE_JS.replace("__TABLE_CONFIG__", &config.to_string());
+
+        let mut headers = Vec::new();
+        let mut rows = Vec::new();
+        let mut seen = HashSet::new();
+        let mut duplicates = 0;
+        let mut previous_rows: Option<Vec<Vec<String>>> = None;
+        let mut pages = 0;
+        let stop_reason = loop {
+            let page = read_table(context, &table_js)?;
+            if previous_rows.as_ref() == Some(&page.rows) {
+                break StopReason::Repeated;
+            }
+            pages += 1;
+
+            if headers.is_empty() {
+                headers = page.headers;
+            }
+            for row in &page.rows {
+                if params.dedup && !seen.insert(row.clone()) {
+                    duplicates += 1;
+                    continue;
+                }
+                rows.push(row.clone());
+            }
+            previous_rows = Some(page.rows);
+
+            if pages >= max_pages {
+                break StopReason::MaxPages;
+            }
+            let next = follow_next_page(
+                context,
+                "extract_table_all_pages",
+                params.next_text.as_deref(),
+                timeout,
+            )?;
+            if !next.found {
+                break StopReason::LastPage;
+            }
+            if next.change.is_none() {
+                break StopReason::NoChange;
+            }
+        };
+
+        let message = format!(
+            "Collected {} rows from {} page(s){}",
+            rows.len(),
+            pages,
+            match stop_reason {
+                StopReason::LastPage => "; reached the last page.",
+                StopReason::NoChange => "; the next-page control stopped changing the page.",
+                StopReason::Repeated => "; the next page repeated the previous rows.",
+                StopReason::MaxPages => "; stopped at the page limit.",
+            }
+        );
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "headers": headers,
+            "rows": rows,
+            "rowCount": rows.len(),
+            "pages": pages,
+            "duplicatesRemoved": duplicates,
+            "stopReason": stop_reason,
+            "url": context.session.tab()?.get_url(),
+            "message": message
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_table_all_pages_params() {
+        let params: ExtractTableAllPagesParams =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(params.selector, None);
+        assert_eq!(params.max_pages, None);
+        assert!(!params.dedup);
+
+        let params: ExtractTableAllPagesParams = serde_json::from_value(serde_json::json!({
+            "selector": "#prices",
+            "max_pages": 3,
+            "dedup": true
+        }))
+        .unwrap();
+        assert_eq!(params.selector.as_deref(), Some("#prices"));
+        assert_eq!(params.max_pages, Some(3));
+        assert!(params.dedup);
+    }
+
+    #[test]
+    fn test_table_page_parsing() {
+        let page: TablePage = serde_json::from_str(
+            r#"{"headers": ["Name", "Price"], "rows": [["Apple", "1.00"], ["Pear", "2.00"]]}"#,
+        )
+        .unwrap();
+        assert_eq!(page.headers, vec!["Name", "Price"]);
+        assert_eq!(page.rows.len(), 2);
+        assert!(page.error.is_none());
+    }
+}
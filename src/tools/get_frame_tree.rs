@@ -0,0 +1,208 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use headless_chrome::protocol::cdp::Page;
+use serde::{Deserialize, Serialize};
+
+/// A frame of the page and the frames nested in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FrameInfo {
+    /// Frame id, as used by the DevTools protocol
+    pub id: String,
+    /// Value of the frame's name attribute, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Document URL, including the fragment
+    pub url: String,
+    /// Security origin, e.g. "https://example.com"; "://" for about:blank frames
+    pub security_origin: String,
+    /// MIME type of the document
+    pub mime_type: String,
+    /// Whether the frame's origin differs from its parent's
+    pub cross_origin: bool,
+    /// URL that failed to load, when the frame shows an error page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unreachable_url: Option<String>,
+    /// Nesting depth, 0 for the main frame
+    pub depth: usize,
+    /// Child frames in document order
+    pub children: Vec<FrameInfo>,
+}
+
+impl FrameInfo {
+    /// Build the frame hierarchy from a DevTools frame tree
+    pub fn from_tree(tree: Page::FrameTree) -> Self {
+        Self::from_subtree(tree, None, 0)
+    }
+
+    fn from_subtree(tree: Page::FrameTree, parent_origin: Option<&str>, depth: usize) -> Self {
+        let frame = tree.frame;
+        let children = tree
+            .child_frames
+            .unwrap_or_default()
+            .into_iter()
+            .map(|child| Self::from_subtree(child, Some(&frame.security_origin), depth + 1))
+            .collect();
+
+        Self {
+            cross_origin: parent_origin.is_some_and(|origin| origin != frame.security_origin),
+            url: match &frame.url_fragment {
+                Some(fragment) => format!("{}{}", frame.url, fragment),
+                None => frame.url,
+            },
+            id: frame.id,
+            name: frame.name.filter(|name| !name.is_empty()),
+            security_origin: frame.security_origin,
+            mime_type: frame.mime_type,
+            unreachable_url: frame.unreachable_url,
+            depth,
+            children,
+        }
+    }
+
+    /// Number of frames in this subtree, including this one
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(FrameInfo::count).sum::<usize>()
+    }
+
+    /// Indented outline of the tree, one frame per line
+    fn outline(&self, lines: &mut Vec<String>) {
+        let name = self
+            .name
+            .as_deref()
+            .map(|name| format!(" name=\"{}\"", name))
+            .unwrap_or_default();
+        let cross_origin = if self.cross_origin {
+            " (cross-origin)"
+        } else {
+            ""
+        };
+        lines.push(format!(
+            "{}- {}{}{}",
+            "  ".repeat(self.depth),
+            self.url,
+            name,
+            cross_origin
+        ));
+        for child in &self.children {
+            child.outline(lines);
+        }
+    }
+}
+
+/// Parameters for the get_frame_tree tool (no parameters needed)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GetFrameTreeParams {}
+
+/// Tool for listing the frames of the current page
+#[derive(Default)]
+pub struct GetFrameTreeTool;
+
+impl Tool for GetFrameTreeTool {
+    type Params = GetFrameTreeParams;
+
+    fn name(&self) -> &str {
+        "get_frame_tree"
+    }
+
+    fn description(&self) -> &str {
+        "Get the hierarchy of frames (iframes) on the current page with their URLs, names and security origins"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        _params: GetFrameTreeParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let response = context
+            .session
+            .tab()?
+            .call_method(Page::GetFrameTree(None))
+            .map_err(|e| BrowserError::ToolExecutionFailed {
+                tool: "get_frame_tree".to_string(),
+                reason: format!("Failed to read the frame tree: {}", e),
+            })?;
+        let tree = FrameInfo::from_tree(response.frame_tree);
+
+        let mut lines = Vec::new();
+        tree.outline(&mut lines);
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "count": tree.count(),
+            "frames": tree,
+            "summary": lines.join("\n"),
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(id: &str, url: &str, origin: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "loaderId": "loader",
+            "url": url,
+            "domainAndRegistry": "",
+            "securityOrigin": origin,
+            "mimeType": "text/html",
+            "secureContextType": "Secure",
+            "crossOriginIsolatedContextType": "NotIsolated",
+            "gatedAPIFeatures": []
+        })
+    }
+
+    #[test]
+    fn test_from_tree() {
+        let mut ad = frame(
+            "3",
+            "https://ads.example.net/slot",
+            "https://ads.example.net",
+        );
+        ad["name"] = "ad_slot".into();
+        let mut local = frame(
+            "2",
+            "https://shop.example.com/cart",
+            "https://shop.example.com",
+        );
+        local["name"] = "".into();
+        local["urlFragment"] = "#items".into();
+        let tree: Page::FrameTree = serde_json::from_value(serde_json::json!({
+            "frame": frame("1", "https://shop.example.com/", "https://shop.example.com"),
+            "childFrames": [
+                { "frame": local },
+                { "frame": ad, "childFrames": [
+                    { "frame": frame("4", "about:blank", "://") }
+                ]}
+            ]
+        }))
+        .unwrap();
+
+        let info = FrameInfo::from_tree(tree);
+        assert_eq!(info.count(), 4);
+        assert!(!info.cross_origin);
+
+        let local = &info.children[0];
+        assert_eq!(local.url, "https://shop.example.com/cart#items");
+        assert_eq!(local.name, None);
+        assert!(!local.cross_origin);
+
+        let ad = &info.children[1];
+        assert_eq!(ad.name.as_deref(), Some("ad_slot"));
+        assert!(ad.cross_origin);
+        assert_eq!(ad.children[0].depth, 2);
+
+        let mut lines = Vec::new();
+        info.outline(&mut lines);
+        assert_eq!(
+            lines[2],
+            "  - https://ads.example.net/slot name=\"ad_slot\" (cross-origin)"
+        );
+    }
+}
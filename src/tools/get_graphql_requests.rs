@@ -0,0 +1,403 @@
+use crate::browser::network::{CapturedRequest, NetworkCapture};
+use crate::error::Result;
+use crate::tools::utils::json_shape;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+/// Default number of operations returned
+const DEFAULT_LIMIT: usize = 50;
+
+/// Nesting depth past which response shapes are cut off
+const MAX_SHAPE_DEPTH: usize = 6;
+
+/// Queries longer than this are truncated in the result
+const MAX_QUERY_CHARS: usize = 2000;
+
+/// Parameters for the get_graphql_requests tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GetGraphqlRequestsParams {
+    /// Only return operations with this name (case-insensitive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+
+    /// Include the shape of each response (default: true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_responses: Option<bool>,
+
+    /// Maximum number of operations to return, most recent first (default: 50)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// One GraphQL operation found in a request
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Operation {
+    operation_type: Option<&'static str>,
+    operation_name: Option<String>,
+    query: Option<String>,
+    variables: Option<Value>,
+    persisted_query_hash: Option<String>,
+}
+
+impl Operation {
+    /// Read an operation from a `{query, operationName, variables, extensions}` object
+    fn from_json(value: &Value) -> Option<Self> {
+        let object = value.as_object()?;
+        let query = object
+            .get("query")
+            .and_then(Value::as_str)
+            .map(String::from);
+        let persisted_query_hash = object
+            .get("extensions")
+            .and_then(|extensions| extensions.pointer("/persistedQuery/sha256Hash"))
+            .and_then(Value::as_str)
+            .map(String::from);
+
+        let header = query.as_deref().and_then(operation_header);
+        // A "query" field alone is common in search APIs; require GraphQL syntax
+        // or a persisted query before treating the request as GraphQL
+        if header.is_none() && persisted_query_hash.is_none() {
+            return None;
+        }
+        let (operation_type, parsed_name) = header.unwrap_or((None, None));
+
+        Some(Self {
+            operation_type,
+            operation_name: object
+                .get("operationName")
+                .and_then(Value::as_str)
+                .map(String::from)
+                .or(parsed_name),
+            query,
+            variables: object.get("variables").filter(|v| !v.is_null()).cloned(),
+            persisted_query_hash,
+        })
+    }
+}
+
+/// Find the GraphQL operations in a request, if it is a GraphQL request
+pub(crate) fn parse_operations(url: &str, body: Option<&str>) -> Vec<Operation> {
+    if let Some(body) = body.map(str::trim).filter(|body| !body.is_empty()) {
+        return match serde_json::from_str::<Value>(body) {
+            Ok(Value::Array(batch)) => batch.iter().filter_map(Operation::from_json).collect(),
+            Ok(value) => Operation::from_json(&value).into_iter().collect(),
+            // application/graphql bodies are the bare query text
+            Err(_) => Operation::from_json(&serde_json::json!({ "query": body }))
+                .into_iter()
+                .collect(),
+        };
+    }
+
+    // GET requests carry the operation in the query string
+    let Ok(url) = Url::parse(url) else {
+        return Vec::new();
+    };
+    let mut fields = serde_json::Map::new();
+    for (key, value) in url.query_pairs() {
+        let value = match key.as_ref() {
+            "query" | "operationName" => Value::String(value.into_owned()),
+            "variables" | "extensions" => serde_json::from_str(&value).unwrap_or(Value::Null),
+            _ => continue,
+        };
+        fields.insert(key.into_owned(), value);
+    }
+    Operation::from_json(&Value::Object(fields))
+        .into_iter()
+        .collect()
+}
+
+/// Operation type and name from the first operation definition in a document
+///
+/// Returns `None` if the text does not look like a GraphQL document.
+fn operation_header(query: &str) -> Option<(Option<&'static str>, Option<String>)> {
+    let mut depth = 0usize;
+    let mut chars = query.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '#' => {
+                // Comment to end of line
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '"' => {
+                // Skip string literals so braces inside them are not counted
+                let mut escaped = false;
+                for (_, c) in chars.by_ref() {
+                    match c {
+                        '\\' if !escaped => escaped = true,
+                        '"' if !escaped => break,
+                        _ => escaped = false,
+                    }
+                }
+            }
+            // Shorthand `{ ... }` is an anonymous query
+            '{' if depth == 0 => return Some((Some("query"), None)),
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            c if depth == 0 && (c.is_ascii_alphabetic() || c == '_') => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = &query[start..end];
+                let operation_type = match word {
+                    "query" => "query",
+                    "mutation" => "mutation",
+                    "subscription" => "subscription",
+                    "fragment" => {
+                        // Skip over the fragment definition to its body
+                        while chars.next_if(|&(_, c)| c != '{').is_some() {}
+                        chars.next();
+                        depth += 1;
+                        continue;
+                    }
+                    _ => return None,
+                };
+                let rest = query[end..].trim_start();
+                let name_len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                let name = (name_len > 0).then(|| rest[..name_len].to_string());
+                return Some((Some(operation_type), name));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Shape of a GraphQL response's `data` and the messages of its `errors`
+fn summarize_response(response: &Value) -> Value {
+    let mut summary = serde_json::json!({
+        "shape": json_shape(response.get("data").unwrap_or(&Value::Null), MAX_SHAPE_DEPTH)
+    });
+    if let Some(errors) = response.get("errors").and_then(Value::as_array) {
+        summary["errors"] = errors
+            .iter()
+            .map(|error| {
+                error
+                    .get("message")
+                    .cloned()
+                    .unwrap_or_else(|| error.clone())
+            })
+            .collect();
+    }
+    summary
+}
+
+fn truncate(query: &str) -> String {
+    match query.char_indices().nth(MAX_QUERY_CHARS) {
+        Some((end, _)) => format!("{}...", &query[..end]),
+        None => query.to_string(),
+    }
+}
+
+/// Tool for inspecting the GraphQL traffic of the page
+///
+/// Looks through the session's captured XHR/fetch requests for GraphQL
+/// operations and reports their names, variables and the shape of their
+/// responses. Capture starts on the first call, so an empty result means the
+/// page should be reloaded or interacted with before calling again.
+#[derive(Default)]
+pub struct GetGraphqlRequestsTool;
+
+impl Tool for GetGraphqlRequestsTool {
+    type Params = GetGraphqlRequestsParams;
+
+    fn name(&self) -> &str {
+        "get_graphql_requests"
+    }
+
+    fn description(&self) -> &str {
+        "List GraphQL operations the page has sent since capture started, with operation names, variables and response shapes"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: GetGraphqlRequestsParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let capture = context.session.network_capture()?;
+        let requests = capture.requests();
+        let tabs = context.session.get_tabs()?;
+        let tab_for = |request: &CapturedRequest| {
+            tabs.iter()
+                .find(|tab| *tab.get_target_id() == request.target_id)
+        };
+
+        let include_responses = params.include_responses.unwrap_or(true);
+        let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+        let wanted = params.operation_name.as_deref();
+
+        let mut operations = Vec::new();
+        for request in requests.iter().rev() {
+            if operations.len() >= limit {
+                break;
+            }
+            let tab = tab_for(request);
+            let body = tab.and_then(|tab| NetworkCapture::request_body(tab, request));
+            let found = parse_operations(&request.url, body.as_deref());
+            if found.is_empty() {
+                continue;
+            }
+
+            let response = if include_responses && request.status.is_some() {
+                tab.and_then(|tab| NetworkCapture::response_body(tab, request).ok())
+                    .and_then(|body| serde_json::from_str::<Value>(&body).ok())
+            } else {
+                None
+            };
+
+            for (i, operation) in found.into_iter().enumerate() {
+                let name_matches = match (wanted, &operation.operation_name) {
+                    (None, _) => true,
+                    (Some(wanted), Some(name)) => name.eq_ignore_ascii_case(wanted),
+                    (Some(_), None) => false,
+                };
+                if !name_matches || operations.len() >= limit {
+                    continue;
+                }
+
+                let mut entry = serde_json::json!({
+                    "url": request.url,
+                    "method": request.method,
+                    "status": request.status,
+                    "operationType": operation.operation_type,
+                    "operationName": operation.operation_name,
+                    "variables": operation.variables,
+                });
+                if let Some(query) = &operation.query {
+                    entry["query"] = Value::from(truncate(query));
+                }
+                if let Some(hash) = operation.persisted_query_hash {
+                    entry["persistedQueryHash"] = Value::from(hash);
+                }
+                // Batched requests answer with an array in request order
+                let response = match &response {
+                    Some(Value::Array(batch)) => batch.get(i),
+                    other => other.as_ref(),
+                };
+                if let Some(response) = response {
+                    entry["response"] = summarize_response(response);
+                }
+                operations.push(entry);
+            }
+        }
+
+        let mut result = serde_json::json!({
+            "count": operations.len(),
+            "operations": operations,
+        });
+        if operations.is_empty() {
+            result["note"] = Value::from(
+                "No GraphQL requests captured yet. Traffic is recorded from the first call on; \
+                 reload the page or interact with it, then call again.",
+            );
+        }
+
+        Ok(ToolResult::success_with(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_graphql_requests_params() {
+        let json = serde_json::json!({
+            "operation_name": "GetUser",
+            "limit": 10
+        });
+
+        let params: GetGraphqlRequestsParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.operation_name.as_deref(), Some("GetUser"));
+        assert_eq!(params.include_responses, None);
+        assert_eq!(params.limit, Some(10));
+    }
+
+    #[test]
+    fn test_operation_header() {
+        assert_eq!(
+            operation_header("query GetUser($id: ID!) { user(id: $id) { name } }"),
+            Some((Some("query"), Some("GetUser".to_string())))
+        );
+        assert_eq!(
+            operation_header("{ viewer { login } }"),
+            Some((Some("query"), None))
+        );
+        assert_eq!(
+            operation_header(
+                "# comment {\nfragment F on User { name }\nmutation Save { save { ...F } }"
+            ),
+            Some((Some("mutation"), Some("Save".to_string())))
+        );
+        assert_eq!(operation_header("running shoes"), None);
+    }
+
+    #[test]
+    fn test_parse_post_body() {
+        let body = r#"{"operationName":"GetUser","query":"query GetUser { user { id } }","variables":{"id":"7"}}"#;
+        let operations = parse_operations("https://api.example.com/graphql", Some(body));
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].operation_type, Some("query"));
+        assert_eq!(operations[0].operation_name.as_deref(), Some("GetUser"));
+        assert_eq!(
+            operations[0].variables,
+            Some(serde_json::json!({ "id": "7" }))
+        );
+
+        // Batched requests and persisted queries
+        let body = r#"[
+            {"query":"mutation Like { like { ok } }"},
+            {"operationName":"Feed","extensions":{"persistedQuery":{"version":1,"sha256Hash":"abc"}}}
+        ]"#;
+        let operations = parse_operations("https://example.com/api", Some(body));
+        assert_eq!(operations.len(), 2);
+        assert_eq!(operations[0].operation_name.as_deref(), Some("Like"));
+        assert_eq!(operations[1].persisted_query_hash.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn test_parse_non_graphql() {
+        let body = r#"{"query":"running shoes","page":2}"#;
+        assert!(parse_operations("https://shop.example.com/search", Some(body)).is_empty());
+        assert!(parse_operations("https://example.com/api/items?page=2", None).is_empty());
+    }
+
+    #[test]
+    fn test_parse_get_request() {
+        let url = "https://example.com/graphql?query=query%20Me%20%7B%20me%20%7B%20id%20%7D%20%7D&variables=%7B%22a%22%3A1%7D";
+        let operations = parse_operations(url, None);
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].operation_name.as_deref(), Some("Me"));
+        assert_eq!(operations[0].variables, Some(serde_json::json!({ "a": 1 })));
+    }
+
+    #[test]
+    fn test_summarize_response() {
+        let response = serde_json::json!({
+            "data": { "user": { "id": "7", "posts": [{ "title": "a", "likes": 3 }], "bio": null } },
+            "errors": [{ "message": "partial failure" }]
+        });
+        let summary = summarize_response(&response);
+        assert_eq!(
+            summary["shape"],
+            serde_json::json!({
+                "user": { "id": "string", "posts": [{ "title": "string", "likes": "number" }], "bio": "null" }
+            })
+        );
+        assert_eq!(summary["errors"], serde_json::json!(["partial failure"]));
+    }
+}
@@ -0,0 +1,160 @@
+use crate::browser::policy::truncate_at;
+use crate::error::{BrowserError, Result};
+use crate::tools::utils::resolve_optional_selector;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Serializes a copy of the element with the requested parts removed
+const GET_HTML_JS: &str = r#"function (options) {
+    const copy = this.cloneNode(true);
+    const remove = function (selector) {
+        copy.querySelectorAll(selector).forEach(function (node) { node.remove(); });
+    };
+    const all = [copy].concat(Array.from(copy.querySelectorAll("*")));
+    if (options.stripScripts) {
+        remove("script, noscript");
+        all.forEach(function (element) {
+            Array.from(element.attributes || []).forEach(function (attribute) {
+                if (/^on/i.test(attribute.name)) element.removeAttribute(attribute.name);
+            });
+        });
+    }
+    if (options.stripStyles) {
+        remove("style, link[rel~=stylesheet]");
+        all.forEach(function (element) {
+            if (element.removeAttribute) element.removeAttribute("style");
+        });
+    }
+    if (options.stripComments) {
+        const walker = document.createTreeWalker(copy, NodeFilter.SHOW_COMMENT);
+        const comments = [];
+        while (walker.nextNode()) comments.push(walker.currentNode);
+        comments.forEach(function (comment) { comment.remove(); });
+    }
+    const doctype = this === document.documentElement && document.doctype
+        ? "<!DOCTYPE " + document.doctype.name + ">\n"
+        : "";
+    return doctype + copy.outerHTML;
+}"#;
+
+/// Parameters for the get_html tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GetHtmlParams {
+    /// CSS selector of the element to return (use either this or index; default: the whole page)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree of the element to return (use either this or selector)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Remove script and noscript elements and inline event handlers (default: false)
+    #[serde(default)]
+    pub strip_scripts: bool,
+
+    /// Remove style elements, stylesheet links and style attributes (default: false)
+    #[serde(default)]
+    pub strip_styles: bool,
+
+    /// Remove HTML comments (default: false)
+    #[serde(default)]
+    pub strip_comments: bool,
+
+    /// Most bytes of HTML returned; longer markup is cut off (default: 100000)
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+}
+
+impl Default for GetHtmlParams {
+    fn default() -> Self {
+        Self {
+            selector: None,
+            index: None,
+            strip_scripts: false,
+            strip_styles: false,
+            strip_comments: false,
+            max_bytes: default_max_bytes(),
+        }
+    }
+}
+
+fn default_max_bytes() -> usize {
+    100_000
+}
+
+/// Tool for reading the raw HTML of the page or of one element
+///
+/// Returns the current DOM serialized as outerHTML, so changes made by
+/// scripts are included; contents of shadow roots and iframes are not.
+#[derive(Default)]
+pub struct GetHtmlTool;
+
+impl Tool for GetHtmlTool {
+    type Params = GetHtmlParams;
+
+    fn name(&self) -> &str {
+        "get_html"
+    }
+
+    fn description(&self) -> &str {
+        "Get the raw HTML (outerHTML) of the page or of an element by CSS selector or index, optionally without scripts, styles or comments and capped in size; use when markdown loses the markup you need"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: GetHtmlParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let selector = resolve_optional_selector(
+            "get_html",
+            context,
+            params.selector.as_deref(),
+            params.index,
+        )?;
+
+        let tab = context.session.tab()?;
+        let element = context
+            .session
+            .find_element(&tab, selector.as_deref().unwrap_or("html"))?;
+        let options = serde_json::json!({
+            "stripScripts": params.strip_scripts,
+            "stripStyles": params.strip_styles,
+            "stripComments": params.strip_comments,
+        });
+        let html = element
+            .call_js_fn(GET_HTML_JS, vec![options], false)
+            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?
+            .value
+            .and_then(|value| value.as_str().map(String::from))
+            .ok_or_else(|| BrowserError::ToolExecutionFailed {
+                tool: "get_html".to_string(),
+                reason: "No HTML returned".to_string(),
+            })?;
+
+        let total_bytes = html.len();
+        let truncated = total_bytes > params.max_bytes;
+        Ok(ToolResult::success_with(serde_json::json!({
+            "html": truncate_at(&html, params.max_bytes),
+            "bytes": total_bytes,
+            "truncated": truncated,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_html_params() {
+        let params: GetHtmlParams = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(params.selector.is_none());
+        assert!(!params.strip_scripts);
+        assert_eq!(params.max_bytes, 100_000);
+    }
+}
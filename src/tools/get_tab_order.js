@@ -0,0 +1,227 @@
+JSON.stringify(
+  (function () {
+    const config = __TAB_ORDER_CONFIG__;
+    const viewportWidth = window.innerWidth || document.documentElement.clientWidth;
+    const viewportHeight = window.innerHeight || document.documentElement.clientHeight;
+
+    const stops = [];
+    const skipped = [];
+
+    // Same selector as the DOM extraction, so stops can be matched to element indices
+    function buildSelector(element) {
+      if (element.id) {
+        return "#" + element.id;
+      }
+      const path = [];
+      let current = element;
+      while (current && current !== document.body) {
+        let selector = current.tagName.toLowerCase();
+        if (current.className && typeof current.className === "string") {
+          const classes = current.className.trim().split(/\s+/);
+          if (classes.length > 0 && classes[0]) {
+            selector += "." + classes[0];
+          }
+        }
+        const parent = current.parentElement;
+        if (parent) {
+          const siblings = Array.from(parent.children);
+          const index = siblings.indexOf(current);
+          if (siblings.filter(function (s) { return s.tagName === current.tagName; }).length > 1) {
+            selector += ":nth-child(" + (index + 1) + ")";
+          }
+        }
+        path.unshift(selector);
+        current = current.parentElement;
+      }
+      return path.join(" > ");
+    }
+
+    function isNativelyFocusable(element) {
+      const tag = element.tagName.toLowerCase();
+      switch (tag) {
+        case "a":
+        case "area":
+          return element.hasAttribute("href");
+        case "input":
+          return element.type !== "hidden";
+        case "button":
+        case "select":
+        case "textarea":
+        case "iframe":
+          return true;
+        case "audio":
+        case "video":
+          return element.hasAttribute("controls");
+        case "summary":
+          return !!element.parentElement &&
+            element.parentElement.tagName.toLowerCase() === "details" &&
+            element.parentElement.querySelector("summary") === element;
+        default:
+          return element.isContentEditable &&
+            (!element.parentElement || !element.parentElement.isContentEditable);
+      }
+    }
+
+    function role(element) {
+      const explicit = element.getAttribute("role");
+      if (explicit) {
+        return explicit.trim().split(/\s+/)[0];
+      }
+      const tag = element.tagName.toLowerCase();
+      if (tag === "a" || tag === "area") return "link";
+      if (tag === "button" || tag === "summary") return "button";
+      if (tag === "select") return element.multiple || element.size > 1 ? "listbox" : "combobox";
+      if (tag === "textarea") return "textbox";
+      if (tag === "input") {
+        const type = (element.type || "text").toLowerCase();
+        if (type === "checkbox" || type === "radio") return type;
+        if (type === "range") return "slider";
+        if (type === "number") return "spinbutton";
+        if (type === "search") return "searchbox";
+        if (["button", "submit", "reset", "image"].includes(type)) return "button";
+        return "textbox";
+      }
+      if (element.isContentEditable) return "textbox";
+      return tag;
+    }
+
+    function name(element) {
+      const root = element.getRootNode();
+      let text = element.getAttribute("aria-label") || "";
+      if (!text && element.hasAttribute("aria-labelledby")) {
+        text = element
+          .getAttribute("aria-labelledby")
+          .split(/\s+/)
+          .map(function (id) {
+            const label = root.getElementById ? root.getElementById(id) : document.getElementById(id);
+            return label ? label.textContent : "";
+          })
+          .join(" ");
+      }
+      if (!text && element.labels && element.labels.length > 0) {
+        text = Array.from(element.labels).map(function (label) { return label.textContent; }).join(" ");
+      }
+      if (!text && element.tagName.toLowerCase() !== "input" && element.tagName.toLowerCase() !== "textarea") {
+        text = element.innerText || element.textContent || "";
+      }
+      if (!text) {
+        text = element.getAttribute("alt") || element.getAttribute("title") ||
+          element.getAttribute("placeholder") || (element.type === "submit" ? element.value : "") || "";
+      }
+      text = text.replace(/\s+/g, " ").trim();
+      return text.length > 80 ? text.slice(0, 80) + "…" : text;
+    }
+
+    function isRendered(element) {
+      if (typeof element.checkVisibility === "function") {
+        return element.checkVisibility({ visibilityProperty: true });
+      }
+      const style = window.getComputedStyle(element);
+      return style.display !== "none" && style.visibility !== "hidden" &&
+        element.getClientRects().length > 0;
+    }
+
+    function isInert(element) {
+      for (let current = element; current; ) {
+        if (current.inert) return true;
+        current = current.parentElement || (current.getRootNode() && current.getRootNode().host) || null;
+      }
+      return false;
+    }
+
+    // Why a focusable element is left out of sequential navigation, or null
+    function skipReason(element) {
+      if (element.tabIndex < 0) return "tabindex=-1";
+      if (element.matches(":disabled")) return "disabled";
+      if (isInert(element)) return "inert";
+      if (!isRendered(element)) return "hidden";
+      return null;
+    }
+
+    function describe(element) {
+      const rect = element.getBoundingClientRect();
+      return {
+        tabIndex: element.tabIndex,
+        tag: element.tagName.toLowerCase(),
+        role: role(element),
+        name: name(element),
+        selector: buildSelector(element),
+        inViewport:
+          rect.bottom > 0 && rect.right > 0 && rect.top < viewportHeight && rect.left < viewportWidth,
+      };
+    }
+
+    // Candidates of one focus navigation scope (a document or shadow root), in tree order
+    function collectScope(root) {
+      const entries = [];
+      const walker = document.createTreeWalker(root, NodeFilter.SHOW_ELEMENT);
+      for (let element = walker.nextNode(); element; element = walker.nextNode()) {
+        const focusable = isNativelyFocusable(element) || element.hasAttribute("tabindex");
+        const shadow = element.shadowRoot;
+        if (!focusable && !shadow) continue;
+        entries.push({ element: element, focusable: focusable, shadow: shadow });
+      }
+      return entries;
+    }
+
+    // Keep one radio button per group: the checked one, else the first
+    function radioGroupSkipped(entries) {
+      const groups = {};
+      entries.forEach(function (entry) {
+        const element = entry.element;
+        if (element.tagName.toLowerCase() !== "input" || element.type !== "radio" || !element.name) return;
+        const key = (element.form ? buildSelector(element.form) : "") + "|" + element.name;
+        (groups[key] = groups[key] || []).push(element);
+      });
+      const hidden = new Set();
+      Object.keys(groups).forEach(function (key) {
+        const group = groups[key];
+        const keep = group.find(function (radio) { return radio.checked; }) || group[0];
+        group.forEach(function (radio) {
+          if (radio !== keep) hidden.add(radio);
+        });
+      });
+      return hidden;
+    }
+
+    // Positive tabindex first in ascending order, then tabindex 0 in tree order
+    function visitScope(root) {
+      const entries = collectScope(root);
+      const groupSkipped = radioGroupSkipped(entries);
+      const ordered = entries
+        .map(function (entry, position) {
+          const tabIndex = entry.focusable ? entry.element.tabIndex : 0;
+          return { entry: entry, position: position, tabIndex: tabIndex };
+        })
+        .sort(function (a, b) {
+          const aKey = a.tabIndex > 0 ? a.tabIndex : Infinity;
+          const bKey = b.tabIndex > 0 ? b.tabIndex : Infinity;
+          return aKey - bKey || a.position - b.position;
+        });
+
+      ordered.forEach(function (item) {
+        const element = item.entry.element;
+        if (item.entry.focusable) {
+          const reason = skipReason(element) || (groupSkipped.has(element) ? "radio group" : null);
+          if (reason) {
+            if (config.includeSkipped) {
+              const skippedEntry = describe(element);
+              skippedEntry.reason = reason;
+              skipped.push(skippedEntry);
+            }
+          } else {
+            stops.push(describe(element));
+          }
+        }
+        // A shadow tree is navigated where its host sits, unless the host is out of sequence
+        if (item.entry.shadow && item.tabIndex >= 0) {
+          visitScope(item.entry.shadow);
+        }
+      });
+    }
+
+    visitScope(document.body || document.documentElement);
+
+    return { stops: stops, skipped: skipped };
+  })()
+)
@@ -0,0 +1,157 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+const GET_TAB_ORDER_JS: &str = include_str!("get_tab_order.js");
+
+/// Parameters for the get_tab_order tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GetTabOrderParams {
+    /// Also list focusable elements that Tab skips, with the reason (default: false)
+    #[serde(default)]
+    pub include_skipped: bool,
+}
+
+/// An element reached by pressing Tab
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TabStop {
+    /// Position in the tab sequence, starting at 1; 0 for skipped elements
+    #[serde(default)]
+    pub position: usize,
+    /// Effective tabindex; positive values jump ahead of the document order
+    pub tab_index: i32,
+    /// Tag name
+    pub tag: String,
+    /// Explicit or implicit ARIA role
+    pub role: String,
+    /// Accessible name, truncated to 80 characters
+    pub name: String,
+    /// CSS selector of the element
+    pub selector: String,
+    /// Element index in the DOM snapshot, when the element is indexed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    /// Whether the element is within the viewport
+    pub in_viewport: bool,
+    /// Why Tab skips the element: "tabindex=-1", "disabled", "inert", "hidden"
+    /// or "radio group"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TabOrder {
+    stops: Vec<TabStop>,
+    skipped: Vec<TabStop>,
+}
+
+/// Tool for listing the order in which Tab moves focus through the page
+///
+/// Follows sequential focus navigation: positive tabindex values first in
+/// ascending order, then the remaining focusable elements in document order,
+/// with each open shadow tree navigated where its host sits. Disabled, inert
+/// and hidden elements are left out, as are unchecked radio buttons of a
+/// group. The contents of iframes are not entered.
+#[derive(Default)]
+pub struct GetTabOrderTool;
+
+impl Tool for GetTabOrderTool {
+    type Params = GetTabOrderParams;
+
+    fn name(&self) -> &str {
+        "get_tab_order"
+    }
+
+    fn description(&self) -> &str {
+        "List the elements the Tab key moves focus through, in order, with tabindex, role, name, selector and snapshot index; optionally also the focusable elements Tab skips and why"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: GetTabOrderParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let tool_error = |reason: String| BrowserError::ToolExecutionFailed {
+            tool: "get_tab_order".to_string(),
+            reason,
+        };
+
+        let config = serde_json::json!({ "includeSkipped": params.include_skipped });
+        let js = GET_TAB_ORDER_JS.replace("__TAB_ORDER_CONFIG__", &config.to_string());
+        let TabOrder {
+            mut stops,
+            mut skipped,
+        } = context
+            .session
+            .tab()?
+            .evaluate(&js, false)
+            .map_err(|e| tool_error(e.to_string()))?
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .ok_or_else(|| tool_error("Failed to parse tab order".to_string()))?;
+
+        // Point stops at snapshot indices, so keyboard and index-based tools can be mixed
+        if let Ok(dom) = context.get_dom() {
+            for stop in stops.iter_mut().chain(skipped.iter_mut()) {
+                stop.index = dom.selectors.iter().position(|s| *s == stop.selector);
+            }
+        }
+        for (position, stop) in stops.iter_mut().enumerate() {
+            stop.position = position + 1;
+        }
+
+        let positive_tabindex = stops.iter().filter(|stop| stop.tab_index > 0).count();
+        let mut result = serde_json::json!({
+            "count": stops.len(),
+            "positiveTabindex": positive_tabindex,
+            "stops": stops,
+        });
+        if params.include_skipped {
+            result["skipped"] = serde_json::json!(skipped);
+        }
+        Ok(ToolResult::success_with(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_stop_from_script() {
+        let order: TabOrder = serde_json::from_value(serde_json::json!({
+            "stops": [{
+                "tabIndex": 0,
+                "tag": "a",
+                "role": "link",
+                "name": "Home",
+                "selector": "#home",
+                "inViewport": true
+            }],
+            "skipped": [{
+                "tabIndex": -1,
+                "tag": "div",
+                "role": "div",
+                "name": "",
+                "selector": "div",
+                "inViewport": false,
+                "reason": "tabindex=-1"
+            }]
+        }))
+        .unwrap();
+        assert_eq!(order.stops[0].position, 0);
+        assert_eq!(order.stops[0].index, None);
+        assert_eq!(order.skipped[0].reason.as_deref(), Some("tabindex=-1"));
+
+        let stop = serde_json::to_value(&order.stops[0]).unwrap();
+        assert!(stop.get("reason").is_none());
+        assert_eq!(stop["tabIndex"], 0);
+    }
+}
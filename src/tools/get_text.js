@@ -0,0 +1,113 @@
+JSON.stringify(
+  (function () {
+    const config = __GET_TEXT_CONFIG__;
+    const root = config.selector ? document.querySelector(config.selector) : document.body;
+    if (!root) {
+      return { error: "Element not found: " + config.selector };
+    }
+
+    // Snapshot selectors by index, to anchor text to indexed elements
+    const indices = new Map();
+    config.selectors.forEach(function (selector, index) {
+      if (!indices.has(selector)) indices.set(selector, index);
+    });
+
+    // Same selector as the DOM extraction, so text can be matched to element indices
+    function buildSelector(element) {
+      if (element.id) {
+        return "#" + element.id;
+      }
+      const path = [];
+      let current = element;
+      while (current && current !== document.body) {
+        let selector = current.tagName.toLowerCase();
+        if (current.className && typeof current.className === "string") {
+          const classes = current.className.trim().split(/\s+/);
+          if (classes.length > 0 && classes[0]) {
+            selector += "." + classes[0];
+          }
+        }
+        const parent = current.parentElement;
+        if (parent) {
+          const siblings = Array.from(parent.children);
+          const index = siblings.indexOf(current);
+          if (siblings.filter(function (s) { return s.tagName === current.tagName; }).length > 1) {
+            selector += ":nth-child(" + (index + 1) + ")";
+          }
+        }
+        path.unshift(selector);
+        current = current.parentElement;
+      }
+      return path.join(" > ");
+    }
+
+    const anchors = new Map();
+    // Nearest indexed element at or above `element`, or null
+    function anchorOf(element) {
+      if (!element || element === document.body || element === document.documentElement) {
+        return null;
+      }
+      if (anchors.has(element)) return anchors.get(element);
+      const index = indices.get(buildSelector(element));
+      const anchor = index !== undefined ? index : anchorOf(element.parentElement);
+      anchors.set(element, anchor);
+      return anchor;
+    }
+
+    const inlineDisplays = ["inline", "inline-block", "inline-flex", "inline-grid", "contents"];
+    // Nearest ancestor laid out as a block, which separates text blocks
+    function blockOf(element) {
+      for (let current = element; current && current !== root; current = current.parentElement) {
+        if (!inlineDisplays.includes(window.getComputedStyle(current).display)) return current;
+      }
+      return root;
+    }
+
+    function isVisible(element) {
+      if (typeof element.checkVisibility === "function") {
+        return element.checkVisibility({ visibilityProperty: true, opacityProperty: true });
+      }
+      const style = window.getComputedStyle(element);
+      return style.display !== "none" && style.visibility !== "hidden" &&
+        style.opacity !== "0" && element.getClientRects().length > 0;
+    }
+
+    const skipped = "script, style, noscript, template, svg";
+    const blocks = [];
+    let current = null;
+    let truncated = false;
+
+    const walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT);
+    for (let node = walker.nextNode(); node; node = walker.nextNode()) {
+      const text = node.textContent.replace(/\s+/g, " ");
+      if (!text.trim()) continue;
+      const parent = node.parentElement;
+      if (!parent || parent.closest(skipped) || !isVisible(parent)) continue;
+
+      const block = blockOf(parent);
+      const anchor = anchorOf(parent);
+      // Consecutive text of one block with the same anchor forms one segment
+      if (current && current.element === block && current.anchor === anchor) {
+        current.text += text;
+        continue;
+      }
+      if (blocks.length >= config.maxBlocks) {
+        truncated = true;
+        break;
+      }
+      current = { element: block, anchor: anchor, text: text };
+      blocks.push(current);
+    }
+
+    return {
+      truncated: truncated,
+      blocks: blocks
+        .map(function (block) {
+          const entry = { text: block.text.trim(), tag: block.element.tagName.toLowerCase() };
+          if (block.anchor !== null) entry.index = block.anchor;
+          return entry;
+        })
+        .filter(function (block) { return block.text; }),
+    };
+  })()
+)
@@ -0,0 +1,151 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::utils::resolve_optional_selector;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+const GET_TEXT_JS: &str = include_str!("get_text.js");
+
+/// Parameters for the get_text tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GetTextParams {
+    /// CSS selector of the element to read (use either this or index; default: the whole page)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree of the element to read (use either this or selector)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Most text blocks returned (default: 500)
+    #[serde(default = "default_max_blocks")]
+    pub max_blocks: usize,
+}
+
+impl Default for GetTextParams {
+    fn default() -> Self {
+        Self {
+            selector: None,
+            index: None,
+            max_blocks: default_max_blocks(),
+        }
+    }
+}
+
+fn default_max_blocks() -> usize {
+    500
+}
+
+/// A run of visible text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextBlock {
+    /// Text with whitespace collapsed
+    pub text: String,
+    /// Tag name of the block-level element holding the text
+    pub tag: String,
+    /// Index of the nearest indexed element containing the text, e.g. the link it belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageText {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    blocks: Vec<TextBlock>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+/// Tool for reading the page's visible text in blocks anchored to element indices
+///
+/// Text is split at block-level elements and wherever the nearest indexed
+/// ancestor changes, so a link inside a paragraph is its own block carrying
+/// the link's index. Text that is hidden, or inside scripts, styles and SVG,
+/// is left out; shadow roots and iframes are not entered.
+#[derive(Default)]
+pub struct GetTextTool;
+
+impl Tool for GetTextTool {
+    type Params = GetTextParams;
+
+    fn name(&self) -> &str {
+        "get_text"
+    }
+
+    fn description(&self) -> &str {
+        "Get the visible text of the page or of an element in blocks, each with the index of the nearest indexed element containing it (e.g. its link or button), to trace quoted text back to something clickable"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: GetTextParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let tool_error = |reason: String| BrowserError::ToolExecutionFailed {
+            tool: "get_text".to_string(),
+            reason,
+        };
+
+        let selector = resolve_optional_selector(
+            "get_text",
+            context,
+            params.selector.as_deref(),
+            params.index,
+        )?;
+        let selectors = context.get_dom()?.selectors.clone();
+
+        let config = serde_json::json!({
+            "selector": selector,
+            "selectors": selectors,
+            "maxBlocks": params.max_blocks,
+        });
+        let js = GET_TEXT_JS.replace("__GET_TEXT_CONFIG__", &config.to_string());
+        let text: PageText = context
+            .session
+            .tab()?
+            .evaluate(&js, false)
+            .map_err(|e| tool_error(e.to_string()))?
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .ok_or_else(|| tool_error("Failed to parse page text".to_string()))?;
+        if let Some(error) = text.error {
+            return Ok(ToolResult::failure(error));
+        }
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "count": text.blocks.len(),
+            "blocks": text.blocks,
+            "truncated": text.truncated,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_blocks_from_script() {
+        let text: PageText = serde_json::from_value(serde_json::json!({
+            "truncated": false,
+            "blocks": [
+                { "text": "Read the", "tag": "p" },
+                { "text": "docs", "tag": "p", "index": 3 }
+            ]
+        }))
+        .unwrap();
+        assert!(text.error.is_none());
+        assert_eq!(text.blocks[0].index, None);
+        assert_eq!(text.blocks[1].index, Some(3));
+
+        let block = serde_json::to_value(&text.blocks[0]).unwrap();
+        assert!(block.get("index").is_none());
+    }
+}
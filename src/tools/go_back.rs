@@ -1,10 +1,10 @@
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the go_back tool (no parameters needed)
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct GoBackParams {}
 
 /// Tool for navigating back in browser history
@@ -18,6 +18,14 @@ impl Tool for GoBackTool {
         "go_back"
     }
 
+    fn description(&self) -> &str {
+        "Navigate back in browser history"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
     fn execute_typed(
         &self,
         _params: GoBackParams,
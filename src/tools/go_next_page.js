@@ -0,0 +1,94 @@
+(function () {
+  const config = __NEXT_PAGE_CONFIG__;
+  const MARK = "data-browser-use-next-page";
+
+  const NEXT_TEXT =
+    /^(next|next page|next results|more results|older posts|older|load more|show more|weiter|suivant|siguiente|次へ|下一页|[›»→>]|next\s*[›»→>]|[›»→>]\s*next)$/i;
+  const NEXT_LABEL = /\bnext\b|next page|[›»→]/i;
+  const CLICKABLE =
+    "a[href], button, [role='button'], [role='link'], input[type='button'], input[type='submit']";
+
+  const isVisible = (el) => {
+    const rect = el.getBoundingClientRect();
+    const style = window.getComputedStyle(el);
+    return (
+      rect.width > 0 &&
+      rect.height > 0 &&
+      style.visibility !== "hidden" &&
+      style.display !== "none"
+    );
+  };
+
+  const isDisabled = (el) =>
+    el.disabled ||
+    el.getAttribute("aria-disabled") === "true" ||
+    /(^|[\s_-])disabled($|[\s_-])/i.test(
+      typeof el.className === "string" ? el.className : ""
+    ) ||
+    (el.parentElement &&
+      /(^|[\s_-])disabled($|[\s_-])/i.test(
+        typeof el.parentElement.className === "string"
+          ? el.parentElement.className
+          : ""
+      ));
+
+  const textOf = (el) =>
+    (el.innerText || el.value || el.textContent || "").trim().replace(/\s+/g, " ");
+
+  const candidates = Array.from(document.querySelectorAll(CLICKABLE)).filter(
+    (el) => isVisible(el) && !isDisabled(el)
+  );
+
+  // Ordered from most to least reliable
+  const rules = [
+    ["rel=next", (el) => /(^|\s)next(\s|$)/i.test(el.getAttribute("rel") || "")],
+    [
+      "aria-label",
+      (el) =>
+        NEXT_LABEL.test(el.getAttribute("aria-label") || "") &&
+        !/prev/i.test(el.getAttribute("aria-label") || ""),
+    ],
+    [
+      "title",
+      (el) =>
+        NEXT_LABEL.test(el.getAttribute("title") || "") &&
+        !/prev/i.test(el.getAttribute("title") || ""),
+    ],
+    ["text", (el) => NEXT_TEXT.test(textOf(el))],
+  ];
+  if (config.text) {
+    const wanted = config.text.toLowerCase();
+    rules.unshift(["custom text", (el) => textOf(el).toLowerCase() === wanted]);
+  }
+
+  document
+    .querySelectorAll("[" + MARK + "]")
+    .forEach((el) => el.removeAttribute(MARK));
+
+  for (const [rule, matches] of rules) {
+    const el = candidates.find(matches);
+    if (el) {
+      el.setAttribute(MARK, "");
+      return JSON.stringify({
+        found: true,
+        rule,
+        text: textOf(el).slice(0, 100),
+        href: el.href || null,
+      });
+    }
+  }
+
+  // <link rel="next"> in the head cannot be clicked but can be followed
+  const link = document.querySelector("link[rel~='next'][href]");
+  if (link) {
+    return JSON.stringify({
+      found: true,
+      rule: "link rel=next",
+      text: null,
+      href: link.href,
+      followLink: true,
+    });
+  }
+
+  return JSON.stringify({ found: false });
+})()
@@ -0,0 +1,288 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::utils::confirm_element;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use headless_chrome::Tab;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default time to wait for the next page to load, in milliseconds
+pub(crate) const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+/// Interval between page state checks while waiting
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Attribute the finder script puts on the chosen control
+const NEXT_PAGE_MARK: &str = "[data-browser-use-next-page]";
+
+/// Parameters for the go_next_page tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GoNextPageParams {
+    /// Exact text of the next-page control, for sites whose label the heuristics miss
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    /// Time to wait for the next page to load, in milliseconds (default: 10000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// Next-page control picked by the finder script
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NextControl {
+    found: bool,
+    #[serde(default)]
+    rule: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    href: Option<String>,
+    #[serde(default)]
+    follow_link: bool,
+}
+
+/// Snapshot used to tell whether a new page loaded
+#[derive(Debug, Deserialize)]
+struct PageState {
+    marker: Option<String>,
+    url: String,
+    length: usize,
+    head: String,
+}
+
+/// Tool for following a page's "next" pagination control
+///
+/// Looks for `rel=next` links, next-page aria labels and titles, and common
+/// "Next" / "›" / "More results" texts, clicks the first visible, enabled
+/// match and waits for a new document, URL or content to appear.
+#[derive(Default)]
+pub struct GoNextPageTool;
+
+const GO_NEXT_PAGE_JS: &str = include_str!("go_next_page.js");
+
+const PAGE_STATE_JS: &str = r#"(function () {
+  const text = document.body ? document.body.innerText : "";
+  return JSON.stringify({
+    marker: window.__browserUseNextPageMarker || null,
+    url: location.href,
+    length: text.length,
+    head: text.slice(0, 2000),
+  });
+})()"#;
+
+fn page_state(tab: &Tab) -> Option<PageState> {
+    tab.evaluate(PAGE_STATE_JS, false)
+        .ok()?
+        .value?
+        .as_str()
+        .and_then(|json| serde_json::from_str(json).ok())
+}
+
+/// Wait until the page differs from `before`, returning how it changed
+fn wait_for_change(tab: &Tab, before: &PageState, timeout: Duration) -> Option<&'static str> {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        std::thread::sleep(POLL_INTERVAL);
+        // Evaluation fails while the old document is being torn down
+        let Some(state) = page_state(tab) else {
+            continue;
+        };
+        if state.marker != before.marker {
+            tab.wait_until_navigated().ok();
+            return Some("document");
+        }
+        if state.url != before.url {
+            return Some("url");
+        }
+        if state.length != before.length || state.head != before.head {
+            return Some("content");
+        }
+    }
+    None
+}
+
+/// Result of looking for and following a next-page control
+#[derive(Debug)]
+pub(crate) struct NextPage {
+    /// Whether a control was found
+    pub found: bool,
+    /// How the page changed ("document", "url" or "content"), if it did
+    pub change: Option<&'static str>,
+    /// Heuristic that matched the control
+    pub rule: Option<String>,
+    /// Visible text of the control
+    pub text: Option<String>,
+    /// Link target of the control, if it is a link
+    pub href: Option<String>,
+}
+
+/// Find the page's next-page control, click it and wait for the next page
+pub(crate) fn follow_next_page(
+    context: &mut ToolContext,
+    tool: &str,
+    text: Option<&str>,
+    timeout: Duration,
+) -> Result<NextPage> {
+    let tool_error = |reason: String| BrowserError::ToolExecutionFailed {
+        tool: tool.to_string(),
+        reason,
+    };
+
+    let config = serde_json::json!({ "text": text });
+    let find_js = GO_NEXT_PAGE_JS.replace("__NEXT_PAGE_CONFIG__", &config.to_string());
+
+    let tab = context.session.tab()?;
+    let control: NextControl = tab
+        .evaluate(&find_js, false)
+        .map_err(|e| tool_error(e.to_string()))?
+        .value
+        .and_then(|v| v.as_str().map(String::from))
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .ok_or_else(|| tool_error("No result returned".to_string()))?;
+
+    if !control.found {
+        return Ok(NextPage {
+            found: false,
+            change: None,
+            rule: None,
+            text: None,
+            href: None,
+        });
+    }
+
+    let change = if control.follow_link {
+        // Only a <link rel="next"> in the head: follow it directly
+        let href = control
+            .href
+            .as_deref()
+            .ok_or_else(|| tool_error("rel=next link has no href".to_string()))?;
+        context.session.navigate(href)?;
+        context.session.wait_for_navigation()?;
+        Some("document")
+    } else {
+        if let Some(href) = control.href.as_deref()
+            && href.starts_with("http")
+        {
+            context.session.check_scope(href)?;
+        }
+
+        let element = context.session.find_element(&tab, NEXT_PAGE_MARK)?;
+        confirm_element(tool, context, &element)?;
+
+        // A fresh document drops this marker, which tells navigation from in-page updates
+        let token = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        tab.evaluate(
+            &format!("window.__browserUseNextPageMarker = \"{}\"", token),
+            false,
+        )
+        .map_err(|e| tool_error(e.to_string()))?;
+        let before = page_state(&tab)
+            .ok_or_else(|| tool_error("Could not read the page state".to_string()))?;
+
+        element.click().map_err(|e| tool_error(e.to_string()))?;
+        context.session.record_page_action();
+        wait_for_change(&tab, &before, timeout)
+    };
+
+    Ok(NextPage {
+        found: true,
+        change,
+        rule: control.rule,
+        text: control.text,
+        href: control.href,
+    })
+}
+
+impl Tool for GoNextPageTool {
+    type Params = GoNextPageParams;
+
+    fn name(&self) -> &str {
+        "go_next_page"
+    }
+
+    fn description(&self) -> &str {
+        "Find and click the page's \"next\" pagination control (rel=next, aria-labels, common texts) and report whether a new page loaded"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        params: GoNextPageParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let timeout = Duration::from_millis(params.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+        let previous_url = context.session.tab()?.get_url();
+        let next = follow_next_page(context, "go_next_page", params.text.as_deref(), timeout)?;
+        let url = context.session.tab()?.get_url();
+
+        if !next.found {
+            return Ok(ToolResult::success_with(serde_json::json!({
+                "found": false,
+                "loaded": false,
+                "url": url,
+                "message": "No next-page control found; this may be the last page"
+            })));
+        }
+
+        let message = match next.change {
+            Some(_) => format!("Moved to the next page: {}", url),
+            None => format!(
+                "Clicked the next-page control but nothing changed within {} ms",
+                timeout.as_millis()
+            ),
+        };
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "found": true,
+            "loaded": next.change.is_some(),
+            "change": next.change,
+            "rule": next.rule,
+            "text": next.text,
+            "href": next.href,
+            "previousUrl": previous_url,
+            "url": url,
+            "message": message
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_go_next_page_params() {
+        let params: GoNextPageParams = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(params.text, None);
+        assert_eq!(params.timeout_ms, None);
+
+        let params: GoNextPageParams = serde_json::from_value(serde_json::json!({
+            "text": "Older entries",
+            "timeout_ms": 5000
+        }))
+        .unwrap();
+        assert_eq!(params.text.as_deref(), Some("Older entries"));
+        assert_eq!(params.timeout_ms, Some(5000));
+    }
+
+    #[test]
+    fn test_next_control_parsing() {
+        let control: NextControl = serde_json::from_str(
+            r#"{"found": true, "rule": "rel=next", "href": "https://example.com/?page=2"}"#,
+        )
+        .unwrap();
+        assert!(control.found);
+        assert!(!control.follow_link);
+
+        let control: NextControl = serde_json::from_str(r#"{"found": false}"#).unwrap();
+        assert!(!control.found);
+        assert_eq!(control.rule, None);
+    }
+}
@@ -1,12 +1,13 @@
+use crate::dom::DeepSelector;
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the hover tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct HoverParams {
-    /// CSS selector (use either this or index, not both)
+    /// CSS selector, with `>>>` to reach into iframes and shadow roots (use either this or index, not both)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub selector: Option<String>,
 
@@ -19,7 +20,14 @@ pub struct HoverParams {
 #[derive(Default)]
 pub struct HoverTool;
 
-const HOVER_JS: &str = include_str!("hover.js");
+/// Element the hover helper dispatched to
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HoveredElement {
+    tag_name: String,
+    id: String,
+    class_name: String,
+}
 
 impl Tool for HoverTool {
     type Params = HoverParams;
@@ -28,6 +36,14 @@ impl Tool for HoverTool {
         "hover"
     }
 
+    fn description(&self) -> &str {
+        "Hover over an element specified by CSS selector or index (index obtained from browser_snapshot tool)"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive().idempotent()
+    }
+
     fn execute_typed(&self, params: HoverParams, context: &mut ToolContext) -> Result<ToolResult> {
         // Validate that exactly one selector method is provided
         match (&params.selector, &params.index) {
@@ -50,60 +66,28 @@ impl Tool for HoverTool {
         let css_selector = if let Some(selector) = params.selector {
             selector
         } else if let Some(index) = params.index {
-            let dom = context.get_dom()?;
-            let selector = dom.get_selector(index).ok_or_else(|| {
-                BrowserError::ElementNotFound(format!("No element with index {}", index))
-            })?;
-            selector.clone()
+            context.selector_for_index(index)?
         } else {
             unreachable!("Validation above ensures one field is Some")
         };
 
-        // Find the element (to verify it exists)
-
-        // Scroll into view if needed, then hover
-        let selector_json =
-            serde_json::to_string(&css_selector).expect("serializing CSS selector never fails");
-        let hover_js = HOVER_JS.replace("__SELECTOR__", &selector_json);
-
-        let result = context
+        let selector = DeepSelector::parse(&css_selector)?;
+        let element: HoveredElement = context
             .session
-            .tab()?
-            .evaluate(&hover_js, false)
-            .map_err(|e| BrowserError::ToolExecutionFailed {
-                tool: "hover".to_string(),
-                reason: e.to_string(),
+            .call_helper("hover", serde_json::json!({ "selector": selector.steps() }))
+            .map_err(|e| match e {
+                BrowserError::ElementNotFound(_) => e.with_selector(css_selector.as_str()),
+                e => BrowserError::ToolExecutionFailed {
+                    tool: "hover".to_string(),
+                    reason: e.to_string(),
+                },
             })?;
+        // Hover styles change what is shown without touching the DOM
+        context.session.record_page_action();
 
-        // Parse the JSON string returned by JavaScript
-        let result_json: serde_json::Value = if let Some(serde_json::Value::String(json_str)) =
-            result.value
-        {
-            serde_json::from_str(&json_str)
-                .unwrap_or(serde_json::json!({"success": false, "error": "Failed to parse result"}))
-        } else {
-            result
-                .value
-                .unwrap_or(serde_json::json!({"success": false, "error": "No result returned"}))
-        };
-
-        if result_json["success"].as_bool() == Some(true) {
-            Ok(ToolResult::success_with(serde_json::json!({
-                "selector": css_selector,
-                "element": {
-                    "tagName": result_json["tagName"],
-                    "id": result_json["id"],
-                    "className": result_json["className"]
-                }
-            })))
-        } else {
-            Err(BrowserError::ToolExecutionFailed {
-                tool: "hover".to_string(),
-                reason: result_json["error"]
-                    .as_str()
-                    .unwrap_or("Unknown error")
-                    .to_string(),
-            })
-        }
+        Ok(ToolResult::success_with(serde_json::json!({
+            "selector": css_selector,
+            "element": element
+        })))
     }
 }
@@ -1,12 +1,13 @@
 use crate::error::{BrowserError, Result};
 use crate::tools::snapshot::{RenderMode, render_aria_tree};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::utils::input_text;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct InputParams {
-    /// CSS selector (use either this or index, not both)
+    /// CSS selector, with `>>>` to reach into iframes and shadow roots (use either this or index, not both)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub selector: Option<String>,
 
@@ -32,6 +33,14 @@ impl Tool for InputTool {
         "input"
     }
 
+    fn description(&self) -> &str {
+        "Type text into an input element specified by CSS selector or index (index obtained from browser_snapshot tool)"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive().idempotent()
+    }
+
     fn execute_typed(&self, params: InputParams, context: &mut ToolContext) -> Result<ToolResult> {
         // Validate that exactly one selector method is provided
         match (&params.selector, &params.index) {
@@ -55,43 +64,129 @@ impl Tool for InputTool {
         let css_selector = if let Some(selector) = params.selector.clone() {
             selector
         } else if let Some(index) = params.index {
-            let dom = context.get_dom()?;
-            let selector = dom.get_selector(index).ok_or_else(|| {
-                BrowserError::ElementNotFound(format!("No element with index {}", index))
-            })?;
-            selector.clone()
+            context.selector_for_index(index)?
         } else {
             unreachable!("Validation above ensures one field is Some")
         };
 
-        let tab = context.session.tab()?;
-        let element = context.session.find_element(&tab, &css_selector)?;
-
-        if params.clear {
-            element.click().ok(); // Focus
-            // Clear with Ctrl+A and Delete
-            tab.press_key("End").ok();
-            for _ in 0..params.text.len() + 100 {
-                tab.press_key("Backspace").ok();
-            }
-        }
-
-        element
-            .type_into(&params.text)
-            .map_err(|e| BrowserError::ToolExecutionFailed {
-                tool: "input".to_string(),
-                reason: e.to_string(),
-            })?;
+        input_text("input", context, &css_selector, &params.text, params.clear)?;
 
-        let snapshot = {
+        let (snapshot, masked) = {
             let dom = context.get_dom()?;
-            render_aria_tree(&dom.root, RenderMode::Ai, None)
+            let index = match params.index {
+                Some(index) => Some(index),
+                None => dom.selectors.iter().position(|s| *s == css_selector),
+            };
+            let masked = index
+                .and_then(|index| dom.find_node_by_index(index))
+                .is_some_and(|node| node.is_masked());
+            (render_aria_tree(&dom.root, RenderMode::Ai, None), masked)
         };
 
-        let result_json = serde_json::json!({
-            "snapshot": snapshot
-        });
+        // Never echo what was typed into a password or other secret field
+        let result_json = if masked {
+            serde_json::json!({
+                "snapshot": snapshot,
+                "masked": true,
+                "text_length": params.text.chars().count()
+            })
+        } else {
+            serde_json::json!({
+                "snapshot": snapshot,
+                "text": params.text
+            })
+        };
 
         Ok(ToolResult::success_with(result_json))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::{MockAction, MockSession};
+    use crate::dom::{AriaChild, AriaNode, DomTree};
+
+    #[test]
+    fn test_input_clears_and_types() {
+        let mut tree = DomTree::new(AriaNode::fragment().with_child(AriaChild::Node(Box::new(
+            AriaNode::new("textbox", "Search").with_index(0),
+        ))));
+        tree.selectors = vec!["#q".to_string()];
+        let mock = MockSession::new(tree);
+        let session = mock.session();
+        let mut context = ToolContext::new(&session);
+
+        let params = InputParams {
+            selector: None,
+            index: Some(0),
+            text: "rust".to_string(),
+            clear: true,
+        };
+        let result = InputTool.execute_typed(params, &mut context).unwrap();
+        assert!(
+            result.data.unwrap()["snapshot"]
+                .as_str()
+                .unwrap()
+                .contains("textbox \"Search\"")
+        );
+        assert_eq!(
+            mock.actions(),
+            vec![
+                MockAction::Clear("#q".to_string()),
+                MockAction::Type {
+                    selector: "#q".to_string(),
+                    text: "rust".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_input_masks_secret_fields() {
+        let mut tree = DomTree::new(
+            AriaNode::fragment()
+                .with_child(AriaChild::Node(Box::new(
+                    AriaNode::new("textbox", "Email").with_index(0),
+                )))
+                .with_child(AriaChild::Node(Box::new(
+                    AriaNode::new("textbox", "Password")
+                        .with_index(1)
+                        .with_prop("masked", "7 characters"),
+                ))),
+        );
+        tree.selectors = vec!["#email".to_string(), "#password".to_string()];
+        let mock = MockSession::new(tree);
+        let session = mock.session();
+        let mut context = ToolContext::new(&session);
+
+        let params = InputParams {
+            selector: Some("#password".to_string()),
+            index: None,
+            text: "hunter2".to_string(),
+            clear: false,
+        };
+        let data = InputTool
+            .execute_typed(params, &mut context)
+            .unwrap()
+            .data
+            .unwrap();
+        assert_eq!(data["masked"], true);
+        assert_eq!(data["text_length"], 7);
+        assert!(!data.to_string().contains("hunter2"));
+
+        let params = InputParams {
+            selector: None,
+            index: Some(0),
+            text: "me@example.com".to_string(),
+            clear: false,
+        };
+        let data = InputTool
+            .execute_typed(params, &mut context)
+            .unwrap()
+            .data
+            .unwrap();
+        assert_eq!(data["text"], "me@example.com");
+        assert!(data.get("masked").is_none());
+    }
+}
@@ -0,0 +1,205 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::utils::{confirm_focused, type_text};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use headless_chrome::browser::tab::ModifierKey;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Default pause between steps, in milliseconds
+const DEFAULT_DELAY_MS: u64 = 100;
+
+/// One step of a key sequence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum KeyStep {
+    /// A key or chord such as "g", "Enter" or "Control+K"
+    Keys(String),
+    /// Text to type, e.g. a command palette query
+    Text {
+        /// Text to type
+        text: String,
+    },
+}
+
+/// Parameters for the key_sequence tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct KeySequenceParams {
+    /// Steps to perform in order: key names or chords joined with "+"
+    /// (e.g. "g", "Control+K", "Shift+Meta+P"), or {"text": "..."} to type text
+    pub steps: Vec<KeyStep>,
+
+    /// Pause between steps in milliseconds (default: 100)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_ms: Option<u64>,
+}
+
+/// A key pressed while holding modifiers
+#[derive(Debug)]
+struct Chord {
+    modifiers: Vec<ModifierKey>,
+    key: String,
+}
+
+impl Chord {
+    /// Parse "Control+Shift+K" style chords; a trailing "+" names the plus key
+    fn parse(chord: &str) -> Result<Self> {
+        let (modifiers, key) = if chord == "+" {
+            ("", "+")
+        } else if let Some(prefix) = chord.strip_suffix("++") {
+            (prefix, "+")
+        } else {
+            chord.rsplit_once('+').unwrap_or(("", chord))
+        };
+        if key.is_empty() {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Invalid key chord '{}': missing key",
+                chord
+            )));
+        }
+
+        let modifiers = modifiers
+            .split('+')
+            .filter(|name| !name.is_empty())
+            .map(|name| match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => Ok(ModifierKey::Ctrl),
+                "alt" | "option" => Ok(ModifierKey::Alt),
+                "shift" => Ok(ModifierKey::Shift),
+                "meta" | "cmd" | "command" => Ok(ModifierKey::Meta),
+                _ => Err(BrowserError::InvalidArgument(format!(
+                    "Unknown modifier '{}' in key chord '{}'",
+                    name, chord
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            modifiers,
+            key: key.to_string(),
+        })
+    }
+}
+
+/// Tool for multi-step keyboard shortcuts
+///
+/// Presses keys and chords and types text in order with a pause between
+/// steps, for sequences like "g" then "i" or "Control+K" then a query.
+#[derive(Default)]
+pub struct KeySequenceTool;
+
+impl Tool for KeySequenceTool {
+    type Params = KeySequenceParams;
+
+    fn name(&self) -> &str {
+        "key_sequence"
+    }
+
+    fn description(&self) -> &str {
+        "Press a sequence of keys and chords (e.g. \"g\" then \"i\", or \"Control+K\" then typing text) with a delay between steps"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
+    fn execute_typed(
+        &self,
+        params: KeySequenceParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        if params.steps.is_empty() {
+            return Err(BrowserError::InvalidArgument(
+                "key_sequence needs at least one step".to_string(),
+            ));
+        }
+
+        // Parse every chord up front so a typo does not leave a half-run sequence
+        let chords = params
+            .steps
+            .iter()
+            .map(|step| match step {
+                KeyStep::Keys(keys) => Chord::parse(keys).map(Some),
+                KeyStep::Text { .. } => Ok(None),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let delay = Duration::from_millis(params.delay_ms.unwrap_or(DEFAULT_DELAY_MS));
+
+        let tab = context.session.tab()?;
+        let tool_error = |e: anyhow::Error| BrowserError::ToolExecutionFailed {
+            tool: "key_sequence".to_string(),
+            reason: e.to_string(),
+        };
+
+        context.session.record_page_action();
+        for (i, (step, chord)) in params.steps.iter().zip(&chords).enumerate() {
+            if i > 0 {
+                std::thread::sleep(delay);
+            }
+            match (step, chord) {
+                (_, Some(chord)) => {
+                    // Focus may have moved since the last step
+                    confirm_focused("key_sequence", context)?;
+                    let modifiers = (!chord.modifiers.is_empty()).then_some(&chord.modifiers[..]);
+                    tab.press_key_with_modifiers(&chord.key, modifiers)
+                        .map_err(tool_error)?;
+                }
+                (KeyStep::Text { text }, None) => {
+                    type_text(&tab, text).map_err(tool_error)?;
+                }
+                (KeyStep::Keys(_), None) => unreachable!("every key step was parsed above"),
+            }
+        }
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "steps": params.steps,
+            "count": params.steps.len()
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_sequence_params() {
+        let json = serde_json::json!({
+            "steps": ["Control+K", {"text": "settings"}, "Enter"],
+            "delay_ms": 50
+        });
+
+        let params: KeySequenceParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.steps.len(), 3);
+        assert_eq!(params.steps[0], KeyStep::Keys("Control+K".to_string()));
+        assert_eq!(
+            params.steps[1],
+            KeyStep::Text {
+                text: "settings".to_string()
+            }
+        );
+        assert_eq!(params.delay_ms, Some(50));
+    }
+
+    #[test]
+    fn test_parse_chord() {
+        let chord = Chord::parse("g").unwrap();
+        assert!(chord.modifiers.is_empty());
+        assert_eq!(chord.key, "g");
+
+        let chord = Chord::parse("ctrl+Shift+P").unwrap();
+        assert_eq!(chord.modifiers.len(), 2);
+        assert_eq!(chord.key, "P");
+
+        let chord = Chord::parse("Control++").unwrap();
+        assert_eq!(chord.modifiers.len(), 1);
+        assert_eq!(chord.key, "+");
+        assert_eq!(Chord::parse("+").unwrap().key, "+");
+    }
+
+    #[test]
+    fn test_parse_invalid_chord() {
+        assert!(Chord::parse("Hyper+K").is_err());
+        assert!(Chord::parse("Control+").is_err());
+    }
+}
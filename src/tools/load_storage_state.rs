@@ -0,0 +1,57 @@
+use crate::error::Result;
+use crate::tools::utils::state_encryption_key;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the load_storage_state tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LoadStorageStateParams {
+    /// Path of the storageState.json file in the output directory (decrypted when BROWSER_USE_STATE_KEY is set)
+    pub path: String,
+}
+
+/// Tool for restoring cookies and localStorage from a storageState.json file
+#[derive(Default)]
+pub struct LoadStorageStateTool;
+
+impl Tool for LoadStorageStateTool {
+    type Params = LoadStorageStateParams;
+
+    fn name(&self) -> &str {
+        "load_storage_state"
+    }
+
+    fn description(&self) -> &str {
+        "Restore cookies and localStorage from a Playwright-compatible storageState.json file"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default().idempotent()
+    }
+
+    fn execute_typed(
+        &self,
+        params: LoadStorageStateParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let path = context.session.input_file(&params.path)?;
+        let key = state_encryption_key()?;
+
+        #[cfg(feature = "encryption")]
+        let state = match &key {
+            Some(key) => context.session.load_storage_state_encrypted(&path, key)?,
+            None => context.session.load_storage_state(&path)?,
+        };
+
+        #[cfg(not(feature = "encryption"))]
+        let state = context.session.load_storage_state(&path)?;
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "path": path.to_string_lossy(),
+            "cookies": state.cookies.len(),
+            "origins": state.origins.len(),
+            "encrypted": key.is_some()
+        })))
+    }
+}
@@ -1,12 +1,20 @@
 use crate::error::{BrowserError, Result};
+#[cfg(feature = "pdf")]
+use crate::pdf::PdfDocument;
+#[cfg(feature = "pdf")]
+use crate::tools::download_url::{DEFAULT_MAX_BYTES, fetch_in_page};
 use crate::tools::html_to_markdown::convert_html_to_markdown;
 use crate::tools::readability_script::READABILITY_SCRIPT;
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::utils::is_pdf_page;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use headless_chrome::Tab;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "pdf")]
+use url::Url;
 
 /// Parameters for getting markdown content with pagination support
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct GetMarkdownParams {
     /// Page number to extract (1-based index, default: 1)
     #[serde(default = "default_page")]
@@ -44,78 +52,31 @@ impl Tool for GetMarkdownTool {
         "get_markdown"
     }
 
+    fn description(&self) -> &str {
+        "Get the markdown content of the current page (use this tool only for information extraction; for interaction use the snapshot tool instead)"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
     fn execute_typed(
         &self,
         params: GetMarkdownParams,
         context: &mut ToolContext,
     ) -> Result<ToolResult> {
-        // Wait for network idle with a timeout
-        // Since headless_chrome doesn't have a direct network idle wait,
-        // we add a small delay to let dynamic content load
-        std::thread::sleep(std::time::Duration::from_millis(1000));
-
-        // Inject Readability.js script and the conversion script
-        // Use 'var' instead of 'const' to allow redeclaration on subsequent calls
-        // This prevents "identifier already declared" errors when calling get_markdown multiple times
-        let js_code = format!(
-            "var READABILITY_SCRIPT = {};\n{}",
-            serde_json::to_string(READABILITY_SCRIPT).unwrap(),
-            include_str!("convert_to_markdown.js")
-        );
-
-        // Execute the JavaScript to extract and convert content
-        let result = context
-            .session
-            .tab()?
-            .evaluate(&js_code, false)
-            .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
-
-        // Parse the result
-        let result_value = result.value.ok_or_else(|| {
-            // Capture description if available
-            let description = result
-                .description
-                .map(|d| format!("Description: {}", d))
-                .unwrap_or_else(|| format!("Type: {:?}", result.Type));
-
-            BrowserError::ToolExecutionFailed {
-                tool: "get_markdown".to_string(),
-                reason: format!("No value returned from JavaScript. {}", description),
-            }
-        })?;
-
-        // The JavaScript returns a JSON string, so we need to parse it
-        let extraction_result: ExtractionResult = if let Some(json_str) = result_value.as_str() {
-            serde_json::from_str(json_str).map_err(|e| BrowserError::ToolExecutionFailed {
-                tool: "get_markdown".to_string(),
-                reason: format!("Failed to parse extraction result: {}", e),
-            })?
+        let tab = context.session.tab()?;
+        let (extraction_result, full_markdown) = if is_pdf_page(&tab) {
+            pdf_content(&tab)?
         } else {
-            // If it's already an object, try to deserialize directly
-            serde_json::from_value(result_value).map_err(|e| BrowserError::ToolExecutionFailed {
-                tool: "get_markdown".to_string(),
-                reason: format!("Failed to deserialize extraction result: {}", e),
-            })?
+            article_content(&tab)?
         };
 
-        // Check if Readability failed
-        if extraction_result.readability_failed {
-            return Err(BrowserError::ToolExecutionFailed {
-                tool: "get_markdown".to_string(),
-                reason: extraction_result
-                    .error
-                    .unwrap_or_else(|| "Readability extraction failed".to_string()),
-            });
-        }
-
-        // Convert the extracted HTML content to Markdown
-        let full_markdown = convert_html_to_markdown(&extraction_result.content);
-
         // Calculate pagination information
         let total_pages = if full_markdown.is_empty() {
             1
         } else {
-            (full_markdown.len() + params.page_size - 1) / params.page_size
+            full_markdown.len().div_ceil(params.page_size)
         };
 
         // Clamp page number to valid range
@@ -171,8 +132,96 @@ impl Tool for GetMarkdownTool {
     }
 }
 
+/// Main content of an HTML page via Readability, with its markdown
+fn article_content(tab: &Tab) -> Result<(ExtractionResult, String)> {
+    // Wait for network idle with a timeout
+    // Since headless_chrome doesn't have a direct network idle wait,
+    // we add a small delay to let dynamic content load
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+
+    // Inject Readability.js script and the conversion script
+    // Use 'var' instead of 'const' to allow redeclaration on subsequent calls
+    // This prevents "identifier already declared" errors when calling get_markdown multiple times
+    let js_code = format!(
+        "var READABILITY_SCRIPT = {};\n{}",
+        serde_json::to_string(READABILITY_SCRIPT).unwrap(),
+        include_str!("convert_to_markdown.js")
+    );
+
+    // Execute the JavaScript to extract and convert content
+    let result = tab
+        .evaluate(&js_code, false)
+        .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
+
+    // Parse the result
+    let result_value = result.value.ok_or_else(|| {
+        // Capture description if available
+        let description = result
+            .description
+            .map(|d| format!("Description: {}", d))
+            .unwrap_or_else(|| format!("Type: {:?}", result.Type));
+
+        BrowserError::ToolExecutionFailed {
+            tool: "get_markdown".to_string(),
+            reason: format!("No value returned from JavaScript. {}", description),
+        }
+    })?;
+
+    // The JavaScript returns a JSON string, so we need to parse it
+    let extraction_result: ExtractionResult = if let Some(json_str) = result_value.as_str() {
+        serde_json::from_str(json_str).map_err(|e| BrowserError::ToolExecutionFailed {
+            tool: "get_markdown".to_string(),
+            reason: format!("Failed to parse extraction result: {}", e),
+        })?
+    } else {
+        // If it's already an object, try to deserialize directly
+        serde_json::from_value(result_value).map_err(|e| BrowserError::ToolExecutionFailed {
+            tool: "get_markdown".to_string(),
+            reason: format!("Failed to deserialize extraction result: {}", e),
+        })?
+    };
+
+    // Check if Readability failed
+    if extraction_result.readability_failed {
+        return Err(BrowserError::ToolExecutionFailed {
+            tool: "get_markdown".to_string(),
+            reason: extraction_result
+                .error
+                .unwrap_or_else(|| "Readability extraction failed".to_string()),
+        });
+    }
+
+    // Convert the extracted HTML content to Markdown
+    let markdown = convert_html_to_markdown(&extraction_result.content);
+    Ok((extraction_result, markdown))
+}
+
+/// Text of the PDF the tab shows, with a heading per page
+#[cfg(feature = "pdf")]
+fn pdf_content(tab: &Tab) -> Result<(ExtractionResult, String)> {
+    let url = Url::parse(&tab.get_url())
+        .map_err(|e| BrowserError::InvalidArgument(format!("Invalid page URL: {}", e)))?;
+    let fetched = fetch_in_page(tab, &url, DEFAULT_MAX_BYTES)?;
+    let pdf = PdfDocument::parse(&fetched.bytes)?;
+    let extraction_result = ExtractionResult {
+        title: pdf.title.clone().unwrap_or_default(),
+        text_content: pdf.text(),
+        url: url.to_string(),
+        ..Default::default()
+    };
+    Ok((extraction_result, pdf.to_markdown()))
+}
+
+#[cfg(not(feature = "pdf"))]
+fn pdf_content(_tab: &Tab) -> Result<(ExtractionResult, String)> {
+    Err(BrowserError::ToolExecutionFailed {
+        tool: "get_markdown".to_string(),
+        reason: "The page is a PDF; reading PDFs requires the `pdf` feature".to_string(),
+    })
+}
+
 /// Structure for extraction result returned from JavaScript
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ExtractionResult {
     title: String,
@@ -0,0 +1,82 @@
+(async function () {
+  const config = __MEDIA_CONFIG__;
+  const element = document.querySelector(config.selector);
+
+  if (!element) {
+    return JSON.stringify({ success: false, error: "Element not found" });
+  }
+
+  if (!(element instanceof HTMLMediaElement)) {
+    return JSON.stringify({ success: false, error: "Element is not a video or audio element" });
+  }
+
+  function waitFor(eventName, timeoutMs) {
+    return new Promise(function (resolve) {
+      const timer = setTimeout(resolve, timeoutMs);
+      element.addEventListener(
+        eventName,
+        function () {
+          clearTimeout(timer);
+          resolve();
+        },
+        { once: true }
+      );
+    });
+  }
+
+  switch (config.action) {
+    case "play":
+      try {
+        await element.play();
+      } catch (e) {
+        return JSON.stringify({ success: false, error: "Playback was blocked: " + e.message });
+      }
+      break;
+    case "pause":
+      element.pause();
+      break;
+    case "seek": {
+      const target = Math.max(0, config.time || 0);
+      const seeked = waitFor("seeked", 5000);
+      element.currentTime = isFinite(element.duration) ? Math.min(target, element.duration) : target;
+      await seeked;
+      if (element.readyState < 2) {
+        await waitFor("loadeddata", 5000);
+      }
+      break;
+    }
+    case "mute":
+      element.muted = true;
+      break;
+    case "unmute":
+      element.muted = false;
+      break;
+    case "status":
+      break;
+    default:
+      return JSON.stringify({ success: false, error: "Unknown action: " + config.action });
+  }
+
+  if (config.volume !== null && config.volume !== undefined) {
+    element.volume = Math.min(1, Math.max(0, config.volume));
+  }
+  if (config.playbackRate !== null && config.playbackRate !== undefined) {
+    element.playbackRate = config.playbackRate;
+  }
+
+  return JSON.stringify({
+    success: true,
+    media: {
+      tag: element.tagName.toLowerCase(),
+      src: element.currentSrc || element.src || "",
+      currentTime: element.currentTime,
+      duration: isFinite(element.duration) ? element.duration : null,
+      paused: element.paused,
+      ended: element.ended,
+      muted: element.muted,
+      volume: element.volume,
+      playbackRate: element.playbackRate,
+      readyState: element.readyState,
+    },
+  });
+})()
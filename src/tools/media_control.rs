@@ -0,0 +1,154 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Action to perform on a media element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum MediaAction {
+    /// Start playback
+    Play,
+    /// Pause playback
+    Pause,
+    /// Jump to `time` seconds and wait until the frame is available
+    Seek,
+    /// Mute audio
+    Mute,
+    /// Unmute audio
+    Unmute,
+    /// Only report the current state
+    Status,
+}
+
+/// Parameters for the media_control tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MediaControlParams {
+    /// Action to perform
+    pub action: MediaAction,
+
+    /// CSS selector of the video/audio element (defaults to the first one on the page)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree (use either this or selector)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Target time in seconds for the seek action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<f64>,
+
+    /// Volume to set, from 0.0 to 1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f64>,
+
+    /// Playback rate to set (1.0 is normal speed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playback_rate: Option<f64>,
+}
+
+/// Tool for controlling video and audio elements
+#[derive(Default)]
+pub struct MediaControlTool;
+
+const MEDIA_CONTROL_JS: &str = include_str!("media_control.js");
+
+impl Tool for MediaControlTool {
+    type Params = MediaControlParams;
+
+    fn name(&self) -> &str {
+        "media_control"
+    }
+
+    fn description(&self) -> &str {
+        "Play, pause, seek, mute or inspect a video/audio element and report its current time and duration"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        params: MediaControlParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let css_selector = match (params.selector, params.index) {
+            (Some(_), Some(_)) => {
+                return Err(BrowserError::ToolExecutionFailed {
+                    tool: "media_control".to_string(),
+                    reason: "Cannot specify both 'selector' and 'index'. Use one or the other."
+                        .to_string(),
+                });
+            }
+            (Some(selector), None) => selector,
+            (None, Some(index)) => context.selector_for_index(index)?,
+            (None, None) => "video, audio".to_string(),
+        };
+
+        if params.action == MediaAction::Seek && params.time.is_none() {
+            return Err(BrowserError::InvalidArgument(
+                "The seek action requires 'time'".to_string(),
+            ));
+        }
+
+        let media_config = serde_json::json!({
+            "selector": css_selector,
+            "action": params.action,
+            "time": params.time,
+            "volume": params.volume,
+            "playbackRate": params.playback_rate,
+        });
+        let media_js = MEDIA_CONTROL_JS.replace("__MEDIA_CONFIG__", &media_config.to_string());
+
+        let result = context
+            .session
+            .tab()?
+            .evaluate(&media_js, true)
+            .map_err(|e| BrowserError::ToolExecutionFailed {
+                tool: "media_control".to_string(),
+                reason: e.to_string(),
+            })?;
+
+        let result_json: serde_json::Value = result
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or(serde_json::json!({"success": false, "error": "No result returned"}));
+
+        if result_json["success"].as_bool() == Some(true) {
+            Ok(ToolResult::success_with(serde_json::json!({
+                "selector": css_selector,
+                "action": params.action,
+                "media": result_json["media"]
+            })))
+        } else {
+            Err(BrowserError::ToolExecutionFailed {
+                tool: "media_control".to_string(),
+                reason: result_json["error"]
+                    .as_str()
+                    .unwrap_or("Unknown error")
+                    .to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_control_params() {
+        let json = serde_json::json!({
+            "action": "seek",
+            "time": 12.5
+        });
+        let params: MediaControlParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.action, MediaAction::Seek);
+        assert_eq!(params.time, Some(12.5));
+        assert!(params.selector.is_none());
+    }
+}
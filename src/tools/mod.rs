@@ -2,60 +2,174 @@
 //!
 //! This module provides a framework for browser automation tools and
 //! includes implementations of common browser operations.
-
+//!
+//! ## Writing a Tool
+//!
+//! Every tool implements [`Tool`] with a typed parameter struct; the JSON
+//! schema is derived from the struct and JSON arguments are parsed into it
+//! before [`Tool::execute_typed`] runs. Registries store tools as
+//! [`DynTool`] objects, which every [`Tool`] is automatically.
+//!
+//! ```rust,no_run
+//! use browser_use::tools::{Tool, ToolContext, ToolRegistry, ToolResult};
+//! use schemars::JsonSchema;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, JsonSchema)]
+//! struct TitleParams {
+//!     /// Uppercase the title
+//!     #[serde(default)]
+//!     upper: bool,
+//! }
+//!
+//! struct TitleTool;
+//!
+//! impl Tool for TitleTool {
+//!     type Params = TitleParams;
+//!
+//!     fn name(&self) -> &str {
+//!         "title"
+//!     }
+//!
+//!     fn execute_typed(
+//!         &self,
+//!         params: TitleParams,
+//!         context: &mut ToolContext,
+//!     ) -> browser_use::Result<ToolResult> {
+//!         let title = context.session.tab()?.get_title()?;
+//!         let title = if params.upper { title.to_uppercase() } else { title };
+//!         Ok(ToolResult::success_with(serde_json::json!({ "title": title })))
+//!     }
+//! }
+//!
+//! let mut registry = ToolRegistry::with_defaults();
+//! registry.register(TitleTool);
+//! ```
+
+pub mod audit_accessibility;
+pub mod capture_canvas;
+pub mod check_fingerprint;
+pub mod checkpoint;
 pub mod click;
 pub mod close;
 pub mod close_tab;
+pub mod compare_screenshot;
+pub mod crawl;
+pub mod custom_tool;
+pub mod discover_api_endpoints;
+pub mod dismiss_overlays;
+pub mod download_url;
 pub mod evaluate;
 pub mod extract;
+pub mod extract_images;
+pub mod extract_table_all_pages;
+pub mod get_frame_tree;
+pub mod get_graphql_requests;
+pub mod get_html;
+pub mod get_tab_order;
+pub mod get_text;
 pub mod go_back;
 pub mod go_forward;
+pub mod go_next_page;
 pub mod hover;
 pub mod html_to_markdown;
 pub mod input;
+pub mod key_sequence;
+pub mod load_storage_state;
 pub mod markdown;
+pub mod media_control;
 pub mod navigate;
+pub mod new_context;
 pub mod new_tab;
+#[cfg(feature = "ocr")]
+pub mod ocr_screenshot;
+pub mod parse_file;
+pub mod plugins;
 pub mod press_key;
 pub mod read_links;
+#[cfg(feature = "pdf")]
+pub mod read_pdf;
 pub mod readability_script;
+pub mod rollback;
+pub mod save_storage_state;
 pub mod screenshot;
+pub mod screenshot_urls;
 pub mod scroll;
+pub mod scroll_to_load;
 pub mod select;
+pub mod set_date;
+pub mod set_scope;
 pub mod snapshot;
 pub mod switch_tab;
 pub mod tab_list;
 mod utils;
 pub mod wait;
+pub mod workflow;
 
 // Re-export Params types for use by MCP layer
+pub use audit_accessibility::{AccessibilityIssue, AuditAccessibilityParams};
+pub use capture_canvas::CaptureCanvasParams;
+pub use check_fingerprint::CheckFingerprintParams;
+pub use checkpoint::CheckpointParams;
 pub use click::ClickParams;
 pub use close::CloseParams;
 pub use close_tab::CloseTabParams;
+pub use compare_screenshot::CompareScreenshotParams;
+pub use crawl::CrawlParams;
+pub use custom_tool::{CustomTool, CustomToolDefinition};
+pub use discover_api_endpoints::DiscoverApiEndpointsParams;
+pub use dismiss_overlays::{DismissOverlaysParams, DismissedOverlay};
+pub use download_url::DownloadUrlParams;
 pub use evaluate::EvaluateParams;
 pub use extract::ExtractParams;
+pub use extract_images::ExtractImagesParams;
+pub use extract_table_all_pages::ExtractTableAllPagesParams;
+pub use get_frame_tree::{FrameInfo, GetFrameTreeParams};
+pub use get_graphql_requests::GetGraphqlRequestsParams;
+pub use get_html::GetHtmlParams;
+pub use get_tab_order::{GetTabOrderParams, TabStop};
+pub use get_text::{GetTextParams, TextBlock};
 pub use go_back::GoBackParams;
 pub use go_forward::GoForwardParams;
+pub use go_next_page::GoNextPageParams;
 pub use hover::HoverParams;
 pub use input::InputParams;
+pub use key_sequence::{KeySequenceParams, KeyStep};
+pub use load_storage_state::LoadStorageStateParams;
 pub use markdown::GetMarkdownParams;
+pub use media_control::MediaControlParams;
 pub use navigate::NavigateParams;
+pub use new_context::NewContextParams;
 pub use new_tab::NewTabParams;
+#[cfg(feature = "ocr")]
+pub use ocr_screenshot::OcrScreenshotParams;
+pub use parse_file::ParseFileParams;
+pub use plugins::load_plugins;
 pub use press_key::PressKeyParams;
 pub use read_links::ReadLinksParams;
+#[cfg(feature = "pdf")]
+pub use read_pdf::ReadPdfParams;
+pub use rollback::RollbackParams;
+pub use save_storage_state::SaveStorageStateParams;
 pub use screenshot::ScreenshotParams;
+pub use screenshot_urls::ScreenshotUrlsParams;
 pub use scroll::ScrollParams;
+pub use scroll_to_load::ScrollToLoadParams;
 pub use select::SelectParams;
+pub use set_date::SetDateParams;
+pub use set_scope::SetScopeParams;
 pub use snapshot::SnapshotParams;
 pub use switch_tab::SwitchTabParams;
 pub use tab_list::TabListParams;
-pub use wait::WaitParams;
+pub use wait::{WaitCondition, WaitParams};
+pub use workflow::{WorkflowStep, WorkflowTool, WorkflowToolDefinition};
 
-use crate::browser::BrowserSession;
+use crate::browser::{BrowserSession, PageGeneration};
 use crate::dom::DomTree;
-use crate::error::Result;
+use crate::error::{BrowserError, Result};
+use crate::plugin::{ToolPack, check_pack};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 /// Tool execution context
@@ -65,6 +179,9 @@ pub struct ToolContext<'a> {
 
     /// Optional DOM tree (extracted on demand)
     pub dom_tree: Option<DomTree>,
+
+    /// Page generation `dom_tree` was extracted at
+    dom_generation: Option<PageGeneration>,
 }
 
 impl<'a> ToolContext<'a> {
@@ -73,24 +190,61 @@ impl<'a> ToolContext<'a> {
         Self {
             session,
             dom_tree: None,
+            dom_generation: None,
         }
     }
 
     /// Create a context with a pre-extracted DOM tree
+    ///
+    /// The tree is taken to describe the page as it is now; it is replaced
+    /// once the page changes.
     pub fn with_dom(session: &'a BrowserSession, dom_tree: DomTree) -> Self {
         Self {
             session,
             dom_tree: Some(dom_tree),
+            dom_generation: Some(session.page_generation()),
         }
     }
 
-    /// Get or extract the DOM tree
+    /// Get the DOM tree, extracting it again if the page changed since it was cached
+    ///
+    /// The cached tree is reused only while the page generation (document,
+    /// DOM mutations and recorded actions) stays the same.
     pub fn get_dom(&mut self) -> Result<&DomTree> {
-        if self.dom_tree.is_none() {
+        let current = self.session.page_generation();
+        let fresh = self.dom_tree.is_some()
+            && self
+                .dom_generation
+                .as_ref()
+                .is_some_and(|seen| seen.matches(&current));
+        if !fresh {
             self.dom_tree = Some(self.session.extract_dom()?);
+            self.dom_generation = Some(current);
         }
         Ok(self.dom_tree.as_ref().unwrap())
     }
+
+    /// Drop the cached DOM tree so the next [`get_dom`](Self::get_dom) extracts it again
+    pub fn invalidate_dom(&mut self) {
+        self.dom_tree = None;
+        self.dom_generation = None;
+    }
+
+    /// CSS selector of the element with the given index
+    ///
+    /// Fails with `ElementNotFound`, listing the elements the caller may have
+    /// meant, when the current DOM has no such element.
+    pub fn selector_for_index(&mut self, index: usize) -> Result<String> {
+        let session = self.session;
+        let dom = self.get_dom()?;
+        match dom.get_selector(index) {
+            Some(selector) => Ok(selector.clone()),
+            None => Err(
+                BrowserError::ElementNotFound(format!("No element with index {}", index))
+                    .with_suggestions(session.suggest_for_index(dom, index)),
+            ),
+        }
+    }
 }
 
 /// Result of tool execution
@@ -150,17 +304,90 @@ impl ToolResult {
     }
 }
 
+/// Hints about a tool's side effects, for clients that decide which calls need approval
+///
+/// These mirror the MCP tool annotations. The default claims nothing: the
+/// tool may change and destroy state, and repeating a call may do more.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct ToolAnnotations {
+    /// Only observes the page and session, changing neither them nor any file
+    pub read_only: bool,
+
+    /// May irreversibly change or discard state, such as submitting a form or closing a tab
+    pub destructive: bool,
+
+    /// Repeating a call with the same arguments has no further effect
+    pub idempotent: bool,
+}
+
+impl Default for ToolAnnotations {
+    fn default() -> Self {
+        Self {
+            read_only: false,
+            destructive: true,
+            idempotent: false,
+        }
+    }
+}
+
+impl ToolAnnotations {
+    /// A tool that only observes
+    pub fn read_only() -> Self {
+        Self {
+            read_only: true,
+            destructive: false,
+            idempotent: true,
+        }
+    }
+
+    /// A tool that changes state but never discards any, such as navigating or saving a new file
+    pub fn additive() -> Self {
+        Self {
+            read_only: false,
+            destructive: false,
+            idempotent: false,
+        }
+    }
+
+    /// Builder method: mark repeated calls with the same arguments as having no further effect
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+        self
+    }
+}
+
 /// Trait for browser automation tools with associated parameter types
-pub trait Tool: Send + Sync + Default {
+pub trait Tool: Send + Sync {
     /// Associated parameter type for this tool
+    #[cfg(feature = "schema")]
     type Params: serde::Serialize + for<'de> serde::Deserialize<'de> + schemars::JsonSchema;
 
+    /// Associated parameter type for this tool
+    #[cfg(not(feature = "schema"))]
+    type Params: serde::Serialize + for<'de> serde::Deserialize<'de>;
+
     /// Get tool name
     fn name(&self) -> &str;
 
+    /// What the tool does, shown to clients that list tools (default: empty)
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Side effects of the tool, shown to clients that list tools (default: may destroy state)
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
     /// Get tool parameter schema (JSON Schema)
+    ///
+    /// Without the `schema` feature the default is a schema accepting any object.
     fn parameters_schema(&self) -> Value {
-        serde_json::to_value(schemars::schema_for!(Self::Params)).unwrap_or_default()
+        #[cfg(feature = "schema")]
+        return serde_json::to_value(schemars::schema_for!(Self::Params)).unwrap_or_default();
+
+        #[cfg(not(feature = "schema"))]
+        serde_json::json!({ "type": "object" })
     }
 
     /// Execute the tool with strongly-typed parameters
@@ -168,17 +395,35 @@ pub trait Tool: Send + Sync + Default {
 
     /// Execute the tool with JSON parameters (default implementation)
     fn execute(&self, params: Value, context: &mut ToolContext) -> Result<ToolResult> {
-        let typed_params: Self::Params = serde_json::from_value(params).map_err(|e| {
-            crate::error::BrowserError::InvalidArgument(format!("Invalid parameters: {}", e))
-        })?;
+        let typed_params: Self::Params = serde_json::from_value(params)
+            .map_err(|e| BrowserError::InvalidArgument(format!("Invalid parameters: {}", e)))?;
         self.execute_typed(typed_params, context)
+            .map_err(|e| e.with_tool(self.name()))
     }
 }
 
 /// Type-erased tool trait for dynamic dispatch
+///
+/// Implemented for every [`Tool`]; implement it directly only for tools whose
+/// parameters are not known at compile time.
 pub trait DynTool: Send + Sync {
+    /// Get tool name
     fn name(&self) -> &str;
+
+    /// What the tool does, for tools that describe themselves (default: empty)
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Side effects of the tool (default: may destroy state)
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
+    /// Get tool parameter schema (JSON Schema)
     fn parameters_schema(&self) -> Value;
+
+    /// Execute the tool with JSON parameters
     fn execute(&self, params: Value, context: &mut ToolContext) -> Result<ToolResult>;
 }
 
@@ -188,6 +433,14 @@ impl<T: Tool> DynTool for T {
         Tool::name(self)
     }
 
+    fn description(&self) -> &str {
+        Tool::description(self)
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        Tool::annotations(self)
+    }
+
     fn parameters_schema(&self) -> Value {
         Tool::parameters_schema(self)
     }
@@ -200,6 +453,9 @@ impl<T: Tool> DynTool for T {
 /// Tool registry for managing and accessing tools
 pub struct ToolRegistry {
     tools: HashMap<String, Arc<dyn DynTool>>,
+
+    /// Names of tools added with [`ToolRegistry::define_tool`]
+    custom: HashSet<String>,
 }
 
 impl ToolRegistry {
@@ -207,6 +463,7 @@ impl ToolRegistry {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            custom: HashSet::new(),
         }
     }
 
@@ -224,25 +481,59 @@ impl ToolRegistry {
         registry.register(click::ClickTool);
         registry.register(input::InputTool);
         registry.register(select::SelectTool);
+        registry.register(set_date::SetDateTool);
         registry.register(hover::HoverTool);
         registry.register(press_key::PressKeyTool);
+        registry.register(key_sequence::KeySequenceTool);
+        registry.register(go_next_page::GoNextPageTool);
+        registry.register(dismiss_overlays::DismissOverlaysTool);
         registry.register(scroll::ScrollTool);
+        registry.register(scroll_to_load::ScrollToLoadTool);
+        registry.register(media_control::MediaControlTool);
 
         // Register tab management tools
         registry.register(new_tab::NewTabTool);
+        registry.register(new_context::NewContextTool);
         registry.register(tab_list::TabListTool);
         registry.register(switch_tab::SwitchTabTool);
         registry.register(close_tab::CloseTabTool);
+        registry.register(get_frame_tree::GetFrameTreeTool);
+
+        // Register session state tools
+        registry.register(save_storage_state::SaveStorageStateTool);
+        registry.register(load_storage_state::LoadStorageStateTool);
+        registry.register(checkpoint::CheckpointTool);
+        registry.register(rollback::RollbackTool);
+        registry.register(set_scope::SetScopeTool);
 
         // Register reading and extraction tools
         registry.register(extract::ExtractContentTool);
         registry.register(markdown::GetMarkdownTool);
+        registry.register(get_html::GetHtmlTool);
+        registry.register(get_text::GetTextTool);
         registry.register(read_links::ReadLinksTool);
+        registry.register(extract_images::ExtractImagesTool);
+        registry.register(get_tab_order::GetTabOrderTool);
+        registry.register(audit_accessibility::AuditAccessibilityTool);
+        registry.register(download_url::DownloadUrlTool);
+        #[cfg(feature = "pdf")]
+        registry.register(read_pdf::ReadPdfTool);
+        registry.register(parse_file::ParseFileTool);
+        registry.register(get_graphql_requests::GetGraphqlRequestsTool);
+        registry.register(discover_api_endpoints::DiscoverApiEndpointsTool);
         registry.register(snapshot::SnapshotTool);
+        registry.register(crawl::CrawlTool);
+        registry.register(extract_table_all_pages::ExtractTableAllPagesTool);
 
         // Register utility tools
         registry.register(screenshot::ScreenshotTool);
+        registry.register(screenshot_urls::ScreenshotUrlsTool);
+        registry.register(compare_screenshot::CompareScreenshotTool);
+        registry.register(capture_canvas::CaptureCanvasTool);
+        #[cfg(feature = "ocr")]
+        registry.register(ocr_screenshot::OcrScreenshotTool);
         registry.register(evaluate::EvaluateTool);
+        registry.register(check_fingerprint::CheckFingerprintTool);
         registry.register(close::CloseTool);
 
         registry
@@ -250,8 +541,59 @@ impl ToolRegistry {
 
     /// Register a tool
     pub fn register<T: Tool + 'static>(&mut self, tool: T) {
+        self.register_dyn(Arc::new(tool));
+    }
+
+    /// Register a type-erased tool
+    pub fn register_dyn(&mut self, tool: Arc<dyn DynTool>) {
+        let name = tool.name().to_string();
+        self.custom.remove(&name);
+        self.tools.insert(name, tool);
+    }
+
+    /// Add a tool that runs a JavaScript snippet, replacing one defined earlier with the same name
+    ///
+    /// Returns whether an earlier definition was replaced. Names of other
+    /// registered tools cannot be reused.
+    pub fn define_tool(&mut self, definition: CustomToolDefinition) -> Result<bool> {
+        let tool = CustomTool::new(definition)?;
         let name = tool.name().to_string();
-        self.tools.insert(name, Arc::new(tool));
+        let replaced = self.custom.contains(&name);
+        if self.has(&name) && !replaced {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Tool '{}' already exists and cannot be redefined",
+                name
+            )));
+        }
+        self.register_dyn(Arc::new(tool));
+        self.custom.insert(name);
+        Ok(replaced)
+    }
+
+    /// Register every tool of a [`ToolPack`]
+    ///
+    /// Fails without registering anything if the pack was written for another
+    /// plugin API version or one of its tools has the name of a registered tool.
+    pub fn register_pack(&mut self, pack: &dyn ToolPack) -> Result<()> {
+        check_pack(pack)?;
+        let tools = pack.tools();
+        if let Some(tool) = tools.iter().find(|tool| self.has(tool.name())) {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Tool pack '{}' defines '{}', which is already registered",
+                pack.name(),
+                tool.name()
+            )));
+        }
+        for tool in tools {
+            self.register_dyn(tool);
+        }
+        Ok(())
+    }
+
+    /// Add a [`ToolPack`]'s tools, for building registries in one expression
+    pub fn with_pack(mut self, pack: &dyn ToolPack) -> Result<Self> {
+        self.register_pack(pack)?;
+        Ok(self)
     }
 
     /// Get a tool by name
@@ -325,4 +667,71 @@ mod tests {
 
         assert!(result.metadata.contains_key("duration_ms"));
     }
+
+    #[test]
+    fn test_dom_cache_follows_page_actions() {
+        use crate::browser::MockSession;
+        use crate::dom::{AriaChild, AriaNode};
+
+        fn page(name: &str) -> DomTree {
+            let mut tree = DomTree::new(AriaNode::fragment().with_child(AriaChild::Node(
+                Box::new(AriaNode::new("button", name).with_index(0)),
+            )));
+            tree.selectors = vec!["#toggle".to_string()];
+            tree
+        }
+        fn button(context: &mut ToolContext) -> String {
+            let dom = context.get_dom().unwrap();
+            dom.find_node_by_index(0).unwrap().name.clone()
+        }
+
+        let mock = MockSession::new(page("Open"))
+            .on_click("#toggle", |mock_page| mock_page.dom = page("Close"));
+        let session = mock.session();
+        let mut context = ToolContext::new(&session);
+        assert_eq!(button(&mut context), "Open");
+
+        // Without an action the cached tree is reused
+        mock.set_dom(page("Changed"));
+        assert_eq!(button(&mut context), "Open");
+
+        // A click is an action, so the next lookup sees the page it left
+        let params = serde_json::json!({ "index": 0, "observe_ms": 0 });
+        ToolRegistry::with_defaults()
+            .execute("click", params, &mut context)
+            .unwrap();
+        assert_eq!(button(&mut context), "Close");
+    }
+
+    #[test]
+    fn test_define_tool() {
+        let definition = |name: &str| CustomToolDefinition {
+            name: name.to_string(),
+            description: "Page title".to_string(),
+            parameters: None,
+            script: "return document.title;".to_string(),
+        };
+        let mut registry = ToolRegistry::with_defaults();
+        let count = registry.count();
+
+        assert!(!registry.define_tool(definition("page_title")).unwrap());
+        assert!(registry.define_tool(definition("page_title")).unwrap());
+        assert!(registry.has("page_title"));
+        assert_eq!(registry.count(), count + 1);
+
+        assert!(registry.define_tool(definition("navigate")).is_err());
+    }
+
+    #[test]
+    fn test_default_tools_are_described() {
+        let registry = ToolRegistry::with_defaults();
+        for name in registry.list_names() {
+            let tool = registry.get(&name).unwrap();
+            assert!(
+                !tool.description().is_empty(),
+                "{} has no description",
+                name
+            );
+        }
+    }
 }
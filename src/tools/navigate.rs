@@ -1,12 +1,15 @@
+use crate::browser::{LoadStatus, NavigateOptions};
 use crate::error::Result;
 use crate::tools::snapshot::{RenderMode, render_aria_tree};
 use crate::tools::utils::normalize_url;
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// Parameters for the navigate tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NavigateParams {
     /// URL to navigate to
     pub url: String,
@@ -14,6 +17,19 @@ pub struct NavigateParams {
     /// Wait for navigation to complete (default: true)
     #[serde(default = "default_wait")]
     pub wait_for_load: bool,
+
+    /// How long to wait for the page to load in milliseconds (default: the session's navigation timeout)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+
+    /// Extra headers to send with this navigation's document request,
+    /// e.g. {"Accept-Language": "de-DE"}
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+
+    /// Referrer to navigate with, e.g. the page that links here
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<String>,
 }
 
 fn default_wait() -> bool {
@@ -31,6 +47,14 @@ impl Tool for NavigateTool {
         "navigate"
     }
 
+    fn description(&self) -> &str {
+        "Navigate to a specified URL in the browser"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive().idempotent()
+    }
+
     fn execute_typed(
         &self,
         params: NavigateParams,
@@ -39,21 +63,75 @@ impl Tool for NavigateTool {
         // Normalize the URL
         let normalized_url = normalize_url(&params.url);
 
-        // Navigate to normalized URL
-        context.session.navigate(&normalized_url)?;
-
-        // Wait for navigation if requested
-        if params.wait_for_load {
-            context.session.wait_for_navigation()?;
-        }
+        // Navigate to normalized URL, recording the main document's response
+        let options = NavigateOptions {
+            headers: params.headers.unwrap_or_default(),
+            referrer: params.referrer,
+            timeout: params.timeout_ms.map(Duration::from_millis),
+        };
+        let (response, load) = context.session.navigate_with_response(
+            &normalized_url,
+            params.wait_for_load,
+            &options,
+        )?;
 
         let snapshot = {
             let dom = context.get_dom()?;
             render_aria_tree(&dom.root, RenderMode::Ai, None)
         };
 
-        Ok(ToolResult::success_with(serde_json::json!({
+        let mut result = serde_json::json!({
             "snapshot": snapshot
-        })))
+        });
+        if let Some(response) = response {
+            result["status"] = response.status.into();
+            result["statusText"] = response.status_text.into();
+            result["url"] = response.url.into();
+            result["redirects"] = serde_json::json!(response.redirects);
+        }
+        if let Some(load) = load {
+            result["load"] = serde_json::json!(load);
+            if load == LoadStatus::Interactive {
+                result["message"] =
+                    "Page is interactive but not fully loaded; some content may still be missing"
+                        .into();
+            }
+        }
+
+        Ok(ToolResult::success_with(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::{LaunchOptions, MockAction, MockSession, OriginScope};
+    use crate::dom::{AriaNode, DomTree};
+    use crate::error::ErrorCode;
+
+    fn navigate(url: &str) -> NavigateParams {
+        serde_json::from_value(serde_json::json!({ "url": url })).unwrap()
+    }
+
+    #[test]
+    fn test_navigate_within_scope() {
+        let mock = MockSession::new(DomTree::new(AriaNode::fragment()));
+        let scope = OriginScope::new(["https://example.com"]).unwrap();
+        let session = mock.session_with(LaunchOptions::new().scope(scope));
+        let mut context = ToolContext::new(&session);
+
+        let result = NavigateTool
+            .execute_typed(navigate("example.com/docs"), &mut context)
+            .unwrap();
+        assert_eq!(result.data.unwrap()["load"], "complete");
+        let error = NavigateTool
+            .execute_typed(navigate("https://evil.example"), &mut context)
+            .unwrap_err();
+        assert_eq!(error.code(), ErrorCode::PolicyDenied);
+
+        assert_eq!(
+            mock.actions(),
+            vec![MockAction::Navigate("https://example.com/docs".to_string())]
+        );
     }
 }
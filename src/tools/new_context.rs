@@ -0,0 +1,81 @@
+use crate::error::Result;
+use crate::tools::snapshot::{RenderMode, render_aria_tree};
+use crate::tools::utils::normalize_url;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the new_context tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NewContextParams {
+    /// URL to open in the new context's tab (default: a blank page)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Tool for opening a tab in a fresh incognito browser context
+///
+/// The tab starts without cookies, storage or cache and becomes the active
+/// tab. If loading the URL fails, the context is closed again and the error
+/// is returned.
+#[derive(Default)]
+pub struct NewContextTool;
+
+impl Tool for NewContextTool {
+    type Params = NewContextParams;
+
+    fn name(&self) -> &str {
+        "new_context"
+    }
+
+    fn description(&self) -> &str {
+        "Open a tab in a fresh incognito browser context with no cookies, storage or cache, optionally loading a URL, and make it the active tab; returns the context id and tab id. Use for clean-slate sessions that must not share logins"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        params: NewContextParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let session = context.session;
+        let context_id = session.new_incognito_context()?;
+        if let Some(url) = params.url {
+            // Scope, confirmation, robots and rate-limit checks run with the navigation
+            let loaded = session
+                .navigate(&normalize_url(&url))
+                .and_then(|_| session.wait_for_navigation());
+            if let Err(e) = loaded {
+                if let Err(close_error) = session.close_incognito_context(&context_id) {
+                    log::warn!(
+                        "Failed to close browser context {}: {}",
+                        context_id,
+                        close_error
+                    );
+                }
+                return Err(e);
+            }
+        }
+        context.invalidate_dom();
+
+        let tab = session.tab()?;
+        let title = tab.get_title().unwrap_or_default();
+        let url = tab.get_url();
+        let snapshot = {
+            let dom = context.get_dom()?;
+            render_aria_tree(&dom.root, RenderMode::Ai, None)
+        };
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "context_id": context_id,
+            "id": tab.get_target_id(),
+            "title": title,
+            "url": url,
+            "message": format!("Opened tab {} in new browser context {}", url, context_id),
+            "snapshot": snapshot
+        })))
+    }
+}
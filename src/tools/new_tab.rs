@@ -1,12 +1,12 @@
 use crate::error::Result;
 use crate::tools::snapshot::{RenderMode, render_aria_tree};
 use crate::tools::utils::normalize_url;
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the new_tab tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct NewTabParams {
     /// URL to open in the new tab
     pub url: String,
@@ -23,32 +23,21 @@ impl Tool for NewTabTool {
         "new_tab"
     }
 
+    fn description(&self) -> &str {
+        "Open a new tab, navigate it to the specified URL and make it the active tab; returns the tab's id"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
     fn execute_typed(&self, params: NewTabParams, context: &mut ToolContext) -> Result<ToolResult> {
         let normalized_url = normalize_url(&params.url);
-        let tab = context.session.browser().new_tab().map_err(|e| {
-            crate::error::BrowserError::TabOperationFailed(format!("Failed to create tab: {}", e))
-        })?;
-
-        // Navigate to the normalized URL
-        tab.navigate_to(&normalized_url).map_err(|e| {
-            crate::error::BrowserError::NavigationFailed(format!(
-                "Failed to navigate to {}: {}",
-                normalized_url, e
-            ))
-        })?;
-
-        // Wait for navigation to complete
-        tab.wait_until_navigated().map_err(|e| {
-            crate::error::BrowserError::NavigationFailed(format!(
-                "Navigation to {} did not complete: {}",
-                normalized_url, e
-            ))
-        })?;
-
-        // Bring the new tab to front
-        tab.activate().map_err(|e| {
-            crate::error::BrowserError::TabOperationFailed(format!("Failed to activate tab: {}", e))
-        })?;
+        // Scope, confirmation, robots and rate-limit checks run with the navigation
+        let tab = context.session.open_tab(&normalized_url)?;
+        context.invalidate_dom();
+        let title = tab.get_title().unwrap_or_default();
+        let url = tab.get_url();
 
         let snapshot = {
             let dom = context.get_dom()?;
@@ -56,6 +45,10 @@ impl Tool for NewTabTool {
         };
 
         Ok(ToolResult::success_with(serde_json::json!({
+            "id": tab.get_target_id(),
+            "title": title,
+            "url": url,
+            "message": format!("Opened tab {} ({})", title, url),
             "snapshot": snapshot
         })))
     }
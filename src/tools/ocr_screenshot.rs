@@ -0,0 +1,86 @@
+use crate::error::Result;
+use crate::ocr::{OcrOptions, recognize};
+use crate::tools::utils::capture_png;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct OcrScreenshotParams {
+    /// CSS selector of the element to read (use either this or index, omit both for the viewport)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree (use either this or selector)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Read the full page instead of the viewport (default: false)
+    #[serde(default)]
+    pub full_page: bool,
+
+    /// Tesseract language code(s), e.g. "eng" or "eng+deu" (default: "eng")
+    #[serde(default = "default_language")]
+    pub language: String,
+
+    /// Drop words below this confidence, 0 - 100 (default: 0)
+    #[serde(default)]
+    pub min_confidence: f32,
+
+    /// Include per-word positions and confidences (default: false)
+    #[serde(default)]
+    pub include_words: bool,
+}
+
+fn default_language() -> String {
+    "eng".to_string()
+}
+
+#[derive(Default)]
+pub struct OcrScreenshotTool;
+
+impl Tool for OcrScreenshotTool {
+    type Params = OcrScreenshotParams;
+
+    fn name(&self) -> &str {
+        "ocr_screenshot"
+    }
+
+    fn description(&self) -> &str {
+        "Read text from a screenshot of the page or an element with OCR (for canvas-rendered or image-based content)"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: OcrScreenshotParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let png = capture_png(
+            "ocr_screenshot",
+            context,
+            params.selector.as_deref(),
+            params.index,
+            params.full_page,
+        )?;
+
+        let options = OcrOptions::new()
+            .language(params.language)
+            .min_confidence(params.min_confidence);
+        let result = recognize(&png, &options)?;
+
+        let mut data = serde_json::json!({
+            "text": result.text,
+            "word_count": result.words.len(),
+            "mean_confidence": result.mean_confidence(),
+        });
+        if params.include_words {
+            data["words"] = serde_json::to_value(&result.words)?;
+        }
+
+        Ok(ToolResult::success_with(data))
+    }
+}
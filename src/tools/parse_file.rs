@@ -0,0 +1,99 @@
+use crate::error::Result;
+use crate::files::{FileFormat, ParseOptions, parse_path};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the parse_file tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParseFileParams {
+    /// Path of the file in the output directory, as returned by download_url
+    pub path: String,
+
+    /// File format (default: detected from the extension)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<FileFormat>,
+
+    /// Worksheet of a spreadsheet to read (default: the first)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sheet: Option<String>,
+
+    /// Use the first row of CSV and spreadsheet files as column names (default: true)
+    #[serde(default = "default_header")]
+    pub header: bool,
+
+    /// Maximum number of rows to return; totals cover every row (default: 1000)
+    #[serde(default = "default_max_rows")]
+    pub max_rows: usize,
+}
+
+fn default_header() -> bool {
+    true
+}
+
+fn default_max_rows() -> usize {
+    1000
+}
+
+/// Tool for reading a downloaded CSV, JSON or spreadsheet file as rows
+#[derive(Default)]
+pub struct ParseFileTool;
+
+impl Tool for ParseFileTool {
+    type Params = ParseFileParams;
+
+    fn name(&self) -> &str {
+        "parse_file"
+    }
+
+    fn description(&self) -> &str {
+        "Parse a downloaded CSV, TSV, JSON, JSON Lines or spreadsheet file into rows, with totals of its numeric columns"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: ParseFileParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let path = context.session.input_file(&params.path)?;
+        let mut options = ParseOptions::new()
+            .header(params.header)
+            .max_rows(params.max_rows);
+        options.sheet = params.sheet;
+
+        let parsed = parse_path(&path, params.format, &options)?;
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "path": path.to_string_lossy(),
+            "format": parsed.format,
+            "columns": parsed.columns,
+            "row_count": parsed.total_rows,
+            "truncated": parsed.rows.len() < parsed.total_rows,
+            "rows": parsed.rows,
+            "totals": parsed.totals,
+            "sheets": parsed.sheets,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_params() {
+        let params: ParseFileParams = serde_json::from_value(serde_json::json!({
+            "path": "report.txt",
+            "format": "json_lines"
+        }))
+        .unwrap();
+        assert_eq!(params.format, Some(FileFormat::JsonLines));
+        assert!(params.header);
+        assert_eq!(params.max_rows, 1000);
+        assert!(params.sheet.is_none());
+    }
+}
@@ -0,0 +1,178 @@
+//! Tool definitions loaded from a plugins directory
+//!
+//! Each `*.json` file in the directory is one plugin:
+//!
+//! ```json
+//! {
+//!   "namespace": "shop",
+//!   "tools": [
+//!     {
+//!       "name": "cart_total",
+//!       "description": "Read the cart total",
+//!       "script": "return document.querySelector('.total').textContent;"
+//!     },
+//!     {
+//!       "name": "search",
+//!       "description": "Search the shop",
+//!       "parameters": { "properties": { "query": { "type": "string" } }, "required": ["query"] },
+//!       "steps": [
+//!         { "tool": "navigate", "params": { "url": "https://shop.example/search?q={{query}}" } },
+//!         { "tool": "get_markdown" }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Tools with a `script` are [`CustomTool`]s, tools with `steps` are
+//! [`WorkflowTool`]s. Tool names are prefixed with the namespace (the file
+//! name without extension when none is given), so the tools above are
+//! `shop_cart_total` and `shop_search`.
+
+use crate::error::{BrowserError, Result};
+use crate::tools::custom_tool::check_tool_name;
+use crate::tools::workflow::{WorkflowTool, WorkflowToolDefinition};
+use crate::tools::{CustomTool, CustomToolDefinition, DynTool};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Contents of a plugin file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginFile {
+    /// Prefix for the plugin's tool names (default: the file name)
+    #[serde(default)]
+    namespace: Option<String>,
+
+    /// Tool definitions
+    tools: Vec<PluginToolDefinition>,
+}
+
+/// A tool defined by a plugin
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PluginToolDefinition {
+    Script(CustomToolDefinition),
+    Workflow(WorkflowToolDefinition),
+}
+
+/// Parse one plugin file's contents into tools named `<namespace>_<name>`
+fn parse_plugin(source: &str, default_namespace: &str) -> Result<Vec<Arc<dyn DynTool>>> {
+    let plugin: PluginFile = serde_json::from_str(source)
+        .map_err(|e| BrowserError::InvalidArgument(format!("Invalid plugin file: {}", e)))?;
+    let namespace = plugin
+        .namespace
+        .unwrap_or_else(|| default_namespace.to_string());
+    check_tool_name(&namespace)?;
+
+    plugin
+        .tools
+        .into_iter()
+        .map(|definition| {
+            let tool: Arc<dyn DynTool> = match definition {
+                PluginToolDefinition::Script(definition) => {
+                    let name = format!("{}_{}", namespace, definition.name);
+                    Arc::new(CustomTool::new(definition)?.renamed(name)?)
+                }
+                PluginToolDefinition::Workflow(definition) => {
+                    let name = format!("{}_{}", namespace, definition.name);
+                    Arc::new(WorkflowTool::new(definition)?.renamed(name)?)
+                }
+            };
+            Ok(tool)
+        })
+        .collect()
+}
+
+/// Load the tools of every `*.json` plugin file in `dir`, in file name order
+///
+/// Fails on the first invalid file or when two tools end up with the same
+/// name, so broken plugins are noticed at startup rather than on first use.
+pub fn load_plugins(dir: impl AsRef<Path>) -> Result<Vec<Arc<dyn DynTool>>> {
+    let dir = dir.as_ref();
+    let mut paths: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut tools = Vec::new();
+    let mut names = HashSet::new();
+    for path in paths {
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().replace(['-', '.', ' '], "_"))
+            .unwrap_or_default();
+        let source = std::fs::read_to_string(&path)?;
+        let plugin_tools = parse_plugin(&source, &stem).map_err(|e| {
+            BrowserError::InvalidArgument(format!("Plugin {}: {}", path.display(), e))
+        })?;
+        for tool in plugin_tools {
+            if !names.insert(tool.name().to_string()) {
+                return Err(BrowserError::InvalidArgument(format!(
+                    "Plugin {}: tool '{}' is already defined by another plugin",
+                    path.display(),
+                    tool.name()
+                )));
+            }
+            tools.push(tool);
+        }
+    }
+    Ok(tools)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plugin() {
+        let source = r#"{
+            "tools": [
+                { "name": "cart_total", "description": "Cart total", "script": "return 1;" },
+                {
+                    "name": "search",
+                    "description": "Search",
+                    "parameters": { "properties": { "query": { "type": "string" } } },
+                    "steps": [{ "tool": "navigate", "params": { "url": "https://shop.example/?q={{query}}" } }]
+                }
+            ]
+        }"#;
+
+        let tools = parse_plugin(source, "shop").unwrap();
+        let names: Vec<&str> = tools.iter().map(|tool| tool.name()).collect();
+        assert_eq!(names, vec!["shop_cart_total", "shop_search"]);
+        assert_eq!(tools[1].description(), "Search");
+    }
+
+    #[test]
+    fn test_parse_plugin_errors() {
+        assert!(parse_plugin(r#"{ "tools": [{ "name": "x" }] }"#, "shop").is_err());
+        assert!(parse_plugin(r#"{ "tools": [], "extra": 1 }"#, "shop").is_err());
+        assert!(parse_plugin(r#"{ "namespace": "9lives", "tools": [] }"#, "shop").is_err());
+    }
+
+    #[test]
+    fn test_load_plugins() {
+        let dir = std::env::temp_dir().join(format!("browser-use-plugins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let tool = r#"{ "tools": [{ "name": "title", "description": "Title", "script": "return document.title;" }] }"#;
+        std::fs::write(dir.join("my-site.json"), tool).unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a plugin").unwrap();
+
+        let tools = load_plugins(&dir).unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "my_site_title");
+
+        std::fs::write(
+            dir.join("other.json"),
+            r#"{ "namespace": "my_site", "tools": [{ "name": "title", "description": "Title", "script": "return 1;" }] }"#,
+        )
+        .unwrap();
+        assert!(load_plugins(&dir).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,10 +1,11 @@
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::utils::confirm_focused;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the press_key tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PressKeyParams {
     /// Name of the key to press (e.g., "Enter", "Tab", "Escape", "ArrowDown", "F1", etc.)
     pub key: String,
@@ -21,17 +22,27 @@ impl Tool for PressKeyTool {
         "press_key"
     }
 
+    fn description(&self) -> &str {
+        "Press a key on the keyboard"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default()
+    }
+
     fn execute_typed(
         &self,
         params: PressKeyParams,
         context: &mut ToolContext,
     ) -> Result<ToolResult> {
+        confirm_focused("press_key", context)?;
         context.session.tab()?.press_key(&params.key).map_err(|e| {
             BrowserError::ToolExecutionFailed {
                 tool: "press_key".to_string(),
                 reason: e.to_string(),
             }
         })?;
+        context.session.record_page_action();
 
         Ok(ToolResult::success_with(serde_json::json!({
             "key": params.key
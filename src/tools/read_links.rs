@@ -1,17 +1,26 @@
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use crate::url::{UrlSet, resolve};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct ReadLinksParams {}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReadLinksParams {
+    /// Drop links whose canonical URL was already listed (default: false)
+    #[serde(default)]
+    pub unique: bool,
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Link {
     /// The visible text content of the link
     pub text: String,
     /// The href attribute of the link
     pub href: String,
+    /// Absolute URL resolved against the page URL (empty for non-navigable links)
+    #[serde(default)]
+    pub url: String,
 }
 
 #[derive(Default)]
@@ -24,9 +33,17 @@ impl Tool for ReadLinksTool {
         "read_links"
     }
 
+    fn description(&self) -> &str {
+        "List the links on the page with their text, href and absolute URL, optionally without duplicates"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
     fn execute_typed(
         &self,
-        _params: ReadLinksParams,
+        params: ReadLinksParams,
         context: &mut ToolContext,
     ) -> Result<ToolResult> {
         // JavaScript code to extract all links on the page
@@ -42,19 +59,28 @@ impl Tool for ReadLinksTool {
             )
         "#;
 
-        let result = context
-            .session
-            .tab()?
+        let tab = context.session.tab()?;
+        let page_url = tab.get_url();
+        let result = tab
             .evaluate(js_code, false)
             .map_err(|e| BrowserError::EvaluationFailed(e.to_string()))?;
 
         // Parse the JSON string result into Link structs
-        let links: Vec<Link> = result
+        let mut links: Vec<Link> = result
             .value
             .and_then(|v| v.as_str().map(String::from))
             .and_then(|s| serde_json::from_str(&s).ok())
             .unwrap_or_default();
 
+        for link in &mut links {
+            link.url = resolve(&page_url, &link.href).unwrap_or_default();
+        }
+
+        if params.unique {
+            let mut seen = UrlSet::new();
+            links.retain(|link| link.url.is_empty() || seen.insert(&link.url));
+        }
+
         Ok(ToolResult::success_with(serde_json::json!({
             "links": links,
             "count": links.len()
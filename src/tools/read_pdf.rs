@@ -0,0 +1,109 @@
+use crate::error::{BrowserError, Result};
+use crate::pdf::{PdfDocument, is_pdf};
+use crate::tools::download_url::{DEFAULT_MAX_BYTES, fetch_in_page, resolve_url};
+use crate::tools::utils::is_pdf_page;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Parameters for the read_pdf tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ReadPdfParams {
+    /// URL of the PDF, absolute or relative to the current page (default: the current page, if it shows a PDF)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Page numbers to read, starting at 1 (default: all pages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pages: Option<Vec<u32>>,
+
+    /// Refuse files larger than this many bytes (default: 25 MB)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+}
+
+/// Tool for reading the text and outline of a PDF
+///
+/// The file is fetched from inside the page with the session's cookies, like
+/// `download_url`, but parsed in memory instead of saved.
+#[derive(Default)]
+pub struct ReadPdfTool;
+
+impl Tool for ReadPdfTool {
+    type Params = ReadPdfParams;
+
+    fn name(&self) -> &str {
+        "read_pdf"
+    }
+
+    fn description(&self) -> &str {
+        "Read the text and outline of a PDF, either the one the current page shows or one at a URL"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
+    fn execute_typed(
+        &self,
+        params: ReadPdfParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let tab = context.session.tab()?;
+        let page_url = tab.get_url();
+        let url = match &params.url {
+            Some(url) => resolve_url(&page_url, url)?,
+            None if is_pdf_page(&tab) => Url::parse(&page_url).map_err(|e| {
+                BrowserError::InvalidArgument(format!("Invalid page URL '{}': {}", page_url, e))
+            })?,
+            None => {
+                return Err(BrowserError::InvalidArgument(
+                    "The current page is not a PDF; pass the url of one".to_string(),
+                ));
+            }
+        };
+
+        context.session.check_scope(url.as_str())?;
+        context.session.check_robots(url.as_str())?;
+        let _permit = context.session.throttle(url.as_str());
+
+        let fetched = fetch_in_page(&tab, &url, params.max_bytes.unwrap_or(DEFAULT_MAX_BYTES))?;
+        if !is_pdf(&fetched.bytes) {
+            return Err(BrowserError::DocumentFailed(format!(
+                "{} is not a PDF (content type: {})",
+                fetched.final_url,
+                fetched.content_type.as_deref().unwrap_or("unknown")
+            )));
+        }
+        let pdf = PdfDocument::parse_pages(&fetched.bytes, params.pages.as_deref())?;
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "url": fetched.final_url.as_str(),
+            "title": pdf.title,
+            "page_count": pdf.page_count,
+            "pages": pdf.pages,
+            "outline": pdf.outline,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_pdf_params() {
+        let params: ReadPdfParams = serde_json::from_value(serde_json::json!({
+            "url": "/report.pdf",
+            "pages": [1, 3]
+        }))
+        .unwrap();
+        assert_eq!(params.url.as_deref(), Some("/report.pdf"));
+        assert_eq!(params.pages, Some(vec![1, 3]));
+        assert_eq!(params.max_bytes, None);
+
+        let params: ReadPdfParams = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(params.url.is_none());
+    }
+}
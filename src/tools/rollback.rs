@@ -0,0 +1,48 @@
+use crate::error::Result;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the rollback tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RollbackParams {
+    /// Id returned by the checkpoint tool
+    pub checkpoint_id: String,
+}
+
+/// Tool for returning to a page state recorded by the checkpoint tool
+///
+/// Cookies set since the checkpoint are discarded.
+#[derive(Default)]
+pub struct RollbackTool;
+
+impl Tool for RollbackTool {
+    type Params = RollbackParams;
+
+    fn name(&self) -> &str {
+        "rollback"
+    }
+
+    fn description(&self) -> &str {
+        "Return to a state recorded by browser_checkpoint: restore its cookies and storage, reload its URL and scroll back"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default().idempotent()
+    }
+
+    fn execute_typed(
+        &self,
+        params: RollbackParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let checkpoint = context.session.rollback(&params.checkpoint_id)?;
+        context.invalidate_dom();
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "checkpoint_id": checkpoint.id,
+            "url": checkpoint.url,
+            "message": format!("Rolled back to checkpoint {} at {}", checkpoint.id, checkpoint.url)
+        })))
+    }
+}
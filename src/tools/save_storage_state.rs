@@ -0,0 +1,57 @@
+use crate::error::Result;
+use crate::tools::utils::state_encryption_key;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the save_storage_state tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SaveStorageStateParams {
+    /// Path of the storageState.json file to write (encrypted when BROWSER_USE_STATE_KEY is set)
+    pub path: String,
+}
+
+/// Tool for saving cookies and localStorage to a storageState.json file
+#[derive(Default)]
+pub struct SaveStorageStateTool;
+
+impl Tool for SaveStorageStateTool {
+    type Params = SaveStorageStateParams;
+
+    fn name(&self) -> &str {
+        "save_storage_state"
+    }
+
+    fn description(&self) -> &str {
+        "Save cookies and localStorage to a Playwright-compatible storageState.json file"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::default().idempotent()
+    }
+
+    fn execute_typed(
+        &self,
+        params: SaveStorageStateParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let path = context.session.output_file(&params.path, true)?;
+        let key = state_encryption_key()?;
+
+        #[cfg(feature = "encryption")]
+        let state = match &key {
+            Some(key) => context.session.save_storage_state_encrypted(&path, key)?,
+            None => context.session.save_storage_state(&path)?,
+        };
+
+        #[cfg(not(feature = "encryption"))]
+        let state = context.session.save_storage_state(&path)?;
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "path": path.to_string_lossy(),
+            "cookies": state.cookies.len(),
+            "origins": state.origins.len(),
+            "encrypted": key.is_some()
+        })))
+    }
+}
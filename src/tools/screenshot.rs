@@ -1,16 +1,32 @@
+use crate::browser::screenshot::{ImageFormat, ScreenshotOptions};
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ScreenshotParams {
-    /// Path to save the screenshot
-    pub path: String,
+    /// Path to save the screenshot (omit to return the image inline as base64)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
 
     /// Capture full page (default: false)
     #[serde(default)]
     pub full_page: bool,
+
+    /// Image format: png, jpeg or webp (default: png)
+    #[serde(default)]
+    pub format: ImageFormat,
+
+    /// Compression quality 0-100 for jpeg and webp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u32>,
+
+    /// Replace the file if it already exists (default: false)
+    #[serde(default)]
+    pub overwrite: bool,
 }
 
 #[derive(Default)]
@@ -23,28 +39,48 @@ impl Tool for ScreenshotTool {
         "screenshot"
     }
 
+    fn description(&self) -> &str {
+        "Capture a screenshot of the current page, saved to a file or returned inline as base64 when no path is given"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
     fn execute_typed(
         &self,
         params: ScreenshotParams,
         context: &mut ToolContext,
     ) -> Result<ToolResult> {
-        let screenshot_data = context
-            .session
-            .tab()?
-            .capture_screenshot(
-                headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption::Png,
-                None,
-                None,
-                params.full_page,
-            )
-            .map_err(|e| BrowserError::ScreenshotFailed(e.to_string()))?;
-
-        std::fs::write(&params.path, &screenshot_data).map_err(|e| {
+        let path = params
+            .path
+            .as_deref()
+            .map(|path| context.session.output_file(path, params.overwrite))
+            .transpose()?;
+
+        let mut options = ScreenshotOptions::new()
+            .format(params.format)
+            .full_page(params.full_page);
+        if let Some(quality) = params.quality {
+            options = options.quality(quality);
+        }
+        let screenshot_data = context.session.screenshot_bytes(&options)?;
+
+        let Some(path) = path else {
+            return Ok(ToolResult::success_with(serde_json::json!({
+                "mime_type": params.format.mime_type(),
+                "size_bytes": screenshot_data.len(),
+                "full_page": params.full_page,
+                "data": STANDARD.encode(&screenshot_data)
+            })));
+        };
+
+        std::fs::write(&path, &screenshot_data).map_err(|e| {
             BrowserError::ScreenshotFailed(format!("Failed to save screenshot: {}", e))
         })?;
 
         Ok(ToolResult::success_with(serde_json::json!({
-            "path": params.path,
+            "path": path.to_string_lossy(),
             "size_bytes": screenshot_data.len(),
             "full_page": params.full_page
         })))
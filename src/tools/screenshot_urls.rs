@@ -0,0 +1,83 @@
+use crate::browser::screenshot_job::ScreenshotJobOptions;
+use crate::error::Result;
+use crate::tools::utils::normalize_url;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Most tabs a single screenshot_urls call opens
+const MAX_CONCURRENCY: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScreenshotUrlsParams {
+    /// URLs to capture
+    pub urls: Vec<String>,
+
+    /// Directory to save the screenshots in
+    pub output_dir: String,
+
+    /// Capture full pages (default: false)
+    #[serde(default)]
+    pub full_page: bool,
+
+    /// Number of pages to capture in parallel (default: 4, at most 16)
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+
+    /// Delay after page load before capturing in milliseconds (default: 0)
+    #[serde(default)]
+    pub settle_ms: u64,
+
+    /// Replace screenshots that already exist (default: false)
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+#[derive(Default)]
+pub struct ScreenshotUrlsTool;
+
+impl Tool for ScreenshotUrlsTool {
+    type Params = ScreenshotUrlsParams;
+
+    fn name(&self) -> &str {
+        "screenshot_urls"
+    }
+
+    fn description(&self) -> &str {
+        "Visit a list of URLs and save a screenshot of each, reporting per-URL status and timing"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        params: ScreenshotUrlsParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let options = ScreenshotJobOptions::new()
+            .output_dir(context.session.output_subdir(&params.output_dir)?)
+            .full_page(params.full_page)
+            .concurrency(params.concurrency.min(MAX_CONCURRENCY))
+            .settle(params.settle_ms)
+            .overwrite(params.overwrite);
+
+        let started = Instant::now();
+        let urls: Vec<String> = params.urls.iter().map(|url| normalize_url(url)).collect();
+        let results = context.session.screenshot_urls(&urls, &options)?;
+        let failed = results.iter().filter(|r| r.error.is_some()).count();
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "results": results,
+            "succeeded": results.len() - failed,
+            "failed": failed,
+            "duration_ms": started.elapsed().as_millis() as u64
+        })))
+    }
+}
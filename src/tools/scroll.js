@@ -1,23 +1,102 @@
 (async function () {
   const config = __SCROLL_CONFIG__;
-  const beforeScrollY = window.scrollY;
+
+  const isScrollable = (el) => {
+    if (!el || el === document.body || el === document.documentElement) {
+      return false;
+    }
+    const overflowY = window.getComputedStyle(el).overflowY;
+    return (
+      (overflowY === "auto" ||
+        overflowY === "scroll" ||
+        overflowY === "overlay") &&
+      el.scrollHeight > el.clientHeight + 1
+    );
+  };
+
+  const describe = (el) => {
+    let desc = el.tagName.toLowerCase();
+    if (el.id) desc += "#" + el.id;
+    if (typeof el.className === "string" && el.className.trim()) {
+      desc += "." + el.className.trim().split(/\s+/).slice(0, 2).join(".");
+    }
+    return desc;
+  };
+
+  const windowCanScroll = () => {
+    const scrollHeight = Math.max(
+      document.documentElement.scrollHeight,
+      document.body.scrollHeight
+    );
+    return scrollHeight > window.innerHeight + 1;
+  };
+
+  // Largest visible scroll pane, for pages whose window never scrolls
+  const largestScrollable = () => {
+    let best = null;
+    let bestArea = 0;
+    for (const el of document.querySelectorAll("*")) {
+      if (!isScrollable(el)) continue;
+      const rect = el.getBoundingClientRect();
+      const area = rect.width * rect.height;
+      if (area > bestArea) {
+        best = el;
+        bestArea = area;
+      }
+    }
+    return best;
+  };
+
+  let container = null;
+  if (config.selector) {
+    const target = document.querySelector(config.selector);
+    if (!target) {
+      return JSON.stringify({ error: "Element not found: " + config.selector });
+    }
+    // The element itself or its nearest scrollable ancestor
+    let el = target;
+    while (el && !isScrollable(el)) {
+      el = el.parentElement;
+    }
+    container = el;
+  } else if (!windowCanScroll()) {
+    container = largestScrollable();
+  }
+
+  const viewport = container ? container.clientHeight : window.innerHeight;
   const scrollAmount =
-    typeof config.amount === "number" ? config.amount : window.innerHeight;
+    typeof config.amount === "number" ? config.amount : viewport;
+  const position = () => (container ? container.scrollTop : window.scrollY);
+  const before = position();
 
-  window.scrollBy(0, scrollAmount);
+  if (container) {
+    container.scrollBy(0, scrollAmount);
+  } else {
+    window.scrollBy(0, scrollAmount);
+  }
 
   await new Promise((resolve) => setTimeout(resolve, 100));
 
-  const actualScroll = window.scrollY - beforeScrollY;
-  const scrollHeight = Math.max(
-    document.documentElement.scrollHeight,
-    document.body.scrollHeight
-  );
-  const scrollTop = window.scrollY;
-  const clientHeight =
-    window.innerHeight || document.documentElement.clientHeight;
-  const isAtBottom =
-    Math.abs(scrollHeight - scrollTop - clientHeight) <= 1;
-
-  return JSON.stringify({ actualScroll, isAtBottom });
+  const actualScroll = Math.round(position() - before);
+  let isAtBottom;
+  if (container) {
+    isAtBottom =
+      Math.abs(
+        container.scrollHeight - container.scrollTop - container.clientHeight
+      ) <= 1;
+  } else {
+    const scrollHeight = Math.max(
+      document.documentElement.scrollHeight,
+      document.body.scrollHeight
+    );
+    const clientHeight =
+      window.innerHeight || document.documentElement.clientHeight;
+    isAtBottom = Math.abs(scrollHeight - window.scrollY - clientHeight) <= 1;
+  }
+
+  return JSON.stringify({
+    actualScroll,
+    isAtBottom,
+    container: container ? describe(container) : null,
+  });
 })()
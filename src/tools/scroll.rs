@@ -1,18 +1,33 @@
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::utils::resolve_optional_selector;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the scroll tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ScrollParams {
     /// Amount to scroll in pixels (positive for down, negative for up).
-    /// If not provided, scrolls to the bottom of the page.
+    /// If not provided, scrolls by one viewport (or container) height.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub amount: Option<i32>,
+
+    /// CSS selector of an element inside the scroll pane to scroll
+    /// (use either this or index, not both)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree of an element inside the scroll pane to scroll
+    /// (use either this or selector, not both)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
 }
 
-/// Tool for scrolling the page
+/// Tool for scrolling the page or a nested scroll container
+///
+/// With a selector or index, the element's nearest scrollable ancestor (or the
+/// element itself) is scrolled. Without one, the window is scrolled, falling
+/// back to the largest scrollable container when the window cannot scroll.
 #[derive(Default)]
 pub struct ScrollTool;
 
@@ -25,9 +40,21 @@ impl Tool for ScrollTool {
         "scroll"
     }
 
+    fn description(&self) -> &str {
+        "Scroll the page, or the scroll container holding a given element, by a specified amount"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
     fn execute_typed(&self, params: ScrollParams, context: &mut ToolContext) -> Result<ToolResult> {
+        let selector =
+            resolve_optional_selector("scroll", context, params.selector.as_deref(), params.index)?;
+
         let config = serde_json::json!({
-            "amount": params.amount
+            "amount": params.amount,
+            "selector": selector
         });
         let scroll_js = SCROLL_JS.replace("__SCROLL_CONFIG__", &config.to_string());
 
@@ -39,6 +66,8 @@ impl Tool for ScrollTool {
                 tool: "scroll".to_string(),
                 reason: e.to_string(),
             })?;
+        // Scrolling changes which elements are visible
+        context.session.record_page_action();
 
         // Parse the JSON string returned by JavaScript
         let result_json: serde_json::Value =
@@ -51,24 +80,34 @@ impl Tool for ScrollTool {
                     .unwrap_or(serde_json::json!({"actualScroll": 0, "isAtBottom": false}))
             };
 
+        if let Some(error) = result_json["error"].as_str() {
+            return Err(BrowserError::ElementNotFound(error.to_string()));
+        }
+
         let actual_scroll = result_json["actualScroll"].as_i64().unwrap_or(0);
         let is_at_bottom = result_json["isAtBottom"].as_bool().unwrap_or(false);
+        let container = result_json["container"].as_str();
 
+        let target = match container {
+            Some(container) => format!("container {}", container),
+            None => "the page".to_string(),
+        };
         let message = if is_at_bottom {
             format!(
-                "Scrolled {} pixels. Reached the bottom of the page.",
-                actual_scroll
+                "Scrolled {} pixels. Reached the bottom of {}.",
+                actual_scroll, target
             )
         } else {
             format!(
-                "Scrolled {} pixels. Did not reach the bottom of the page.",
-                actual_scroll
+                "Scrolled {} pixels. Did not reach the bottom of {}.",
+                actual_scroll, target
             )
         };
 
         Ok(ToolResult::success_with(serde_json::json!({
             "scrolled": actual_scroll,
             "isAtBottom": is_at_bottom,
+            "container": container,
             "message": message
         })))
     }
@@ -104,5 +143,20 @@ mod tests {
 
         let params: ScrollParams = serde_json::from_value(json).unwrap();
         assert_eq!(params.amount, None);
+        assert_eq!(params.selector, None);
+        assert_eq!(params.index, None);
+    }
+
+    #[test]
+    fn test_scroll_params_with_container() {
+        let json = serde_json::json!({
+            "amount": 200,
+            "selector": "#results"
+        });
+
+        let params: ScrollParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.amount, Some(200));
+        assert_eq!(params.selector.as_deref(), Some("#results"));
+        assert_eq!(params.index, None);
     }
 }
@@ -0,0 +1,79 @@
+(async function () {
+  const config = __SCROLL_TO_LOAD_CONFIG__;
+
+  const isScrollable = (el) => {
+    if (!el || el === document.body || el === document.documentElement) {
+      return false;
+    }
+    const overflowY = window.getComputedStyle(el).overflowY;
+    return (
+      (overflowY === "auto" ||
+        overflowY === "scroll" ||
+        overflowY === "overlay") &&
+      el.scrollHeight > el.clientHeight + 1
+    );
+  };
+
+  let container = null;
+  if (config.selector) {
+    const target = document.querySelector(config.selector);
+    if (!target) {
+      return JSON.stringify({ error: "Element not found: " + config.selector });
+    }
+    let el = target;
+    while (el && !isScrollable(el)) {
+      el = el.parentElement;
+    }
+    container = el;
+  }
+
+  const root = container || document.body;
+  const height = () =>
+    container
+      ? container.scrollHeight
+      : Math.max(
+          document.documentElement.scrollHeight,
+          document.body.scrollHeight
+        );
+  const countItems = () =>
+    root.querySelectorAll(
+      config.itemSelector || "article, li, [role='article'], [role='listitem']"
+    ).length;
+  const resources = () => performance.getEntriesByType("resource").length;
+
+  const beforeHeight = height();
+  const beforeItems = countItems();
+
+  if (container) {
+    container.scrollTop = container.scrollHeight;
+  } else {
+    window.scrollTo(0, height());
+  }
+
+  // Wait until the page grows and the network has been quiet for idleMs,
+  // or give up after waitMs without growth
+  const start = Date.now();
+  let lastResources = resources();
+  let quietSince = Date.now();
+  let grew = false;
+  while (Date.now() - start < config.waitMs) {
+    await new Promise((resolve) => setTimeout(resolve, 100));
+    const current = resources();
+    if (current !== lastResources) {
+      lastResources = current;
+      quietSince = Date.now();
+    }
+    grew = height() > beforeHeight || countItems() > beforeItems;
+    if (grew && Date.now() - quietSince >= config.idleMs) {
+      break;
+    }
+  }
+
+  return JSON.stringify({
+    grew,
+    height: height(),
+    items: countItems(),
+    beforeHeight,
+    beforeItems,
+  });
+})()
@@ -0,0 +1,241 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::utils::resolve_optional_selector;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Default number of scroll rounds
+const DEFAULT_MAX_ITERATIONS: usize = 10;
+
+/// Default time to wait for new content after each scroll, in milliseconds
+const DEFAULT_WAIT_MS: u64 = 2000;
+
+/// Longest wait per scroll, kept below the CDP call timeout
+const MAX_WAIT_MS: u64 = 15_000;
+
+/// How long the network must stay quiet after new content appears, in milliseconds
+const NETWORK_IDLE_MS: u64 = 500;
+
+/// Parameters for the scroll_to_load tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScrollToLoadParams {
+    /// Maximum number of times to scroll to the bottom (default: 10)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<usize>,
+
+    /// Stop once the page (or container) is taller than this many pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_height: Option<u64>,
+
+    /// Time to wait for new content after each scroll, in milliseconds (default: 2000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_ms: Option<u64>,
+
+    /// CSS selector matching one loaded item, used to count new items
+    /// (default: list items and articles)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_selector: Option<String>,
+
+    /// CSS selector of an element inside the scroll pane to load, instead of the window
+    /// (use either this or index, not both)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree of an element inside the scroll pane to load
+    /// (use either this or selector, not both)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+}
+
+/// Why scroll_to_load stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StopReason {
+    /// A scroll produced no new content within the wait time
+    NoNewContent,
+    /// The iteration cap was reached
+    MaxIterations,
+    /// The height cap was reached
+    MaxHeight,
+}
+
+/// Result of one scroll round, as reported by the page
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScrollRound {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    grew: bool,
+    #[serde(default)]
+    height: u64,
+    #[serde(default)]
+    items: usize,
+    #[serde(default)]
+    before_height: u64,
+    #[serde(default)]
+    before_items: usize,
+}
+
+/// Tool for loading infinite-scroll feeds and result lists
+///
+/// Scrolls to the bottom repeatedly, waiting after each scroll for the page to
+/// grow and the network to settle, until nothing new loads or a cap is hit.
+#[derive(Default)]
+pub struct ScrollToLoadTool;
+
+const SCROLL_TO_LOAD_JS: &str = include_str!("scroll_to_load.js");
+
+impl Tool for ScrollToLoadTool {
+    type Params = ScrollToLoadParams;
+
+    fn name(&self) -> &str {
+        "scroll_to_load"
+    }
+
+    fn description(&self) -> &str {
+        "Load an infinite-scroll feed by scrolling to the bottom until no new content appears or a cap is reached, and report how many new items loaded"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive()
+    }
+
+    fn execute_typed(
+        &self,
+        params: ScrollToLoadParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let selector = resolve_optional_selector(
+            "scroll_to_load",
+            context,
+            params.selector.as_deref(),
+            params.index,
+        )?;
+        let max_iterations = params.max_iterations.unwrap_or(DEFAULT_MAX_ITERATIONS);
+        let wait_ms = params.wait_ms.unwrap_or(DEFAULT_WAIT_MS).min(MAX_WAIT_MS);
+
+        let config = serde_json::json!({
+            "selector": selector,
+            "itemSelector": params.item_selector,
+            "waitMs": wait_ms,
+            "idleMs": NETWORK_IDLE_MS,
+        });
+        let scroll_js = SCROLL_TO_LOAD_JS.replace("__SCROLL_TO_LOAD_CONFIG__", &config.to_string());
+
+        let tab = context.session.tab()?;
+        let mut before: Option<(usize, u64)> = None;
+        let mut after = (0, 0);
+        let mut iterations = 0;
+        let mut stop_reason = StopReason::MaxIterations;
+
+        while iterations < max_iterations {
+            let result =
+                tab.evaluate(&scroll_js, true)
+                    .map_err(|e| BrowserError::ToolExecutionFailed {
+                        tool: "scroll_to_load".to_string(),
+                        reason: e.to_string(),
+                    })?;
+
+            let round: ScrollRound = result
+                .value
+                .and_then(|v| v.as_str().map(String::from))
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .ok_or_else(|| BrowserError::ToolExecutionFailed {
+                    tool: "scroll_to_load".to_string(),
+                    reason: "No result returned".to_string(),
+                })?;
+            if let Some(error) = round.error {
+                return Err(BrowserError::ElementNotFound(error));
+            }
+            iterations += 1;
+
+            before.get_or_insert((round.before_items, round.before_height));
+            after = (round.items, round.height);
+
+            if !round.grew {
+                stop_reason = StopReason::NoNewContent;
+                break;
+            }
+            if params.max_height.is_some_and(|max| round.height >= max) {
+                stop_reason = StopReason::MaxHeight;
+                break;
+            }
+        }
+
+        let Some((items_before, height_before)) = before else {
+            return Err(BrowserError::InvalidArgument(
+                "max_iterations must be at least 1".to_string(),
+            ));
+        };
+        let (items_after, height_after) = after;
+        let new_items = items_after.saturating_sub(items_before);
+
+        let message = match stop_reason {
+            StopReason::NoNewContent => format!(
+                "Loaded {} new items in {} scrolls; no more content appeared.",
+                new_items, iterations
+            ),
+            StopReason::MaxIterations => format!(
+                "Loaded {} new items in {} scrolls; stopped at the iteration limit.",
+                new_items, iterations
+            ),
+            StopReason::MaxHeight => format!(
+                "Loaded {} new items in {} scrolls; stopped at the height limit.",
+                new_items, iterations
+            ),
+        };
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "iterations": iterations,
+            "newItems": new_items,
+            "itemsBefore": items_before,
+            "itemsAfter": items_after,
+            "heightBefore": height_before,
+            "heightAfter": height_after,
+            "stopReason": stop_reason,
+            "message": message
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_to_load_params_defaults() {
+        let params: ScrollToLoadParams = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(params.max_iterations, None);
+        assert_eq!(params.max_height, None);
+        assert_eq!(params.item_selector, None);
+    }
+
+    #[test]
+    fn test_scroll_to_load_params() {
+        let json = serde_json::json!({
+            "max_iterations": 5,
+            "max_height": 20000,
+            "wait_ms": 1000,
+            "item_selector": ".result"
+        });
+
+        let params: ScrollToLoadParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.max_iterations, Some(5));
+        assert_eq!(params.max_height, Some(20000));
+        assert_eq!(params.wait_ms, Some(1000));
+        assert_eq!(params.item_selector.as_deref(), Some(".result"));
+    }
+
+    #[test]
+    fn test_stop_reason_serialization() {
+        assert_eq!(
+            serde_json::to_value(StopReason::NoNewContent).unwrap(),
+            "no_new_content"
+        );
+        assert_eq!(
+            serde_json::to_value(StopReason::MaxHeight).unwrap(),
+            "max_height"
+        );
+    }
+}
@@ -1,12 +1,13 @@
+use crate::dom::DeepSelector;
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the select tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SelectParams {
-    /// CSS selector (use either this or index, not both)
+    /// CSS selector, with `>>>` to reach into iframes and shadow roots (use either this or index, not both)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub selector: Option<String>,
 
@@ -22,7 +23,12 @@ pub struct SelectParams {
 #[derive(Default)]
 pub struct SelectTool;
 
-const SELECT_JS: &str = include_str!("select.js");
+/// Option the select helper ended up on
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SelectedOption {
+    selected_text: Option<String>,
+}
 
 impl Tool for SelectTool {
     type Params = SelectParams;
@@ -31,6 +37,14 @@ impl Tool for SelectTool {
         "select"
     }
 
+    fn description(&self) -> &str {
+        "Select an option in a dropdown element by CSS selector or index (index obtained from browser_snapshot tool)"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive().idempotent()
+    }
+
     fn execute_typed(&self, params: SelectParams, context: &mut ToolContext) -> Result<ToolResult> {
         // Validate that exactly one selector method is provided
         match (&params.selector, &params.index) {
@@ -53,58 +67,36 @@ impl Tool for SelectTool {
         let css_selector = if let Some(selector) = params.selector {
             selector
         } else if let Some(index) = params.index {
-            let dom = context.get_dom()?;
-            let selector = dom.get_selector(index).ok_or_else(|| {
-                BrowserError::ElementNotFound(format!("No element with index {}", index))
-            })?;
-            selector.clone()
+            context.selector_for_index(index)?
         } else {
             unreachable!("Validation above ensures one field is Some")
         };
         let value = params.value;
+        context
+            .session
+            .confirm_action("select", Some(&value), None)?;
 
-        let select_config = serde_json::json!({
-            "selector": css_selector,
-            "value": value,
-        });
-        let select_js = SELECT_JS.replace("__SELECT_CONFIG__", &select_config.to_string());
-
-        let result = context
+        let selector = DeepSelector::parse(&css_selector)?;
+        let selected: SelectedOption = context
             .session
-            .tab()?
-            .evaluate(&select_js, false)
-            .map_err(|e| BrowserError::ToolExecutionFailed {
-                tool: "select".to_string(),
-                reason: e.to_string(),
+            .call_helper(
+                "select",
+                serde_json::json!({ "selector": selector.steps(), "value": value }),
+            )
+            .map_err(|e| match e {
+                BrowserError::ElementNotFound(_) => e.with_selector(css_selector.as_str()),
+                e => BrowserError::ToolExecutionFailed {
+                    tool: "select".to_string(),
+                    reason: e.to_string(),
+                },
             })?;
+        context.session.record_page_action();
 
-        // Parse the JSON string returned by JavaScript
-        let result_json: serde_json::Value = if let Some(serde_json::Value::String(json_str)) =
-            result.value
-        {
-            serde_json::from_str(&json_str)
-                .unwrap_or(serde_json::json!({"success": false, "error": "Failed to parse result"}))
-        } else {
-            result
-                .value
-                .unwrap_or(serde_json::json!({"success": false, "error": "No result returned"}))
-        };
-
-        if result_json["success"].as_bool() == Some(true) {
-            Ok(ToolResult::success_with(serde_json::json!({
-                "selector": css_selector,
-                "value": value,
-                "selectedText": result_json["selectedText"]
-            })))
-        } else {
-            Err(BrowserError::ToolExecutionFailed {
-                tool: "select".to_string(),
-                reason: result_json["error"]
-                    .as_str()
-                    .unwrap_or("Unknown error")
-                    .to_string(),
-            })
-        }
+        Ok(ToolResult::success_with(serde_json::json!({
+            "selector": css_selector,
+            "value": value,
+            "selectedText": selected.selected_text
+        })))
     }
 }
 
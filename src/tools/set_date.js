@@ -0,0 +1,189 @@
+(async function () {
+  const config = __DATE_CONFIG__;
+  const { year, month, day, time } = config;
+  const pad = (n) => String(n).padStart(2, "0");
+  const isoDate = year + "-" + pad(month) + "-" + pad(day);
+  const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+
+  const element = document.querySelector(config.selector);
+  if (!element) {
+    return JSON.stringify({ success: false, error: "Element not found" });
+  }
+
+  // Set a value the way typing would, so React/Vue listeners see it
+  const setValue = (el, value) => {
+    const proto =
+      el instanceof HTMLTextAreaElement
+        ? HTMLTextAreaElement.prototype
+        : HTMLInputElement.prototype;
+    const setter = Object.getOwnPropertyDescriptor(proto, "value").set;
+    setter.call(el, value);
+    el.dispatchEvent(new Event("input", { bubbles: true }));
+    el.dispatchEvent(new Event("change", { bubbles: true }));
+  };
+
+  const done = (method) =>
+    JSON.stringify({ success: true, method, value: element.value ?? null });
+
+  // 1. Native date inputs take ISO values directly
+  const nativeValues = {
+    date: isoDate,
+    "datetime-local": isoDate + "T" + (time || "00:00"),
+    month: year + "-" + pad(month),
+  };
+  if (element.tagName === "INPUT" && element.type in nativeValues) {
+    setValue(element, nativeValues[element.type]);
+    if (element.value === nativeValues[element.type]) {
+      return done("native");
+    }
+    return JSON.stringify({
+      success: false,
+      error: "The input rejected " + nativeValues[element.type] + " (check min/max)",
+    });
+  }
+
+  const target = new Date(year, month - 1, day);
+
+  // 2. Widgets with a JS API we can call
+  if (element._flatpickr) {
+    element._flatpickr.setDate(target, true);
+    return done("flatpickr");
+  }
+  const $ = window.jQuery;
+  if ($ && $(element).data("datepicker")) {
+    const picker = $(element).data("datepicker");
+    if (typeof picker.update === "function") {
+      $(element).datepicker("update", target);
+    } else {
+      $(element).datepicker("setDate", target);
+    }
+    $(element).trigger("change");
+    return done("jquery");
+  }
+
+  // 3. Open the calendar and click through it
+  const CALENDARS = [
+    ".ui-datepicker",
+    ".flatpickr-calendar.open",
+    ".react-datepicker",
+    ".datepicker-dropdown",
+    ".pika-single:not(.is-hidden)",
+    ".MuiPickersPopper-root",
+    ".MuiDateCalendar-root",
+    ".mat-datepicker-content",
+    ".vdp-datepicker__calendar",
+    "[role='dialog'] [role='grid']",
+    "[role='grid']",
+  ];
+  const NEXT =
+    ".ui-datepicker-next, .flatpickr-next-month, .react-datepicker__navigation--next, .pika-next, .next, .mat-calendar-next-button, [aria-label*='next' i]";
+  const PREV =
+    ".ui-datepicker-prev, .flatpickr-prev-month, .react-datepicker__navigation--previous, .pika-prev, .prev, .mat-calendar-previous-button, [aria-label*='prev' i]";
+  const OUTSIDE =
+    /other-month|outside|prevMonthDay|nextMonthDay|(^|\s)(old|new)(\s|$)|disabled|is-empty/i;
+
+  const isVisible = (el) => {
+    const rect = el.getBoundingClientRect();
+    return (
+      rect.width > 0 &&
+      rect.height > 0 &&
+      window.getComputedStyle(el).visibility !== "hidden"
+    );
+  };
+  const findCalendar = () => {
+    for (const selector of CALENDARS) {
+      const found = Array.from(document.querySelectorAll(selector)).find(
+        isVisible
+      );
+      if (found) return found;
+    }
+    return null;
+  };
+
+  element.focus();
+  element.click();
+  let calendar = null;
+  for (let i = 0; i < 10 && !calendar; i++) {
+    await sleep(100);
+    calendar = findCalendar();
+  }
+
+  if (calendar) {
+    const root =
+      calendar.closest(".ui-datepicker, .flatpickr-calendar, .react-datepicker, [role='dialog']") ||
+      calendar.parentElement ||
+      calendar;
+    const monthNames = Array.from({ length: 12 }, (_, m) => [
+      new Date(2000, m, 1).toLocaleString("en", { month: "long" }).toLowerCase(),
+      new Date(2000, m, 1).toLocaleString(undefined, { month: "long" }).toLowerCase(),
+    ]);
+
+    // Month/year currently shown, read from the calendar's header text
+    const shownMonth = () => {
+      const text = (root.innerText || "").toLowerCase();
+      const yearMatch = text.match(/\b(19|20)\d{2}\b/);
+      let best = null;
+      monthNames.forEach((names, m) => {
+        for (const name of names) {
+          const at = text.indexOf(name);
+          if (at >= 0 && (!best || at < best.at)) best = { at, month: m + 1 };
+          const short = name.slice(0, 3);
+          const shortAt = text.search(new RegExp("\\b" + short + "\\b"));
+          if (shortAt >= 0 && (!best || shortAt < best.at)) {
+            best = { at: shortAt, month: m + 1 };
+          }
+        }
+      });
+      if (!yearMatch || !best) return null;
+      return { year: Number(yearMatch[0]), month: best.month };
+    };
+
+    for (let step = 0; step < 240; step++) {
+      const shown = shownMonth();
+      if (!shown) break;
+      const diff = (year - shown.year) * 12 + (month - shown.month);
+      if (diff === 0) break;
+      const button = Array.from(root.querySelectorAll(diff > 0 ? NEXT : PREV)).find(
+        isVisible
+      );
+      if (!button) break;
+      button.click();
+      await sleep(80);
+    }
+
+    const shown = shownMonth();
+    if (!shown || (shown.year === year && shown.month === month)) {
+      const dayCells = Array.from(
+        root.querySelectorAll(
+          "td, button, [role='gridcell'], .flatpickr-day, .react-datepicker__day, a"
+        )
+      ).filter(
+        (el) =>
+          isVisible(el) &&
+          (el.innerText || "").trim() === String(day) &&
+          !OUTSIDE.test(typeof el.className === "string" ? el.className : "") &&
+          !(el.parentElement && OUTSIDE.test(el.parentElement.className || "")) &&
+          el.getAttribute("aria-disabled") !== "true"
+      );
+      const cell = dayCells[0];
+      if (cell) {
+        const clickable = cell.querySelector("a, button") || cell;
+        clickable.click();
+        await sleep(100);
+        return done("widget");
+      }
+    }
+  }
+
+  // 4. Last resort: type the ISO date into the field
+  if (element.tagName === "INPUT" || element.tagName === "TEXTAREA") {
+    setValue(element, time ? isoDate + " " + time : isoDate);
+    element.dispatchEvent(new Event("blur", { bubbles: true }));
+    return done("typed");
+  }
+
+  return JSON.stringify({
+    success: false,
+    error: "Could not find a date input or calendar widget",
+  });
+})()
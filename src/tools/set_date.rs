@@ -0,0 +1,225 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::utils::{confirm_element, resolve_optional_selector};
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the set_date tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SetDateParams {
+    /// CSS selector of the date input (use either this or index, not both)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+
+    /// Element index from DOM tree (use either this or selector, not both)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Date to set as YYYY-MM-DD, optionally with a time as YYYY-MM-DDTHH:MM
+    pub date: String,
+}
+
+/// A calendar date with an optional time of day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DateValue {
+    year: u16,
+    month: u8,
+    day: u8,
+    time: Option<(u8, u8)>,
+}
+
+impl DateValue {
+    /// Parse `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM`
+    fn parse(value: &str) -> Result<Self> {
+        let invalid = || {
+            BrowserError::InvalidArgument(format!(
+                "Invalid date '{}': expected YYYY-MM-DD or YYYY-MM-DDTHH:MM",
+                value
+            ))
+        };
+
+        let (date, time) = match value.trim().split_once(['T', ' ']) {
+            Some((date, time)) => (date, Some(time)),
+            None => (value.trim(), None),
+        };
+
+        let mut parts = date.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return Err(invalid());
+        }
+        let year: u16 = year.parse().map_err(|_| invalid())?;
+        let month: u8 = month.parse().map_err(|_| invalid())?;
+        let day: u8 = day.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(invalid());
+        }
+
+        let time = match time {
+            Some(time) => {
+                let (hour, minute) = time.split_once(':').ok_or_else(invalid)?;
+                let hour: u8 = hour.parse().map_err(|_| invalid())?;
+                let minute: u8 = minute.parse().map_err(|_| invalid())?;
+                if hour > 23 || minute > 59 {
+                    return Err(invalid());
+                }
+                Some((hour, minute))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            year,
+            month,
+            day,
+            time,
+        })
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Tool for setting dates on native date inputs and JS datepicker widgets
+///
+/// Native `date`, `datetime-local` and `month` inputs get their value set
+/// directly. Flatpickr and jQuery datepickers are driven through their APIs;
+/// other widgets are opened, stepped to the right month and the day clicked.
+#[derive(Default)]
+pub struct SetDateTool;
+
+const SET_DATE_JS: &str = include_str!("set_date.js");
+
+impl Tool for SetDateTool {
+    type Params = SetDateParams;
+
+    fn name(&self) -> &str {
+        "set_date"
+    }
+
+    fn description(&self) -> &str {
+        "Set a date on a native date input or a JS datepicker widget (opens it, moves to the month and clicks the day)"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive().idempotent()
+    }
+
+    fn execute_typed(
+        &self,
+        params: SetDateParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let css_selector = resolve_optional_selector(
+            "set_date",
+            context,
+            params.selector.as_deref(),
+            params.index,
+        )?
+        .ok_or_else(|| BrowserError::ToolExecutionFailed {
+            tool: "set_date".to_string(),
+            reason: "Must specify either 'selector' or 'index'.".to_string(),
+        })?;
+        let date = DateValue::parse(&params.date)?;
+
+        let tab = context.session.tab()?;
+        let element = context.session.find_element(&tab, &css_selector)?;
+        confirm_element("set_date", context, &element)?;
+
+        let date_config = serde_json::json!({
+            "selector": css_selector,
+            "year": date.year,
+            "month": date.month,
+            "day": date.day,
+            "time": date.time.map(|(hour, minute)| format!("{:02}:{:02}", hour, minute)),
+        });
+        let date_js = SET_DATE_JS.replace("__DATE_CONFIG__", &date_config.to_string());
+
+        let result =
+            tab.evaluate(&date_js, true)
+                .map_err(|e| BrowserError::ToolExecutionFailed {
+                    tool: "set_date".to_string(),
+                    reason: e.to_string(),
+                })?;
+        context.session.record_page_action();
+
+        let result_json: serde_json::Value = result
+            .value
+            .and_then(|v| v.as_str().map(String::from))
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or(serde_json::json!({"success": false, "error": "No result returned"}));
+
+        if result_json["success"].as_bool() == Some(true) {
+            Ok(ToolResult::success_with(serde_json::json!({
+                "selector": css_selector,
+                "date": params.date,
+                "method": result_json["method"],
+                "value": result_json["value"]
+            })))
+        } else {
+            Err(BrowserError::ToolExecutionFailed {
+                tool: "set_date".to_string(),
+                reason: result_json["error"]
+                    .as_str()
+                    .unwrap_or("Unknown error")
+                    .to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_date_params() {
+        let json = serde_json::json!({
+            "selector": "#checkin",
+            "date": "2025-03-14"
+        });
+
+        let params: SetDateParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.selector.as_deref(), Some("#checkin"));
+        assert_eq!(params.index, None);
+        assert_eq!(params.date, "2025-03-14");
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            DateValue::parse("2024-02-29").unwrap(),
+            DateValue {
+                year: 2024,
+                month: 2,
+                day: 29,
+                time: None
+            }
+        );
+        assert_eq!(
+            DateValue::parse("2025-12-01T09:30").unwrap().time,
+            Some((9, 30))
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_date() {
+        assert!(DateValue::parse("2025-02-29").is_err());
+        assert!(DateValue::parse("2025-13-01").is_err());
+        assert!(DateValue::parse("14/03/2025").is_err());
+        assert!(DateValue::parse("2025-3-14").is_err());
+        assert!(DateValue::parse("2025-03-14T25:00").is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::browser::OriginScope;
+use crate::error::Result;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+
+/// Parameters for the set_scope tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SetScopeParams {
+    /// Allowed origins (e.g. "https://example.com") or host patterns (e.g. "*.example.com")
+    pub origins: Vec<String>,
+}
+
+/// Tool for pinning the session to a set of origins
+#[derive(Default)]
+pub struct SetScopeTool;
+
+impl Tool for SetScopeTool {
+    type Params = SetScopeParams;
+
+    fn name(&self) -> &str {
+        "set_scope"
+    }
+
+    fn description(&self) -> &str {
+        "Pin this session to a set of allowed origins; tools then refuse to act outside them. A scope can be narrowed later but never widened"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive().idempotent()
+    }
+
+    fn execute_typed(
+        &self,
+        params: SetScopeParams,
+        context: &mut ToolContext,
+    ) -> Result<ToolResult> {
+        let scope = OriginScope::new(&params.origins)?;
+        context.session.set_scope(scope.clone())?;
+
+        let current_url = context.session.tab()?.get_url();
+
+        Ok(ToolResult::success_with(serde_json::json!({
+            "origins": scope.origins(),
+            "current_page_in_scope": scope.allows(&current_url)
+        })))
+    }
+}
@@ -1,11 +1,11 @@
 use crate::dom::{AriaChild, AriaNode, yaml_escape_key_if_needed, yaml_escape_value_if_needed};
 use crate::error::Result;
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the snapshot tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SnapshotParams {
     /// Whether to include full snapshot or incremental
     #[serde(default)]
@@ -23,6 +23,14 @@ impl Tool for SnapshotTool {
         "snapshot"
     }
 
+    fn description(&self) -> &str {
+        "Get a snapshot of the current page with indexed interactive elements for interaction"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
     fn execute_typed(
         &self,
         params: SnapshotParams,
@@ -37,7 +45,7 @@ impl Tool for SnapshotTool {
         // Count interactive elements
         let interactive_count = dom.count_interactive();
 
-        let result = if params.incremental {
+        let mut result = if params.incremental {
             // TODO: Implement incremental snapshots
             serde_json::json!({
                 "full": yaml_snapshot,
@@ -49,6 +57,12 @@ impl Tool for SnapshotTool {
                 "interactive_count": interactive_count,
             })
         };
+        // Tells the caller whether scrolling would reveal more of the page
+        if let Some(page) = &dom.page {
+            result["page"] = serde_json::to_value(page).unwrap_or_default();
+            result["offscreen_count"] = page.offscreen().into();
+            result["page_summary"] = page.summary(interactive_count).into();
+        }
 
         Ok(ToolResult::success_with(result))
     }
@@ -153,8 +167,10 @@ fn visit(
         // Node with props and/or children
         lines.push(format!("{}:", escaped_key));
 
-        // Render props
-        for (name, value) in &aria_node.props {
+        // Render props, sorted so snapshots of the same page compare equal
+        let mut props: Vec<_> = aria_node.props.iter().collect();
+        props.sort();
+        for (name, value) in props {
             lines.push(format!(
                 "{}  - /{}: {}",
                 indent,
@@ -250,10 +266,11 @@ fn create_key(aria_node: &AriaNode, render_cursor_pointer: bool, render_active:
 }
 
 fn get_single_inlined_text_child(aria_node: &AriaNode) -> Option<String> {
-    if aria_node.children.len() == 1 && aria_node.props.is_empty() {
-        if let AriaChild::Text(text) = &aria_node.children[0] {
-            return Some(text.clone());
-        }
+    if aria_node.children.len() == 1
+        && aria_node.props.is_empty()
+        && let AriaChild::Text(text) = &aria_node.children[0]
+    {
+        return Some(text.clone());
     }
     None
 }
@@ -326,6 +343,29 @@ mod tests {
         assert!(yaml.contains("https://example.com"));
     }
 
+    #[test]
+    fn test_render_props_sorted() {
+        let mut root = AriaNode::fragment();
+        root.children.push(AriaChild::Node(Box::new(
+            AriaNode::new("combobox", "")
+                .with_index(0)
+                .with_prop("value", "Express")
+                .with_prop("hint", "name=shipping")
+                .with_prop("placeholder", "Choose"),
+        )));
+
+        let yaml = render_aria_tree(&root, RenderMode::Ai, None);
+        let props: Vec<&str> = yaml.lines().skip(1).map(str::trim).collect();
+        assert_eq!(
+            props,
+            vec![
+                "- /hint: name=shipping",
+                "- /placeholder: Choose",
+                "- /value: Express"
+            ]
+        );
+    }
+
     #[test]
     fn test_render_with_aria_states() {
         let mut root = AriaNode::fragment();
@@ -1,13 +1,20 @@
 use crate::error::Result;
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::utils::resolve_tab;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Parameters for the switch_tab tool
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct SwitchTabParams {
-    /// Tab index to switch to
-    pub index: usize,
+    /// Index of the tab to switch to, from the tab list (use either this or id)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+
+    /// Id of the tab to switch to, from the tab list; unlike indices it stays
+    /// the same when other tabs close
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
 }
 
 /// Tool for switching to a specific tab
@@ -21,36 +28,32 @@ impl Tool for SwitchTabTool {
         "switch_tab"
     }
 
+    fn description(&self) -> &str {
+        "Switch to a tab by id or index (from browser_tab_list); later tools act on it"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::additive().idempotent()
+    }
+
     fn execute_typed(
         &self,
         params: SwitchTabParams,
         context: &mut ToolContext,
     ) -> Result<ToolResult> {
-        // Get all tabs to validate index
         let tabs = context.session.get_tabs()?;
+        let index = match resolve_tab(&tabs, params.index, params.id.as_deref()) {
+            Ok(Some(index)) => index,
+            Ok(None) => {
+                return Ok(ToolResult::failure(
+                    "Must specify either 'index' or 'id'".to_string(),
+                ));
+            }
+            Err(message) => return Ok(ToolResult::failure(message)),
+        };
 
-        if params.index >= tabs.len() {
-            return Ok(ToolResult::failure(format!(
-                "Invalid tab index: {}. Valid range: 0-{}",
-                params.index,
-                tabs.len() - 1
-            )));
-        }
-
-        // Note: We can't directly call session.switch_tab() because we only have &BrowserSession
-        // Instead, we'll need to work with the browser directly
-        // However, since this requires mutable access to the session, we need to handle this differently
-
-        // Get the tab at the specified index
-        let target_tab = tabs[params.index].clone();
-
-        // Activate the tab
-        target_tab.activate().map_err(|e| {
-            crate::error::BrowserError::TabOperationFailed(format!(
-                "Failed to activate tab {}: {}",
-                params.index, e
-            ))
-        })?;
+        let target_tab = context.session.switch_to_tab(tabs[index].get_target_id())?;
+        context.invalidate_dom();
 
         // Get updated tab info
         let title = target_tab.get_title().unwrap_or_default();
@@ -64,13 +67,11 @@ impl Tool for SwitchTabTool {
             tab_list_str.push_str(&format!("[{}] {} ({})\n", idx, tab_title, tab_url));
         }
 
-        let summary = format!(
-            "Switched to tab {}\nAll Tabs:\n{}",
-            params.index, tab_list_str
-        );
+        let summary = format!("Switched to tab {}\nAll Tabs:\n{}", index, tab_list_str);
 
         Ok(ToolResult::success_with(serde_json::json!({
-            "index": params.index,
+            "index": index,
+            "id": target_tab.get_target_id(),
             "title": title,
             "url": url,
             "message": summary
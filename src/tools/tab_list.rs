@@ -1,11 +1,13 @@
 use crate::error::Result;
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
 
 /// Information about a browser tab
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TabInfo {
+    /// Target id, stable while the tab is open
+    pub id: String,
     /// Tab index
     pub index: usize,
     /// Whether this is the active tab
@@ -17,7 +19,8 @@ pub struct TabInfo {
 }
 
 /// Parameters for the tab_list tool (no parameters needed)
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TabListParams {}
 
 /// Tool for listing all browser tabs
@@ -31,34 +34,31 @@ impl Tool for TabListTool {
         "tab_list"
     }
 
+    fn description(&self) -> &str {
+        "Get the list of all browser tabs with their ids, titles and URLs, marking the active one"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
     fn execute_typed(
         &self,
         _params: TabListParams,
         context: &mut ToolContext,
     ) -> Result<ToolResult> {
-        // Get all tabs
-        let tabs = context.session.get_tabs()?;
-        let active_tab = context.session.tab()?;
-
-        // Build tab info list
-        let mut tab_list = Vec::new();
-        for (index, tab) in tabs.iter().enumerate() {
-            // Check if this is the active tab by comparing Arc pointers
-            let is_active = std::sync::Arc::ptr_eq(tab, &active_tab);
-
-            // Get tab title (fallback to empty string on error)
-            let title = tab.get_title().unwrap_or_default();
-
-            // Get tab URL (not a Result, returns String directly)
-            let url = tab.get_url();
-
-            tab_list.push(TabInfo {
-                index,
-                active: is_active,
-                title,
-                url,
-            });
-        }
+        let tab_list: Vec<TabInfo> = context
+            .session
+            .list_tabs()?
+            .into_iter()
+            .map(|tab| TabInfo {
+                id: tab.id,
+                index: tab.index,
+                active: tab.active,
+                title: tab.title,
+                url: tab.url,
+            })
+            .collect();
 
         // Build summary text
         let active_index = tab_list.iter().position(|t| t.active).unwrap_or(0);
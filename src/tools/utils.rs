@@ -1,3 +1,70 @@
+use crate::error::{BrowserError, Result};
+use crate::tools::ToolContext;
+use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+use headless_chrome::{Element, Tab};
+use std::sync::Arc;
+
+#[cfg(feature = "encryption")]
+use crate::browser::encryption::STATE_KEY_ENV;
+/// Same variable as `browser::encryption::STATE_KEY_ENV`, which needs the `encryption` feature
+#[cfg(not(feature = "encryption"))]
+const STATE_KEY_ENV: &str = "BROWSER_USE_STATE_KEY";
+
+/// Returns the visible text or accessible label of an element, truncated to 200 characters
+///
+/// The value of secret inputs is left out, matching `isSecretInput` in extract_dom.js.
+const ELEMENT_LABEL_JS: &str = r#"
+    function() {
+        const autocomplete = (this.getAttribute('autocomplete') || '').toLowerCase();
+        const secret = this.type === 'password'
+            || /current-password|new-password|one-time-code|cc-number|cc-csc/.test(autocomplete)
+            || /passw|passcode|secret|token|api[-_]?key|\botp\b|cvc|cvv/i.test((this.name || '') + ' ' + (this.id || ''));
+        const label = this.innerText || (secret ? '' : this.value) || this.getAttribute('aria-label')
+            || this.getAttribute('title') || this.getAttribute('placeholder') || this.name || '';
+        return String(label).trim().replace(/\s+/g, ' ').slice(0, 200);
+    }
+"#;
+
+/// Whether a character can be typed as a key event on the US layout
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_graphic() || matches!(c, ' ' | '\n' | '\r' | '\t')
+}
+
+/// Split text into runs that are typed as key events (`true`) or inserted whole (`false`)
+fn text_runs(text: &str) -> Vec<(bool, &str)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut keys = None;
+    for (i, c) in text.char_indices() {
+        let is_key = is_key_char(c);
+        if keys.is_some_and(|keys| keys != is_key) {
+            runs.push((keys.unwrap_or(true), &text[start..i]));
+            start = i;
+        }
+        keys = Some(is_key);
+    }
+    if let Some(keys) = keys {
+        runs.push((keys, &text[start..]));
+    }
+    runs
+}
+
+/// Type text into the focused element
+///
+/// ASCII runs are typed as key events so key listeners fire. Everything else
+/// (emoji, CJK, accented letters) is committed whole with `Input.insertText`,
+/// the way an IME does, so multi-codepoint characters arrive intact.
+pub fn type_text(tab: &Tab, text: &str) -> anyhow::Result<()> {
+    for (keys, run) in text_runs(text) {
+        if keys {
+            tab.type_str(run)?;
+        } else {
+            tab.send_character(run)?;
+        }
+    }
+    Ok(())
+}
+
 /// Normalize an incomplete URL by adding missing protocol and handling common patterns
 pub fn normalize_url(url: &str) -> String {
     let trimmed = url.trim();
@@ -35,10 +102,256 @@ pub fn normalize_url(url: &str) -> String {
     format!("https://www.{}.com", trimmed)
 }
 
+/// Passphrase for encrypting persisted state, read from the environment
+///
+/// Returns `None` when the variable is unset, in which case state files are
+/// written as plain JSON. Fails when the variable is set but the `encryption`
+/// feature is disabled, rather than writing the state unencrypted.
+pub fn state_encryption_key() -> Result<Option<String>> {
+    let key = std::env::var(STATE_KEY_ENV)
+        .ok()
+        .filter(|key| !key.is_empty());
+
+    #[cfg(not(feature = "encryption"))]
+    if key.is_some() {
+        return Err(BrowserError::EncryptionFailed(format!(
+            "{} is set, but this build lacks the `encryption` feature",
+            STATE_KEY_ENV
+        )));
+    }
+
+    Ok(key)
+}
+
+/// Type skeleton of a JSON value: objects keep their keys, arrays show their first item
+///
+/// Containers nested deeper than `max_depth` are replaced with `"..."`.
+pub fn json_shape(value: &serde_json::Value, max_depth: usize) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Null => Value::from("null"),
+        Value::Bool(_) => Value::from("boolean"),
+        Value::Number(_) => Value::from("number"),
+        Value::String(_) => Value::from("string"),
+        _ if max_depth == 0 => Value::from("..."),
+        Value::Array(items) => match items.first() {
+            Some(item) => Value::Array(vec![json_shape(item, max_depth - 1)]),
+            None => Value::Array(Vec::new()),
+        },
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), json_shape(value, max_depth - 1)))
+                .collect(),
+        ),
+    }
+}
+
+/// Resolve an optional `selector` or `index` to a CSS selector; `None` when neither is set
+pub fn resolve_optional_selector(
+    tool: &str,
+    context: &mut ToolContext,
+    selector: Option<&str>,
+    index: Option<usize>,
+) -> Result<Option<String>> {
+    match (selector, index) {
+        (Some(_), Some(_)) => Err(BrowserError::ToolExecutionFailed {
+            tool: tool.to_string(),
+            reason: "Cannot specify both 'selector' and 'index'. Use one or the other.".to_string(),
+        }),
+        (Some(selector), None) => Ok(Some(selector.to_string())),
+        (None, Some(index)) => Ok(Some(context.selector_for_index(index)?)),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Position in `tabs` of the tab given by `index` or target `id`; `None` when neither is set
+///
+/// Fails with a message for the model when both are given or neither matches a tab.
+pub fn resolve_tab(
+    tabs: &[Arc<Tab>],
+    index: Option<usize>,
+    id: Option<&str>,
+) -> std::result::Result<Option<usize>, String> {
+    match (index, id) {
+        (Some(_), Some(_)) => {
+            Err("Cannot specify both 'index' and 'id'. Use one or the other.".to_string())
+        }
+        (Some(index), None) if index >= tabs.len() => Err(format!(
+            "Invalid tab index: {}. Valid range: 0-{}",
+            index,
+            tabs.len().saturating_sub(1)
+        )),
+        (Some(index), None) => Ok(Some(index)),
+        (None, Some(id)) => tabs
+            .iter()
+            .position(|tab| tab.get_target_id() == id)
+            .map(Some)
+            .ok_or_else(|| format!("No tab with id '{}'; see tab_list", id)),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Whether the tab shows a PDF in Chrome's viewer rather than an HTML document
+pub fn is_pdf_page(tab: &Tab) -> bool {
+    tab.evaluate("document.contentType", false)
+        .ok()
+        .and_then(|result| result.value)
+        .is_some_and(|value| value == "application/pdf")
+}
+
+/// Capture a PNG of the element given by `selector` or `index`, or of the page when neither is set
+pub fn capture_png(
+    tool: &str,
+    context: &mut ToolContext,
+    selector: Option<&str>,
+    index: Option<usize>,
+    full_page: bool,
+) -> Result<Vec<u8>> {
+    let selector = resolve_optional_selector(tool, context, selector, index)?;
+
+    let tab = context.session.tab()?;
+    match &selector {
+        Some(selector) => context
+            .session
+            .find_element(&tab, selector)?
+            .capture_screenshot(CaptureScreenshotFormatOption::Png),
+        None => tab.capture_screenshot(CaptureScreenshotFormatOption::Png, None, None, full_page),
+    }
+    .map_err(|e| BrowserError::ScreenshotFailed(e.to_string()))
+}
+
+/// Ask for approval before `tool` acts on `element`, if the session's confirmation policy requires it
+pub fn confirm_element(tool: &str, context: &ToolContext, element: &Element) -> Result<()> {
+    confirm_label(tool, context, || {
+        Ok(element
+            .call_js_fn(ELEMENT_LABEL_JS, vec![], false)
+            .ok()
+            .and_then(|result| result.value)
+            .and_then(|value| value.as_str().map(String::from))
+            .unwrap_or_default())
+    })
+}
+
+/// Ask for approval before `tool` sends keys to the focused element, if the policy requires it
+///
+/// Nothing is checked when the page itself has focus.
+pub fn confirm_focused(tool: &str, context: &ToolContext) -> Result<()> {
+    if context.session.mock().is_some() {
+        return Ok(());
+    }
+    confirm_label(tool, context, || {
+        let js = format!(
+            "(function() {{
+                const el = document.activeElement;
+                if (!el || el === document.body || el === document.documentElement) return '';
+                return ({}).call(el);
+            }})()",
+            ELEMENT_LABEL_JS
+        );
+        Ok(context
+            .session
+            .tab()?
+            .evaluate(&js, false)
+            .ok()
+            .and_then(|result| result.value)
+            .and_then(|value| value.as_str().map(String::from))
+            .unwrap_or_default())
+    })
+}
+
+/// Ask for approval before `tool` acts on the element labelled `label()`, if the policy requires it
+fn confirm_label(
+    tool: &str,
+    context: &ToolContext,
+    label: impl FnOnce() -> Result<String>,
+) -> Result<()> {
+    if !context.session.confirmation().inspects_text() {
+        return Ok(());
+    }
+    context.session.confirm_action(tool, Some(&label()?), None)
+}
+
+/// Click the element matching `selector` on behalf of `tool`, after approval if needed
+pub fn click_element(tool: &str, context: &ToolContext, selector: &str) -> Result<()> {
+    if let Some(mock) = context.session.mock() {
+        confirm_label(tool, context, || mock.element_label(selector))?;
+        context.session.record_page_action();
+        return mock.click(selector);
+    }
+
+    let tab = context.session.tab()?;
+    let element = context.session.find_element(&tab, selector)?;
+    confirm_element(tool, context, &element)?;
+    let clicked = element.click();
+    context.session.record_page_action();
+    clicked.map_err(|e| BrowserError::ToolExecutionFailed {
+        tool: tool.to_string(),
+        reason: e.to_string(),
+    })?;
+    Ok(())
+}
+
+/// Type `text` into the element matching `selector` on behalf of `tool`, optionally clearing it first
+pub fn input_text(
+    tool: &str,
+    context: &ToolContext,
+    selector: &str,
+    text: &str,
+    clear: bool,
+) -> Result<()> {
+    if let Some(mock) = context.session.mock() {
+        confirm_label(tool, context, || mock.element_label(selector))?;
+        context.session.record_page_action();
+        return mock.type_text(selector, text, clear);
+    }
+
+    let tab = context.session.tab()?;
+    let element = context.session.find_element(&tab, selector)?;
+    confirm_element(tool, context, &element)?;
+    // Typed values live in properties, which DOM mutations do not reveal
+    context.session.record_page_action();
+
+    if clear {
+        element.click().ok(); // Focus
+        // Clear with Ctrl+A and Delete
+        tab.press_key("End").ok();
+        for _ in 0..text.len() + 100 {
+            tab.press_key("Backspace").ok();
+        }
+    }
+
+    element
+        .click()
+        .and_then(|_| type_text(&tab, text))
+        .map_err(|e| BrowserError::ToolExecutionFailed {
+            tool: tool.to_string(),
+            reason: e.to_string(),
+        })?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_text_runs() {
+        assert_eq!(text_runs("hello"), vec![(true, "hello")]);
+        assert_eq!(text_runs(""), Vec::<(bool, &str)>::new());
+        assert_eq!(
+            text_runs("Hi 你好, 👨‍👩‍👧!"),
+            vec![
+                (true, "Hi "),
+                (false, "你好"),
+                (true, ", "),
+                (false, "👨‍👩‍👧"),
+                (true, "!")
+            ]
+        );
+        assert_eq!(text_runs("café"), vec![(true, "caf"), (false, "é")]);
+    }
+
     #[test]
     fn test_normalize_url_complete() {
         assert_eq!(normalize_url("https://example.com"), "https://example.com");
@@ -1,23 +1,64 @@
+use crate::dom::DeepSelector;
 use crate::error::{BrowserError, Result};
-use crate::tools::{Tool, ToolContext, ToolResult};
-use schemars::JsonSchema;
+use crate::tools::{Tool, ToolAnnotations, ToolContext, ToolResult};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct WaitParams {
-    /// CSS selector to wait for
+    /// CSS selector to wait for; use `>>>` to reach into iframes and shadow roots
     pub selector: String,
 
     /// Timeout in milliseconds (default: 30000)
     #[serde(default = "default_timeout")]
     pub timeout_ms: u64,
+
+    /// What to wait for (default: present)
+    #[serde(default)]
+    pub condition: WaitCondition,
+
+    /// Consecutive animation frames the element's box must stay unchanged
+    /// for the `stable` condition (default: 5)
+    #[serde(default = "default_stable_frames")]
+    pub stable_frames: u32,
+}
+
+/// Condition the wait tool waits for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum WaitCondition {
+    /// The element is in the document
+    #[default]
+    Present,
+    /// The element is in the document and its position and size stopped
+    /// changing, e.g. after an animation or a layout shift
+    Stable,
+}
+
+/// Box reported by the `stableBox` page helper
+#[derive(Debug, Deserialize)]
+struct Stability {
+    stable: bool,
+    #[serde(rename = "box")]
+    bounds: Option<serde_json::Value>,
 }
 
+/// How often a deep selector is retried
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Longest a single stability check runs in the page before it is retried
+const STABILITY_SLICE_MS: u64 = 1000;
+
 fn default_timeout() -> u64 {
     30000
 }
 
+fn default_stable_frames() -> u32 {
+    5
+}
+
 #[derive(Default)]
 pub struct WaitTool;
 
@@ -28,29 +69,115 @@ impl Tool for WaitTool {
         "wait"
     }
 
+    fn description(&self) -> &str {
+        "Wait for an element to appear on the page, or with condition=stable until it also stops moving or resizing"
+    }
+
+    fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations::read_only()
+    }
+
     fn execute_typed(&self, params: WaitParams, context: &mut ToolContext) -> Result<ToolResult> {
-        let start = std::time::Instant::now();
-
-        context
-            .session
-            .tab()?
-            .wait_for_element_with_custom_timeout(
-                &params.selector,
-                Duration::from_millis(params.timeout_ms),
-            )
-            .map_err(|e| {
-                BrowserError::Timeout(format!(
-                    "Element '{}' not found within {} ms: {}",
-                    params.selector, params.timeout_ms, e
-                ))
-            })?;
+        let start = Instant::now();
+        let timeout = Duration::from_millis(params.timeout_ms);
+        let tab = context.session.tab()?;
+
+        if DeepSelector::is_deep(&params.selector) {
+            let selector = DeepSelector::parse(&params.selector)?;
+            // The frame or shadow root may not exist yet, so retry the whole path
+            while let Err(e) = selector.find(&tab) {
+                if start.elapsed() >= timeout {
+                    return Err(BrowserError::Timeout(format!(
+                        "Element '{}' not found within {} ms: {}",
+                        params.selector, params.timeout_ms, e
+                    )));
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        } else {
+            tab.wait_for_element_with_custom_timeout(&params.selector, timeout)
+                .map_err(|e| {
+                    BrowserError::Timeout(format!(
+                        "Element '{}' not found within {} ms: {}",
+                        params.selector, params.timeout_ms, e
+                    ))
+                })?;
+        }
+
+        let bounds = match params.condition {
+            WaitCondition::Present => None,
+            WaitCondition::Stable => Some(wait_until_stable(context, &params, start)?),
+        };
 
         let elapsed = start.elapsed().as_millis() as u64;
 
-        Ok(ToolResult::success_with(serde_json::json!({
+        let mut data = serde_json::json!({
             "selector": params.selector,
             "found": true,
             "elapsed_ms": elapsed
-        })))
+        });
+        if let Some(bounds) = bounds {
+            data["stable"] = true.into();
+            data["box"] = bounds;
+        }
+        Ok(ToolResult::success_with(data))
+    }
+}
+
+/// Wait until the element's box stays the same for `stable_frames` frames
+fn wait_until_stable(
+    context: &ToolContext,
+    params: &WaitParams,
+    start: Instant,
+) -> Result<serde_json::Value> {
+    let selector = DeepSelector::parse(&params.selector)?;
+    let timeout = Duration::from_millis(params.timeout_ms);
+    loop {
+        let remaining = timeout.saturating_sub(start.elapsed()).as_millis() as u64;
+        // The element may be re-rendered meanwhile; it is looked up again each slice
+        let result = context.session.call_helper::<Stability>(
+            "stableBox",
+            serde_json::json!({
+                "selector": selector.steps(),
+                "frames": params.stable_frames.max(1),
+                "maxMs": remaining.clamp(1, STABILITY_SLICE_MS),
+            }),
+        );
+        match result {
+            Ok(Stability {
+                stable: true,
+                bounds,
+            }) => return Ok(bounds.unwrap_or_default()),
+            Ok(_) | Err(BrowserError::ElementNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+        if start.elapsed() >= timeout {
+            return Err(BrowserError::Timeout(format!(
+                "Element '{}' did not stop moving within {} ms",
+                params.selector, params.timeout_ms
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_params() {
+        let params: WaitParams =
+            serde_json::from_value(serde_json::json!({ "selector": "#menu" })).unwrap();
+        assert_eq!(params.condition, WaitCondition::Present);
+        assert_eq!(params.stable_frames, 5);
+
+        let params: WaitParams = serde_json::from_value(serde_json::json!({
+            "selector": "#menu",
+            "condition": "stable",
+            "stable_frames": 10
+        }))
+        .unwrap();
+        assert_eq!(params.condition, WaitCondition::Stable);
+        assert_eq!(params.stable_frames, 10);
     }
 }
@@ -0,0 +1,241 @@
+//! Tools that run a fixed sequence of other tools
+//!
+//! A [`WorkflowToolDefinition`] lists tool calls whose arguments may refer to
+//! the workflow's own arguments with `{{name}}` placeholders, e.g. a "search"
+//! workflow that navigates to a site, fills the search box and submits. Steps
+//! run through the session's tool registry and stop at the first failure.
+
+use crate::error::{BrowserError, Result};
+use crate::tools::custom_tool::{check_arguments, check_tool_name, object_schema};
+use crate::tools::{DynTool, ToolContext, ToolResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// One tool call of a workflow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowStep {
+    /// Name of the registered tool to run, e.g. "navigate"
+    pub tool: String,
+
+    /// Arguments for the tool; `{{name}}` placeholders are filled from the workflow's arguments
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// Definition of a tool made of other tools' calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowToolDefinition {
+    /// Tool name: letters, digits, '_' and '-', starting with a letter
+    pub name: String,
+
+    /// What the tool does, shown to clients
+    pub description: String,
+
+    /// JSON schema of the tool's arguments (default: no arguments)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Value>,
+
+    /// Tool calls to make, in order
+    pub steps: Vec<WorkflowStep>,
+}
+
+/// Tool that runs a [`WorkflowToolDefinition`]'s steps
+#[derive(Debug, Clone)]
+pub struct WorkflowTool {
+    definition: WorkflowToolDefinition,
+    schema: Map<String, Value>,
+}
+
+/// Fill `{{name}}` placeholders in string values from `arguments`
+///
+/// A string that is exactly one placeholder takes the argument's JSON value
+/// (`null` if not given), so numbers and booleans keep their type; other
+/// placeholders are replaced with the argument's text.
+fn fill_placeholders(value: &Value, arguments: &Map<String, Value>) -> Value {
+    match value {
+        Value::String(text) => {
+            if let Some(name) = text
+                .strip_prefix("{{")
+                .and_then(|rest| rest.strip_suffix("}}"))
+                .filter(|name| !name.contains("{{") && !name.contains("}}"))
+            {
+                return arguments.get(name.trim()).cloned().unwrap_or(Value::Null);
+            }
+            let mut filled = text.clone();
+            for (name, argument) in arguments {
+                let argument = match argument {
+                    Value::String(argument) => argument.clone(),
+                    other => other.to_string(),
+                };
+                filled = filled.replace(&format!("{{{{{}}}}}", name), &argument);
+            }
+            Value::String(filled)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| fill_placeholders(item, arguments))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, field)| (key.clone(), fill_placeholders(field, arguments)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+impl WorkflowTool {
+    /// Validate a definition and build the tool
+    pub fn new(definition: WorkflowToolDefinition) -> Result<Self> {
+        let name = &definition.name;
+        check_tool_name(name)?;
+        if definition.steps.is_empty() {
+            return Err(BrowserError::InvalidArgument(format!(
+                "Workflow '{}' has no steps",
+                name
+            )));
+        }
+        for (i, step) in definition.steps.iter().enumerate() {
+            if step.tool.is_empty() || !matches!(step.params, Value::Null | Value::Object(_)) {
+                return Err(BrowserError::InvalidArgument(format!(
+                    "Step {} of workflow '{}' needs a tool name and object params",
+                    i + 1,
+                    name
+                )));
+            }
+        }
+        let schema = object_schema(name, definition.parameters.as_ref())?;
+
+        Ok(Self { definition, schema })
+    }
+
+    /// The definition the tool was built from
+    pub fn definition(&self) -> &WorkflowToolDefinition {
+        &self.definition
+    }
+
+    /// Give the tool another name, e.g. to namespace it
+    pub fn renamed(mut self, name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        check_tool_name(&name)?;
+        self.definition.name = name;
+        Ok(self)
+    }
+
+    /// Run the steps in order, stopping at the first that fails
+    fn run(&self, params: Value, context: &mut ToolContext) -> Result<ToolResult> {
+        let arguments = check_arguments(&self.schema, &params)?;
+        let session = context.session;
+        let registry = session.tool_registry();
+
+        let mut results = Vec::new();
+        for (i, step) in self.definition.steps.iter().enumerate() {
+            if !registry.has(&step.tool) {
+                return Err(BrowserError::ToolExecutionFailed {
+                    tool: self.definition.name.clone(),
+                    reason: format!("Step {} uses unknown tool '{}'", i + 1, step.tool),
+                });
+            }
+            // Each step sees the page as the previous step left it
+            context.invalidate_dom();
+            let result = registry.execute(
+                &step.tool,
+                fill_placeholders(&step.params, &arguments),
+                context,
+            )?;
+            if !result.success {
+                let error = result.error.unwrap_or_else(|| "Unknown error".to_string());
+                return Ok(ToolResult::failure(format!(
+                    "Step {} ({}) failed: {}",
+                    i + 1,
+                    step.tool,
+                    error
+                )));
+            }
+            results.push(serde_json::json!({
+                "tool": step.tool,
+                "result": result.data,
+            }));
+        }
+
+        let last = results
+            .last()
+            .map(|step| step["result"].clone())
+            .unwrap_or_default();
+        Ok(ToolResult::success_with(serde_json::json!({
+            "result": last,
+            "steps": results,
+        })))
+    }
+}
+
+impl DynTool for WorkflowTool {
+    fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    fn description(&self) -> &str {
+        &self.definition.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        Value::Object(self.schema.clone())
+    }
+
+    fn execute(&self, params: Value, context: &mut ToolContext) -> Result<ToolResult> {
+        self.run(params, context)
+            .map_err(|e| e.with_tool(self.definition.name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_placeholders() {
+        let arguments = serde_json::json!({ "query": "rust", "limit": 5 });
+        let arguments = arguments.as_object().unwrap();
+
+        let params = serde_json::json!({
+            "url": "https://example.com/search?q={{query}}",
+            "limit": "{{limit}}",
+            "missing": "{{other}}",
+            "keys": ["{{query}}", "Enter"]
+        });
+        assert_eq!(
+            fill_placeholders(&params, arguments),
+            serde_json::json!({
+                "url": "https://example.com/search?q=rust",
+                "limit": 5,
+                "missing": null,
+                "keys": ["rust", "Enter"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_workflow_validation() {
+        let definition: WorkflowToolDefinition = serde_json::from_value(serde_json::json!({
+            "name": "search",
+            "description": "Search the docs",
+            "steps": [
+                { "tool": "navigate", "params": { "url": "https://docs.rs/{{crate}}" } },
+                { "tool": "get_markdown" }
+            ]
+        }))
+        .unwrap();
+        assert!(WorkflowTool::new(definition.clone()).is_ok());
+
+        let mut empty = definition.clone();
+        empty.steps.clear();
+        assert!(WorkflowTool::new(empty).is_err());
+
+        let mut bad_params = definition;
+        bad_params.steps[0].params = serde_json::json!("https://docs.rs");
+        assert!(WorkflowTool::new(bad_params).is_err());
+    }
+}
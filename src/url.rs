@@ -0,0 +1,251 @@
+//! URL normalization and deduplication helpers
+//!
+//! These utilities canonicalize URLs so that links differing only in tracking
+//! parameters, fragments, default ports or trailing slashes compare equal. They
+//! are used by `read_links` and the crawler, and are exported for library users
+//! building their own crawl frontiers.
+
+use ::url::Url;
+use std::collections::HashSet;
+
+/// Query parameters that only carry analytics information
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "gclsrc", "dclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid", "_ga",
+    "_gl", "_hsenc", "_hsmi", "mkt_tok", "ref_src", "spm",
+];
+
+/// Second-level labels under which registrations happen one level deeper (e.g. `example.co.uk`)
+const SECOND_LEVEL_SUFFIXES: &[&str] = &["co", "com", "net", "org", "gov", "edu", "ac", "ne", "or"];
+
+/// Check whether a query parameter name is a known tracking parameter
+pub fn is_tracking_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("utm_") || TRACKING_PARAMS.contains(&name.as_str())
+}
+
+/// Remove tracking parameters (utm_*, fbclid, gclid, ...) from a URL
+pub fn strip_tracking_params(url: &mut Url) {
+    if url.query().is_none() {
+        return;
+    }
+
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !is_tracking_param(name))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+}
+
+/// Resolve a possibly relative `href` against `base`
+///
+/// Returns `None` for unparsable input and for non-navigable schemes such as
+/// `javascript:` and `mailto:`.
+pub fn resolve(base: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    let resolved = match Url::parse(base) {
+        Ok(base) => base.join(href).ok()?,
+        Err(_) => Url::parse(href).ok()?,
+    };
+
+    matches!(resolved.scheme(), "http" | "https" | "file").then(|| resolved.to_string())
+}
+
+/// Canonicalize a URL for deduplication
+///
+/// Lowercases the scheme and host, drops default ports, the fragment and
+/// tracking parameters, sorts the remaining query parameters and removes a
+/// trailing slash from non-root paths.
+pub fn canonicalize(url: &str) -> Option<String> {
+    let mut url = Url::parse(url.trim()).ok()?;
+
+    url.set_fragment(None);
+    strip_tracking_params(&mut url);
+
+    if url.query().is_some() {
+        let mut pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect();
+        pairs.sort();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+
+    let path = url.path().to_string();
+    if path.len() > 1 && path.ends_with('/') {
+        url.set_path(path.trim_end_matches('/'));
+    }
+
+    Some(url.to_string())
+}
+
+/// Check whether two URLs share scheme, host and port
+pub fn is_same_origin(a: &str, b: &str) -> bool {
+    match (Url::parse(a), Url::parse(b)) {
+        (Ok(a), Ok(b)) => a.origin() == b.origin() && a.origin().is_tuple(),
+        _ => false,
+    }
+}
+
+/// Approximate the registrable domain of a host (e.g. `www.example.co.uk` → `example.co.uk`)
+///
+/// This is a heuristic rather than a full public suffix list lookup.
+pub fn registrable_domain(host: &str) -> String {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return host;
+    }
+
+    let labels: Vec<&str> = host.split('.').collect();
+    let keep = if labels.len() >= 3
+        && labels[labels.len() - 1].len() == 2
+        && SECOND_LEVEL_SUFFIXES.contains(&labels[labels.len() - 2])
+    {
+        3
+    } else {
+        2
+    };
+
+    labels[labels.len().saturating_sub(keep)..].join(".")
+}
+
+/// Check whether two URLs belong to the same site (scheme and registrable domain)
+pub fn is_same_site(a: &str, b: &str) -> bool {
+    let (Ok(a), Ok(b)) = (Url::parse(a), Url::parse(b)) else {
+        return false;
+    };
+    match (a.host_str(), b.host_str()) {
+        (Some(host_a), Some(host_b)) => {
+            a.scheme() == b.scheme() && registrable_domain(host_a) == registrable_domain(host_b)
+        }
+        _ => false,
+    }
+}
+
+/// Set of canonical URLs for deduplicating a crawl frontier
+#[derive(Debug, Clone, Default)]
+pub struct UrlSet {
+    seen: HashSet<String>,
+}
+
+impl UrlSet {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a URL, returning `true` if its canonical form was not seen before
+    pub fn insert(&mut self, url: &str) -> bool {
+        let key = canonicalize(url).unwrap_or_else(|| url.to_string());
+        self.seen.insert(key)
+    }
+
+    /// Check whether a URL's canonical form has been seen
+    pub fn contains(&self, url: &str) -> bool {
+        let key = canonicalize(url).unwrap_or_else(|| url.to_string());
+        self.seen.contains(&key)
+    }
+
+    /// Number of distinct URLs seen
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether no URLs have been seen
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+/// Remove duplicate URLs (by canonical form), keeping the first occurrence
+pub fn dedupe<I, S>(urls: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut seen = UrlSet::new();
+    urls.into_iter()
+        .filter(|url| seen.insert(url.as_ref()))
+        .map(|url| url.as_ref().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_tracking_params() {
+        let mut url = Url::parse("https://example.com/a?utm_source=x&id=1&fbclid=abc").unwrap();
+        strip_tracking_params(&mut url);
+        assert_eq!(url.as_str(), "https://example.com/a?id=1");
+
+        let mut url = Url::parse("https://example.com/a?utm_medium=email").unwrap();
+        strip_tracking_params(&mut url);
+        assert_eq!(url.as_str(), "https://example.com/a");
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            resolve("https://example.com/docs/page", "../about").as_deref(),
+            Some("https://example.com/about")
+        );
+        assert_eq!(
+            resolve("https://example.com/", "//cdn.example.com/x").as_deref(),
+            Some("https://cdn.example.com/x")
+        );
+        assert_eq!(resolve("https://example.com/", "javascript:void(0)"), None);
+        assert_eq!(resolve("https://example.com/", "mailto:a@b.c"), None);
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        assert_eq!(
+            canonicalize("HTTPS://Example.COM:443/path/?b=2&a=1&utm_campaign=x#section").as_deref(),
+            Some("https://example.com/path?a=1&b=2")
+        );
+        assert_eq!(
+            canonicalize("https://example.com/").as_deref(),
+            Some("https://example.com/")
+        );
+        assert_eq!(canonicalize("not a url"), None);
+    }
+
+    #[test]
+    fn test_same_origin_and_site() {
+        assert!(is_same_origin(
+            "https://example.com/a",
+            "https://example.com/b"
+        ));
+        assert!(!is_same_origin("https://example.com", "http://example.com"));
+        assert!(is_same_site(
+            "https://www.example.com",
+            "https://shop.example.com"
+        ));
+        assert!(is_same_site(
+            "https://a.example.co.uk",
+            "https://b.example.co.uk"
+        ));
+        assert!(!is_same_site(
+            "https://example.co.uk",
+            "https://other.co.uk"
+        ));
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let urls = dedupe([
+            "https://example.com/a",
+            "https://example.com/a/",
+            "https://example.com/a#top",
+            "https://example.com/b",
+        ]);
+        assert_eq!(urls, vec!["https://example.com/a", "https://example.com/b"]);
+    }
+}
@@ -0,0 +1,517 @@
+//! WebSocket JSON-RPC control channel
+//!
+//! Every WebSocket connection launches its own browser session, which is
+//! closed when the connection ends. Clients send JSON-RPC 2.0 requests:
+//!
+//! | Method | Params | Result |
+//! |--------|--------|--------|
+//! | `tools/list` | none | `{"tools": [{"name", "description", "parameters"}]}` |
+//! | `tools/call` | `{"name", "arguments"}` | The tool result `{"success", "data", "error", "metadata"}` |
+//! | `dom/get` | none | The active tab's DOM tree |
+//!
+//! Requests on one connection run in order. Meanwhile the server pushes
+//! `page/event` notifications for console errors, uncaught page errors and
+//! failed requests as they happen, so a frontend can show live page problems
+//! next to the commands it runs:
+//!
+//! ```json
+//! {"jsonrpc": "2.0", "method": "page/event", "params": {"type": "consoleError", "message": "..."}}
+//! ```
+//!
+//! Library errors are JSON-RPC errors with code `-32000` and the stable error
+//! code, retryability and context in `data`. If the browser fails to launch,
+//! the server sends one such error with a `null` id and closes the connection.
+//! Requests beyond the [`REQUEST_QUEUE_SIZE`] waiting on a connection are
+//! answered with [`SERVER_BUSY`] instead of being queued.
+//!
+//! Since every connection gets a browser, the number of connections is
+//! capped, and upgrades from web pages (requests with an `Origin` header) are
+//! refused unless the origin is the server's own or allow-listed, so a page
+//! open in the user's browser cannot drive the channel.
+
+use crate::browser::BrowserSession;
+use crate::browser::LaunchOptions;
+use crate::error::BrowserError;
+use axum::Router;
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+/// Connections served at once unless configured otherwise
+pub const DEFAULT_MAX_CONNECTIONS: usize = 4;
+/// Requests that may wait on one connection while another runs
+pub const REQUEST_QUEUE_SIZE: usize = 32;
+
+/// JSON-RPC error code for invalid JSON
+pub const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for a message that is not a request
+pub const INVALID_REQUEST: i64 = -32600;
+/// JSON-RPC error code for an unknown method
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for malformed params or an unknown tool
+pub const INVALID_PARAMS: i64 = -32602;
+/// JSON-RPC error code for library errors; details are in `data`
+pub const BROWSER_ERROR: i64 = -32000;
+/// JSON-RPC error code for a request refused because the connection's queue is full
+pub const SERVER_BUSY: i64 = -32001;
+
+type SharedSession = Arc<Mutex<BrowserSession>>;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// A JSON-RPC error object
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut error = json!({ "code": self.code, "message": self.message });
+        if let Some(data) = &self.data {
+            error["data"] = data.clone();
+        }
+        error
+    }
+}
+
+impl From<BrowserError> for RpcError {
+    fn from(error: BrowserError) -> Self {
+        let mut data = json!({
+            "code": error.code(),
+            "retryable": error.retryable(),
+        });
+        if let Some(context) = error.context()
+            && let Ok(Value::Object(context)) = serde_json::to_value(context)
+            && let Value::Object(ref mut fields) = data
+        {
+            fields.extend(context);
+        }
+        Self {
+            code: BROWSER_ERROR,
+            message: error.to_string(),
+            data: Some(data),
+        }
+    }
+}
+
+/// A request or notification from the client
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// Absent for notifications, which get no response
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct CallParams {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+fn response(id: Value, outcome: Result<Value, RpcError>) -> Value {
+    match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error.to_json() }),
+    }
+}
+
+fn notification(method: &str, params: impl serde::Serialize) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+/// Parse one text frame; malformed messages yield the error response to send back
+fn parse_request(text: &str) -> Result<Request, Value> {
+    let value: Value = serde_json::from_str(text).map_err(|e| {
+        response(
+            Value::Null,
+            Err(RpcError::new(PARSE_ERROR, format!("Invalid JSON: {}", e))),
+        )
+    })?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    serde_json::from_value(value).map_err(|e| {
+        response(
+            id,
+            Err(RpcError::new(
+                INVALID_REQUEST,
+                format!("Invalid request: {}", e),
+            )),
+        )
+    })
+}
+
+/// Run one method on the session
+fn dispatch(session: &BrowserSession, method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "tools/list" => {
+            let mut tools: Vec<Value> = session
+                .tool_registry()
+                .all_tools()
+                .iter()
+                .map(|tool| {
+                    json!({
+                        "name": tool.name(),
+                        "description": tool.description(),
+                        "parameters": tool.parameters_schema(),
+                    })
+                })
+                .collect();
+            tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+            Ok(json!({ "tools": tools }))
+        }
+        "tools/call" => {
+            let params: CallParams = serde_json::from_value(params)
+                .map_err(|e| RpcError::new(INVALID_PARAMS, format!("Invalid params: {}", e)))?;
+            if !session.tool_registry().has(&params.name) {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    format!("Unknown tool '{}'", params.name),
+                ));
+            }
+            let arguments = match params.arguments {
+                Value::Null => json!({}),
+                arguments => arguments,
+            };
+            let result = session.execute_tool(&params.name, arguments)?;
+            serde_json::to_value(result).map_err(|e| RpcError::new(BROWSER_ERROR, e.to_string()))
+        }
+        "dom/get" => {
+            let tree = session.extract_dom()?;
+            serde_json::to_value(tree).map_err(|e| RpcError::new(BROWSER_ERROR, e.to_string()))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method '{}'", method),
+        )),
+    }
+}
+
+/// Run requests in arrival order, queueing their responses
+async fn run_requests(
+    session: SharedSession,
+    mut requests: mpsc::Receiver<Request>,
+    outgoing: mpsc::UnboundedSender<Value>,
+) {
+    while let Some(request) = requests.recv().await {
+        let session = Arc::clone(&session);
+        let outcome = tokio::task::spawn_blocking(move || {
+            dispatch(&lock(&session), &request.method, request.params)
+        })
+        .await
+        .unwrap_or_else(|e| Err(RpcError::new(BROWSER_ERROR, format!("Call aborted: {}", e))));
+
+        if let Some(id) = request.id {
+            let _ = outgoing.send(response(id, outcome));
+        }
+    }
+}
+
+async fn send(socket: &mut WebSocket, message: &Value) -> bool {
+    socket
+        .send(Message::Text(message.to_string().into()))
+        .await
+        .is_ok()
+}
+
+/// Own a session for the lifetime of one connection
+async fn serve_connection(mut socket: WebSocket, options: LaunchOptions) {
+    let (outgoing, mut outgoing_rx) = mpsc::unbounded_channel();
+    let events = outgoing.clone();
+    let launched = tokio::task::spawn_blocking(move || {
+        let session = BrowserSession::launch(options)?;
+        session.on_page_event(move |event| {
+            // The connection may already be gone; the event is dropped
+            let _ = events.send(notification("page/event", event));
+        })?;
+        Ok::<_, BrowserError>(session)
+    })
+    .await;
+    let session = match launched {
+        Ok(Ok(session)) => Arc::new(Mutex::new(session)),
+        Ok(Err(e)) => {
+            send(&mut socket, &response(Value::Null, Err(e.into()))).await;
+            return;
+        }
+        Err(e) => {
+            log::error!("Session launch aborted: {}", e);
+            return;
+        }
+    };
+
+    let (requests, requests_rx) = mpsc::channel(REQUEST_QUEUE_SIZE);
+    let worker = tokio::spawn(run_requests(Arc::clone(&session), requests_rx, outgoing));
+
+    loop {
+        tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => match parse_request(&text) {
+                    Ok(request) => {
+                        if let Err(TrySendError::Full(request)) = requests.try_send(request)
+                            && let Some(id) = request.id
+                        {
+                            let busy = RpcError::new(
+                                SERVER_BUSY,
+                                format!("Server busy: {} requests already queued", REQUEST_QUEUE_SIZE),
+                            );
+                            if !send(&mut socket, &response(id, Err(busy))).await {
+                                break;
+                            }
+                        }
+                    }
+                    Err(error) => {
+                        if !send(&mut socket, &error).await {
+                            break;
+                        }
+                    }
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by axum; binary frames are not part of the protocol
+                Some(Ok(_)) => {}
+            },
+            Some(message) = outgoing_rx.recv() => {
+                if !send(&mut socket, &message).await {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Queued requests are dropped; a call already running finishes first
+    worker.abort();
+    let _ = tokio::task::spawn_blocking(move || lock(&session).close()).await;
+}
+
+struct WsState {
+    /// Options every connection's browser is launched with
+    options: LaunchOptions,
+    max_connections: usize,
+    /// Web page origins allowed to connect besides the server's own
+    allowed_origins: Vec<String>,
+    connections: AtomicUsize,
+}
+
+/// A connection slot, released when dropped
+struct ConnectionSlot(Arc<WsState>);
+
+impl ConnectionSlot {
+    fn acquire(state: &Arc<WsState>) -> Option<Self> {
+        state
+            .connections
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |open| {
+                (open < state.max_connections).then_some(open + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(state)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.connections.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Whether an upgrade request may connect, judged by its `Origin` header
+///
+/// Clients other than browsers send no origin and are allowed. A page may
+/// connect if it was served by this server (its origin names the `Host` the
+/// request went to) or its origin is in `allowed`.
+fn origin_allowed(headers: &HeaderMap, allowed: &[String]) -> bool {
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return true;
+    };
+    let Ok(origin) = origin.to_str() else {
+        return false;
+    };
+    let origin = origin.trim_end_matches('/');
+    if allowed
+        .iter()
+        .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+    {
+        return true;
+    }
+    let host = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok());
+    match (url::Url::parse(origin), host) {
+        (Ok(origin), Some(host)) => {
+            let authority = match (origin.host_str(), origin.port()) {
+                (Some(name), Some(port)) => format!("{}:{}", name, port),
+                (Some(name), None) => name.to_string(),
+                (None, _) => return false,
+            };
+            authority.eq_ignore_ascii_case(host)
+        }
+        _ => false,
+    }
+}
+
+async fn upgrade(
+    State(state): State<Arc<WsState>>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Response {
+    if !origin_allowed(&headers, &state.allowed_origins) {
+        return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+    }
+    let Some(slot) = ConnectionSlot::acquire(&state) else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("At most {} connections may be open", state.max_connections),
+        )
+            .into_response();
+    };
+    let options = state.options.clone();
+    ws.on_upgrade(move |socket| async move {
+        serve_connection(socket, options).await;
+        drop(slot);
+    })
+}
+
+/// Router accepting WebSocket connections at `/`; each launches a browser with `options`
+///
+/// At most `max_connections` are served at once. Web pages may only connect
+/// from the server's own origin or one of `allowed_origins` (e.g.
+/// `https://app.example.com`).
+pub fn router(
+    options: LaunchOptions,
+    max_connections: usize,
+    allowed_origins: Vec<String>,
+) -> Router {
+    Router::new()
+        .route("/", get(upgrade))
+        .with_state(Arc::new(WsState {
+            options,
+            max_connections,
+            allowed_origins,
+            connections: AtomicUsize::new(0),
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::MockSession;
+    use crate::browser::events::PageEvent;
+    use crate::dom::{AriaNode, DomTree};
+
+    #[test]
+    fn test_parse_request() {
+        let request = parse_request(
+            r#"{"jsonrpc":"2.0","id":7,"method":"tools/call","params":{"name":"click"}}"#,
+        )
+        .unwrap();
+        assert_eq!(request.id, Some(json!(7)));
+        assert_eq!(request.method, "tools/call");
+
+        let notification = parse_request(r#"{"jsonrpc":"2.0","method":"dom/get"}"#).unwrap();
+        assert_eq!(notification.id, None);
+        assert_eq!(notification.params, Value::Null);
+
+        let error = parse_request("{not json").unwrap_err();
+        assert_eq!(error["id"], Value::Null);
+        assert_eq!(error["error"]["code"], PARSE_ERROR);
+
+        let error = parse_request(r#"{"jsonrpc":"2.0","id":"a"}"#).unwrap_err();
+        assert_eq!(error["id"], "a");
+        assert_eq!(error["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[test]
+    fn test_origin_allowed() {
+        let allowed = vec!["https://app.example.com".to_string()];
+        let headers = |origin: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::HOST, "127.0.0.1:8765".parse().unwrap());
+            headers.insert(header::ORIGIN, origin.parse().unwrap());
+            headers
+        };
+
+        let mut no_origin = HeaderMap::new();
+        no_origin.insert(header::HOST, "127.0.0.1:8765".parse().unwrap());
+        assert!(origin_allowed(&no_origin, &allowed));
+        assert!(origin_allowed(&headers("http://127.0.0.1:8765"), &allowed));
+        assert!(origin_allowed(
+            &headers("https://app.example.com"),
+            &allowed
+        ));
+        assert!(!origin_allowed(&headers("https://evil.example"), &allowed));
+        assert!(!origin_allowed(&headers("http://127.0.0.1:3000"), &allowed));
+        assert!(!origin_allowed(&headers("null"), &allowed));
+    }
+
+    #[test]
+    fn test_connection_slots() {
+        let state = Arc::new(WsState {
+            options: LaunchOptions::default(),
+            max_connections: 1,
+            allowed_origins: Vec::new(),
+            connections: AtomicUsize::new(0),
+        });
+        let slot = ConnectionSlot::acquire(&state).unwrap();
+        assert!(ConnectionSlot::acquire(&state).is_none());
+        drop(slot);
+        assert!(ConnectionSlot::acquire(&state).is_some());
+    }
+
+    #[test]
+    fn test_browser_error() {
+        let error =
+            RpcError::from(BrowserError::ElementNotFound("#go".to_string()).with_selector("#go"));
+        let message = response(json!(1), Err(error));
+        assert_eq!(message["error"]["code"], BROWSER_ERROR);
+        assert_eq!(message["error"]["data"]["code"], "ELEMENT_NOT_FOUND");
+        assert_eq!(message["error"]["data"]["retryable"], true);
+        assert_eq!(message["error"]["data"]["selector"], "#go");
+    }
+
+    #[test]
+    fn test_tools_list_describes_tools() {
+        let mock = MockSession::new(DomTree::new(AriaNode::fragment()));
+        let result = dispatch(&mock.session(), "tools/list", Value::Null).unwrap();
+        let tools = result["tools"].as_array().unwrap();
+        assert!(tools.iter().any(|tool| tool["name"] == "navigate"));
+        for tool in tools {
+            assert!(
+                tool["description"].as_str().is_some_and(|d| !d.is_empty()),
+                "{} has no description",
+                tool["name"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_page_event_notification() {
+        let event = PageEvent::ConsoleError {
+            message: "boom".to_string(),
+        };
+        let message = notification("page/event", &event);
+        assert_eq!(message["method"], "page/event");
+        assert_eq!(message["params"]["type"], "consoleError");
+        assert!(message.get("id").is_none());
+    }
+}
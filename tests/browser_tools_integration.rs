@@ -1,8 +1,22 @@
+use browser_use::browser::ConfirmationPolicy;
+use browser_use::browser::screenshot_job::ScreenshotJobOptions;
 use browser_use::tools::{
-    HoverParams, ScrollParams, SelectParams, Tool, ToolContext, hover::HoverTool,
-    scroll::ScrollTool, select::SelectTool,
+    AuditAccessibilityParams, CheckFingerprintParams, ClickParams, CrawlParams,
+    CustomToolDefinition, DiscoverApiEndpointsParams, DismissOverlaysParams, ExtractImagesParams,
+    ExtractTableAllPagesParams, GetFrameTreeParams, GetGraphqlRequestsParams, GetHtmlParams,
+    GetTabOrderParams, GetTextParams, GoNextPageParams, HoverParams, ScrollParams,
+    ScrollToLoadParams, SelectParams, SetDateParams, SetScopeParams, Tool, ToolContext,
+    ToolRegistry, WaitCondition, WaitParams, audit_accessibility::AuditAccessibilityTool,
+    check_fingerprint::CheckFingerprintTool, click::ClickTool, crawl::CrawlTool,
+    discover_api_endpoints::DiscoverApiEndpointsTool, dismiss_overlays::DismissOverlaysTool,
+    extract_images::ExtractImagesTool, extract_table_all_pages::ExtractTableAllPagesTool,
+    get_frame_tree::GetFrameTreeTool, get_graphql_requests::GetGraphqlRequestsTool,
+    get_html::GetHtmlTool, get_tab_order::GetTabOrderTool, get_text::GetTextTool,
+    go_next_page::GoNextPageTool, hover::HoverTool, scroll::ScrollTool,
+    scroll_to_load::ScrollToLoadTool, select::SelectTool, set_date::SetDateTool,
+    set_scope::SetScopeTool, wait::WaitTool,
 };
-use browser_use::{BrowserSession, LaunchOptions};
+use browser_use::{BrowserError, BrowserSession, LaunchOptions};
 use log::info;
 
 #[test]
@@ -37,7 +51,7 @@ fn test_select_tool() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create tool and context
-    let tool = SelectTool::default();
+    let tool = SelectTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool to select an option
@@ -94,7 +108,7 @@ fn test_hover_tool() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create tool and context
-    let tool = HoverTool::default();
+    let tool = HoverTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool
@@ -145,12 +159,18 @@ fn test_scroll_tool_with_amount() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create tool and context
-    let tool = ScrollTool::default();
+    let tool = ScrollTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool to scroll down 500 pixels
     let result = tool
-        .execute_typed(ScrollParams { amount: Some(500) }, &mut context)
+        .execute_typed(
+            ScrollParams {
+                amount: Some(500),
+                ..Default::default()
+            },
+            &mut context,
+        )
         .expect("Failed to execute scroll tool");
 
     // Verify the result
@@ -170,6 +190,125 @@ fn test_scroll_tool_with_amount() {
     );
 }
 
+#[test]
+#[ignore]
+fn test_scroll_tool_nested_container() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    // The window cannot scroll; results live in an inner scroll pane
+    let html = r#"
+        <!DOCTYPE html>
+        <html>
+        <body style="margin: 0; height: 100vh; overflow: hidden;">
+            <div id="pane" style="height: 300px; overflow-y: auto;">
+                <div id="first">First result</div>
+                <div style="height: 2000px;">Results</div>
+            </div>
+        </body>
+        </html>
+    "#;
+
+    let data_url = format!("data:text/html,{}", html);
+    session.navigate(&data_url).expect("Failed to navigate");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let tool = ScrollTool;
+    let mut context = ToolContext::new(&session);
+
+    // Scrolling by an element inside the pane moves the pane
+    let result = tool
+        .execute_typed(
+            ScrollParams {
+                amount: Some(200),
+                selector: Some("#first".to_string()),
+                ..Default::default()
+            },
+            &mut context,
+        )
+        .expect("Failed to execute scroll tool");
+
+    let data = result.data.unwrap();
+    info!(
+        "Container scroll result: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+    assert_eq!(data["scrolled"].as_i64(), Some(200));
+    assert_eq!(data["container"].as_str(), Some("div#pane"));
+
+    // Without a target the pane is picked because the window cannot scroll
+    let result = tool
+        .execute_typed(ScrollParams::default(), &mut context)
+        .expect("Failed to execute scroll tool");
+
+    let data = result.data.unwrap();
+    assert!(data["scrolled"].as_i64().unwrap_or(0) > 0);
+    assert_eq!(data["container"].as_str(), Some("div#pane"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_scroll_to_load_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    // A feed that appends ten items whenever the bottom is reached, three times
+    let html = r#"
+        <!DOCTYPE html>
+        <html>
+        <body>
+            <ul id="feed"></ul>
+            <script>
+                const feed = document.getElementById('feed');
+                let pages = 0;
+                function loadMore() {
+                    for (let i = 0; i < 10; i++) {
+                        const li = document.createElement('li');
+                        li.style.height = '100px';
+                        li.textContent = 'Item ' + feed.children.length;
+                        feed.appendChild(li);
+                    }
+                    pages++;
+                }
+                loadMore();
+                window.addEventListener('scroll', () => {
+                    const bottom = window.innerHeight + window.scrollY >= document.body.scrollHeight - 5;
+                    if (bottom && pages < 4) setTimeout(loadMore, 200);
+                });
+            </script>
+        </body>
+        </html>
+    "#;
+
+    let data_url = format!("data:text/html,{}", html);
+    session.navigate(&data_url).expect("Failed to navigate");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let tool = ScrollToLoadTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(
+            ScrollToLoadParams {
+                wait_ms: Some(1000),
+                ..Default::default()
+            },
+            &mut context,
+        )
+        .expect("Failed to execute scroll_to_load tool");
+
+    let data = result.data.unwrap();
+    info!(
+        "Scroll to load result: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+    assert_eq!(data["itemsBefore"].as_u64(), Some(10));
+    assert_eq!(data["newItems"].as_u64(), Some(30));
+    assert_eq!(data["stopReason"].as_str(), Some("no_new_content"));
+}
+
 #[test]
 #[ignore]
 fn test_scroll_tool_to_bottom() {
@@ -193,13 +332,13 @@ fn test_scroll_tool_to_bottom() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create tool and context
-    let tool = ScrollTool::default();
+    let tool = ScrollTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool multiple times to reach bottom
     for _ in 0..10 {
         let result = tool
-            .execute_typed(ScrollParams { amount: None }, &mut context)
+            .execute_typed(ScrollParams::default(), &mut context)
             .expect("Failed to execute scroll tool");
 
         assert!(result.success);
@@ -250,7 +389,7 @@ fn test_select_with_index() {
     let _dom = session.extract_dom().expect("Failed to extract DOM");
 
     // Create tool and context
-    let tool = SelectTool::default();
+    let tool = SelectTool;
     let mut context = ToolContext::new(&session);
 
     // Try to select using index (the select element should have index 0 since it's the first interactive element)
@@ -274,3 +413,964 @@ fn test_select_with_index() {
         info!("Select with index failed (may be expected if select not indexed)");
     }
 }
+
+#[test]
+#[ignore]
+fn test_check_fingerprint_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    session
+        .navigate("data:text/html,<h1>Fingerprint</h1>")
+        .expect("Failed to navigate");
+
+    let tool = CheckFingerprintTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(CheckFingerprintParams {}, &mut context)
+        .expect("Failed to execute check_fingerprint tool");
+
+    assert!(result.success);
+    let data = result.data.unwrap();
+    info!(
+        "Fingerprint result: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+
+    let signals = data["signals"]
+        .as_array()
+        .expect("signals should be an array");
+    assert!(signals.iter().any(|s| s["name"] == "webdriver"));
+    // The launcher disables AutomationControlled, so webdriver should not be exposed
+    let webdriver = signals.iter().find(|s| s["name"] == "webdriver").unwrap();
+    assert_eq!(webdriver["exposed"].as_bool(), Some(false));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_crawl_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let tool = CrawlTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(
+            CrawlParams {
+                url: "data:text/html,<title>Crawl</title><a href='https://example.com'>x</a>"
+                    .to_string(),
+                max_pages: 5,
+                max_depth: 0,
+                concurrency: 2,
+                follow_external: false,
+            },
+            &mut context,
+        )
+        .expect("Failed to execute crawl tool");
+
+    assert!(result.success);
+    let data = result.data.unwrap();
+    info!(
+        "Crawl result: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+
+    // Depth 0 visits only the seed page
+    assert_eq!(data["count"].as_u64(), Some(1));
+    let page = &data["pages"][0];
+    assert_eq!(page["title"].as_str(), Some("Crawl"));
+    assert_eq!(page["links"][0].as_str(), Some("https://example.com/"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_confirm_url_covers_crawl_and_screenshot_urls() {
+    let policy = ConfirmationPolicy::new().url_pattern("checkout").unwrap();
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true).confirmation(policy))
+        .expect("Failed to launch browser");
+    let url = "data:text/html,<title>Checkout</title>";
+
+    let mut context = ToolContext::new(&session);
+    let result = CrawlTool
+        .execute_typed(
+            CrawlParams {
+                url: url.to_string(),
+                max_pages: 1,
+                max_depth: 0,
+                concurrency: 1,
+                follow_external: false,
+            },
+            &mut context,
+        )
+        .expect("Failed to execute crawl tool");
+    let data = result.data.unwrap();
+    assert_eq!(data["failed"].as_u64(), Some(1));
+    let error = data["pages"][0]["error"].as_str().unwrap();
+    assert!(error.contains("requires human confirmation"), "{}", error);
+
+    let dir = std::env::temp_dir().join("browser-use-confirm-url");
+    let results = session
+        .screenshot_urls([url], &ScreenshotJobOptions::new().output_dir(&dir))
+        .expect("Failed to run screenshot job");
+    let error = results[0].error.as_deref().unwrap();
+    assert!(error.contains("requires human confirmation"), "{}", error);
+    assert!(results[0].path.is_none());
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_extract_images_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"
+        <html><body>
+            <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=" alt="Pixel" width="10" height="10">
+            <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=" style="display:none">
+            <img data-src="/real.png" loading="lazy">
+        </body></html>
+    "#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+
+    let tool = ExtractImagesTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(
+            ExtractImagesParams {
+                visible_only: false,
+            },
+            &mut context,
+        )
+        .expect("Failed to execute extract_images tool");
+
+    assert!(result.success);
+    let data = result.data.unwrap();
+    info!("Images: {}", serde_json::to_string_pretty(&data).unwrap());
+
+    assert_eq!(data["count"].as_u64(), Some(3));
+    assert_eq!(data["missingAlt"].as_u64(), Some(2));
+    let images = data["images"].as_array().unwrap();
+    assert_eq!(images[0]["alt"].as_str(), Some("Pixel"));
+    assert_eq!(images[1]["visible"].as_bool(), Some(false));
+    assert_eq!(images[2]["lazy"].as_bool(), Some(true));
+    assert_eq!(images[2]["lazySrc"].as_str(), Some("/real.png"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_get_tab_order_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"
+        <a id="home" href="/">Home</a>
+        <button id="later">Later</button>
+        <input id="first" tabindex="2" aria-label="First">
+        <button disabled>Disabled</button>
+        <div id="skip" tabindex="-1">Skipped</div>
+        <input type="radio" name="size" value="s">
+        <input type="radio" name="size" value="m" checked>
+        <button style="display: none">Hidden</button>
+    "#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+
+    let tool = GetTabOrderTool;
+    let mut context = ToolContext::new(&session);
+    let result = tool
+        .execute_typed(
+            GetTabOrderParams {
+                include_skipped: true,
+            },
+            &mut context,
+        )
+        .expect("Failed to execute get_tab_order tool");
+
+    assert!(result.success);
+    let data = result.data.unwrap();
+    info!(
+        "Tab order: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+
+    let selectors: Vec<&str> = data["stops"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|stop| stop["selector"].as_str().unwrap())
+        .collect();
+    assert_eq!(selectors[..3], ["#first", "#home", "#later"]);
+    assert_eq!(data["count"].as_u64(), Some(4));
+    assert_eq!(data["positiveTabindex"].as_u64(), Some(1));
+    assert_eq!(data["stops"][0]["position"].as_u64(), Some(1));
+    assert!(data["stops"][1]["index"].is_u64());
+
+    let reasons: Vec<&str> = data["skipped"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|stop| stop["reason"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        reasons,
+        ["disabled", "tabindex=-1", "radio group", "hidden"]
+    );
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_set_scope_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let tool = SetScopeTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(
+            SetScopeParams {
+                origins: vec!["https://example.com".to_string()],
+            },
+            &mut context,
+        )
+        .expect("Failed to execute set_scope tool");
+    assert!(result.success);
+    info!("Scope: {:?}", result.data);
+
+    // Navigation outside the scope is refused
+    assert!(matches!(
+        session.navigate("https://example.org/"),
+        Err(BrowserError::PolicyDenied(_))
+    ));
+
+    // The scope cannot be widened afterwards
+    let widened = tool.execute_typed(
+        SetScopeParams {
+            origins: vec!["*.example.com".to_string()],
+        },
+        &mut context,
+    );
+    assert!(widened.is_err());
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_go_next_page_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    // Client-side pagination: the "Next ›" button swaps the results in place
+    let html = r#"
+        <!DOCTYPE html>
+        <html>
+        <body>
+            <ul id="results"><li>Page 1 result</li></ul>
+            <nav>
+                <button class="prev disabled" aria-disabled="true">‹ Prev</button>
+                <button id="next">Next ›</button>
+            </nav>
+            <script>
+                let page = 1;
+                document.getElementById('next').addEventListener('click', () => {
+                    page++;
+                    document.getElementById('results').innerHTML =
+                        '<li>Page ' + page + ' result</li><li>Another result</li>';
+                });
+            </script>
+        </body>
+        </html>
+    "#;
+
+    let data_url = format!("data:text/html,{}", html);
+    session.navigate(&data_url).expect("Failed to navigate");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let tool = GoNextPageTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(GoNextPageParams::default(), &mut context)
+        .expect("Failed to execute go_next_page tool");
+
+    let data = result.data.unwrap();
+    info!(
+        "Next page result: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+    assert_eq!(data["found"].as_bool(), Some(true));
+    assert_eq!(data["loaded"].as_bool(), Some(true));
+    assert_eq!(data["change"].as_str(), Some("content"));
+    assert_eq!(data["rule"].as_str(), Some("text"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_extract_table_all_pages_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    // Three pages of two rows; the last row of page 2 repeats on page 3
+    let html = r#"
+        <!DOCTYPE html>
+        <html>
+        <body>
+            <table id="prices">
+                <thead><tr><th>Name</th><th>Price</th></tr></thead>
+                <tbody></tbody>
+            </table>
+            <button id="next">Next</button>
+            <script>
+                const pages = [
+                    [['Apple', '1'], ['Pear', '2']],
+                    [['Plum', '3'], ['Fig', '4']],
+                    [['Fig', '4'], ['Kiwi', '5']],
+                ];
+                let page = 0;
+                function render() {
+                    document.getElementById('prices').tBodies[0].innerHTML = pages[page]
+                        .map(r => '<tr><td>' + r[0] + '</td><td>' + r[1] + '</td></tr>')
+                        .join('');
+                    if (page === pages.length - 1) document.getElementById('next').remove();
+                }
+                render();
+                document.getElementById('next').addEventListener('click', () => {
+                    page++;
+                    render();
+                });
+            </script>
+        </body>
+        </html>
+    "#;
+
+    let data_url = format!("data:text/html,{}", html);
+    session.navigate(&data_url).expect("Failed to navigate");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let tool = ExtractTableAllPagesTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(
+            ExtractTableAllPagesParams {
+                dedup: true,
+                ..Default::default()
+            },
+            &mut context,
+        )
+        .expect("Failed to execute extract_table_all_pages tool");
+
+    let data = result.data.unwrap();
+    info!(
+        "Table result: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+    assert_eq!(data["headers"], serde_json::json!(["Name", "Price"]));
+    assert_eq!(data["pages"].as_u64(), Some(3));
+    assert_eq!(data["rowCount"].as_u64(), Some(5));
+    assert_eq!(data["duplicatesRemoved"].as_u64(), Some(1));
+    assert_eq!(data["stopReason"].as_str(), Some("last_page"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_set_date_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    // A native date input and a minimal calendar widget on a text field
+    let html = r#"
+        <!DOCTYPE html>
+        <html>
+        <body>
+            <input type="date" id="native">
+            <input type="text" id="picker" readonly>
+            <div id="calendar" role="dialog" style="display: none;">
+                <button class="prev">Prev</button>
+                <span id="header"></span>
+                <button class="next">Next</button>
+                <table role="grid"><tbody id="days"></tbody></table>
+            </div>
+            <script>
+                const names = ['January', 'February', 'March', 'April', 'May', 'June', 'July',
+                    'August', 'September', 'October', 'November', 'December'];
+                let shown = new Date(2025, 0, 1);
+                const picker = document.getElementById('picker');
+                const calendar = document.getElementById('calendar');
+                function render() {
+                    document.getElementById('header').textContent =
+                        names[shown.getMonth()] + ' ' + shown.getFullYear();
+                    const days = new Date(shown.getFullYear(), shown.getMonth() + 1, 0).getDate();
+                    let html = '<tr>';
+                    for (let d = 1; d <= days; d++) html += '<td>' + d + '</td>';
+                    document.getElementById('days').innerHTML = html + '</tr>';
+                }
+                picker.addEventListener('click', () => { calendar.style.display = 'block'; render(); });
+                calendar.querySelector('.next').addEventListener('click', () => {
+                    shown = new Date(shown.getFullYear(), shown.getMonth() + 1, 1); render();
+                });
+                calendar.querySelector('.prev').addEventListener('click', () => {
+                    shown = new Date(shown.getFullYear(), shown.getMonth() - 1, 1); render();
+                });
+                document.getElementById('days').addEventListener('click', e => {
+                    const m = String(shown.getMonth() + 1).padStart(2, '0');
+                    const d = String(e.target.textContent).padStart(2, '0');
+                    picker.value = shown.getFullYear() + '-' + m + '-' + d;
+                    calendar.style.display = 'none';
+                });
+            </script>
+        </body>
+        </html>
+    "#;
+
+    let data_url = format!("data:text/html,{}", urlencoding::encode(html));
+    session.navigate(&data_url).expect("Failed to navigate");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let tool = SetDateTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(
+            SetDateParams {
+                selector: Some("#native".to_string()),
+                index: None,
+                date: "2025-03-14".to_string(),
+            },
+            &mut context,
+        )
+        .expect("Failed to set native date");
+    let data = result.data.unwrap();
+    assert_eq!(data["method"].as_str(), Some("native"));
+    assert_eq!(data["value"].as_str(), Some("2025-03-14"));
+
+    let result = tool
+        .execute_typed(
+            SetDateParams {
+                selector: Some("#picker".to_string()),
+                index: None,
+                date: "2025-04-09".to_string(),
+            },
+            &mut context,
+        )
+        .expect("Failed to set date through the widget");
+    let data = result.data.unwrap();
+    info!(
+        "Widget date result: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+    assert_eq!(data["method"].as_str(), Some("widget"));
+    assert_eq!(data["value"].as_str(), Some("2025-04-09"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_get_graphql_requests_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    session
+        .navigate("https://countries.trevorblades.com/")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+
+    let tool = GetGraphqlRequestsTool;
+    let mut context = ToolContext::new(&session);
+
+    // The first call starts capture
+    let result = tool
+        .execute_typed(GetGraphqlRequestsParams::default(), &mut context)
+        .expect("Failed to execute get_graphql_requests tool");
+    assert!(result.success);
+
+    session
+        .tab()
+        .unwrap()
+        .evaluate(
+            r#"fetch("/graphql", {
+                method: "POST",
+                headers: { "Content-Type": "application/json" },
+                body: JSON.stringify({
+                    operationName: "Country",
+                    query: "query Country($code: ID!) { country(code: $code) { name capital } }",
+                    variables: { code: "FR" }
+                })
+            }).then(r => r.json())"#,
+            true,
+        )
+        .expect("Failed to send GraphQL request");
+
+    let mut context = ToolContext::new(&session);
+    let result = tool
+        .execute_typed(
+            GetGraphqlRequestsParams {
+                operation_name: Some("country".to_string()),
+                ..Default::default()
+            },
+            &mut context,
+        )
+        .expect("Failed to execute get_graphql_requests tool");
+
+    let data = result.data.unwrap();
+    info!(
+        "GraphQL requests: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+    assert_eq!(data["count"].as_u64(), Some(1));
+    let operation = &data["operations"][0];
+    assert_eq!(operation["operationType"].as_str(), Some("query"));
+    assert_eq!(operation["variables"]["code"].as_str(), Some("FR"));
+    assert_eq!(
+        operation["response"]["shape"]["country"]["name"].as_str(),
+        Some("string")
+    );
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_discover_api_endpoints_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    session
+        .navigate("https://httpbin.org/")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+
+    let tool = DiscoverApiEndpointsTool;
+    let mut context = ToolContext::new(&session);
+
+    // The first call starts capture
+    let result = tool
+        .execute_typed(DiscoverApiEndpointsParams::default(), &mut context)
+        .expect("Failed to execute discover_api_endpoints tool");
+    assert!(result.success);
+
+    session
+        .tab()
+        .unwrap()
+        .evaluate(
+            r#"Promise.all([
+                fetch("/anything/items/1?fields=name"),
+                fetch("/anything/items/2"),
+                fetch("/anything/items", { method: "POST", body: JSON.stringify({ name: "x" }) })
+            ]).then(() => true)"#,
+            true,
+        )
+        .expect("Failed to send requests");
+
+    let mut context = ToolContext::new(&session);
+    let result = tool
+        .execute_typed(
+            DiscoverApiEndpointsParams {
+                url_contains: Some("/anything/".to_string()),
+                ..Default::default()
+            },
+            &mut context,
+        )
+        .expect("Failed to execute discover_api_endpoints tool");
+
+    let data = result.data.unwrap();
+    info!(
+        "API endpoints: {}",
+        serde_json::to_string_pretty(&data).unwrap()
+    );
+    assert_eq!(data["count"].as_u64(), Some(2));
+    let endpoints = data["endpoints"].as_array().unwrap();
+    let get = endpoints
+        .iter()
+        .find(|endpoint| endpoint["method"] == "GET")
+        .unwrap();
+    assert_eq!(
+        get["urlTemplate"].as_str(),
+        Some("https://httpbin.org/anything/items/{id}")
+    );
+    assert_eq!(get["count"].as_u64(), Some(2));
+    let post = endpoints
+        .iter()
+        .find(|endpoint| endpoint["method"] == "POST")
+        .unwrap();
+    assert_eq!(post["exampleBody"]["name"].as_str(), Some("x"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_define_custom_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    let html = "<html><body><ul><li>Milk</li><li>Eggs</li></ul></body></html>";
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+
+    let mut registry = ToolRegistry::new();
+    registry
+        .define_tool(CustomToolDefinition {
+            name: "count_items".to_string(),
+            description: "Count elements matching a selector".to_string(),
+            parameters: Some(serde_json::json!({
+                "properties": { "selector": { "type": "string" } },
+                "required": ["selector"]
+            })),
+            script: "return document.querySelectorAll({{selector}}).length;".to_string(),
+        })
+        .expect("Failed to define tool");
+
+    let mut context = ToolContext::new(&session);
+    let result = registry
+        .execute(
+            "count_items",
+            serde_json::json!({ "selector": "li" }),
+            &mut context,
+        )
+        .expect("Failed to execute custom tool");
+    info!("Custom tool result: {:?}", result.data);
+    assert_eq!(result.data.unwrap()["result"], 2);
+
+    let missing = registry.execute("count_items", serde_json::json!({}), &mut context);
+    assert!(matches!(
+        missing.unwrap_err().inner(),
+        BrowserError::InvalidArgument(_)
+    ));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_get_frame_tree_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    let html = r#"<html><body>
+        <iframe name="checkout" srcdoc="<iframe name='card' src='about:blank'></iframe>"></iframe>
+        <iframe src="about:blank"></iframe>
+    </body></html>"#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(300));
+
+    let mut context = ToolContext::new(&session);
+    let result = GetFrameTreeTool
+        .execute_typed(GetFrameTreeParams::default(), &mut context)
+        .expect("Failed to execute get_frame_tree tool");
+    let data = result.data.unwrap();
+    info!("Frame tree: {}", data["summary"]);
+
+    assert_eq!(data["count"], 4);
+    let frames = &data["frames"];
+    assert_eq!(frames["depth"], 0);
+    assert_eq!(frames["children"][0]["name"], "checkout");
+    assert_eq!(frames["children"][0]["children"][0]["name"], "card");
+    assert!(frames["children"][1].get("name").is_none());
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_injected_helpers_survive_navigation() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    let page = |width: u32| {
+        let html = format!(
+            r#"<html><body><div id="box" style="width: {}px; height: 10px"></div></body></html>"#,
+            width
+        );
+        format!("data:text/html,{}", urlencoding::encode(&html))
+    };
+    let box_width = || {
+        session
+            .call_helper::<serde_json::Value>("getBox", serde_json::json!({ "selector": ["#box"] }))
+            .expect("Failed to call getBox")["width"]
+            .as_f64()
+    };
+
+    session.navigate(&page(40)).expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+    assert_eq!(box_width(), Some(40.0));
+
+    // Page scripts cannot see the helpers
+    let visible = session
+        .tab()
+        .unwrap()
+        .evaluate("typeof globalThis.__browserUse", false)
+        .unwrap()
+        .value;
+    assert_eq!(visible, Some(serde_json::json!("undefined")));
+
+    // A new document gets a fresh helper world
+    session.navigate(&page(80)).expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+    assert_eq!(box_width(), Some(80.0));
+
+    let missing = session
+        .call_helper::<serde_json::Value>("getBox", serde_json::json!({ "selector": ["#nope"] }));
+    assert!(matches!(missing, Err(BrowserError::ElementNotFound(_))));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_click_reports_effects() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    let html = r#"<html><body>
+        <button id="noop">Nothing</button>
+        <button id="add" onclick="document.body.append(document.createElement('p'))">Add</button>
+        <button id="hash" onclick="location.hash = 'next'">Hash</button>
+    </body></html>"#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+
+    let mut context = ToolContext::new(&session);
+    let mut click = |selector: &str| {
+        let params = ClickParams {
+            selector: Some(selector.to_string()),
+            index: None,
+            observe_ms: 300,
+        };
+        ClickTool
+            .execute_typed(params, &mut context)
+            .expect("Failed to click")
+            .data
+            .unwrap()["effects"]
+            .clone()
+    };
+
+    assert_eq!(click("#noop")["effects"], serde_json::json!([]));
+    assert_eq!(
+        click("#add")["effects"],
+        serde_json::json!(["dom_mutation"])
+    );
+    assert_eq!(click("#hash")["effects"][0], "url_change");
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_dismiss_overlays_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    let html = r#"<html><body style="overflow: hidden">
+        <div id="cookie-banner" style="position: fixed; bottom: 0; left: 0; right: 0; z-index: 10">
+            We use cookies.
+            <button onclick="document.title = 'rejected'; this.parentElement.remove()">Reject all</button>
+            <button onclick="document.title = 'accepted'; this.parentElement.remove()">Accept all</button>
+        </div>
+        <div id="paywall" style="position: fixed; inset: 0; z-index: 5; background: rgba(0,0,0,0.5)"></div>
+        <p>Article</p>
+    </body></html>"#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+
+    let mut context = ToolContext::new(&session);
+    let result = DismissOverlaysTool
+        .execute_typed(DismissOverlaysParams::default(), &mut context)
+        .expect("Failed to dismiss overlays");
+    let data = result.data.unwrap();
+    info!("Dismissed: {}", data);
+    assert_eq!(data["count"], 2);
+    assert_eq!(data["dismissed"][0]["rule"], "consent-banner");
+    assert_eq!(data["dismissed"][1]["rule"], "fullscreen-overlay");
+
+    let title = session.tab().unwrap().get_title().unwrap();
+    assert_eq!(title, "accepted");
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_wait_for_stable_element() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    let html = r#"<html><body>
+        <div id="drawer" style="position: absolute; left: -300px; width: 200px; height: 50px;
+            transition: left 800ms linear">Menu</div>
+        <script>requestAnimationFrame(() => requestAnimationFrame(() => {
+            document.getElementById('drawer').style.left = '0px';
+        }));</script>
+    </body></html>"#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+
+    let mut context = ToolContext::new(&session);
+    let params = WaitParams {
+        selector: "#drawer".to_string(),
+        timeout_ms: 5000,
+        condition: WaitCondition::Stable,
+        stable_frames: 5,
+    };
+    let data = WaitTool
+        .execute_typed(params, &mut context)
+        .expect("Element never settled")
+        .data
+        .unwrap();
+    assert_eq!(data["stable"], true);
+    assert_eq!(data["box"]["x"], 0.0);
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_audit_accessibility_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"
+        <h1>Shop</h1>
+        <h3>Offers</h3>
+        <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=" width="10" height="10">
+        <input id="email" placeholder="Email">
+        <p style="color: #bbb; background: #fff">Faint text</p>
+        <div aria-hidden="true"><a href="/hidden">Hidden link</a></div>
+        <button aria-describedby="nowhere">Buy</button>
+    "#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+
+    let tool = AuditAccessibilityTool;
+    let mut context = ToolContext::new(&session);
+    let result = tool
+        .execute_typed(AuditAccessibilityParams::default(), &mut context)
+        .expect("Failed to execute audit_accessibility tool");
+
+    assert!(result.success);
+    let data = result.data.unwrap();
+    info!("Audit: {}", serde_json::to_string_pretty(&data).unwrap());
+
+    for rule in [
+        "missing-alt",
+        "unlabeled-control",
+        "low-contrast",
+        "aria-hidden-focusable",
+        "broken-aria-reference",
+        "heading-order",
+    ] {
+        assert_eq!(data["byRule"][rule].as_u64(), Some(1), "{}", rule);
+    }
+    assert_eq!(data["truncated"].as_bool(), Some(false));
+
+    // Unknown rules are refused
+    let result = tool
+        .execute_typed(
+            AuditAccessibilityParams {
+                rules: Some(vec!["colour".to_string()]),
+                ..Default::default()
+            },
+            &mut context,
+        )
+        .expect("Failed to execute audit_accessibility tool");
+    assert!(!result.success);
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_get_html_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"<!DOCTYPE html>
+        <html><head><style>p { color: red }</style></head><body>
+            <!-- note -->
+            <div id="card" style="margin: 0"><p onclick="buy()">Price</p><script>var x = 1;</script></div>
+        </body></html>"#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+
+    let tool = GetHtmlTool;
+    let mut context = ToolContext::new(&session);
+
+    let page = tool
+        .execute_typed(GetHtmlParams::default(), &mut context)
+        .expect("Failed to execute get_html tool")
+        .data
+        .unwrap();
+    let markup = page["html"].as_str().unwrap();
+    assert!(markup.starts_with("<!DOCTYPE html>"));
+    assert!(markup.contains("<!-- note -->"));
+    assert_eq!(page["truncated"].as_bool(), Some(false));
+
+    let card = tool
+        .execute_typed(
+            GetHtmlParams {
+                selector: Some("#card".to_string()),
+                strip_scripts: true,
+                strip_styles: true,
+                strip_comments: true,
+                ..Default::default()
+            },
+            &mut context,
+        )
+        .expect("Failed to execute get_html tool")
+        .data
+        .unwrap();
+    assert_eq!(
+        card["html"].as_str(),
+        Some(r#"<div id="card"><p>Price</p></div>"#)
+    );
+
+    let capped = tool
+        .execute_typed(
+            GetHtmlParams {
+                max_bytes: 10,
+                ..Default::default()
+            },
+            &mut context,
+        )
+        .expect("Failed to execute get_html tool")
+        .data
+        .unwrap();
+    assert_eq!(capped["html"].as_str().unwrap().len(), 10);
+    assert_eq!(capped["truncated"].as_bool(), Some(true));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_get_text_tool() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"
+        <h1>Pricing</h1>
+        <p>Read the <a href="/docs">documentation</a> first.</p>
+        <p style="display: none">Hidden</p>
+        <button id="buy"><span>Buy now</span></button>
+    "#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+
+    let tool = GetTextTool;
+    let mut context = ToolContext::new(&session);
+    let result = tool
+        .execute_typed(GetTextParams::default(), &mut context)
+        .expect("Failed to execute get_text tool");
+    assert!(result.success);
+    let data = result.data.unwrap();
+    info!("Text blocks: {}", data["blocks"]);
+
+    let blocks = data["blocks"].as_array().unwrap();
+    let texts: Vec<&str> = blocks.iter().map(|b| b["text"].as_str().unwrap()).collect();
+    assert_eq!(
+        texts,
+        ["Pricing", "Read the", "documentation", "first.", "Buy now"]
+    );
+    assert!(blocks[0].get("index").is_none());
+    assert!(blocks[2]["index"].is_u64());
+    assert!(blocks[4]["index"].is_u64());
+    assert_ne!(blocks[2]["index"], blocks[4]["index"]);
+}
@@ -1,3 +1,4 @@
+use browser_use::browser::Viewport;
 use browser_use::{BrowserSession, LaunchOptions};
 use log::info;
 
@@ -77,11 +78,11 @@ fn test_read_links() {
 
     std::thread::sleep(std::time::Duration::from_millis(500));
 
-    let tool = ReadLinksTool::default();
+    let tool = ReadLinksTool;
     let mut context = ToolContext::new(&session);
 
     let result = tool
-        .execute_typed(ReadLinksParams {}, &mut context)
+        .execute_typed(ReadLinksParams { unique: false }, &mut context)
         .expect("Failed execute");
 
     assert!(result.success);
@@ -160,7 +161,7 @@ fn test_press_key_enter() {
     std::thread::sleep(std::time::Duration::from_millis(200));
 
     // Create tool and context
-    let tool = PressKeyTool::default();
+    let tool = PressKeyTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool to press Enter
@@ -202,3 +203,385 @@ fn test_press_key_enter() {
     // Note: Due to limitations with data: URLs and event handling,
     // we mainly verify that the tool executes without error
 }
+
+#[test]
+#[ignore]
+fn test_key_sequence_palette() {
+    use browser_use::tools::{
+        KeySequenceParams, KeyStep, Tool, ToolContext, key_sequence::KeySequenceTool,
+    };
+
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    // "g" then "i" opens the palette, which focuses its search box
+    let html = r#"
+        <html>
+        <body>
+            <input type="text" id="palette" style="display: none;">
+            <script>
+                let last = '';
+                document.addEventListener('keydown', function(e) {
+                    if (e.target.id === 'palette') return;
+                    if (last === 'g' && e.key === 'i') {
+                        const palette = document.getElementById('palette');
+                        palette.style.display = 'block';
+                        palette.focus();
+                        e.preventDefault();
+                    }
+                    last = e.key;
+                });
+            </script>
+        </body>
+        </html>
+    "#;
+
+    session
+        .navigate(&format!("data:text/html,{}", html))
+        .expect("Failed to navigate");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let tool = KeySequenceTool;
+    let mut context = ToolContext::new(&session);
+
+    let result = tool
+        .execute_typed(
+            KeySequenceParams {
+                steps: vec![
+                    KeyStep::Keys("g".to_string()),
+                    KeyStep::Keys("i".to_string()),
+                    KeyStep::Text {
+                        text: "inbox".to_string(),
+                    },
+                ],
+                delay_ms: Some(50),
+            },
+            &mut context,
+        )
+        .expect("Failed to execute key_sequence tool");
+
+    assert!(result.success);
+    assert_eq!(result.data.unwrap()["count"].as_u64(), Some(3));
+
+    let value = session
+        .tab()
+        .unwrap()
+        .evaluate("document.getElementById('palette').value", false)
+        .ok()
+        .and_then(|result| result.value);
+    info!("Palette value after key sequence: {:?}", value);
+    assert_eq!(value, Some(serde_json::json!("inbox")));
+}
+
+#[test]
+#[ignore]
+fn test_input_unicode_text() {
+    use browser_use::tools::{InputParams, Tool, ToolContext, input::InputTool};
+
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"
+        <html>
+        <body>
+            <input type="text" id="name">
+        </body>
+        </html>
+    "#;
+
+    session
+        .navigate(&format!("data:text/html,{}", html))
+        .expect("Failed to navigate");
+
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let text = "Zoë 山田 👨‍👩‍👧 ok";
+    let tool = InputTool;
+    let mut context = ToolContext::new(&session);
+    tool.execute_typed(
+        InputParams {
+            selector: Some("#name".to_string()),
+            index: None,
+            text: text.to_string(),
+            clear: false,
+        },
+        &mut context,
+    )
+    .expect("Failed to execute input tool");
+
+    let value = session
+        .tab()
+        .unwrap()
+        .evaluate("document.getElementById('name').value", false)
+        .ok()
+        .and_then(|result| result.value);
+    info!("Input value: {:?}", value);
+    assert_eq!(value, Some(serde_json::json!(text)));
+}
+
+#[test]
+#[ignore]
+fn test_deep_selector_across_frame_and_shadow_root() {
+    use browser_use::tools::{
+        ClickParams, InputParams, Tool, ToolContext, WaitCondition, WaitParams, click::ClickTool,
+        input::InputTool, wait::WaitTool,
+    };
+
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let frame = r#"
+        <pay-widget></pay-widget>
+        <script>
+            customElements.define('pay-widget', class extends HTMLElement {
+                connectedCallback() {
+                    const root = this.attachShadow({ mode: 'open' });
+                    root.innerHTML = '<input id="card"><button class="pay">Pay</button>';
+                    root.querySelector('.pay').onclick = () => { window.parent.paid = true; };
+                }
+            });
+        </script>
+    "#;
+    let html = format!(
+        r#"<html><body><iframe id="checkout" srcdoc="{}"></iframe></body></html>"#,
+        frame.replace('"', "&quot;")
+    );
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(&html)))
+        .expect("Failed to navigate");
+
+    let mut context = ToolContext::new(&session);
+    WaitTool
+        .execute_typed(
+            WaitParams {
+                selector: "iframe#checkout >>> pay-widget >>> button.pay".to_string(),
+                timeout_ms: 5000,
+                condition: WaitCondition::Present,
+                stable_frames: 5,
+            },
+            &mut context,
+        )
+        .expect("Deep selector never matched");
+
+    InputTool
+        .execute_typed(
+            InputParams {
+                selector: Some("#checkout >>> pay-widget >>> #card".to_string()),
+                index: None,
+                text: "4242".to_string(),
+                clear: false,
+            },
+            &mut context,
+        )
+        .expect("Failed to type through the deep selector");
+    ClickTool
+        .execute_typed(
+            ClickParams {
+                selector: Some("#checkout >>> pay-widget >>> button.pay".to_string()),
+                index: None,
+                observe_ms: 0,
+            },
+            &mut context,
+        )
+        .expect("Failed to click through the deep selector");
+
+    let paid = session
+        .tab()
+        .unwrap()
+        .evaluate("window.paid === true", false)
+        .ok()
+        .and_then(|result| result.value);
+    info!("Paid: {:?}", paid);
+    assert_eq!(paid, Some(serde_json::json!(true)));
+
+    let error = ClickTool
+        .execute_typed(
+            ClickParams {
+                selector: Some("#checkout >>> #missing".to_string()),
+                index: None,
+                observe_ms: 0,
+            },
+            &mut context,
+        )
+        .unwrap_err();
+    assert!(error.to_string().contains("inside '#checkout'"));
+}
+
+#[test]
+#[ignore]
+fn test_snapshot_masks_password_values() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"
+        <html>
+        <body>
+            <input id="user" aria-label="User" value="alice">
+            <input id="pass" type="password" aria-label="Password" value="hunter2">
+            <input id="key" name="api_key" aria-label="Key" value="sk-123">
+        </body>
+        </html>
+    "#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let dom = session.extract_dom().expect("Failed to extract DOM");
+    let json = serde_json::to_string(&dom.root).unwrap();
+    info!("Snapshot: {}", json);
+    assert!(json.contains("alice"));
+    assert!(!json.contains("hunter2"));
+    assert!(!json.contains("sk-123"));
+    assert!(json.contains("7 characters"));
+}
+
+#[test]
+#[ignore]
+fn test_snapshot_describes_unlabeled_controls() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"
+        <html>
+        <body>
+            <button><svg><title>Edit</title></svg></button>
+            <button data-testid="delete-row"><svg></svg></button>
+            <button><i class="fa-solid fa-share"></i></button>
+            <input type="search" placeholder="Search docs" disabled>
+            <select name="shipping"><option>Standard</option><option selected>Express</option></select>
+        </body>
+        </html>
+    "#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let dom = session.extract_dom().expect("Failed to extract DOM");
+    let json = serde_json::to_string(&dom.root).unwrap();
+    info!("Snapshot: {}", json);
+    assert!(json.contains(r#""name":"Edit""#));
+    assert!(json.contains("data-testid=delete-row"));
+    assert!(json.contains("icon=fa-share"));
+    assert!(json.contains("Search docs"));
+    assert!(json.contains(r#""value":"Express""#));
+    assert!(json.contains(r#""disabled":true"#));
+}
+
+#[test]
+#[ignore]
+fn test_snapshot_reports_page_metrics() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let html = r#"
+        <html>
+        <body style="margin: 0">
+            <button>Top</button>
+            <div style="height: 5000px"></div>
+            <button>Bottom</button>
+        </body>
+        </html>
+    "#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let dom = session.extract_dom().expect("Failed to extract DOM");
+    let page = dom.page.expect("No page metrics");
+    info!("Page: {}", page.summary(dom.count_interactive()));
+    assert_eq!(page.scroll_y, 0.0);
+    assert!(page.page_height > 5000.0);
+    assert!(page.remaining_below() > 0.0);
+    assert_eq!(page.offscreen_below, 1);
+}
+
+#[test]
+#[ignore]
+fn test_visual_index_order() {
+    use browser_use::dom::IndexOrder;
+
+    let options = LaunchOptions::new()
+        .headless(true)
+        .index_order(IndexOrder::Visual);
+    let session = BrowserSession::launch(options).expect("Failed to launch browser");
+
+    // Sidebar comes after the content in the DOM but is read first
+    let html = r#"
+        <html>
+        <body style="margin: 0; display: flex; flex-direction: row-reverse; justify-content: flex-end">
+            <div style="width: 400px; margin-left: 50px">
+                <button>Content one</button>
+                <button>Content two</button>
+            </div>
+            <div style="width: 200px; display: flex; flex-direction: column">
+                <button>Side one</button>
+                <button>Side two</button>
+            </div>
+        </body>
+        </html>
+    "#;
+    session
+        .navigate(&format!("data:text/html,{}", urlencoding::encode(html)))
+        .expect("Failed to navigate");
+    std::thread::sleep(std::time::Duration::from_millis(500));
+
+    let dom = session.extract_dom().expect("Failed to extract DOM");
+    let names: Vec<String> = dom
+        .interactive_indices()
+        .into_iter()
+        .filter_map(|index| dom.find_node_by_index(index))
+        .filter(|node| node.role == "button")
+        .map(|node| node.name.clone())
+        .collect();
+    assert_eq!(
+        names,
+        ["Side one", "Side two", "Content one", "Content two"]
+    );
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_viewport() {
+    let session = BrowserSession::launch(
+        LaunchOptions::new()
+            .headless(true)
+            .window_size(1024, 768)
+            .device_scale_factor(2.0),
+    )
+    .expect("Failed to launch browser");
+    session
+        .navigate("data:text/html,<p>Viewport</p>")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+
+    let metrics = |session: &BrowserSession| {
+        session
+            .tab()
+            .unwrap()
+            .evaluate(
+                "[innerWidth, innerHeight, devicePixelRatio].join('x')",
+                false,
+            )
+            .unwrap()
+            .value
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(metrics(&session), "1024x768x2");
+
+    session
+        .set_viewport(Viewport::new(390, 844).device_scale_factor(3.0))
+        .expect("Failed to set viewport");
+    info!("Metrics after set_viewport: {}", metrics(&session));
+    assert_eq!(metrics(&session), "390x844x3");
+
+    // Tabs opened later get the same viewport
+    session.open_tab("data:text/html,<p>Second</p>").unwrap();
+    assert_eq!(metrics(&session), "390x844x3");
+}
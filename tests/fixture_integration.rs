@@ -0,0 +1,92 @@
+//! Integration tests against the bundled fixture pages
+//!
+//! Run with `cargo test --features test-fixtures -- --ignored`.
+#![cfg(feature = "test-fixtures")]
+
+use browser_use::fixtures::FixtureServer;
+use browser_use::{BrowserSession, LaunchOptions};
+use serde_json::json;
+
+fn launch() -> BrowserSession {
+    BrowserSession::launch(LaunchOptions::new().headless(true)).expect("Failed to launch browser")
+}
+
+fn open(session: &BrowserSession, server: &FixtureServer, path: &str) {
+    session
+        .navigate(&server.url(path))
+        .and_then(|_| session.wait_for_navigation())
+        .expect("Failed to load fixture");
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_form_submission() {
+    let server = FixtureServer::start().unwrap();
+    let session = launch();
+    open(&session, &server, "/form.html");
+
+    // Served pages have an origin, so every field is visible and indexed
+    let dom = session.extract_dom().expect("Failed to extract DOM");
+    assert!(dom.count_interactive() >= 5);
+
+    session
+        .execute_tool("input", json!({ "selector": "#name", "text": "Ada" }))
+        .unwrap();
+    session
+        .execute_tool("select", json!({ "selector": "#plan", "value": "pro" }))
+        .unwrap();
+    session
+        .execute_tool("click", json!({ "selector": "#submit" }))
+        .unwrap();
+    session.wait_for_navigation().unwrap();
+
+    let url = session.current_url().unwrap();
+    assert!(url.contains("/submitted.html"));
+    assert!(url.contains("name=Ada"));
+    assert!(url.contains("plan=pro"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_iframe_content() {
+    let server = FixtureServer::start().unwrap();
+    let session = launch();
+    open(&session, &server, "/iframe.html");
+
+    let dom = session.extract_dom().expect("Failed to extract DOM");
+    assert!(!dom.get_iframe_indices().is_empty());
+    let json = dom.to_json().unwrap();
+    assert!(json.contains("Parent button"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_shadow_dom() {
+    let server = FixtureServer::start().unwrap();
+    let session = launch();
+    open(&session, &server, "/shadow_dom.html");
+
+    let dom = session.extract_dom().expect("Failed to extract DOM");
+    let json = dom.to_json().unwrap();
+    assert!(json.contains("Shadow DOM"));
+    // Open shadow roots are traversed
+    assert!(json.contains("Follow"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_infinite_scroll() {
+    let server = FixtureServer::start().unwrap();
+    let session = launch();
+    open(&session, &server, "/infinite_scroll.html");
+
+    let result = session
+        .execute_tool(
+            "scroll_to_load",
+            json!({ "item_selector": ".item", "wait_ms": 500 }),
+        )
+        .unwrap();
+    let data = result.data.unwrap();
+    assert!(data["itemsAfter"].as_u64().unwrap() > data["itemsBefore"].as_u64().unwrap());
+    assert!(data["itemsAfter"].as_u64().unwrap() <= 100);
+}
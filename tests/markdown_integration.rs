@@ -32,7 +32,7 @@ fn test_basic_markdown_extraction() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create tool and context
-    let tool = GetMarkdownTool::default();
+    let tool = GetMarkdownTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool
@@ -119,7 +119,7 @@ fn test_readability_filtering() {
 
     std::thread::sleep(std::time::Duration::from_millis(500));
 
-    let tool = GetMarkdownTool::default();
+    let tool = GetMarkdownTool;
     let mut context = ToolContext::new(&session);
 
     let result = tool
@@ -186,7 +186,7 @@ fn test_markdown_pagination() {
 
     std::thread::sleep(std::time::Duration::from_millis(1000));
 
-    let tool = GetMarkdownTool::default();
+    let tool = GetMarkdownTool;
     let mut context = ToolContext::new(&session);
 
     // Get first page with small page size
@@ -292,7 +292,7 @@ fn test_empty_page() {
 
     std::thread::sleep(std::time::Duration::from_millis(500));
 
-    let tool = GetMarkdownTool::default();
+    let tool = GetMarkdownTool;
     let mut context = ToolContext::new(&session);
 
     let result = tool.execute_typed(GetMarkdownParams::default(), &mut context);
@@ -358,7 +358,7 @@ fn test_table_conversion() {
 
     std::thread::sleep(std::time::Duration::from_millis(500));
 
-    let tool = GetMarkdownTool::default();
+    let tool = GetMarkdownTool;
     let mut context = ToolContext::new(&session);
 
     let result = tool
@@ -412,7 +412,7 @@ fn test_double_execution_same_page() {
 
     std::thread::sleep(std::time::Duration::from_millis(500));
 
-    let tool = GetMarkdownTool::default();
+    let tool = GetMarkdownTool;
     let mut context = ToolContext::new(&session);
 
     // First execution
@@ -494,7 +494,7 @@ fn test_page_clamping() {
 
     std::thread::sleep(std::time::Duration::from_millis(500));
 
-    let tool = GetMarkdownTool::default();
+    let tool = GetMarkdownTool;
     let mut context = ToolContext::new(&session);
 
     // Request page 999 (way beyond available content)
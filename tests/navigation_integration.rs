@@ -1,3 +1,4 @@
+use browser_use::browser::{LoadStatus, NavigateOptions};
 use browser_use::tools::{
     CloseParams, GoBackParams, GoForwardParams, Tool, ToolContext, close::CloseTool,
     go_back::GoBackTool, go_forward::GoForwardTool,
@@ -30,7 +31,7 @@ fn test_go_back_tool() {
     assert!(current_url.contains("Page 2"));
 
     // Create tool and context
-    let tool = GoBackTool::default();
+    let tool = GoBackTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool to go back
@@ -87,7 +88,7 @@ fn test_go_forward_tool() {
     assert!(current_url.contains("Page 1"));
 
     // Create tool and context
-    let tool = GoForwardTool::default();
+    let tool = GoForwardTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool to go forward
@@ -152,8 +153,8 @@ fn test_navigation_workflow() {
     info!("On page 3");
 
     // Create tools
-    let go_back_tool = GoBackTool::default();
-    let go_forward_tool = GoForwardTool::default();
+    let go_back_tool = GoBackTool;
+    let go_forward_tool = GoForwardTool;
 
     // Go back to page 2
     let mut context = ToolContext::new(&session);
@@ -215,7 +216,7 @@ fn test_close_tool() {
     assert!(!tabs.is_empty(), "Should have at least one tab");
 
     // Create tool and context
-    let tool = CloseTool::default();
+    let tool = CloseTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool to close the browser
@@ -258,7 +259,7 @@ fn test_go_back_on_first_page() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create tool and context
-    let tool = GoBackTool::default();
+    let tool = GoBackTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool - should succeed but do nothing
@@ -291,7 +292,7 @@ fn test_go_forward_on_last_page() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create tool and context
-    let tool = GoForwardTool::default();
+    let tool = GoForwardTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool - should succeed but do nothing
@@ -308,3 +309,280 @@ fn test_go_forward_on_last_page() {
         serde_json::to_string_pretty(&result.data.unwrap()).unwrap()
     );
 }
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_restart_browser() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    session
+        .navigate("https://example.com")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+    session
+        .tab()
+        .unwrap()
+        .evaluate("document.cookie = 'restart=kept; max-age=3600'", false)
+        .expect("Failed to set cookie");
+
+    let session = session.restart(true).expect("Failed to restart browser");
+
+    // Page state is gone, cookies survive
+    let state = session
+        .storage_state()
+        .expect("Failed to read storage state");
+    info!("Cookies after restart: {:?}", state.cookies);
+    assert!(state.cookies.iter().any(|cookie| cookie.name == "restart"));
+    assert_eq!(session.get_tabs().unwrap().len(), 1);
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_navigate_reports_status_and_redirects() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let (response, _) = session
+        .navigate_with_response(
+            "https://httpbin.org/redirect-to?url=/status/404&status_code=302",
+            true,
+            &NavigateOptions::default(),
+        )
+        .expect("Failed to navigate");
+    let response = response.expect("No response recorded");
+    info!("Navigation response: {:?}", response);
+
+    assert_eq!(response.status, 404);
+    assert!(response.is_error());
+    assert_eq!(response.url, "https://httpbin.org/status/404");
+    assert_eq!(response.redirects.len(), 1);
+    assert_eq!(response.redirects[0].status, 302);
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_extra_headers() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    session
+        .set_extra_headers(
+            [("X-Request-Id".to_string(), "crawl-42".to_string())]
+                .into_iter()
+                .collect(),
+        )
+        .expect("Failed to set extra headers");
+
+    let echoed_headers = |session: &BrowserSession| {
+        let text = session
+            .tab()
+            .unwrap()
+            .evaluate("document.body.innerText", false)
+            .unwrap()
+            .value
+            .unwrap();
+        serde_json::from_str::<serde_json::Value>(text.as_str().unwrap()).unwrap()["headers"]
+            .clone()
+    };
+
+    // Session headers plus a header for this navigation only
+    session
+        .navigate_with_response(
+            "https://httpbin.org/headers",
+            true,
+            &NavigateOptions::new().header("Accept-Language", "de-DE"),
+        )
+        .expect("Failed to navigate");
+    let headers = echoed_headers(&session);
+    info!("Echoed headers: {}", headers);
+    assert_eq!(headers["X-Request-Id"].as_str(), Some("crawl-42"));
+    assert_eq!(headers["Accept-Language"].as_str(), Some("de-DE"));
+
+    session
+        .navigate_with_response("https://httpbin.org/headers", true, &NavigateOptions::new())
+        .expect("Failed to navigate");
+    let headers = echoed_headers(&session);
+    assert_eq!(headers["X-Request-Id"].as_str(), Some("crawl-42"));
+    assert_ne!(headers["Accept-Language"].as_str(), Some("de-DE"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_navigate_with_referrer() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    session
+        .navigate_with_response(
+            "https://httpbin.org/headers",
+            true,
+            &NavigateOptions::new().referrer("https://partner.example/offers"),
+        )
+        .expect("Failed to navigate");
+
+    let referrer = session
+        .tab()
+        .unwrap()
+        .evaluate("document.referrer", false)
+        .unwrap()
+        .value
+        .unwrap();
+    info!("document.referrer: {}", referrer);
+    assert_eq!(referrer.as_str(), Some("https://partner.example/"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_navigate_to_fragment() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    let page = "data:text/html,<div style='height:3000px'></div><h2 id='details'>Details</h2>";
+    session.navigate(page).expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+
+    // Same-document navigation: no load happens, so waiting must return at once
+    let started = std::time::Instant::now();
+    let (response, _) = session
+        .navigate_with_response(&format!("{}#details", page), true, &NavigateOptions::new())
+        .expect("Failed to navigate to fragment");
+    assert!(response.is_none());
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+
+    let scroll_y = session
+        .tab()
+        .unwrap()
+        .evaluate("window.scrollY", false)
+        .unwrap()
+        .value
+        .unwrap();
+    info!("scrollY after fragment navigation: {}", scroll_y);
+    assert!(scroll_y.as_f64().unwrap() > 0.0);
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_navigate_partial_load() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    // An image whose server accepts the connection but never answers
+    let server = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = server.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        let _connections: Vec<_> = server.incoming().collect();
+    });
+    let page = format!(
+        "<h1>Ready</h1><img src='http://127.0.0.1:{}/stalled.png'>",
+        port
+    );
+
+    let started = std::time::Instant::now();
+    let (_, load) = session
+        .navigate_with_response(
+            &format!("data:text/html,{}", urlencoding::encode(&page)),
+            true,
+            &NavigateOptions::new().timeout(std::time::Duration::from_millis(1500)),
+        )
+        .expect("A stalled subresource must not fail the navigation");
+    info!("Load status: {:?} after {:?}", load, started.elapsed());
+    assert_eq!(load, Some(LoadStatus::Interactive));
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+
+    let heading = session
+        .tab()
+        .unwrap()
+        .evaluate("document.querySelector('h1').textContent", false)
+        .unwrap()
+        .value
+        .unwrap();
+    assert_eq!(heading.as_str(), Some("Ready"));
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_route_change_detection() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    session
+        .navigate("https://httpbin.org/html")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().unwrap();
+
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorded = std::sync::Arc::clone(&seen);
+    session
+        .on_route_change(move |change| recorded.lock().unwrap().push(change.url.clone()))
+        .expect("Failed to watch route changes");
+
+    // A client-side router changes the URL shortly after a click
+    session
+        .tab()
+        .unwrap()
+        .evaluate(
+            "setTimeout(() => history.pushState({}, '', '/spa/inbox'), 100)",
+            false,
+        )
+        .unwrap();
+    session.wait_for_navigation().expect("Failed to wait");
+
+    let changes = session.route_watcher().unwrap().changes();
+    info!("Route changes: {:?}", changes);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, "pushState");
+    assert_eq!(changes[0].url, "https://httpbin.org/spa/inbox");
+    assert_eq!(seen.lock().unwrap().len(), 1);
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_checkpoint_rollback() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    session
+        .navigate("https://example.com/")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Page did not load");
+    let tab = session.tab().unwrap();
+    tab.evaluate(
+        "document.cookie = 'step=cart'; localStorage.setItem('items', '2')",
+        false,
+    )
+    .unwrap();
+
+    let checkpoint = session.checkpoint().expect("Failed to take checkpoint");
+    info!("Checkpoint: {:?}", checkpoint);
+    assert_eq!(checkpoint.origin.as_deref(), Some("https://example.com"));
+    assert!(
+        checkpoint
+            .cookies
+            .iter()
+            .any(|cookie| cookie.name == "step")
+    );
+
+    tab.evaluate(
+        "document.cookie = 'step=payment'; localStorage.setItem('items', '5')",
+        false,
+    )
+    .unwrap();
+    session
+        .navigate("https://example.org/")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Page did not load");
+
+    session
+        .rollback(&checkpoint.id)
+        .expect("Failed to roll back");
+    let tab = session.tab().unwrap();
+    assert_eq!(tab.get_url(), "https://example.com/");
+    let restored = tab
+        .evaluate(
+            "document.cookie + ';' + localStorage.getItem('items')",
+            false,
+        )
+        .unwrap()
+        .value
+        .unwrap();
+    assert_eq!(restored, "step=cart;2");
+    assert!(session.rollback("cp-99").is_err());
+}
@@ -1,3 +1,4 @@
+use browser_use::error::ErrorCode;
 use browser_use::tools::{
     CloseTabParams, NewTabParams, SwitchTabParams, TabListParams, Tool, ToolContext,
     close_tab::CloseTabTool, new_tab::NewTabTool, switch_tab::SwitchTabTool, tab_list::TabListTool,
@@ -26,7 +27,7 @@ fn test_new_tab() {
     info!("Initial tab count: {}", initial_count);
 
     // Create tool and context
-    let tool = NewTabTool::default();
+    let tool = NewTabTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool to create a new tab
@@ -86,7 +87,7 @@ fn test_tab_list() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create tool and context
-    let tool = TabListTool::default();
+    let tool = TabListTool;
     let mut context = ToolContext::new(&session);
 
     // Execute the tool
@@ -136,7 +137,7 @@ fn test_new_tab_and_switch() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Create a new tab
-    let new_tab_tool = NewTabTool::default();
+    let new_tab_tool = NewTabTool;
     let mut context = ToolContext::new(&session);
 
     let result = new_tab_tool
@@ -153,7 +154,7 @@ fn test_new_tab_and_switch() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // List tabs to verify count increased by 1
-    let tab_list_tool = TabListTool::default();
+    let tab_list_tool = TabListTool;
     let mut context = ToolContext::new(&session);
 
     let result = tab_list_tool
@@ -168,11 +169,17 @@ fn test_new_tab_and_switch() {
     assert!(count >= 2, "Should have at least 2 tabs, got {}", count);
 
     // Switch to first tab (index 0)
-    let switch_tab_tool = SwitchTabTool::default();
+    let switch_tab_tool = SwitchTabTool;
     let mut context = ToolContext::new(&session);
 
     let result = switch_tab_tool
-        .execute_typed(SwitchTabParams { index: 0 }, &mut context)
+        .execute_typed(
+            SwitchTabParams {
+                index: Some(0),
+                id: None,
+            },
+            &mut context,
+        )
         .expect("Failed to execute switch_tab tool");
 
     assert!(result.success, "Switch tab should succeed");
@@ -198,11 +205,17 @@ fn test_switch_tab_invalid_index() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Try to switch to invalid index
-    let switch_tab_tool = SwitchTabTool::default();
+    let switch_tab_tool = SwitchTabTool;
     let mut context = ToolContext::new(&session);
 
     let result = switch_tab_tool
-        .execute_typed(SwitchTabParams { index: 999 }, &mut context)
+        .execute_typed(
+            SwitchTabParams {
+                index: Some(999),
+                id: None,
+            },
+            &mut context,
+        )
         .expect("Failed to execute switch_tab tool");
 
     // Should fail gracefully
@@ -224,7 +237,7 @@ fn test_close_tab() {
 
     std::thread::sleep(std::time::Duration::from_millis(500));
 
-    let new_tab_tool = NewTabTool::default();
+    let new_tab_tool = NewTabTool;
     let mut context = ToolContext::new(&session);
 
     new_tab_tool
@@ -239,7 +252,7 @@ fn test_close_tab() {
     std::thread::sleep(std::time::Duration::from_millis(500));
 
     // Verify we have at least 2 tabs
-    let tab_list_tool = TabListTool::default();
+    let tab_list_tool = TabListTool;
     let mut context = ToolContext::new(&session);
 
     let result = tab_list_tool
@@ -255,11 +268,11 @@ fn test_close_tab() {
     );
 
     // Close the active tab (second tab)
-    let close_tab_tool = CloseTabTool::default();
+    let close_tab_tool = CloseTabTool;
     let mut context = ToolContext::new(&session);
 
     let result = close_tab_tool
-        .execute_typed(CloseTabParams {}, &mut context)
+        .execute_typed(CloseTabParams::default(), &mut context)
         .expect("Failed to execute close_tab tool");
 
     assert!(result.success, "Close tab should succeed");
@@ -300,7 +313,7 @@ fn test_tab_workflow() {
     std::thread::sleep(std::time::Duration::from_millis(300));
 
     // Create second tab
-    let new_tab_tool = NewTabTool::default();
+    let new_tab_tool = NewTabTool;
     let mut context = ToolContext::new(&session);
 
     new_tab_tool
@@ -328,7 +341,7 @@ fn test_tab_workflow() {
     std::thread::sleep(std::time::Duration::from_millis(300));
 
     // List all tabs
-    let tab_list_tool = TabListTool::default();
+    let tab_list_tool = TabListTool;
     let mut context = ToolContext::new(&session);
 
     let result = tab_list_tool
@@ -344,11 +357,17 @@ fn test_tab_workflow() {
     );
 
     // Switch to second tab (index 1)
-    let switch_tab_tool = SwitchTabTool::default();
+    let switch_tab_tool = SwitchTabTool;
     let mut context = ToolContext::new(&session);
 
     let result = switch_tab_tool
-        .execute_typed(SwitchTabParams { index: 1 }, &mut context)
+        .execute_typed(
+            SwitchTabParams {
+                index: Some(1),
+                id: None,
+            },
+            &mut context,
+        )
         .expect("Failed to switch to tab 1");
 
     assert!(result.success);
@@ -357,11 +376,11 @@ fn test_tab_workflow() {
     std::thread::sleep(std::time::Duration::from_millis(300));
 
     // Close the current tab (tab 2, index 1)
-    let close_tab_tool = CloseTabTool::default();
+    let close_tab_tool = CloseTabTool;
     let mut context = ToolContext::new(&session);
 
     let result = close_tab_tool
-        .execute_typed(CloseTabParams {}, &mut context)
+        .execute_typed(CloseTabParams::default(), &mut context)
         .expect("Failed to close tab");
 
     assert!(result.success);
@@ -386,3 +405,193 @@ fn test_tab_workflow() {
         "Should have one less tab after closing"
     );
 }
+
+#[test]
+#[ignore]
+fn test_close_tab_by_index() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    session
+        .navigate("data:text/html,<html><body><h1>Product A</h1></body></html>")
+        .expect("Failed to navigate");
+
+    let mut context = ToolContext::new(&session);
+    NewTabTool
+        .execute_typed(
+            NewTabParams {
+                url: "data:text/html,<html><body><h1>Product B</h1></body></html>".to_string(),
+            },
+            &mut context,
+        )
+        .expect("Failed to open tab");
+
+    std::thread::sleep(std::time::Duration::from_millis(300));
+
+    // Close the first tab while the second one is active
+    let mut context = ToolContext::new(&session);
+    let result = CloseTabTool
+        .execute_typed(
+            CloseTabParams {
+                index: Some(0),
+                id: None,
+            },
+            &mut context,
+        )
+        .expect("Failed to close tab");
+
+    assert!(result.success);
+    let data = result.data.unwrap();
+    info!("Closed: {}", data["message"]);
+    assert_eq!(data["remaining"].as_u64(), Some(1));
+
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    let url = session.tab().expect("No active tab").get_url();
+    assert!(url.contains("Product B"));
+}
+
+#[test]
+#[ignore]
+fn test_tabs_by_id_and_popups() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+
+    session
+        .navigate("data:text/html,<html><body><h1>Opener</h1></body></html>")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Navigation failed");
+    let opener = session
+        .tab()
+        .expect("No active tab")
+        .get_target_id()
+        .clone();
+
+    let tab = session
+        .open_tab("data:text/html,<html><body><h1>Second</h1></body></html>")
+        .expect("Failed to open tab");
+    let tabs = session.list_tabs().expect("Failed to list tabs");
+    assert_eq!(tabs.len(), 2);
+    assert!(
+        tabs.iter()
+            .any(|t| t.active && t.id == *tab.get_target_id())
+    );
+
+    // A window the page opens becomes the active tab
+    session.switch_to_tab(&opener).expect("Failed to switch");
+    session
+        .tab()
+        .unwrap()
+        .evaluate("window.open('data:text/html,<h1>Popup</h1>')", false)
+        .expect("Failed to open popup");
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let active = session.tab().expect("No active tab");
+    assert_ne!(*active.get_target_id(), opener);
+    assert!(active.get_url().contains("Popup"));
+
+    // Closing it hands the active tab on instead of leaving none
+    let popup = active.get_target_id().clone();
+    session.close_tab(&popup).expect("Failed to close popup");
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    let tabs = session.list_tabs().expect("Failed to list tabs");
+    assert!(tabs.iter().all(|t| t.id != popup));
+    assert_eq!(tabs.iter().filter(|t| t.active).count(), 1);
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_crashed_tab() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    session
+        .navigate("data:text/html,<p>Before</p>")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+    let tab = session.tab().unwrap();
+
+    // chrome://crash kills the renderer the way an out-of-memory page does
+    let _ = tab.navigate_to("chrome://crash");
+    std::thread::sleep(std::time::Duration::from_secs(2));
+
+    assert!(session.is_degraded());
+    let error = session
+        .tab()
+        .err()
+        .expect("A crashed tab must not be usable");
+    assert_eq!(error.code(), ErrorCode::PageCrashed);
+
+    // Navigating elsewhere gives the tab a new renderer
+    session
+        .navigate("data:text/html,<p>After</p>")
+        .expect("Failed to navigate crashed tab");
+    session.wait_for_navigation().expect("Failed to wait");
+    assert!(!session.is_degraded());
+    assert!(session.tab().is_ok());
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_new_context() {
+    use browser_use::tools::{NewContextParams, new_context::NewContextTool};
+
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    session
+        .navigate("data:text/html,<h1>Default</h1>")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Failed to wait");
+    let default_tab = session.tab().unwrap();
+    let default_context = default_tab.get_browser_context_id().unwrap();
+
+    let mut context = ToolContext::new(&session);
+    let result = NewContextTool
+        .execute_typed(
+            NewContextParams {
+                url: Some("data:text/html,<h1>Incognito</h1>".to_string()),
+            },
+            &mut context,
+        )
+        .expect("Failed to execute new_context tool");
+    let data = result.data.unwrap();
+    let context_id = data["context_id"].as_str().unwrap().to_string();
+
+    // The new tab is active and lives in its own context
+    let tab = session.tab().unwrap();
+    assert_eq!(data["id"].as_str().unwrap(), tab.get_target_id());
+    assert!(tab.get_url().contains("Incognito"));
+    assert_eq!(
+        tab.get_browser_context_id().unwrap().as_deref(),
+        Some(context_id.as_str())
+    );
+    assert_ne!(default_context.as_deref(), Some(context_id.as_str()));
+
+    session
+        .close_incognito_context(&context_id)
+        .expect("Failed to close context");
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    let tabs = session.list_tabs().expect("Failed to list tabs");
+    assert!(tabs.iter().all(|t| t.id != data["id"].as_str().unwrap()));
+    assert!(session.close_incognito_context(&context_id).is_err());
+}
+
+#[test]
+#[ignore] // Requires Chrome to be installed
+fn test_session_info() {
+    let session = BrowserSession::launch(LaunchOptions::new().headless(true))
+        .expect("Failed to launch browser");
+    session
+        .navigate("data:text/html,<title>Status</title><h1>Status</h1>")
+        .expect("Failed to navigate");
+    session.wait_for_navigation().expect("Page did not load");
+
+    assert!(session.is_alive());
+    let info = session.session_info();
+    info!("Session info: {:?}", info);
+    assert!(info.alive);
+    assert!(info.error.is_none());
+    assert!(info.chrome_version.unwrap().contains("Chrome"));
+    assert!(
+        info.targets
+            .iter()
+            .any(|target| target.kind == "page" && target.title == "Status")
+    );
+}